@@ -0,0 +1,148 @@
+use std::io::{Cursor, Write};
+
+use bytes::Bytes;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use protocol_buf::{
+    buffer::{Buffer, NormalBuffer, PacketBuffer},
+    compression::{CompressionData, CompressionType, Inflater},
+    types::VarInt,
+    FromNetwork, ToNetwork,
+};
+
+/// Writes a packet body made of 256 VarInts of every length.
+fn varint_packet() -> Vec<u8> {
+    (0..256)
+        .flat_map(|i| VarInt::from(i * 8_388_607).to_network())
+        .collect()
+}
+
+/// Writes a packet body made of 32 strings, like a tab list or chat packet.
+fn string_packet() -> Vec<u8> {
+    (0..32)
+        .flat_map(|i| format!("minecraft:some_long_identifier_{}", i).to_network())
+        .collect()
+}
+
+fn read_varints<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> i32 {
+    (0..256).fold(0, |sum, _| sum ^ *VarInt::from_network(buffer).unwrap())
+}
+
+fn read_strings<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> usize {
+    (0..32).fold(0, |sum, _| {
+        sum + String::from_network(buffer).unwrap().len()
+    })
+}
+
+/// Compares dispatching a received packet to a handler with a `Vec<u8>` buffer, which copied the data for every handler,
+/// with a `[NormalBuffer]` that shares the received frame.
+fn dispatch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dispatch");
+
+    for (name, packet) in [("varint", varint_packet()), ("string", string_packet())] {
+        let before = Cursor::new(packet.clone());
+        group.bench_with_input(BenchmarkId::new("vec", name), &before, |b, buffer| {
+            b.iter(|| {
+                let mut buffer = buffer.clone();
+                match name {
+                    "varint" => read_varints(&mut buffer) as usize,
+                    _ => read_strings(&mut buffer),
+                }
+            })
+        });
+
+        let after = NormalBuffer::from_bytes(Bytes::from(packet));
+        group.bench_with_input(BenchmarkId::new("bytes", name), &after, |b, buffer| {
+            b.iter(|| {
+                let mut buffer = buffer.clone();
+                match name {
+                    "varint" => read_varints(&mut buffer.buffer) as usize,
+                    _ => read_strings(&mut buffer.buffer),
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Compares cloning a written packet, which happens for every client a packet is broadcasted to.
+/// Broadcasted packets are frozen, so the clone only clones a reference.
+fn broadcast(c: &mut Criterion) {
+    let mut group = c.benchmark_group("broadcast");
+    let packet = string_packet().repeat(64);
+
+    group.bench_function("vec", |b| b.iter(|| black_box(packet.clone())));
+
+    let mut buffer = NormalBuffer::new(packet.clone());
+    buffer.freeze();
+    group.bench_function("bytes", |b| b.iter(|| black_box(buffer.clone())));
+
+    group.finish();
+}
+
+/// Compares writing a packet to a `Vec<u8>` cursor with writing it to a `[NormalBuffer]`, which owns its data while writing.
+fn write(c: &mut Criterion) {
+    let mut group = c.benchmark_group("write");
+
+    group.bench_function("vec/varint", |b| {
+        b.iter(|| {
+            let mut buffer = Cursor::new(Vec::new());
+            for i in 0..256 {
+                buffer
+                    .write_all(&VarInt::from(i * 8_388_607).to_network())
+                    .unwrap();
+            }
+            buffer
+        })
+    });
+    group.bench_function("varint", |b| {
+        b.iter(|| {
+            let mut buffer = NormalBuffer::new(Vec::new());
+            for i in 0..256 {
+                buffer.write_varint(VarInt::from(i * 8_388_607));
+            }
+            buffer
+        })
+    });
+    group.bench_function("string", |b| {
+        b.iter(|| {
+            let mut buffer = NormalBuffer::new(Vec::new());
+            for i in 0..32 {
+                buffer.write_string(format!("minecraft:some_long_identifier_{}", i));
+            }
+            buffer
+        })
+    });
+
+    group.finish();
+}
+
+/// Compares decompressing a chunk-sized packet into a new buffer with decompressing it using an `[Inflater]`,
+/// which reuses its output buffer once the previous packet was dropped.
+fn inflate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("inflate");
+    let compression = CompressionData::new(256, CompressionType::Zlib);
+    let packet = PacketBuffer::with_id(
+        VarInt::from(0x27),
+        NormalBuffer::new(string_packet().repeat(256)),
+    );
+    let frame = Bytes::from(compression.to_buffer(packet, &compression).unwrap());
+
+    group.bench_function("new", |b| {
+        b.iter(|| {
+            compression
+                .grab_from_buffer(frame.clone(), &compression)
+                .unwrap()
+        })
+    });
+
+    let mut inflater = Inflater::new();
+    group.bench_function("reused", |b| {
+        b.iter(|| inflater.read_packet(frame.clone(), &compression).unwrap())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, dispatch, broadcast, write, inflate);
+criterion_main!(benches);
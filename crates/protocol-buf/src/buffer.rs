@@ -1,58 +1,49 @@
-use std::io::{Cursor, Write};
+use std::{
+    io::{Cursor, Write},
+    mem,
+    ops::Deref,
+};
 
-use thiserror::Error;
+use bytes::Bytes;
 
 use crate::{
     compression::CompressionData,
+    error::ProtocolError,
     register_buffer,
-    types::{VarInt, VarLong},
+    types::{self, VarInt, VarLong},
     FromNetwork, ToNetwork,
 };
 
+/// The largest frame the protocol allows, this is `2^21 - 1` bytes since the length prefix is at most 3 bytes long.
+pub const MAX_PACKET_SIZE: usize = 2097151;
+
 /// Errors that can occur when reading or writing to a buffer.
 ///
-/// This enum is used as the error type for the `Buffer` trait.
+/// This is the crate-wide `[ProtocolError]`, the name is kept for the `Buffer` trait.
 ///
 /// # Examples
 /// ```rust
-/// use buffer::BufferError;
+/// use protocol_buf::buffer::BufferError;
 ///
-/// fn main() -> Result<(), BufferError> {
+/// fn read() -> Result<(), BufferError> {
 ///    Err(BufferError::InsufficientData)
 /// }
-/// ```
-///
-/// # Variants
-///
-/// - `VarIntOverflow` - The VarInt is too large to be read.
-/// - `InsufficientData` - There is not enough data in the buffer to read.
-/// - `Utf8Error` - The data in the buffer is not valid UTF-8.
-/// - `BadPacketId` - The packet ID is not valid.
-/// - `BadPacketLength` - The packet length is not valid.
 ///
-#[derive(Debug, Error)]
-pub enum BufferError {
-    #[error("VarInt overflow")]
-    VarIntOverflow,
-    #[error("Not enough data in buffer")]
-    InsufficientData,
-    #[error("Invalid UTF-8 sequence")]
-    Utf8Error,
-    #[error("Invalid packet ID")]
-    BadPacketId,
-    #[error("Invalid packet length")]
-    BadPacketLength,
-}
+/// assert!(read().is_err());
+/// ```
+pub type BufferError = ProtocolError;
 
 /// A type alias for a `Result` that uses `BufferError` as the error type.
 ///
 /// # Examples
 /// ```rust
-/// use buffer::BufferResult;
+/// use protocol_buf::buffer::{BufferError, BufferResult};
 ///
-/// fn main() -> BufferResult<()> {
+/// fn read() -> BufferResult<()> {
 ///    Err(BufferError::InsufficientData)
 /// }
+///
+/// assert!(read().is_err());
 /// ```
 ///
 pub type BufferResult<T> = Result<T, BufferError>;
@@ -72,6 +63,102 @@ register_buffer! {
     VarLong => (read_varlong, write_varlong)
 }
 
+/// The data of a `[NormalBuffer]`.
+///
+/// Buffers that are being written own their data, so writing is as cheap as pushing to a `Vec<u8>`.
+/// Received packets share the frame they were split off from, cloning them only clones a reference.
+/// Written packets can be moved into shared data with `[NormalBuffer::freeze]` before they are cloned, like for a broadcast.
+///
+/// # Examples
+/// ```rust
+/// use bytes::Bytes;
+/// use protocol_buf::buffer::BufferData;
+///
+/// let mut data = BufferData::Shared(Bytes::from_static(&[0x01, 0x02]));
+/// data.to_mut().push(0x03);
+///
+/// assert!(matches!(data, BufferData::Owned(_)));
+/// assert_eq!(data, [0x01, 0x02, 0x03]);
+/// ```
+///
+/// # Variants
+/// - `Owned` - The buffer owns its data, cloning copies it.
+/// - `Shared` - The data is shared, cloning is cheap and writing copies it first.
+#[derive(Debug, Clone)]
+pub enum BufferData {
+    Owned(Vec<u8>),
+    Shared(Bytes),
+}
+
+impl BufferData {
+    /// Returns the data as a mutable `Vec<u8>`, shared data is copied unless this is its only reference.
+    pub fn to_mut(&mut self) -> &mut Vec<u8> {
+        if let Self::Shared(bytes) = self {
+            *self = Self::Owned(Vec::from(mem::take(bytes)));
+        }
+
+        match self {
+            Self::Owned(vec) => vec,
+            Self::Shared(_) => unreachable!(),
+        }
+    }
+
+    /// Moves the data into shared data and returns a reference to it.
+    pub fn freeze(&mut self) -> Bytes {
+        if let Self::Owned(vec) = self {
+            *self = Self::Shared(Bytes::from(mem::take(vec)));
+        }
+
+        match self {
+            Self::Shared(bytes) => bytes.clone(),
+            Self::Owned(_) => unreachable!(),
+        }
+    }
+}
+
+impl Default for BufferData {
+    fn default() -> Self {
+        Self::Owned(Vec::new())
+    }
+}
+
+impl Deref for BufferData {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Owned(vec) => vec,
+            Self::Shared(bytes) => bytes,
+        }
+    }
+}
+
+impl AsRef<[u8]> for BufferData {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl PartialEq for BufferData {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for BufferData {}
+
+impl<const N: usize> PartialEq<[u8; N]> for BufferData {
+    fn eq(&self, other: &[u8; N]) -> bool {
+        **self == *other
+    }
+}
+
+impl PartialEq<Vec<u8>> for BufferData {
+    fn eq(&self, other: &Vec<u8>) -> bool {
+        **self == **other
+    }
+}
+
 /// Represents a buffer that can be read from and written to.
 ///
 /// This buffer has no data for packets. This can be used for a less complex buffer.
@@ -79,17 +166,17 @@ register_buffer! {
 ///
 /// # Examples
 /// ```rust
-/// use buffer::NormalBuffer;
+/// use protocol_buf::buffer::NormalBuffer;
 ///
 /// let buffer = NormalBuffer::new(vec![0x01, 0x02, 0x03]);
 /// ```
 ///
 /// # Fields
 ///
-/// - `buffer` - The buffer that contains the data.
+/// - `buffer` - The buffer that contains the data, see `[BufferData]`.
 #[derive(Debug, Clone)]
 pub struct NormalBuffer {
-    pub buffer: Cursor<Vec<u8>>,
+    pub buffer: Cursor<BufferData>,
 }
 
 impl Buffer for NormalBuffer {
@@ -99,12 +186,17 @@ impl Buffer for NormalBuffer {
     ///
     /// # Examples
     /// ```rust
-    /// use buffer::NormalBuffer;
+    /// use protocol_buf::buffer::{Buffer, NormalBuffer};
     ///
     /// let mut buffer = NormalBuffer::new(vec![0x01, 0x02, 0x03]);
-    /// let value: u8 = buffer.read();
+    /// let value: u8 = buffer.read().unwrap();
+    ///
+    /// assert_eq!(value, 0x01);
     /// ```
-    fn read<T: FromNetwork>(&mut self) -> T {
+    ///
+    /// # Returns
+    /// The value, an error if the data is malformed or there is not enough data left.
+    fn read<T: FromNetwork>(&mut self) -> BufferResult<T> {
         T::from_network(&mut self.buffer)
     }
 
@@ -114,20 +206,28 @@ impl Buffer for NormalBuffer {
     ///
     /// # Examples
     /// ```rust
-    /// use buffer::NormalBuffer;
+    /// use protocol_buf::buffer::{Buffer, NormalBuffer};
     ///
     /// let mut buffer = NormalBuffer::new(Vec::new());
-    /// buffer.write(0x01);
+    /// buffer.write(0x01_u8);
+    ///
+    /// assert_eq!(buffer.get_ref(), &[0x01]);
     /// ```
     fn write<T: ToNetwork>(&mut self, buf: T) {
-        self.buffer.write_all(&buf.to_network()).unwrap();
+        let position = self.buffer.position();
+        let mut data = Cursor::new(self.buffer.get_mut().to_mut());
+        data.set_position(position);
+        data.write_all(&buf.to_network()).unwrap();
+
+        let position = data.position();
+        self.buffer.set_position(position);
     }
 
     /// Returns a reference to the buffer.
     ///
     /// # Examples
     /// ```rust
-    /// use buffer::NormalBuffer;
+    /// use protocol_buf::buffer::{Buffer, NormalBuffer};
     ///
     /// let buffer = NormalBuffer::new(vec![0x01, 0x02, 0x03]);
     ///
@@ -136,7 +236,7 @@ impl Buffer for NormalBuffer {
     ///
     /// # Returns
     /// A reference to the buffer.
-    fn get_ref(&self) -> &Vec<u8> {
+    fn get_ref(&self) -> &[u8] {
         self.buffer.get_ref()
     }
 
@@ -144,7 +244,7 @@ impl Buffer for NormalBuffer {
     ///
     /// # Examples
     /// ```rust
-    /// use buffer::NormalBuffer;
+    /// use protocol_buf::buffer::{Buffer, NormalBuffer};
     ///
     /// let mut buffer = NormalBuffer::new(vec![0x01, 0x02, 0x03]);
     /// buffer.get_mut().push(0x04);
@@ -153,9 +253,48 @@ impl Buffer for NormalBuffer {
     /// ```
     ///
     /// # Returns
-    /// A mutable reference to the buffer.
+    /// A mutable reference to the buffer, shared data is copied first.
     fn get_mut(&mut self) -> &mut Vec<u8> {
-        self.buffer.get_mut()
+        self.buffer.get_mut().to_mut()
+    }
+
+    /// Returns the amount of bytes that weren't read yet.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_buf::buffer::{Buffer, NormalBuffer};
+    ///
+    /// let mut buffer = NormalBuffer::new(vec![0x01, 0x02, 0x03]);
+    /// buffer.read_byte().unwrap();
+    ///
+    /// assert_eq!(buffer.remaining(), 2);
+    /// ```
+    fn remaining(&self) -> usize {
+        types::remaining(&self.buffer)
+    }
+
+    /// Reads the given amount of bytes.
+    ///
+    /// Like every other read, nothing is read if there are not enough bytes left,
+    /// so a failed read can't move the buffer past the data of the packet.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_buf::buffer::{Buffer, BufferError, NormalBuffer};
+    ///
+    /// let mut buffer = NormalBuffer::new(vec![0x01, 0x02, 0x03]);
+    ///
+    /// assert_eq!(buffer.read_bytes(2).unwrap(), vec![0x01, 0x02]);
+    /// assert!(matches!(buffer.read_bytes(2), Err(BufferError::InsufficientData)));
+    /// assert!(matches!(buffer.read_int(), Err(BufferError::InsufficientData)));
+    /// assert_eq!(buffer.remaining(), 1);
+    /// assert_eq!(buffer.read_byte().unwrap(), 0x03);
+    /// ```
+    ///
+    /// # Returns
+    /// The bytes, an error if there are not enough bytes left.
+    fn read_bytes(&mut self, length: usize) -> BufferResult<Vec<u8>> {
+        types::read_bytes(&mut self.buffer, length)
     }
 }
 
@@ -164,7 +303,7 @@ impl NormalBuffer {
     ///
     /// # Examples
     /// ```rust
-    /// use buffer::NormalBuffer;
+    /// use protocol_buf::buffer::NormalBuffer;
     ///
     /// let buffer = NormalBuffer::new(vec![0x01, 0x02, 0x03]);
     ///
@@ -172,9 +311,98 @@ impl NormalBuffer {
     /// ```
     pub const fn new(buffer: Vec<u8>) -> Self {
         Self {
-            buffer: Cursor::new(buffer),
+            buffer: Cursor::new(BufferData::Owned(buffer)),
+        }
+    }
+
+    /// Creates a new `NormalBuffer` that reads the given bytes, without copying them.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use bytes::Bytes;
+    /// use protocol_buf::buffer::{Buffer, NormalBuffer};
+    ///
+    /// let frame = Bytes::from(vec![0x02, 0x00, 0x01]);
+    /// let mut buffer = NormalBuffer::from_bytes(frame.slice(1..));
+    ///
+    /// assert_eq!(buffer.read_byte().unwrap(), 0x00);
+    /// assert_eq!(buffer.get_ref().as_ptr(), frame[1..].as_ptr());
+    /// ```
+    pub const fn from_bytes(buffer: Bytes) -> Self {
+        Self {
+            buffer: Cursor::new(BufferData::Shared(buffer)),
         }
     }
+
+    /// Moves the data into shared data, so following clones of the buffer don't copy it.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_buf::buffer::{Buffer, NormalBuffer};
+    ///
+    /// let mut buffer = NormalBuffer::new(vec![0x01, 0x02, 0x03]);
+    /// let bytes = buffer.freeze();
+    /// let clone = buffer.clone();
+    ///
+    /// assert_eq!(clone.get_ref().as_ptr(), bytes.as_ptr());
+    /// ```
+    ///
+    /// # Returns
+    /// A reference to the data.
+    pub fn freeze(&mut self) -> Bytes {
+        self.buffer.get_mut().freeze()
+    }
+
+    /// Returns the position of the next byte that is read.
+    pub fn position(&self) -> u64 {
+        self.buffer.position()
+    }
+
+    /// Reads a field of a packet. If the field can't be read, the error contains the packet, the field and the offset.
+    ///
+    /// This is used by the packet macros, so errors of malformed packets can be tracked down from the logs.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_buf::{buffer::NormalBuffer, error::ProtocolError};
+    ///
+    /// let mut buffer = NormalBuffer::new(vec![0x00, 0x01]);
+    /// let error = buffer
+    ///     .read_field::<i64>("KeepAlivePacket", "serverbound", "Play", 0x18, "keep_alive_id")
+    ///     .unwrap_err();
+    ///
+    /// assert!(matches!(error, ProtocolError::Decode { offset: 0, .. }));
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "Failed to read `keep_alive_id` of serverbound Play packet KeepAlivePacket (0x18) at offset 0: Not enough data in buffer"
+    /// );
+    /// ```
+    ///
+    /// # Parameters
+    /// - `packet` - The name of the packet.
+    /// - `direction` - The direction of the packet, `serverbound` or `clientbound`.
+    /// - `state` - The name of the state the packet is sent in.
+    /// - `packet_id` - The ID of the packet.
+    /// - `field` - The name of the field.
+    pub fn read_field<T: FromNetwork>(
+        &mut self,
+        packet: &'static str,
+        direction: &'static str,
+        state: &'static str,
+        packet_id: i32,
+        field: &'static str,
+    ) -> BufferResult<T> {
+        let offset = self.position();
+        self.read().map_err(|source| ProtocolError::Decode {
+            packet,
+            direction,
+            state,
+            packet_id,
+            field,
+            offset,
+            source: Box::new(source),
+        })
+    }
 }
 
 impl From<Vec<u8>> for NormalBuffer {
@@ -182,7 +410,7 @@ impl From<Vec<u8>> for NormalBuffer {
     ///
     /// # Examples
     /// ```rust
-    /// use buffer::NormalBuffer;
+    /// use protocol_buf::buffer::NormalBuffer;
     ///
     /// let buffer = NormalBuffer::from(vec![0x01, 0x02, 0x03]);
     ///
@@ -199,9 +427,9 @@ impl From<Vec<u8>> for NormalBuffer {
 ///
 /// # Examples
 /// ```rust
-/// use buffer::PacketBuffer;
+/// use protocol_buf::{buffer::PacketBuffer, compression::CompressionData};
 ///
-/// let buffer = PacketBuffer::new(CompressionData::new(256, CompressionType::Zlib));
+/// let buffer = PacketBuffer::new(vec![0x02, 0x00, 0x01], &CompressionData::default());
 /// ```
 ///
 /// # Fields
@@ -227,14 +455,14 @@ impl Buffer for PacketBuffer {
     ///
     /// # Examples
     /// ```rust
-    /// use buffer::PacketBuffer;
+    /// use protocol_buf::{buffer::{Buffer, PacketBuffer}, compression::CompressionData};
     ///
-    /// let mut buffer = PacketBuffer::new(CompressionData::new(256, CompressionType::Zlib));
-    /// let value: u8 = buffer.read();
+    /// let mut buffer = PacketBuffer::new(vec![0x02, 0x00, 0x01], &CompressionData::default()).unwrap();
+    /// let value: u8 = buffer.read().unwrap();
     ///
     /// assert_eq!(value, 0x01);
     /// ```
-    fn read<T: FromNetwork>(&mut self) -> T {
+    fn read<T: FromNetwork>(&mut self) -> BufferResult<T> {
         self.buffer.read()
     }
 
@@ -245,11 +473,11 @@ impl Buffer for PacketBuffer {
     ///
     /// # Examples
     /// ```rust
-    /// use buffer::PacketBuffer;
+    /// use protocol_buf::{buffer::{Buffer, PacketBuffer}, compression::CompressionData};
     ///
-    /// let mut buffer = PacketBuffer::new(CompressionData::new(256, CompressionType::Zlib));
+    /// let mut buffer = PacketBuffer::new(vec![0x01, 0x00], &CompressionData::default()).unwrap();
     ///
-    /// buffer.write(0x01);
+    /// buffer.write(0x01_u8);
     /// ```
     fn write<T: ToNetwork>(&mut self, buf: T) {
         self.buffer.write(buf);
@@ -259,16 +487,16 @@ impl Buffer for PacketBuffer {
     ///
     /// # Examples
     /// ```rust
-    /// use buffer::PacketBuffer;
+    /// use protocol_buf::{buffer::{Buffer, PacketBuffer}, compression::CompressionData};
     ///
-    /// let buffer = PacketBuffer::new(CompressionData::new(256, CompressionType::Zlib), vec![0x01, 0x02, 0x03]);
+    /// let buffer = PacketBuffer::new(vec![0x02, 0x00, 0x01], &CompressionData::default()).unwrap();
     ///
-    /// assert_eq!(buffer.get_ref(), &[0x01, 0x02, 0x03]);
+    /// assert_eq!(buffer.get_ref(), &[0x02, 0x00, 0x01]);
     /// ```
     ///
     /// # Returns
     /// A reference to the buffer.
-    fn get_ref(&self) -> &Vec<u8> {
+    fn get_ref(&self) -> &[u8] {
         self.buffer.get_ref()
     }
 
@@ -276,12 +504,12 @@ impl Buffer for PacketBuffer {
     ///
     /// # Examples
     /// ```rust
-    /// use buffer::PacketBuffer;
+    /// use protocol_buf::{buffer::{Buffer, PacketBuffer}, compression::CompressionData};
     ///
-    /// let mut buffer = PacketBuffer::new(CompressionData::new(256, CompressionType::Zlib), vec![0x01, 0x02, 0x03]);
+    /// let mut buffer = PacketBuffer::new(vec![0x02, 0x00, 0x01], &CompressionData::default()).unwrap();
     /// buffer.get_mut().push(0x04);
     ///
-    /// assert_eq!(buffer.get_mut(), &mut vec![0x01, 0x02, 0x03]);
+    /// assert_eq!(buffer.get_mut(), &mut vec![0x02, 0x00, 0x01, 0x04]);
     /// ```
     ///
     /// # Returns
@@ -289,6 +517,16 @@ impl Buffer for PacketBuffer {
     fn get_mut(&mut self) -> &mut Vec<u8> {
         self.buffer.get_mut()
     }
+
+    /// Returns the amount of bytes of the packet that weren't read yet.
+    fn remaining(&self) -> usize {
+        self.buffer.remaining()
+    }
+
+    /// Reads the given amount of bytes, nothing is read if there are not enough bytes left.
+    fn read_bytes(&mut self, length: usize) -> BufferResult<Vec<u8>> {
+        self.buffer.read_bytes(length)
+    }
 }
 
 impl PacketBuffer {
@@ -298,20 +536,126 @@ impl PacketBuffer {
     ///
     /// # Examples
     /// ```rust
-    /// use buffer::PacketBuffer;
+    /// use protocol_buf::{buffer::{Buffer, PacketBuffer}, compression::CompressionData};
     ///
-    /// let buffer = PacketBuffer::new(CompressionData::new(256, CompressionType::Zlib), NormalBuffer::new(vec![0x01, 0x02, 0x03]));
+    /// let buffer = PacketBuffer::new(vec![0x02, 0x00, 0x01], &CompressionData::default()).unwrap();
     ///
-    /// assert_eq!(buffer.get_ref(), &[0x01, 0x02, 0x03]);
+    /// assert_eq!(*buffer.packet_id, 0x00);
     /// ```
     ///
     /// # Returns
     /// A new `PacketBuffer`. If the buffer had an error, it will return `None`.
     pub fn new(buffer: Vec<u8>, compression: &CompressionData) -> Option<Self> {
-        if let Ok(data) = compression.grab_from_buffer(buffer, compression) {
-            Some(data)
+        Self::from_bytes(Bytes::from(buffer), compression)
+    }
+
+    /// Creates a new `PacketBuffer` from a received frame, without copying it unless it has to be decompressed.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use bytes::Bytes;
+    /// use protocol_buf::{buffer::{Buffer, PacketBuffer}, compression::CompressionData};
+    ///
+    /// let mut buffer = PacketBuffer::from_bytes(Bytes::from_static(&[0x02, 0x00, 0x01]), &CompressionData::default()).unwrap();
+    ///
+    /// assert_eq!(*buffer.packet_id, 0x00);
+    /// assert_eq!(buffer.read_byte().unwrap(), 0x01);
+    /// ```
+    ///
+    /// # Returns
+    /// A new `PacketBuffer`. If the frame is malformed, it will return `None`.
+    pub fn from_bytes(buffer: Bytes, compression: &CompressionData) -> Option<Self> {
+        compression.grab_from_buffer(buffer, compression).ok()
+    }
+
+    /// Creates a new outgoing `PacketBuffer` from the packet ID and the already written packet data.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_buf::{buffer::{NormalBuffer, PacketBuffer}, types::VarInt};
+    ///
+    /// let buffer = PacketBuffer::with_id(VarInt::from(0x01), NormalBuffer::new(vec![0x01, 0x02]));
+    ///
+    /// assert_eq!(*buffer.packet_length, 3);
+    /// ```
+    ///
+    /// # Returns
+    /// A new `PacketBuffer` where the buffer only contains the packet data.
+    pub fn with_id(packet_id: VarInt, buffer: NormalBuffer) -> Self {
+        Self {
+            packet_length: VarInt::from((packet_id.len() + buffer.get_ref().len()) as i32),
+            data_length: VarInt::from(0),
+            packet_id,
+            buffer,
+        }
+    }
+
+    /// Returns the size of the first complete frame inside the given bytes.
+    ///
+    /// A frame is the `Length` VarInt followed by that many bytes. The length is at most 3 bytes long,
+    /// since the protocol does not allow packets larger than `2^21 - 1` bytes.
+    /// The length doesn't have to be minimal, proxies like Velocity pad it to 3 bytes so it can be written afterwards.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_buf::buffer::PacketBuffer;
+    ///
+    /// assert_eq!(PacketBuffer::frame_size(&[0x02, 0x00, 0x01, 0x05]).unwrap(), Some(3));
+    /// assert_eq!(PacketBuffer::frame_size(&[0x02, 0x00]).unwrap(), None);
+    /// assert_eq!(PacketBuffer::frame_size(&[0x82, 0x80, 0x00, 0x00, 0x01]).unwrap(), Some(5));
+    /// assert!(PacketBuffer::frame_size(&[0x80, 0x80, 0x80, 0x01]).is_err());
+    /// ```
+    ///
+    /// # Returns
+    /// The size of the frame including its length prefix, `None` if more data is needed to complete the frame.
+    /// If the length prefix is too large or longer than 3 bytes, `[BufferError::BadPacketLength]` is returned.
+    pub fn frame_size(bytes: &[u8]) -> BufferResult<Option<usize>> {
+        Self::frame_size_limited(bytes, MAX_PACKET_SIZE)
+    }
+
+    /// Returns the size of the first complete frame inside the given bytes, like `[PacketBuffer::frame_size]`,
+    /// but rejects frames that are larger than the given size.
+    ///
+    /// The length is checked as soon as the length prefix was received, so the rest of the frame never has to be buffered.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_buf::buffer::{BufferError, PacketBuffer};
+    ///
+    /// assert_eq!(PacketBuffer::frame_size_limited(&[0x02, 0x00, 0x01], 2).unwrap(), Some(3));
+    /// assert!(matches!(
+    ///     PacketBuffer::frame_size_limited(&[0x80, 0x01], 64),
+    ///     Err(BufferError::BadPacketLength)
+    /// ));
+    /// ```
+    ///
+    /// # Parameters
+    /// - `bytes` - The received bytes, starting at a frame.
+    /// - `max_size` - The largest allowed length of the frame, without its length prefix. It can't exceed `[MAX_PACKET_SIZE]`.
+    ///
+    /// # Returns
+    /// The size of the frame including its length prefix, `None` if more data is needed to complete the frame.
+    /// If the length prefix is too large or longer than 3 bytes, `[BufferError::BadPacketLength]` is returned.
+    pub fn frame_size_limited(bytes: &[u8], max_size: usize) -> BufferResult<Option<usize>> {
+        let mut length = 0;
+
+        for (index, byte) in bytes.iter().take(3).enumerate() {
+            length |= ((byte & 0b01111111) as usize) << (7 * index);
+
+            if byte & 0b10000000 == 0 {
+                if length > max_size {
+                    return Err(BufferError::BadPacketLength);
+                }
+
+                let size = index + 1 + length;
+                return Ok((bytes.len() >= size).then_some(size));
+            }
+        }
+
+        if bytes.len() >= 3 {
+            Err(BufferError::BadPacketLength)
         } else {
-            None
+            Ok(None)
         }
     }
 }
@@ -0,0 +1,198 @@
+use aes::Aes128;
+use bytes::Bytes;
+use cfb8::{
+    cipher::{generic_array::GenericArray, BlockDecryptMut, BlockEncryptMut, KeyIvInit},
+    Decryptor, Encryptor,
+};
+
+use crate::{
+    buffer::{BufferResult, NormalBuffer, PacketBuffer},
+    compression::CompressionData,
+    types::VarInt,
+};
+
+/// The longest `Length` prefix of a frame, the protocol doesn't allow packets larger than `2^21 - 1` bytes.
+const MAX_LENGTH_PREFIX: usize = 3;
+
+/// Encrypts and decrypts the frames of a connection with AES-128 in CFB8 mode, the way Minecraft encrypts connections.
+///
+/// The shared secret of the encryption handshake is used as the key and the IV. Both directions of a connection
+/// keep their own state, so every byte has to be encrypted or decrypted exactly once, in the order it is sent.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::codec::FrameCipher;
+///
+/// let mut client = FrameCipher::new(&[7; 16]);
+/// let mut server = FrameCipher::new(&[7; 16]);
+///
+/// let mut bytes = b"Hello".to_vec();
+/// client.encrypt(&mut bytes);
+/// assert_ne!(bytes, b"Hello");
+///
+/// server.decrypt(&mut bytes);
+/// assert_eq!(bytes, b"Hello");
+/// ```
+///
+/// # Fields
+/// - `encryptor` - The state of the bytes that are sent.
+/// - `decryptor` - The state of the bytes that are received.
+#[derive(Debug, Clone)]
+pub struct FrameCipher {
+    encryptor: Encryptor<Aes128>,
+    decryptor: Decryptor<Aes128>,
+}
+
+impl FrameCipher {
+    /// Creates a new `[FrameCipher]` from the shared secret of the encryption handshake.
+    pub fn new(shared_secret: &[u8; 16]) -> Self {
+        let key = GenericArray::from_slice(shared_secret);
+        Self {
+            encryptor: Encryptor::new(key, key),
+            decryptor: Decryptor::new(key, key),
+        }
+    }
+
+    /// Encrypts bytes that are sent, in place.
+    pub fn encrypt(&mut self, bytes: &mut [u8]) {
+        for byte in bytes {
+            self.encryptor
+                .encrypt_block_mut(GenericArray::from_mut_slice(std::slice::from_mut(byte)));
+        }
+    }
+
+    /// Decrypts bytes that were received, in place.
+    pub fn decrypt(&mut self, bytes: &mut [u8]) {
+        decrypt(&mut self.decryptor, bytes);
+    }
+}
+
+/// Writes a complete frame, exactly the way it is sent over a connection.
+///
+/// This doesn't need a connection, so tests, proxies and packet builders can create frames on their own.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::{codec::encode_frame, compression::CompressionData, types::VarInt};
+///
+/// let frame = encode_frame(VarInt::from(0x01), &[0x05], &CompressionData::default(), None).unwrap();
+///
+/// assert_eq!(frame, vec![0x02, 0x01, 0x05]);
+/// ```
+///
+/// # Parameters
+/// - `packet_id` - The ID of the packet.
+/// - `body` - The data of the packet, without its ID.
+/// - `compression` - The compression of the connection, packets reaching the threshold are compressed.
+/// - `cipher` - The cipher of the connection, `None` if the connection isn't encrypted.
+///
+/// # Returns
+/// The bytes of the frame, including its `Length` prefix.
+pub fn encode_frame(
+    packet_id: VarInt,
+    body: &[u8],
+    compression: &CompressionData,
+    cipher: Option<&mut FrameCipher>,
+) -> BufferResult<Vec<u8>> {
+    let packet = PacketBuffer::with_id(packet_id, NormalBuffer::new(body.to_vec()));
+    let mut frame = compression.to_buffer(packet, compression)?;
+    if let Some(cipher) = cipher {
+        cipher.encrypt(&mut frame);
+    }
+
+    Ok(frame)
+}
+
+/// Reads the first frame of the given bytes, the same way a connection reads it.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::{
+///     buffer::Buffer,
+///     codec::{decode_frame, encode_frame, FrameCipher},
+///     compression::{CompressionData, CompressionType},
+///     types::VarInt,
+/// };
+///
+/// let compression = CompressionData::new(4, CompressionType::Zlib);
+/// let mut client = FrameCipher::new(&[7; 16]);
+/// let mut server = FrameCipher::new(&[7; 16]);
+///
+/// let mut bytes = encode_frame(VarInt::from(0x01), &[7; 64], &compression, Some(&mut client)).unwrap();
+/// bytes.extend(encode_frame(VarInt::from(0x02), &[], &compression, Some(&mut client)).unwrap());
+///
+/// // Incomplete frames are not read.
+/// assert!(decode_frame(&bytes[..4], &compression, Some(&mut server)).unwrap().is_none());
+///
+/// let (mut packet, size) = decode_frame(&bytes, &compression, Some(&mut server)).unwrap().unwrap();
+/// assert_eq!(*packet.packet_id, 0x01);
+/// assert_eq!(packet.read_byte().unwrap(), 7);
+///
+/// let (packet, _) = decode_frame(&bytes[size..], &compression, Some(&mut server)).unwrap().unwrap();
+/// assert_eq!(*packet.packet_id, 0x02);
+/// ```
+///
+/// # Parameters
+/// - `bytes` - The bytes that were received, starting at a frame.
+/// - `compression` - The compression of the connection.
+/// - `cipher` - The cipher of the connection, `None` if the connection isn't encrypted.
+///   The cipher only moves forward if a complete frame was read.
+///
+/// # Returns
+/// The packet and the size of its frame, `None` if more bytes are needed to complete the frame.
+/// An error if the frame is malformed.
+pub fn decode_frame(
+    bytes: &[u8],
+    compression: &CompressionData,
+    cipher: Option<&mut FrameCipher>,
+) -> BufferResult<Option<(PacketBuffer, usize)>> {
+    let Some(cipher) = cipher else {
+        let Some(size) = PacketBuffer::frame_size(bytes)? else {
+            return Ok(None);
+        };
+        let frame = Bytes::copy_from_slice(&bytes[..size]);
+        return Ok(Some((
+            compression.grab_from_buffer(frame, compression)?,
+            size,
+        )));
+    };
+
+    // The end of the frame is only known once its length was decrypted, so the cipher only moves forward
+    // once the whole frame was received.
+    let mut decryptor = cipher.decryptor.clone();
+    let mut frame = Vec::new();
+    while frame.last().is_none_or(|byte| byte & 0x80 != 0) && frame.len() < MAX_LENGTH_PREFIX {
+        let Some(&byte) = bytes.get(frame.len()) else {
+            return Ok(None);
+        };
+        let mut byte = [byte];
+        decrypt(&mut decryptor, &mut byte);
+        frame.push(byte[0]);
+    }
+
+    // This only checks the length prefix, the rest of the frame is still encrypted.
+    PacketBuffer::frame_size(&frame)?;
+    let length = frame
+        .iter()
+        .rev()
+        .fold(0, |length, byte| length << 7 | (byte & 0x7F) as usize);
+    let size = frame.len() + length;
+    if bytes.len() < size {
+        return Ok(None);
+    }
+
+    let mut rest = bytes[frame.len()..size].to_vec();
+    decrypt(&mut decryptor, &mut rest);
+    frame.extend_from_slice(&rest);
+
+    let packet = compression.grab_from_buffer(frame, compression)?;
+    cipher.decryptor = decryptor;
+    Ok(Some((packet, size)))
+}
+
+/// Decrypts the given bytes in place.
+fn decrypt(decryptor: &mut Decryptor<Aes128>, bytes: &mut [u8]) {
+    for byte in bytes {
+        decryptor.decrypt_block_mut(GenericArray::from_mut_slice(std::slice::from_mut(byte)));
+    }
+}
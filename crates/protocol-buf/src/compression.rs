@@ -1,14 +1,18 @@
-use std::io::Write;
+use std::io::{Read, Write};
 
-use flate2::write::ZlibEncoder;
-use thiserror::Error;
+use bytes::{Bytes, BytesMut};
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Decompress, FlushDecompress, Status};
 
 use crate::{
-    buffer::{Buffer, BufferResult, NormalBuffer, PacketBuffer},
-    types::{encode_varint, VarInt},
+    buffer::{Buffer, BufferError, BufferResult, NormalBuffer, PacketBuffer},
+    pool::BufferPool,
+    types::VarInt,
     ToNetwork,
 };
 
+/// The largest uncompressed packet the client is allowed to send, this is `2^23` bytes.
+pub const MAX_UNCOMPRESSED_LENGTH: i32 = 8388608;
+
 /// Defines the compression types that can be used to compress / decompress packets.
 ///
 /// # Variants
@@ -21,10 +25,28 @@ pub enum CompressionType {
     Zlib,
 }
 
+/// Defines how a single outgoing packet should be compressed.
+///
+/// This is used by packets to bypass the compression threshold, for example for small packets that are sent very often
+/// or packets that were already compressed ahead of time.
+///
+/// # Variants
+///
+/// - `Threshold` - The packet is compressed if it reaches the compression threshold.
+/// - `Never` - The packet is never compressed, even if it reaches the compression threshold.
+/// - `Precompressed` - The packet data was already compressed using `[CompressionData::precompress]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompressionHint {
+    #[default]
+    Threshold,
+    Never,
+    Precompressed,
+}
+
 /// Represents the result of compressing / decompressing a packet.
 ///
 /// This is a type alias for a `BufferResult` with a `PacketBuffer` containing the compressed / decompressed packet.
-pub type CompressionResult<B: Buffer> = BufferResult<B>;
+pub type CompressionResult<B> = BufferResult<B>;
 
 /// Contains the data needed to compress / decompress packets.
 ///
@@ -35,7 +57,7 @@ pub type CompressionResult<B: Buffer> = BufferResult<B>;
 ///
 /// # Examples
 /// ```rust
-/// use buffer::CompressionData;
+/// use protocol_buf::compression::{CompressionData, CompressionType};
 ///
 /// let data = CompressionData {
 ///    threshold: 256,
@@ -65,7 +87,7 @@ impl CompressionData {
     ///
     /// # Examples
     /// ```rust
-    /// use buffer::{CompressionData, CompressionType};
+    /// use protocol_buf::compression::{CompressionData, CompressionType};
     ///
     /// let data = CompressionData::new(256, CompressionType::Zlib);
     /// ```
@@ -85,20 +107,35 @@ impl CompressionData {
 
     /// Grabs the compressed packet from the buffer.
     ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_buf::{buffer::{Buffer, NormalBuffer, PacketBuffer}, compression::{CompressionData, CompressionType}, types::VarInt};
+    ///
+    /// let compression = CompressionData::new(4, CompressionType::Zlib);
+    /// let packet = PacketBuffer::with_id(VarInt::from(0x01), NormalBuffer::new(vec![7; 64]));
+    ///
+    /// let bytes = compression.to_buffer(packet, &compression).unwrap();
+    /// let mut packet = compression.grab_from_buffer(bytes, &compression).unwrap();
+    ///
+    /// assert_eq!(*packet.packet_id, 0x01);
+    /// assert_eq!(packet.read_byte().unwrap(), 7);
+    /// ```
+    ///
     /// # Parameters
-    /// - `buffer` - The buffer to grab the compressed packet from.
+    /// - `buffer` - The buffer to grab the compressed packet from, uncompressed packets are read without copying it.
     ///
     /// # Returns
     /// The compressed packet in a `[CompressionResult]` format.
     pub fn grab_from_buffer(
         &self,
-        buffer: Vec<u8>,
+        buffer: impl Into<Bytes>,
         data: &CompressionData,
     ) -> CompressionResult<PacketBuffer> {
-        Ok(match self.compression_type {
+        let buffer = buffer.into();
+        match self.compression_type {
             CompressionType::None => NormalCompression::decompress(buffer, data),
             CompressionType::Zlib => ZlibCompression::decompress(buffer, data),
-        })
+        }
     }
 
     /// Compresses the given buffer.
@@ -113,24 +150,110 @@ impl CompressionData {
         buffer: PacketBuffer,
         data: &CompressionData,
     ) -> CompressionResult<Vec<u8>> {
-        match self.compression_type {
-            CompressionType::None => NormalCompression::compress(buffer, data),
-            CompressionType::Zlib => ZlibCompression::compress(buffer, data),
+        self.to_buffer_with_hint(buffer, data, CompressionHint::Threshold)
+    }
+
+    /// Compresses the given buffer using the compression hint of the packet.
+    ///
+    /// The frame is written into a buffer of the global `[BufferPool]`, the buffer of the packet is returned to it.
+    ///
+    /// # Parameters
+    /// - `buffer` - The buffer to compress.
+    /// - `hint` - How the packet wants to be compressed.
+    ///
+    /// # Returns
+    /// The compressed buffer in a `[CompressionResult]` format.
+    pub fn to_buffer_with_hint(
+        &self,
+        buffer: PacketBuffer,
+        data: &CompressionData,
+        hint: CompressionHint,
+    ) -> CompressionResult<Vec<u8>> {
+        let result = match self.compression_type {
+            CompressionType::None => NormalCompression::compress(&buffer, data, hint),
+            CompressionType::Zlib => ZlibCompression::compress(&buffer, data, hint),
+        };
+        BufferPool::global().give_packet(buffer);
+        result
+    }
+
+    /// Compresses the given packet ahead of time.
+    ///
+    /// The returned buffer contains the compressed packet ID and data, with `data_length` set to the uncompressed size.
+    /// It can be cached and sent multiple times using `[CompressionHint::Precompressed]` without compressing it again.
+    /// Packets below the threshold aren't compressed, their `data_length` is `0` like in an uncompressed frame.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_buf::{
+    ///     buffer::{NormalBuffer, PacketBuffer},
+    ///     compression::{CompressionData, CompressionHint, CompressionType},
+    ///     types::VarInt,
+    /// };
+    ///
+    /// let data = CompressionData::new(256, CompressionType::Zlib);
+    /// let packet = PacketBuffer::with_id(VarInt::from(0x27), NormalBuffer::new(vec![0; 1024]));
+    /// let cached = data.precompress(packet).unwrap();
+    ///
+    /// assert_eq!(*cached.data_length, 1025);
+    ///
+    /// let bytes = data.to_buffer_with_hint(cached, &data, CompressionHint::Precompressed).unwrap();
+    ///
+    /// assert_eq!(PacketBuffer::frame_size(&bytes).unwrap(), Some(bytes.len()));
+    ///
+    /// let small = PacketBuffer::with_id(VarInt::from(0x27), NormalBuffer::new(vec![0; 16]));
+    /// assert_eq!(*data.precompress(small).unwrap().data_length, 0);
+    /// ```
+    ///
+    /// # Parameters
+    /// - `buffer` - The buffer to compress.
+    ///
+    /// # Returns
+    /// The precompressed buffer in a `[CompressionResult]` format.
+    pub fn precompress(&self, buffer: PacketBuffer) -> CompressionResult<PacketBuffer> {
+        let data_length = VarInt::from(*buffer.packet_length);
+        if *data_length < self.threshold {
+            let mut body = buffer.packet_id.to_network();
+            body.extend_from_slice(buffer.get_ref());
+            let data_length = VarInt::from(0);
+
+            return Ok(PacketBuffer {
+                packet_length: VarInt::from((data_length.len() + body.len()) as i32),
+                data_length,
+                packet_id: buffer.packet_id,
+                buffer: NormalBuffer::new(body),
+            });
         }
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder
+            .write_all(&buffer.packet_id.to_network())
+            .map_err(|_| BufferError::CompressionError)?;
+        encoder
+            .write_all(buffer.get_ref())
+            .map_err(|_| BufferError::CompressionError)?;
+
+        let compressed = encoder
+            .finish()
+            .map_err(|_| BufferError::CompressionError)?;
+        Ok(PacketBuffer {
+            packet_length: VarInt::from((data_length.len() + compressed.len()) as i32),
+            data_length,
+            packet_id: buffer.packet_id,
+            buffer: NormalBuffer::new(compressed),
+        })
     }
 }
 
 /// A trait that defines a compression algorithm type. This is used for values inside `[CompressionType]`.
 ///
 /// # Examples
-/// ```rust
-/// use buffer::Compression;
-///
+/// ```rust,ignore
 /// struct CustomCompression;
 ///
 /// impl Compression for CustomCompression {
-///    fn compress<B: Buffer>(buffer: B) -> CompressionResult<B> {}
-///    fn decompress<B: Buffer>(buffer: B) -> CompressionResult<B> {}
+///    fn compress(buffer: &PacketBuffer, data: &CompressionData, hint: CompressionHint) -> CompressionResult<Vec<u8>> {}
+///    fn decompress(buffer: Vec<u8>, data: &CompressionData) -> CompressionResult<PacketBuffer> {}
 /// }
 /// ```
 ///
@@ -141,13 +264,18 @@ trait Compression {
     ///
     /// # Parameters
     /// - `buffer` - The buffer to compress.
-    fn compress(buffer: PacketBuffer, data: &CompressionData) -> CompressionResult<Vec<u8>>;
+    /// - `hint` - How the packet wants to be compressed.
+    fn compress(
+        buffer: &PacketBuffer,
+        data: &CompressionData,
+        hint: CompressionHint,
+    ) -> CompressionResult<Vec<u8>>;
 
     /// Decompresses the given buffer. This is used for values inside `[CompressionType]`.
     ///
     /// # Parameters
     /// - `buffer` - The buffer to decompress.
-    fn decompress(buffer: Vec<u8>, data: &CompressionData) -> PacketBuffer;
+    fn decompress(buffer: Bytes, data: &CompressionData) -> CompressionResult<PacketBuffer>;
 }
 
 /// This struct represents the `[CompressionType::None]` variant.
@@ -155,10 +283,8 @@ trait Compression {
 /// This is used for a compression type that does not compress packets.
 ///
 /// # Examples
-/// ```rust
-/// use buffer::NormalCompression;
-///
-/// let result = NormalCompression::compress(PacketBuffer::new(vec![0, 1, 2, 3]));
+/// ```rust,ignore
+/// let result = NormalCompression::compress(packet, &CompressionData::default());
 /// ```
 struct NormalCompression;
 
@@ -176,8 +302,35 @@ impl Compression for NormalCompression {
     ///
     /// # Returns
     /// The compressed packet in a `[CompressionResult]` format.
-    fn compress(buffer: PacketBuffer, _data: &CompressionData) -> CompressionResult<Vec<u8>> {
-        Ok(buffer.get_ref().clone())
+    ///
+    /// # Note
+    /// Precompressed packets are decompressed again, since the connection doesn't use compression.
+    fn compress(
+        buffer: &PacketBuffer,
+        _data: &CompressionData,
+        hint: CompressionHint,
+    ) -> CompressionResult<Vec<u8>> {
+        if hint == CompressionHint::Precompressed {
+            let mut body = Vec::with_capacity(*buffer.data_length as usize);
+            read_precompressed(buffer, &mut body)?;
+
+            let packet_length = VarInt::from(body.len() as i32);
+            let mut result = packet_length.to_network();
+            result.extend_from_slice(&body);
+            return Ok(result);
+        }
+
+        let data = buffer.get_ref();
+        let packet_id = buffer.packet_id;
+        let packet_length = VarInt::from((packet_id.len() + data.len()) as i32);
+
+        let mut result = BufferPool::global().take();
+        result.reserve(packet_length.len() + *packet_length as usize);
+        result.extend_from_slice(&packet_length.to_network());
+        result.extend_from_slice(&packet_id.to_network());
+        result.extend_from_slice(data);
+
+        Ok(result)
     }
 
     /// This decompression algorithm doesn't actually decompress anything. This is used for values inside `[CompressionType]`
@@ -197,14 +350,15 @@ impl Compression for NormalCompression {
     /// # Note
     /// The uncompressed packet does not contain the `data_length` field. Therefore, it's always set to `0`.
     /// This is because the `data_length` field is only used for compressed packets.
-    fn decompress(buffer: Vec<u8>, data: &CompressionData) -> PacketBuffer {
-        let mut normal_buffer = NormalBuffer::new(buffer);
-        PacketBuffer {
-            packet_length: normal_buffer.read_varint(),
+    fn decompress(buffer: Bytes, _data: &CompressionData) -> CompressionResult<PacketBuffer> {
+        let mut normal_buffer = NormalBuffer::from_bytes(buffer);
+        let packet_length = read_packet_length(&mut normal_buffer)?;
+        Ok(PacketBuffer {
+            packet_length,
             data_length: VarInt::from(0),
-            packet_id: normal_buffer.read_varint(),
+            packet_id: normal_buffer.read_varint()?,
             buffer: normal_buffer,
-        }
+        })
     }
 }
 
@@ -213,59 +367,297 @@ impl Compression for NormalCompression {
 /// This is used for a compression type that compresses packets using the Zlib algorithm.
 ///
 /// # Examples
-/// ```rust
-/// use buffer::ZlibCompression;
-///
-/// let result = ZlibCompression::compress(PacketBuffer::new(vec![0, 1, 2, 3]));
+/// ```rust,ignore
+/// let result = ZlibCompression::compress(packet, &CompressionData::new(256, CompressionType::Zlib));
 /// ```
 struct ZlibCompression;
 
 impl Compression for ZlibCompression {
     /// Compresses the given buffer using the Zlib algorithm. This is used for values inside `[CompressionType]`
     ///
+    /// The format of the `Zlib` compression is as follows:
+    /// - Field Name  | Field Type | Notes
+    /// - Length      | VarInt     | Length of Data Length + compressed Packet ID + Data
+    /// - Data Length | VarInt     | Length of the uncompressed Packet ID + Data, `0` if it's not compressed.
+    /// - Packet ID   | VarInt     | Zlib compressed together with the data.
+    /// - Data        | Byte Array | The data of the packet.
+    ///
+    /// Only packets reaching the threshold are compressed. Packets with `[CompressionHint::Never]` are always sent uncompressed
+    /// and packets with `[CompressionHint::Precompressed]` are sent as they are, unless they are compressed but below the
+    /// threshold of this connection, since the client rejects those.
+    ///
     /// # Parameters
     /// - `buffer` - The buffer to compress.
     ///
     /// # Returns
     /// The compressed packet in a `[CompressionResult]` format.
-    fn compress(buffer: PacketBuffer, data: &CompressionData) -> CompressionResult<Vec<u8>> {
-        let mut result = Vec::new();
-        let buffer_data = buffer.get_ref().clone();
+    fn compress(
+        buffer: &PacketBuffer,
+        data: &CompressionData,
+        hint: CompressionHint,
+    ) -> CompressionResult<Vec<u8>> {
         let packet_id = buffer.packet_id;
+        let buffer_data = buffer.get_ref();
+        let uncompressed_length = packet_id.len() + buffer_data.len();
 
-        result.extend_from_slice(&encode_varint(0));
+        let pool = BufferPool::global();
+        let mut body = pool.take();
+        match hint {
+            CompressionHint::Precompressed
+                if *buffer.data_length != 0 && *buffer.data_length < data.threshold =>
+            {
+                body.extend_from_slice(&VarInt::from(0).to_network());
+                read_precompressed(buffer, &mut body)?;
+            }
+            CompressionHint::Precompressed => {
+                body.extend_from_slice(&buffer.data_length.to_network());
+                body.extend_from_slice(buffer_data);
+            }
+            CompressionHint::Threshold if uncompressed_length as i32 >= data.threshold => {
+                body.extend_from_slice(&VarInt::from(uncompressed_length as i32).to_network());
 
-        if buffer_data.len() as i32 >= data.threshold {
-            result.extend_from_slice(&encode_varint((packet_id.len() + buffer_data.len()) as i32));
+                let mut encoder = ZlibEncoder::new(body, flate2::Compression::default());
+                encoder
+                    .write_all(&packet_id.to_network())
+                    .map_err(|_| BufferError::CompressionError)?;
+                encoder
+                    .write_all(buffer_data)
+                    .map_err(|_| BufferError::CompressionError)?;
+                body = encoder
+                    .finish()
+                    .map_err(|_| BufferError::CompressionError)?;
+            }
+            _ => {
+                body.extend_from_slice(&VarInt::from(0).to_network());
+                body.extend_from_slice(&packet_id.to_network());
+                body.extend_from_slice(buffer_data);
+            }
+        }
 
-            let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
-            encoder.write_all(&packet_id.to_network()).unwrap();
-            encoder.write_all(&buffer_data).unwrap();
-            let compressed_data = encoder.finish().unwrap();
+        let packet_length = VarInt::from(body.len() as i32);
+        let mut result = pool.take();
+        result.reserve(packet_length.len() + body.len());
+        result.extend_from_slice(&packet_length.to_network());
+        result.extend_from_slice(&body);
+        pool.give(body);
+        Ok(result)
+    }
 
-            result.extend_from_slice(&compressed_data);
+    /// Decompresses the given buffer using the Zlib algorithm. This is used for values inside `[CompressionType]`
+    ///
+    /// The buffer has the same format as the one created by `compress`. Packets with a `Data Length` of `0` are not compressed.
+    /// Compressed packets have to reach the threshold and can't be larger than `[MAX_UNCOMPRESSED_LENGTH]`.
+    ///
+    /// # Parameters
+    /// - `buffer` - The buffer to decompress.
+    ///
+    /// # Returns
+    /// The decompressed packet in a `[CompressionResult]` format.
+    fn decompress(buffer: Bytes, data: &CompressionData) -> CompressionResult<PacketBuffer> {
+        let mut frame = NormalBuffer::from_bytes(buffer);
+        let packet_length = read_packet_length(&mut frame)?;
+        let data_length = frame.read_varint()?;
+
+        let mut normal_buffer = if *data_length == 0 {
+            frame
         } else {
-            result.extend_from_slice(&encode_varint(0));
+            if *data_length < data.threshold || *data_length > MAX_UNCOMPRESSED_LENGTH {
+                return Err(BufferError::BadPacketLength);
+            }
 
-            result.extend_from_slice(&packet_id.to_network());
-            result.extend_from_slice(&buffer_data);
-        }
+            let start = frame.buffer.position() as usize;
+            let mut body = BytesMut::new();
+            inflate(
+                &mut Decompress::new(true),
+                &mut body,
+                &frame.get_ref()[start..],
+                *data_length as usize,
+            )?;
 
-        let packet_length = (result.len() - encode_varint(0).len()) as i32;
-        let packet_length_encoded = encode_varint(packet_length);
-        result[..packet_length_encoded.len()].copy_from_slice(&packet_length_encoded);
+            NormalBuffer::from_bytes(body.freeze())
+        };
 
-        Ok(result)
+        Ok(PacketBuffer {
+            packet_length,
+            data_length,
+            packet_id: normal_buffer.read_varint()?,
+            buffer: normal_buffer,
+        })
     }
+}
 
-    /// Decompresses the given buffer using the Zlib algorithm. This is used for values inside `[CompressionType]`
+/// Decompresses received packets, reusing its Zlib state and its output buffer for every packet of a connection.
+///
+/// Packets are decompressed directly into the output buffer, which is then split off without copying it. Once a
+/// packet is dropped, its memory is reused for the next packets, so reading chunk-sized packets doesn't allocate
+/// and copy every time.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::{buffer::{Buffer, NormalBuffer, PacketBuffer}, compression::{CompressionData, CompressionType, Inflater}, types::VarInt};
+///
+/// let compression = CompressionData::new(4, CompressionType::Zlib);
+/// let packet = PacketBuffer::with_id(VarInt::from(0x01), NormalBuffer::new(vec![7; 64]));
+/// let bytes = compression.to_buffer(packet, &compression).unwrap();
+///
+/// let mut inflater = Inflater::new();
+/// let mut packet = inflater.read_packet(bytes, &compression).unwrap();
+///
+/// assert_eq!(*packet.packet_id, 0x01);
+/// assert_eq!(packet.read_byte().unwrap(), 7);
+/// ```
+///
+/// # Fields
+/// - `decompress` - The Zlib state, which is reset for every packet.
+/// - `output` - The buffer the packets are decompressed into.
+/// - `max_length` - The largest uncompressed packet that is accepted, at most `[MAX_UNCOMPRESSED_LENGTH]`.
+#[derive(Debug)]
+pub struct Inflater {
+    decompress: Decompress,
+    output: BytesMut,
+    max_length: i32,
+}
+
+impl Default for Inflater {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Inflater {
+    /// Creates a new `[Inflater]` with an empty output buffer.
+    pub fn new() -> Self {
+        Self {
+            decompress: Decompress::new(true),
+            output: BytesMut::new(),
+            max_length: MAX_UNCOMPRESSED_LENGTH,
+        }
+    }
+
+    /// Limits the size of the uncompressed packets, larger packets are rejected before they are decompressed.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_buf::{buffer::{BufferError, NormalBuffer, PacketBuffer}, compression::{CompressionData, CompressionType, Inflater}, types::VarInt};
+    ///
+    /// let compression = CompressionData::new(4, CompressionType::Zlib);
+    /// let packet = PacketBuffer::with_id(VarInt::from(0x01), NormalBuffer::new(vec![7; 64]));
+    /// let bytes = compression.to_buffer(packet, &compression).unwrap();
+    ///
+    /// let mut inflater = Inflater::new();
+    /// inflater.set_max_length(32);
+    /// assert!(matches!(inflater.read_packet(bytes, &compression), Err(BufferError::BadPacketLength)));
+    /// ```
     ///
     /// # Parameters
-    /// - `buffer` - The buffer to decompress.
+    /// - `max_length` - The largest uncompressed packet in bytes, it can't exceed `[MAX_UNCOMPRESSED_LENGTH]`.
+    pub fn set_max_length(&mut self, max_length: usize) {
+        self.max_length = max_length.min(MAX_UNCOMPRESSED_LENGTH as usize) as i32;
+    }
+
+    /// Reads a packet from a received frame, the same way as `[CompressionData::grab_from_buffer]`.
+    ///
+    /// # Parameters
+    /// - `buffer` - The frame to read the packet from, uncompressed packets are read without copying it.
+    /// - `data` - The compression data of the connection.
     ///
     /// # Returns
     /// The decompressed packet in a `[CompressionResult]` format.
-    fn decompress(buffer: Vec<u8>, data: &CompressionData) -> PacketBuffer {
-        unimplemented!()
+    pub fn read_packet(
+        &mut self,
+        buffer: impl Into<Bytes>,
+        data: &CompressionData,
+    ) -> CompressionResult<PacketBuffer> {
+        let buffer = buffer.into();
+        if data.compression_type == CompressionType::None {
+            return NormalCompression::decompress(buffer, data);
+        }
+
+        let mut frame = NormalBuffer::from_bytes(buffer);
+        let packet_length = read_packet_length(&mut frame)?;
+        let data_length = frame.read_varint()?;
+
+        let mut normal_buffer = if *data_length == 0 {
+            frame
+        } else {
+            if *data_length < data.threshold || *data_length > self.max_length {
+                return Err(BufferError::BadPacketLength);
+            }
+
+            let start = frame.buffer.position() as usize;
+            inflate(
+                &mut self.decompress,
+                &mut self.output,
+                &frame.get_ref()[start..],
+                *data_length as usize,
+            )?;
+
+            NormalBuffer::from_bytes(self.output.split().freeze())
+        };
+
+        Ok(PacketBuffer {
+            packet_length,
+            data_length,
+            packet_id: normal_buffer.read_varint()?,
+            buffer: normal_buffer,
+        })
+    }
+}
+
+/// Decompresses Zlib data of exactly the given length into the empty output buffer.
+///
+/// The output buffer reclaims the memory of packets that were already dropped before it allocates.
+fn inflate(
+    decompress: &mut Decompress,
+    output: &mut BytesMut,
+    input: &[u8],
+    length: usize,
+) -> CompressionResult<()> {
+    decompress.reset(true);
+    output.clear();
+    output.resize(length, 0);
+
+    let status = decompress
+        .decompress(input, output, FlushDecompress::Finish)
+        .map_err(|_| BufferError::CompressionError)?;
+    if status != Status::StreamEnd || decompress.total_out() != length as u64 {
+        output.clear();
+        return Err(BufferError::CompressionError);
     }
+
+    Ok(())
+}
+
+/// Appends the uncompressed packet ID and data of a packet created by `[CompressionData::precompress]` to the output.
+fn read_precompressed(buffer: &PacketBuffer, output: &mut Vec<u8>) -> CompressionResult<()> {
+    if *buffer.data_length == 0 {
+        output.extend_from_slice(buffer.get_ref());
+        return Ok(());
+    }
+
+    ZlibDecoder::new(buffer.get_ref())
+        .read_to_end(output)
+        .map_err(|_| BufferError::CompressionError)?;
+    Ok(())
+}
+
+/// Reads the `Length` of a frame and checks that it matches the amount of bytes that follow it.
+///
+/// Unlike the VarInts inside of packets, the length may be padded to 3 bytes, like Velocity does.
+fn read_packet_length(frame: &mut NormalBuffer) -> CompressionResult<VarInt> {
+    let mut length = 0;
+    for index in 0..3 {
+        let byte = frame.read_byte()?;
+        length |= ((byte & 0b01111111) as u64) << (7 * index);
+
+        if byte & 0b10000000 == 0 {
+            let remaining = frame.get_ref().len() as u64 - frame.position();
+            if length != remaining {
+                return Err(BufferError::BadPacketLength);
+            }
+            return Ok(VarInt::from(length as i32));
+        }
+    }
+
+    Err(BufferError::BadPacketLength)
 }
@@ -0,0 +1,68 @@
+use thiserror::Error;
+
+/// Errors that can occur while reading or writing data of the protocol.
+///
+/// Every read path returns this error instead of panicking, so a malformed packet only drops the connection that sent it.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{error::ProtocolError, types::VarInt, FromNetwork};
+///
+/// let mut buffer = Cursor::new(vec![0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]);
+///
+/// assert!(matches!(VarInt::from_network(&mut buffer), Err(ProtocolError::VarIntOverflow)));
+///
+/// let mut buffer = Cursor::new(vec![0xFF, 0xFF, 0xFF, 0xFF, 0x1F]);
+/// assert!(matches!(VarInt::from_network(&mut buffer), Err(ProtocolError::VarIntOverflow)));
+///
+/// let mut buffer = Cursor::new(vec![0x80, 0x00]);
+/// assert!(matches!(VarInt::from_network(&mut buffer), Err(ProtocolError::OverlongVarInt)));
+///
+/// let mut buffer = Cursor::new(vec![0xFF, 0xFF, 0xFF, 0xFF, 0x0F]);
+/// assert_eq!(*VarInt::from_network(&mut buffer).unwrap(), -1);
+/// ```
+///
+/// # Variants
+///
+/// - `VarIntOverflow` - The VarInt is too large to be read, it has too many bytes or bits that don't fit into the value.
+/// - `OverlongVarInt` - The VarInt isn't encoded with the least amount of bytes.
+/// - `InsufficientData` - There is not enough data in the buffer to read.
+/// - `Utf8Error` - The data in the buffer is not valid UTF-8.
+/// - `BadPacketId` - The packet ID is not valid.
+/// - `BadPacketLength` - The packet length is not valid.
+/// - `CompressionError` - The compressed data is not valid.
+/// - `InvalidData` - The data was read, but its value is not valid.
+/// - `Decode` - A field of a packet couldn't be read, this contains the packet, the field and the offset of the field.
+#[derive(Debug, Error)]
+pub enum ProtocolError {
+    #[error("VarInt overflow")]
+    VarIntOverflow,
+    #[error("Over-long VarInt encoding")]
+    OverlongVarInt,
+    #[error("Not enough data in buffer")]
+    InsufficientData,
+    #[error("Invalid UTF-8 sequence")]
+    Utf8Error,
+    #[error("Invalid packet ID")]
+    BadPacketId,
+    #[error("Invalid packet length")]
+    BadPacketLength,
+    #[error("Invalid compressed data")]
+    CompressionError,
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("Failed to read `{field}` of {direction} {state} packet {packet} (0x{packet_id:02X}) at offset {offset}: {source}")]
+    Decode {
+        packet: &'static str,
+        direction: &'static str,
+        state: &'static str,
+        packet_id: i32,
+        field: &'static str,
+        offset: u64,
+        source: Box<ProtocolError>,
+    },
+}
+
+/// A type alias for a `Result` that uses `[ProtocolError]` as the error type.
+pub type ProtocolResult<T> = Result<T, ProtocolError>;
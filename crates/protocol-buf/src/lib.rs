@@ -1,16 +1,70 @@
 use std::io::Cursor;
 
+use error::ProtocolResult;
+
 pub mod buffer;
+pub mod codec;
 pub mod compression;
+pub mod error;
 pub(crate) mod macros;
+pub mod nbt;
+pub mod pool;
+pub mod text;
 pub mod types;
 
 /// Defines a trait for an object that can be written to a `[Buffer]`
+///
+/// This and `[FromNetwork]` are the only serialization traits of the protocol. Every buffer uses them,
+/// so a type only needs a single implementation to be written with `[Buffer::write]` or on its own.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::{buffer::{Buffer, NormalBuffer}, types::VarInt, ToNetwork};
+///
+/// let mut buffer = NormalBuffer::new(Vec::new());
+/// buffer.write(VarInt::from(300));
+/// buffer.write_string("hi".to_string());
+///
+/// let mut bytes = VarInt::from(300).to_network();
+/// bytes.extend_from_slice(&"hi".to_string().to_network());
+/// assert_eq!(buffer.get_ref(), bytes.as_slice());
+/// ```
 pub trait ToNetwork {
     fn to_network(&self) -> Vec<u8>;
 }
 
 /// Defines a trait for an object that can be read from a `[Buffer]`
+///
+/// Reading fails with a `[ProtocolError]` if the data is malformed or incomplete.
+/// Every read is bounds checked and only advances the buffer by the bytes it consumed.
+/// Values can be read from any cursor over bytes, like the `[Bytes]` of a `[NormalBuffer]`, a `Vec<u8>` or a slice.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{error::ProtocolError, FromNetwork};
+///
+/// // A single byte is read and the buffer moves forward.
+/// let mut buffer = Cursor::new(vec![0x01, 0x02]);
+/// assert_eq!(u8::from_network(&mut buffer).unwrap(), 0x01);
+/// assert_eq!(u8::from_network(&mut buffer).unwrap(), 0x02);
+/// assert!(matches!(u8::from_network(&mut buffer), Err(ProtocolError::InsufficientData)));
+///
+/// // A string that claims to be longer than the remaining data.
+/// let mut buffer = Cursor::new(vec![0x05, b'a', b'b']);
+/// assert!(matches!(String::from_network(&mut buffer), Err(ProtocolError::InsufficientData)));
+///
+/// // Strings are read from the current position, not the start of the buffer.
+/// let mut buffer = Cursor::new(vec![0x07, 0x02, b'h', b'i']);
+/// buffer.set_position(1);
+/// assert_eq!(String::from_network(&mut buffer).unwrap(), "hi");
+/// assert_eq!(buffer.position(), 4);
+///
+/// // Truncated numbers and arrays.
+/// assert!(matches!(i32::from_network(&mut Cursor::new(vec![0x00, 0x01])), Err(ProtocolError::InsufficientData)));
+/// assert!(matches!(Vec::<i64>::from_network(&mut Cursor::new(vec![0x7F, 0x00])), Err(ProtocolError::InsufficientData)));
+/// assert!(matches!(String::from_network(&mut Cursor::new(vec![0xFF, 0xFF, 0xFF, 0xFF, 0x0F])), Err(ProtocolError::InvalidData(_))));
+/// ```
 pub trait FromNetwork: Sized {
-    fn from_network(buffer: &mut Cursor<Vec<u8>>) -> Self;
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self>;
 }
@@ -6,13 +6,16 @@ macro_rules! register_buffer {
     } => {
         pub trait $buf_name {
             fn write<T: ToNetwork>(&mut self, buf: T);
-            fn read<T: FromNetwork>(&mut self) -> T;
+            fn read<T: FromNetwork>(&mut self) -> BufferResult<T>;
 
-            fn get_ref(&self) -> &Vec<u8>;
+            fn get_ref(&self) -> &[u8];
             fn get_mut(&mut self) -> &mut Vec<u8>;
 
+            fn remaining(&self) -> usize;
+            fn read_bytes(&mut self, length: usize) -> BufferResult<Vec<u8>>;
+
             $(
-                fn $read(&mut self) -> $buf_type {
+                fn $read(&mut self) -> BufferResult<$buf_type> {
                     self.read::<$buf_type>()
                 }
 
@@ -70,33 +73,46 @@ macro_rules! register_varnum {
             }
         }
 
+        /// Reads the value, only the shortest encoding of a value is accepted.
+        ///
+        /// Bits that don't fit into the value are an overflow, a trailing zero byte is an over-long encoding.
         impl FromNetwork for $name {
-            fn from_network(buffer: &mut Cursor<Vec<u8>>) -> Self {
+            fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+                const BITS: u32 = <$working_type>::BITS;
+
                 let mut value = 0;
-                let mut size = 0;
+                let mut size: u32 = 0;
 
                 loop {
-                    let byte = buffer.get_ref()[buffer.position() as usize];
-                    buffer.set_position(buffer.position() + 1);
-
-                    value |= ((byte & 0b01111111) as $working_type) << (7 * size);
-                    size += 1;
+                    if size >= $max_size {
+                        return Err(ProtocolError::VarIntOverflow);
+                    }
 
-                    if size > $max_size {
-                        panic!("VarInt too large");
+                    let byte = u8::from_network(buffer)?;
+                    let shift = 7 * size;
+                    let bits = (byte & 0b01111111) as $working_type;
+                    if shift + 7 > BITS && bits >> (BITS - shift) != 0 {
+                        return Err(ProtocolError::VarIntOverflow);
                     }
 
+                    value |= bits << shift;
+                    size += 1;
+
                     if byte & 0b10000000 == 0 {
+                        if byte == 0 && size > 1 {
+                            return Err(ProtocolError::OverlongVarInt);
+                        }
                         break;
                     }
                 }
 
-                Self {
+                Ok(Self {
                     value: value as $varnum_type,
-                }
+                })
             }
         }
 
+        #[allow(clippy::len_without_is_empty)]
         impl $name {
             pub fn len(&self) -> usize {
                 let mut value = self.value as $working_type;
@@ -120,11 +136,8 @@ macro_rules! register_varnum {
 #[macro_export]
 macro_rules! handle_primitive_read {
     ($buffer:expr, $type:ty, $bytes:literal) => {{
-        let mut bytes = [0; $bytes];
-        $buffer
-            .read_exact(&mut bytes)
-            .expect("Failed to read bytes");
-        <$type>::from_be_bytes(bytes)
+        let bytes = $crate::types::read_array::<$bytes, _>($buffer)?;
+        Ok(<$type>::from_be_bytes(bytes))
     }};
 }
 
@@ -138,7 +151,7 @@ macro_rules! handle_primitive_type {
         }
 
         impl FromNetwork for $type {
-            fn from_network(buffer: &mut Cursor<Vec<u8>>) -> Self {
+            fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
                 handle_primitive_read!(buffer, $type, $size)
             }
         }
@@ -0,0 +1,590 @@
+use std::{
+    collections::HashMap,
+    io::{Cursor, Read, Write},
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder};
+
+use crate::{
+    error::{ProtocolError, ProtocolResult},
+    types::{read_bytes, remaining},
+    FromNetwork, ToNetwork,
+};
+
+/// The maximum depth of nested lists and compounds, this is the same limit the vanilla client uses.
+pub const MAX_DEPTH: usize = 512;
+
+/// The first bytes of gzip compressed data, NBT files are usually compressed with gzip.
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+
+/// Represents a single NBT tag.
+///
+/// # Variants
+///
+/// - `Byte` - A signed byte.
+/// - `Short` - A signed short.
+/// - `Int` - A signed integer.
+/// - `Long` - A signed long.
+/// - `Float` - A float.
+/// - `Double` - A double.
+/// - `ByteArray` - An array of signed bytes.
+/// - `String` - A string.
+/// - `List` - A list of tags, all tags have to be of the same type.
+/// - `Compound` - A list of named tags.
+/// - `IntArray` - An array of signed integers.
+/// - `LongArray` - An array of signed longs.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NbtTag {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    List(Vec<NbtTag>),
+    Compound(NbtCompound),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+impl NbtTag {
+    /// Returns the type ID of this tag.
+    pub const fn id(&self) -> u8 {
+        match self {
+            Self::Byte(_) => 1,
+            Self::Short(_) => 2,
+            Self::Int(_) => 3,
+            Self::Long(_) => 4,
+            Self::Float(_) => 5,
+            Self::Double(_) => 6,
+            Self::ByteArray(_) => 7,
+            Self::String(_) => 8,
+            Self::List(_) => 9,
+            Self::Compound(_) => 10,
+            Self::IntArray(_) => 11,
+            Self::LongArray(_) => 12,
+        }
+    }
+
+    /// Converts a JSON value into a NBT tag, the way data packs are read.
+    ///
+    /// Booleans become bytes, integers become ints or longs and other numbers become doubles.
+    /// Lists that mix integers and other numbers are converted to a list of doubles, as NBT lists can only hold one type.
+    /// Lists that mix other types become a list of compounds, like vanilla does: every element that isn't a compound
+    /// is wrapped into a compound with an empty name.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_buf::nbt::{NbtCompound, NbtTag};
+    ///
+    /// let json = serde_json::json!({ "has_precipitation": true, "temperature": 0.8, "values": [1, 2.5] });
+    /// let NbtTag::Compound(compound) = NbtTag::from_json(&json).unwrap() else { panic!() };
+    ///
+    /// assert_eq!(compound.get("has_precipitation"), Some(&NbtTag::Byte(1)));
+    /// assert_eq!(compound.get("temperature"), Some(&NbtTag::Double(0.8)));
+    /// assert_eq!(compound.get("values"), Some(&NbtTag::List(vec![NbtTag::Double(1.0), NbtTag::Double(2.5)])));
+    ///
+    /// let mixed = NbtTag::from_json(&serde_json::json!(["a", 1, { "b": 2 }])).unwrap();
+    /// assert_eq!(
+    ///     mixed,
+    ///     NbtTag::List(vec![
+    ///         NbtCompound::new().with("", "a").into(),
+    ///         NbtCompound::new().with("", 1).into(),
+    ///         NbtCompound::new().with("b", 2).into(),
+    ///     ])
+    /// );
+    /// ```
+    ///
+    /// # Returns
+    /// The tag, `None` for `null`, which can't be represented in NBT.
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        use serde_json::Value;
+
+        Some(match value {
+            Value::Null => return None,
+            Value::Bool(value) => Self::from(*value),
+            Value::Number(number) => match number.as_i64() {
+                Some(value) => match i32::try_from(value) {
+                    Ok(value) => Self::Int(value),
+                    Err(_) => Self::Long(value),
+                },
+                None => Self::Double(number.as_f64()?),
+            },
+            Value::String(value) => Self::String(value.clone()),
+            Value::Array(values) => {
+                let mut tags: Vec<Self> = values.iter().filter_map(Self::from_json).collect();
+                let numbers = tags
+                    .iter()
+                    .all(|tag| matches!(tag, Self::Int(_) | Self::Long(_) | Self::Double(_)));
+                let mixed = tags.iter().any(|tag| tag.id() != tags[0].id());
+                if numbers && mixed {
+                    tags = values
+                        .iter()
+                        .filter_map(|value| value.as_f64().map(Self::Double))
+                        .collect();
+                } else if mixed {
+                    tags = tags.into_iter().map(Self::wrap_list_element).collect();
+                }
+
+                Self::List(tags)
+            }
+            Value::Object(entries) => {
+                let mut compound = NbtCompound::new();
+                for (name, value) in entries {
+                    if let Some(tag) = Self::from_json(value) {
+                        compound.insert(name.as_str(), tag);
+                    }
+                }
+
+                Self::Compound(compound)
+            }
+        })
+    }
+
+    /// Wraps an element of a list that mixes types into a compound, compounds are kept unless they look like a wrapper themselves.
+    fn wrap_list_element(self) -> Self {
+        match self {
+            Self::Compound(compound)
+                if compound.iter().count() != 1 || compound.get("").is_none() =>
+            {
+                Self::Compound(compound)
+            }
+            tag => Self::Compound(NbtCompound::new().with("", tag)),
+        }
+    }
+
+    /// Reads the payload of a tag with the given type ID.
+    ///
+    /// # Parameters
+    /// - `id` - The type ID of the tag.
+    /// - `buffer` - The buffer to read from.
+    /// - `depth` - The amount of lists and compounds the tag is nested in.
+    fn read_payload<B: AsRef<[u8]>>(
+        id: u8,
+        buffer: &mut Cursor<B>,
+        depth: usize,
+    ) -> ProtocolResult<Self> {
+        if depth > MAX_DEPTH {
+            return Err(ProtocolError::InvalidData(
+                "NBT is nested too deeply".to_string(),
+            ));
+        }
+
+        Ok(match id {
+            1 => Self::Byte(i8::from_network(buffer)?),
+            2 => Self::Short(i16::from_network(buffer)?),
+            3 => Self::Int(i32::from_network(buffer)?),
+            4 => Self::Long(i64::from_network(buffer)?),
+            5 => Self::Float(f32::from_network(buffer)?),
+            6 => Self::Double(f64::from_network(buffer)?),
+            7 => {
+                let length = read_length(buffer, 1)?;
+                Self::ByteArray(
+                    read_bytes(buffer, length)?
+                        .into_iter()
+                        .map(|value| value as i8)
+                        .collect(),
+                )
+            }
+            8 => Self::String(read_string(buffer)?),
+            9 => {
+                let element = u8::from_network(buffer)?;
+                let length = read_length(buffer, 1)?;
+                if length > 0 && element == 0 {
+                    return Err(ProtocolError::InvalidData(
+                        "NBT list of end tags".to_string(),
+                    ));
+                }
+
+                let mut tags = Vec::with_capacity(length);
+                for _ in 0..length {
+                    tags.push(Self::read_payload(element, buffer, depth + 1)?);
+                }
+                Self::List(tags)
+            }
+            10 => Self::Compound(NbtCompound::read_payload(buffer, depth + 1)?),
+            11 => {
+                let length = read_length(buffer, 4)?;
+                let mut values = Vec::with_capacity(length);
+                for _ in 0..length {
+                    values.push(i32::from_network(buffer)?);
+                }
+                Self::IntArray(values)
+            }
+            12 => {
+                let length = read_length(buffer, 8)?;
+                let mut values = Vec::with_capacity(length);
+                for _ in 0..length {
+                    values.push(i64::from_network(buffer)?);
+                }
+                Self::LongArray(values)
+            }
+            id => {
+                return Err(ProtocolError::InvalidData(format!(
+                    "Invalid NBT tag type: {}",
+                    id
+                )))
+            }
+        })
+    }
+
+    /// Writes the payload of this tag, without its type ID and name.
+    fn write_payload(&self, bytes: &mut Vec<u8>) {
+        match self {
+            Self::Byte(value) => bytes.push(*value as u8),
+            Self::Short(value) => bytes.extend_from_slice(&value.to_be_bytes()),
+            Self::Int(value) => bytes.extend_from_slice(&value.to_be_bytes()),
+            Self::Long(value) => bytes.extend_from_slice(&value.to_be_bytes()),
+            Self::Float(value) => bytes.extend_from_slice(&value.to_be_bytes()),
+            Self::Double(value) => bytes.extend_from_slice(&value.to_be_bytes()),
+            Self::ByteArray(values) => {
+                bytes.extend_from_slice(&(values.len() as i32).to_be_bytes());
+                bytes.extend(values.iter().map(|value| *value as u8));
+            }
+            Self::String(value) => write_string(value, bytes),
+            Self::List(tags) => {
+                bytes.push(tags.first().map_or(0, NbtTag::id));
+                bytes.extend_from_slice(&(tags.len() as i32).to_be_bytes());
+                for tag in tags {
+                    tag.write_payload(bytes);
+                }
+            }
+            Self::Compound(compound) => compound.write_payload(bytes),
+            Self::IntArray(values) => {
+                bytes.extend_from_slice(&(values.len() as i32).to_be_bytes());
+                for value in values {
+                    bytes.extend_from_slice(&value.to_be_bytes());
+                }
+            }
+            Self::LongArray(values) => {
+                bytes.extend_from_slice(&(values.len() as i32).to_be_bytes());
+                for value in values {
+                    bytes.extend_from_slice(&value.to_be_bytes());
+                }
+            }
+        }
+    }
+}
+
+/// Writes the tag as network NBT, which is the type ID followed by the payload without a name.
+impl ToNetwork for NbtTag {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = vec![self.id()];
+        self.write_payload(&mut bytes);
+        bytes
+    }
+}
+
+/// Reads network NBT, which is the type ID followed by the payload without a name.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{nbt::NbtTag, FromNetwork, ToNetwork};
+///
+/// let tag = NbtTag::List(vec![NbtTag::Int(1), NbtTag::Int(2)]);
+/// let mut buffer = Cursor::new(tag.to_network());
+///
+/// assert_eq!(NbtTag::from_network(&mut buffer).unwrap(), tag);
+/// ```
+impl FromNetwork for NbtTag {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        let id = u8::from_network(buffer)?;
+        Self::read_payload(id, buffer, 0)
+    }
+}
+
+/// Reads the length of a NBT array or list.
+///
+/// The length is checked against the remaining bytes, so a malformed length can't allocate a huge array.
+///
+/// # Parameters
+/// - `buffer` - The buffer to read from.
+/// - `element_size` - The minimum size of a single element in bytes.
+fn read_length<B: AsRef<[u8]>>(
+    buffer: &mut Cursor<B>,
+    element_size: usize,
+) -> ProtocolResult<usize> {
+    let length = usize::try_from(i32::from_network(buffer)?)
+        .map_err(|_| ProtocolError::InvalidData("negative NBT length".to_string()))?;
+
+    if length.saturating_mul(element_size) > remaining(buffer) {
+        return Err(ProtocolError::InsufficientData);
+    }
+    Ok(length)
+}
+
+/// Reads a NBT string, which is prefixed with its length as an unsigned short.
+fn read_string<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<String> {
+    let length = u16::from_network(buffer)? as usize;
+    String::from_utf8(read_bytes(buffer, length)?).map_err(|_| ProtocolError::Utf8Error)
+}
+
+/// Writes a NBT string, which is prefixed with its length as an unsigned short.
+fn write_string(value: &str, bytes: &mut Vec<u8>) {
+    bytes.extend_from_slice(&(value.len() as u16).to_be_bytes());
+    bytes.extend_from_slice(value.as_bytes());
+}
+
+macro_rules! nbt_tag_from {
+    ($($type:ty => $variant:ident),*) => {
+        $(
+            impl From<$type> for NbtTag {
+                fn from(value: $type) -> Self {
+                    Self::$variant(value.into())
+                }
+            }
+        )*
+    };
+}
+
+nbt_tag_from! {
+    i8 => Byte,
+    i16 => Short,
+    i32 => Int,
+    i64 => Long,
+    f32 => Float,
+    f64 => Double,
+    Vec<i8> => ByteArray,
+    String => String,
+    &str => String,
+    Vec<NbtTag> => List,
+    NbtCompound => Compound,
+    Vec<i32> => IntArray,
+    Vec<i64> => LongArray
+}
+
+impl From<bool> for NbtTag {
+    fn from(value: bool) -> Self {
+        Self::Byte(value as i8)
+    }
+}
+
+/// Represents a NBT compound tag, a list of named tags.
+///
+/// The tags keep the order they were inserted in, they are indexed by their name so looking up and inserting a tag doesn't scan the compound.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::nbt::{NbtCompound, NbtTag};
+///
+/// let compound = NbtCompound::new()
+///     .with("name", "minecraft:plains")
+///     .with("has_precipitation", true);
+///
+/// assert_eq!(compound.get("has_precipitation"), Some(&NbtTag::Byte(1)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NbtCompound {
+    entries: Vec<(String, NbtTag)>,
+    index: HashMap<String, usize>,
+}
+
+impl NbtCompound {
+    /// Creates a new empty `[NbtCompound]`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts the given tag. If a tag with the same name already exists, it is replaced.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the tag.
+    /// - `tag` - The tag to insert.
+    pub fn insert(&mut self, name: impl Into<String>, tag: impl Into<NbtTag>) {
+        let name = name.into();
+        let tag = tag.into();
+
+        match self.index.get(&name) {
+            Some(&position) => self.entries[position].1 = tag,
+            None => {
+                self.index.insert(name.clone(), self.entries.len());
+                self.entries.push((name, tag));
+            }
+        }
+    }
+
+    /// Inserts the given tag and returns the compound. This is useful for building compounds.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the tag.
+    /// - `tag` - The tag to insert.
+    pub fn with(mut self, name: impl Into<String>, tag: impl Into<NbtTag>) -> Self {
+        self.insert(name, tag);
+        self
+    }
+
+    /// Returns the tag with the given name.
+    pub fn get(&self, name: &str) -> Option<&NbtTag> {
+        self.index
+            .get(name)
+            .map(|&position| &self.entries[position].1)
+    }
+
+    /// Returns an iterator over the names and tags of this compound.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &NbtTag)> {
+        self.entries.iter().map(|(name, tag)| (name.as_str(), tag))
+    }
+
+    /// Reads named tags until the end tag is reached.
+    fn read_payload<B: AsRef<[u8]>>(buffer: &mut Cursor<B>, depth: usize) -> ProtocolResult<Self> {
+        let mut compound = Self::new();
+        loop {
+            let id = u8::from_network(buffer)?;
+            if id == 0 {
+                return Ok(compound);
+            }
+
+            let name = read_string(buffer)?;
+            compound.insert(name, NbtTag::read_payload(id, buffer, depth)?);
+        }
+    }
+
+    /// Writes the named tags of this compound, followed by the end tag.
+    fn write_payload(&self, bytes: &mut Vec<u8>) {
+        for (name, tag) in &self.entries {
+            bytes.push(tag.id());
+            write_string(name, bytes);
+            tag.write_payload(bytes);
+        }
+
+        bytes.push(0);
+    }
+}
+
+/// Represents a network NBT value.
+///
+/// Since Minecraft 1.20.2, NBT sent over the network has an unnamed root compound.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::{nbt::{Nbt, NbtCompound}, ToNetwork};
+///
+/// let nbt = Nbt::new(NbtCompound::new().with("value", 1_i8));
+///
+/// assert_eq!(nbt.to_network(), vec![0x0A, 0x01, 0x00, 0x05, b'v', b'a', b'l', b'u', b'e', 0x01, 0x00]);
+/// ```
+///
+/// # Fields
+/// - `root` - The root compound.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Nbt {
+    pub root: NbtCompound,
+}
+
+impl Nbt {
+    /// Creates a new `[Nbt]` with the given root compound.
+    pub const fn new(root: NbtCompound) -> Self {
+        Self { root }
+    }
+
+    /// Reads NBT in the format of files like structures, which has a named root compound and is usually gzip compressed.
+    ///
+    /// The name of the root compound is ignored. Uncompressed files are read as well.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_buf::nbt::{Nbt, NbtCompound};
+    ///
+    /// let nbt = Nbt::new(NbtCompound::new().with("DataVersion", 3953));
+    /// let bytes = nbt.to_file_bytes();
+    ///
+    /// assert_eq!(&bytes[..2], &[0x1F, 0x8B]);
+    /// assert_eq!(Nbt::from_file_bytes(&bytes).unwrap(), nbt);
+    /// ```
+    ///
+    /// # Parameters
+    /// - `bytes` - The content of the file.
+    pub fn from_file_bytes(bytes: &[u8]) -> ProtocolResult<Self> {
+        let mut decompressed = Vec::new();
+        let bytes = if bytes.starts_with(&GZIP_MAGIC) {
+            GzDecoder::new(bytes)
+                .read_to_end(&mut decompressed)
+                .map_err(|e| ProtocolError::InvalidData(format!("invalid gzip data: {}", e)))?;
+            &decompressed
+        } else {
+            bytes
+        };
+
+        let mut buffer = Cursor::new(bytes);
+        match u8::from_network(&mut buffer)? {
+            10 => {
+                read_string(&mut buffer)?;
+                Ok(Self::new(NbtCompound::read_payload(&mut buffer, 0)?))
+            }
+            id => Err(ProtocolError::InvalidData(format!(
+                "NBT root has to be a compound, not type {}",
+                id
+            ))),
+        }
+    }
+
+    /// Writes the NBT in the format of files, with an unnamed root compound and gzip compressed.
+    pub fn to_file_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![10];
+        write_string("", &mut bytes);
+        self.root.write_payload(&mut bytes);
+
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&bytes).unwrap();
+        encoder.finish().unwrap()
+    }
+}
+
+impl From<NbtCompound> for Nbt {
+    fn from(root: NbtCompound) -> Self {
+        Self::new(root)
+    }
+}
+
+impl ToNetwork for Nbt {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = vec![10];
+        self.root.write_payload(&mut bytes);
+        bytes
+    }
+}
+
+/// Reads network NBT, the root tag has to be a compound.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{error::ProtocolError, nbt::{Nbt, NbtCompound, NbtTag}, FromNetwork, ToNetwork};
+///
+/// let nbt = Nbt::new(NbtCompound::new().with("name", "minecraft:plains").with("temperature", 0.8_f32));
+/// let mut buffer = Cursor::new(nbt.to_network());
+///
+/// assert_eq!(Nbt::from_network(&mut buffer).unwrap(), nbt);
+///
+/// // The root isn't a compound.
+/// let mut buffer = Cursor::new(NbtTag::Int(1).to_network());
+/// assert!(matches!(Nbt::from_network(&mut buffer), Err(ProtocolError::InvalidData(_))));
+///
+/// // A byte array that claims to be longer than the remaining data.
+/// let mut buffer = Cursor::new(vec![0x0A, 0x07, 0x00, 0x00, 0x7F, 0xFF, 0xFF, 0xFF]);
+/// assert!(matches!(Nbt::from_network(&mut buffer), Err(ProtocolError::InsufficientData)));
+///
+/// // Large compounds are read in linear time.
+/// let mut root = NbtCompound::new();
+/// for i in 0..200_000 {
+///     root.insert(i.to_string(), 0_i8);
+/// }
+/// let mut buffer = Cursor::new(Nbt::new(root).to_network());
+/// let nbt = Nbt::from_network(&mut buffer).unwrap();
+/// assert_eq!(nbt.root.iter().count(), 200_000);
+/// assert_eq!(nbt.root.get("199999"), Some(&NbtTag::Byte(0)));
+/// ```
+impl FromNetwork for Nbt {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        match u8::from_network(buffer)? {
+            10 => Ok(Self::new(NbtCompound::read_payload(buffer, 0)?)),
+            id => Err(ProtocolError::InvalidData(format!(
+                "NBT root has to be a compound, not type {}",
+                id
+            ))),
+        }
+    }
+}
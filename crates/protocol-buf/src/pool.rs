@@ -0,0 +1,138 @@
+use std::{
+    hash::{BuildHasher, RandomState},
+    sync::{Mutex, OnceLock},
+    thread,
+};
+
+use crate::buffer::{BufferData, PacketBuffer};
+
+/// The amount of shards of the global pool, every thread mostly uses its own shard.
+const SHARDS: usize = 16;
+
+/// The capacity of a new buffer, which fits most packets without growing.
+pub const DEFAULT_BUFFER_SIZE: usize = 512;
+
+/// The largest capacity of a buffer that is kept, larger buffers like the ones of chunk packets are dropped.
+pub const MAX_POOLED_CAPACITY: usize = 64 * 1024;
+
+/// The amount of buffers a single shard keeps.
+pub const MAX_POOLED_BUFFERS: usize = 64;
+
+/// Lends buffers for writing packets, which are returned once the packet was written.
+///
+/// Sending a packet needs a buffer for its fields and one for its frame. Taking them from the pool instead of
+/// allocating them avoids most of the allocations of sending packets under high packet rates.
+/// The buffers are split into shards by thread, so threads don't wait for each other.
+///
+/// Buffers that grew larger than `max_capacity` aren't kept, so a single large packet doesn't keep its memory forever.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::pool::BufferPool;
+///
+/// let pool = BufferPool::new(256, 1024, 8);
+/// let mut buffer = pool.take();
+/// assert!(buffer.is_empty());
+/// assert!(buffer.capacity() >= 256);
+///
+/// buffer.extend_from_slice(&[1, 2, 3]);
+/// pool.give(buffer);
+/// assert_eq!(pool.pooled(), 1);
+///
+/// // Returned buffers are cleared.
+/// assert!(pool.take().is_empty());
+///
+/// // Buffers that grew too large are dropped.
+/// pool.give(Vec::with_capacity(4096));
+/// assert_eq!(pool.pooled(), 0);
+/// ```
+///
+/// # Fields
+/// - `shards` - The pooled buffers of every shard.
+/// - `hasher` - Assigns the threads to the shards.
+/// - `buffer_size` - The capacity of new buffers.
+/// - `max_capacity` - The largest capacity of a buffer that is kept.
+/// - `max_buffers` - The amount of buffers a single shard keeps.
+#[derive(Debug)]
+pub struct BufferPool {
+    shards: [Mutex<Vec<Vec<u8>>>; SHARDS],
+    hasher: RandomState,
+    buffer_size: usize,
+    max_capacity: usize,
+    max_buffers: usize,
+}
+
+impl Default for BufferPool {
+    fn default() -> Self {
+        Self::new(DEFAULT_BUFFER_SIZE, MAX_POOLED_CAPACITY, MAX_POOLED_BUFFERS)
+    }
+}
+
+impl BufferPool {
+    /// Creates a new empty `[BufferPool]`.
+    ///
+    /// # Parameters
+    /// - `buffer_size` - The capacity of new buffers.
+    /// - `max_capacity` - The largest capacity of a buffer that is kept.
+    /// - `max_buffers` - The amount of buffers a single shard keeps.
+    pub fn new(buffer_size: usize, max_capacity: usize, max_buffers: usize) -> Self {
+        Self {
+            shards: Default::default(),
+            hasher: RandomState::new(),
+            buffer_size,
+            max_capacity,
+            max_buffers,
+        }
+    }
+
+    /// Returns the pool shared by every connection.
+    pub fn global() -> &'static BufferPool {
+        static POOL: OnceLock<BufferPool> = OnceLock::new();
+        POOL.get_or_init(BufferPool::default)
+    }
+
+    /// Takes an empty buffer from the pool, a new buffer is created if the pool is empty.
+    pub fn take(&self) -> Vec<u8> {
+        self.shard()
+            .lock()
+            .unwrap()
+            .pop()
+            .unwrap_or_else(|| Vec::with_capacity(self.buffer_size))
+    }
+
+    /// Returns a buffer to the pool once its data isn't needed anymore.
+    ///
+    /// The buffer is dropped if it is too large or the shard is full.
+    pub fn give(&self, mut buffer: Vec<u8>) {
+        if buffer.capacity() < self.buffer_size || buffer.capacity() > self.max_capacity {
+            return;
+        }
+
+        let mut shard = self.shard().lock().unwrap();
+        if shard.len() < self.max_buffers {
+            buffer.clear();
+            shard.push(buffer);
+        }
+    }
+
+    /// Returns the buffer of a written packet to the pool, shared buffers are only dropped.
+    pub fn give_packet(&self, packet: PacketBuffer) {
+        if let BufferData::Owned(buffer) = packet.buffer.buffer.into_inner() {
+            self.give(buffer);
+        }
+    }
+
+    /// Returns the amount of buffers that are currently in the pool.
+    pub fn pooled(&self) -> usize {
+        self.shards
+            .iter()
+            .map(|shard| shard.lock().unwrap().len())
+            .sum()
+    }
+
+    /// Returns the shard of the current thread.
+    fn shard(&self) -> &Mutex<Vec<Vec<u8>>> {
+        let index = self.hasher.hash_one(thread::current().id()) as usize % SHARDS;
+        &self.shards[index]
+    }
+}
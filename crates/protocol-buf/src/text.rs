@@ -0,0 +1,417 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nbt::{NbtCompound, NbtTag},
+    ToNetwork,
+};
+
+/// Represents a Minecraft text component, used for chat messages, disconnect reasons, the MOTD and more.
+///
+/// Components are serialized as JSON for the status response and as NBT for packets since Minecraft 1.20.3.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::text::{ClickEvent, Component};
+///
+/// let component = Component::text("Hello ")
+///     .color("gold")
+///     .append(Component::text("World").bold(true))
+///     .click(ClickEvent::open_url("https://minecraft.net"));
+///
+/// assert_eq!(
+///     component.to_json(),
+///     r#"{"text":"Hello ","color":"gold","clickEvent":{"action":"open_url","value":"https://minecraft.net"},"extra":[{"text":"World","bold":true}]}"#
+/// );
+/// assert_eq!(Component::from_json(&component.to_json()).unwrap(), component);
+/// ```
+///
+/// # Fields
+/// - `content` - The content of the component.
+/// - `color` - The color, either a named color like `red` or a hex color like `#FF0000`.
+/// - `bold` - Whether the text is bold.
+/// - `italic` - Whether the text is italic.
+/// - `underlined` - Whether the text is underlined.
+/// - `strikethrough` - Whether the text is struck through.
+/// - `obfuscated` - Whether the text is obfuscated.
+/// - `insertion` - The text inserted into the chat when the component is shift-clicked.
+/// - `click_event` - The action executed when the component is clicked.
+/// - `hover_event` - The action executed when the component is hovered.
+/// - `extra` - The child components, which inherit the style of this component.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Component {
+    #[serde(flatten)]
+    pub content: ComponentContent,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bold: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub italic: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub underlined: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strikethrough: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub obfuscated: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub insertion: Option<String>,
+    #[serde(
+        rename = "clickEvent",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub click_event: Option<ClickEvent>,
+    #[serde(
+        rename = "hoverEvent",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub hover_event: Option<HoverEvent>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra: Vec<Component>,
+}
+
+/// Defines what a `[Component]` displays.
+///
+/// # Variants
+///
+/// - `Text` - Plain text.
+/// - `Translate` - A translation key, which is translated by the client. The arguments are inserted into the translation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ComponentContent {
+    Text {
+        text: String,
+    },
+    Translate {
+        translate: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        with: Vec<Component>,
+    },
+}
+
+/// The action executed when a `[Component]` is clicked.
+///
+/// # Fields
+/// - `action` - The type of the action.
+/// - `value` - The value of the action, like the URL or the command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClickEvent {
+    pub action: ClickAction,
+    pub value: String,
+}
+
+impl ClickEvent {
+    /// Opens the given URL.
+    pub fn open_url(url: impl Into<String>) -> Self {
+        Self {
+            action: ClickAction::OpenUrl,
+            value: url.into(),
+        }
+    }
+
+    /// Runs the given command.
+    pub fn run_command(command: impl Into<String>) -> Self {
+        Self {
+            action: ClickAction::RunCommand,
+            value: command.into(),
+        }
+    }
+
+    /// Puts the given command into the chat box.
+    pub fn suggest_command(command: impl Into<String>) -> Self {
+        Self {
+            action: ClickAction::SuggestCommand,
+            value: command.into(),
+        }
+    }
+
+    /// Copies the given text to the clipboard.
+    pub fn copy_to_clipboard(text: impl Into<String>) -> Self {
+        Self {
+            action: ClickAction::CopyToClipboard,
+            value: text.into(),
+        }
+    }
+}
+
+/// The type of a `[ClickEvent]`.
+///
+/// # Variants
+///
+/// - `OpenUrl` - Opens an URL.
+/// - `RunCommand` - Runs a command.
+/// - `SuggestCommand` - Puts a command into the chat box.
+/// - `ChangePage` - Changes the page of a book.
+/// - `CopyToClipboard` - Copies text to the clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClickAction {
+    OpenUrl,
+    RunCommand,
+    SuggestCommand,
+    ChangePage,
+    CopyToClipboard,
+}
+
+impl ClickAction {
+    /// Returns the name of the action, as it is sent to the client.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::OpenUrl => "open_url",
+            Self::RunCommand => "run_command",
+            Self::SuggestCommand => "suggest_command",
+            Self::ChangePage => "change_page",
+            Self::CopyToClipboard => "copy_to_clipboard",
+        }
+    }
+}
+
+/// The action executed when a `[Component]` is hovered.
+///
+/// # Variants
+///
+/// - `ShowText` - Shows the given component as a tooltip.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", content = "contents", rename_all = "snake_case")]
+pub enum HoverEvent {
+    ShowText(Box<Component>),
+}
+
+impl Component {
+    /// Creates a new `[Component]` with the given plain text.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::new(ComponentContent::Text { text: text.into() })
+    }
+
+    /// Creates a new `[Component]` with the given translation key and arguments.
+    pub fn translate(key: impl Into<String>, with: Vec<Component>) -> Self {
+        Self::new(ComponentContent::Translate {
+            translate: key.into(),
+            with,
+        })
+    }
+
+    /// Creates a new `[Component]` with the given content and no style.
+    pub const fn new(content: ComponentContent) -> Self {
+        Self {
+            content,
+            color: None,
+            bold: None,
+            italic: None,
+            underlined: None,
+            strikethrough: None,
+            obfuscated: None,
+            insertion: None,
+            click_event: None,
+            hover_event: None,
+            extra: Vec::new(),
+        }
+    }
+
+    /// Sets the color of the component.
+    pub fn color(mut self, color: impl Into<String>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Sets whether the component is bold.
+    pub fn bold(mut self, bold: bool) -> Self {
+        self.bold = Some(bold);
+        self
+    }
+
+    /// Sets whether the component is italic.
+    pub fn italic(mut self, italic: bool) -> Self {
+        self.italic = Some(italic);
+        self
+    }
+
+    /// Sets whether the component is underlined.
+    pub fn underlined(mut self, underlined: bool) -> Self {
+        self.underlined = Some(underlined);
+        self
+    }
+
+    /// Sets whether the component is struck through.
+    pub fn strikethrough(mut self, strikethrough: bool) -> Self {
+        self.strikethrough = Some(strikethrough);
+        self
+    }
+
+    /// Sets whether the component is obfuscated.
+    pub fn obfuscated(mut self, obfuscated: bool) -> Self {
+        self.obfuscated = Some(obfuscated);
+        self
+    }
+
+    /// Sets the text inserted into the chat when the component is shift-clicked.
+    pub fn insertion(mut self, insertion: impl Into<String>) -> Self {
+        self.insertion = Some(insertion.into());
+        self
+    }
+
+    /// Sets the action executed when the component is clicked.
+    pub fn click(mut self, click_event: ClickEvent) -> Self {
+        self.click_event = Some(click_event);
+        self
+    }
+
+    /// Shows the given component as a tooltip when the component is hovered.
+    pub fn hover_text(mut self, text: Component) -> Self {
+        self.hover_event = Some(HoverEvent::ShowText(Box::new(text)));
+        self
+    }
+
+    /// Adds a child component.
+    pub fn append(mut self, component: Component) -> Self {
+        self.extra.push(component);
+        self
+    }
+
+    /// Returns the text of the component and all its children, without any style.
+    ///
+    /// Translated components can't be translated on the server, so their translation key is used instead.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_buf::text::Component;
+    ///
+    /// let component = Component::text("Hello ").bold(true).append(Component::text("World"));
+    ///
+    /// assert_eq!(component.to_plain_text(), "Hello World");
+    /// ```
+    pub fn to_plain_text(&self) -> String {
+        let mut text = match &self.content {
+            ComponentContent::Text { text } => text.clone(),
+            ComponentContent::Translate { translate, .. } => translate.clone(),
+        };
+
+        for child in &self.extra {
+            text.push_str(&child.to_plain_text());
+        }
+        text
+    }
+
+    /// Serializes the component as JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Components are always valid JSON")
+    }
+
+    /// Deserializes a component from JSON.
+    ///
+    /// # Returns
+    /// The component, an error if the JSON is not a valid component.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Converts the component into NBT, the way it is sent in packets since Minecraft 1.20.3.
+    ///
+    /// A component that only contains plain text is converted into a string tag.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_buf::{nbt::NbtTag, text::Component};
+    ///
+    /// assert_eq!(Component::text("Hello").to_nbt(), NbtTag::String("Hello".to_string()));
+    /// ```
+    pub fn to_nbt(&self) -> NbtTag {
+        if let ComponentContent::Text { text } = &self.content {
+            if *self == Component::text(text.clone()) {
+                return NbtTag::String(text.clone());
+            }
+        }
+
+        let mut compound = NbtCompound::new();
+        match &self.content {
+            ComponentContent::Text { text } => compound.insert("text", text.as_str()),
+            ComponentContent::Translate { translate, with } => {
+                compound.insert("translate", translate.as_str());
+                if !with.is_empty() {
+                    compound.insert("with", component_list(with));
+                }
+            }
+        }
+
+        if let Some(color) = &self.color {
+            compound.insert("color", color.as_str());
+        }
+
+        let styles = [
+            ("bold", self.bold),
+            ("italic", self.italic),
+            ("underlined", self.underlined),
+            ("strikethrough", self.strikethrough),
+            ("obfuscated", self.obfuscated),
+        ];
+        for (name, value) in styles {
+            if let Some(value) = value {
+                compound.insert(name, value);
+            }
+        }
+
+        if let Some(insertion) = &self.insertion {
+            compound.insert("insertion", insertion.as_str());
+        }
+
+        if let Some(click_event) = &self.click_event {
+            compound.insert(
+                "clickEvent",
+                NbtCompound::new()
+                    .with("action", click_event.action.name())
+                    .with("value", click_event.value.as_str()),
+            );
+        }
+
+        if let Some(HoverEvent::ShowText(text)) = &self.hover_event {
+            compound.insert(
+                "hoverEvent",
+                NbtCompound::new()
+                    .with("action", "show_text")
+                    .with("contents", text.to_nbt_compound()),
+            );
+        }
+
+        if !self.extra.is_empty() {
+            compound.insert("extra", component_list(&self.extra));
+        }
+
+        NbtTag::Compound(compound)
+    }
+
+    /// Converts the component into a NBT compound, even if it only contains plain text.
+    fn to_nbt_compound(&self) -> NbtCompound {
+        match self.to_nbt() {
+            NbtTag::Compound(compound) => compound,
+            tag => NbtCompound::new().with("text", tag),
+        }
+    }
+}
+
+/// Converts the components into a NBT list. All entries of a NBT list have to be compounds.
+fn component_list(components: &[Component]) -> Vec<NbtTag> {
+    components
+        .iter()
+        .map(|component| NbtTag::Compound(component.to_nbt_compound()))
+        .collect()
+}
+
+impl From<&str> for Component {
+    fn from(text: &str) -> Self {
+        Self::text(text)
+    }
+}
+
+impl From<String> for Component {
+    fn from(text: String) -> Self {
+        Self::text(text)
+    }
+}
+
+/// Writes the component as network NBT.
+impl ToNetwork for Component {
+    fn to_network(&self) -> Vec<u8> {
+        self.to_nbt().to_network()
+    }
+}
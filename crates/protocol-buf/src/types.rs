@@ -1,9 +1,11 @@
 use std::{
+    fmt,
     io::{Cursor, Read},
     ops::Deref,
 };
 
 use crate::{
+    error::{ProtocolError, ProtocolResult},
     handle_primitive_read, handle_primitive_type, register_varnum, FromNetwork, ToNetwork,
 };
 
@@ -14,8 +16,8 @@ impl ToNetwork for bool {
 }
 
 impl FromNetwork for bool {
-    fn from_network(buffer: &mut Cursor<Vec<u8>>) -> Self {
-        u8::from_network(buffer) != 0
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        Ok(u8::from_network(buffer)? != 0)
     }
 }
 
@@ -26,8 +28,8 @@ impl ToNetwork for u8 {
 }
 
 impl FromNetwork for u8 {
-    fn from_network(buffer: &mut Cursor<Vec<u8>>) -> Self {
-        buffer.get_ref()[buffer.position() as usize]
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        handle_primitive_read!(buffer, u8, 1)
     }
 }
 
@@ -42,43 +44,648 @@ impl ToNetwork for String {
     }
 }
 
+/// Reads a string with up to `[MAX_STRING_LENGTH]` characters, use a `[BoundedString]` for strings with a lower limit.
 impl FromNetwork for String {
-    fn from_network(buffer: &mut Cursor<Vec<u8>>) -> Self {
-        let length = *VarInt::from_network(buffer) as usize;
-        let bytes = &buffer.get_ref()[buffer.position() as usize..];
-        let string = String::from_utf8(bytes[..length].to_vec()).unwrap();
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        read_string(buffer, MAX_STRING_LENGTH)
+    }
+}
+
+/// The maximum amount of characters of a string, if the protocol doesn't define a lower limit.
+pub const MAX_STRING_LENGTH: usize = 32767;
+
+/// A string with at most `MAX` characters, like the server address of a handshake.
+///
+/// The length is checked before the string is read, so a client can't make the server allocate more than the limit.
+/// Characters are counted the way the vanilla server counts them, in UTF-16 code units.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{error::ProtocolError, types::BoundedString, FromNetwork, ToNetwork};
+///
+/// let name = BoundedString::<16>::new("Notch").unwrap();
+/// assert_eq!(name.to_network(), vec![0x05, b'N', b'o', b't', b'c', b'h']);
+/// assert!(BoundedString::<4>::new("Notch").is_err());
+///
+/// let mut buffer = Cursor::new(name.to_network());
+/// assert_eq!(BoundedString::<16>::from_network(&mut buffer).unwrap(), name);
+///
+/// // The declared length is rejected before anything is read.
+/// let mut buffer = Cursor::new(vec![0xFF, 0xFF, 0x03]);
+/// assert!(matches!(BoundedString::<255>::from_network(&mut buffer), Err(ProtocolError::InvalidData(_))));
+/// assert_eq!(buffer.position(), 3);
+///
+/// // Characters that take multiple bytes are counted once.
+/// let mut buffer = Cursor::new("äöü".to_string().to_network());
+/// assert_eq!(BoundedString::<3>::from_network(&mut buffer).unwrap().as_str(), "äöü");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BoundedString<const MAX: usize>(String);
+
+impl<const MAX: usize> BoundedString<MAX> {
+    /// Creates a new `[BoundedString]`.
+    ///
+    /// # Returns
+    /// An error if the string has more than `MAX` characters.
+    pub fn new(value: impl Into<String>) -> ProtocolResult<Self> {
+        let value = value.into();
+        check_string_length(&value, MAX)?;
+        Ok(Self(value))
+    }
 
-        buffer.set_position(buffer.position() + length as u64);
-        string
+    /// Returns the string.
+    pub fn into_inner(self) -> String {
+        self.0
     }
 }
 
-handle_primitive_type!(u16, 2);
-handle_primitive_type!(u32, 4);
-handle_primitive_type!(u64, 8);
-handle_primitive_type!(f32, 4);
-handle_primitive_type!(f64, 8);
+impl<const MAX: usize> Deref for BoundedString<MAX> {
+    type Target = String;
 
-register_varnum!(VarInt, i32, u32, 5);
-register_varnum!(VarLong, i64, u64, 10);
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const MAX: usize> fmt::Display for BoundedString<MAX> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<const MAX: usize> ToNetwork for BoundedString<MAX> {
+    fn to_network(&self) -> Vec<u8> {
+        self.0.to_network()
+    }
+}
+
+impl<const MAX: usize> FromNetwork for BoundedString<MAX> {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        read_string(buffer, MAX).map(Self)
+    }
+}
+
+/// Reads a string with up to `max_length` characters.
+///
+/// A character takes up to three bytes, so the length prefix is checked against three times the limit
+/// before the string is read. The characters are counted once the string is decoded.
+fn read_string<B: AsRef<[u8]>>(
+    buffer: &mut Cursor<B>,
+    max_length: usize,
+) -> ProtocolResult<String> {
+    let length = usize::try_from(*VarInt::from_network(buffer)?)
+        .map_err(|_| ProtocolError::InvalidData("negative string length".to_string()))?;
+    if length > max_length * 3 {
+        return Err(ProtocolError::InvalidData(format!(
+            "string has {length} bytes, but only {max_length} characters are allowed"
+        )));
+    }
+
+    let bytes = read_bytes(buffer, length)?;
+    let value = String::from_utf8(bytes).map_err(|_| ProtocolError::Utf8Error)?;
+    check_string_length(&value, max_length)?;
+    Ok(value)
+}
+
+/// Checks that the string doesn't have more than `max_length` characters.
+fn check_string_length(value: &str, max_length: usize) -> ProtocolResult<()> {
+    // A string never has more UTF-16 code units than bytes, so short strings don't have to be counted.
+    if value.len() <= max_length {
+        return Ok(());
+    }
+
+    let length = value.encode_utf16().count();
+    if length > max_length {
+        return Err(ProtocolError::InvalidData(format!(
+            "string has {length} characters, but only {max_length} are allowed"
+        )));
+    }
+    Ok(())
+}
+
+/// Writes a prefixed array. The length of the array is written as a `[VarInt]` before the values.
+impl<T: ToNetwork> ToNetwork for Vec<T> {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = VarInt::from(self.len() as i32).to_network();
+        for value in self {
+            bytes.extend_from_slice(&value.to_network());
+        }
+        bytes
+    }
+}
+
+/// Reads a prefixed array. The length is checked against the remaining bytes before anything is allocated.
+impl<T: FromNetwork> FromNetwork for Vec<T> {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        let length = usize::try_from(*VarInt::from_network(buffer)?)
+            .map_err(|_| ProtocolError::InvalidData("negative array length".to_string()))?;
+        if length > remaining(buffer) {
+            return Err(ProtocolError::InsufficientData);
+        }
+
+        (0..length).map(|_| T::from_network(buffer)).collect()
+    }
+}
+
+/// Returns the amount of bytes that weren't read yet.
+pub(crate) fn remaining<B: AsRef<[u8]>>(buffer: &Cursor<B>) -> usize {
+    buffer
+        .get_ref()
+        .as_ref()
+        .len()
+        .saturating_sub(buffer.position() as usize)
+}
+
+/// Reads the given amount of bytes and advances the buffer.
+///
+/// Nothing is read if there are not enough bytes left.
+pub(crate) fn read_bytes<B: AsRef<[u8]>>(
+    buffer: &mut Cursor<B>,
+    length: usize,
+) -> ProtocolResult<Vec<u8>> {
+    if length > remaining(buffer) {
+        return Err(ProtocolError::InsufficientData);
+    }
+
+    let mut bytes = vec![0; length];
+    buffer
+        .read_exact(&mut bytes)
+        .map_err(|_| ProtocolError::InsufficientData)?;
+    Ok(bytes)
+}
+
+/// Reads a fixed amount of bytes and advances the buffer, this is used for all primitive types.
+///
+/// Nothing is read if there are not enough bytes left.
+pub(crate) fn read_array<const N: usize, B: AsRef<[u8]>>(
+    buffer: &mut Cursor<B>,
+) -> ProtocolResult<[u8; N]> {
+    if N > remaining(buffer) {
+        return Err(ProtocolError::InsufficientData);
+    }
+
+    let mut bytes = [0; N];
+    buffer
+        .read_exact(&mut bytes)
+        .map_err(|_| ProtocolError::InsufficientData)?;
+    Ok(bytes)
+}
+
+/// An optional value that is prefixed with a boolean, which says whether the value is present.
+///
+/// This is the most common optional in the protocol, like the death location of a player.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{types::PrefixedOptional, FromNetwork, ToNetwork};
+///
+/// let present = PrefixedOptional::from(Some(5_i32));
+/// assert_eq!(present.to_network(), vec![0x01, 0x00, 0x00, 0x00, 0x05]);
+/// assert_eq!(PrefixedOptional::<i32>::from(None).to_network(), vec![0x00]);
+///
+/// let mut buffer = Cursor::new(present.to_network());
+/// assert_eq!(PrefixedOptional::<i32>::from_network(&mut buffer).unwrap(), present);
+///
+/// let mut buffer = Cursor::new(vec![0x00, 0x07]);
+/// assert_eq!(*PrefixedOptional::<u8>::from_network(&mut buffer).unwrap(), None);
+/// assert_eq!(buffer.position(), 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PrefixedOptional<T>(pub Option<T>);
+
+impl<T> Deref for PrefixedOptional<T> {
+    type Target = Option<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> From<Option<T>> for PrefixedOptional<T> {
+    fn from(value: Option<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: ToNetwork> ToNetwork for PrefixedOptional<T> {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = self.0.is_some().to_network();
+        if let Some(value) = &self.0 {
+            bytes.extend_from_slice(&value.to_network());
+        }
+        bytes
+    }
+}
+
+impl<T: FromNetwork> FromNetwork for PrefixedOptional<T> {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        if bool::from_network(buffer)? {
+            Ok(Self(Some(T::from_network(buffer)?)))
+        } else {
+            Ok(Self(None))
+        }
+    }
+}
+
+/// An optional value at the end of a packet, which is present if there is data left.
+///
+/// This can only be used as the last field of a packet, nothing is written if the value is missing.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{types::TrailingOptional, FromNetwork, ToNetwork};
+///
+/// let present = TrailingOptional::from(Some(5_i32));
+/// assert_eq!(present.to_network(), vec![0x00, 0x00, 0x00, 0x05]);
+/// assert!(TrailingOptional::<i32>::from(None).to_network().is_empty());
+///
+/// let mut buffer = Cursor::new(present.to_network());
+/// assert_eq!(TrailingOptional::<i32>::from_network(&mut buffer).unwrap(), present);
+/// assert_eq!(*TrailingOptional::<i32>::from_network(&mut buffer).unwrap(), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TrailingOptional<T>(pub Option<T>);
+
+impl<T> Deref for TrailingOptional<T> {
+    type Target = Option<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> From<Option<T>> for TrailingOptional<T> {
+    fn from(value: Option<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: ToNetwork> ToNetwork for TrailingOptional<T> {
+    fn to_network(&self) -> Vec<u8> {
+        self.0
+            .as_ref()
+            .map(ToNetwork::to_network)
+            .unwrap_or_default()
+    }
+}
+
+impl<T: FromNetwork> FromNetwork for TrailingOptional<T> {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        if remaining(buffer) == 0 {
+            Ok(Self(None))
+        } else {
+            Ok(Self(Some(T::from_network(buffer)?)))
+        }
+    }
+}
+
+/// The raw bytes at the end of a packet, without a length prefix.
+///
+/// This can only be used as the last field of a packet, like the data of a plugin message.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{types::RemainingBytes, FromNetwork, ToNetwork};
+///
+/// let data = RemainingBytes::from(vec![1, 2, 3]);
+/// assert_eq!(data.to_network(), vec![1, 2, 3]);
+///
+/// let mut buffer = Cursor::new(vec![1, 2, 3]);
+/// assert_eq!(RemainingBytes::from_network(&mut buffer).unwrap(), data);
+/// assert!(RemainingBytes::from_network(&mut buffer).unwrap().is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct RemainingBytes(pub Vec<u8>);
+
+impl Deref for RemainingBytes {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
 
-pub(crate) fn encode_varint(mut value: i32) -> Vec<u8> {
-    let mut bytes = Vec::new();
+impl From<Vec<u8>> for RemainingBytes {
+    fn from(value: Vec<u8>) -> Self {
+        Self(value)
+    }
+}
+
+impl ToNetwork for RemainingBytes {
+    fn to_network(&self) -> Vec<u8> {
+        self.0.clone()
+    }
+}
+
+impl FromNetwork for RemainingBytes {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        let length = remaining(buffer);
+        Ok(Self(read_bytes(buffer, length)?))
+    }
+}
+
+/// A set of bits of any length, written as a prefixed array of longs like `java.util.BitSet`.
+///
+/// Bit `n` is bit `n % 64` of the long `n / 64`. Longs at the end without any set bits are never written,
+/// the same way the vanilla server writes bit sets, so reading and writing a bit set keeps its bytes.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{types::BitSet, FromNetwork, ToNetwork};
+///
+/// let mut bits = BitSet::new();
+/// bits.set(0);
+/// bits.set(65);
+/// assert!(bits.get(65));
+/// assert!(!bits.get(64));
+///
+/// assert_eq!(
+///     bits.to_network(),
+///     vec![0x02, 0, 0, 0, 0, 0, 0, 0, 0x01, 0, 0, 0, 0, 0, 0, 0, 0x02],
+/// );
+///
+/// bits.clear(65);
+/// assert_eq!(bits.to_network(), vec![0x01, 0, 0, 0, 0, 0, 0, 0, 0x01]);
+///
+/// bits.clear(0);
+/// assert!(bits.is_empty());
+/// assert_eq!(bits.to_network(), vec![0x00]);
+///
+/// let mut buffer = Cursor::new(vec![0x01, 0, 0, 0, 0, 0, 0, 0, 0x05]);
+/// let bits = BitSet::from_network(&mut buffer).unwrap();
+/// assert_eq!(bits.ones().collect::<Vec<_>>(), vec![0, 2]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BitSet(Vec<i64>);
+
+impl BitSet {
+    /// Creates a new `[BitSet]` without any set bits.
+    pub const fn new() -> Self {
+        Self(Vec::new())
+    }
 
-    loop {
-        let mut byte = (value & 0b01111111) as u8;
-        value >>= 7;
+    /// Returns whether the bit at the given index is set.
+    pub fn get(&self, index: usize) -> bool {
+        self.0
+            .get(index / 64)
+            .is_some_and(|long| long & (1 << (index % 64)) != 0)
+    }
+
+    /// Sets the bit at the given index.
+    pub fn set(&mut self, index: usize) {
+        let long = index / 64;
+        if long >= self.0.len() {
+            self.0.resize(long + 1, 0);
+        }
+        self.0[long] |= 1 << (index % 64);
+    }
 
-        if value != 0 {
-            byte |= 0b10000000;
+    /// Clears the bit at the given index.
+    pub fn clear(&mut self, index: usize) {
+        if let Some(long) = self.0.get_mut(index / 64) {
+            *long &= !(1 << (index % 64));
+            self.trim();
         }
+    }
+
+    /// Returns whether no bit is set.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the indices of the set bits, from the lowest to the highest.
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.0.len() * 64).filter(|&index| self.get(index))
+    }
 
-        bytes.push(byte);
+    /// Returns the longs the bits are stored in, without the longs at the end that don't have any set bits.
+    pub fn as_longs(&self) -> &[i64] {
+        &self.0
+    }
 
-        if value == 0 {
-            break;
+    /// Removes the longs at the end that don't have any set bits.
+    fn trim(&mut self) {
+        while self.0.last() == Some(&0) {
+            self.0.pop();
         }
     }
+}
+
+impl From<Vec<i64>> for BitSet {
+    fn from(longs: Vec<i64>) -> Self {
+        let mut bits = Self(longs);
+        bits.trim();
+        bits
+    }
+}
+
+impl ToNetwork for BitSet {
+    fn to_network(&self) -> Vec<u8> {
+        self.0.to_network()
+    }
+}
+
+impl FromNetwork for BitSet {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        Ok(Self::from(Vec::<i64>::from_network(buffer)?))
+    }
+}
+
+/// An angle in 1/256 of a turn, like the rotation of an entity.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{types::Angle, FromNetwork, ToNetwork};
+///
+/// assert_eq!(Angle::from_degrees(90.0), Angle(64));
+/// assert_eq!(Angle::from_degrees(-90.0), Angle(192));
+/// assert_eq!(Angle(128).degrees(), 180.0);
+///
+/// let mut buffer = Cursor::new(Angle(32).to_network());
+/// assert_eq!(Angle::from_network(&mut buffer).unwrap(), Angle(32));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Angle(pub u8);
+
+impl Angle {
+    /// Converts an angle in degrees, angles outside of a single turn wrap around like on the vanilla server.
+    pub fn from_degrees(degrees: f32) -> Self {
+        Self((degrees * 256.0 / 360.0).floor() as i32 as u8)
+    }
+
+    /// Returns the angle in degrees, from `0` to less than `360`.
+    pub fn degrees(self) -> f32 {
+        self.0 as f32 * 360.0 / 256.0
+    }
+}
+
+impl ToNetwork for Angle {
+    fn to_network(&self) -> Vec<u8> {
+        vec![self.0]
+    }
+}
+
+impl FromNetwork for Angle {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        Ok(Self(u8::from_network(buffer)?))
+    }
+}
+
+/// A fixed-point number sent as a short, which has `SCALE` steps per unit.
+///
+/// Values outside of the range of a short are clamped.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::{types::{PositionDelta, Velocity}, ToNetwork};
+///
+/// let velocity = Velocity::from_f64(0.5);
+/// assert_eq!(velocity, Velocity::new(4000));
+/// assert_eq!(velocity.to_network(), vec![0x0F, 0xA0]);
+///
+/// assert_eq!(PositionDelta::from_f64(-1.0).to_f64(), -1.0);
+/// assert_eq!(Velocity::from_f64(100.0), Velocity::new(i16::MAX));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct FixedPoint<const SCALE: u32>(i16);
+
+/// A velocity in 1/8000 of a block per tick, like the velocity of a spawned entity.
+pub type Velocity = FixedPoint<8000>;
+
+/// A change of a position in 1/4096 of a block, like the movement of an entity since the last update.
+pub type PositionDelta = FixedPoint<4096>;
+
+impl<const SCALE: u32> FixedPoint<SCALE> {
+    /// Creates a new `[FixedPoint]` from its raw value.
+    pub const fn new(raw: i16) -> Self {
+        Self(raw)
+    }
+
+    /// Converts a number, the fractional steps that can't be represented are dropped.
+    pub fn from_f64(value: f64) -> Self {
+        Self((value * SCALE as f64) as i16)
+    }
+
+    /// Returns the number.
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / SCALE as f64
+    }
+
+    /// Returns the raw value that is sent to the client.
+    pub const fn raw(self) -> i16 {
+        self.0
+    }
+}
+
+impl<const SCALE: u32> ToNetwork for FixedPoint<SCALE> {
+    fn to_network(&self) -> Vec<u8> {
+        self.0.to_network()
+    }
+}
+
+impl<const SCALE: u32> FromNetwork for FixedPoint<SCALE> {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        Ok(Self(i16::from_network(buffer)?))
+    }
+}
 
-    bytes
+/// A block position, packed into a single long.
+///
+/// The X and Z positions use 26 bits and the Y position uses 12 bits.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{types::Position, FromNetwork, ToNetwork};
+///
+/// let position = Position::new(-10, -64, 300);
+/// let mut buffer = Cursor::new(position.to_network());
+///
+/// assert_eq!(Position::from_network(&mut buffer).unwrap(), position);
+/// ```
+///
+/// # Fields
+/// - `x` - The X position.
+/// - `y` - The Y position.
+/// - `z` - The Z position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Position {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
 }
+
+impl Position {
+    /// Creates a new `[Position]`.
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl ToNetwork for Position {
+    fn to_network(&self) -> Vec<u8> {
+        let packed = ((self.x as i64 & 0x3FFFFFF) << 38)
+            | ((self.z as i64 & 0x3FFFFFF) << 12)
+            | (self.y as i64 & 0xFFF);
+        packed.to_network()
+    }
+}
+
+impl FromNetwork for Position {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        let packed = i64::from_network(buffer)?;
+        Ok(Self {
+            x: (packed >> 38) as i32,
+            y: (packed << 52 >> 52) as i32,
+            z: (packed << 26 >> 38) as i32,
+        })
+    }
+}
+
+/// A block position inside a dimension, like the location a player died at.
+///
+/// # Fields
+/// - `dimension` - The name of the dimension, like `minecraft:overworld`.
+/// - `position` - The position inside the dimension.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GlobalPosition {
+    pub dimension: String,
+    pub position: Position,
+}
+
+impl ToNetwork for GlobalPosition {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = self.dimension.to_network();
+        bytes.extend_from_slice(&self.position.to_network());
+        bytes
+    }
+}
+
+impl FromNetwork for GlobalPosition {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        Ok(Self {
+            dimension: String::from_network(buffer)?,
+            position: Position::from_network(buffer)?,
+        })
+    }
+}
+
+handle_primitive_type!(i8, 1);
+handle_primitive_type!(i16, 2);
+handle_primitive_type!(i32, 4);
+handle_primitive_type!(i64, 8);
+handle_primitive_type!(u16, 2);
+handle_primitive_type!(u32, 4);
+handle_primitive_type!(u64, 8);
+handle_primitive_type!(u128, 16);
+handle_primitive_type!(f32, 4);
+handle_primitive_type!(f64, 8);
+
+register_varnum!(VarInt, i32, u32, 5);
+register_varnum!(VarLong, i64, u64, 10);
@@ -0,0 +1,189 @@
+//! Property tests for the decoder.
+//!
+//! Every value that is written has to be read back as the same value, and reading arbitrary bytes has to return
+//! an error instead of panicking, since the bytes come straight from the clients.
+
+use std::io::Cursor;
+
+use proptest::{collection::vec, prelude::*};
+use protocol_buf::{
+    buffer::{Buffer, NormalBuffer, PacketBuffer},
+    compression::{CompressionData, CompressionHint, CompressionType, Inflater},
+    nbt::{Nbt, NbtCompound, NbtTag},
+    types::{Position, VarInt, VarLong},
+    FromNetwork, ToNetwork,
+};
+
+/// Generates NBT tags, including nested lists and compounds.
+fn nbt_tag() -> impl Strategy<Value = NbtTag> {
+    let leaf = prop_oneof![
+        any::<i8>().prop_map(NbtTag::Byte),
+        any::<i16>().prop_map(NbtTag::Short),
+        any::<i32>().prop_map(NbtTag::Int),
+        any::<i64>().prop_map(NbtTag::Long),
+        prop::num::f32::NORMAL.prop_map(NbtTag::Float),
+        prop::num::f64::NORMAL.prop_map(NbtTag::Double),
+        vec(any::<i8>(), 0..16).prop_map(NbtTag::ByteArray),
+        ".{0,16}".prop_map(NbtTag::String),
+        vec(any::<i32>(), 0..16).prop_map(NbtTag::IntArray),
+        vec(any::<i64>(), 0..16).prop_map(NbtTag::LongArray),
+    ];
+
+    leaf.prop_recursive(4, 64, 8, |inner| {
+        prop_oneof![
+            // Every element of a list has the same type.
+            vec(any::<i32>(), 0..8)
+                .prop_map(|values| NbtTag::List(values.into_iter().map(NbtTag::Int).collect())),
+            vec((".{0,8}", inner), 0..8).prop_map(|entries| {
+                let mut compound = NbtCompound::new();
+                for (name, tag) in entries {
+                    compound.insert(name, tag);
+                }
+                NbtTag::Compound(compound)
+            }),
+        ]
+    })
+}
+
+/// Reads a value from the bytes, the result is ignored since only panics fail the test.
+fn read<T: FromNetwork>(bytes: &[u8]) {
+    let _ = T::from_network(&mut Cursor::new(bytes));
+}
+
+proptest! {
+    #[test]
+    fn varint_round_trips(value in any::<i32>()) {
+        let bytes = VarInt::from(value).to_network();
+        prop_assert_eq!(bytes.len(), VarInt::from(value).len());
+        prop_assert_eq!(*VarInt::from_network(&mut Cursor::new(bytes)).unwrap(), value);
+    }
+
+    #[test]
+    fn varlong_round_trips(value in any::<i64>()) {
+        let bytes = VarLong::from(value).to_network();
+        prop_assert_eq!(*VarLong::from_network(&mut Cursor::new(bytes)).unwrap(), value);
+    }
+
+    #[test]
+    fn string_round_trips(value in ".{0,256}") {
+        let bytes = value.to_network();
+        prop_assert_eq!(String::from_network(&mut Cursor::new(bytes)).unwrap(), value);
+    }
+
+    #[test]
+    fn position_round_trips(
+        x in -(1 << 25)..(1 << 25),
+        y in -2048..2048,
+        z in -(1 << 25)..(1 << 25),
+    ) {
+        let position = Position::new(x, y, z);
+        let bytes = position.to_network();
+        prop_assert_eq!(Position::from_network(&mut Cursor::new(bytes)).unwrap(), position);
+    }
+
+    #[test]
+    fn nbt_round_trips(tag in nbt_tag()) {
+        let bytes = tag.to_network();
+        prop_assert_eq!(NbtTag::from_network(&mut Cursor::new(bytes)).unwrap(), tag.clone());
+
+        if let NbtTag::Compound(compound) = tag {
+            let nbt = Nbt::new(compound);
+            let bytes = nbt.to_network();
+            prop_assert_eq!(Nbt::from_network(&mut Cursor::new(bytes)).unwrap(), nbt);
+        }
+    }
+
+    #[test]
+    fn arbitrary_bytes_dont_panic(bytes in vec(any::<u8>(), 0..512)) {
+        read::<VarInt>(&bytes);
+        read::<VarLong>(&bytes);
+        read::<String>(&bytes);
+        read::<Position>(&bytes);
+        read::<NbtTag>(&bytes);
+        read::<Nbt>(&bytes);
+        read::<Vec<String>>(&bytes);
+    }
+
+    #[test]
+    fn mutated_nbt_doesnt_panic(
+        tag in nbt_tag(),
+        mutations in vec((any::<prop::sample::Index>(), any::<u8>()), 1..4),
+        truncate in any::<prop::sample::Index>(),
+    ) {
+        let mut bytes = Nbt::new(NbtCompound::new().with("tag", tag)).to_network();
+        for (index, byte) in mutations {
+            let index = index.index(bytes.len());
+            bytes[index] = byte;
+        }
+        read::<Nbt>(&bytes);
+
+        bytes.truncate(truncate.index(bytes.len()));
+        read::<Nbt>(&bytes);
+    }
+
+    #[test]
+    fn arbitrary_frames_dont_panic(bytes in vec(any::<u8>(), 0..512), threshold in -1..512) {
+        for compression_type in [CompressionType::None, CompressionType::Zlib] {
+            let compression = CompressionData::new(threshold, compression_type);
+            let _ = PacketBuffer::new(bytes.clone(), &compression);
+        }
+    }
+
+    #[test]
+    fn mutated_frames_dont_panic(
+        data in vec(any::<u8>(), 0..1024),
+        mutations in vec((any::<prop::sample::Index>(), any::<u8>()), 1..4),
+    ) {
+        let compression = CompressionData::new(64, CompressionType::Zlib);
+        let packet = PacketBuffer::with_id(VarInt::from(0x01), NormalBuffer::new(data));
+        let mut bytes = compression.to_buffer(packet, &compression).unwrap();
+        for (index, byte) in mutations {
+            let index = index.index(bytes.len());
+            bytes[index] = byte;
+        }
+
+        let _ = PacketBuffer::new(bytes, &compression);
+    }
+
+    #[test]
+    fn padded_frame_lengths_are_read(data in vec(any::<u8>(), 0..1024), threshold in -1..512) {
+        let compression = CompressionData::new(threshold, CompressionType::Zlib);
+        let packet = PacketBuffer::with_id(VarInt::from(0x01), NormalBuffer::new(data.clone()));
+        let bytes = compression.to_buffer(packet, &compression).unwrap();
+
+        // Pads the length to 3 bytes, the way Velocity writes it.
+        let mut cursor = Cursor::new(&bytes);
+        let length = *VarInt::from_network(&mut cursor).unwrap() as u32;
+        let mut padded = vec![(length & 0x7F) as u8 | 0x80, ((length >> 7) & 0x7F) as u8 | 0x80, (length >> 14) as u8];
+        padded.extend_from_slice(&bytes[cursor.position() as usize..]);
+
+        prop_assert_eq!(PacketBuffer::frame_size(&padded).unwrap(), Some(padded.len()));
+        let packet = PacketBuffer::new(padded, &compression).unwrap();
+        prop_assert_eq!(*packet.packet_id, 0x01);
+        prop_assert_eq!(&packet.buffer.get_ref()[packet.buffer.position() as usize..], &data[..]);
+    }
+
+    #[test]
+    fn precompressed_packets_are_read(
+        data in vec(any::<u8>(), 0..512),
+        precompress_threshold in 0..512,
+        threshold in 0..512,
+    ) {
+        let packet = PacketBuffer::with_id(VarInt::from(0x01), NormalBuffer::new(data.clone()));
+        let cached = CompressionData::new(precompress_threshold, CompressionType::Zlib)
+            .precompress(packet)
+            .unwrap();
+
+        // The connection may use another threshold than the one the packet was precompressed with.
+        for compression_type in [CompressionType::None, CompressionType::Zlib] {
+            let compression = CompressionData::new(threshold, compression_type);
+            let bytes = compression
+                .to_buffer_with_hint(cached.clone(), &compression, CompressionHint::Precompressed)
+                .unwrap();
+
+            let packet = Inflater::new().read_packet(bytes, &compression).unwrap();
+            prop_assert_eq!(*packet.packet_id, 0x01);
+            prop_assert_eq!(&packet.buffer.get_ref()[packet.buffer.position() as usize..], &data[..]);
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use std::{
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use uuid::Uuid;
+
+/// Something a player did that is written to the `[AuditLog]`.
+///
+/// # Variants
+/// - `Join` - The player joined the game.
+/// - `Quit` - The player left the game.
+/// - `Chat` - The player sent a message in the chat.
+/// - `Command` - The player ran a command, without the leading slash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuditEvent {
+    Join { name: String, uuid: Uuid },
+    Quit { name: String, uuid: Uuid },
+    Chat { name: String, message: String },
+    Command { name: String, command: String },
+}
+
+/// Writes the event like the vanilla server logs it.
+impl fmt::Display for AuditEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Join { name, uuid } => write!(f, "{name} ({uuid}) joined the game"),
+            Self::Quit { name, uuid } => write!(f, "{name} ({uuid}) left the game"),
+            Self::Chat { name, message } => write!(f, "<{name}> {message}"),
+            Self::Command { name, command } => {
+                write!(f, "{name} issued server command: /{command}")
+            }
+        }
+    }
+}
+
+/// A destination of the `[AuditLog]`, like a file or a moderation tool.
+///
+/// # Examples
+/// ```rust
+/// use std::{io, sync::Mutex, time::SystemTime};
+/// use protocol_core::audit::{AuditEvent, AuditLog, AuditSink};
+///
+/// /// Keeps the last events in memory.
+/// #[derive(Debug, Default)]
+/// struct MemorySink(Mutex<Vec<String>>);
+///
+/// impl AuditSink for MemorySink {
+///     fn record(&self, _timestamp: SystemTime, event: &AuditEvent) -> io::Result<()> {
+///         self.0.lock().unwrap().push(event.to_string());
+///         Ok(())
+///     }
+/// }
+///
+/// let sink = MemorySink::default();
+/// let event = AuditEvent::Chat { name: "Steve".to_string(), message: "Hello!".to_string() };
+/// sink.record(SystemTime::now(), &event).unwrap();
+///
+/// assert_eq!(sink.0.lock().unwrap()[0], "<Steve> Hello!");
+/// ```
+pub trait AuditSink: fmt::Debug + Send + Sync {
+    /// Records an event.
+    ///
+    /// # Parameters
+    /// - `timestamp` - When the event happened.
+    /// - `event` - The event.
+    fn record(&self, timestamp: SystemTime, event: &AuditEvent) -> io::Result<()>;
+}
+
+/// Records the chat messages, commands, joins and quits of the players, so server owners can moderate their server.
+///
+/// Every event is passed to all sinks of the log. Sinks that fail don't stop the other sinks from recording the event.
+///
+/// # Examples
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use protocol_core::audit::{AuditLog, RotatingFileSink};
+///
+/// let log = AuditLog::new().with_sink(
+///     RotatingFileSink::new("logs/audit.log")
+///         .with_max_size(10 * 1024 * 1024)
+///         .with_max_age(Duration::from_secs(24 * 60 * 60)),
+/// );
+/// ```
+///
+/// # Fields
+/// - `sinks` - The destinations of the events.
+#[derive(Debug, Clone, Default)]
+pub struct AuditLog {
+    sinks: Vec<Arc<dyn AuditSink>>,
+}
+
+impl AuditLog {
+    /// Creates a new `[AuditLog]` without any sinks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a sink the events are recorded to.
+    pub fn with_sink(mut self, sink: impl AuditSink + 'static) -> Self {
+        self.sinks.push(Arc::new(sink));
+        self
+    }
+
+    /// Records an event in every sink.
+    pub fn record(&self, event: AuditEvent) {
+        let timestamp = SystemTime::now();
+        for sink in &self.sinks {
+            if let Err(e) = sink.record(timestamp, &event) {
+                println!("Failed to write to audit log {:?}; err = {}", sink, e);
+            }
+        }
+    }
+}
+
+/// Writes the events to a file, which is rotated once it gets too large or too old.
+///
+/// Rotated files are renamed to the date they were rotated at followed by a counter, so `audit.log` becomes
+/// `audit-2024-06-13-1.log`. Without a maximum size or age the file is never rotated.
+///
+/// # Fields
+/// - `path` - The path of the file the events are written to.
+/// - `max_size` - The size in bytes after which the file is rotated.
+/// - `max_age` - The time after which the file is rotated.
+/// - `file` - The opened file, this is opened once the first event is recorded.
+#[derive(Debug)]
+pub struct RotatingFileSink {
+    path: PathBuf,
+    max_size: Option<u64>,
+    max_age: Option<Duration>,
+    file: Mutex<Option<LogFile>>,
+}
+
+/// A file opened by a `[RotatingFileSink]`.
+///
+/// # Fields
+/// - `file` - The file.
+/// - `size` - The size of the file in bytes.
+/// - `opened_at` - When the file was opened.
+#[derive(Debug)]
+struct LogFile {
+    file: File,
+    size: u64,
+    opened_at: SystemTime,
+}
+
+impl RotatingFileSink {
+    /// Creates a new `[RotatingFileSink]`, which appends to the file if it already exists.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            max_size: None,
+            max_age: None,
+            file: Mutex::new(None),
+        }
+    }
+
+    /// Rotates the file once it is larger than the given size in bytes.
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Rotates the file once it has been written to for the given time, like once a day.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Returns the path of the file the events are written to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Opens the file, creating it and its directory if they don't exist.
+    fn open(&self, now: SystemTime) -> io::Result<LogFile> {
+        if let Some(directory) = self
+            .path
+            .parent()
+            .filter(|path| !path.as_os_str().is_empty())
+        {
+            fs::create_dir_all(directory)?;
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        Ok(LogFile {
+            size: file.metadata()?.len(),
+            file,
+            opened_at: now,
+        })
+    }
+
+    /// Returns whether the file has to be rotated before the next event is written.
+    fn needs_rotation(&self, file: &LogFile, now: SystemTime) -> bool {
+        let too_large = self.max_size.is_some_and(|max_size| file.size >= max_size);
+        let too_old = self.max_age.is_some_and(|max_age| {
+            now.duration_since(file.opened_at)
+                .is_ok_and(|age| age >= max_age)
+        });
+        file.size > 0 && (too_large || too_old)
+    }
+
+    /// Renames the file to the first free name for the current date.
+    fn rotate(&self, now: SystemTime) -> io::Result<()> {
+        let stem = self
+            .path
+            .file_stem()
+            .map_or_else(String::new, |stem| stem.to_string_lossy().into_owned());
+        let extension = self.path.extension().map_or_else(String::new, |extension| {
+            format!(".{}", extension.to_string_lossy())
+        });
+        let (date, _) = format_timestamp(now);
+
+        let rotated = (1..)
+            .map(|index| {
+                self.path
+                    .with_file_name(format!("{stem}-{date}-{index}{extension}"))
+            })
+            .find(|path| !path.exists())
+            .expect("there are always free file names");
+        fs::rename(&self.path, rotated)
+    }
+}
+
+impl AuditSink for RotatingFileSink {
+    fn record(&self, timestamp: SystemTime, event: &AuditEvent) -> io::Result<()> {
+        let mut file = self.file.lock().unwrap();
+        if file
+            .as_ref()
+            .is_some_and(|file| self.needs_rotation(file, timestamp))
+        {
+            *file = None;
+            self.rotate(timestamp)?;
+        }
+
+        let file = match &mut *file {
+            Some(file) => file,
+            file => file.insert(self.open(timestamp)?),
+        };
+
+        let (date, time) = format_timestamp(timestamp);
+        let line = format!("[{date} {time}] {event}\n");
+        file.file.write_all(line.as_bytes())?;
+        file.size += line.len() as u64;
+        Ok(())
+    }
+}
+
+/// Formats a timestamp as the date and the time of day in UTC.
+///
+/// # Examples
+/// ```rust
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use protocol_core::audit::format_timestamp;
+///
+/// let timestamp = UNIX_EPOCH + Duration::from_secs(1_718_284_245);
+/// assert_eq!(format_timestamp(timestamp), ("2024-06-13".to_string(), "13:10:45".to_string()));
+/// ```
+pub fn format_timestamp(timestamp: SystemTime) -> (String, String) {
+    let seconds = timestamp
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let (days, seconds) = (seconds / 86_400, seconds % 86_400);
+
+    // Converts the days since the Unix epoch to a date of the proleptic Gregorian calendar.
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    (
+        format!("{year:04}-{month:02}-{day:02}"),
+        format!(
+            "{:02}:{:02}:{:02}",
+            seconds / 3_600,
+            seconds % 3_600 / 60,
+            seconds % 60
+        ),
+    )
+}
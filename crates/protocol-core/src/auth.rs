@@ -0,0 +1,482 @@
+use std::{fmt, future::Future, io, net::IpAddr, pin::Pin, sync::OnceLock};
+
+use md5::{Digest, Md5};
+use protocol_buf::types::PrefixedOptional;
+use protocol_packets::v1_21::login::ProfileProperty;
+use rand::rngs::OsRng;
+use rsa::{pkcs8::EncodePublicKey, Pkcs1v15Encrypt, RsaPrivateKey};
+use serde::Deserialize;
+use serde_json::json;
+use sha1::Sha1;
+use uuid::{Builder, Uuid};
+
+use crate::http::HttpClient;
+
+/// The URL of the Mojang session server.
+pub const MOJANG_SESSION_SERVER: &str = "https://sessionserver.mojang.com";
+
+/// The name of the profile property that contains the skin and the cape of a player.
+pub const TEXTURES_PROPERTY: &str = "textures";
+
+/// The size of the RSA key of the server in bits, the vanilla client expects a 1024 bit key.
+pub const SERVER_KEY_BITS: usize = 1024;
+
+/// The longest username of a player.
+pub const MAX_USERNAME_LENGTH: usize = 16;
+
+/// The RSA key pair of the server, which the client uses to send the shared secret of the encryption handshake.
+///
+/// Generating the key takes a while, so it is only generated once it is used the first time and shared by every connection.
+///
+/// # Examples
+/// ```rust
+/// use rand::rngs::OsRng;
+/// use rsa::{pkcs8::DecodePublicKey, Pkcs1v15Encrypt, RsaPublicKey};
+/// use protocol_core::auth::ServerKey;
+///
+/// let key = ServerKey::new();
+///
+/// // The client encrypts its shared secret with the public key.
+/// let public_key = RsaPublicKey::from_public_key_der(key.public_key()).unwrap();
+/// let encrypted = public_key.encrypt(&mut OsRng, Pkcs1v15Encrypt, &[7; 16]).unwrap();
+///
+/// assert_eq!(key.decrypt(&encrypted).unwrap(), vec![7; 16]);
+/// assert!(key.decrypt(&[1, 2, 3]).is_err());
+/// ```
+///
+/// # Fields
+/// - `key` - The private key and the public key encoded as DER, `None` until the key is used the first time.
+#[derive(Debug, Default)]
+pub struct ServerKey {
+    key: OnceLock<(RsaPrivateKey, Vec<u8>)>,
+}
+
+impl ServerKey {
+    /// Creates a new `[ServerKey]`, the key is generated once it is used the first time.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the public key, encoded as DER the way it is sent in the `[EncryptionRequestPacket]`.
+    pub fn public_key(&self) -> &[u8] {
+        &self.key().1
+    }
+
+    /// Decrypts bytes the client encrypted with the public key, like the shared secret.
+    pub fn decrypt(&self, bytes: &[u8]) -> io::Result<Vec<u8>> {
+        self.key()
+            .0
+            .decrypt(Pkcs1v15Encrypt, bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Returns the key pair, generating it if it wasn't used yet.
+    fn key(&self) -> &(RsaPrivateKey, Vec<u8>) {
+        self.key.get_or_init(|| {
+            let private_key = RsaPrivateKey::new(&mut OsRng, SERVER_KEY_BITS)
+                .expect("failed to generate the RSA key of the server");
+            let public_key = private_key
+                .to_public_key()
+                .to_public_key_der()
+                .expect("failed to encode the RSA key of the server")
+                .into_vec();
+            (private_key, public_key)
+        })
+    }
+}
+
+/// The future returned by an `[Authenticator]`.
+pub type AuthFuture<'a> = Pin<Box<dyn Future<Output = io::Result<GameProfile>> + Send + 'a>>;
+
+/// Returns the server hash of an encryption handshake, which the client and the server send to the session server.
+///
+/// The hash is the SHA-1 digest of the server ID, the shared secret and the public key of the server, written as a
+/// signed hexadecimal number the way the vanilla server writes it.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::auth::server_hash;
+///
+/// assert_eq!(server_hash("Notch", &[], &[]), "4ed1f46bbe04bc756bcb17c0c7ce3e4632f06a48");
+/// assert_eq!(server_hash("jeb_", &[], &[]), "-7c9d5b0044c130109a5d7b5fb5c317c02b4e28c1");
+/// assert_eq!(server_hash("simon", &[], &[]), "88e16a1019277b15d58faf0541e11910eb756f6");
+/// ```
+///
+/// # Parameters
+/// - `server_id` - The server ID of the encryption request, empty for vanilla servers.
+/// - `shared_secret` - The shared secret chosen by the client.
+/// - `public_key` - The public key of the server, encoded as DER.
+pub fn server_hash(server_id: &str, shared_secret: &[u8], public_key: &[u8]) -> String {
+    let mut digest: [u8; 20] = Sha1::new()
+        .chain_update(server_id)
+        .chain_update(shared_secret)
+        .chain_update(public_key)
+        .finalize()
+        .into();
+
+    // The digest is a two's complement number, negative digests are written as their absolute value with a minus.
+    let negative = digest[0] & 0x80 != 0;
+    if negative {
+        let mut carry = true;
+        for byte in digest.iter_mut().rev() {
+            (*byte, carry) = (!*byte).overflowing_add(carry as u8);
+        }
+    }
+
+    let hex: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+    let hex = hex.trim_start_matches('0');
+    if negative {
+        format!("-{}", hex)
+    } else {
+        hex.to_string()
+    }
+}
+
+/// Returns whether the given name can be the username of a player.
+///
+/// Usernames are 1 to 16 characters long and only contain letters, digits and underscores, like Mojang accounts.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::auth::is_valid_username;
+///
+/// assert!(is_valid_username("jeb_"));
+/// assert!(!is_valid_username(""));
+/// assert!(!is_valid_username("x&ip=1.2.3.4"));
+/// assert!(!is_valid_username("SeventeenLetters_"));
+/// ```
+pub fn is_valid_username(name: &str) -> bool {
+    (1..=MAX_USERNAME_LENGTH).contains(&name.len())
+        && name
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
+}
+
+/// Percent-encodes a value of a query string, every byte except the unreserved characters is encoded.
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// The profile of a player that logged in.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::auth::GameProfile;
+/// use uuid::Uuid;
+///
+/// let mut profile = GameProfile { uuid: Uuid::nil(), name: "Notch".to_string(), properties: Vec::new() };
+/// assert!(profile.textures().is_none());
+///
+/// profile.set_textures("e30=", Some("c2lnbmF0dXJl".to_string()));
+/// profile.set_textures("e30=", None);
+///
+/// assert_eq!(profile.properties.len(), 1);
+/// assert_eq!(profile.textures().unwrap().signature, None);
+/// ```
+///
+/// # Fields
+/// - `uuid` - The UUID of the player.
+/// - `name` - The username of the player.
+/// - `properties` - The properties of the profile, like the skin of the player.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct GameProfile {
+    #[serde(rename = "id")]
+    pub uuid: Uuid,
+    pub name: String,
+    #[serde(default)]
+    pub properties: Vec<GameProfileProperty>,
+}
+
+impl GameProfile {
+    /// Returns the property that contains the skin and the cape of the player.
+    pub fn textures(&self) -> Option<&GameProfileProperty> {
+        self.properties
+            .iter()
+            .find(|property| property.name == TEXTURES_PROPERTY)
+    }
+
+    /// Replaces the skin and the cape of the player.
+    ///
+    /// # Parameters
+    /// - `value` - The base64 encoded JSON that contains the URLs of the textures.
+    /// - `signature` - The signature of the value, clients don't load textures of other players without it.
+    pub fn set_textures(&mut self, value: impl Into<String>, signature: Option<String>) {
+        self.properties
+            .retain(|property| property.name != TEXTURES_PROPERTY);
+        self.properties.push(GameProfileProperty {
+            name: TEXTURES_PROPERTY.to_string(),
+            value: value.into(),
+            signature,
+        });
+    }
+}
+
+/// A property of a `[GameProfile]`.
+///
+/// # Fields
+/// - `name` - The name of the property, like `textures`.
+/// - `value` - The value of the property, usually base64 encoded JSON.
+/// - `signature` - The signature of the value, signed by the session server.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct GameProfileProperty {
+    pub name: String,
+    pub value: String,
+    pub signature: Option<String>,
+}
+
+impl From<&GameProfileProperty> for ProfileProperty {
+    fn from(property: &GameProfileProperty) -> Self {
+        Self {
+            name: property.name.clone(),
+            value: property.value.clone(),
+            signature: PrefixedOptional(property.signature.clone()),
+        }
+    }
+}
+
+/// Verifies players that log in and returns their `[GameProfile]`.
+///
+/// The authenticator is selected through `[ServerConfig::authenticator]`, so servers can use the Mojang session server,
+/// another Yggdrasil compatible session server or no authentication at all.
+///
+/// # Examples
+/// ```rust
+/// use std::{io, net::IpAddr};
+/// use protocol_core::auth::{AuthFuture, Authenticator, GameProfile};
+/// use uuid::Uuid;
+///
+/// /// Lets everyone join with the same UUID.
+/// #[derive(Debug)]
+/// struct SharedAuthenticator;
+///
+/// impl Authenticator for SharedAuthenticator {
+///     fn requires_encryption(&self) -> bool {
+///         false
+///     }
+///
+///     fn authenticate<'a>(&'a self, username: &'a str, _server_hash: &'a str, _ip: Option<IpAddr>) -> AuthFuture<'a> {
+///         Box::pin(async move {
+///             Ok(GameProfile { uuid: Uuid::nil(), name: username.to_string(), properties: Vec::new() })
+///         })
+///     }
+/// }
+/// ```
+pub trait Authenticator: fmt::Debug + Send + Sync {
+    /// Returns whether the connection has to be encrypted before the player can be authenticated.
+    ///
+    /// Session servers need the server hash, which is only known after the encryption handshake.
+    fn requires_encryption(&self) -> bool;
+
+    /// Authenticates the player with the given username.
+    ///
+    /// # Parameters
+    /// - `username` - The username sent by the client.
+    /// - `server_hash` - The server hash of the encryption handshake, empty if the connection isn't encrypted.
+    /// - `ip` - The IP address of the client, if it should be checked by the session server.
+    ///
+    /// # Returns
+    /// The profile of the player, an error if the player couldn't be authenticated.
+    fn authenticate<'a>(
+        &'a self,
+        username: &'a str,
+        server_hash: &'a str,
+        ip: Option<IpAddr>,
+    ) -> AuthFuture<'a>;
+}
+
+/// Lets every player join without authentication, like a server with `online-mode=false`.
+///
+/// The UUID of a player is derived from its username, the same way the vanilla server does.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::auth::OfflineAuthenticator;
+///
+/// let uuid = OfflineAuthenticator::offline_uuid("Notch");
+///
+/// assert_eq!(uuid.to_string(), "b50ad385-829d-3141-a216-7e7d7539ba7f");
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OfflineAuthenticator;
+
+impl OfflineAuthenticator {
+    /// Returns the offline UUID of a player, this is a version 3 UUID of `OfflinePlayer:<username>`.
+    pub fn offline_uuid(username: &str) -> Uuid {
+        let hash = Md5::digest(format!("OfflinePlayer:{}", username));
+        Builder::from_md5_bytes(hash.into()).into_uuid()
+    }
+}
+
+impl Authenticator for OfflineAuthenticator {
+    fn requires_encryption(&self) -> bool {
+        false
+    }
+
+    fn authenticate<'a>(
+        &'a self,
+        username: &'a str,
+        _server_hash: &'a str,
+        _ip: Option<IpAddr>,
+    ) -> AuthFuture<'a> {
+        Box::pin(async move {
+            Ok(GameProfile {
+                uuid: Self::offline_uuid(username),
+                name: username.to_string(),
+                properties: Vec::new(),
+            })
+        })
+    }
+}
+
+/// Authenticates players with a Yggdrasil compatible session server, like a server with `online-mode=true`.
+///
+/// By default the Mojang session server is used, other session servers can be used with `[MojangAuthenticator::with_base_url]`.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::auth::MojangAuthenticator;
+///
+/// let authenticator = MojangAuthenticator::with_base_url("https://session.example.com/");
+///
+/// assert_eq!(
+///     authenticator.has_joined_url("Notch", "-1a2b", None),
+///     "https://session.example.com/session/minecraft/hasJoined?username=Notch&serverId=-1a2b",
+/// );
+///
+/// // The parameters are percent-encoded, so they can't add parameters of their own.
+/// assert_eq!(
+///     authenticator.has_joined_url("x&ip=1.2.3.4#", "-1a2b", None),
+///     "https://session.example.com/session/minecraft/hasJoined?username=x%26ip%3D1.2.3.4%23&serverId=-1a2b",
+/// );
+/// ```
+///
+/// # Fields
+/// - `base_url` - The URL of the session server, without a trailing slash.
+/// - `prevent_proxy_connections` - Whether the IP address of the client is sent to the session server,
+///   so players can't join through a proxy.
+/// - `client` - The HTTP client used to send the requests.
+#[derive(Debug, Clone)]
+pub struct MojangAuthenticator {
+    base_url: String,
+    pub prevent_proxy_connections: bool,
+    client: HttpClient,
+}
+
+impl MojangAuthenticator {
+    /// Creates a new `[MojangAuthenticator]` that uses the Mojang session server.
+    pub fn new() -> Self {
+        Self::with_base_url(MOJANG_SESSION_SERVER)
+    }
+
+    /// Creates a new `[MojangAuthenticator]` that uses the given session server.
+    ///
+    /// # Parameters
+    /// - `base_url` - The URL of the session server, like `https://sessionserver.mojang.com`.
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            prevent_proxy_connections: false,
+            client: HttpClient::default(),
+        }
+    }
+
+    /// Sets the HTTP client used to send the requests, this can be used to change the timeouts or to use a proxy.
+    pub fn with_client(mut self, client: HttpClient) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Returns the URL of the session server.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Returns the URL used to check whether the player joined the server, the username and server hash are percent-encoded.
+    pub fn has_joined_url(&self, username: &str, server_hash: &str, ip: Option<IpAddr>) -> String {
+        let mut url = format!(
+            "{}/session/minecraft/hasJoined?username={}&serverId={}",
+            self.base_url,
+            percent_encode(username),
+            percent_encode(server_hash)
+        );
+        if let Some(ip) = ip.filter(|_| self.prevent_proxy_connections) {
+            url.push_str(&format!("&ip={}", ip));
+        }
+
+        url
+    }
+
+    /// Tells the session server that the player joins a server, this is the client side of authenticating a player.
+    ///
+    /// The server checks this once the encryption handshake is done, so this has to be called before the
+    /// encryption response is sent.
+    ///
+    /// # Parameters
+    /// - `access_token` - The access token of the account of the player.
+    /// - `uuid` - The UUID of the player.
+    /// - `server_hash` - The server hash of the encryption handshake, created with `[server_hash]`.
+    pub async fn join(&self, access_token: &str, uuid: Uuid, server_hash: &str) -> io::Result<()> {
+        let url = format!("{}/session/minecraft/join", self.base_url);
+        let body = json!({
+            "accessToken": access_token,
+            "selectedProfile": uuid.simple().to_string(),
+            "serverId": server_hash,
+        });
+        let response = self.client.post_json(&url, &body.to_string()).await?;
+
+        // The session server answers with `204 No Content` if the join was accepted.
+        if !response.is_success() {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!(
+                    "The session server refused the join with status {}",
+                    response.status
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for MojangAuthenticator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Authenticator for MojangAuthenticator {
+    fn requires_encryption(&self) -> bool {
+        true
+    }
+
+    fn authenticate<'a>(
+        &'a self,
+        username: &'a str,
+        server_hash: &'a str,
+        ip: Option<IpAddr>,
+    ) -> AuthFuture<'a> {
+        Box::pin(async move {
+            let url = self.has_joined_url(username, server_hash, ip);
+            let response = self.client.get(&url).await?;
+
+            // The session server answers with `204 No Content` if the player didn't join.
+            if response.status != 200 {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    "Failed to verify username",
+                ));
+            }
+
+            response.json()
+        })
+    }
+}
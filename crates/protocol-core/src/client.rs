@@ -1,18 +1,307 @@
+use std::{io, net::SocketAddr, sync::Arc, time::Duration};
+
+use bytes::BytesMut;
+
 use protocol_buf::{
-    buffer::{Buffer, PacketBuffer},
-    compression::CompressionData,
+    buffer::{Buffer, NormalBuffer, PacketBuffer, MAX_PACKET_SIZE},
+    codec::FrameCipher,
+    compression::{CompressionData, CompressionHint, CompressionType, Inflater},
+    error::ProtocolResult,
+    pool::BufferPool,
+    text::Component,
+    types::{PrefixedOptional, RemainingBytes, VarInt},
+};
+use protocol_packets::{
+    chat::CHAT_TYPE,
+    chunk::ChunkPos,
+    equipment::{Equipment, EquipmentSlot},
+    item::Slot,
+    player_info::{PlayerAction, PlayerInfo},
+    plugin::{decode_brand, BRAND_CHANNEL},
+    registry::KnownPack,
+    report::{ReportDetail, MAX_REPORT_DETAILS},
+    settings::ClientInformation,
+    teleport::RelativeFlags,
+    v1_21::{
+        configuration::{
+            ClientboundConfigurationKeepAlivePacket, ClientboundConfigurationPluginMessagePacket,
+            ClientboundKnownPacksPacket, ConfigurationCookieRequestPacket,
+            ConfigurationCustomReportDetailsPacket, ConfigurationDisconnectPacket,
+            ConfigurationRemoveResourcePackPacket, ConfigurationServerLinksPacket,
+            ConfigurationStoreCookiePacket, ConfigurationTransferPacket, FinishConfigurationPacket,
+            ServerboundConfigurationKeepAlivePacket, UpdateEnabledFeaturesPacket,
+        },
+        login::{
+            EncryptionRequestPacket, EncryptionResponsePacket, LoginCookieRequestPacket,
+            LoginDisconnectPacket, LoginPluginRequestPacket, LoginPluginResponsePacket,
+            LoginStartPacket, LoginSuccessPacket, SetCompressionPacket,
+        },
+        play::{
+            AcknowledgeBlockChangePacket, ChunkDataAndUpdateLightPacket,
+            ClientboundPluginMessagePacket, DisguisedChatMessagePacket, LoginPlayPacket,
+            PlayCookieRequestPacket, PlayCustomReportDetailsPacket, PlayDisconnectPacket,
+            PlayRemoveResourcePackPacket, PlayStoreCookiePacket, PlayTransferPacket,
+            PlayerInfoRemovePacket, PlayerInfoUpdatePacket, RespawnPacket, SetCenterChunkPacket,
+            SetContainerSlotPacket, SetCooldownPacket, SetEntityMetadataPacket, SetEquipmentPacket,
+            SetRenderDistancePacket, SynchronizePlayerPositionPacket, SystemChatMessagePacket,
+            UnloadChunkPacket, UseItemPacket,
+        },
+        PROTOCOL_VERSION,
+    },
+    ClientboundPacket, ConnectionState, Packet, ServerboundPacket,
+};
+use rand::{rngs::OsRng, RngCore};
+use tokio::{
+    io::AsyncReadExt,
+    net::{tcp::OwnedReadHalf, TcpStream},
+    sync::mpsc::UnboundedReceiver,
+    task,
+    time::{self, Instant},
 };
-use tokio::{io::AsyncReadExt, net::TcpStream};
+use uuid::Uuid;
+
+use crate::{
+    audit::AuditEvent,
+    auth::{is_valid_username, server_hash, GameProfile},
+    config::ServerConfig,
+    config_cache::ConfigurationKey,
+    cookie::{CookieJar, MAX_COOKIE_SIZE},
+    cooldown::ItemCooldowns,
+    dispatch::builtin_handler,
+    entity::{next_entity_id, PlayerMetadata},
+    forwarding::{
+        ForwardedPlayer, PlayerForwarding, VELOCITY_CHANNEL, VELOCITY_FORWARDING_VERSION,
+    },
+    handler::{HandlerPhase, PacketHandlers},
+    idle::{IdleAction, IDLE_CHECK_INTERVAL},
+    interceptor::{InterceptedPacket, Interception, PacketDirection},
+    inventory::PlayerInventory,
+    keep_alive::KeepAlive,
+    operators::{op_level_event, PermissionProvider},
+    outbound::{OutboundSettings, PacketWriter},
+    queue::LoginPermit,
+    raycast::{Rotation, Vec3},
+    registry::{ClientHandle, ClientMessage, EncodedPacket},
+    resource_pack::{ResourcePack, ResourcePackStack},
+    sequence::ProtocolSequence,
+    throttle::PacketRateLimiter,
+    timings::{PacketKey, Timings},
+    ui::UiState,
+    world::{ChunkView, LoadedChunks, OVERWORLD},
+};
+
+/// The view distance of the server in chunks, clients with a larger view distance are limited to it.
+const SERVER_VIEW_DISTANCE: u32 = 10;
+
+/// The furthest a player can move with a single movement packet, further movements are undone.
+pub const MAX_PLAYER_MOVEMENT: f64 = 10.0;
 
 /// Represents a client connection.
 ///
 /// The TCP stream usually is grabbed from the server connection. This is rarely created manually. If so, it is usually for testing purposes.
 /// Its not recommended to create this struct manually yourself.
 ///
+/// Packets are written by a `[PacketWriter]`, which batches the packets that are sent while it is writing.
+///
 /// # Fields
-/// - `listener` - The TCP stream that listens for incoming data.
+/// - `listener` - The read half of the TCP stream that listens for incoming data.
+/// - `incoming` - The bytes that were received but don't form a full packet yet.
+/// - `inflater` - Decompresses the received packets into a buffer that is reused across packets.
+/// - `writer` - The queue of the task that writes to the TCP stream.
+/// - `bytes_sent` - The amount of bytes that were queued to be written.
+/// - `address` - The address of the client, this is the address sent by the proxy if the PROXY protocol is used.
+/// - `cipher` - The cipher of the connection, `None` until encryption is enabled.
+/// - `max_packet_size` - The largest frame that is accepted, larger frames are rejected before they are buffered.
 pub struct ClientConnection {
-    listener: TcpStream,
+    listener: OwnedReadHalf,
+    incoming: BytesMut,
+    inflater: Inflater,
+    writer: PacketWriter,
+    bytes_sent: u64,
+    address: Option<SocketAddr>,
+    cipher: Option<FrameCipher>,
+    max_packet_size: usize,
+}
+
+impl ClientConnection {
+    /// Creates a new `[ClientConnection]` with bytes that were already read from the TCP stream.
+    ///
+    /// # Parameters
+    /// - `stream` - The TCP stream, its write half is moved to the writer task.
+    /// - `incoming` - The bytes that were already read.
+    /// - `settings` - The limits of the queue of the writer task.
+    /// - `timings` - The timings the writes of the writer task are recorded to, `None` if they aren't recorded.
+    pub(crate) fn with_incoming(
+        stream: TcpStream,
+        incoming: &[u8],
+        settings: OutboundSettings,
+        timings: Option<Arc<Timings>>,
+    ) -> Self {
+        let address = stream.peer_addr().ok();
+        let (listener, writer) = stream.into_split();
+        Self {
+            listener,
+            incoming: BytesMut::from(incoming),
+            inflater: Inflater::new(),
+            writer: PacketWriter::spawn_with_timings(writer, settings, timings),
+            bytes_sent: 0,
+            address,
+            cipher: None,
+            max_packet_size: MAX_PACKET_SIZE,
+        }
+    }
+
+    /// Limits the size of the packets that are read, before and after they are decompressed.
+    ///
+    /// Frames that declare a larger length are rejected with `[BufferError::BadPacketLength]` as soon as their
+    /// length was received, so the size of the buffers can't be chosen by the other side of the connection.
+    ///
+    /// # Parameters
+    /// - `max_packet_size` - The largest packet in bytes, frames can't exceed `[MAX_PACKET_SIZE]` either way.
+    pub fn set_max_packet_size(&mut self, max_packet_size: usize) {
+        self.max_packet_size = max_packet_size.min(MAX_PACKET_SIZE);
+        self.inflater.set_max_length(max_packet_size);
+    }
+
+    /// Returns the address of the other side of the connection, this is the address of the proxy if the client connected through one.
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.peer_addr()
+    }
+
+    /// Returns the address of the client, this is the address sent by the proxy if the PROXY protocol is used.
+    pub fn address(&self) -> Option<SocketAddr> {
+        self.address
+    }
+
+    /// Replaces the address of the client, like with the address read from a PROXY protocol header.
+    pub(crate) fn set_address(&mut self, address: SocketAddr) {
+        self.address = Some(address);
+    }
+
+    /// Returns the `[PacketWriter]` of the connection, which can be used to check how many writes are queued.
+    pub fn writer(&self) -> &PacketWriter {
+        &self.writer
+    }
+
+    /// Returns the amount of bytes that were queued to be written, after compression.
+    pub fn bytes_sent(&self) -> u64 {
+        self.bytes_sent
+    }
+
+    /// Reads the next full packet from the TCP stream.
+    ///
+    /// Bytes are buffered until a complete frame has been received, so a packet can be split over multiple reads
+    /// and one read can contain multiple packets. Frames are split off the received bytes without copying them,
+    /// compressed packets are decompressed directly into a buffer that is reused once the previous packets were dropped.
+    ///
+    /// # Parameters
+    /// - `compression` - The compression data used to decompress the packet.
+    ///
+    /// # Returns
+    /// The next packet, `None` if the client disconnected.
+    pub async fn read_packet(
+        &mut self,
+        compression: &CompressionData,
+    ) -> io::Result<Option<PacketBuffer>> {
+        loop {
+            match PacketBuffer::frame_size_limited(&self.incoming, self.max_packet_size) {
+                Ok(Some(size)) => {
+                    let frame = self.incoming.split_to(size).freeze();
+                    return self
+                        .inflater
+                        .read_packet(frame, compression)
+                        .map(Some)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e));
+                }
+                Ok(None) => {}
+                Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+            }
+
+            let start = self.incoming.len();
+            if self.listener.read_buf(&mut self.incoming).await? == 0 {
+                return Ok(None);
+            }
+            if let Some(cipher) = &mut self.cipher {
+                cipher.decrypt(&mut self.incoming[start..]);
+            }
+        }
+    }
+
+    /// Encrypts every byte sent and received from now on, with the shared secret of the encryption handshake.
+    ///
+    /// This has to be called right after the encryption response was read or sent. Bytes that were received after
+    /// the encryption response but not read yet are decrypted right away, since the other side may send its first
+    /// encrypted packet in the same TCP segment.
+    ///
+    /// # Parameters
+    /// - `shared_secret` - The shared secret of the encryption handshake.
+    pub(crate) fn enable_encryption(&mut self, shared_secret: &[u8; 16]) {
+        let mut cipher = FrameCipher::new(shared_secret);
+        cipher.decrypt(&mut self.incoming);
+        self.cipher = Some(cipher);
+    }
+
+    /// Queues the given packet to be written to the TCP stream, waiting if the queue of the `[PacketWriter]` is full.
+    ///
+    /// # Parameters
+    /// - `packet` - The packet to send.
+    /// - `compression` - The compression data used to compress the packet.
+    pub async fn send_packet<P: ClientboundPacket>(
+        &mut self,
+        packet: &P,
+        compression: &CompressionData,
+    ) -> io::Result<()> {
+        let buffer = packet.write_packet(NormalBuffer::new(BufferPool::global().take()));
+        self.send_buffer(buffer, compression, packet.compression_hint())
+            .await
+    }
+
+    /// Queues an already written packet to be written to the TCP stream.
+    ///
+    /// # Parameters
+    /// - `buffer` - The written packet.
+    /// - `compression` - The compression data used to compress the packet.
+    /// - `hint` - How the packet should be compressed.
+    pub async fn send_buffer(
+        &mut self,
+        buffer: PacketBuffer,
+        compression: &CompressionData,
+        hint: CompressionHint,
+    ) -> io::Result<()> {
+        let mut bytes = compression
+            .to_buffer_with_hint(buffer, compression, hint)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if let Some(cipher) = &mut self.cipher {
+            cipher.encrypt(&mut bytes);
+        }
+
+        self.bytes_sent += bytes.len() as u64;
+        self.writer.send(bytes).await
+    }
+
+    /// Writes frames that were already compressed, like the cached frames of the `[ConfigurationCache]`.
+    ///
+    /// # Parameters
+    /// - `frames` - One or more complete frames.
+    pub async fn send_frames(&mut self, frames: &[u8]) -> io::Result<()> {
+        let mut frames = frames.to_vec();
+        if let Some(cipher) = &mut self.cipher {
+            cipher.encrypt(&mut frames);
+        }
+
+        self.bytes_sent += frames.len() as u64;
+        self.writer.send(frames).await
+    }
+
+    /// Waits until every queued packet was written to the TCP stream.
+    pub async fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush().await
+    }
+
+    /// Writes every queued packet and closes the TCP stream.
+    pub async fn close(&mut self) -> io::Result<()> {
+        self.writer.close().await
+    }
 }
 
 /// Represents a client connection.
@@ -25,9 +314,71 @@ pub struct ClientConnection {
 /// # Fields
 /// - `connection` - The client connection.
 /// - `compression` - The compression data, which includes threshold and compression type.
+/// - `state` - The state the connection is currently in.
+/// - `keep_alive` - The keep alive packets that were sent to the client while in the `Play` state.
+/// - `config` - The server config this client was created with.
+/// - `handlers` - The packet handlers registered on the server.
+/// - `pending_teleport` - The ID of the teleport the client still has to confirm.
+/// - `sequence` - The packets the client may send next and the teleports that were sent to it.
+/// - `uuid` - The random UUID of the connection, the UUID of the player is part of its `profile`.
+/// - `protocol_version` - The protocol version the client sent in its handshake.
+/// - `transferred` - Whether the client was transferred from another server, it logs in like any other client.
+/// - `cookies` - The cookies that were stored on the client or requested from it.
+/// - `known_packs` - The data packs the client knows, this is set once the client answered the known packs request.
+/// - `entity_id` - The entity ID of the player.
+/// - `profile` - The game profile of the player, this is set once the player logged in. It is shared with the `[ClientRegistry]`.
+/// - `brand` - The brand of the client, like `vanilla` or `fabric`, this is set once the client sent it.
+/// - `information` - The settings of the client, like its language, this is set once the client sent them.
+/// - `metadata` - The metadata other clients need to render the player, like its pose.
+/// - `position` - The position of the feet of the player, this is set once the player joined the game.
+/// - `rotation` - The direction the player is facing.
+/// - `on_ground` - Whether the player is standing on the ground, as reported by the client.
+/// - `last_activity` - When the player moved, chatted or interacted with something the last time.
+/// - `packet_limiter` - Limits the amount of packets the client can send per second.
+/// - `inventory` - The inventory of the player, as the server set it.
+/// - `cooldowns` - The cooldowns of the items of the player, items on cooldown can't be used.
+/// - `resource_packs` - The resource packs of the player and their statuses, starting with the packs from the `[ServerConfig]`.
+/// - `ui` - The boss bars, scoreboards and tab list of the client, which are sent again after a respawn.
+/// - `chunks` - The chunks of the `[World]` the client has loaded.
+/// - `joined` - Whether the client received the `[LoginPlayPacket]`, it can't be teleported before that.
+/// - `login_permit` - The permit of the `[LoginQueue]`, which is held until the client is in the `Play` state.
+/// - `forwarded` - The player forwarded by BungeeCord in the handshake, which is used once the client logs in.
+/// - `handle` - The handle other tasks use to send messages to this client.
+/// - `messages` - The messages sent to this client through its `[ClientHandle]`.
 pub struct Client {
     pub connection: ClientConnection,
     pub compression: CompressionData,
+    pub state: ConnectionState,
+    pub keep_alive: KeepAlive,
+    pub config: Arc<ServerConfig>,
+    pub handlers: Arc<PacketHandlers>,
+    pub pending_teleport: Option<i32>,
+    pub sequence: ProtocolSequence,
+    pub uuid: Uuid,
+    pub protocol_version: i32,
+    pub transferred: bool,
+    pub cookies: CookieJar,
+    pub known_packs: Vec<KnownPack>,
+    pub entity_id: i32,
+    pub profile: Option<GameProfile>,
+    pub brand: Option<String>,
+    pub information: Option<ClientInformation>,
+    pub metadata: PlayerMetadata,
+    pub position: Vec3,
+    pub rotation: Rotation,
+    pub on_ground: bool,
+    pub inventory: PlayerInventory,
+    pub cooldowns: ItemCooldowns,
+    pub resource_packs: ResourcePackStack,
+    pub ui: UiState,
+    pub chunks: ChunkView,
+    last_activity: Instant,
+    packet_limiter: PacketRateLimiter,
+    joined: bool,
+    login_permit: Option<LoginPermit>,
+    forwarded: Option<ForwardedPlayer>,
+    handle: ClientHandle,
+    messages: UnboundedReceiver<ClientMessage>,
 }
 
 impl Client {
@@ -35,43 +386,1700 @@ impl Client {
     ///
     /// The TCP stream is usually created by the server connection. This is rarely created manually.
     /// The compression data is usually created by the server connection. This is rarely created manually.
-    pub const fn new(listener: TcpStream, compression: CompressionData) -> Self {
+    pub fn new(listener: TcpStream, compression: CompressionData) -> Self {
+        Self::with_connection(
+            ClientConnection::with_incoming(listener, &[], OutboundSettings::default(), None),
+            compression,
+            ConnectionState::Handshake,
+            Arc::new(ServerConfig::default()),
+            Arc::new(PacketHandlers::default()),
+        )
+    }
+
+    /// Creates a new `[Client]` from a connection that already went through the handshake.
+    pub(crate) fn with_connection(
+        mut connection: ClientConnection,
+        compression: CompressionData,
+        state: ConnectionState,
+        config: Arc<ServerConfig>,
+        handlers: Arc<PacketHandlers>,
+    ) -> Self {
+        let uuid = Uuid::new_v4();
+        let (handle, messages) = ClientHandle::new(uuid);
+        let packet_limiter = PacketRateLimiter::new(config.throttle.max_packets_per_second);
+        connection.set_max_packet_size(config.throttle.max_packet_size);
+        let resource_packs = config.resource_packs.iter().cloned().collect();
+        let chunks = ChunkView::with_tickets(config.chunks.clone());
+
         Self {
-            connection: ClientConnection { listener },
+            connection,
             compression,
+            state,
+            keep_alive: KeepAlive::default(),
+            config,
+            handlers,
+            pending_teleport: None,
+            sequence: ProtocolSequence::new(),
+            uuid,
+            protocol_version: PROTOCOL_VERSION,
+            transferred: false,
+            cookies: CookieJar::new(),
+            known_packs: Vec::new(),
+            entity_id: next_entity_id(),
+            profile: None,
+            brand: None,
+            information: None,
+            metadata: PlayerMetadata::default(),
+            position: Vec3::default(),
+            rotation: Rotation::default(),
+            on_ground: false,
+            inventory: PlayerInventory::new(),
+            cooldowns: ItemCooldowns::new(),
+            resource_packs,
+            ui: UiState::default(),
+            chunks,
+            last_activity: Instant::now(),
+            packet_limiter,
+            joined: false,
+            login_permit: None,
+            forwarded: None,
+            handle,
+            messages,
+        }
+    }
+
+    /// Returns a `[ClientHandle]` that can be used to send packets to this client from other tasks.
+    pub fn handle(&self) -> ClientHandle {
+        self.handle.clone()
+    }
+
+    /// Sends the given packet to the client.
+    ///
+    /// The packet is queued and written by the writer task of the connection, use `[Client::flush]` to wait until it was written.
+    /// It is passed through the interceptors first, a cancelled packet isn't sent.
+    ///
+    /// # Parameters
+    /// - `packet` - The packet to send.
+    pub async fn send_packet<P: ClientboundPacket>(&mut self, packet: &P) -> io::Result<()> {
+        let sent = self.connection.bytes_sent();
+        let hint = packet.compression_hint();
+        let started = self.config.packet_timings.is_some().then(Instant::now);
+        let mut buffer = packet.write_packet(NormalBuffer::new(BufferPool::global().take()));
+        if let (Some(timings), Some(started)) = (&self.config.packet_timings, started) {
+            let key = PacketKey {
+                direction: PacketDirection::Clientbound,
+                state: self.state,
+                packet_id: *buffer.packet_id,
+            };
+            timings.record(key, buffer.get_ref().len(), started.elapsed());
+        }
+
+        if self.handlers.has_interceptors() && hint != CompressionHint::Precompressed {
+            let mut intercepted = InterceptedPacket::new(
+                PacketDirection::Clientbound,
+                self.state,
+                *buffer.packet_id,
+                buffer.get_ref().to_vec(),
+            );
+            let handlers = self.handlers.clone();
+            if handlers.dispatch_interceptors(self, &mut intercepted) == Interception::Cancel {
+                return Ok(());
+            }
+            buffer = intercepted.into_buffer();
+        }
+
+        let result = self
+            .connection
+            .send_buffer(buffer, &self.compression, hint)
+            .await;
+        if let Some(controller) = &self.config.view_distance {
+            controller.record_sent(self.connection.bytes_sent() - sent);
+        }
+        result
+    }
+
+    /// Waits until every packet that was sent to the client was written to the TCP stream.
+    pub async fn flush(&mut self) -> io::Result<()> {
+        self.connection.flush().await
+    }
+
+    /// Enables compression for the connection, using the threshold of the `[CompressionData]`.
+    ///
+    /// The `[SetCompressionPacket]` is still sent uncompressed, every packet after it is compressed using zlib.
+    /// This has to be called during the `Login` state. A negative threshold keeps compression disabled.
+    pub async fn enable_compression(&mut self) -> io::Result<()> {
+        let threshold = self.compression.threshold;
+        if threshold < 0 || self.compression.compression_type == CompressionType::Zlib {
+            return Ok(());
+        }
+
+        let packet = SetCompressionPacket {
+            threshold: VarInt::from(threshold),
+        };
+        self.send_packet(&packet).await?;
+
+        self.compression = CompressionData::new(threshold, CompressionType::Zlib);
+        Ok(())
+    }
+
+    /// Kicks the client with the given reason and closes the connection.
+    ///
+    /// The disconnect packet of the current `[ConnectionState]` is used. Clients in the `Handshake` or `Status` state
+    /// can't receive a reason, their connection is just closed.
+    ///
+    /// # Parameters
+    /// - `reason` - The reason shown to the client.
+    pub async fn disconnect_with_reason(&mut self, reason: impl Into<Component>) -> io::Result<()> {
+        let reason = reason.into();
+        match self.state {
+            ConnectionState::Login | ConnectionState::Transfer => {
+                let packet = LoginDisconnectPacket {
+                    reason: reason.to_json(),
+                };
+                self.send_packet(&packet).await?;
+            }
+            ConnectionState::Configuration => {
+                self.send_packet(&ConfigurationDisconnectPacket { reason })
+                    .await?;
+            }
+            ConnectionState::Play => {
+                self.send_packet(&PlayDisconnectPacket { reason }).await?;
+            }
+            ConnectionState::Handshake | ConnectionState::Status => {}
+        }
+
+        self.connection.close().await
+    }
+
+    /// Logs the client in, using the `[Authenticator]` of the `[ServerConfig]`.
+    ///
+    /// Behind a proxy the player is taken from the `[PlayerForwarding]` of the `[ServerConfig]` instead,
+    /// the proxy already authenticated it.
+    /// Once the player is authenticated, compression is enabled and the `[LoginSuccessPacket]` is sent.
+    /// Players that can't be authenticated or whose username isn't valid are kicked, see `[is_valid_username]`.
+    ///
+    /// # Parameters
+    /// - `packet` - The login start packet sent by the client.
+    ///
+    /// # Returns
+    /// `false` if the client was kicked.
+    pub async fn login(&mut self, packet: LoginStartPacket) -> io::Result<bool> {
+        if !is_valid_username(&packet.name) {
+            self.disconnect_with_reason("Invalid characters in username")
+                .await?;
+            return Ok(false);
+        }
+
+        let config = self.config.clone();
+        let profile = match &config.forwarding {
+            PlayerForwarding::None => self.authenticate(&packet.name).await?,
+            PlayerForwarding::BungeeCord => match self.forwarded.take() {
+                Some(forwarded) => Some(forwarded.into_profile(&packet.name)),
+                None => {
+                    self.disconnect_with_reason(
+                        "If you wish to use IP forwarding, please enable it in your BungeeCord config as well!",
+                    )
+                    .await?;
+                    None
+                }
+            },
+            PlayerForwarding::Velocity { secret } => self.read_velocity_forwarding(secret).await?,
+        };
+        let Some(profile) = profile else {
+            return Ok(false);
+        };
+
+        self.enable_compression().await?;
+
+        let success = LoginSuccessPacket {
+            uuid: profile.uuid.as_u128(),
+            username: profile.name.clone(),
+            properties: profile.properties.iter().map(Into::into).collect(),
+            strict_error_handling: false,
+        };
+        self.send_packet(&success).await?;
+        self.sequence.sent_login_success();
+
+        self.handle.set_profile(profile.clone());
+        self.profile = Some(profile);
+        Ok(true)
+    }
+
+    /// Authenticates the player with the `[Authenticator]` of the `[ServerConfig]`.
+    ///
+    /// If the authenticator requires encryption, the connection is encrypted first and the server hash of the
+    /// encryption handshake is passed to it.
+    ///
+    /// # Returns
+    /// The profile of the player, `None` if the client was kicked.
+    async fn authenticate(&mut self, name: &str) -> io::Result<Option<GameProfile>> {
+        let authenticator = self.config.authenticator.clone();
+        let server_hash = match authenticator.requires_encryption() {
+            true => match self.start_encryption().await {
+                Ok(Some(server_hash)) => server_hash,
+                Ok(None) => return Ok(None),
+                Err(e) => {
+                    println!("Failed to encrypt the connection of {}; err = {}", name, e);
+                    self.disconnect_with_reason("Failed to verify username!")
+                        .await?;
+                    return Ok(None);
+                }
+            },
+            false => String::new(),
+        };
+
+        let ip = self.connection.address().map(|address| address.ip());
+        match authenticator.authenticate(name, &server_hash, ip).await {
+            Ok(profile) => Ok(Some(profile)),
+            Err(e) => {
+                println!("Failed to authenticate {}; err = {}", name, e);
+                self.disconnect_with_reason("Failed to verify username!")
+                    .await?;
+                Ok(None)
+            }
         }
     }
 
+    /// Encrypts the connection with the `[ServerKey]` of the `[ServerConfig]`.
+    ///
+    /// The client answers the `[EncryptionRequestPacket]` with the shared secret and the verify token, both encrypted
+    /// with the public key of the server. The connection is encrypted once the verify token matches.
+    ///
+    /// # Returns
+    /// The server hash the session server checks the player with, `None` if the client closed the connection.
+    async fn start_encryption(&mut self) -> io::Result<Option<String>> {
+        let key = self.config.server_key.clone();
+        let mut verify_token = vec![0; 4];
+        OsRng.fill_bytes(&mut verify_token);
+
+        let request = EncryptionRequestPacket {
+            server_id: String::new(),
+            public_key: key.public_key().to_vec(),
+            verify_token: verify_token.clone(),
+            should_authenticate: true,
+        };
+        self.send_packet(&request).await?;
+
+        let response = match self.connection.read_packet(&self.compression).await? {
+            Some(packet) if *packet.packet_id == EncryptionResponsePacket::ID => {
+                EncryptionResponsePacket::read_packet(packet.buffer)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+            }
+            Some(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Expected encryption response",
+                ))
+            }
+            None => return Ok(None),
+        };
+
+        if key.decrypt(&response.verify_token)? != verify_token {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the verify token doesn't match",
+            ));
+        }
+        let shared_secret: [u8; 16] =
+            key.decrypt(&response.shared_secret)?
+                .try_into()
+                .map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "the shared secret isn't 16 bytes",
+                    )
+                })?;
+
+        self.connection.enable_encryption(&shared_secret);
+        Ok(Some(server_hash("", &shared_secret, key.public_key())))
+    }
+
+    /// Asks Velocity for the forwarded player and checks its signature.
+    ///
+    /// # Parameters
+    /// - `secret` - The forwarding secret shared with Velocity.
+    ///
+    /// # Returns
+    /// The profile of the player, `None` if the client was kicked.
+    async fn read_velocity_forwarding(&mut self, secret: &[u8]) -> io::Result<Option<GameProfile>> {
+        let message_id = self.entity_id;
+        let request = LoginPluginRequestPacket {
+            message_id: VarInt::from(message_id),
+            channel: VELOCITY_CHANNEL.to_string(),
+            data: RemainingBytes::from(vec![VELOCITY_FORWARDING_VERSION]),
+        };
+        self.send_packet(&request).await?;
+
+        let forwarded = match self.connection.read_packet(&self.compression).await? {
+            Some(packet) if *packet.packet_id == LoginPluginResponsePacket::ID => {
+                let response = LoginPluginResponsePacket::read_packet(packet.buffer)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                match &*response.data {
+                    Some(data) if *response.message_id == message_id => {
+                        ForwardedPlayer::from_velocity(data, secret)
+                    }
+                    _ => Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "the client didn't forward the player",
+                    )),
+                }
+            }
+            Some(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Expected login plugin response",
+            )),
+            None => return Ok(None),
+        };
+
+        match forwarded {
+            Ok(forwarded) => {
+                self.set_forwarded_address(&forwarded);
+                Ok(Some(forwarded.into_profile("")))
+            }
+            Err(e) => {
+                println!("Failed to read the forwarded player; err = {}", e);
+                self.disconnect_with_reason("This server requires you to connect with Velocity.")
+                    .await?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Stores the player BungeeCord forwarded in the handshake, it is used once the client logs in.
+    pub(crate) fn set_forwarded(&mut self, forwarded: ForwardedPlayer) {
+        self.set_forwarded_address(&forwarded);
+        self.forwarded = Some(forwarded);
+    }
+
+    /// Replaces the address of the client with the address forwarded by the proxy.
+    fn set_forwarded_address(&mut self, forwarded: &ForwardedPlayer) {
+        let port = self
+            .connection
+            .address()
+            .map_or(0, |address| address.port());
+        self.connection
+            .set_address(SocketAddr::new(forwarded.address, port));
+    }
+
+    /// Waits until the `[LoginQueue]` lets the client log in.
+    ///
+    /// While the client waits, configuration keep alive packets are sent so it doesn't time out.
+    /// The configuration state can't show any text, so the position in the queue is only shown if the queue is full
+    /// and the client is kicked.
+    ///
+    /// # Returns
+    /// `false` if the client was kicked or disconnected while waiting.
+    pub async fn wait_in_queue(&mut self) -> io::Result<bool> {
+        let queue = self.config.login_queue.clone();
+        if !queue.has_room(self.uuid) {
+            let reason = format!(
+                "The server is busy, {} players are waiting to join. Please try again later.",
+                queue.waiting()
+            );
+            self.disconnect_with_reason(reason).await?;
+            return Ok(false);
+        }
+
+        let position = match queue.try_acquire(self.uuid) {
+            Ok(permit) => {
+                self.login_permit = Some(permit);
+                return Ok(true);
+            }
+            Err(position) => position,
+        };
+        println!(
+            "Client is waiting to log in, position {} in the queue",
+            position
+        );
+
+        let interval = self.keep_alive.interval;
+        let mut keep_alive = time::interval(interval);
+        let acquire = queue.acquire(self.uuid);
+        tokio::pin!(acquire);
+
+        loop {
+            tokio::select! {
+                permit = &mut acquire => {
+                    self.login_permit = Some(permit);
+                    return Ok(true);
+                }
+                _ = keep_alive.tick() => {
+                    let Some(packet) = self.keep_alive.next_packet() else {
+                        return Ok(false);
+                    };
+
+                    let packet = ClientboundConfigurationKeepAlivePacket {
+                        keep_alive_id: packet.keep_alive_id,
+                    };
+                    self.send_packet(&packet).await?;
+                }
+                packet = self.connection.read_packet(&self.compression) => match packet? {
+                    Some(packet) if *packet.packet_id == ServerboundConfigurationKeepAlivePacket::ID => {
+                        let packet = ServerboundConfigurationKeepAlivePacket::read_packet(packet.buffer)
+                            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                        if self.keep_alive.acknowledge(packet.keep_alive_id) {
+                            self.record_latency();
+                        }
+                    }
+                    Some(_) => {}
+                    None => return Ok(false),
+                },
+            }
+        }
+    }
+
+    /// Switches the client into the `Configuration` state and sends the configuration packets.
+    ///
+    /// This sends the brand, the enabled feature flags, the links and the report details from the `[ServerConfig]`,
+    /// the resource packs of the player, which are applied again if the player is configured again,
+    /// and the data packs the server knows.
+    /// Once the client answered with the packs it knows, the registries are sent by `[Client::finish_configuration]`.
+    pub async fn start_configuration(&mut self) -> io::Result<()> {
+        self.state = ConnectionState::Configuration;
+        self.joined = false;
+
+        let brand = ClientboundConfigurationPluginMessagePacket::brand(&self.config.brand);
+        self.send_packet(&brand).await?;
+
+        let features = UpdateEnabledFeaturesPacket {
+            feature_flags: self.config.feature_flags.clone(),
+        };
+        self.send_packet(&features).await?;
+
+        if !self.config.links.is_empty() {
+            let links = ConfigurationServerLinksPacket {
+                links: self.config.links.to_links(),
+            };
+            self.send_packet(&links).await?;
+        }
+
+        if !self.config.report_details.is_empty() {
+            let details = self.config.report_details.clone();
+            self.send_report_details(details).await?;
+        }
+
+        self.resource_packs.reset_statuses();
+        let packs: Vec<_> = self
+            .resource_packs
+            .packs()
+            .map(ResourcePack::configuration_packet)
+            .collect();
+        for pack in &packs {
+            self.send_packet(pack).await?;
+        }
+
+        let packs = ClientboundKnownPacksPacket {
+            packs: self.config.registries.known_packs(),
+        };
+        self.send_packet(&packs).await?;
+        self.sequence.sent_known_packs();
+        Ok(())
+    }
+
+    /// Sends the registries and tags from the `[ServerConfig]`, followed by the `[FinishConfigurationPacket]`.
+    ///
+    /// The registries and tags are taken from the `[ConfigurationCache]` of the config, they are only written
+    /// if no client with the same protocol version, known packs and compression was configured before.
+    /// Once the client acknowledges the configuration, it is teleported to the spawn position.
+    pub async fn finish_configuration(&mut self) -> io::Result<()> {
+        let key = ConfigurationKey::new(
+            self.protocol_version,
+            self.known_packs.clone(),
+            &self.compression,
+        );
+        let frames = self
+            .config
+            .configuration_cache
+            .get_or_insert_with(key, || self.write_configuration())?;
+        self.connection.send_frames(&frames).await?;
+
+        self.send_packet(&FinishConfigurationPacket {}).await?;
+        self.sequence.sent_finish_configuration();
+        Ok(())
+    }
+
+    /// Writes and compresses the registry and tag packets that are sent by `[Client::finish_configuration]`.
+    fn write_configuration(&self) -> io::Result<Vec<u8>> {
+        let registries = &self.config.registries;
+        let tags = EncodedPacket::new(&self.config.tags.to_packet(registries));
+
+        let mut frames = Vec::new();
+        for packet in registries
+            .to_packets()
+            .iter()
+            .map(EncodedPacket::new)
+            .chain([tags])
+        {
+            let buffer = packet.write_packet(NormalBuffer::new(Vec::new()));
+            let frame = self
+                .compression
+                .to_buffer_with_hint(buffer, &self.compression, packet.compression_hint())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            frames.extend_from_slice(&frame);
+        }
+
+        Ok(frames)
+    }
+
+    /// Switches the client into the `Play` state once it acknowledged the configuration.
+    ///
+    /// The client leaves the `[LoginQueue]`, receives the `[LoginPlayPacket]` with its permission level and is teleported to the spawn position.
+    /// The player is added to the `[SpatialIndex]` and to the player list of every client, the client receives
+    /// the player list of the server and the vehicles placed by the server afterwards.
+    /// The equipment of the player is shown to every other client, the client receives the equipment of the other players.
+    pub async fn join_game(&mut self) -> io::Result<()> {
+        self.state = ConnectionState::Play;
+        self.login_permit = None;
+        self.mark_active();
+        self.audit(|profile| AuditEvent::Join {
+            name: profile.name.clone(),
+            uuid: profile.uuid,
+        });
+
+        let spawn = self.config.spawn;
+        let login = self.login_play_packet();
+        self.send_packet(&login).await?;
+        self.joined = true;
+        self.send_permission_level().await?;
+
+        self.config.spatial.write().unwrap().insert_player(
+            self.entity_id,
+            self.uuid,
+            Vec3::new(spawn.x, spawn.y, spawn.z),
+            self.view_distance(),
+        );
+        self.teleport(spawn.x, spawn.y, spawn.z, spawn.yaw, spawn.pitch)
+            .await?;
+
+        if let Some(info) = self.player_info() {
+            let packet = PlayerInfoUpdatePacket::new(vec![info]);
+            let message = ClientMessage::PlayPacket(EncodedPacket::new(&packet));
+            self.config.clients.broadcast_except(self.uuid, message);
+        }
+        let players = self.config.clients.player_latencies();
+        let entries = players
+            .iter()
+            .map(|(profile, latency)| {
+                player_info(profile).with(PlayerAction::UpdateLatency(latency.as_millis() as i32))
+            })
+            .collect();
+        self.send_packet(&PlayerInfoUpdatePacket::new(entries))
+            .await?;
+
+        let equipment = EncodedPacket::new(&self.equipment_packet());
+        self.handle.set_equipment(equipment.clone());
+        self.config
+            .clients
+            .broadcast_except(self.uuid, ClientMessage::PlayPacket(equipment));
+        for packet in self.config.clients.equipment_except(self.uuid) {
+            self.send_packet(&packet).await?;
+        }
+
+        for vehicle in self.config.vehicles.vehicles() {
+            self.send_packet(&vehicle.spawn_packet()).await?;
+            if vehicle.passenger.is_some() {
+                self.send_packet(&vehicle.passengers_packet()).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Creates the `[LoginPlayPacket]` for this client, using the `[ServerConfig]` and its game rules.
+    ///
+    /// The player spawns in survival mode, in the dimension of the `[World]` or in the overworld if there is no world.
+    pub fn login_play_packet(&self) -> LoginPlayPacket {
+        let (dimension_name, dimension_type) = self
+            .config
+            .world
+            .as_ref()
+            .map_or((OVERWORLD, OVERWORLD), |world| {
+                (world.dimension_name(), world.dimension_type())
+            });
+        let dimension_type = self
+            .config
+            .registries
+            .get("minecraft:dimension_type")
+            .and_then(|registry| registry.id_of(dimension_type))
+            .unwrap_or(0);
+
+        let mut packet = LoginPlayPacket {
+            entity_id: self.entity_id,
+            is_hardcore: false,
+            dimension_names: vec![dimension_name.to_string()],
+            max_players: VarInt::from(20),
+            view_distance: VarInt::from(self.server_view_distance() as i32),
+            simulation_distance: VarInt::from(self.server_view_distance() as i32),
+            reduced_debug_info: false,
+            enable_respawn_screen: true,
+            do_limited_crafting: false,
+            dimension_type: VarInt::from(dimension_type),
+            dimension_name: dimension_name.to_string(),
+            hashed_seed: self.config.hashed_seed(),
+            game_mode: 0,
+            previous_game_mode: -1,
+            is_debug: false,
+            is_flat: false,
+            death_location: PrefixedOptional(None),
+            portal_cooldown: VarInt::from(0),
+            enforces_secure_chat: false,
+        };
+        self.config.game_rules.read().unwrap().apply(&mut packet);
+
+        packet
+    }
+
+    /// Teleports the client to the given absolute position.
+    ///
+    /// The teleport is pending until the client confirms it, movements the client sends before that are ignored.
+    /// The position is updated right away, so other players see the player at its new position.
+    /// The client has to receive the `[LoginPlayPacket]` first, see `[Client::join_game]`.
+    ///
+    /// # Parameters
+    /// - `x` - The X position.
+    /// - `y` - The Y position.
+    /// - `z` - The Z position.
+    /// - `yaw` - The yaw in degrees.
+    /// - `pitch` - The pitch in degrees.
+    pub async fn teleport(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: f64,
+        yaw: f32,
+        pitch: f32,
+    ) -> io::Result<()> {
+        self.teleport_relative(x, y, z, yaw, pitch, RelativeFlags::ABSOLUTE)
+            .await
+    }
+
+    /// Teleports the client, the values that are set in the flags are added to the current position and rotation.
+    ///
+    /// A relative rotation of `0` keeps the direction the player is looking at, which makes the teleport smooth.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::client::Client;
+    /// use protocol_packets::teleport::RelativeFlags;
+    ///
+    /// async fn launch(client: &mut Client) -> std::io::Result<()> {
+    ///     // Moves the player up 5 blocks without changing where it is looking.
+    ///     client.teleport_relative(0.0, 5.0, 0.0, 0.0, 0.0, RelativeFlags::ALL).await
+    /// }
+    /// ```
+    ///
+    /// # Parameters
+    /// - `x` - The X position or offset.
+    /// - `y` - The Y position or offset.
+    /// - `z` - The Z position or offset.
+    /// - `yaw` - The yaw or the change of the yaw in degrees.
+    /// - `pitch` - The pitch or the change of the pitch in degrees.
+    /// - `flags` - The values that are relative.
+    ///
+    /// # Returns
+    /// An error if the client didn't receive the `[LoginPlayPacket]` yet.
+    pub async fn teleport_relative(
+        &mut self,
+        x: f64,
+        y: f64,
+        z: f64,
+        yaw: f32,
+        pitch: f32,
+        flags: RelativeFlags,
+    ) -> io::Result<()> {
+        if !self.joined {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Clients can only be teleported after they received the login packet",
+            ));
+        }
+
+        let teleport_id = self.sequence.next_teleport();
+        self.pending_teleport = Some(teleport_id);
+
+        let relative = |flag, value: f64, current: f64| {
+            if flags.contains(flag) {
+                current + value
+            } else {
+                value
+            }
+        };
+        let (position, rotation) = (self.position, self.rotation);
+        self.move_to(Vec3::new(
+            relative(RelativeFlags::X, x, position.x),
+            relative(RelativeFlags::Y, y, position.y),
+            relative(RelativeFlags::Z, z, position.z),
+        ));
+        self.rotation = Rotation::new(
+            relative(RelativeFlags::YAW, yaw as f64, rotation.yaw as f64) as f32,
+            relative(RelativeFlags::PITCH, pitch as f64, rotation.pitch as f64) as f32,
+        );
+
+        let packet = SynchronizePlayerPositionPacket {
+            x,
+            y,
+            z,
+            yaw,
+            pitch,
+            flags,
+            teleport_id: VarInt::from(teleport_id),
+        };
+        self.send_packet(&packet).await?;
+        self.stream_chunks().await
+    }
+
+    /// Handles a movement sent by the client, parts that didn't change are `None`.
+    ///
+    /// Movements are ignored while a teleport is pending, since the client sent them before it knew about the teleport.
+    /// A movement further than `[MAX_PLAYER_MOVEMENT]` is undone by teleporting the client back.
+    ///
+    /// # Parameters
+    /// - `position` - The new position of the feet of the player.
+    /// - `rotation` - The new direction the player is facing.
+    /// - `on_ground` - Whether the player is standing on the ground.
+    pub(crate) async fn handle_movement(
+        &mut self,
+        position: Option<Vec3>,
+        rotation: Option<Rotation>,
+        on_ground: bool,
+    ) -> io::Result<()> {
+        if self.pending_teleport.is_some() {
+            return Ok(());
+        }
+
+        if position.is_some_and(|position| position != self.position)
+            || rotation.is_some_and(|rotation| rotation != self.rotation)
+        {
+            self.mark_active();
+        }
+
+        if let Some(position) = position {
+            if self.position.distance_squared(position) > MAX_PLAYER_MOVEMENT * MAX_PLAYER_MOVEMENT
+            {
+                let (current, rotation) = (self.position, self.rotation);
+                return self
+                    .teleport(
+                        current.x,
+                        current.y,
+                        current.z,
+                        rotation.yaw,
+                        rotation.pitch,
+                    )
+                    .await;
+            }
+            self.move_to(position);
+            self.stream_chunks().await?;
+        }
+
+        if let Some(rotation) = rotation {
+            self.rotation = rotation;
+        }
+        self.on_ground = on_ground;
+        Ok(())
+    }
+
+    /// Sends the chunks of the `[World]` that came into view and unloads the chunks that left it.
+    ///
+    /// The `[SetCenterChunkPacket]` is sent whenever the player enters another chunk, the chunks are sent
+    /// from the chunk of the player outwards. Nothing is sent if there is no world or the player isn't in the game.
+    /// The chunks are loaded and encoded on a blocking thread, chunks the world doesn't have are requested again the next time.
+    pub async fn stream_chunks(&mut self) -> io::Result<()> {
+        let Some(world) = self.config.world.clone() else {
+            return Ok(());
+        };
+        if self.state != ConnectionState::Play {
+            return Ok(());
+        }
+
+        let center = ChunkPos::from_block(
+            self.position.x.floor() as i32,
+            self.position.z.floor() as i32,
+        );
+        let update = self.chunks.update(center, self.view_distance());
+
+        if let Some(center) = update.center {
+            let packet = SetCenterChunkPacket {
+                chunk_x: VarInt::from(center.x),
+                chunk_z: VarInt::from(center.z),
+            };
+            self.send_packet(&packet).await?;
+        }
+        for chunk in update.unload {
+            let packet = UnloadChunkPacket {
+                chunk_z: chunk.z,
+                chunk_x: chunk.x,
+            };
+            self.send_packet(&packet).await?;
+        }
+        let timings = self.config.timings.clone();
+        for pos in update.load {
+            let _span = timings.span("chunk_send");
+            let (world, chunks) = (world.clone(), self.config.chunks.clone());
+            let packet = task::spawn_blocking(move || {
+                LoadedChunks::load(&chunks, &*world, pos)
+                    .map(|chunk| ChunkDataAndUpdateLightPacket::new(&chunk, world.light(&chunk)))
+            })
+            .await
+            .map_err(io::Error::other)?;
+
+            if let Some(packet) = packet {
+                self.send_packet(&packet).await?;
+                self.chunks.mark_sent(pos);
+            }
+        }
+        Ok(())
+    }
+
+    /// Changes the position of the player, the `[SpatialIndex]` is only updated once the player joined the game.
+    fn move_to(&mut self, position: Vec3) {
+        self.position = position;
+        if self.state == ConnectionState::Play {
+            self.config
+                .spatial
+                .write()
+                .unwrap()
+                .update(self.entity_id, position);
+        }
+    }
+
+    /// Respawns the client, or moves it into another dimension.
+    ///
+    /// The client forgets its boss bars, scoreboards and tab list when it respawns,
+    /// so the persistent components of the `[UiState]` are sent again right after the `[RespawnPacket]`.
+    /// Components that aren't persistent are removed.
+    ///
+    /// # Parameters
+    /// - `packet` - The respawn packet to send.
+    pub async fn respawn(&mut self, packet: &RespawnPacket) -> io::Result<()> {
+        self.send_packet(packet).await?;
+        self.chunks.clear();
+
+        self.ui.clear_transient();
+        let packets: Vec<_> = self.ui.respawn_packets().cloned().collect();
+        for packet in &packets {
+            self.send_packet(packet).await?;
+        }
+
+        Ok(())
+    }
+
     /// This method is used to "start" the client connection. This is where the client connection will start listening for incoming data aka packets.
     ///
     /// Here the bytes are being converted into a `[PacketBuffer]`, which is a custom `[Buffer]` inside `protocol_buf`.
     /// This makes it easier to read and write packets.
     ///
+    /// Messages sent through the `[ClientHandle]` of the client are handled here as well.
+    ///
+    /// While the client is in the `Play` state, a keep alive packet is sent every `[KeepAlive::interval]`.
+    /// If the client doesn't respond in time or responds with an unknown ID, the connection is closed.
+    ///
     /// # Note
     /// If you are using `[ServerConnection]` to accept connections, if you aren't defining the callback parameter yourself, this is automatically called within the API.
     pub async fn start(&mut self) {
+        let interval = self.keep_alive.interval;
+        let mut keep_alive = time::interval_at(Instant::now() + interval, interval);
+        let idle_policy = self.config.idle_policy.clone();
+        let mut idle_check = time::interval(IDLE_CHECK_INTERVAL);
+
         loop {
-            let mut buffer = [0_u8; 1024];
-            match self.connection.listener.read(&mut buffer).await {
-                Ok(0) => {
-                    println!("Client Disconnected...");
-                    break;
+            tokio::select! {
+                packet = self.connection.read_packet(&self.compression) => match packet {
+                    Ok(None) => {
+                        println!("Client Disconnected...");
+                        break;
+                    }
+                    Ok(Some(_)) if !self.packet_limiter.try_acquire() => {
+                        let reason = Component::translate("disconnect.exceeded_packet_rate", Vec::new());
+                        let _ = self.disconnect_with_reason(reason).await;
+                        break;
+                    }
+                    Ok(Some(packet_data)) => {
+                        let key = PacketKey {
+                            direction: PacketDirection::Serverbound,
+                            state: self.state,
+                            packet_id: *packet_data.packet_id,
+                        };
+                        let bytes = packet_data.get_ref().len() - packet_data.buffer.position() as usize;
+                        let started = Instant::now();
+                        let handled = self.handle_packet(packet_data).await;
+                        if let Some(timings) = &self.config.packet_timings {
+                            timings.record(key, bytes, started.elapsed());
+                        }
+
+                        match handled {
+                            Ok(true) => {}
+                            Ok(false) => break,
+                            Err(e) => {
+                                println!("Failed to handle packet; err = {}", e);
+                                break;
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        println!("Failed to read from socket; err = {:?}", e);
+                        break;
+                    }
+                },
+                Some(message) = self.messages.recv() => {
+                    let result = match message {
+                        ClientMessage::Packet(packet) => self.send_packet(&packet).await,
+                        ClientMessage::PlayPacket(packet) if self.state == ConnectionState::Play => {
+                            self.send_packet(&packet).await
+                        }
+                        ClientMessage::PlayPacket(_) => Ok(()),
+                        ClientMessage::Disconnect(reason) => {
+                            let _ = self.disconnect_with_reason(reason).await;
+                            break;
+                        }
+                        // Clients that aren't playing yet get the game rules with the `[LoginPlayPacket]`.
+                        ClientMessage::GameRule(change) if self.state == ConnectionState::Play => {
+                            self.send_packet(&change.to_packet(self.entity_id)).await
+                        }
+                        ClientMessage::GameRule(_) => Ok(()),
+                        ClientMessage::ViewDistance { from, to } if self.state == ConnectionState::Play => {
+                            self.change_view_distance(from, to).await
+                        }
+                        ClientMessage::ViewDistance { .. } => Ok(()),
+                        // Clients that aren't playing yet get their permission level when they join.
+                        ClientMessage::PermissionLevel if self.state == ConnectionState::Play => {
+                            self.send_permission_level().await
+                        }
+                        ClientMessage::PermissionLevel => Ok(()),
+                    };
+
+                    if let Err(e) = result {
+                        println!("Failed to write to socket; err = {:?}", e);
+                        break;
+                    }
                 }
-                Ok(n) => {
-                    let buffer = buffer[..n].to_vec();
-                    if let Some(packet_data) = PacketBuffer::new(buffer, &self.compression) {
-                        println!(
-                            "Packet Length: {} // Packet ID: {}",
-                            *packet_data.packet_length, *packet_data.packet_id
-                        );
-                        println!("Received: {:?}", packet_data.get_ref());
+                _ = keep_alive.tick(), if self.state == ConnectionState::Play => {
+                    let Some(packet) = self.keep_alive.next_packet() else {
+                        println!("Client Timed Out...");
+                        break;
+                    };
+
+                    if let Err(e) = self.send_packet(&packet).await {
+                        println!("Failed to write to socket; err = {:?}", e);
+                        break;
                     }
                 }
-                Err(e) => {
-                    println!("Failed to read from socket; err = {:?}", e);
-                    break;
+                _ = idle_check.tick(), if self.state == ConnectionState::Play && idle_policy.is_some() => {
+                    let result = match idle_policy.as_ref().map(|policy| policy.check(self.idle_time())) {
+                        Some(IdleAction::Warn(message)) => self.send_action_bar(message).await,
+                        Some(IdleAction::Kick(reason)) => {
+                            let _ = self.disconnect_with_reason(reason).await;
+                            break;
+                        }
+                        _ => Ok(()),
+                    };
+
+                    if let Err(e) = result {
+                        println!("Failed to write to socket; err = {:?}", e);
+                        break;
+                    }
                 }
             }
         }
     }
+
+    /// Sends a message on a plugin channel, using the plugin message packet of the current state.
+    ///
+    /// # Parameters
+    /// - `channel` - The identifier of the channel, like `minecraft:brand`.
+    /// - `data` - The data of the message, its format depends on the channel.
+    ///
+    /// # Returns
+    /// An error if the client isn't in the `Configuration` or `Play` state.
+    pub async fn send_plugin_message(
+        &mut self,
+        channel: impl Into<String>,
+        data: Vec<u8>,
+    ) -> io::Result<()> {
+        let (channel, data) = (channel.into(), RemainingBytes::from(data));
+
+        match self.state {
+            ConnectionState::Configuration => {
+                let packet = ClientboundConfigurationPluginMessagePacket { channel, data };
+                self.send_packet(&packet).await
+            }
+            ConnectionState::Play => {
+                self.send_packet(&ClientboundPluginMessagePacket { channel, data })
+                    .await
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Plugin messages can only be sent while configuring or playing",
+            )),
+        }
+    }
+
+    /// Transfers the client to another server, using the transfer packet of the current state.
+    ///
+    /// The client disconnects and logs in to the other server with the `Transfer` intent. Cookies stored on the
+    /// client are kept, so they can be used to pass data to the other server.
+    ///
+    /// # Parameters
+    /// - `host` - The address of the other server.
+    /// - `port` - The port of the other server.
+    ///
+    /// # Returns
+    /// An error if the client isn't in the `Configuration` or `Play` state.
+    pub async fn transfer(&mut self, host: impl Into<String>, port: u16) -> io::Result<()> {
+        let (host, port) = (host.into(), VarInt::from(port as i32));
+
+        match self.state {
+            ConnectionState::Configuration => {
+                self.send_packet(&ConfigurationTransferPacket { host, port })
+                    .await
+            }
+            ConnectionState::Play => self.send_packet(&PlayTransferPacket { host, port }).await,
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Clients can only be transferred while configuring or playing",
+            )),
+        }
+    }
+
+    /// Asks the client for a cookie, using the cookie request packet of the current state.
+    ///
+    /// The answer arrives later, once it was received the cookie can be read from `[Client::cookies]`.
+    ///
+    /// # Parameters
+    /// - `key` - The identifier of the cookie, like `example:session`.
+    ///
+    /// # Returns
+    /// An error if the client isn't in the `Login`, `Configuration` or `Play` state.
+    pub async fn request_cookie(&mut self, key: impl Into<String>) -> io::Result<()> {
+        let key = key.into();
+        match self.state {
+            ConnectionState::Login => {
+                self.send_packet(&LoginCookieRequestPacket { key: key.clone() })
+                    .await?
+            }
+            ConnectionState::Configuration => {
+                self.send_packet(&ConfigurationCookieRequestPacket { key: key.clone() })
+                    .await?
+            }
+            ConnectionState::Play => {
+                self.send_packet(&PlayCookieRequestPacket { key: key.clone() })
+                    .await?
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Cookies can only be requested while logging in, configuring or playing",
+                ))
+            }
+        }
+
+        self.cookies.request(key);
+        Ok(())
+    }
+
+    /// Stores a cookie on the client, using the store cookie packet of the current state.
+    ///
+    /// The cookie is kept by the client until it disconnects, so it can be read by the server it is transferred to.
+    ///
+    /// # Parameters
+    /// - `key` - The identifier of the cookie, like `example:session`.
+    /// - `payload` - The data of the cookie, up to `[MAX_COOKIE_SIZE]` bytes.
+    ///
+    /// # Returns
+    /// An error if the payload is too large or the client isn't in the `Configuration` or `Play` state.
+    pub async fn store_cookie(
+        &mut self,
+        key: impl Into<String>,
+        payload: Vec<u8>,
+    ) -> io::Result<()> {
+        if payload.len() > MAX_COOKIE_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Cookies can't be larger than {} bytes", MAX_COOKIE_SIZE),
+            ));
+        }
+
+        let key = key.into();
+        match self.state {
+            ConnectionState::Configuration => {
+                let packet = ConfigurationStoreCookiePacket {
+                    key: key.clone(),
+                    payload: payload.clone(),
+                };
+                self.send_packet(&packet).await?;
+            }
+            ConnectionState::Play => {
+                let packet = PlayStoreCookiePacket {
+                    key: key.clone(),
+                    payload: payload.clone(),
+                };
+                self.send_packet(&packet).await?;
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Cookies can only be stored while configuring or playing",
+                ))
+            }
+        }
+
+        self.cookies.store(key, payload);
+        Ok(())
+    }
+
+    /// Adds a resource pack to the top of the resource packs of the player, using the packet of the current state.
+    ///
+    /// A pack with the same UUID replaces the pack the player had, the status of the pack is tracked in `[Client::resource_packs]`.
+    ///
+    /// # Returns
+    /// An error if the client isn't in the `Configuration` or `Play` state.
+    pub async fn add_resource_pack(&mut self, pack: ResourcePack) -> io::Result<()> {
+        match self.state {
+            ConnectionState::Configuration => {
+                self.send_packet(&pack.configuration_packet()).await?
+            }
+            ConnectionState::Play => self.send_packet(&pack.play_packet()).await?,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Resource packs can only be added while configuring or playing",
+                ))
+            }
+        }
+
+        self.resource_packs.push(pack);
+        Ok(())
+    }
+
+    /// Removes a resource pack from the player, `None` removes every pack.
+    ///
+    /// # Returns
+    /// An error if the client isn't in the `Configuration` or `Play` state.
+    pub async fn remove_resource_pack(&mut self, uuid: Option<Uuid>) -> io::Result<()> {
+        let packet_uuid = PrefixedOptional(uuid.map(|uuid| uuid.as_u128()));
+        match self.state {
+            ConnectionState::Configuration => {
+                let packet = ConfigurationRemoveResourcePackPacket { uuid: packet_uuid };
+                self.send_packet(&packet).await?
+            }
+            ConnectionState::Play => {
+                let packet = PlayRemoveResourcePackPacket { uuid: packet_uuid };
+                self.send_packet(&packet).await?
+            }
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Resource packs can only be removed while configuring or playing",
+                ))
+            }
+        }
+
+        match uuid {
+            Some(uuid) => {
+                self.resource_packs.remove(uuid);
+            }
+            None => self.resource_packs.clear(),
+        }
+        Ok(())
+    }
+
+    /// Starts a cooldown on an item, the player can't use the item until the cooldown is over.
+    ///
+    /// The client shows the cooldown on every stack of the item. The server ignores the item being used during
+    /// the cooldown, so modified clients can't skip it.
+    ///
+    /// # Parameters
+    /// - `item_id` - The ID of the item in the `minecraft:item` registry.
+    /// - `ticks` - The duration of the cooldown in ticks, `0` removes the cooldown.
+    pub async fn set_cooldown(&mut self, item_id: i32, ticks: u32) -> io::Result<()> {
+        self.cooldowns.set(item_id, ticks);
+
+        let packet = SetCooldownPacket {
+            item_id: VarInt::from(item_id),
+            cooldown_ticks: VarInt::from(ticks.min(i32::MAX as u32) as i32),
+        };
+        self.send_packet(&packet).await
+    }
+
+    /// Replaces the item in a slot of the inventory of the player.
+    ///
+    /// # Parameters
+    /// - `index` - The index of the slot in the inventory window, like `[HOTBAR_START]` for the first hotbar slot.
+    /// - `slot` - The new content of the slot.
+    ///
+    /// # Returns
+    /// An error if the index is out of range.
+    pub async fn set_inventory_slot(&mut self, index: usize, slot: Slot) -> io::Result<()> {
+        let equipment_slot = self.inventory.equipment_slot(index);
+        if !self.inventory.set(index, slot.clone()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid inventory slot {}", index),
+            ));
+        }
+
+        if let Some(equipment_slot) = equipment_slot {
+            self.broadcast_equipment(vec![(equipment_slot, slot.clone())]);
+        }
+
+        let packet = SetContainerSlotPacket {
+            window_id: 0,
+            state_id: VarInt::from(self.inventory.state_id()),
+            slot: index as i16,
+            item: slot,
+        };
+        self.send_packet(&packet).await
+    }
+
+    /// Selects a slot of the hotbar, other clients are shown the new item in the main hand of the player.
+    ///
+    /// # Returns
+    /// `false` if the slot isn't part of the hotbar.
+    pub(crate) fn select_hotbar_slot(&mut self, hotbar_slot: usize) -> bool {
+        let previous = self.inventory.equipped(EquipmentSlot::MainHand).clone();
+        if !self.inventory.select(hotbar_slot) {
+            return false;
+        }
+
+        let held = self.inventory.equipped(EquipmentSlot::MainHand);
+        if *held != previous {
+            self.broadcast_equipment(vec![(EquipmentSlot::MainHand, held.clone())]);
+        }
+        true
+    }
+
+    /// Creates the `[SetEquipmentPacket]` that shows the whole equipment of the player.
+    pub fn equipment_packet(&self) -> SetEquipmentPacket {
+        SetEquipmentPacket {
+            entity_id: VarInt::from(self.entity_id),
+            equipment: self.inventory.equipment(),
+        }
+    }
+
+    /// Shows changed equipment slots of the player to every other client in the game.
+    ///
+    /// Only the changed slots are sent, clients that join later receive the whole equipment.
+    fn broadcast_equipment(&self, changed: Vec<(EquipmentSlot, Slot)>) {
+        if self.state != ConnectionState::Play {
+            return;
+        }
+
+        self.handle
+            .set_equipment(EncodedPacket::new(&self.equipment_packet()));
+        let packet = SetEquipmentPacket {
+            entity_id: VarInt::from(self.entity_id),
+            equipment: Equipment(changed),
+        };
+        self.config.clients.broadcast_except(
+            self.uuid,
+            ClientMessage::PlayPacket(EncodedPacket::new(&packet)),
+        );
+    }
+
+    /// Sends a message from the server to the chat of the client.
+    ///
+    /// # Parameters
+    /// - `message` - The message, which is shown without a sender.
+    pub async fn send_message(&mut self, message: impl Into<Component>) -> io::Result<()> {
+        let packet = SystemChatMessagePacket {
+            content: message.into(),
+            overlay: false,
+        };
+        self.send_packet(&packet).await
+    }
+
+    /// Shows a message in the action bar of the client, above the hotbar.
+    ///
+    /// # Parameters
+    /// - `message` - The message, which disappears after a few seconds.
+    pub async fn send_action_bar(&mut self, message: impl Into<Component>) -> io::Result<()> {
+        let packet = SystemChatMessagePacket {
+            content: message.into(),
+            overlay: true,
+        };
+        self.send_packet(&packet).await
+    }
+
+    /// Returns how long the player has been idle, which is checked by the `[IdlePolicy]` of the server.
+    pub fn idle_time(&self) -> Duration {
+        self.last_activity.elapsed()
+    }
+
+    /// Marks the player as active, which resets its `[Client::idle_time]`.
+    pub fn mark_active(&mut self) {
+        self.last_activity = Instant::now();
+    }
+
+    /// Records a command the player ran in the `[AuditLog]`, the server doesn't run any commands itself.
+    pub(crate) fn handle_command(&mut self, command: &str) {
+        self.audit(|profile| AuditEvent::Command {
+            name: profile.name.clone(),
+            command: command.to_string(),
+        });
+    }
+
+    /// Records an event of the player in the `[AuditLog]` of the server, if the server has one and the player logged in.
+    fn audit(&self, event: impl FnOnce(&GameProfile) -> AuditEvent) {
+        if let (Some(audit_log), Some(profile)) = (&self.config.audit_log, &self.profile) {
+            audit_log.record(event(profile));
+        }
+    }
+
+    /// Creates the packet that shows a chat message sent by this player.
+    ///
+    /// The message is sent as a `[DisguisedChatMessagePacket]` with the `minecraft:chat` chat type, as the server doesn't
+    /// sign messages and the client only accepts player chat messages of players in its player list.
+    pub fn chat_packet(&self, message: impl Into<Component>) -> DisguisedChatMessagePacket {
+        let chat_type = self
+            .config
+            .registries
+            .get("minecraft:chat_type")
+            .and_then(|registry| registry.id_of(CHAT_TYPE))
+            .unwrap_or(0);
+        let sender_name = self
+            .profile
+            .as_ref()
+            .map_or_else(String::new, |profile| profile.name.clone());
+
+        DisguisedChatMessagePacket {
+            message: message.into(),
+            chat_type: VarInt::from(chat_type + 1),
+            sender_name: Component::text(sender_name),
+            target_name: PrefixedOptional(None),
+        }
+    }
+
+    /// Passes a chat message of the player to the chat handlers and broadcasts it to every client in the game.
+    ///
+    /// The message isn't broadcasted if one of the handlers cancels it, it is recorded in the `[AuditLog]` either way.
+    pub(crate) fn handle_chat(&mut self, message: &str) {
+        self.audit(|profile| AuditEvent::Chat {
+            name: profile.name.clone(),
+            message: message.to_string(),
+        });
+
+        let handlers = self.handlers.clone();
+        if !handlers.dispatch_chat(self, message) {
+            return;
+        }
+
+        let packet = self.chat_packet(message);
+        self.config
+            .clients
+            .broadcast(ClientMessage::PlayPacket(EncodedPacket::new(&packet)));
+    }
+
+    /// Sends the details the client adds to its crash reports and disconnect reports, replacing the previous details.
+    ///
+    /// Only the first `[MAX_REPORT_DETAILS]` details are sent, the client disconnects if it receives more.
+    ///
+    /// # Returns
+    /// An error if the client isn't in the `Configuration` or `Play` state.
+    pub async fn send_report_details(&mut self, mut details: Vec<ReportDetail>) -> io::Result<()> {
+        details.truncate(MAX_REPORT_DETAILS);
+
+        match self.state {
+            ConnectionState::Configuration => {
+                self.send_packet(&ConfigurationCustomReportDetailsPacket { details })
+                    .await
+            }
+            ConnectionState::Play => {
+                self.send_packet(&PlayCustomReportDetailsPacket { details })
+                    .await
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Report details can only be sent while configuring or playing",
+            )),
+        }
+    }
+
+    /// Stores the settings of the client and applies its skin parts and main hand to the metadata.
+    ///
+    /// Changes made while the client is in the game are broadcasted, so other clients render them.
+    pub(crate) fn update_information(&mut self, information: ClientInformation) {
+        let changed = self.metadata.apply_information(&information);
+        self.information = Some(information);
+
+        if self.state == ConnectionState::Play {
+            self.config
+                .spatial
+                .write()
+                .unwrap()
+                .set_view_distance(self.entity_id, self.view_distance());
+        }
+
+        if changed && self.state == ConnectionState::Play {
+            self.broadcast_metadata();
+        }
+    }
+
+    /// Returns the latency of the client, measured with the keep alive packets.
+    ///
+    /// This is `Duration::ZERO` until the client answered the first keep alive.
+    pub fn latency(&self) -> Duration {
+        self.keep_alive.latency()
+    }
+
+    /// Shares the latency of the client after it answered a keep alive.
+    ///
+    /// The latency is stored on the `[ClientHandle]`, the server sends the latency of every player to every client
+    /// in one packet every `[LATENCY_UPDATE_INTERVAL]`.
+    pub(crate) fn record_latency(&self) {
+        self.handle.set_latency(self.keep_alive.latency());
+    }
+
+    /// Returns the entry that adds this player to the player list of other clients, `None` if the player didn't log in yet.
+    pub fn player_info(&self) -> Option<PlayerInfo> {
+        self.profile.as_ref().map(player_info)
+    }
+
+    /// Replaces the skin and the cape of the player, like a skin chosen on the server.
+    ///
+    /// While the player is in the game, it is removed from the player list of every client and added again,
+    /// so the clients load the new textures. Players that join afterwards get the new textures right away.
+    ///
+    /// # Parameters
+    /// - `value` - The base64 encoded JSON that contains the URLs of the textures.
+    /// - `signature` - The signature of the value, clients don't load textures of other players without it.
+    ///
+    /// # Returns
+    /// `false` if the player didn't log in yet.
+    pub fn set_skin(&mut self, value: impl Into<String>, signature: Option<String>) -> bool {
+        let Some(profile) = self.profile.as_mut() else {
+            return false;
+        };
+        profile.set_textures(value, signature);
+        self.handle.replace_profile(profile.clone());
+
+        if self.state == ConnectionState::Play {
+            let remove = PlayerInfoRemovePacket {
+                uuids: vec![profile.uuid.as_u128()],
+            };
+            let add = PlayerInfoUpdatePacket::new(vec![player_info(profile)]);
+            for packet in [EncodedPacket::new(&remove), EncodedPacket::new(&add)] {
+                self.config
+                    .clients
+                    .broadcast(ClientMessage::PlayPacket(packet));
+            }
+        }
+        true
+    }
+
+    /// Returns the view distance of the client in chunks, which is limited by the view distance of the server.
+    pub fn view_distance(&self) -> u32 {
+        self.limit_view_distance(self.server_view_distance())
+    }
+
+    /// Returns the permission level of the player, which is decided by the operators of the server.
+    ///
+    /// Clients that haven't logged in yet have no permission level.
+    pub fn permission_level(&self) -> u8 {
+        self.profile.as_ref().map_or(0, |profile| {
+            self.config
+                .operators
+                .read()
+                .unwrap()
+                .permission_level(&profile.uuid)
+        })
+    }
+
+    /// Tells the client about the permission level of its player with an `[EntityEventPacket]`.
+    pub async fn send_permission_level(&mut self) -> io::Result<()> {
+        let packet = op_level_event(self.entity_id, self.permission_level());
+        self.send_packet(&packet).await
+    }
+
+    /// Returns the view distance of the server in chunks, which is scaled by the `[ViewDistanceController]` if there is one.
+    pub fn server_view_distance(&self) -> u32 {
+        self.config
+            .view_distance
+            .as_ref()
+            .map_or(SERVER_VIEW_DISTANCE, |controller| {
+                controller.view_distance()
+            })
+    }
+
+    /// Limits the view distance of the client by the given view distance of the server.
+    fn limit_view_distance(&self, server_view_distance: u32) -> u32 {
+        self.information
+            .as_ref()
+            .map_or(server_view_distance, |information| {
+                (information.view_distance.max(2) as u32).min(server_view_distance)
+            })
+    }
+
+    /// Tells the client that the view distance of the server changed.
+    ///
+    /// Once the view distance got smaller, the chunks around the player that are outside of the new view distance are unloaded.
+    ///
+    /// # Parameters
+    /// - `from` - The previous view distance of the server.
+    /// - `to` - The new view distance of the server.
+    pub(crate) async fn change_view_distance(&mut self, from: u32, to: u32) -> io::Result<()> {
+        self.send_packet(&SetRenderDistancePacket {
+            view_distance: VarInt::from(to as i32),
+        })
+        .await?;
+
+        let (previous, current) = (self.limit_view_distance(from), self.limit_view_distance(to));
+        self.config
+            .spatial
+            .write()
+            .unwrap()
+            .set_view_distance(self.entity_id, current);
+
+        if self.config.world.is_some() {
+            return self.stream_chunks().await;
+        }
+
+        let center = ChunkPos::from_block(
+            self.position.x.floor() as i32,
+            self.position.z.floor() as i32,
+        );
+        for chunk in center.square(previous) {
+            if chunk.distance(center) > current {
+                let packet = UnloadChunkPacket {
+                    chunk_z: chunk.z,
+                    chunk_x: chunk.x,
+                };
+                self.send_packet(&packet).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends the metadata of the player to every client in the game, including the player itself.
+    pub fn broadcast_metadata(&self) {
+        let packet = SetEntityMetadataPacket {
+            entity_id: VarInt::from(self.entity_id),
+            metadata: self.metadata.to_metadata(),
+        };
+        self.config
+            .clients
+            .broadcast(ClientMessage::PlayPacket(EncodedPacket::new(&packet)));
+    }
+
+    /// Handles a plugin message sent by the client.
+    ///
+    /// The brand of the client is stored, every message is passed to the registered channel handlers.
+    pub(crate) fn handle_plugin_message(&mut self, channel: &str, data: &[u8]) {
+        if channel == BRAND_CHANNEL {
+            match decode_brand(data) {
+                Ok(brand) => self.brand = Some(brand),
+                Err(e) => println!("Invalid brand; err = {:?}", e),
+            }
+        }
+
+        let handlers = self.handlers.clone();
+        if !handlers.dispatch_channel(self, channel, data) && channel != BRAND_CHANNEL {
+            println!("Unhandled plugin message on channel {}", channel);
+        }
+    }
+
+    /// Handles a packet received from the client.
+    ///
+    /// The built-in handler is looked up in the `[DispatchTable]`, the registered `[PacketHandlers]` are called before and after it.
+    /// Clients that send a packet the `[ProtocolSequence]` doesn't allow are kicked before any handler is called.
+    ///
+    /// # Parameters
+    /// - `packet_data` - The packet received from the client.
+    ///
+    /// # Returns
+    /// `false` if the client should be disconnected, an error if the packet is malformed.
+    async fn handle_packet(&mut self, mut packet_data: PacketBuffer) -> ProtocolResult<bool> {
+        let handlers = self.handlers.clone();
+        let (state, packet_id) = (self.state, *packet_data.packet_id);
+
+        // Packets that are passed through don't have to be known, so they aren't checked against the sequence.
+        if state == ConnectionState::Play && handlers.passes_through(packet_id) {
+            let position = packet_data.buffer.position() as usize;
+            let data = &packet_data.get_ref()[position..];
+            handlers.dispatch_passthrough(self, packet_id, data);
+            return Ok(true);
+        }
+
+        if let Err(violation) = self.sequence.receive(state, packet_id) {
+            println!(
+                "Kicking client for a protocol violation; err = {}",
+                violation
+            );
+            if let Err(e) = self.disconnect_with_reason(violation.to_string()).await {
+                println!("Failed to write to socket; err = {:?}", e);
+            }
+            return Ok(false);
+        }
+
+        if handlers.has_interceptors() {
+            let buffer = &packet_data.buffer;
+            let position = buffer.position() as usize;
+            let mut intercepted = InterceptedPacket::new(
+                PacketDirection::Serverbound,
+                state,
+                packet_id,
+                buffer.get_ref()[position..].to_vec(),
+            );
+            if handlers.dispatch_interceptors(self, &mut intercepted) == Interception::Cancel {
+                return Ok(true);
+            }
+            packet_data = intercepted.into_buffer();
+        }
+        let packet_id = *packet_data.packet_id;
+
+        // Items on cooldown can't be used, so the handlers never see the packet. The action is still acknowledged.
+        if state == ConnectionState::Play && packet_id == UseItemPacket::ID {
+            let packet = UseItemPacket::read_packet(packet_data.buffer.clone())?;
+            let on_cooldown = self
+                .inventory
+                .held_item(*packet.hand)
+                .is_some_and(|item| self.cooldowns.is_on_cooldown(item.item_id));
+            if on_cooldown {
+                let acknowledge = AcknowledgeBlockChangePacket {
+                    sequence: packet.sequence,
+                };
+                if let Err(e) = self.send_packet(&acknowledge).await {
+                    println!("Failed to write to socket; err = {:?}", e);
+                    return Ok(false);
+                }
+                return Ok(true);
+            }
+        }
+
+        handlers.dispatch(
+            HandlerPhase::Before,
+            self,
+            state,
+            packet_id,
+            &packet_data.buffer,
+        )?;
+        let after = handlers
+            .contains(HandlerPhase::After, state, packet_id)
+            .then(|| packet_data.buffer.clone());
+
+        match builtin_handler(state, packet_id) {
+            Some(handler) => {
+                if !handler(self, packet_data.buffer).await? {
+                    return Ok(false);
+                }
+            }
+            None => {
+                println!(
+                    "Packet Length: {} // Packet ID: {}",
+                    *packet_data.packet_length, *packet_data.packet_id
+                );
+                println!("Received: {:?}", packet_data.get_ref());
+            }
+        }
+
+        if let Some(buffer) = after {
+            handlers.dispatch(HandlerPhase::After, self, state, packet_id, &buffer)?;
+        }
+
+        Ok(true)
+    }
+}
+
+/// Creates the entry that adds a player to the player list, including the properties of its profile like its skin.
+fn player_info(profile: &GameProfile) -> PlayerInfo {
+    let properties = profile.properties.iter().map(Into::into).collect();
+    PlayerInfo::add(profile.uuid.as_u128(), &profile.name, properties)
 }
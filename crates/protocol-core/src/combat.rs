@@ -0,0 +1,308 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
+};
+
+use protocol_buf::types::{PrefixedOptional, VarInt, Velocity};
+use protocol_packets::{
+    damage_type::{DAMAGE_TYPE_REGISTRY, PLAYER_ATTACK},
+    v1_21::play::{DamageEventPacket, SetEntityVelocityPacket, SetHealthPacket},
+    ClientboundPacket,
+};
+use uuid::Uuid;
+
+use crate::{
+    client::Client,
+    entity::PlayerMetadata,
+    raycast::{Vec3, ENTITY_INTERACTION_RANGE},
+    registry::{ClientMessage, ClientRegistry, EncodedPacket},
+    spatial::{chunk_of, SpatialIndex},
+};
+
+/// The health of a player that didn't take any damage.
+pub const PLAYER_MAX_HEALTH: f32 = 20.0;
+
+/// The settings of the `[Combat]` module, the defaults match an unarmed vanilla player.
+///
+/// # Fields
+/// - `damage` - The damage of a single hit, `1` is half a heart.
+/// - `knockback` - The strength of the knockback of every hit.
+/// - `sprint_knockback` - The strength of the extra knockback of hits while sprinting.
+/// - `reach` - The largest distance from the eyes of the attacker to the hitbox of the target.
+/// - `invulnerability` - How long a player can't take damage after being hit.
+/// - `max_health` - The health players start with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CombatSettings {
+    pub damage: f32,
+    pub knockback: f64,
+    pub sprint_knockback: f64,
+    pub reach: f64,
+    pub invulnerability: Duration,
+    pub max_health: f32,
+}
+
+impl Default for CombatSettings {
+    /// The reach leaves room for the latency of the client, like the vanilla server does.
+    fn default() -> Self {
+        Self {
+            damage: 1.0,
+            knockback: 0.4,
+            sprint_knockback: 0.5,
+            reach: ENTITY_INTERACTION_RANGE + 3.0,
+            invulnerability: Duration::from_millis(500),
+            max_health: PLAYER_MAX_HEALTH,
+        }
+    }
+}
+
+/// The result of an attack of a player.
+///
+/// # Variants
+/// - `Hit` - The target took damage and has the given health left.
+/// - `Killed` - The target took damage and died, it has to be healed with `[Combat::heal]` after respawning.
+/// - `OutOfReach` - The target is too far away from the attacker.
+/// - `Invulnerable` - The target was hit recently or is dead.
+/// - `UnknownTarget` - The target isn't a player, like a vehicle.
+/// - `InvalidTarget` - The player attacked itself, the vanilla server kicks the player in that case.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AttackResult {
+    Hit { health: f32 },
+    Killed,
+    OutOfReach,
+    Invulnerable,
+    UnknownTarget,
+    InvalidTarget,
+}
+
+/// The health of a player that was attacked.
+///
+/// # Fields
+/// - `health` - The health that is left.
+/// - `hurt_at` - When the player took damage the last time.
+#[derive(Debug, Clone, Copy)]
+struct Health {
+    health: f32,
+    hurt_at: Option<Instant>,
+}
+
+/// Handles melee attacks between players, so simple PvP servers can be built.
+///
+/// Attacks are validated against the reach and the invulnerability of the target. Hits damage the target,
+/// knock it back and play the hurt animation for every player that can see it. Combat is disabled unless
+/// it is enabled on the server.
+///
+/// # Examples
+/// ```rust
+/// use std::sync::{Arc, RwLock};
+/// use protocol_core::{
+///     combat::{Combat, CombatSettings, PLAYER_MAX_HEALTH},
+///     registry::ClientRegistry,
+///     spatial::SpatialIndex,
+/// };
+///
+/// let settings = CombatSettings { damage: 4.0, ..CombatSettings::default() };
+/// let combat = Combat::new(settings, Arc::new(ClientRegistry::new()), Arc::new(RwLock::new(SpatialIndex::new())));
+///
+/// assert_eq!(combat.settings().damage, 4.0);
+/// assert_eq!(combat.health(1), PLAYER_MAX_HEALTH);
+/// ```
+///
+/// # Fields
+/// - `settings` - The damage and knockback of the attacks.
+/// - `players` - The health of the players that were attacked, players that weren't attacked have full health.
+/// - `clients` - The clients the attacks are sent to.
+/// - `spatial` - The positions of the players, used for the reach and to find the players that can see the target.
+#[derive(Debug)]
+pub struct Combat {
+    settings: CombatSettings,
+    players: Mutex<HashMap<i32, Health>>,
+    clients: Arc<ClientRegistry>,
+    spatial: Arc<RwLock<SpatialIndex>>,
+}
+
+impl Combat {
+    /// Creates a new `[Combat]` module.
+    ///
+    /// # Parameters
+    /// - `settings` - The damage and knockback of the attacks.
+    /// - `clients` - The clients the attacks are sent to.
+    /// - `spatial` - The positions of the players.
+    pub fn new(
+        settings: CombatSettings,
+        clients: Arc<ClientRegistry>,
+        spatial: Arc<RwLock<SpatialIndex>>,
+    ) -> Self {
+        Self {
+            settings,
+            players: Mutex::new(HashMap::new()),
+            clients,
+            spatial,
+        }
+    }
+
+    /// Returns the settings of the attacks.
+    pub fn settings(&self) -> &CombatSettings {
+        &self.settings
+    }
+
+    /// Returns the health of a player.
+    pub fn health(&self, entity_id: i32) -> f32 {
+        self.players
+            .lock()
+            .unwrap()
+            .get(&entity_id)
+            .map_or(self.settings.max_health, |health| health.health)
+    }
+
+    /// Handles an attack of a player on another player.
+    ///
+    /// # Parameters
+    /// - `attacker` - The client of the attacking player.
+    /// - `target` - The entity ID of the attacked entity.
+    pub fn attack(&self, attacker: &Client, target: i32) -> AttackResult {
+        if target == attacker.entity_id {
+            return AttackResult::InvalidTarget;
+        }
+
+        let spatial = self.spatial.read().unwrap();
+        let (Some(viewer), Some(target_position), Some(attacker_position)) = (
+            spatial.viewer(target),
+            spatial.position(target),
+            spatial.position(attacker.entity_id),
+        ) else {
+            return AttackResult::UnknownTarget;
+        };
+
+        // The pose of other players isn't known, so they are hit as if they were standing.
+        let hitbox = PlayerMetadata::default().hitbox(target_position);
+        let eyes = attacker.metadata.eye_position(attacker_position);
+        if hitbox.distance_squared(eyes) > self.settings.reach * self.settings.reach {
+            return AttackResult::OutOfReach;
+        }
+
+        let now = Instant::now();
+        let mut players = self.players.lock().unwrap();
+        let health = players.entry(target).or_insert(Health {
+            health: self.settings.max_health,
+            hurt_at: None,
+        });
+        let recently_hurt = health
+            .hurt_at
+            .is_some_and(|hurt_at| now - hurt_at < self.settings.invulnerability);
+        if health.health <= 0.0 || recently_hurt {
+            return AttackResult::Invulnerable;
+        }
+        health.health = (health.health - self.settings.damage).max(0.0);
+        health.hurt_at = Some(now);
+        let remaining = health.health;
+        drop(players);
+
+        let direction = attacker_position.to(target_position);
+        let mut velocity = knockback(Vec3::default(), direction, self.settings.knockback);
+        if attacker.metadata.sprinting {
+            velocity = knockback(velocity, direction, self.settings.sprint_knockback);
+        }
+
+        let damage_type = attacker
+            .config
+            .registries
+            .get(DAMAGE_TYPE_REGISTRY)
+            .and_then(|registry| registry.id_of(PLAYER_ATTACK))
+            .unwrap_or(0);
+        let damage = DamageEventPacket {
+            entity_id: VarInt::from(target),
+            source_type_id: VarInt::from(damage_type),
+            source_cause_id: VarInt::from(attacker.entity_id + 1),
+            source_direct_id: VarInt::from(attacker.entity_id + 1),
+            source_position: PrefixedOptional(None),
+        };
+
+        let viewers = spatial.players_viewing(chunk_of(target_position));
+        self.send(viewers.iter().copied(), &damage);
+        self.send(viewers, &velocity_packet(target, velocity));
+        self.send([viewer.uuid], &health_packet(remaining));
+
+        if remaining <= 0.0 {
+            AttackResult::Killed
+        } else {
+            AttackResult::Hit { health: remaining }
+        }
+    }
+
+    /// Gives a player its full health back, like after it respawned.
+    ///
+    /// # Returns
+    /// `false` if the player isn't in the game.
+    pub fn heal(&self, entity_id: i32) -> bool {
+        let Some(viewer) = self.spatial.read().unwrap().viewer(entity_id) else {
+            return false;
+        };
+
+        self.players.lock().unwrap().remove(&entity_id);
+        self.send([viewer.uuid], &health_packet(self.settings.max_health));
+        true
+    }
+
+    /// Forgets the health of a player that left the game.
+    pub fn remove(&self, entity_id: i32) {
+        self.players.lock().unwrap().remove(&entity_id);
+    }
+
+    /// Sends a packet to the given clients.
+    fn send<P: ClientboundPacket>(&self, uuids: impl IntoIterator<Item = Uuid>, packet: &P) {
+        let message = ClientMessage::PlayPacket(EncodedPacket::new(packet));
+        self.clients.broadcast_to(uuids, message);
+    }
+}
+
+/// Applies knockback to the velocity of an entity standing on the ground, the same way the vanilla server does.
+///
+/// Every knockback halves the previous velocity, so the extra knockback of a sprinting attacker adds up
+/// with the knockback of the hit.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::{combat::knockback, raycast::Vec3};
+///
+/// let velocity = knockback(Vec3::default(), Vec3::new(2.0, 0.0, 0.0), 0.4);
+/// assert_eq!(velocity, Vec3::new(0.4, 0.4, 0.0));
+///
+/// let velocity = knockback(velocity, Vec3::new(2.0, 0.0, 0.0), 0.5);
+/// assert_eq!(velocity, Vec3::new(0.7, 0.4, 0.0));
+/// ```
+///
+/// # Parameters
+/// - `velocity` - The velocity of the entity in blocks per tick.
+/// - `direction` - The direction the entity is pushed to, only the horizontal part is used.
+/// - `strength` - The strength of the knockback.
+pub fn knockback(velocity: Vec3, direction: Vec3, strength: f64) -> Vec3 {
+    let Some(push) = Vec3::new(direction.x, 0.0, direction.z).normalize() else {
+        return velocity;
+    };
+
+    Vec3::new(
+        velocity.x / 2.0 + push.x * strength,
+        (velocity.y / 2.0 + strength).min(0.4),
+        velocity.z / 2.0 + push.z * strength,
+    )
+}
+
+/// Creates the packet that sets the velocity of an entity, the velocity is in blocks per tick.
+fn velocity_packet(entity_id: i32, velocity: Vec3) -> SetEntityVelocityPacket {
+    SetEntityVelocityPacket {
+        entity_id: VarInt::from(entity_id),
+        velocity_x: Velocity::from_f64(velocity.x),
+        velocity_y: Velocity::from_f64(velocity.y),
+        velocity_z: Velocity::from_f64(velocity.z),
+    }
+}
+
+/// Creates the packet that shows the health of a player, with a full food bar.
+fn health_packet(health: f32) -> SetHealthPacket {
+    SetHealthPacket {
+        health,
+        food: VarInt::from(20),
+        saturation: 5.0,
+    }
+}
@@ -0,0 +1,333 @@
+use std::{
+    any::{Any, TypeId},
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use crate::entity::next_entity_id;
+
+/// A component that can be attached to an entity, like its position or the player riding it.
+///
+/// This is implemented for every type that can be shared between tasks.
+pub trait Component: Any + Send + Sync {}
+
+impl<T: Any + Send + Sync> Component for T {}
+
+/// The components of a single type, stored as a sparse set.
+///
+/// The components are stored next to each other, so iterating over them doesn't have to skip any entities.
+/// Removing a component moves the last component into its place, so the order of the components changes.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::components::ComponentStorage;
+///
+/// let mut storage = ComponentStorage::new();
+/// storage.insert(1, "boat");
+/// storage.insert(7, "minecart");
+///
+/// assert_eq!(storage.get(7), Some(&"minecart"));
+/// assert_eq!(storage.remove(1), Some("boat"));
+/// assert_eq!(storage.iter().collect::<Vec<_>>(), vec![(7, &"minecart")]);
+/// ```
+///
+/// # Fields
+/// - `sparse` - The index of the component of every entity.
+/// - `entities` - The entity of every component, in the same order as the components.
+/// - `components` - The components.
+#[derive(Debug, Clone)]
+pub struct ComponentStorage<T> {
+    sparse: HashMap<i32, usize>,
+    entities: Vec<i32>,
+    components: Vec<T>,
+}
+
+impl<T> Default for ComponentStorage<T> {
+    fn default() -> Self {
+        Self {
+            sparse: HashMap::new(),
+            entities: Vec::new(),
+            components: Vec::new(),
+        }
+    }
+}
+
+impl<T> ComponentStorage<T> {
+    /// Creates a new empty `[ComponentStorage]`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Attaches the component to the entity.
+    ///
+    /// # Returns
+    /// The component the entity had before.
+    pub fn insert(&mut self, entity_id: i32, component: T) -> Option<T> {
+        if let Some(&index) = self.sparse.get(&entity_id) {
+            return Some(std::mem::replace(&mut self.components[index], component));
+        }
+
+        self.sparse.insert(entity_id, self.components.len());
+        self.entities.push(entity_id);
+        self.components.push(component);
+        None
+    }
+
+    /// Removes the component of the entity.
+    pub fn remove(&mut self, entity_id: i32) -> Option<T> {
+        let index = self.sparse.remove(&entity_id)?;
+
+        self.entities.swap_remove(index);
+        let component = self.components.swap_remove(index);
+        if let Some(&moved) = self.entities.get(index) {
+            self.sparse.insert(moved, index);
+        }
+
+        Some(component)
+    }
+
+    /// Returns the component of the entity.
+    pub fn get(&self, entity_id: i32) -> Option<&T> {
+        let index = *self.sparse.get(&entity_id)?;
+        Some(&self.components[index])
+    }
+
+    /// Returns the component of the entity, which can be changed.
+    pub fn get_mut(&mut self, entity_id: i32) -> Option<&mut T> {
+        let index = *self.sparse.get(&entity_id)?;
+        Some(&mut self.components[index])
+    }
+
+    /// Returns whether the entity has the component.
+    pub fn contains(&self, entity_id: i32) -> bool {
+        self.sparse.contains_key(&entity_id)
+    }
+
+    /// Returns the amount of entities with the component.
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    /// Returns whether no entity has the component.
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// Returns every entity together with its component.
+    pub fn iter(&self) -> impl Iterator<Item = (i32, &T)> + '_ {
+        self.entities.iter().copied().zip(&self.components)
+    }
+
+    /// Returns every entity together with its component, which can be changed.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (i32, &mut T)> + '_ {
+        self.entities.iter().copied().zip(&mut self.components)
+    }
+}
+
+/// The storage of a component type without knowing the type, so an entity can be removed from every storage.
+trait ErasedStorage: Any + Send + Sync {
+    fn remove_entity(&mut self, entity_id: i32);
+
+    fn as_any(&self) -> &dyn Any;
+
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Component> ErasedStorage for ComponentStorage<T> {
+    fn remove_entity(&mut self, entity_id: i32) {
+        self.remove(entity_id);
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Stores the entities of the server together with their components.
+///
+/// Every component type has its own `[ComponentStorage]`, so systems only look at the components they need.
+/// The tick loop iterates over a single component with `[Entities::iter_mut]`, tracking systems can combine
+/// two components with `[Entities::join]`.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::{components::Entities, raycast::Vec3};
+///
+/// struct Velocity(Vec3);
+///
+/// let mut entities = Entities::new();
+/// let boat = entities.spawn();
+/// entities.insert(boat, Vec3::new(0.0, 63.0, 0.0));
+/// entities.insert(boat, Velocity(Vec3::new(0.5, 0.0, 0.0)));
+///
+/// let marker = entities.spawn();
+/// entities.insert(marker, Vec3::new(10.0, 63.0, 10.0));
+///
+/// let moving: Vec<_> = entities.join::<Vec3, Velocity>().map(|(entity_id, _, _)| entity_id).collect();
+/// assert_eq!(moving, vec![boat]);
+///
+/// for (_, position) in entities.iter_mut::<Vec3>() {
+///     position.y += 1.0;
+/// }
+/// assert_eq!(entities.get::<Vec3>(marker), Some(&Vec3::new(10.0, 64.0, 10.0)));
+///
+/// assert!(entities.despawn(boat));
+/// assert!(entities.get::<Velocity>(boat).is_none());
+/// assert_eq!(entities.len(), 1);
+/// ```
+///
+/// # Fields
+/// - `entities` - The IDs of all entities.
+/// - `storages` - The `[ComponentStorage]` of every component type.
+#[derive(Default)]
+pub struct Entities {
+    entities: HashSet<i32>,
+    storages: HashMap<TypeId, Box<dyn ErasedStorage>>,
+}
+
+impl fmt::Debug for Entities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Entities")
+            .field("entities", &self.entities)
+            .field("components", &self.storages.len())
+            .finish()
+    }
+}
+
+impl Entities {
+    /// Creates a new `[Entities]` without any entities.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new entity without any components.
+    ///
+    /// # Returns
+    /// The entity ID of the entity, which is unique for the whole server.
+    pub fn spawn(&mut self) -> i32 {
+        let entity_id = next_entity_id();
+        self.entities.insert(entity_id);
+        entity_id
+    }
+
+    /// Adds an entity that already has an entity ID, like a player.
+    ///
+    /// # Returns
+    /// `false` if the entity was already added.
+    pub fn add(&mut self, entity_id: i32) -> bool {
+        self.entities.insert(entity_id)
+    }
+
+    /// Removes an entity together with all of its components.
+    ///
+    /// # Returns
+    /// `false` if the entity doesn't exist.
+    pub fn despawn(&mut self, entity_id: i32) -> bool {
+        if !self.entities.remove(&entity_id) {
+            return false;
+        }
+
+        for storage in self.storages.values_mut() {
+            storage.remove_entity(entity_id);
+        }
+        true
+    }
+
+    /// Returns whether the entity exists.
+    pub fn contains(&self, entity_id: i32) -> bool {
+        self.entities.contains(&entity_id)
+    }
+
+    /// Returns the amount of entities.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns whether there are no entities.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Returns the IDs of all entities.
+    pub fn ids(&self) -> impl Iterator<Item = i32> + '_ {
+        self.entities.iter().copied()
+    }
+
+    /// Attaches a component to an entity, a component of the same type is replaced.
+    ///
+    /// # Returns
+    /// The component the entity had before, or the given component if the entity doesn't exist.
+    pub fn insert<T: Component>(&mut self, entity_id: i32, component: T) -> Option<T> {
+        if !self.contains(entity_id) {
+            return Some(component);
+        }
+
+        self.storage_mut::<T>().insert(entity_id, component)
+    }
+
+    /// Removes a component from an entity.
+    pub fn remove<T: Component>(&mut self, entity_id: i32) -> Option<T> {
+        self.existing_storage_mut::<T>()?.remove(entity_id)
+    }
+
+    /// Returns the component of an entity.
+    pub fn get<T: Component>(&self, entity_id: i32) -> Option<&T> {
+        self.storage::<T>()?.get(entity_id)
+    }
+
+    /// Returns the component of an entity, which can be changed.
+    pub fn get_mut<T: Component>(&mut self, entity_id: i32) -> Option<&mut T> {
+        self.existing_storage_mut::<T>()?.get_mut(entity_id)
+    }
+
+    /// Returns every entity that has the component, together with the component.
+    pub fn iter<T: Component>(&self) -> impl Iterator<Item = (i32, &T)> + '_ {
+        self.storage::<T>()
+            .into_iter()
+            .flat_map(ComponentStorage::iter)
+    }
+
+    /// Returns every entity that has the component, together with the component, which can be changed.
+    pub fn iter_mut<T: Component>(&mut self) -> impl Iterator<Item = (i32, &mut T)> + '_ {
+        self.existing_storage_mut::<T>()
+            .into_iter()
+            .flat_map(ComponentStorage::iter_mut)
+    }
+
+    /// Returns every entity that has both components, together with the components.
+    pub fn join<A: Component, B: Component>(&self) -> impl Iterator<Item = (i32, &A, &B)> + '_ {
+        let other = self.storage::<B>();
+        self.iter::<A>().filter_map(move |(entity_id, a)| {
+            let b = other?.get(entity_id)?;
+            Some((entity_id, a, b))
+        })
+    }
+
+    /// Returns the storage of a component type, `None` if no entity ever had the component.
+    pub fn storage<T: Component>(&self) -> Option<&ComponentStorage<T>> {
+        self.storages
+            .get(&TypeId::of::<T>())?
+            .as_any()
+            .downcast_ref()
+    }
+
+    fn existing_storage_mut<T: Component>(&mut self) -> Option<&mut ComponentStorage<T>> {
+        self.storages
+            .get_mut(&TypeId::of::<T>())?
+            .as_any_mut()
+            .downcast_mut()
+    }
+
+    fn storage_mut<T: Component>(&mut self) -> &mut ComponentStorage<T> {
+        self.storages
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(ComponentStorage::<T>::new()))
+            .as_any_mut()
+            .downcast_mut()
+            .unwrap()
+    }
+}
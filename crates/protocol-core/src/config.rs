@@ -0,0 +1,323 @@
+use std::sync::{Arc, Mutex, RwLock};
+
+use protocol_packets::{
+    chunk::Chunk,
+    links::{BuiltInLink, ServerLink},
+    registry::Registries,
+    report::ReportDetail,
+    tags::TagRegistry,
+};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::{
+    audit::AuditLog,
+    auth::{Authenticator, OfflineAuthenticator, ServerKey},
+    combat::Combat,
+    config_cache::ConfigurationCache,
+    entity::RemovalBatch,
+    forwarding::PlayerForwarding,
+    game_rules::GameRules,
+    idle::IdlePolicy,
+    operators::{OperatorList, OPS_FILE},
+    outbound::OutboundSettings,
+    queue::LoginQueue,
+    registry::ClientRegistry,
+    resource_pack::ResourcePack,
+    spatial::SpatialIndex,
+    status::HandshakeRejections,
+    throttle::ThrottleSettings,
+    tickets::DEFAULT_UNLOAD_DELAY,
+    timings::{PacketTimings, Timings},
+    vehicle::VehicleRegistry,
+    view_distance::ViewDistanceController,
+    world::{LoadedChunks, World},
+};
+
+/// The feature flag that is enabled on every vanilla server.
+pub const VANILLA_FEATURE_FLAG: &str = "minecraft:vanilla";
+
+/// The brand the server sends to the client by default, it is shown in the debug screen of the client.
+pub const DEFAULT_BRAND: &str = "minecraft-protocol";
+
+/// Contains the settings of the server that are shared with every client.
+///
+/// Every client gets a snapshot of the config when it connects. Changing the config won't affect existing connections.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::config::ServerConfig;
+///
+/// let config = ServerConfig::default();
+///
+/// assert_eq!(config.feature_flags, vec!["minecraft:vanilla".to_string()]);
+/// ```
+///
+/// # Fields
+/// - `feature_flags` - The feature flags sent to the client during the configuration state.
+/// - `brand` - The brand of the server, which is sent on the `minecraft:brand` channel.
+/// - `spawn` - The position players are teleported to when they join.
+/// - `links` - The links shown in the pause menu of the client, there are no links by default.
+/// - `report_details` - The details the client adds to its crash reports and disconnect reports.
+/// - `resource_packs` - The resource packs sent to the client during the configuration state, there are no packs by default.
+/// - `seed` - The seed of the world.
+/// - `authenticator` - Verifies players that log in, players join without authentication by default.
+/// - `server_key` - The RSA key used for the encryption handshake of authenticators that require encryption.
+/// - `registries` - The registries sent to the client during the configuration state, the vanilla registries by default.
+/// - `tags` - The tags sent to the client after the registries, the vanilla fluid and damage type tags by default.
+/// - `configuration_cache` - The written registry and tag packets, it has to be replaced when the registries or tags change.
+/// - `login_queue` - Limits the amount of players logging in at the same time, unlimited by default.
+/// - `game_rules` - The game rules of the server, these are shared with existing connections.
+/// - `operators` - The operators of the server, these are shared with existing connections. No operators by default.
+/// - `clients` - The clients that are currently connected, clients use it to tell each other about changes.
+/// - `spatial` - The positions of the players and entities in the game, used to find the players near something.
+/// - `vehicles` - The vehicles placed by the server and the players riding them.
+/// - `removals` - The entities removed during the current tick, the clients remove them once every tick.
+/// - `combat` - Handles attacks between players, `None` if players can't attack each other, which is the default.
+/// - `idle_policy` - Warns and kicks players that are idle, `None` if idle players stay in the game, which is the default.
+/// - `audit_log` - Records the chat messages, commands, joins and quits of the players, `None` if nothing is recorded, which is the default.
+/// - `outbound` - The limits of the queue of packets that are written to every client.
+/// - `throttle` - Limits the connections per IP address and the packets per client.
+/// - `proxy_protocol` - Whether new connections start with a PROXY protocol header, which contains the address of the client.
+/// - `forwarding` - How a proxy like BungeeCord or Velocity forwards the players, players are authenticated by the server if they aren't forwarded.
+/// - `view_distance` - Scales the view distance with the load of the server, `None` if the view distance is fixed, which is the default.
+/// - `packet_timings` - Counts the packets and the time spent on them, `None` if packets aren't timed, which is the default.
+/// - `timings` - Captures the spans of ticks, like the chunks sent and the packets flushed to the players.
+/// - `strict_handshake` - Whether handshakes for another port than the port of the listener are rejected, disabled by default.
+/// - `handshake_rejections` - Counts the handshakes that were rejected.
+/// - `world` - The world whose chunks are streamed to the players, `None` if no chunks are sent, which is the default.
+/// - `chunks` - The chunks of the world that are loaded, they stay loaded while a player sees them.
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    pub feature_flags: Vec<String>,
+    pub brand: String,
+    pub spawn: SpawnPosition,
+    pub links: ServerLinks,
+    pub report_details: Vec<ReportDetail>,
+    pub resource_packs: Vec<ResourcePack>,
+    pub seed: i64,
+    pub authenticator: Arc<dyn Authenticator>,
+    pub server_key: Arc<ServerKey>,
+    pub registries: Arc<Registries>,
+    pub tags: Arc<TagRegistry>,
+    pub configuration_cache: Arc<ConfigurationCache>,
+    pub login_queue: Arc<LoginQueue>,
+    pub game_rules: Arc<RwLock<GameRules>>,
+    pub operators: Arc<RwLock<OperatorList>>,
+    pub clients: Arc<ClientRegistry>,
+    pub spatial: Arc<RwLock<SpatialIndex>>,
+    pub vehicles: Arc<VehicleRegistry>,
+    pub removals: Arc<Mutex<RemovalBatch<Uuid>>>,
+    pub combat: Option<Arc<Combat>>,
+    pub idle_policy: Option<Arc<dyn IdlePolicy>>,
+    pub audit_log: Option<Arc<AuditLog>>,
+    pub outbound: OutboundSettings,
+    pub throttle: ThrottleSettings,
+    pub proxy_protocol: bool,
+    pub forwarding: PlayerForwarding,
+    pub view_distance: Option<Arc<ViewDistanceController>>,
+    pub packet_timings: Option<Arc<PacketTimings>>,
+    pub timings: Arc<Timings>,
+    pub strict_handshake: bool,
+    pub handshake_rejections: Arc<HandshakeRejections>,
+    pub world: Option<Arc<dyn World>>,
+    pub chunks: Arc<Mutex<LoadedChunks>>,
+}
+
+impl ServerConfig {
+    /// Returns the hashed seed of the world, which is sent to the client instead of the real seed.
+    pub fn hashed_seed(&self) -> i64 {
+        hash_seed(self.seed)
+    }
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        let clients = Arc::new(ClientRegistry::default());
+        let spatial = Arc::new(RwLock::new(SpatialIndex::new()));
+        let removals = Arc::new(Mutex::new(RemovalBatch::new()));
+
+        Self {
+            feature_flags: vec![VANILLA_FEATURE_FLAG.to_string()],
+            brand: DEFAULT_BRAND.to_string(),
+            spawn: SpawnPosition::default(),
+            links: ServerLinks::default(),
+            report_details: Vec::new(),
+            resource_packs: Vec::new(),
+            seed: 0,
+            authenticator: Arc::new(OfflineAuthenticator),
+            server_key: Arc::new(ServerKey::new()),
+            registries: Arc::new(Registries::vanilla()),
+            tags: Arc::new(TagRegistry::vanilla()),
+            configuration_cache: Arc::new(ConfigurationCache::new()),
+            login_queue: LoginQueue::unlimited(),
+            game_rules: Arc::new(RwLock::new(GameRules::default())),
+            operators: Arc::new(RwLock::new(OperatorList::new(OPS_FILE))),
+            vehicles: Arc::new(VehicleRegistry::new(
+                clients.clone(),
+                spatial.clone(),
+                removals.clone(),
+            )),
+            removals,
+            clients,
+            spatial,
+            combat: None,
+            idle_policy: None,
+            audit_log: None,
+            outbound: OutboundSettings::default(),
+            throttle: ThrottleSettings::default(),
+            proxy_protocol: false,
+            forwarding: PlayerForwarding::None,
+            view_distance: None,
+            packet_timings: None,
+            timings: Arc::new(Timings::new()),
+            strict_handshake: false,
+            handshake_rejections: Arc::new(HandshakeRejections::default()),
+            world: None,
+            chunks: Arc::new(Mutex::new(LoadedChunks::new(DEFAULT_UNLOAD_DELAY))),
+        }
+    }
+}
+
+/// Hashes the seed of a world the same way the vanilla server does.
+///
+/// The client uses the hashed seed for biome noise, so the real seed is never sent.
+/// The hash is made out of the first 8 bytes of the SHA-256 hash of the seed.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::config::hash_seed;
+///
+/// assert_eq!(hash_seed(12345), 293737985876514017);
+/// ```
+pub fn hash_seed(seed: i64) -> i64 {
+    let hash = Sha256::digest(seed.to_le_bytes());
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&hash[..8]);
+    i64::from_le_bytes(bytes)
+}
+
+/// Represents the position players spawn at.
+///
+/// # Fields
+/// - `x` - The X position.
+/// - `y` - The Y position.
+/// - `z` - The Z position.
+/// - `yaw` - The yaw in degrees.
+/// - `pitch` - The pitch in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpawnPosition {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+impl SpawnPosition {
+    /// Creates a spawn position on top of the highest block of the given column.
+    ///
+    /// Players are placed in the center of the block, so they don't spawn inside a wall.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_core::config::SpawnPosition;
+    /// use protocol_packets::chunk::Chunk;
+    ///
+    /// let chunk = Chunk::flat(0, 0, 24, 0, &[79, 10, 10, 9]);
+    /// let spawn = SpawnPosition::on_surface(&chunk, 8, 8, -64);
+    ///
+    /// assert_eq!((spawn.x, spawn.y, spawn.z), (8.5, -60.0, 8.5));
+    /// ```
+    ///
+    /// # Parameters
+    /// - `chunk` - The chunk the column is in.
+    /// - `x` - The X position of the column inside the chunk.
+    /// - `z` - The Z position of the column inside the chunk.
+    /// - `min_y` - The Y position of the bottom of the chunk in the world.
+    pub fn on_surface(chunk: &Chunk, x: usize, z: usize, min_y: i32) -> Self {
+        let y = chunk.highest_block_at(x, z).map_or(0, |y| y + 1);
+
+        Self {
+            x: chunk.x as f64 * 16.0 + x as f64 + 0.5,
+            y: (min_y + y as i32) as f64,
+            z: chunk.z as f64 * 16.0 + z as f64 + 0.5,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for SpawnPosition {
+    fn default() -> Self {
+        Self {
+            x: 0.0,
+            y: 64.0,
+            z: 0.0,
+            yaw: 0.0,
+            pitch: 0.0,
+        }
+    }
+}
+
+/// The links of the server, which are shown in the pause menu of the client.
+///
+/// The bug report link is also shown on the disconnect screen.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::config::ServerLinks;
+///
+/// let links = ServerLinks {
+///     website: Some("https://example.com".to_string()),
+///     discord: Some("https://discord.gg/example".to_string()),
+///     ..ServerLinks::default()
+/// };
+///
+/// assert_eq!(links.to_links().len(), 2);
+/// assert!(ServerLinks::default().is_empty());
+/// ```
+///
+/// # Fields
+/// - `website` - The website of the server.
+/// - `discord` - The invite to the Discord server, which is labeled `Discord`.
+/// - `bug_report` - The link to report bugs.
+/// - `other` - Any other links, which are shown after the others.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ServerLinks {
+    pub website: Option<String>,
+    pub discord: Option<String>,
+    pub bug_report: Option<String>,
+    pub other: Vec<ServerLink>,
+}
+
+impl ServerLinks {
+    /// Returns whether there are no links, the links aren't sent to the client in that case.
+    pub fn is_empty(&self) -> bool {
+        self.website.is_none()
+            && self.discord.is_none()
+            && self.bug_report.is_none()
+            && self.other.is_empty()
+    }
+
+    /// Returns the links in the order they are shown.
+    pub fn to_links(&self) -> Vec<ServerLink> {
+        let website = self
+            .website
+            .as_ref()
+            .map(|url| ServerLink::built_in(BuiltInLink::Website, url));
+        let discord = self
+            .discord
+            .as_ref()
+            .map(|url| ServerLink::custom("Discord", url));
+        let bug_report = self
+            .bug_report
+            .as_ref()
+            .map(|url| ServerLink::built_in(BuiltInLink::BugReport, url));
+
+        [website, discord, bug_report]
+            .into_iter()
+            .flatten()
+            .chain(self.other.iter().cloned())
+            .collect()
+    }
+}
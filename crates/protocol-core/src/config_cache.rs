@@ -0,0 +1,137 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use protocol_buf::compression::{CompressionData, CompressionType};
+use protocol_packets::registry::KnownPack;
+
+/// The maximum amount of configuration sequences that are cached, the cache is cleared once it is full.
+pub const MAX_CACHED_SEQUENCES: usize = 32;
+
+/// Identifies a configuration sequence, clients with the same key receive the exact same frames.
+///
+/// # Fields
+/// - `protocol_version` - The protocol version the client sent in its handshake.
+/// - `known_packs` - The data packs the client knows.
+/// - `compression_threshold` - The compression threshold of the connection, `None` if compression is disabled.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConfigurationKey {
+    pub protocol_version: i32,
+    pub known_packs: Vec<KnownPack>,
+    pub compression_threshold: Option<i32>,
+}
+
+impl ConfigurationKey {
+    /// Creates the key for a client with the given protocol version, known packs and compression.
+    pub fn new(
+        protocol_version: i32,
+        known_packs: Vec<KnownPack>,
+        compression: &CompressionData,
+    ) -> Self {
+        let compression_threshold = match compression.compression_type {
+            CompressionType::None => None,
+            CompressionType::Zlib => Some(compression.threshold),
+        };
+
+        Self {
+            protocol_version,
+            known_packs,
+            compression_threshold,
+        }
+    }
+}
+
+/// Caches the registry and tag packets of the configuration state as frames that are ready to be written.
+///
+/// Writing the registries means serializing a lot of NBT and compressing it, which is the same work for every client
+/// with the same `[ConfigurationKey]`. With the cache only the first client pays for it, clients that reconnect,
+/// for example after a transfer, get the cached frames written in one go.
+///
+/// The cache belongs to the registries and tags it was filled with. The `[ServerConnection]` replaces it
+/// whenever the registries or tags are changed.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::compression::CompressionData;
+/// use protocol_core::config_cache::{ConfigurationCache, ConfigurationKey};
+/// use protocol_packets::registry::KnownPack;
+///
+/// let cache = ConfigurationCache::new();
+/// let key = ConfigurationKey::new(767, vec![KnownPack::core()], &CompressionData::default());
+///
+/// let mut encoded = 0;
+/// for _ in 0..3 {
+///     let frames = cache
+///         .get_or_insert_with(key.clone(), || {
+///             encoded += 1;
+///             Ok::<_, ()>(vec![1, 2, 3])
+///         })
+///         .unwrap();
+///     assert_eq!(*frames, vec![1, 2, 3]);
+/// }
+///
+/// assert_eq!(encoded, 1);
+/// assert_eq!(cache.len(), 1);
+/// ```
+///
+/// # Fields
+/// - `sequences` - The cached frames, by the key of the clients they were written for.
+#[derive(Debug, Default)]
+pub struct ConfigurationCache {
+    sequences: Mutex<HashMap<ConfigurationKey, Arc<Vec<u8>>>>,
+}
+
+impl ConfigurationCache {
+    /// Creates a new empty `[ConfigurationCache]`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached frames for the given key.
+    pub fn get(&self, key: &ConfigurationKey) -> Option<Arc<Vec<u8>>> {
+        self.sequences.lock().unwrap().get(key).cloned()
+    }
+
+    /// Returns the cached frames for the given key, or writes and caches them if they aren't cached yet.
+    ///
+    /// The lock isn't held while writing, so two clients connecting at the same time might both write the frames.
+    ///
+    /// # Parameters
+    /// - `key` - The key of the client.
+    /// - `encode` - Writes the frames, nothing is cached if it fails.
+    pub fn get_or_insert_with<E>(
+        &self,
+        key: ConfigurationKey,
+        encode: impl FnOnce() -> Result<Vec<u8>, E>,
+    ) -> Result<Arc<Vec<u8>>, E> {
+        if let Some(frames) = self.get(&key) {
+            return Ok(frames);
+        }
+
+        let frames = Arc::new(encode()?);
+
+        let mut sequences = self.sequences.lock().unwrap();
+        if sequences.len() >= MAX_CACHED_SEQUENCES {
+            sequences.clear();
+        }
+        sequences.insert(key, frames.clone());
+
+        Ok(frames)
+    }
+
+    /// Returns the amount of cached sequences.
+    pub fn len(&self) -> usize {
+        self.sequences.lock().unwrap().len()
+    }
+
+    /// Returns whether nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached sequences.
+    pub fn clear(&self) {
+        self.sequences.lock().unwrap().clear();
+    }
+}
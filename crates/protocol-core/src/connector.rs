@@ -0,0 +1,637 @@
+use std::{
+    io,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use protocol_buf::{
+    buffer::{Buffer, NormalBuffer, PacketBuffer},
+    compression::{CompressionData, CompressionHint, CompressionType},
+    nbt::NbtTag,
+    pool::BufferPool,
+    text::Component,
+    types::{BoundedString, PrefixedOptional, RemainingBytes, VarInt},
+};
+use protocol_packets::{
+    plugin::{encode_brand, BRAND_CHANNEL},
+    registry::KnownPack,
+    settings::ClientInformation,
+    v1_21::{
+        configuration::{
+            AcknowledgeFinishConfigurationPacket, ConfigurationClientInformationPacket,
+            ConfigurationCookieResponsePacket, ServerboundConfigurationKeepAlivePacket,
+            ServerboundConfigurationPluginMessagePacket, ServerboundKnownPacksPacket,
+        },
+        handshake::HandshakePacket,
+        login::{
+            EncryptionResponsePacket, LoginAcknowledgedPacket, LoginCookieResponsePacket,
+            LoginPluginResponsePacket, LoginStartPacket,
+        },
+        status::{PingRequestPacket, StatusRequestPacket},
+        PROTOCOL_VERSION,
+    },
+    ConnectionState, ServerboundPacket,
+};
+use rand::{rngs::OsRng, RngCore};
+use rsa::{pkcs8::DecodePublicKey, Pkcs1v15Encrypt, RsaPublicKey};
+use tokio::net::TcpStream;
+use uuid::Uuid;
+
+use crate::{
+    auth::{
+        server_hash, GameProfile, GameProfileProperty, MojangAuthenticator, OfflineAuthenticator,
+    },
+    client::ClientConnection,
+    interceptor::PacketDirection,
+    outbound::OutboundSettings,
+    recording::PacketRecorder,
+    status::StatusResponse,
+};
+
+/// Represents a connection to another Minecraft server, this is the client side of the protocol.
+///
+/// This can be used to build server pingers, bots or proxies. The connector takes care of the framing and compression,
+/// received packets are returned as `[PacketBuffer]`s so they can be handled or forwarded as they are.
+///
+/// # Examples
+/// ```rust,no_run
+/// use protocol_core::connector::MinecraftConnector;
+/// use protocol_packets::ConnectionState;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut connector = MinecraftConnector::connect("127.0.0.1", 25565).await.unwrap();
+///     connector.handshake(ConnectionState::Status).await.unwrap();
+///
+///     let status = connector.status().await.unwrap();
+///     println!("Status: {}", status);
+/// }
+/// ```
+///
+/// # Fields
+/// - `connection` - The connection to the server.
+/// - `compression` - The compression data, compression is enabled once the server sends a `SetCompression` packet.
+/// - `state` - The state the connection is currently in.
+/// - `address` - The address used to connect to the server.
+/// - `port` - The port used to connect to the server.
+/// - `recorder` - Records every packet sent and received, `None` if the connection isn't recorded.
+pub struct MinecraftConnector {
+    connection: ClientConnection,
+    pub compression: CompressionData,
+    pub state: ConnectionState,
+    address: String,
+    port: u16,
+    recorder: Option<PacketRecorder>,
+}
+
+impl MinecraftConnector {
+    /// Opens a connection to the given server.
+    ///
+    /// # Parameters
+    /// - `address` - The address of the server.
+    /// - `port` - The port of the server.
+    pub async fn connect(address: &str, port: u16) -> io::Result<Self> {
+        let stream = TcpStream::connect((address, port)).await?;
+
+        Ok(Self {
+            connection: ClientConnection::with_incoming(
+                stream,
+                &[],
+                OutboundSettings::default(),
+                None,
+            ),
+            compression: CompressionData::new(-1, CompressionType::None),
+            state: ConnectionState::Handshake,
+            address: address.to_string(),
+            port,
+            recorder: None,
+        })
+    }
+
+    /// Returns the address used to connect to the server.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Returns the port used to connect to the server.
+    pub const fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Sends the handshake packet and switches to the given state.
+    ///
+    /// # Parameters
+    /// - `next_state` - The state after the handshake, this has to be `Status`, `Login` or `Transfer`.
+    pub async fn handshake(&mut self, next_state: ConnectionState) -> io::Result<()> {
+        let Some(intent) = next_state.to_intent() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "State can't follow the handshake",
+            ));
+        };
+
+        let handshake = HandshakePacket {
+            protocol_version: VarInt::from(PROTOCOL_VERSION),
+            server_address: BoundedString::new(self.address.clone()).map_err(invalid_input)?,
+            server_port: self.port,
+            next_state: VarInt::from(intent),
+        };
+        self.send_packet(&handshake).await?;
+
+        // A transfer is logged in like any other login, only the intent tells the server about it.
+        self.state = match next_state {
+            ConnectionState::Transfer => ConnectionState::Login,
+            state => state,
+        };
+        Ok(())
+    }
+
+    /// Sends the given packet to the server.
+    ///
+    /// # Parameters
+    /// - `packet` - The packet to send.
+    pub async fn send_packet<P: ServerboundPacket>(&mut self, packet: &P) -> io::Result<()> {
+        let buffer = packet.write_packet(NormalBuffer::new(BufferPool::global().take()));
+        if let Some(recorder) = &mut self.recorder {
+            let direction = PacketDirection::Serverbound;
+            recorder.record(direction, self.state, *buffer.packet_id, buffer.get_ref())?;
+        }
+        self.connection
+            .send_buffer(buffer, &self.compression, packet.compression_hint())
+            .await
+    }
+
+    /// Sends a packet that was already written, like a packet that is forwarded from a client.
+    ///
+    /// # Parameters
+    /// - `packet_id` - The ID of the packet.
+    /// - `data` - The data of the packet, without the packet ID.
+    pub async fn send_raw(&mut self, packet_id: i32, data: &[u8]) -> io::Result<()> {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(PacketDirection::Serverbound, self.state, packet_id, data)?;
+        }
+
+        let buffer =
+            PacketBuffer::with_id(VarInt::from(packet_id), NormalBuffer::new(data.to_vec()));
+        self.connection
+            .send_buffer(buffer, &self.compression, CompressionHint::Threshold)
+            .await
+    }
+
+    /// Reads the next packet sent by the server.
+    ///
+    /// A `SetCompression` packet received while logging in is handled directly, all following packets are decompressed.
+    /// The packet is still returned, so proxies can forward it.
+    ///
+    /// # Returns
+    /// The next packet, `None` if the server closed the connection.
+    pub async fn read_packet(&mut self) -> io::Result<Option<PacketBuffer>> {
+        let Some(packet) = self.connection.read_packet(&self.compression).await? else {
+            return Ok(None);
+        };
+
+        if let Some(recorder) = &mut self.recorder {
+            let data = &packet.get_ref()[packet.buffer.position() as usize..];
+            let direction = PacketDirection::Clientbound;
+            recorder.record(direction, self.state, *packet.packet_id, data)?;
+        }
+
+        if self.state == ConnectionState::Login && *packet.packet_id == 0x03 {
+            let threshold = *packet.buffer.clone().read_varint().map_err(invalid_data)?;
+            if threshold >= 0 {
+                self.compression = CompressionData::new(threshold, CompressionType::Zlib);
+            }
+        }
+
+        Ok(Some(packet))
+    }
+
+    /// Requests the server list status. The connection has to be in the `Status` state.
+    ///
+    /// # Returns
+    /// The status JSON sent by the server.
+    pub async fn status(&mut self) -> io::Result<String> {
+        self.send_packet(&StatusRequestPacket {}).await?;
+
+        let mut packet = self.expect_packet(0x00).await?;
+        packet.read_string().map_err(invalid_data)
+    }
+
+    /// Sends a ping request and waits for the response. The connection has to be in the `Status` state.
+    ///
+    /// The server closes the connection after the response.
+    ///
+    /// # Parameters
+    /// - `payload` - The payload the server has to send back.
+    ///
+    /// # Returns
+    /// The payload sent back by the server.
+    pub async fn ping(&mut self, payload: i64) -> io::Result<i64> {
+        self.send_packet(&PingRequestPacket { payload }).await?;
+
+        let mut packet = self.expect_packet(0x01).await?;
+        packet.read().map_err(invalid_data)
+    }
+
+    /// Starts logging in with the given username and UUID. The connection has to be in the `Login` state.
+    ///
+    /// The responses of the server can be read with `[MinecraftConnector::read_packet]`.
+    ///
+    /// # Parameters
+    /// - `name` - The username of the player, up to 16 characters.
+    /// - `uuid` - The UUID of the player.
+    pub async fn login_start(&mut self, name: &str, uuid: Uuid) -> io::Result<()> {
+        let packet = LoginStartPacket {
+            name: BoundedString::new(name).map_err(invalid_input)?,
+            uuid: uuid.as_u128(),
+        };
+        self.send_packet(&packet).await
+    }
+
+    /// Encrypts every byte sent and received from now on, this has to be called right after the encryption response was sent.
+    ///
+    /// # Parameters
+    /// - `shared_secret` - The shared secret sent in the encryption response.
+    pub fn enable_encryption(&mut self, shared_secret: &[u8; 16]) {
+        self.connection.enable_encryption(shared_secret);
+    }
+
+    /// Records every packet sent and received from now on, replacing the previous recorder.
+    ///
+    /// The recording can be read with `[PacketReplay]`, to decode the packets again without a connection.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::{connector::MinecraftConnector, recording::PacketRecorder};
+    /// use protocol_packets::ConnectionState;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut connector = MinecraftConnector::connect("127.0.0.1", 25565).await.unwrap();
+    ///     connector.record(PacketRecorder::create("status.mcpr").unwrap());
+    ///     connector.handshake(ConnectionState::Status).await.unwrap();
+    ///     connector.status().await.unwrap();
+    ///
+    ///     connector.stop_recording().unwrap().flush().unwrap();
+    /// }
+    /// ```
+    ///
+    /// # Parameters
+    /// - `recorder` - The recorder the packets are written to.
+    pub fn record(&mut self, recorder: PacketRecorder) {
+        self.recorder = Some(recorder);
+    }
+
+    /// Stops recording the packets of the connection.
+    ///
+    /// # Returns
+    /// The recorder, `None` if the connection wasn't recorded.
+    pub fn stop_recording(&mut self) -> Option<PacketRecorder> {
+        self.recorder.take()
+    }
+
+    /// Closes the connection to the server.
+    pub async fn close(&mut self) -> io::Result<()> {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.flush()?;
+        }
+        self.connection.close().await
+    }
+
+    /// Reads the next packet and checks that it has the given ID.
+    async fn expect_packet(&mut self, packet_id: i32) -> io::Result<PacketBuffer> {
+        match self.read_packet().await? {
+            Some(packet) if *packet.packet_id == packet_id => Ok(packet),
+            Some(packet) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Unexpected packet ID: {}", *packet.packet_id),
+            )),
+            None => Err(io::ErrorKind::UnexpectedEof.into()),
+        }
+    }
+}
+
+/// Requests the status of a server, the way the server list of the client does.
+///
+/// This does the handshake, requests the status and measures the latency with a ping request.
+///
+/// # Examples
+/// ```rust,no_run
+/// use protocol_core::connector;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let status = connector::ping("127.0.0.1", 25565).await.unwrap();
+///     println!("{}/{} players, {:?}", status.online_players, status.max_players, status.latency);
+/// }
+/// ```
+///
+/// # Parameters
+/// - `address` - The address of the server.
+/// - `port` - The port of the server.
+///
+/// # Returns
+/// The status of the server, an error if the server can't be reached or sent an invalid response.
+pub async fn ping(address: &str, port: u16) -> io::Result<StatusResponse> {
+    let mut connector = MinecraftConnector::connect(address, port).await?;
+    connector.handshake(ConnectionState::Status).await?;
+    let json = connector.status().await?;
+
+    let payload = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_millis() as i64);
+    let started = Instant::now();
+    if connector.ping(payload).await? != payload {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Invalid pong payload",
+        ));
+    }
+
+    StatusResponse::from_json(&json, started.elapsed()).map_err(invalid_data)
+}
+
+/// A connection that finished the login and the configuration, it is in the `Play` state.
+///
+/// # Fields
+/// - `connector` - The connection to the server, the packets of the `Play` state are read from it.
+/// - `profile` - The profile of the player, as sent by the server.
+pub struct JoinedSession {
+    pub connector: MinecraftConnector,
+    pub profile: GameProfile,
+}
+
+/// A Minecraft account that is used to join servers with `online-mode=true`.
+///
+/// # Fields
+/// - `name` - The username of the account.
+/// - `uuid` - The UUID of the account.
+/// - `access_token` - The access token of the account, as given by the launcher.
+/// - `session` - The session server the join is announced to.
+#[derive(Debug, Clone)]
+pub struct OnlineAccount {
+    pub name: String,
+    pub uuid: Uuid,
+    pub access_token: String,
+    pub session: MojangAuthenticator,
+}
+
+/// Joins a server in offline mode, the way a client does when the server has `online-mode=false`.
+///
+/// This does the handshake, the login and the configuration. Encryption isn't supported without an account,
+/// so servers that request it fail the login.
+///
+/// # Examples
+/// ```rust,no_run
+/// use protocol_core::connector::{self, MinecraftConnector};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let connector = MinecraftConnector::connect("127.0.0.1", 25565).await.unwrap();
+///     let mut session = connector::login_offline(connector, "Notch").await.unwrap();
+///
+///     println!("Joined as {}", session.profile.name);
+///     while let Some(packet) = session.connector.read_packet().await.unwrap() {
+///         println!("Received packet {}", *packet.packet_id);
+///     }
+/// }
+/// ```
+///
+/// # Parameters
+/// - `connector` - A new connection to the server, before the handshake.
+/// - `username` - The username of the player, its UUID is the offline UUID of the username.
+///
+/// # Returns
+/// The connection in the `Play` state, an error if the server kicked the player or sent invalid packets.
+pub async fn login_offline(
+    connector: MinecraftConnector,
+    username: &str,
+) -> io::Result<JoinedSession> {
+    let uuid = OfflineAuthenticator::offline_uuid(username);
+    join(connector, username, uuid, None).await
+}
+
+/// Joins a server with a Minecraft account, the way a client does when the server has `online-mode=true`.
+///
+/// This does the handshake, the encryption, the login and the configuration. The join is announced to the
+/// session server of the account before the encryption response is sent, so the server can authenticate the player.
+///
+/// # Examples
+/// ```rust,no_run
+/// use protocol_core::{
+///     auth::MojangAuthenticator,
+///     connector::{self, MinecraftConnector, OnlineAccount},
+/// };
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let account = OnlineAccount {
+///         name: "Notch".to_string(),
+///         uuid: "069a79f4-44e9-4726-a5be-fca90e38aaf5".parse().unwrap(),
+///         access_token: "access token".to_string(),
+///         session: MojangAuthenticator::default(),
+///     };
+///
+///     let connector = MinecraftConnector::connect("127.0.0.1", 25565).await.unwrap();
+///     let session = connector::login_online(connector, &account).await.unwrap();
+///     println!("Joined as {}", session.profile.name);
+/// }
+/// ```
+///
+/// # Parameters
+/// - `connector` - A new connection to the server, before the handshake.
+/// - `account` - The account of the player.
+///
+/// # Returns
+/// The connection in the `Play` state, an error if the session server refused the join, the server kicked the player
+/// or sent invalid packets.
+pub async fn login_online(
+    connector: MinecraftConnector,
+    account: &OnlineAccount,
+) -> io::Result<JoinedSession> {
+    join(connector, &account.name, account.uuid, Some(account)).await
+}
+
+/// Does the handshake, the login and the configuration, the encryption is only done with an account.
+async fn join(
+    mut connector: MinecraftConnector,
+    name: &str,
+    uuid: Uuid,
+    account: Option<&OnlineAccount>,
+) -> io::Result<JoinedSession> {
+    connector.handshake(ConnectionState::Login).await?;
+    connector.login_start(name, uuid).await?;
+
+    let mut profile = None;
+    loop {
+        let Some(mut packet) = connector.read_packet().await? else {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        };
+
+        match (connector.state, *packet.packet_id) {
+            (ConnectionState::Login, 0x00) | (ConnectionState::Configuration, 0x02) => {
+                // The login sends the reason as JSON, the configuration as NBT. Only plain NBT reasons are read.
+                let reason = if connector.state == ConnectionState::Login {
+                    let json = packet.read_string().map_err(invalid_data)?;
+                    Component::from_json(&json)
+                        .map_err(invalid_data)?
+                        .to_plain_text()
+                } else {
+                    match packet.read().map_err(invalid_data)? {
+                        NbtTag::String(reason) => reason,
+                        _ => "no plain reason".to_string(),
+                    }
+                };
+                return Err(io::Error::new(
+                    io::ErrorKind::ConnectionRefused,
+                    format!("Disconnected: {}", reason),
+                ));
+            }
+            (ConnectionState::Login, 0x01) => {
+                let Some(account) = account else {
+                    return Err(io::Error::new(
+                        io::ErrorKind::PermissionDenied,
+                        "The server requires an online account",
+                    ));
+                };
+                encrypt(&mut connector, &mut packet, account).await?;
+            }
+            (ConnectionState::Login, 0x02) => {
+                let uuid = Uuid::from_u128(packet.read().map_err(invalid_data)?);
+                let name = packet.read_string().map_err(invalid_data)?;
+                let count = *packet.read_varint().map_err(invalid_data)?;
+                let mut properties = Vec::new();
+                for _ in 0..count {
+                    properties.push(GameProfileProperty {
+                        name: packet.read_string().map_err(invalid_data)?,
+                        value: packet.read_string().map_err(invalid_data)?,
+                        signature: packet
+                            .read::<PrefixedOptional<String>>()
+                            .map_err(invalid_data)?
+                            .0,
+                    });
+                }
+                profile = Some(GameProfile {
+                    uuid,
+                    name,
+                    properties,
+                });
+
+                connector.send_packet(&LoginAcknowledgedPacket {}).await?;
+                connector.state = ConnectionState::Configuration;
+                connector
+                    .send_packet(&ConfigurationClientInformationPacket {
+                        information: ClientInformation::default(),
+                    })
+                    .await?;
+                connector
+                    .send_packet(&ServerboundConfigurationPluginMessagePacket {
+                        channel: BRAND_CHANNEL.to_string(),
+                        data: RemainingBytes::from(encode_brand("vanilla")),
+                    })
+                    .await?;
+            }
+            (ConnectionState::Login, 0x04) => {
+                // Plugin requests aren't understood, which the server is told by answering without data.
+                let message_id = packet.read_varint().map_err(invalid_data)?;
+                connector
+                    .send_packet(&LoginPluginResponsePacket {
+                        message_id,
+                        data: PrefixedOptional(None),
+                    })
+                    .await?;
+            }
+            // The connector doesn't store cookies, so it answers that it doesn't have the cookie.
+            (ConnectionState::Login, 0x05) => {
+                let key = packet.read_string().map_err(invalid_data)?;
+                connector
+                    .send_packet(&LoginCookieResponsePacket {
+                        key,
+                        payload: PrefixedOptional(None),
+                    })
+                    .await?;
+            }
+            (ConnectionState::Configuration, 0x00) => {
+                let key = packet.read_string().map_err(invalid_data)?;
+                connector
+                    .send_packet(&ConfigurationCookieResponsePacket {
+                        key,
+                        payload: PrefixedOptional(None),
+                    })
+                    .await?;
+            }
+            (ConnectionState::Configuration, 0x03) => {
+                connector
+                    .send_packet(&AcknowledgeFinishConfigurationPacket {})
+                    .await?;
+                connector.state = ConnectionState::Play;
+
+                let Some(profile) = profile else {
+                    return Err(invalid_data("The configuration finished before the login"));
+                };
+                return Ok(JoinedSession { connector, profile });
+            }
+            (ConnectionState::Configuration, 0x04) => {
+                let keep_alive_id = packet.read().map_err(invalid_data)?;
+                connector
+                    .send_packet(&ServerboundConfigurationKeepAlivePacket { keep_alive_id })
+                    .await?;
+            }
+            (ConnectionState::Configuration, 0x0E) => {
+                connector
+                    .send_packet(&ServerboundKnownPacksPacket {
+                        packs: vec![KnownPack::core()],
+                    })
+                    .await?;
+            }
+            // Compression is handled by `read_packet`, the registries and tags aren't needed to join.
+            _ => {}
+        }
+    }
+}
+
+/// Answers an encryption request, announces the join to the session server and enables the encryption.
+async fn encrypt(
+    connector: &mut MinecraftConnector,
+    packet: &mut PacketBuffer,
+    account: &OnlineAccount,
+) -> io::Result<()> {
+    let server_id = packet.read_string().map_err(invalid_data)?;
+    let public_key: Vec<u8> = packet.read().map_err(invalid_data)?;
+    let verify_token: Vec<u8> = packet.read().map_err(invalid_data)?;
+    let should_authenticate: bool = packet.read().map_err(invalid_data)?;
+
+    let mut shared_secret = [0; 16];
+    OsRng.fill_bytes(&mut shared_secret);
+
+    if should_authenticate {
+        let hash = server_hash(&server_id, &shared_secret, &public_key);
+        account
+            .session
+            .join(&account.access_token, account.uuid, &hash)
+            .await?;
+    }
+
+    let key = RsaPublicKey::from_public_key_der(&public_key).map_err(invalid_data)?;
+    let response = EncryptionResponsePacket {
+        shared_secret: key
+            .encrypt(&mut OsRng, Pkcs1v15Encrypt, &shared_secret)
+            .map_err(invalid_data)?,
+        verify_token: key
+            .encrypt(&mut OsRng, Pkcs1v15Encrypt, &verify_token)
+            .map_err(invalid_data)?,
+    };
+    connector.send_packet(&response).await?;
+    connector.enable_encryption(&shared_secret);
+
+    Ok(())
+}
+
+/// Converts an error about a value passed to the connector into an IO error.
+fn invalid_input(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, error)
+}
+
+/// Converts a protocol error into an IO error.
+fn invalid_data(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
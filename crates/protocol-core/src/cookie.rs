@@ -0,0 +1,94 @@
+use std::collections::{HashMap, HashSet};
+
+/// The maximum size of the payload of a cookie, the client refuses to store larger cookies.
+pub const MAX_COOKIE_SIZE: usize = 5120;
+
+/// The cookies of a client, which the server stored on it or requested from it.
+///
+/// Cookies are stored by the client until it disconnects, even if it is transferred to another server.
+/// This lets a server pass small pieces of data to the server it transfers the client to, like a session token.
+/// The client can send any data, so cookies should be signed if they are trusted.
+///
+/// Cookies are requested with `[Client::request_cookie]`, the answers of the client are stored here once they arrive.
+/// Answers to cookies that weren't requested are rejected.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::cookie::CookieJar;
+///
+/// let mut cookies = CookieJar::new();
+/// assert!(!cookies.receive("example:session", Some(b"token".to_vec())));
+///
+/// cookies.request("example:session");
+/// assert!(cookies.is_requested("example:session"));
+/// assert!(cookies.receive("example:session", Some(b"token".to_vec())));
+///
+/// assert_eq!(cookies.get("example:session"), Some(&b"token"[..]));
+/// assert!(!cookies.is_requested("example:session"));
+/// ```
+///
+/// # Fields
+/// - `cookies` - The payloads of the known cookies, by their key.
+/// - `requested` - The keys of the cookies that were requested and not answered yet.
+#[derive(Debug, Clone, Default)]
+pub struct CookieJar {
+    cookies: HashMap<String, Vec<u8>>,
+    requested: HashSet<String>,
+}
+
+impl CookieJar {
+    /// Creates a new empty `[CookieJar]`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the payload of the cookie with the given key, `None` if the cookie isn't known.
+    pub fn get(&self, key: &str) -> Option<&[u8]> {
+        self.cookies.get(key).map(Vec::as_slice)
+    }
+
+    /// Returns whether the cookie with the given key was requested and not answered yet.
+    pub fn is_requested(&self, key: &str) -> bool {
+        self.requested.contains(key)
+    }
+
+    /// Records that the cookie with the given key was requested from the client.
+    pub fn request(&mut self, key: impl Into<String>) {
+        self.requested.insert(key.into());
+    }
+
+    /// Records a cookie that was stored on the client.
+    ///
+    /// # Returns
+    /// `false` if the payload is larger than `[MAX_COOKIE_SIZE]`, the cookie isn't recorded in that case.
+    pub fn store(&mut self, key: impl Into<String>, payload: Vec<u8>) -> bool {
+        if payload.len() > MAX_COOKIE_SIZE {
+            return false;
+        }
+
+        self.cookies.insert(key.into(), payload);
+        true
+    }
+
+    /// Records the answer of the client to a cookie request.
+    ///
+    /// # Parameters
+    /// - `key` - The key of the cookie.
+    /// - `payload` - The payload of the cookie, `None` if the client doesn't have the cookie.
+    ///
+    /// # Returns
+    /// `false` if the cookie wasn't requested or the payload is larger than `[MAX_COOKIE_SIZE]`.
+    pub fn receive(&mut self, key: &str, payload: Option<Vec<u8>>) -> bool {
+        if !self.requested.remove(key) {
+            return false;
+        }
+
+        match payload {
+            Some(payload) => self.store(key, payload),
+            None => {
+                self.cookies.remove(key);
+                true
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use crate::view_distance::TICK_DURATION;
+
+/// The cooldowns of the items of a player, like the cooldown of ender pearls after one was thrown.
+///
+/// A cooldown affects every stack of an item. While an item is on cooldown, the server ignores the player using it,
+/// even if a modified client sends the packet anyway.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::cooldown::ItemCooldowns;
+///
+/// let mut cooldowns = ItemCooldowns::new();
+/// cooldowns.set(887, 20);
+///
+/// assert!(cooldowns.is_on_cooldown(887));
+/// assert!(!cooldowns.is_on_cooldown(888));
+///
+/// cooldowns.set(887, 0);
+/// assert!(!cooldowns.is_on_cooldown(887));
+/// ```
+///
+/// # Fields
+/// - `cooldowns` - When the cooldown of an item ends, by the ID of the item.
+#[derive(Debug, Clone, Default)]
+pub struct ItemCooldowns {
+    cooldowns: HashMap<i32, Instant>,
+}
+
+impl ItemCooldowns {
+    /// Creates a new `[ItemCooldowns]` without any cooldowns.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a cooldown on an item, replacing its previous cooldown.
+    ///
+    /// # Parameters
+    /// - `item_id` - The ID of the item in the `minecraft:item` registry.
+    /// - `ticks` - The duration of the cooldown in ticks, `0` removes the cooldown.
+    pub fn set(&mut self, item_id: i32, ticks: u32) {
+        if ticks == 0 {
+            self.cooldowns.remove(&item_id);
+            return;
+        }
+
+        let now = Instant::now();
+        self.cooldowns.retain(|_, ends| *ends > now);
+        self.cooldowns.insert(item_id, now + TICK_DURATION * ticks);
+    }
+
+    /// Returns the time left on the cooldown of an item, `None` if the item isn't on cooldown.
+    pub fn remaining(&self, item_id: i32) -> Option<Duration> {
+        self.cooldowns
+            .get(&item_id)
+            .map(|ends| ends.saturating_duration_since(Instant::now()))
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Returns whether an item is on cooldown.
+    pub fn is_on_cooldown(&self, item_id: i32) -> bool {
+        self.remaining(item_id).is_some()
+    }
+}
@@ -0,0 +1,554 @@
+use std::{future::Future, io, pin::Pin};
+
+use lazy_static::lazy_static;
+use protocol_buf::{buffer::NormalBuffer, error::ProtocolResult};
+use protocol_packets::{
+    chat::is_valid_message,
+    input::{InteractAction, INPUT_UNMOUNT, MAIN_HAND},
+    resource_pack::ResourcePackStatus,
+    v1_21::{
+        configuration::{
+            AcknowledgeFinishConfigurationPacket, ConfigurationClientInformationPacket,
+            ConfigurationCookieResponsePacket, ConfigurationResourcePackResponsePacket,
+            ServerboundConfigurationPluginMessagePacket, ServerboundKnownPacksPacket,
+        },
+        login::{LoginAcknowledgedPacket, LoginCookieResponsePacket, LoginStartPacket},
+        play::{
+            AcknowledgeBlockChangePacket, ChatCommandPacket, ClientInformationPacket,
+            ConfirmTeleportationPacket, InteractPacket, PaddleBoatPacket, PlayCookieResponsePacket,
+            PlayResourcePackResponsePacket, PlayerCommandPacket, PlayerInputPacket,
+            ServerboundChatMessagePacket, ServerboundKeepAlivePacket, ServerboundMoveVehiclePacket,
+            ServerboundPluginMessagePacket, SetHeldItemPacket, SetPlayerOnGroundPacket,
+            SetPlayerPositionAndRotationPacket, SetPlayerPositionPacket, SetPlayerRotationPacket,
+            UseItemPacket,
+        },
+    },
+    ConnectionState, ServerboundPacket,
+};
+use uuid::Uuid;
+
+use crate::{
+    client::Client,
+    combat::AttackResult,
+    raycast::{Rotation, Vec3},
+    vehicle::{Passenger, VehicleMovement},
+};
+
+/// The future returned by a built-in handler, it resolves to `false` if the client should be disconnected.
+pub type BuiltinFuture<'a> = Pin<Box<dyn Future<Output = ProtocolResult<bool>> + Send + 'a>>;
+
+/// A built-in handler, which reads the packet from the buffer and handles it.
+pub type BuiltinHandler = for<'a> fn(&'a mut Client, NormalBuffer) -> BuiltinFuture<'a>;
+
+/// The amount of connection states, every state has its own table.
+const STATES: usize = 6;
+
+lazy_static! {
+    /// The built-in handlers of the server, built once when the first packet is handled.
+    static ref BUILTIN_HANDLERS: DispatchTable = DispatchTable::builtin();
+}
+
+/// A lookup table of packet handlers, indexed by the state and the ID of the packet.
+///
+/// Handlers are declared by the type of the packet they handle, the state and the ID are taken from
+/// `[ServerboundPacket::STATE]` and `[ServerboundPacket::ID]`. Looking up a handler is a plain array access,
+/// so the cost doesn't grow with the amount of handled packets.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::dispatch::{BuiltinFuture, DispatchTable};
+/// use protocol_core::client::Client;
+/// use protocol_buf::buffer::NormalBuffer;
+/// use protocol_packets::{v1_21::play::ServerboundKeepAlivePacket, ConnectionState, ServerboundPacket};
+///
+/// fn ignore(_client: &mut Client, _buffer: NormalBuffer) -> BuiltinFuture<'_> {
+///     Box::pin(async { Ok(true) })
+/// }
+///
+/// let mut table = DispatchTable::new();
+/// table.register::<ServerboundKeepAlivePacket>(ignore);
+///
+/// assert!(table.get(ConnectionState::Play, ServerboundKeepAlivePacket::ID).is_some());
+/// assert!(table.get(ConnectionState::Play, 0x7F).is_none());
+/// assert!(table.get(ConnectionState::Login, ServerboundKeepAlivePacket::ID).is_none());
+/// ```
+///
+/// # Fields
+/// - `states` - The handlers of every state, indexed by the packet ID.
+#[derive(Clone, Default)]
+pub struct DispatchTable {
+    states: [Vec<Option<BuiltinHandler>>; STATES],
+}
+
+impl DispatchTable {
+    /// Creates a new `[DispatchTable]` without any handlers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the table with the handlers the server uses for every client.
+    pub fn builtin() -> Self {
+        let mut table = Self::new();
+
+        table.register::<LoginStartPacket>(login_start);
+        table.register::<LoginAcknowledgedPacket>(login_acknowledged);
+        table.register::<LoginCookieResponsePacket>(login_cookie_response);
+        table.register::<ConfigurationCookieResponsePacket>(configuration_cookie_response);
+        table.register::<PlayCookieResponsePacket>(play_cookie_response);
+        table.register::<ConfigurationResourcePackResponsePacket>(configuration_resource_pack);
+        table.register::<PlayResourcePackResponsePacket>(play_resource_pack);
+        table.register::<ServerboundConfigurationPluginMessagePacket>(configuration_plugin_message);
+        table.register::<ServerboundKnownPacksPacket>(known_packs);
+        table.register::<AcknowledgeFinishConfigurationPacket>(acknowledge_finish_configuration);
+        table.register::<ConfirmTeleportationPacket>(confirm_teleportation);
+        table.register::<ServerboundPluginMessagePacket>(plugin_message);
+        table.register::<ServerboundKeepAlivePacket>(keep_alive);
+        table.register::<ConfigurationClientInformationPacket>(configuration_client_information);
+        table.register::<ClientInformationPacket>(client_information);
+        table.register::<PlayerCommandPacket>(player_command);
+        table.register::<InteractPacket>(interact);
+        table.register::<PlayerInputPacket>(player_input);
+        table.register::<ServerboundMoveVehiclePacket>(move_vehicle);
+        table.register::<PaddleBoatPacket>(paddle_boat);
+        table.register::<ServerboundChatMessagePacket>(chat_message);
+        table.register::<ChatCommandPacket>(chat_command);
+        table.register::<SetPlayerPositionPacket>(set_player_position);
+        table.register::<SetPlayerPositionAndRotationPacket>(set_player_position_and_rotation);
+        table.register::<SetPlayerRotationPacket>(set_player_rotation);
+        table.register::<SetPlayerOnGroundPacket>(set_player_on_ground);
+        table.register::<SetHeldItemPacket>(set_held_item);
+        table.register::<UseItemPacket>(use_item);
+
+        table
+    }
+
+    /// Registers the handler of a packet, a handler that was registered for the same packet is replaced.
+    ///
+    /// # Parameters
+    /// - `handler` - The handler, which reads the packet from the buffer.
+    pub fn register<P: ServerboundPacket>(&mut self, handler: BuiltinHandler) {
+        let handlers = &mut self.states[P::STATE as usize];
+        let index = P::ID as usize;
+        if handlers.len() <= index {
+            handlers.resize(index + 1, None);
+        }
+
+        handlers[index] = Some(handler);
+    }
+
+    /// Returns the handler of the packet with the given state and ID.
+    pub fn get(&self, state: ConnectionState, packet_id: i32) -> Option<BuiltinHandler> {
+        let index = usize::try_from(packet_id).ok()?;
+        self.states[state as usize].get(index).copied().flatten()
+    }
+}
+
+/// Returns the built-in handler of the packet with the given state and ID.
+pub(crate) fn builtin_handler(state: ConnectionState, packet_id: i32) -> Option<BuiltinHandler> {
+    BUILTIN_HANDLERS.get(state, packet_id)
+}
+
+/// Logs a failed write, the client is disconnected in that case.
+fn written(result: io::Result<bool>) -> bool {
+    result.unwrap_or_else(|e| {
+        println!("Failed to write to socket; err = {:?}", e);
+        false
+    })
+}
+
+fn login_start(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = LoginStartPacket::read_packet(buffer)?;
+        Ok(written(client.login(packet).await))
+    })
+}
+
+fn login_acknowledged(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        LoginAcknowledgedPacket::read_packet(buffer)?;
+        client.state = ConnectionState::Configuration;
+
+        if !written(client.wait_in_queue().await) {
+            return Ok(false);
+        }
+        Ok(written(client.start_configuration().await.map(|()| true)))
+    })
+}
+
+fn login_cookie_response(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = LoginCookieResponsePacket::read_packet(buffer)?;
+        cookie_response(client, &packet.key, packet.payload.0).await
+    })
+}
+
+fn configuration_cookie_response(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = ConfigurationCookieResponsePacket::read_packet(buffer)?;
+        cookie_response(client, &packet.key, packet.payload.0).await
+    })
+}
+
+fn play_cookie_response(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = PlayCookieResponsePacket::read_packet(buffer)?;
+        cookie_response(client, &packet.key, packet.payload.0).await
+    })
+}
+
+fn configuration_resource_pack(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = ConfigurationResourcePackResponsePacket::read_packet(buffer)?;
+        resource_pack_response(client, packet.uuid, packet.result.value).await
+    })
+}
+
+fn play_resource_pack(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = PlayResourcePackResponsePacket::read_packet(buffer)?;
+        resource_pack_response(client, packet.uuid, packet.result.value).await
+    })
+}
+
+fn configuration_plugin_message(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = ServerboundConfigurationPluginMessagePacket::read_packet(buffer)?;
+        client.handle_plugin_message(&packet.channel, &packet.data);
+        Ok(true)
+    })
+}
+
+fn known_packs(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = ServerboundKnownPacksPacket::read_packet(buffer)?;
+        let required = client.config.registries.known_packs();
+        if let Some(pack) = required.iter().find(|pack| !packet.packs.contains(pack)) {
+            println!(
+                "Client doesn't know the {}:{} pack, its registry entries can't be loaded",
+                pack.namespace, pack.id
+            );
+        }
+        client.known_packs = packet.packs;
+
+        Ok(written(client.finish_configuration().await.map(|()| true)))
+    })
+}
+
+fn acknowledge_finish_configuration(
+    client: &mut Client,
+    buffer: NormalBuffer,
+) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        AcknowledgeFinishConfigurationPacket::read_packet(buffer)?;
+        Ok(written(client.join_game().await.map(|()| true)))
+    })
+}
+
+fn confirm_teleportation(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = ConfirmTeleportationPacket::read_packet(buffer)?;
+        let teleport_id = *packet.teleport_id;
+        if let Err(violation) = client.sequence.confirm_teleport(teleport_id) {
+            let kicked = client.disconnect_with_reason(violation.to_string()).await;
+            return Ok(written(kicked.map(|()| false)));
+        }
+
+        if client.pending_teleport == Some(teleport_id) {
+            client.pending_teleport = None;
+        }
+        Ok(true)
+    })
+}
+
+fn plugin_message(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = ServerboundPluginMessagePacket::read_packet(buffer)?;
+        client.handle_plugin_message(&packet.channel, &packet.data);
+        Ok(true)
+    })
+}
+
+fn keep_alive(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = ServerboundKeepAlivePacket::read_packet(buffer)?;
+        if !client.keep_alive.acknowledge(packet.keep_alive_id) {
+            println!("Invalid Keep Alive ID: {}", packet.keep_alive_id);
+            return Ok(false);
+        }
+        client.record_latency();
+        Ok(true)
+    })
+}
+
+fn configuration_client_information(
+    client: &mut Client,
+    buffer: NormalBuffer,
+) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = ConfigurationClientInformationPacket::read_packet(buffer)?;
+        client.update_information(packet.information);
+        Ok(true)
+    })
+}
+
+fn client_information(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = ClientInformationPacket::read_packet(buffer)?;
+        client.update_information(packet.information);
+        Ok(true)
+    })
+}
+
+fn player_command(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = PlayerCommandPacket::read_packet(buffer)?;
+        client.mark_active();
+        if client.metadata.apply_command(*packet.action) {
+            client.broadcast_metadata();
+        }
+        Ok(true)
+    })
+}
+
+fn interact(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = InteractPacket::read_packet(buffer)?;
+        client.mark_active();
+        match packet.action {
+            InteractAction::Interact { hand: MAIN_HAND } if !packet.sneaking => {
+                let passenger = Passenger {
+                    uuid: client.uuid,
+                    entity_id: client.entity_id,
+                };
+                client.config.vehicles.mount(*packet.entity_id, passenger);
+            }
+            InteractAction::Attack => {
+                let Some(combat) = client.config.combat.clone() else {
+                    return Ok(true);
+                };
+
+                if combat.attack(client, *packet.entity_id) == AttackResult::InvalidTarget {
+                    let kicked = client
+                        .disconnect_with_reason("Attempting to attack an invalid entity")
+                        .await;
+                    return Ok(written(kicked.map(|()| false)));
+                }
+            }
+            _ => {}
+        }
+        Ok(true)
+    })
+}
+
+fn player_input(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = PlayerInputPacket::read_packet(buffer)?;
+        if packet.flags & INPUT_UNMOUNT != 0 {
+            client.config.vehicles.dismount(client.uuid);
+        }
+        Ok(true)
+    })
+}
+
+fn move_vehicle(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = ServerboundMoveVehiclePacket::read_packet(buffer)?;
+        let values = [
+            packet.x,
+            packet.y,
+            packet.z,
+            packet.yaw as f64,
+            packet.pitch as f64,
+        ];
+        if values.iter().any(|value| !value.is_finite()) {
+            let kicked = client
+                .disconnect_with_reason("Invalid move vehicle packet received")
+                .await;
+            return Ok(written(kicked.map(|()| false)));
+        }
+
+        let position = Vec3::new(packet.x, packet.y, packet.z);
+        let movement =
+            client
+                .config
+                .vehicles
+                .move_vehicle(client.uuid, position, packet.yaw, packet.pitch);
+        match movement {
+            VehicleMovement::TooFast(correction) => Ok(written(
+                client.send_packet(&correction).await.map(|()| true),
+            )),
+            VehicleMovement::Moved | VehicleMovement::NotRiding => Ok(true),
+        }
+    })
+}
+
+fn paddle_boat(_client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        // The paddles are only animated on the client of the passenger.
+        PaddleBoatPacket::read_packet(buffer)?;
+        Ok(true)
+    })
+}
+
+fn chat_message(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = ServerboundChatMessagePacket::read_packet(buffer)?;
+        if !is_valid_message(&packet.message) {
+            let kicked = client
+                .disconnect_with_reason("Illegal characters in chat")
+                .await;
+            return Ok(written(kicked.map(|()| false)));
+        }
+
+        client.mark_active();
+        client.handle_chat(&packet.message);
+        Ok(true)
+    })
+}
+
+fn chat_command(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = ChatCommandPacket::read_packet(buffer)?;
+        if !is_valid_message(&packet.command) {
+            let kicked = client
+                .disconnect_with_reason("Illegal characters in chat")
+                .await;
+            return Ok(written(kicked.map(|()| false)));
+        }
+
+        client.mark_active();
+        client.handle_command(&packet.command);
+        Ok(true)
+    })
+}
+
+fn set_player_position(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = SetPlayerPositionPacket::read_packet(buffer)?;
+        let position = Vec3::new(packet.x, packet.y, packet.z);
+        player_movement(client, Some(position), None, packet.on_ground).await
+    })
+}
+
+fn set_player_position_and_rotation(
+    client: &mut Client,
+    buffer: NormalBuffer,
+) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = SetPlayerPositionAndRotationPacket::read_packet(buffer)?;
+        let position = Vec3::new(packet.x, packet.y, packet.z);
+        let rotation = Rotation::new(packet.yaw, packet.pitch);
+        player_movement(client, Some(position), Some(rotation), packet.on_ground).await
+    })
+}
+
+fn set_player_rotation(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = SetPlayerRotationPacket::read_packet(buffer)?;
+        let rotation = Rotation::new(packet.yaw, packet.pitch);
+        player_movement(client, None, Some(rotation), packet.on_ground).await
+    })
+}
+
+fn set_player_on_ground(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = SetPlayerOnGroundPacket::read_packet(buffer)?;
+        player_movement(client, None, None, packet.on_ground).await
+    })
+}
+
+fn set_held_item(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        let packet = SetHeldItemPacket::read_packet(buffer)?;
+        let selected =
+            usize::try_from(packet.slot).is_ok_and(|slot| client.select_hotbar_slot(slot));
+        if !selected {
+            println!("Player tried to select an invalid slot: {}", packet.slot);
+        }
+        Ok(true)
+    })
+}
+
+fn use_item(client: &mut Client, buffer: NormalBuffer) -> BuiltinFuture<'_> {
+    Box::pin(async move {
+        // Using items has no built-in effect, it only has to be acknowledged.
+        let packet = UseItemPacket::read_packet(buffer)?;
+        client.mark_active();
+        let acknowledge = AcknowledgeBlockChangePacket {
+            sequence: packet.sequence,
+        };
+        Ok(written(
+            client.send_packet(&acknowledge).await.map(|()| true),
+        ))
+    })
+}
+
+/// Stores the answer to a cookie request, clients that answer a cookie that wasn't requested are kicked.
+async fn cookie_response(
+    client: &mut Client,
+    key: &str,
+    payload: Option<Vec<u8>>,
+) -> ProtocolResult<bool> {
+    if !client.cookies.receive(key, payload) {
+        let kicked = client
+            .disconnect_with_reason("Unexpected cookie response")
+            .await;
+        return Ok(written(kicked.map(|()| false)));
+    }
+    Ok(true)
+}
+
+/// Passes the status of a resource pack to the handlers, clients that don't load a required pack are kicked.
+async fn resource_pack_response(
+    client: &mut Client,
+    uuid: u128,
+    result: i32,
+) -> ProtocolResult<bool> {
+    let Some(status) = ResourcePackStatus::from_id(result) else {
+        let kicked = client
+            .disconnect_with_reason("Invalid resource pack status")
+            .await;
+        return Ok(written(kicked.map(|()| false)));
+    };
+
+    let uuid = Uuid::from_u128(uuid);
+    client.resource_packs.record(uuid, status);
+    let required = client
+        .resource_packs
+        .get(uuid)
+        .is_some_and(|pack| pack.required);
+    if required && status.is_failure() {
+        let kicked = client
+            .disconnect_with_reason("This server requires a custom resource pack")
+            .await;
+        return Ok(written(kicked.map(|()| false)));
+    }
+
+    let handlers = client.handlers.clone();
+    handlers.dispatch_resource_pack(client, uuid, status);
+    Ok(true)
+}
+
+/// Kicks clients that sent a position or rotation that isn't finite, like the vanilla server does.
+async fn player_movement(
+    client: &mut Client,
+    position: Option<Vec3>,
+    rotation: Option<Rotation>,
+    on_ground: bool,
+) -> ProtocolResult<bool> {
+    let positions = position
+        .iter()
+        .flat_map(|position| [position.x, position.y, position.z]);
+    let rotations = rotation
+        .iter()
+        .flat_map(|rotation| [rotation.yaw as f64, rotation.pitch as f64]);
+    if positions.chain(rotations).any(|value| !value.is_finite()) {
+        let kicked = client
+            .disconnect_with_reason("Invalid move player packet received")
+            .await;
+        return Ok(written(kicked.map(|()| false)));
+    }
+
+    let moved = client.handle_movement(position, rotation, on_ground).await;
+    Ok(written(moved.map(|()| true)))
+}
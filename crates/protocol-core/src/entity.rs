@@ -0,0 +1,345 @@
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{
+        atomic::{AtomicI32, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use protocol_buf::types::VarInt;
+use protocol_packets::{
+    metadata::{
+        EntityMetadata, MetadataValue, Pose, ENTITY_FLAGS_INDEX, FLAG_FALL_FLYING, FLAG_SNEAKING,
+        FLAG_SPRINTING, FLAG_SWIMMING, PLAYER_MAIN_HAND_INDEX, PLAYER_SKIN_PARTS_INDEX, POSE_INDEX,
+    },
+    settings::{ClientInformation, MAIN_HAND_RIGHT},
+    v1_21::play::RemoveEntitiesPacket,
+};
+
+use tokio::time;
+use uuid::Uuid;
+
+use crate::{
+    raycast::{Aabb, Vec3},
+    registry::{ClientMessage, ClientRegistry, EncodedPacket},
+    view_distance::TICK_DURATION,
+};
+
+/// Collects the entities that are removed during a tick, so every viewer only gets a single `[RemoveEntitiesPacket]`.
+///
+/// The server shares a batch keyed by the UUIDs of the connections, like the `[VehicleRegistry]` uses when a vehicle is removed,
+/// and flushes it once every tick.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::types::VarInt;
+/// use protocol_core::entity::RemovalBatch;
+/// use protocol_packets::v1_21::play::RemoveEntitiesPacket;
+///
+/// let mut batch = RemovalBatch::new();
+/// batch.queue("Notch", 1);
+/// batch.queue("Notch", 2);
+/// batch.queue("jeb_", 1);
+///
+/// let mut packets: Vec<_> = batch.drain().collect();
+/// packets.sort_by_key(|(viewer, _)| *viewer);
+///
+/// assert_eq!(packets.len(), 2);
+/// assert_eq!(packets[0], ("Notch", RemoveEntitiesPacket { entity_ids: vec![VarInt::from(1), VarInt::from(2)] }));
+/// assert!(batch.is_empty());
+/// ```
+///
+/// # Type Parameters
+/// - `K` - The type used to identify a viewer, like the UUID or entity ID of the player.
+///
+/// # Fields
+/// - `removals` - The removed entity IDs of every viewer.
+#[derive(Debug, Clone)]
+pub struct RemovalBatch<K> {
+    removals: HashMap<K, Vec<i32>>,
+}
+
+impl<K> Default for RemovalBatch<K> {
+    fn default() -> Self {
+        Self {
+            removals: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash> RemovalBatch<K> {
+    /// Creates a new empty `[RemovalBatch]`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues the removal of an entity for the given viewer. An entity is only removed once per viewer.
+    ///
+    /// # Parameters
+    /// - `viewer` - The viewer that should no longer see the entity.
+    /// - `entity_id` - The ID of the removed entity.
+    pub fn queue(&mut self, viewer: K, entity_id: i32) {
+        let removals = self.removals.entry(viewer).or_default();
+        if !removals.contains(&entity_id) {
+            removals.push(entity_id);
+        }
+    }
+
+    /// Queues the removal of an entity for all of the given viewers.
+    ///
+    /// This is used when an entity despawns, since every player tracking it has to remove it.
+    pub fn queue_all(&mut self, viewers: impl IntoIterator<Item = K>, entity_id: i32) {
+        for viewer in viewers {
+            self.queue(viewer, entity_id);
+        }
+    }
+
+    /// Checks if there are no queued removals.
+    pub fn is_empty(&self) -> bool {
+        self.removals.is_empty()
+    }
+
+    /// Creates a `[RemoveEntitiesPacket]` for every viewer and clears the batch. This should be called at the end of every tick.
+    pub fn drain(&mut self) -> impl Iterator<Item = (K, RemoveEntitiesPacket)> + '_ {
+        self.removals.drain().map(|(viewer, entity_ids)| {
+            let packet = RemoveEntitiesPacket {
+                entity_ids: entity_ids.into_iter().map(VarInt::from).collect(),
+            };
+            (viewer, packet)
+        })
+    }
+}
+
+impl RemovalBatch<Uuid> {
+    /// Sends the queued removals to the clients and clears the batch, the viewers are the UUIDs of the connections.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_core::{entity::RemovalBatch, registry::{ClientHandle, ClientRegistry}};
+    /// use uuid::Uuid;
+    ///
+    /// let clients = ClientRegistry::new();
+    /// let (handle, mut messages) = ClientHandle::new(Uuid::new_v4());
+    /// clients.register(handle.clone());
+    ///
+    /// let mut batch = RemovalBatch::new();
+    /// batch.queue_all([handle.uuid(), Uuid::new_v4()], 7);
+    ///
+    /// // Clients that disconnected in the meantime are skipped.
+    /// assert_eq!(batch.flush(&clients), 1);
+    /// assert!(messages.try_recv().is_ok());
+    /// assert!(batch.is_empty());
+    /// ```
+    ///
+    /// # Returns
+    /// The amount of clients a `[RemoveEntitiesPacket]` was queued for.
+    pub fn flush(&mut self, clients: &ClientRegistry) -> usize {
+        self.drain()
+            .map(|(viewer, packet)| {
+                let message = ClientMessage::PlayPacket(EncodedPacket::new(&packet));
+                clients.broadcast_to([viewer], message)
+            })
+            .sum()
+    }
+
+    /// Flushes the batch once every `[TICK_DURATION]`.
+    pub(crate) async fn run(batch: Arc<Mutex<Self>>, clients: Arc<ClientRegistry>) {
+        let mut interval = time::interval(TICK_DURATION);
+        loop {
+            interval.tick().await;
+            batch.lock().unwrap().flush(&clients);
+        }
+    }
+}
+
+/// The entity ID that is given to the next entity.
+static NEXT_ENTITY_ID: AtomicI32 = AtomicI32::new(1);
+
+/// Returns a new unique entity ID. Entity IDs are shared by all entities of the server, including players.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::entity::next_entity_id;
+///
+/// assert_ne!(next_entity_id(), next_entity_id());
+/// ```
+pub fn next_entity_id() -> i32 {
+    NEXT_ENTITY_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// The `[PlayerCommandPacket]` action sent when the player starts sneaking.
+const START_SNEAKING: i32 = 0;
+
+/// The `[PlayerCommandPacket]` action sent when the player stops sneaking.
+const STOP_SNEAKING: i32 = 1;
+
+/// The `[PlayerCommandPacket]` action sent when the player leaves a bed.
+const LEAVE_BED: i32 = 2;
+
+/// The `[PlayerCommandPacket]` action sent when the player starts sprinting.
+const START_SPRINTING: i32 = 3;
+
+/// The `[PlayerCommandPacket]` action sent when the player stops sprinting.
+const STOP_SPRINTING: i32 = 4;
+
+/// The `[PlayerCommandPacket]` action sent when the player starts flying with an elytra.
+const START_FALL_FLYING: i32 = 8;
+
+/// The metadata of a player that other clients need to render it, like whether it is sneaking.
+///
+/// The pose is derived from the state of the player, the same way the vanilla server does it every tick.
+/// Sneaking and sprinting are changed by the client through the `[PlayerCommandPacket]`,
+/// swimming has to be set by the server, since it depends on the blocks around the player.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::{entity::PlayerMetadata, raycast::Vec3};
+/// use protocol_packets::metadata::{MetadataValue, Pose, POSE_INDEX};
+///
+/// let mut player = PlayerMetadata::default();
+/// assert!(player.apply_command(0));
+/// assert!(!player.apply_command(0));
+///
+/// assert_eq!(player.pose(), Pose::Sneaking);
+/// assert_eq!(player.hitbox(Vec3::new(0.0, 64.0, 0.0)).max.y, 65.5);
+/// assert_eq!(player.to_metadata().get(POSE_INDEX), Some(&MetadataValue::Pose(Pose::Sneaking)));
+///
+/// player.swimming = true;
+/// assert_eq!(player.pose(), Pose::Swimming);
+/// ```
+///
+/// # Fields
+/// - `sneaking` - Whether the player is sneaking.
+/// - `sprinting` - Whether the player is sprinting.
+/// - `swimming` - Whether the player is swimming or crawling.
+/// - `fall_flying` - Whether the player is flying with an elytra.
+/// - `sleeping` - Whether the player is lying in a bed.
+/// - `skin_parts` - The layers of the skin the player displays.
+/// - `main_hand` - The main hand of the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlayerMetadata {
+    pub sneaking: bool,
+    pub sprinting: bool,
+    pub swimming: bool,
+    pub fall_flying: bool,
+    pub sleeping: bool,
+    pub skin_parts: u8,
+    pub main_hand: i32,
+}
+
+impl Default for PlayerMetadata {
+    fn default() -> Self {
+        Self {
+            sneaking: false,
+            sprinting: false,
+            swimming: false,
+            fall_flying: false,
+            sleeping: false,
+            skin_parts: 0,
+            main_hand: MAIN_HAND_RIGHT,
+        }
+    }
+}
+
+impl PlayerMetadata {
+    /// Returns the entity flags of the player.
+    pub fn flags(&self) -> u8 {
+        [
+            (self.sneaking, FLAG_SNEAKING),
+            (self.sprinting, FLAG_SPRINTING),
+            (self.swimming, FLAG_SWIMMING),
+            (self.fall_flying, FLAG_FALL_FLYING),
+        ]
+        .iter()
+        .filter(|(set, _)| *set)
+        .fold(0, |flags, (_, flag)| flags | flag)
+    }
+
+    /// Returns the pose of the player. Sleeping wins over flying, flying over swimming and swimming over sneaking.
+    pub fn pose(&self) -> Pose {
+        if self.sleeping {
+            Pose::Sleeping
+        } else if self.fall_flying {
+            Pose::FallFlying
+        } else if self.swimming {
+            Pose::Swimming
+        } else if self.sneaking {
+            Pose::Sneaking
+        } else {
+            Pose::Standing
+        }
+    }
+
+    /// Returns the hitbox of the player in its current pose.
+    ///
+    /// # Parameters
+    /// - `position` - The position of the feet of the player.
+    pub fn hitbox(&self, position: Vec3) -> Aabb {
+        let (width, height) = self.pose().player_dimensions();
+        Aabb::entity(position, width, height)
+    }
+
+    /// Returns the position of the eyes of the player in its current pose.
+    ///
+    /// # Parameters
+    /// - `position` - The position of the feet of the player.
+    pub fn eye_position(&self, position: Vec3) -> Vec3 {
+        Vec3::new(
+            position.x,
+            position.y + self.pose().player_eye_height(),
+            position.z,
+        )
+    }
+
+    /// Applies the action of a `[PlayerCommandPacket]`.
+    ///
+    /// # Parameters
+    /// - `action` - The action, actions that don't change the metadata are ignored.
+    ///
+    /// # Returns
+    /// Whether the metadata changed.
+    pub fn apply_command(&mut self, action: i32) -> bool {
+        let previous = *self;
+
+        match action {
+            START_SNEAKING => self.sneaking = true,
+            STOP_SNEAKING => self.sneaking = false,
+            LEAVE_BED => self.sleeping = false,
+            START_SPRINTING => self.sprinting = true,
+            STOP_SPRINTING => self.sprinting = false,
+            START_FALL_FLYING => self.fall_flying = true,
+            _ => {}
+        }
+
+        *self != previous
+    }
+
+    /// Applies the skin parts and the main hand of the settings of the client.
+    ///
+    /// # Returns
+    /// Whether the metadata changed.
+    pub fn apply_information(&mut self, information: &ClientInformation) -> bool {
+        let previous = *self;
+
+        self.skin_parts = information.displayed_skin_parts;
+        self.main_hand = information.main_hand;
+
+        *self != previous
+    }
+
+    /// Creates the metadata other clients need to render the player.
+    pub fn to_metadata(&self) -> EntityMetadata {
+        EntityMetadata::new()
+            .with(ENTITY_FLAGS_INDEX, MetadataValue::Byte(self.flags()))
+            .with(POSE_INDEX, MetadataValue::Pose(self.pose()))
+            .with(
+                PLAYER_SKIN_PARTS_INDEX,
+                MetadataValue::Byte(self.skin_parts),
+            )
+            .with(
+                PLAYER_MAIN_HAND_INDEX,
+                MetadataValue::Byte(self.main_hand as u8),
+            )
+    }
+}
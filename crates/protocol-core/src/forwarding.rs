@@ -0,0 +1,215 @@
+use std::{
+    io::{self, Cursor},
+    net::IpAddr,
+};
+
+use hmac::{Hmac, Mac};
+use protocol_buf::{
+    types::{PrefixedOptional, VarInt},
+    FromNetwork,
+};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::auth::{GameProfile, GameProfileProperty};
+
+/// The plugin channel Velocity forwards the data of the player on.
+pub const VELOCITY_CHANNEL: &str = "velocity:player_info";
+
+/// The version of the Velocity forwarding data that is requested, this version doesn't contain chat signing keys.
+pub const VELOCITY_FORWARDING_VERSION: u8 = 1;
+
+/// The length of the HMAC-SHA256 signature in front of the Velocity forwarding data.
+const VELOCITY_SIGNATURE_LENGTH: usize = 32;
+
+/// Defines how a proxy in front of the server forwards the players to it.
+///
+/// A server behind a proxy doesn't authenticate players itself, the proxy already did that. Instead the proxy
+/// forwards the address, the UUID and the skin of the player. The forwarded data is trusted, so the server must
+/// not be reachable without going through the proxy.
+///
+/// # Variants
+/// - `None` - Players aren't forwarded, they are authenticated with the `[ServerConfig::authenticator]`.
+/// - `BungeeCord` - Players are forwarded in the server address of the handshake, like BungeeCord with `ip_forward` enabled.
+///   This can't be verified, so the server must only be reachable by the proxy.
+/// - `Velocity` - Players are forwarded in a login plugin message, which is signed with the secret shared with the proxy.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum PlayerForwarding {
+    #[default]
+    None,
+    BungeeCord,
+    Velocity {
+        secret: Vec<u8>,
+    },
+}
+
+/// A player forwarded by a proxy.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::forwarding::ForwardedPlayer;
+///
+/// let address = "play.example.com\0203.0.113.7\0069a79f444e94726a5befca90e38aaf5\0[]";
+/// let (host, forwarded) = ForwardedPlayer::from_bungee_address(address).unwrap();
+/// let forwarded = forwarded.unwrap();
+///
+/// assert_eq!(host, "play.example.com");
+/// assert_eq!(forwarded.address, "203.0.113.7".parse::<std::net::IpAddr>().unwrap());
+///
+/// let profile = forwarded.into_profile("Notch");
+/// assert_eq!(profile.uuid.to_string(), "069a79f4-44e9-4726-a5be-fca90e38aaf5");
+/// assert_eq!(profile.name, "Notch");
+/// ```
+///
+/// # Fields
+/// - `address` - The IP address of the player.
+/// - `uuid` - The UUID of the player.
+/// - `name` - The username of the player, only Velocity forwards it.
+/// - `properties` - The properties of the profile of the player, like its skin.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForwardedPlayer {
+    pub address: IpAddr,
+    pub uuid: Uuid,
+    pub name: Option<String>,
+    pub properties: Vec<GameProfileProperty>,
+}
+
+impl ForwardedPlayer {
+    /// Reads the player BungeeCord forwards in the server address of the handshake.
+    ///
+    /// The address has the format `host\0ip\0uuid\0properties`, where the properties are a JSON array.
+    ///
+    /// # Parameters
+    /// - `address` - The server address of the handshake.
+    ///
+    /// # Returns
+    /// The host the player used to connect and the forwarded player, `None` if the address doesn't contain one.
+    /// An error if the forwarded data is malformed.
+    pub fn from_bungee_address(address: &str) -> io::Result<(&str, Option<Self>)> {
+        let mut parts = address.split('\0');
+        let host = parts.next().unwrap_or_default();
+        let (Some(ip), Some(uuid)) = (parts.next(), parts.next()) else {
+            return Ok((host, None));
+        };
+
+        let properties = match parts.next() {
+            Some(properties) => serde_json::from_str(properties)
+                .map_err(|_| invalid("the forwarded properties aren't valid JSON".to_string()))?,
+            None => Vec::new(),
+        };
+        let player = Self {
+            address: ip
+                .parse()
+                .map_err(|_| invalid(format!("invalid forwarded address {ip}")))?,
+            uuid: Uuid::parse_str(uuid)
+                .map_err(|_| invalid(format!("invalid forwarded UUID {uuid}")))?,
+            name: None,
+            properties,
+        };
+
+        Ok((host, Some(player)))
+    }
+
+    /// Reads the player Velocity forwards in its answer to the `velocity:player_info` login plugin request.
+    ///
+    /// The data starts with a HMAC-SHA256 signature of the rest of the data, which is created with the secret
+    /// shared between the proxy and the server.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use hmac::{Hmac, Mac};
+    /// use protocol_buf::{types::VarInt, ToNetwork};
+    /// use protocol_core::forwarding::ForwardedPlayer;
+    /// use sha2::Sha256;
+    ///
+    /// let mut data = VarInt::from(1).to_network();
+    /// data.extend("203.0.113.7".to_string().to_network());
+    /// data.extend(0x069a79f444e94726a5befca90e38aaf5_u128.to_network());
+    /// data.extend("Notch".to_string().to_network());
+    /// data.extend(VarInt::from(0).to_network());
+    ///
+    /// let mut mac = Hmac::<Sha256>::new_from_slice(b"secret").unwrap();
+    /// mac.update(&data);
+    /// let mut signed = mac.finalize().into_bytes().to_vec();
+    /// signed.extend(data);
+    ///
+    /// let forwarded = ForwardedPlayer::from_velocity(&signed, b"secret").unwrap();
+    /// assert_eq!(forwarded.name.as_deref(), Some("Notch"));
+    /// assert!(ForwardedPlayer::from_velocity(&signed, b"other secret").is_err());
+    /// ```
+    ///
+    /// # Parameters
+    /// - `data` - The data of the login plugin response.
+    /// - `secret` - The forwarding secret of the proxy.
+    ///
+    /// # Returns
+    /// The forwarded player, an error if the signature doesn't match or the data is malformed.
+    pub fn from_velocity(data: &[u8], secret: &[u8]) -> io::Result<Self> {
+        if data.len() < VELOCITY_SIGNATURE_LENGTH {
+            return Err(invalid("the forwarded data isn't signed".to_string()));
+        }
+
+        let (signature, data) = data.split_at(VELOCITY_SIGNATURE_LENGTH);
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret)
+            .map_err(|_| invalid("invalid forwarding secret".to_string()))?;
+        mac.update(data);
+        mac.verify_slice(signature).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "the signature of the forwarded data doesn't match",
+            )
+        })?;
+
+        let mut buffer = Cursor::new(data);
+        let version = *read::<VarInt>(&mut buffer)?;
+        if version < 1 {
+            return Err(invalid(format!("unsupported forwarding version {version}")));
+        }
+
+        let ip = read::<String>(&mut buffer)?;
+        let address = ip
+            .parse()
+            .map_err(|_| invalid(format!("invalid forwarded address {ip}")))?;
+        let uuid = Uuid::from_u128(read(&mut buffer)?);
+        let name = read::<String>(&mut buffer)?;
+
+        let count = *read::<VarInt>(&mut buffer)?;
+        let mut properties = Vec::new();
+        for _ in 0..count {
+            properties.push(GameProfileProperty {
+                name: read(&mut buffer)?,
+                value: read(&mut buffer)?,
+                signature: read::<PrefixedOptional<String>>(&mut buffer)?.0,
+            });
+        }
+
+        Ok(Self {
+            address,
+            uuid,
+            name: Some(name),
+            properties,
+        })
+    }
+
+    /// Turns the forwarded player into its profile.
+    ///
+    /// # Parameters
+    /// - `name` - The username sent by the client, this is used if the proxy didn't forward the username.
+    pub fn into_profile(self, name: &str) -> GameProfile {
+        GameProfile {
+            uuid: self.uuid,
+            name: self.name.unwrap_or_else(|| name.to_string()),
+            properties: self.properties,
+        }
+    }
+}
+
+/// Reads a value of the forwarded data.
+fn read<T: FromNetwork>(buffer: &mut Cursor<&[u8]>) -> io::Result<T> {
+    T::from_network(buffer).map_err(|e| invalid(format!("malformed forwarded data: {e}")))
+}
+
+/// Creates the error of malformed forwarded data.
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+use protocol_packets::v1_21::play::{EntityEventPacket, GameEventPacket, LoginPlayPacket};
+
+use crate::registry::EncodedPacket;
+
+/// Whether players respawn without seeing the respawn screen.
+pub const DO_IMMEDIATE_RESPAWN: &str = "doImmediateRespawn";
+
+/// Whether the debug screen shows less information.
+pub const REDUCED_DEBUG_INFO: &str = "reducedDebugInfo";
+
+/// Whether the time of day moves forward.
+pub const DO_DAYLIGHT_CYCLE: &str = "doDaylightCycle";
+
+/// Whether players keep their inventory when they die.
+pub const KEEP_INVENTORY: &str = "keepInventory";
+
+/// Whether players can only craft recipes they unlocked.
+pub const DO_LIMITED_CRAFTING: &str = "doLimitedCrafting";
+
+/// The game event that enables or disables the respawn screen.
+const ENABLE_RESPAWN_SCREEN_EVENT: u8 = 11;
+
+/// The entity status that enables the reduced debug info, the next status disables it again.
+const REDUCED_DEBUG_INFO_STATUS: i8 = 22;
+
+/// The value of a game rule.
+///
+/// # Variants
+/// - `Bool` - A game rule that is either enabled or disabled.
+/// - `Int` - A game rule with a number, like `randomTickSpeed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameRuleValue {
+    Bool(bool),
+    Int(i32),
+}
+
+impl From<bool> for GameRuleValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i32> for GameRuleValue {
+    fn from(value: i32) -> Self {
+        Self::Int(value)
+    }
+}
+
+/// A change of a game rule the client has to know about.
+///
+/// # Variants
+/// - `RespawnScreen` - The respawn screen was enabled or disabled, through `doImmediateRespawn`.
+/// - `ReducedDebugInfo` - The reduced debug info was enabled or disabled, through `reducedDebugInfo`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameRuleChange {
+    RespawnScreen(bool),
+    ReducedDebugInfo(bool),
+}
+
+impl GameRuleChange {
+    /// Creates the packet that tells the client about the change.
+    ///
+    /// # Parameters
+    /// - `entity_id` - The entity ID of the player the packet is sent to.
+    pub fn to_packet(&self, entity_id: i32) -> EncodedPacket {
+        match self {
+            Self::RespawnScreen(enabled) => EncodedPacket::new(&GameEventPacket {
+                event: ENABLE_RESPAWN_SCREEN_EVENT,
+                value: if *enabled { 0.0 } else { 1.0 },
+            }),
+            Self::ReducedDebugInfo(enabled) => EncodedPacket::new(&EntityEventPacket {
+                entity_id,
+                entity_status: REDUCED_DEBUG_INFO_STATUS + !enabled as i8,
+            }),
+        }
+    }
+}
+
+/// The game rules of the server.
+///
+/// Game rules that the client knows about are sent automatically, both in the `[LoginPlayPacket]` and when they change.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::game_rules::{GameRuleChange, GameRules, DO_IMMEDIATE_RESPAWN};
+///
+/// let mut rules = GameRules::default();
+/// assert!(rules.do_daylight_cycle());
+///
+/// let change = rules.set(DO_IMMEDIATE_RESPAWN, true);
+///
+/// assert!(rules.do_immediate_respawn());
+/// assert_eq!(change, Some(GameRuleChange::RespawnScreen(false)));
+/// assert_eq!(rules.set(DO_IMMEDIATE_RESPAWN, true), None);
+/// ```
+///
+/// # Fields
+/// - `rules` - The values of the game rules, by their name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameRules {
+    rules: HashMap<String, GameRuleValue>,
+}
+
+impl Default for GameRules {
+    /// Creates the game rules with their vanilla defaults.
+    fn default() -> Self {
+        let mut rules = HashMap::new();
+        for (name, value) in [
+            (DO_IMMEDIATE_RESPAWN, GameRuleValue::Bool(false)),
+            (REDUCED_DEBUG_INFO, GameRuleValue::Bool(false)),
+            (DO_DAYLIGHT_CYCLE, GameRuleValue::Bool(true)),
+            (KEEP_INVENTORY, GameRuleValue::Bool(false)),
+            (DO_LIMITED_CRAFTING, GameRuleValue::Bool(false)),
+        ] {
+            rules.insert(name.to_string(), value);
+        }
+
+        Self { rules }
+    }
+}
+
+impl GameRules {
+    /// Returns the value of the given game rule.
+    pub fn get(&self, name: &str) -> Option<GameRuleValue> {
+        self.rules.get(name).copied()
+    }
+
+    /// Returns the value of the given boolean game rule, `false` if it isn't set or isn't a boolean.
+    pub fn get_bool(&self, name: &str) -> bool {
+        matches!(self.get(name), Some(GameRuleValue::Bool(true)))
+    }
+
+    /// Returns the value of the given number game rule.
+    pub fn get_int(&self, name: &str) -> Option<i32> {
+        match self.get(name)? {
+            GameRuleValue::Int(value) => Some(value),
+            GameRuleValue::Bool(_) => None,
+        }
+    }
+
+    /// Sets the value of the given game rule.
+    ///
+    /// # Returns
+    /// The change the clients have to know about, `None` if the value didn't change or the client doesn't use the game rule.
+    pub fn set(&mut self, name: &str, value: impl Into<GameRuleValue>) -> Option<GameRuleChange> {
+        let value = value.into();
+        if self.rules.insert(name.to_string(), value) == Some(value) {
+            return None;
+        }
+
+        match (name, value) {
+            (DO_IMMEDIATE_RESPAWN, GameRuleValue::Bool(value)) => {
+                Some(GameRuleChange::RespawnScreen(!value))
+            }
+            (REDUCED_DEBUG_INFO, GameRuleValue::Bool(value)) => {
+                Some(GameRuleChange::ReducedDebugInfo(value))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns an iterator over the names and values of all game rules.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, GameRuleValue)> {
+        self.rules
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value))
+    }
+
+    /// Returns whether players respawn without seeing the respawn screen.
+    pub fn do_immediate_respawn(&self) -> bool {
+        self.get_bool(DO_IMMEDIATE_RESPAWN)
+    }
+
+    /// Returns whether the debug screen shows less information.
+    pub fn reduced_debug_info(&self) -> bool {
+        self.get_bool(REDUCED_DEBUG_INFO)
+    }
+
+    /// Returns whether the time of day moves forward.
+    pub fn do_daylight_cycle(&self) -> bool {
+        self.get_bool(DO_DAYLIGHT_CYCLE)
+    }
+
+    /// Returns whether players keep their inventory when they die.
+    pub fn keep_inventory(&self) -> bool {
+        self.get_bool(KEEP_INVENTORY)
+    }
+
+    /// Returns whether players can only craft recipes they unlocked.
+    pub fn do_limited_crafting(&self) -> bool {
+        self.get_bool(DO_LIMITED_CRAFTING)
+    }
+
+    /// Writes the game rules the client knows about into the given `[LoginPlayPacket]`.
+    pub fn apply(&self, packet: &mut LoginPlayPacket) {
+        packet.reduced_debug_info = self.reduced_debug_info();
+        packet.enable_respawn_screen = !self.do_immediate_respawn();
+        packet.do_limited_crafting = self.do_limited_crafting();
+    }
+}
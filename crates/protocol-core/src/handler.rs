@@ -0,0 +1,411 @@
+use std::{collections::HashMap, sync::Arc};
+
+use protocol_buf::{buffer::NormalBuffer, error::ProtocolResult};
+use protocol_packets::{resource_pack::ResourcePackStatus, ConnectionState, ServerboundPacket};
+use uuid::Uuid;
+
+use crate::{
+    client::Client,
+    interceptor::{InterceptedPacket, Interception, PacketInterceptor},
+};
+
+/// A registered handler, which reads the packet from the buffer before calling the callback.
+type Handler = Arc<dyn Fn(&mut Client, &NormalBuffer) -> ProtocolResult<()> + Send + Sync>;
+
+/// A registered plugin channel handler, which receives the raw data of the message.
+type ChannelHandler = Arc<dyn Fn(&mut Client, &[u8]) + Send + Sync>;
+
+/// A registered chat handler, which receives the message and returns whether it is broadcasted.
+type ChatHandler = Arc<dyn Fn(&mut Client, &str) -> bool + Send + Sync>;
+
+/// A registered pass-through sink, which receives the ID and the raw data of a packet the server doesn't handle.
+type PassthroughSink = Arc<dyn Fn(&mut Client, i32, &[u8]) + Send + Sync>;
+
+/// A registered resource pack handler, which receives the UUID of the pack and its new status.
+type ResourcePackHandler = Arc<dyn Fn(&mut Client, Uuid, ResourcePackStatus) + Send + Sync>;
+
+/// Defines when a handler is called, compared to the built-in handling of the packet.
+///
+/// # Variants
+///
+/// - `Before` - The handler is called before the built-in handling.
+/// - `After` - The handler is called after the built-in handling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HandlerPhase {
+    Before,
+    After,
+}
+
+/// Contains the packet handlers registered by the user of the server.
+///
+/// The handlers are called for every packet of the registered type, based on the `[ServerboundPacket::STATE]` and `[ServerboundPacket::ID]` of the packet.
+/// This allows customizing the server without changing the built-in handling.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::handler::{HandlerPhase, PacketHandlers};
+/// use protocol_packets::{v1_21::play::ServerboundKeepAlivePacket, ServerboundPacket};
+///
+/// let mut handlers = PacketHandlers::new();
+/// handlers.on::<ServerboundKeepAlivePacket>(|_client, packet| {
+///     println!("Keep Alive: {}", packet.keep_alive_id);
+/// });
+///
+/// assert!(handlers.contains(HandlerPhase::Before, ServerboundKeepAlivePacket::STATE, ServerboundKeepAlivePacket::ID));
+/// ```
+///
+/// # Fields
+/// - `handlers` - The handlers, grouped by phase, state and packet ID.
+/// - `channels` - The plugin channel handlers, grouped by channel.
+/// - `chat` - The chat handlers, in the order they were registered.
+/// - `resource_packs` - The resource pack handlers, in the order they were registered.
+/// - `interceptors` - The packet interceptors, in the order they were registered.
+/// - `passthrough` - The sinks of the `Play` packets that are passed through, by packet ID.
+#[derive(Clone, Default)]
+pub struct PacketHandlers {
+    handlers: HashMap<(HandlerPhase, ConnectionState, i32), Vec<Handler>>,
+    channels: HashMap<String, Vec<ChannelHandler>>,
+    chat: Vec<ChatHandler>,
+    resource_packs: Vec<ResourcePackHandler>,
+    interceptors: Vec<Arc<dyn PacketInterceptor>>,
+    passthrough: HashMap<i32, PassthroughSink>,
+}
+
+impl PacketHandlers {
+    /// Creates a new `[PacketHandlers]` without any handlers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a handler that is called before the built-in handling of the packet.
+    ///
+    /// # Parameters
+    /// - `handler` - The callback, which receives the client and the packet.
+    pub fn on<P: ServerboundPacket + 'static>(
+        &mut self,
+        handler: impl Fn(&mut Client, &P) + Send + Sync + 'static,
+    ) {
+        self.register(HandlerPhase::Before, handler);
+    }
+
+    /// Registers a handler that is called after the built-in handling of the packet.
+    ///
+    /// # Parameters
+    /// - `handler` - The callback, which receives the client and the packet.
+    pub fn after<P: ServerboundPacket + 'static>(
+        &mut self,
+        handler: impl Fn(&mut Client, &P) + Send + Sync + 'static,
+    ) {
+        self.register(HandlerPhase::After, handler);
+    }
+
+    /// Registers a handler for the given phase.
+    ///
+    /// # Parameters
+    /// - `phase` - When the handler is called.
+    /// - `handler` - The callback, which receives the client and the packet.
+    pub fn register<P: ServerboundPacket + 'static>(
+        &mut self,
+        phase: HandlerPhase,
+        handler: impl Fn(&mut Client, &P) + Send + Sync + 'static,
+    ) {
+        let handler: Handler = Arc::new(move |client, buffer| {
+            let packet = P::read_packet(buffer.clone())?;
+            handler(client, &packet);
+            Ok(())
+        });
+
+        self.handlers
+            .entry((phase, P::STATE, P::ID))
+            .or_default()
+            .push(handler);
+    }
+
+    /// Registers a handler for the messages the client sends on a plugin channel.
+    ///
+    /// The handler is called for messages sent in the `Configuration` and the `Play` state.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_core::handler::PacketHandlers;
+    ///
+    /// let mut handlers = PacketHandlers::new();
+    /// handlers.on_channel("example:ping", |_client, data| {
+    ///     println!("Ping: {:?}", data);
+    /// });
+    ///
+    /// assert!(handlers.has_channel("example:ping"));
+    /// ```
+    ///
+    /// # Parameters
+    /// - `channel` - The identifier of the channel, like `example:ping`.
+    /// - `handler` - The callback, which receives the client and the data of the message.
+    pub fn on_channel(
+        &mut self,
+        channel: impl Into<String>,
+        handler: impl Fn(&mut Client, &[u8]) + Send + Sync + 'static,
+    ) {
+        self.channels
+            .entry(channel.into())
+            .or_default()
+            .push(Arc::new(handler));
+    }
+
+    /// Registers a handler for the chat messages players send.
+    ///
+    /// Messages are broadcasted to every client in the game, unless a handler returns `false`.
+    /// The message isn't passed to the handlers after that, so a handler can replace the message with its own.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_core::handler::PacketHandlers;
+    ///
+    /// let mut handlers = PacketHandlers::new();
+    /// handlers.on_chat(|client, message| {
+    ///     println!("{}: {}", client.uuid, message);
+    ///     !message.contains("spam")
+    /// });
+    ///
+    /// assert!(handlers.has_chat());
+    /// ```
+    ///
+    /// # Parameters
+    /// - `handler` - The callback, which receives the client and the message and returns whether it is broadcasted.
+    pub fn on_chat(&mut self, handler: impl Fn(&mut Client, &str) -> bool + Send + Sync + 'static) {
+        self.chat.push(Arc::new(handler));
+    }
+
+    /// Registers a handler for the resource pack statuses players send.
+    ///
+    /// The handler is called for statuses sent in the `Configuration` and the `Play` state.
+    /// Players that don't load a required pack are kicked, the handlers aren't called for them.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_core::handler::PacketHandlers;
+    ///
+    /// let mut handlers = PacketHandlers::new();
+    /// handlers.on_resource_pack(|client, pack, status| {
+    ///     if status.is_failure() {
+    ///         println!("{} didn't load {}: {:?}", client.uuid, pack, status);
+    ///     }
+    /// });
+    ///
+    /// assert!(handlers.has_resource_pack());
+    /// ```
+    ///
+    /// # Parameters
+    /// - `handler` - The callback, which receives the client, the UUID of the pack and its status.
+    pub fn on_resource_pack(
+        &mut self,
+        handler: impl Fn(&mut Client, Uuid, ResourcePackStatus) + Send + Sync + 'static,
+    ) {
+        self.resource_packs.push(Arc::new(handler));
+    }
+
+    /// Checks if there is any resource pack handler.
+    pub fn has_resource_pack(&self) -> bool {
+        !self.resource_packs.is_empty()
+    }
+
+    /// Registers an interceptor, which sees every packet the clients send and every packet that is sent to them.
+    ///
+    /// The interceptors are called in the order they were registered, until one of them cancels the packet.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_core::{
+    ///     handler::PacketHandlers,
+    ///     interceptor::{Interception, PacketDirection},
+    /// };
+    ///
+    /// let mut handlers = PacketHandlers::new();
+    /// handlers.intercept(|client, packet| {
+    ///     if packet.direction == PacketDirection::Serverbound {
+    ///         println!("{} sent packet {:#04x}", client.uuid, packet.packet_id);
+    ///     }
+    ///     Interception::Continue
+    /// });
+    ///
+    /// assert!(handlers.has_interceptors());
+    /// ```
+    ///
+    /// # Parameters
+    /// - `interceptor` - The callback, which receives the client and the packet and returns whether the packet is kept.
+    pub fn intercept(
+        &mut self,
+        interceptor: impl Fn(&Client, &mut InterceptedPacket) -> Interception + Send + Sync + 'static,
+    ) {
+        self.add_interceptor(interceptor);
+    }
+
+    /// Registers an interceptor that is implemented by a type, like an anti-cheat that keeps its own state.
+    ///
+    /// # Parameters
+    /// - `interceptor` - The interceptor, which can change or cancel the packet.
+    pub fn add_interceptor(&mut self, interceptor: impl PacketInterceptor + 'static) {
+        self.interceptors.push(Arc::new(interceptor));
+    }
+
+    /// Checks if there is any interceptor.
+    pub fn has_interceptors(&self) -> bool {
+        !self.interceptors.is_empty()
+    }
+
+    /// Passes the given `Play` packets to a sink instead of handling them, like when the server is placed in front of another server.
+    ///
+    /// The packets don't have to be known to this server, they are passed on without being read.
+    /// Packets that are passed through skip the interceptors, the packet handlers and the built-in handling.
+    /// A packet ID that was already passed to another sink is moved to the new sink.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_core::handler::PacketHandlers;
+    ///
+    /// let mut handlers = PacketHandlers::new();
+    /// handlers.pass_through([0x3A, 0x3B], |_client, packet_id, data| {
+    ///     println!("Forwarding packet {:#04x} with {} bytes", packet_id, data.len());
+    /// });
+    ///
+    /// assert!(handlers.passes_through(0x3A));
+    /// assert!(!handlers.passes_through(0x00));
+    /// ```
+    ///
+    /// # Parameters
+    /// - `packet_ids` - The IDs of the `Play` packets that are passed through.
+    /// - `sink` - The callback, which receives the client, the packet ID and the data of the packet without its ID.
+    pub fn pass_through(
+        &mut self,
+        packet_ids: impl IntoIterator<Item = i32>,
+        sink: impl Fn(&mut Client, i32, &[u8]) + Send + Sync + 'static,
+    ) {
+        let sink: PassthroughSink = Arc::new(sink);
+        for packet_id in packet_ids {
+            self.passthrough.insert(packet_id, sink.clone());
+        }
+    }
+
+    /// Checks if the `Play` packet with the given ID is passed through.
+    pub fn passes_through(&self, packet_id: i32) -> bool {
+        self.passthrough.contains_key(&packet_id)
+    }
+
+    /// Checks if there is any chat handler.
+    pub fn has_chat(&self) -> bool {
+        !self.chat.is_empty()
+    }
+
+    /// Checks if there is any handler for the given plugin channel.
+    pub fn has_channel(&self, channel: &str) -> bool {
+        self.channels.contains_key(channel)
+    }
+
+    /// Returns the plugin channels that have a handler.
+    pub fn channels(&self) -> impl Iterator<Item = &str> {
+        self.channels.keys().map(String::as_str)
+    }
+
+    /// Checks if there is any handler for the given phase, state and packet ID.
+    pub fn contains(&self, phase: HandlerPhase, state: ConnectionState, packet_id: i32) -> bool {
+        self.handlers.contains_key(&(phase, state, packet_id))
+    }
+
+    /// Calls all handlers of the given phase for the packet.
+    ///
+    /// # Parameters
+    /// - `phase` - The phase of the handlers to call.
+    /// - `client` - The client that sent the packet.
+    /// - `state` - The state the packet was received in.
+    /// - `packet_id` - The ID of the packet.
+    /// - `buffer` - The packet data.
+    ///
+    /// # Returns
+    /// An error if the packet couldn't be read.
+    pub(crate) fn dispatch(
+        &self,
+        phase: HandlerPhase,
+        client: &mut Client,
+        state: ConnectionState,
+        packet_id: i32,
+        buffer: &NormalBuffer,
+    ) -> ProtocolResult<()> {
+        let Some(handlers) = self.handlers.get(&(phase, state, packet_id)) else {
+            return Ok(());
+        };
+
+        for handler in handlers {
+            handler(client, buffer)?;
+        }
+
+        Ok(())
+    }
+
+    /// Calls all handlers of the given plugin channel.
+    ///
+    /// # Returns
+    /// `false` if there is no handler for the channel.
+    pub(crate) fn dispatch_channel(&self, client: &mut Client, channel: &str, data: &[u8]) -> bool {
+        let Some(handlers) = self.channels.get(channel) else {
+            return false;
+        };
+
+        for handler in handlers {
+            handler(client, data);
+        }
+
+        true
+    }
+
+    /// Calls the chat handlers until one of them cancels the message.
+    ///
+    /// # Returns
+    /// `false` if the message shouldn't be broadcasted.
+    pub(crate) fn dispatch_chat(&self, client: &mut Client, message: &str) -> bool {
+        self.chat.iter().all(|handler| handler(client, message))
+    }
+
+    /// Passes a packet through the interceptors, until one of them cancels it.
+    pub(crate) fn dispatch_interceptors(
+        &self,
+        client: &Client,
+        packet: &mut InterceptedPacket,
+    ) -> Interception {
+        for interceptor in &self.interceptors {
+            if interceptor.intercept(client, packet) == Interception::Cancel {
+                return Interception::Cancel;
+            }
+        }
+
+        Interception::Continue
+    }
+
+    /// Passes a `Play` packet to its sink.
+    ///
+    /// # Returns
+    /// `false` if the packet isn't passed through.
+    pub(crate) fn dispatch_passthrough(
+        &self,
+        client: &mut Client,
+        packet_id: i32,
+        data: &[u8],
+    ) -> bool {
+        let Some(sink) = self.passthrough.get(&packet_id) else {
+            return false;
+        };
+
+        sink(client, packet_id, data);
+        true
+    }
+
+    /// Calls all resource pack handlers with the new status of a pack.
+    pub(crate) fn dispatch_resource_pack(
+        &self,
+        client: &mut Client,
+        pack: Uuid,
+        status: ResourcePackStatus,
+    ) {
+        for handler in &self.resource_packs {
+            handler(client, pack, status);
+        }
+    }
+}
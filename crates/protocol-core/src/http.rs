@@ -0,0 +1,299 @@
+use std::{fmt, future::Future, io, pin::Pin, sync::Arc, time::Duration};
+
+use serde::de::DeserializeOwned;
+use tokio::time;
+
+/// The future returned by a `[HttpBackend]`.
+pub type HttpFuture<'a> = Pin<Box<dyn Future<Output = io::Result<HttpResponse>> + Send + 'a>>;
+
+/// A response to a HTTP request.
+///
+/// # Fields
+/// - `status` - The status code of the response.
+/// - `body` - The body of the response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    /// Returns whether the status code is in the `2xx` range.
+    pub const fn is_success(&self) -> bool {
+        self.status >= 200 && self.status < 300
+    }
+
+    /// Parses the body as JSON.
+    pub fn json<T: DeserializeOwned>(&self) -> io::Result<T> {
+        serde_json::from_slice(&self.body)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Returns whether the request should be sent again, this is the case for server errors and rate limits.
+    const fn is_retryable(&self) -> bool {
+        self.status >= 500 || self.status == 429
+    }
+}
+
+/// Sends the HTTP requests of a `[HttpClient]`.
+///
+/// With the `reqwest` feature, which is enabled by default, `[ReqwestBackend]` is used.
+/// Other HTTP clients can be used by implementing this trait.
+pub trait HttpBackend: fmt::Debug + Send + Sync {
+    /// Sends a GET request to the given URL.
+    fn get<'a>(&'a self, url: &'a str) -> HttpFuture<'a>;
+
+    /// Sends a POST request with a JSON body to the given URL.
+    ///
+    /// Backends that only send GET requests don't have to implement this, the request fails with `[io::ErrorKind::Unsupported]`.
+    fn post_json<'a>(&'a self, _url: &'a str, _body: &'a str) -> HttpFuture<'a> {
+        Box::pin(async {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "The HTTP backend doesn't support POST requests",
+            ))
+        })
+    }
+}
+
+/// The settings of a `[HttpClient]`.
+///
+/// # Fields
+/// - `timeout` - The maximum time a single request can take, including connecting.
+/// - `connect_timeout` - The maximum time connecting to the server can take.
+/// - `retries` - The amount of times a failed request is sent again.
+/// - `backoff` - The delay before the first retry, the delay is doubled after every retry.
+/// - `proxy` - The URL of the proxy all requests are sent through, like `http://127.0.0.1:8080`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpConfig {
+    pub timeout: Duration,
+    pub connect_timeout: Duration,
+    pub retries: u32,
+    pub backoff: Duration,
+    pub proxy: Option<String>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            connect_timeout: Duration::from_secs(5),
+            retries: 2,
+            backoff: Duration::from_millis(250),
+            proxy: None,
+        }
+    }
+}
+
+/// The HTTP client used for all outgoing web requests, like authenticating players.
+///
+/// Every request has a timeout, so a stalled web service can't hang the login of a player.
+/// Requests that fail or are answered with a server error are sent again, with an increasing delay.
+///
+/// # Examples
+/// ```rust
+/// use std::{io, sync::{atomic::{AtomicU32, Ordering}, Arc}, time::Duration};
+/// use protocol_core::http::{HttpBackend, HttpClient, HttpConfig, HttpFuture, HttpResponse};
+///
+/// /// Fails twice, then answers.
+/// #[derive(Debug, Default)]
+/// struct FlakyBackend(AtomicU32);
+///
+/// impl HttpBackend for FlakyBackend {
+///     fn get<'a>(&'a self, _url: &'a str) -> HttpFuture<'a> {
+///         Box::pin(async move {
+///             match self.0.fetch_add(1, Ordering::SeqCst) {
+///                 0 => Err(io::ErrorKind::ConnectionReset.into()),
+///                 1 => Ok(HttpResponse { status: 503, body: Vec::new() }),
+///                 _ => Ok(HttpResponse { status: 200, body: b"[1, 2]".to_vec() }),
+///             }
+///         })
+///     }
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let config = HttpConfig { backoff: Duration::from_millis(1), ..HttpConfig::default() };
+///     let client = HttpClient::with_backend(Arc::new(FlakyBackend::default()), config);
+///
+///     let response = client.get("https://example.com").await.unwrap();
+///     assert_eq!(response.json::<Vec<i32>>().unwrap(), vec![1, 2]);
+/// }
+/// ```
+///
+/// # Fields
+/// - `backend` - The backend that sends the requests.
+/// - `config` - The timeouts and retries of the requests.
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    backend: Arc<dyn HttpBackend>,
+    config: HttpConfig,
+}
+
+impl HttpClient {
+    /// Creates a new `[HttpClient]` with the default backend.
+    ///
+    /// Without the `reqwest` feature there is no default backend, every request fails with `[io::ErrorKind::Unsupported]`.
+    pub fn new(config: HttpConfig) -> io::Result<Self> {
+        #[cfg(feature = "reqwest")]
+        let backend: Arc<dyn HttpBackend> = Arc::new(ReqwestBackend::new(&config)?);
+        #[cfg(not(feature = "reqwest"))]
+        let backend: Arc<dyn HttpBackend> = Arc::new(UnsupportedBackend);
+
+        Ok(Self::with_backend(backend, config))
+    }
+
+    /// Creates a new `[HttpClient]` that sends its requests with the given backend.
+    ///
+    /// The timeout of the config is applied to every request, the other settings are up to the backend.
+    pub fn with_backend(backend: Arc<dyn HttpBackend>, config: HttpConfig) -> Self {
+        Self { backend, config }
+    }
+
+    /// Returns the settings of the client.
+    pub const fn config(&self) -> &HttpConfig {
+        &self.config
+    }
+
+    /// Sends a GET request to the given URL.
+    ///
+    /// # Returns
+    /// The response, an error if every attempt failed or timed out. If the last attempt was answered
+    /// with a server error, that response is returned.
+    pub async fn get(&self, url: &str) -> io::Result<HttpResponse> {
+        self.send(url, || self.backend.get(url)).await
+    }
+
+    /// Sends a POST request with a JSON body to the given URL.
+    ///
+    /// # Returns
+    /// The response, an error if every attempt failed or timed out. If the last attempt was answered
+    /// with a server error, that response is returned.
+    pub async fn post_json(&self, url: &str, body: &str) -> io::Result<HttpResponse> {
+        self.send(url, || self.backend.post_json(url, body)).await
+    }
+
+    /// Sends a request until it succeeds or every retry was used.
+    async fn send<'a>(
+        &'a self,
+        url: &str,
+        request: impl Fn() -> HttpFuture<'a>,
+    ) -> io::Result<HttpResponse> {
+        let mut backoff = self.config.backoff;
+        let mut attempt = 0;
+
+        loop {
+            let result = match time::timeout(self.config.timeout, request()).await {
+                Ok(result) => result,
+                Err(_) => Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("Request to {} timed out", url),
+                )),
+            };
+
+            let retry = match &result {
+                Ok(response) => response.is_retryable(),
+                Err(_) => true,
+            };
+            if !retry || attempt >= self.config.retries {
+                return result;
+            }
+
+            attempt += 1;
+            time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+}
+
+impl Default for HttpClient {
+    /// Creates a new `[HttpClient]` with the default backend and settings.
+    ///
+    /// # Panics
+    /// If the backend can't be created, which only happens if the TLS backend can't be initialized.
+    fn default() -> Self {
+        Self::new(HttpConfig::default()).expect("Failed to create the HTTP client")
+    }
+}
+
+/// A `[HttpBackend]` using reqwest, with rustls for TLS.
+#[cfg(feature = "reqwest")]
+#[derive(Debug, Clone)]
+pub struct ReqwestBackend {
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "reqwest")]
+impl ReqwestBackend {
+    /// Creates a new `[ReqwestBackend]` using the timeouts and proxy of the given config.
+    pub fn new(config: &HttpConfig) -> io::Result<Self> {
+        let mut builder = reqwest::Client::builder()
+            .timeout(config.timeout)
+            .connect_timeout(config.connect_timeout);
+        if let Some(proxy) = &config.proxy {
+            builder = builder.proxy(reqwest::Proxy::all(proxy).map_err(io::Error::other)?);
+        }
+
+        Ok(Self {
+            client: builder.build().map_err(io::Error::other)?,
+        })
+    }
+}
+
+#[cfg(feature = "reqwest")]
+impl HttpBackend for ReqwestBackend {
+    fn get<'a>(&'a self, url: &'a str) -> HttpFuture<'a> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .get(url)
+                .send()
+                .await
+                .map_err(io::Error::other)?;
+            let status = response.status().as_u16();
+            let body = response.bytes().await.map_err(io::Error::other)?;
+
+            Ok(HttpResponse {
+                status,
+                body: body.to_vec(),
+            })
+        })
+    }
+
+    fn post_json<'a>(&'a self, url: &'a str, body: &'a str) -> HttpFuture<'a> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .post(url)
+                .header("Content-Type", "application/json")
+                .body(body.to_string())
+                .send()
+                .await
+                .map_err(io::Error::other)?;
+            let status = response.status().as_u16();
+            let body = response.bytes().await.map_err(io::Error::other)?;
+
+            Ok(HttpResponse {
+                status,
+                body: body.to_vec(),
+            })
+        })
+    }
+}
+
+/// The backend used without the `reqwest` feature, every request fails.
+#[cfg(not(feature = "reqwest"))]
+#[derive(Debug, Clone, Copy)]
+struct UnsupportedBackend;
+
+#[cfg(not(feature = "reqwest"))]
+impl HttpBackend for UnsupportedBackend {
+    fn get<'a>(&'a self, _url: &'a str) -> HttpFuture<'a> {
+        Box::pin(async {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "No HTTP backend, enable the `reqwest` feature",
+            ))
+        })
+    }
+}
@@ -0,0 +1,122 @@
+use std::{fmt, time::Duration};
+
+use protocol_buf::text::Component;
+
+/// How often the `[IdlePolicy]` of the server checks the players in the game.
+pub const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The time before the kick in which players are warned by `[IdleTimeout]`.
+pub const IDLE_WARNING: Duration = Duration::from_secs(30);
+
+/// What happens to a player that has been idle for a while.
+///
+/// # Variants
+/// - `Nothing` - The player stays in the game.
+/// - `Warn` - The message is shown in the action bar of the player.
+/// - `Kick` - The player is kicked with the message as the reason.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdleAction {
+    Nothing,
+    Warn(Component),
+    Kick(Component),
+}
+
+/// Decides what happens to players that have been idle, like kicking players that are away from the keyboard.
+///
+/// Players are idle until they move, turn, chat or interact with something. The policy is checked every
+/// `[IDLE_CHECK_INTERVAL]` for every player in the game, so it can be replaced to warn or kick players differently.
+///
+/// # Examples
+/// ```rust
+/// use std::time::Duration;
+/// use protocol_buf::text::Component;
+/// use protocol_core::idle::{IdleAction, IdlePolicy};
+///
+/// /// Kicks players after 5 minutes without warning them.
+/// #[derive(Debug)]
+/// struct StrictPolicy;
+///
+/// impl IdlePolicy for StrictPolicy {
+///     fn check(&self, idle: Duration) -> IdleAction {
+///         if idle >= Duration::from_secs(300) {
+///             IdleAction::Kick(Component::text("Away from keyboard"))
+///         } else {
+///             IdleAction::Nothing
+///         }
+///     }
+/// }
+///
+/// assert_eq!(StrictPolicy.check(Duration::from_secs(10)), IdleAction::Nothing);
+/// ```
+pub trait IdlePolicy: fmt::Debug + Send + Sync {
+    /// Decides what happens to a player.
+    ///
+    /// # Parameters
+    /// - `idle` - How long the player has been idle.
+    fn check(&self, idle: Duration) -> IdleAction;
+}
+
+/// Kicks players that have been idle for too long, like the `player-idle-timeout` of the vanilla server.
+///
+/// Players are warned in their action bar during the last `warning` before they are kicked.
+///
+/// # Examples
+/// ```rust
+/// use std::time::Duration;
+/// use protocol_core::idle::{IdleAction, IdlePolicy, IdleTimeout};
+///
+/// let policy = IdleTimeout::new(Duration::from_secs(600));
+///
+/// assert_eq!(policy.check(Duration::from_secs(60)), IdleAction::Nothing);
+/// assert!(matches!(policy.check(Duration::from_secs(580)), IdleAction::Warn(_)));
+/// assert!(matches!(policy.check(Duration::from_secs(600)), IdleAction::Kick(_)));
+/// ```
+///
+/// # Fields
+/// - `timeout` - How long players can be idle before they are kicked.
+/// - `warning` - How long before the kick players are warned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdleTimeout {
+    pub timeout: Duration,
+    pub warning: Duration,
+}
+
+impl IdleTimeout {
+    /// Creates a new `[IdleTimeout]`, which warns players `[IDLE_WARNING]` before they are kicked.
+    pub const fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            warning: IDLE_WARNING,
+        }
+    }
+
+    /// Changes how long before the kick players are warned, a warning of zero doesn't warn players.
+    pub const fn with_warning(mut self, warning: Duration) -> Self {
+        self.warning = warning;
+        self
+    }
+}
+
+impl IdlePolicy for IdleTimeout {
+    fn check(&self, idle: Duration) -> IdleAction {
+        let Some(remaining) = self
+            .timeout
+            .checked_sub(idle)
+            .filter(|remaining| !remaining.is_zero())
+        else {
+            return IdleAction::Kick(Component::translate(
+                "multiplayer.disconnect.idling",
+                Vec::new(),
+            ));
+        };
+
+        if remaining > self.warning {
+            return IdleAction::Nothing;
+        }
+
+        let seconds = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+        IdleAction::Warn(Component::text(format!(
+            "You will be kicked for being idle in {seconds} seconds"
+        )))
+    }
+}
@@ -0,0 +1,140 @@
+use protocol_buf::{
+    buffer::{Buffer, NormalBuffer, PacketBuffer},
+    error::ProtocolResult,
+    types::VarInt,
+};
+use protocol_packets::{ClientboundPacket, ConnectionState, ServerboundPacket};
+
+use crate::client::Client;
+
+/// The direction a packet is sent in.
+///
+/// # Variants
+/// - `Serverbound` - The packet was sent by the client.
+/// - `Clientbound` - The packet is sent to the client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PacketDirection {
+    Serverbound,
+    Clientbound,
+}
+
+/// Defines what happens with a packet after an interceptor saw it.
+///
+/// # Variants
+/// - `Continue` - The packet is passed to the next interceptor, and then handled or sent like any other packet.
+/// - `Cancel` - The packet is dropped, the interceptors after this one don't see it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Interception {
+    Continue,
+    Cancel,
+}
+
+/// A packet that is passed through the interceptors, before it is handled or written to the client.
+///
+/// The data can be changed by the interceptors, the changed data is handled or sent instead.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::interceptor::{InterceptedPacket, PacketDirection};
+/// use protocol_packets::{
+///     v1_21::play::{ClientboundKeepAlivePacket, ServerboundKeepAlivePacket},
+///     ConnectionState, ServerboundPacket,
+/// };
+///
+/// let mut packet = InterceptedPacket::new(
+///     PacketDirection::Serverbound,
+///     ConnectionState::Play,
+///     ServerboundKeepAlivePacket::ID,
+///     7_i64.to_be_bytes().to_vec(),
+/// );
+/// assert_eq!(packet.read::<ServerboundKeepAlivePacket>().unwrap().keep_alive_id, 7);
+///
+/// packet.replace(&ClientboundKeepAlivePacket { keep_alive_id: 8 });
+/// assert_eq!(packet.data, 8_i64.to_be_bytes());
+/// ```
+///
+/// # Fields
+/// - `direction` - The direction the packet is sent in.
+/// - `state` - The state the connection is in.
+/// - `packet_id` - The ID of the packet.
+/// - `data` - The fields of the packet, without the packet ID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterceptedPacket {
+    pub direction: PacketDirection,
+    pub state: ConnectionState,
+    pub packet_id: i32,
+    pub data: Vec<u8>,
+}
+
+impl InterceptedPacket {
+    /// Creates a new `[InterceptedPacket]`.
+    pub fn new(
+        direction: PacketDirection,
+        state: ConnectionState,
+        packet_id: i32,
+        data: Vec<u8>,
+    ) -> Self {
+        Self {
+            direction,
+            state,
+            packet_id,
+            data,
+        }
+    }
+
+    /// Checks if the packet is the given serverbound packet, based on its state and ID.
+    pub fn is<P: ServerboundPacket>(&self) -> bool {
+        self.direction == PacketDirection::Serverbound
+            && self.state == P::STATE
+            && self.packet_id == P::ID
+    }
+
+    /// Reads the data of the packet as the given packet.
+    ///
+    /// # Returns
+    /// An error if the data isn't a valid packet of that type.
+    pub fn read<P: ServerboundPacket>(&self) -> ProtocolResult<P> {
+        P::read_packet(NormalBuffer::new(self.data.clone()))
+    }
+
+    /// Replaces the packet with another packet, which can have another ID.
+    pub fn replace<P: ClientboundPacket>(&mut self, packet: &P) {
+        let buffer = packet.write_packet(NormalBuffer::new(Vec::new()));
+        self.packet_id = *buffer.packet_id;
+        self.data = buffer.get_ref().to_vec();
+    }
+
+    /// Writes the packet into a `[PacketBuffer]`, which can be handled or sent.
+    pub(crate) fn into_buffer(self) -> PacketBuffer {
+        PacketBuffer::with_id(VarInt::from(self.packet_id), NormalBuffer::new(self.data))
+    }
+}
+
+/// Sees every packet of a client before it is handled or sent, which allows logging, changing or cancelling it.
+///
+/// Serverbound packets are intercepted before the packet handlers and the built-in handling, but after the client was
+/// kicked for sending a packet it isn't allowed to send.
+/// Clientbound packets are intercepted before they are compressed, packets that were compressed ahead of time and the
+/// cached registries aren't intercepted.
+///
+/// Every closure that takes the client and the packet is an interceptor.
+pub trait PacketInterceptor: Send + Sync {
+    /// Intercepts a packet.
+    ///
+    /// # Parameters
+    /// - `client` - The client that sent the packet, or that the packet is sent to.
+    /// - `packet` - The packet, which can be changed.
+    ///
+    /// # Returns
+    /// Whether the packet is handled or sent.
+    fn intercept(&self, client: &Client, packet: &mut InterceptedPacket) -> Interception;
+}
+
+impl<F> PacketInterceptor for F
+where
+    F: Fn(&Client, &mut InterceptedPacket) -> Interception + Send + Sync,
+{
+    fn intercept(&self, client: &Client, packet: &mut InterceptedPacket) -> Interception {
+        self(client, packet)
+    }
+}
@@ -0,0 +1,167 @@
+use protocol_packets::{
+    equipment::{Equipment, EquipmentSlot},
+    input::OFF_HAND,
+    item::{ItemStack, Slot},
+};
+
+/// The amount of slots of the inventory window of a player, including the crafting grid and the armor.
+pub const INVENTORY_SLOTS: usize = 46;
+
+/// The indices of the armor slots in the inventory window, from the helmet to the boots.
+pub const ARMOR_SLOTS: [usize; 4] = [5, 6, 7, 8];
+
+/// The index of the first hotbar slot in the inventory window.
+pub const HOTBAR_START: usize = 36;
+
+/// The amount of slots of the hotbar.
+pub const HOTBAR_SLOTS: usize = 9;
+
+/// The index of the off hand slot in the inventory window.
+pub const OFF_HAND_SLOT: usize = 45;
+
+/// The inventory of a player, as the server set it.
+///
+/// The slots use the indices of the inventory window, so the hotbar starts at `[HOTBAR_START]`.
+/// The server is authoritative, so the slots are only changed by the server, like with `[Client::set_inventory_slot]`.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::inventory::{PlayerInventory, HOTBAR_START};
+/// use protocol_packets::{input::MAIN_HAND, item::ItemStack};
+///
+/// let mut inventory = PlayerInventory::new();
+/// inventory.set(HOTBAR_START + 2, ItemStack::new(887, 16).into());
+///
+/// assert!(inventory.held_item(MAIN_HAND).is_none());
+///
+/// inventory.select(2);
+/// assert_eq!(inventory.held_item(MAIN_HAND).map(|item| item.item_id), Some(887));
+/// ```
+///
+/// # Fields
+/// - `slots` - The content of every slot of the inventory window.
+/// - `selected` - The selected slot of the hotbar, from `0` to `8`.
+/// - `state_id` - The state of the inventory window, which changes with every change of a slot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerInventory {
+    slots: Vec<Slot>,
+    selected: usize,
+    state_id: i32,
+}
+
+impl PlayerInventory {
+    /// Creates a new empty `[PlayerInventory]`, the first hotbar slot is selected.
+    pub fn new() -> Self {
+        Self {
+            slots: vec![Slot::empty(); INVENTORY_SLOTS],
+            selected: 0,
+            state_id: 0,
+        }
+    }
+
+    /// Returns the content of the slot with the given index, `None` if the index is out of range.
+    pub fn get(&self, index: usize) -> Option<&Slot> {
+        self.slots.get(index)
+    }
+
+    /// Replaces the content of a slot.
+    ///
+    /// # Returns
+    /// `false` if the index is out of range.
+    pub fn set(&mut self, index: usize, slot: Slot) -> bool {
+        let Some(current) = self.slots.get_mut(index) else {
+            return false;
+        };
+
+        *current = slot;
+        self.state_id = self.state_id.wrapping_add(1);
+        true
+    }
+
+    /// Returns the state of the inventory window, which is sent with every change of a slot.
+    pub const fn state_id(&self) -> i32 {
+        self.state_id
+    }
+
+    /// Returns the selected slot of the hotbar, from `0` to `8`.
+    pub const fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Selects a slot of the hotbar.
+    ///
+    /// # Returns
+    /// `false` if the slot isn't part of the hotbar.
+    pub fn select(&mut self, hotbar_slot: usize) -> bool {
+        if hotbar_slot >= HOTBAR_SLOTS {
+            return false;
+        }
+
+        self.selected = hotbar_slot;
+        true
+    }
+
+    /// Returns the item in the given hand, `None` if the hand is empty.
+    ///
+    /// # Parameters
+    /// - `hand` - The hand, `[MAIN_HAND]` or `[OFF_HAND]`.
+    pub fn held_item(&self, hand: i32) -> Option<&ItemStack> {
+        let slot = if hand == OFF_HAND {
+            EquipmentSlot::OffHand
+        } else {
+            EquipmentSlot::MainHand
+        };
+        self.equipped(slot).as_ref()
+    }
+
+    /// Returns the item in an equipment slot, the main hand is the selected hotbar slot.
+    pub fn equipped(&self, slot: EquipmentSlot) -> &Slot {
+        &self.slots[self.index_of(slot)]
+    }
+
+    /// Returns the equipment slot that shows the slot with the given index, `None` if other players can't see it.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_core::inventory::{PlayerInventory, HOTBAR_START};
+    /// use protocol_packets::equipment::EquipmentSlot;
+    ///
+    /// let inventory = PlayerInventory::new();
+    ///
+    /// assert_eq!(inventory.equipment_slot(5), Some(EquipmentSlot::Helmet));
+    /// assert_eq!(inventory.equipment_slot(HOTBAR_START), Some(EquipmentSlot::MainHand));
+    /// assert_eq!(inventory.equipment_slot(HOTBAR_START + 1), None);
+    /// ```
+    pub fn equipment_slot(&self, index: usize) -> Option<EquipmentSlot> {
+        EquipmentSlot::ALL
+            .into_iter()
+            .find(|&slot| self.index_of(slot) == index)
+    }
+
+    /// Returns every equipment slot with its item, like it is shown to other players.
+    pub fn equipment(&self) -> Equipment {
+        let entries = EquipmentSlot::ALL
+            .into_iter()
+            .map(|slot| (slot, self.equipped(slot).clone()))
+            .collect();
+        Equipment(entries)
+    }
+
+    /// Returns the index of the slot of the inventory window that is shown in the given equipment slot.
+    fn index_of(&self, slot: EquipmentSlot) -> usize {
+        match slot {
+            EquipmentSlot::MainHand => HOTBAR_START + self.selected,
+            EquipmentSlot::OffHand => OFF_HAND_SLOT,
+            EquipmentSlot::Helmet => ARMOR_SLOTS[0],
+            EquipmentSlot::Chestplate => ARMOR_SLOTS[1],
+            EquipmentSlot::Leggings => ARMOR_SLOTS[2],
+            EquipmentSlot::Boots => ARMOR_SLOTS[3],
+        }
+    }
+}
+
+impl Default for PlayerInventory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,196 @@
+use std::{
+    collections::VecDeque,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use protocol_packets::{
+    player_info::{PlayerAction, PlayerInfo},
+    v1_21::play::{ClientboundKeepAlivePacket, PlayerInfoUpdatePacket},
+};
+use tokio::time;
+
+use crate::{
+    auth::GameProfile,
+    registry::{ClientMessage, ClientRegistry, EncodedPacket},
+};
+
+/// The interval at which keep alive packets are sent to the client.
+pub const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// The duration after which a client is disconnected if it didn't respond to a keep alive packet.
+pub const KEEP_ALIVE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The interval at which the latency of every player is sent to every client, this is 600 ticks like on the vanilla server.
+pub const LATENCY_UPDATE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Creates the packet that updates the latency of the given players in the player list.
+///
+/// # Examples
+/// ```rust
+/// use std::time::Duration;
+/// use protocol_core::{auth::{GameProfile, OfflineAuthenticator}, keep_alive::latency_update};
+///
+/// let players: Vec<_> = ["Notch", "jeb_"]
+///     .into_iter()
+///     .map(|name| {
+///         let profile = GameProfile {
+///             uuid: OfflineAuthenticator::offline_uuid(name),
+///             name: name.to_string(),
+///             properties: Vec::new(),
+///         };
+///         (profile, Duration::from_millis(42))
+///     })
+///     .collect();
+///
+/// assert_eq!(latency_update(&players).unwrap().players.len(), 2);
+/// assert!(latency_update(&[]).is_none());
+/// ```
+///
+/// # Parameters
+/// - `players` - The game profile and the latency of every player.
+///
+/// # Returns
+/// The packet, `None` if there are no players.
+pub fn latency_update(players: &[(GameProfile, Duration)]) -> Option<PlayerInfoUpdatePacket> {
+    if players.is_empty() {
+        return None;
+    }
+
+    let entries = players
+        .iter()
+        .map(|(profile, latency)| {
+            PlayerInfo::new(profile.uuid.as_u128())
+                .with(PlayerAction::UpdateLatency(latency.as_millis() as i32))
+        })
+        .collect();
+    Some(PlayerInfoUpdatePacket::new(entries))
+}
+
+/// Sends the latency of every player to every client in one packet, every `[LATENCY_UPDATE_INTERVAL]`.
+pub(crate) async fn broadcast_latencies(clients: Arc<ClientRegistry>) {
+    let mut interval = time::interval(LATENCY_UPDATE_INTERVAL);
+    loop {
+        interval.tick().await;
+        if let Some(packet) = latency_update(&clients.player_latencies()) {
+            clients.broadcast(ClientMessage::PlayPacket(EncodedPacket::new(&packet)));
+        }
+    }
+}
+
+/// Keeps track of the keep alive packets sent to a client.
+///
+/// Every keep alive packet that was sent is stored until the client responds with the same ID.
+/// If the oldest outstanding ID is older than the timeout, the client should be disconnected.
+///
+/// The time until the client answers is the round trip time of the connection. Like the vanilla server, the latency
+/// moves a quarter of the way towards every new round trip time, so a single slow answer doesn't make it jump.
+///
+/// # Fields
+/// - `interval` - The interval at which keep alive packets are sent.
+/// - `timeout` - The duration after which an unanswered keep alive times out.
+/// - `pending` - The IDs that were sent but not answered yet, together with the time they were sent.
+/// - `latency` - The smoothed round trip time, `None` until the client answered the first keep alive.
+///
+/// # Examples
+/// ```rust
+/// use std::time::Duration;
+/// use protocol_core::keep_alive::KeepAlive;
+///
+/// let mut keep_alive = KeepAlive::default();
+/// let packet = keep_alive.next_packet().unwrap();
+/// assert_eq!(keep_alive.latency(), Duration::ZERO);
+///
+/// assert!(keep_alive.acknowledge(packet.keep_alive_id));
+/// assert!(!keep_alive.acknowledge(packet.keep_alive_id));
+/// assert!(keep_alive.latency() < Duration::from_secs(1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct KeepAlive {
+    pub interval: Duration,
+    pub timeout: Duration,
+    pending: VecDeque<(i64, Instant)>,
+    latency: Option<Duration>,
+}
+
+impl Default for KeepAlive {
+    fn default() -> Self {
+        Self::new(KEEP_ALIVE_INTERVAL, KEEP_ALIVE_TIMEOUT)
+    }
+}
+
+impl KeepAlive {
+    /// Creates a new `[KeepAlive]` with the given interval and timeout.
+    ///
+    /// # Parameters
+    /// - `interval` - The interval at which keep alive packets are sent.
+    /// - `timeout` - The duration after which an unanswered keep alive times out.
+    pub const fn new(interval: Duration, timeout: Duration) -> Self {
+        Self {
+            interval,
+            timeout,
+            pending: VecDeque::new(),
+            latency: None,
+        }
+    }
+
+    /// Returns the latency of the client, `Duration::ZERO` until the client answered the first keep alive.
+    pub fn latency(&self) -> Duration {
+        self.latency.unwrap_or_default()
+    }
+
+    /// Checks if the oldest outstanding keep alive has timed out.
+    ///
+    /// # Returns
+    /// `true` if the client didn't respond in time and should be disconnected.
+    pub fn is_timed_out(&self) -> bool {
+        self.pending
+            .front()
+            .is_some_and(|(_, sent)| sent.elapsed() >= self.timeout)
+    }
+
+    /// Creates the next keep alive packet and starts tracking its ID.
+    ///
+    /// The ID is the current time in milliseconds, the same as the vanilla server.
+    ///
+    /// # Returns
+    /// The packet to send, `None` if the client has timed out and should be disconnected instead.
+    pub fn next_packet(&mut self) -> Option<ClientboundKeepAlivePacket> {
+        if self.is_timed_out() {
+            return None;
+        }
+
+        let mut keep_alive_id = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_millis() as i64);
+        if let Some((last_id, _)) = self.pending.back() {
+            keep_alive_id = keep_alive_id.max(last_id + 1);
+        }
+
+        self.pending.push_back((keep_alive_id, Instant::now()));
+        Some(ClientboundKeepAlivePacket { keep_alive_id })
+    }
+
+    /// Marks the keep alive with the given ID as answered and updates the latency with its round trip time.
+    ///
+    /// # Parameters
+    /// - `keep_alive_id` - The ID the client responded with.
+    ///
+    /// # Returns
+    /// `true` if the ID was outstanding, `false` if the client responded with an unknown ID.
+    pub fn acknowledge(&mut self, keep_alive_id: i64) -> bool {
+        let Some(index) = self.pending.iter().position(|(id, _)| *id == keep_alive_id) else {
+            return false;
+        };
+
+        let Some((_, sent)) = self.pending.remove(index) else {
+            return false;
+        };
+        let round_trip = sent.elapsed();
+        self.latency = Some(match self.latency {
+            Some(latency) => (latency * 3 + round_trip) / 4,
+            None => round_trip,
+        });
+        true
+    }
+}
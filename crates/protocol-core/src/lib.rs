@@ -1,2 +1,42 @@
+pub mod audit;
+pub mod auth;
 pub mod client;
+pub mod combat;
+pub mod components;
+pub mod config;
+pub mod config_cache;
+pub mod connector;
+pub mod cookie;
+pub mod cooldown;
+pub mod dispatch;
+pub mod entity;
+pub mod forwarding;
+pub mod game_rules;
+pub mod handler;
+pub mod http;
+pub mod idle;
+pub mod interceptor;
+pub mod inventory;
+pub mod keep_alive;
+pub mod listener;
+pub mod operators;
+pub mod outbound;
+pub mod proxy;
+pub mod queue;
+pub mod raycast;
+pub mod recording;
+pub mod registry;
+pub mod resource_pack;
+pub mod sequence;
 pub mod server;
+pub mod shutdown;
+pub mod spatial;
+pub mod status;
+pub mod throttle;
+pub mod tickets;
+pub mod time;
+pub mod timings;
+pub mod ui;
+pub mod vehicle;
+pub mod view_distance;
+pub mod world;
@@ -0,0 +1,117 @@
+use std::{io, net::SocketAddr, sync::Arc};
+
+use tokio::{
+    net::{TcpListener, TcpStream},
+    sync::watch,
+};
+
+use crate::shutdown::ShutdownHandle;
+
+/// Controls a single address the server accepts connections on.
+///
+/// A stopped listener stays bound to its address, it only stops accepting new connections until it is started again.
+/// Connections that were accepted before are not affected.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::listener::ListenerHandle;
+///
+/// let handle = ListenerHandle::new("127.0.0.1:25565".parse().unwrap());
+/// assert!(handle.is_running());
+///
+/// handle.stop();
+/// assert!(!handle.is_running());
+/// ```
+///
+/// # Fields
+/// - `address` - The address the listener is bound to.
+/// - `running` - Whether the listener accepts connections, shared with the task of the listener.
+#[derive(Debug, Clone)]
+pub struct ListenerHandle {
+    address: SocketAddr,
+    running: Arc<watch::Sender<bool>>,
+}
+
+impl ListenerHandle {
+    /// Creates a new `[ListenerHandle]` for a running listener.
+    pub fn new(address: SocketAddr) -> Self {
+        Self {
+            address,
+            running: Arc::new(watch::Sender::new(true)),
+        }
+    }
+
+    /// Returns the address the listener is bound to.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Returns whether the listener accepts connections.
+    pub fn is_running(&self) -> bool {
+        *self.running.borrow()
+    }
+
+    /// Lets the listener accept connections again.
+    pub fn start(&self) {
+        self.running.send_replace(true);
+    }
+
+    /// Stops the listener from accepting new connections.
+    pub fn stop(&self) {
+        self.running.send_replace(false);
+    }
+}
+
+/// A TCP listener of the server together with its `[ListenerHandle]`.
+///
+/// # Fields
+/// - `stream` - The TCP listener, shared with the task that accepts its connections.
+/// - `handle` - The handle that starts and stops the listener.
+#[derive(Clone)]
+pub(crate) struct Listener {
+    pub(crate) stream: Arc<TcpListener>,
+    pub(crate) handle: ListenerHandle,
+}
+
+impl Listener {
+    /// Creates a new running `[Listener]`.
+    pub(crate) fn new(stream: TcpListener) -> io::Result<Self> {
+        let handle = ListenerHandle::new(stream.local_addr()?);
+        Ok(Self {
+            stream: Arc::new(stream),
+            handle,
+        })
+    }
+
+    /// Accepts connections until the server stops, connections are only accepted while the listener is running.
+    ///
+    /// # Parameters
+    /// - `shutdown` - The shutdown handle of the server, the listener stops once the server stopped.
+    /// - `accept` - Called for every accepted connection.
+    pub(crate) async fn listen(
+        &self,
+        shutdown: ShutdownHandle,
+        mut accept: impl FnMut(TcpStream, SocketAddr),
+    ) {
+        let mut running = self.handle.running.subscribe();
+        while shutdown.is_running() {
+            if !*running.borrow_and_update() {
+                tokio::select! {
+                    _ = running.changed() => {}
+                    _ = shutdown.stopped() => {}
+                }
+                continue;
+            }
+
+            tokio::select! {
+                accepted = self.stream.accept() => {
+                    if let Ok((socket, address)) = accepted {
+                        accept(socket, address);
+                    }
+                }
+                _ = running.changed() => {}
+                _ = shutdown.stopped() => {}
+            }
+        }
+    }
+}
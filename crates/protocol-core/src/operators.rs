@@ -0,0 +1,221 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use protocol_packets::v1_21::play::EntityEventPacket;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The default file the operators are stored in.
+pub const OPS_FILE: &str = "ops.json";
+
+/// The highest permission level an operator can have.
+pub const MAX_OP_LEVEL: u8 = 4;
+
+/// The entity status that tells the client it has permission level 0. Level 1 to 4 follow directly after.
+const OP_LEVEL_STATUS: i8 = 24;
+
+/// Decides the permission level of the players, like the `[OperatorList]` does.
+///
+/// The level decides which commands a player can use, the client is told about it with `[op_level_event]`.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::operators::PermissionProvider;
+/// use uuid::Uuid;
+///
+/// /// Every player is an operator, like on a local test server.
+/// #[derive(Debug)]
+/// struct EveryoneIsOp;
+///
+/// impl PermissionProvider for EveryoneIsOp {
+///     fn permission_level(&self, _uuid: &Uuid) -> u8 {
+///         4
+///     }
+/// }
+///
+/// assert_eq!(EveryoneIsOp.permission_level(&Uuid::nil()), 4);
+/// ```
+pub trait PermissionProvider: fmt::Debug + Send + Sync {
+    /// Returns the permission level of the given player, from 0 to `[MAX_OP_LEVEL]`.
+    ///
+    /// # Parameters
+    /// - `uuid` - The UUID of the player.
+    fn permission_level(&self, uuid: &Uuid) -> u8;
+}
+
+/// Creates the `[EntityEventPacket]` that tells the client the permission level of its player.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::operators::op_level_event;
+///
+/// assert_eq!(op_level_event(7, 0).entity_status, 24);
+/// assert_eq!(op_level_event(7, 4).entity_status, 28);
+/// assert_eq!(op_level_event(7, 9).entity_status, 28);
+/// ```
+///
+/// # Parameters
+/// - `entity_id` - The entity ID of the player.
+/// - `level` - The permission level of the player, it is clamped to `[MAX_OP_LEVEL]`.
+pub fn op_level_event(entity_id: i32, level: u8) -> EntityEventPacket {
+    EntityEventPacket {
+        entity_id,
+        entity_status: OP_LEVEL_STATUS + level.min(MAX_OP_LEVEL) as i8,
+    }
+}
+
+/// Represents a single operator, stored the same way as in a vanilla `ops.json`.
+///
+/// # Fields
+/// - `uuid` - The UUID of the player.
+/// - `name` - The name of the player.
+/// - `level` - The permission level of the player, from 0 to 4.
+/// - `bypasses_player_limit` - Whether the player can join while the server is full.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Operator {
+    pub uuid: Uuid,
+    pub name: String,
+    pub level: u8,
+    pub bypasses_player_limit: bool,
+}
+
+/// Contains all operators of the server.
+///
+/// The list is persisted to a vanilla compatible `ops.json`. Every change made with `add` or `remove` is saved directly.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::operators::{Operator, OperatorList};
+/// use uuid::Uuid;
+///
+/// let path = std::env::temp_dir().join("protocol_core_ops_example.json");
+/// let mut operators = OperatorList::new(&path);
+/// let uuid = Uuid::from_u128(1);
+///
+/// operators.add(Operator { uuid, name: "Notch".to_string(), level: 4, bypasses_player_limit: false }).unwrap();
+/// assert_eq!(operators.level(&uuid), 4);
+///
+/// let loaded = OperatorList::load(&path).unwrap();
+/// assert_eq!(loaded.get(&uuid), operators.get(&uuid));
+/// # std::fs::remove_file(path).unwrap();
+/// ```
+///
+/// # Fields
+/// - `path` - The file the operators are saved to.
+/// - `operators` - The operators of the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperatorList {
+    path: PathBuf,
+    operators: Vec<Operator>,
+}
+
+impl OperatorList {
+    /// Creates a new empty `[OperatorList]` that is saved to the given file.
+    ///
+    /// # Parameters
+    /// - `path` - The file the operators are saved to.
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            operators: Vec::new(),
+        }
+    }
+
+    /// Loads the operators from the given file.
+    ///
+    /// If the file doesn't exist yet, an empty list is returned. It will be created once the list is saved.
+    ///
+    /// # Parameters
+    /// - `path` - The file to load the operators from.
+    ///
+    /// # Returns
+    /// The loaded operators, an error if the file couldn't be read or isn't a valid `ops.json`.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut list = Self::new(path);
+
+        match fs::read_to_string(&list.path) {
+            Ok(contents) => {
+                list.operators = serde_json::from_str(&contents)
+                    .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+                Ok(list)
+            }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(list),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Saves the operators to the file of this list.
+    pub fn save(&self) -> io::Result<()> {
+        let contents = serde_json::to_string_pretty(&self.operators)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        fs::write(&self.path, contents)
+    }
+
+    /// Adds the given operator and saves the list. An existing operator with the same UUID is replaced.
+    ///
+    /// The level is clamped to `[MAX_OP_LEVEL]`.
+    ///
+    /// # Parameters
+    /// - `operator` - The operator to add.
+    pub fn add(&mut self, mut operator: Operator) -> io::Result<()> {
+        operator.level = operator.level.min(MAX_OP_LEVEL);
+
+        match self
+            .operators
+            .iter_mut()
+            .find(|op| op.uuid == operator.uuid)
+        {
+            Some(existing) => *existing = operator,
+            None => self.operators.push(operator),
+        }
+
+        self.save()
+    }
+
+    /// Removes the operator with the given UUID and saves the list.
+    ///
+    /// # Returns
+    /// The removed operator, `None` if the player wasn't an operator.
+    pub fn remove(&mut self, uuid: &Uuid) -> io::Result<Option<Operator>> {
+        let Some(index) = self.operators.iter().position(|op| op.uuid == *uuid) else {
+            return Ok(None);
+        };
+
+        let operator = self.operators.remove(index);
+        self.save()?;
+        Ok(Some(operator))
+    }
+
+    /// Returns the operator with the given UUID.
+    pub fn get(&self, uuid: &Uuid) -> Option<&Operator> {
+        self.operators.iter().find(|op| op.uuid == *uuid)
+    }
+
+    /// Returns the permission level of the given player. Players that aren't operators have level 0.
+    pub fn level(&self, uuid: &Uuid) -> u8 {
+        self.get(uuid).map_or(0, |op| op.level)
+    }
+
+    /// Returns an iterator over all operators.
+    pub fn iter(&self) -> impl Iterator<Item = &Operator> {
+        self.operators.iter()
+    }
+
+    /// Creates the `[EntityEventPacket]` that tells the client the permission level of the given player.
+    ///
+    /// # Parameters
+    /// - `entity_id` - The entity ID of the player.
+    /// - `uuid` - The UUID of the player.
+    pub fn entity_event(&self, entity_id: i32, uuid: &Uuid) -> EntityEventPacket {
+        op_level_event(entity_id, self.level(uuid))
+    }
+}
+
+impl PermissionProvider for OperatorList {
+    fn permission_level(&self, uuid: &Uuid) -> u8 {
+        self.level(uuid)
+    }
+}
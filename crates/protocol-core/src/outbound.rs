@@ -0,0 +1,254 @@
+use std::{io, mem, sync::Arc};
+
+use protocol_buf::pool::BufferPool;
+
+use tokio::{
+    io::{AsyncWrite, AsyncWriteExt},
+    sync::{mpsc, oneshot},
+};
+
+use crate::timings::Timings;
+
+/// The amount of writes that can be queued before sending waits for the writer, like a client that doesn't read.
+pub const MAX_QUEUED_WRITES: usize = 1024;
+
+/// The amount of bytes after which queued frames are written, even if more frames are queued.
+pub const MAX_BATCH_SIZE: usize = 64 * 1024;
+
+/// The limits of the queue of a `[PacketWriter]`.
+///
+/// # Fields
+/// - `max_queued` - The amount of writes that can be queued before sending waits until the writer caught up.
+/// - `max_batch_size` - The amount of bytes after which queued frames are written in a single write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutboundSettings {
+    pub max_queued: usize,
+    pub max_batch_size: usize,
+}
+
+impl Default for OutboundSettings {
+    fn default() -> Self {
+        Self {
+            max_queued: MAX_QUEUED_WRITES,
+            max_batch_size: MAX_BATCH_SIZE,
+        }
+    }
+}
+
+/// A message sent to the writer task of a `[PacketWriter]`.
+///
+/// # Variants
+/// - `Frames` - One or more complete frames that are written.
+/// - `Flush` - Writes the queued frames and flushes the stream.
+/// - `Close` - Writes the queued frames and shuts the stream down.
+enum Outbound {
+    Frames(Vec<u8>),
+    Flush(oneshot::Sender<io::Result<()>>),
+    Close(oneshot::Sender<io::Result<()>>),
+}
+
+/// Queues frames that are written to a stream by a separate task.
+///
+/// Frames are returned to the global `[BufferPool]` once they were copied into a batch or written.
+///
+/// The writer task takes every frame that is queued at the time it writes and writes them with a single `write_all`,
+/// so sending many small packets doesn't cause a write for every packet. Frames are written as soon as the task gets to
+/// them, batching never delays a frame. Once `max_queued` writes are waiting, sending waits until the task caught up.
+///
+/// Errors of the stream are returned by the next send, flush or close, as the frames are written by the task.
+///
+/// # Examples
+/// ```rust
+/// use tokio::io::AsyncReadExt;
+/// use protocol_core::outbound::{OutboundSettings, PacketWriter};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let (stream, mut peer) = tokio::io::duplex(1024);
+///     let writer = PacketWriter::spawn(stream, OutboundSettings::default());
+///
+///     writer.send(vec![2, 0x00, 0x01]).await.unwrap();
+///     writer.send(vec![1, 0x02]).await.unwrap();
+///     writer.close().await.unwrap();
+///
+///     let mut bytes = Vec::new();
+///     peer.read_to_end(&mut bytes).await.unwrap();
+///     assert_eq!(bytes, vec![2, 0x00, 0x01, 1, 0x02]);
+/// }
+/// ```
+///
+/// # Fields
+/// - `sender` - The queue of the writer task.
+/// - `max_queued` - The amount of writes that can be queued.
+#[derive(Debug, Clone)]
+pub struct PacketWriter {
+    sender: mpsc::Sender<Outbound>,
+    max_queued: usize,
+}
+
+impl PacketWriter {
+    /// Spawns the writer task for the given stream.
+    ///
+    /// # Parameters
+    /// - `stream` - The stream the frames are written to.
+    /// - `settings` - The limits of the queue.
+    pub fn spawn<W>(stream: W, settings: OutboundSettings) -> Self
+    where
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        Self::spawn_with_timings(stream, settings, None)
+    }
+
+    /// Spawns the writer task for the given stream, every write is recorded as a `player_flush` span of the timings.
+    ///
+    /// # Parameters
+    /// - `stream` - The stream the frames are written to.
+    /// - `settings` - The limits of the queue.
+    /// - `timings` - The timings the writes are recorded to, `None` if they aren't recorded.
+    pub fn spawn_with_timings<W>(
+        stream: W,
+        settings: OutboundSettings,
+        timings: Option<Arc<Timings>>,
+    ) -> Self
+    where
+        W: AsyncWrite + Unpin + Send + 'static,
+    {
+        let max_queued = settings.max_queued.max(1);
+        let (sender, receiver) = mpsc::channel(max_queued);
+        tokio::spawn(write_frames(
+            stream,
+            receiver,
+            settings.max_batch_size,
+            timings,
+        ));
+
+        Self { sender, max_queued }
+    }
+
+    /// Queues complete frames, waiting if the queue is full.
+    pub async fn send(&self, frames: Vec<u8>) -> io::Result<()> {
+        self.sender
+            .send(Outbound::Frames(frames))
+            .await
+            .map_err(|_| closed())
+    }
+
+    /// Queues complete frames without waiting.
+    ///
+    /// # Returns
+    /// `false` if the queue is full, an error if the stream was closed.
+    pub fn try_send(&self, frames: Vec<u8>) -> io::Result<bool> {
+        match self.sender.try_send(Outbound::Frames(frames)) {
+            Ok(()) => Ok(true),
+            Err(mpsc::error::TrySendError::Full(_)) => Ok(false),
+            Err(mpsc::error::TrySendError::Closed(_)) => Err(closed()),
+        }
+    }
+
+    /// Waits until every queued frame was written and flushed to the stream.
+    pub async fn flush(&self) -> io::Result<()> {
+        self.request(Outbound::Flush).await
+    }
+
+    /// Writes every queued frame and shuts the stream down, frames that are queued afterwards are rejected.
+    pub async fn close(&self) -> io::Result<()> {
+        self.request(Outbound::Close).await
+    }
+
+    /// Returns the amount of writes that are waiting in the queue.
+    pub fn queued(&self) -> usize {
+        self.max_queued - self.sender.capacity()
+    }
+
+    /// Sends a message to the writer task and waits for its answer.
+    async fn request(
+        &self,
+        message: fn(oneshot::Sender<io::Result<()>>) -> Outbound,
+    ) -> io::Result<()> {
+        let (sender, receiver) = oneshot::channel();
+        self.sender
+            .send(message(sender))
+            .await
+            .map_err(|_| closed())?;
+        receiver.await.map_err(|_| closed())?
+    }
+}
+
+/// The error returned once the writer task stopped.
+fn closed() -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, "the connection was closed")
+}
+
+/// Writes the queued frames to the stream until every `[PacketWriter]` was dropped or the stream was closed.
+async fn write_frames<W>(
+    mut stream: W,
+    mut receiver: mpsc::Receiver<Outbound>,
+    max_batch_size: usize,
+    timings: Option<Arc<Timings>>,
+) where
+    W: AsyncWrite + Unpin,
+{
+    let mut batch = Vec::new();
+    while let Some(message) = receiver.recv().await {
+        let mut next = Some(message);
+        while let Some(message) = next.take() {
+            match message {
+                Outbound::Frames(frames) if batch.is_empty() => {
+                    BufferPool::global().give(mem::replace(&mut batch, frames));
+                }
+                Outbound::Frames(frames) => {
+                    batch.extend_from_slice(&frames);
+                    BufferPool::global().give(frames);
+                }
+                Outbound::Flush(reply) => {
+                    let _span = timings.as_ref().map(|timings| timings.span("player_flush"));
+                    let result = async {
+                        write_batch(&mut stream, &mut batch).await?;
+                        stream.flush().await
+                    }
+                    .await;
+                    let failed = result.is_err();
+                    let _ = reply.send(result);
+                    if failed {
+                        return;
+                    }
+                }
+                Outbound::Close(reply) => {
+                    let result = async {
+                        write_batch(&mut stream, &mut batch).await?;
+                        stream.shutdown().await
+                    }
+                    .await;
+                    let _ = reply.send(result);
+                    return;
+                }
+            }
+
+            if batch.len() < max_batch_size {
+                next = receiver.try_recv().ok();
+            }
+        }
+
+        let _span = timings
+            .as_ref()
+            .filter(|_| !batch.is_empty())
+            .map(|timings| timings.span("player_flush"));
+        if write_batch(&mut stream, &mut batch).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Writes the batch with a single write and clears it.
+async fn write_batch<W>(stream: &mut W, batch: &mut Vec<u8>) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    if batch.is_empty() {
+        return Ok(());
+    }
+
+    let result = stream.write_all(batch).await;
+    batch.clear();
+    result
+}
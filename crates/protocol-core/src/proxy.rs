@@ -0,0 +1,135 @@
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// The first bytes of a version 2 PROXY protocol header.
+pub const PROXY_V2_SIGNATURE: [u8; 12] = *b"\r\n\r\n\0\r\nQUIT\n";
+
+/// The maximum length of a version 1 PROXY protocol header, including the line break.
+pub const MAX_PROXY_V1_LENGTH: usize = 107;
+
+/// The time a connection has to send its PROXY protocol header, before it is dropped.
+pub const PROXY_HEADER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Reads the PROXY protocol header a load balancer sends at the start of every connection.
+///
+/// Both the text based version 1 and the binary version 2 of the header are supported. Exactly the header is read,
+/// so the handshake of the client is read from the stream afterwards.
+///
+/// This should only be used if every connection comes from a trusted proxy, otherwise clients can send any address.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::proxy::read_proxy_header;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut stream: &[u8] = b"PROXY TCP4 203.0.113.7 10.0.0.2 51234 25565\r\n\x10\x00";
+///     let address = read_proxy_header(&mut stream).await.unwrap();
+///
+///     assert_eq!(address, Some("203.0.113.7:51234".parse().unwrap()));
+///     assert_eq!(stream, b"\x10\x00");
+/// }
+/// ```
+///
+/// # Returns
+/// The address of the client, `None` if the proxy didn't send one, like for its own health checks.
+/// An error if the connection doesn't start with a valid header.
+pub async fn read_proxy_header<R>(stream: &mut R) -> io::Result<Option<SocketAddr>>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut start = [0; 12];
+    stream.read_exact(&mut start).await?;
+
+    if start == PROXY_V2_SIGNATURE {
+        read_v2(stream).await
+    } else if start.starts_with(b"PROXY ") {
+        read_v1(stream, &start).await
+    } else {
+        Err(invalid(
+            "the connection didn't start with a PROXY protocol header",
+        ))
+    }
+}
+
+/// Reads the rest of a version 1 header, which is a single line of text.
+async fn read_v1<R>(stream: &mut R, start: &[u8]) -> io::Result<Option<SocketAddr>>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut line = start.to_vec();
+    while !line.ends_with(b"\r\n") {
+        if line.len() >= MAX_PROXY_V1_LENGTH {
+            return Err(invalid("the PROXY protocol header is too long"));
+        }
+        line.push(stream.read_u8().await?);
+    }
+
+    let line = std::str::from_utf8(&line[..line.len() - 2])
+        .map_err(|_| invalid("the PROXY protocol header isn't valid text"))?;
+    let parts: Vec<_> = line.split(' ').collect();
+    match parts.as_slice() {
+        ["PROXY", "UNKNOWN", ..] => Ok(None),
+        ["PROXY", "TCP4" | "TCP6", source, _, port, _] => {
+            let address: IpAddr = source
+                .parse()
+                .map_err(|_| invalid("invalid source address in the PROXY protocol header"))?;
+            let port: u16 = port
+                .parse()
+                .map_err(|_| invalid("invalid source port in the PROXY protocol header"))?;
+            Ok(Some(SocketAddr::new(address, port)))
+        }
+        _ => Err(invalid("malformed PROXY protocol header")),
+    }
+}
+
+/// Reads the rest of a version 2 header, which is binary.
+async fn read_v2<R>(stream: &mut R) -> io::Result<Option<SocketAddr>>
+where
+    R: AsyncRead + Unpin,
+{
+    let version_command = stream.read_u8().await?;
+    let family = stream.read_u8().await?;
+    let length = stream.read_u16().await? as usize;
+
+    let mut data = vec![0; length];
+    stream.read_exact(&mut data).await?;
+
+    if version_command >> 4 != 2 {
+        return Err(invalid("unsupported PROXY protocol version"));
+    }
+    // The `LOCAL` command is used by the proxy itself, like for health checks.
+    if version_command & 0x0F == 0 {
+        return Ok(None);
+    }
+
+    match family >> 4 {
+        0x1 if data.len() >= 12 => {
+            let address = Ipv4Addr::new(data[0], data[1], data[2], data[3]);
+            let port = u16::from_be_bytes([data[8], data[9]]);
+            Ok(Some(SocketAddr::new(IpAddr::V4(address), port)))
+        }
+        0x2 if data.len() >= 36 => {
+            let mut octets = [0; 16];
+            octets.copy_from_slice(&data[..16]);
+            let port = u16::from_be_bytes([data[32], data[33]]);
+            Ok(Some(SocketAddr::new(
+                IpAddr::V6(Ipv6Addr::from(octets)),
+                port,
+            )))
+        }
+        0x1 | 0x2 => Err(invalid("the PROXY protocol header is too short")),
+        // Unix sockets and unspecified families don't have an IP address.
+        _ => Ok(None),
+    }
+}
+
+/// Creates the error of a malformed header.
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
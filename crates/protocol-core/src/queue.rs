@@ -0,0 +1,193 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use tokio::sync::Notify;
+use uuid::Uuid;
+
+/// The players that are logging in and the players that are waiting for their turn.
+#[derive(Debug, Default)]
+struct QueueState {
+    active: usize,
+    waiting: VecDeque<Uuid>,
+}
+
+/// Limits the amount of players that are logging in at the same time.
+///
+/// Sending the registries and chunks to many players at once, like after a restart, can overwhelm the server.
+/// Players that can't log in yet wait in the queue in the order they arrived, the `[Client]` keeps their connection alive.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::queue::LoginQueue;
+/// use uuid::Uuid;
+///
+/// let queue = LoginQueue::new(1, 10);
+/// let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+///
+/// let permit = queue.try_acquire(first).unwrap();
+/// assert_eq!(queue.try_acquire(second).unwrap_err(), 1);
+/// assert_eq!(queue.position(second), Some(1));
+///
+/// drop(permit);
+/// assert!(queue.try_acquire(second).is_ok());
+/// assert_eq!(queue.position(second), None);
+/// ```
+///
+/// # Fields
+/// - `max_active` - The maximum amount of players in the `Login` and `Configuration` states.
+/// - `max_waiting` - The maximum amount of players waiting in the queue, other players are kicked.
+/// - `state` - The players that are logging in and waiting.
+/// - `notify` - Wakes up the waiting players once a player finished logging in.
+#[derive(Debug)]
+pub struct LoginQueue {
+    max_active: usize,
+    max_waiting: usize,
+    state: Mutex<QueueState>,
+    notify: Notify,
+}
+
+impl LoginQueue {
+    /// Creates a new `[LoginQueue]`.
+    ///
+    /// # Parameters
+    /// - `max_active` - The maximum amount of players in the `Login` and `Configuration` states.
+    /// - `max_waiting` - The maximum amount of players waiting in the queue.
+    pub fn new(max_active: usize, max_waiting: usize) -> Arc<Self> {
+        Arc::new(Self {
+            max_active,
+            max_waiting,
+            state: Mutex::new(QueueState::default()),
+            notify: Notify::new(),
+        })
+    }
+
+    /// Creates a new `[LoginQueue]` that lets every player log in at once.
+    pub fn unlimited() -> Arc<Self> {
+        Self::new(usize::MAX, 0)
+    }
+
+    /// Returns the maximum amount of players in the `Login` and `Configuration` states.
+    pub const fn max_active(&self) -> usize {
+        self.max_active
+    }
+
+    /// Returns the maximum amount of players waiting in the queue.
+    pub const fn max_waiting(&self) -> usize {
+        self.max_waiting
+    }
+
+    /// Returns the amount of players that are logging in.
+    pub fn active(&self) -> usize {
+        self.state.lock().unwrap().active
+    }
+
+    /// Returns the amount of players that are waiting in the queue.
+    pub fn waiting(&self) -> usize {
+        self.state.lock().unwrap().waiting.len()
+    }
+
+    /// Returns the position of the player in the queue, starting at `1`.
+    pub fn position(&self, uuid: Uuid) -> Option<usize> {
+        let state = self.state.lock().unwrap();
+        state
+            .waiting
+            .iter()
+            .position(|waiting| *waiting == uuid)
+            .map(|index| index + 1)
+    }
+
+    /// Lets the player log in if it is its turn, otherwise it is added to the end of the queue.
+    ///
+    /// # Returns
+    /// The permit to log in, or the position of the player in the queue.
+    pub fn try_acquire(self: &Arc<Self>, uuid: Uuid) -> Result<LoginPermit, usize> {
+        let mut state = self.state.lock().unwrap();
+
+        let position = match state.waiting.iter().position(|waiting| *waiting == uuid) {
+            Some(index) => index,
+            None => {
+                state.waiting.push_back(uuid);
+                state.waiting.len() - 1
+            }
+        };
+
+        if position == 0 && state.active < self.max_active {
+            state.waiting.pop_front();
+            state.active += 1;
+            return Ok(LoginPermit {
+                queue: self.clone(),
+            });
+        }
+
+        Err(position + 1)
+    }
+
+    /// Returns whether the queue has room for another waiting player.
+    pub fn has_room(&self, uuid: Uuid) -> bool {
+        let state = self.state.lock().unwrap();
+        state.active < self.max_active
+            || state.waiting.len() < self.max_waiting
+            || state.waiting.contains(&uuid)
+    }
+
+    /// Waits until it is the turn of the player.
+    ///
+    /// If the future is dropped before, for example because the player disconnected, the player leaves the queue.
+    pub async fn acquire(self: &Arc<Self>, uuid: Uuid) -> LoginPermit {
+        let mut leave = LeaveOnDrop {
+            queue: self,
+            uuid,
+            admitted: false,
+        };
+
+        loop {
+            let notified = self.notify.notified();
+            if let Ok(permit) = self.try_acquire(uuid) {
+                leave.admitted = true;
+                return permit;
+            }
+
+            notified.await;
+        }
+    }
+
+    /// Removes the player from the queue.
+    pub fn leave(&self, uuid: Uuid) {
+        self.state
+            .lock()
+            .unwrap()
+            .waiting
+            .retain(|waiting| *waiting != uuid);
+        self.notify.notify_waiters();
+    }
+}
+
+/// Removes the player from the queue if it stopped waiting.
+struct LeaveOnDrop<'a> {
+    queue: &'a LoginQueue,
+    uuid: Uuid,
+    admitted: bool,
+}
+
+impl Drop for LeaveOnDrop<'_> {
+    fn drop(&mut self) {
+        if !self.admitted {
+            self.queue.leave(self.uuid);
+        }
+    }
+}
+
+/// The permission of a player to log in, the next player in the queue can log in once this is dropped.
+#[derive(Debug)]
+pub struct LoginPermit {
+    queue: Arc<LoginQueue>,
+}
+
+impl Drop for LoginPermit {
+    fn drop(&mut self) {
+        self.queue.state.lock().unwrap().active -= 1;
+        self.queue.notify.notify_waiters();
+    }
+}
@@ -0,0 +1,440 @@
+/// The default range in which a player can interact with blocks, like the `block_interaction_range` attribute.
+pub const BLOCK_INTERACTION_RANGE: f64 = 4.5;
+
+/// The default range in which a player can interact with entities, like the `entity_interaction_range` attribute.
+pub const ENTITY_INTERACTION_RANGE: f64 = 3.0;
+
+/// Gives access to the blocks of a world, this is everything a raycast needs to know.
+///
+/// This is implemented for every `Fn(i32, i32, i32) -> bool`, so a closure can be used as a world.
+pub trait BlockView {
+    /// Returns whether the block at the given position stops a raycast.
+    fn is_solid(&self, x: i32, y: i32, z: i32) -> bool;
+}
+
+impl<F: Fn(i32, i32, i32) -> bool> BlockView for F {
+    fn is_solid(&self, x: i32, y: i32, z: i32) -> bool {
+        self(x, y, z)
+    }
+}
+
+/// A position or direction in the world.
+///
+/// # Fields
+/// - `x` - The X component.
+/// - `y` - The Y component.
+/// - `z` - The Z component.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Vec3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vec3 {
+    /// Creates a new `[Vec3]`.
+    pub const fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Returns the length of the vector.
+    pub fn length(&self) -> f64 {
+        (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+    }
+
+    /// Returns the vector from this position to the given position.
+    pub fn to(&self, other: Vec3) -> Vec3 {
+        Vec3::new(other.x - self.x, other.y - self.y, other.z - self.z)
+    }
+
+    /// Returns the squared distance between two positions.
+    pub fn distance_squared(&self, other: Vec3) -> f64 {
+        let delta = self.to(other);
+        delta.x * delta.x + delta.y * delta.y + delta.z * delta.z
+    }
+
+    /// Returns the vector with a length of one, `None` if the vector has no length.
+    pub fn normalize(&self) -> Option<Vec3> {
+        let length = self.length();
+        (length > 0.0).then(|| Vec3::new(self.x / length, self.y / length, self.z / length))
+    }
+
+    /// Returns the direction a player is looking at, using the yaw and pitch in degrees.
+    pub fn from_rotation(yaw: f32, pitch: f32) -> Vec3 {
+        let (yaw, pitch) = ((yaw as f64).to_radians(), (pitch as f64).to_radians());
+        Vec3::new(
+            -yaw.sin() * pitch.cos(),
+            -pitch.sin(),
+            yaw.cos() * pitch.cos(),
+        )
+    }
+
+    fn axis(&self, axis: usize) -> f64 {
+        [self.x, self.y, self.z][axis]
+    }
+}
+
+/// The direction an entity is facing, in degrees.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::raycast::{Rotation, Vec3};
+///
+/// let rotation = Rotation::new(0.0, 90.0);
+/// assert!(rotation.direction().distance_squared(Vec3::new(0.0, -1.0, 0.0)) < 1e-9);
+/// ```
+///
+/// # Fields
+/// - `yaw` - The rotation around the Y axis, `0` is facing south.
+/// - `pitch` - The vertical rotation, `-90` is looking straight up and `90` straight down.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Rotation {
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+impl Rotation {
+    /// Creates a new `[Rotation]`.
+    pub const fn new(yaw: f32, pitch: f32) -> Self {
+        Self { yaw, pitch }
+    }
+
+    /// Returns the direction of the rotation, with a length of one.
+    pub fn direction(&self) -> Vec3 {
+        Vec3::from_rotation(self.yaw, self.pitch)
+    }
+}
+
+/// The face of a block, in the order the protocol uses.
+///
+/// # Variants
+/// - `Bottom` - The face pointing towards negative Y.
+/// - `Top` - The face pointing towards positive Y.
+/// - `North` - The face pointing towards negative Z.
+/// - `South` - The face pointing towards positive Z.
+/// - `West` - The face pointing towards negative X.
+/// - `East` - The face pointing towards positive X.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockFace {
+    Bottom,
+    Top,
+    North,
+    South,
+    West,
+    East,
+}
+
+impl BlockFace {
+    /// Returns the face a ray enters through when it moves along the given axis in the given direction.
+    fn entered(axis: usize, step: i32) -> Self {
+        match (axis, step > 0) {
+            (0, true) => Self::West,
+            (0, false) => Self::East,
+            (1, true) => Self::Bottom,
+            (1, false) => Self::Top,
+            (_, true) => Self::North,
+            (_, false) => Self::South,
+        }
+    }
+}
+
+/// The block that was hit by a raycast.
+///
+/// # Fields
+/// - `x` - The X position of the block.
+/// - `y` - The Y position of the block.
+/// - `z` - The Z position of the block.
+/// - `face` - The face of the block that was hit.
+/// - `distance` - The distance from the origin of the ray to the hit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockHit {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    pub face: BlockFace,
+    pub distance: f64,
+}
+
+/// An axis aligned bounding box, like the hitbox of an entity.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::raycast::{Aabb, Vec3};
+///
+/// let hitbox = Aabb::entity(Vec3::new(0.0, 64.0, 5.0), 1.0, 1.8);
+/// let distance = hitbox.intersect_ray(Vec3::new(0.0, 65.0, 0.0), Vec3::new(0.0, 0.0, 1.0), 10.0);
+///
+/// assert_eq!(distance, Some(4.5));
+/// ```
+///
+/// # Fields
+/// - `min` - The corner with the smallest coordinates.
+/// - `max` - The corner with the largest coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    /// Creates a new `[Aabb]` from two corners.
+    pub const fn new(min: Vec3, max: Vec3) -> Self {
+        Self { min, max }
+    }
+
+    /// Creates the hitbox of an entity standing at the given position.
+    ///
+    /// # Parameters
+    /// - `position` - The position of the feet of the entity.
+    /// - `width` - The width of the entity, the box is centered on the position.
+    /// - `height` - The height of the entity.
+    pub fn entity(position: Vec3, width: f64, height: f64) -> Self {
+        let half = width / 2.0;
+        Self::new(
+            Vec3::new(position.x - half, position.y, position.z - half),
+            Vec3::new(position.x + half, position.y + height, position.z + half),
+        )
+    }
+
+    /// Returns the center of the box.
+    pub fn center(&self) -> Vec3 {
+        Vec3::new(
+            (self.min.x + self.max.x) / 2.0,
+            (self.min.y + self.max.y) / 2.0,
+            (self.min.z + self.max.z) / 2.0,
+        )
+    }
+
+    /// Returns whether the given position is inside the box.
+    pub fn contains(&self, point: Vec3) -> bool {
+        (0..3).all(|axis| {
+            point.axis(axis) >= self.min.axis(axis) && point.axis(axis) <= self.max.axis(axis)
+        })
+    }
+
+    /// Returns the squared distance from the given position to the closest point of the box.
+    pub fn distance_squared(&self, point: Vec3) -> f64 {
+        let closest = Vec3::new(
+            point.x.clamp(self.min.x, self.max.x),
+            point.y.clamp(self.min.y, self.max.y),
+            point.z.clamp(self.min.z, self.max.z),
+        );
+        point.distance_squared(closest)
+    }
+
+    /// Finds where a ray enters the box, using the slab method.
+    ///
+    /// # Parameters
+    /// - `origin` - The start of the ray.
+    /// - `direction` - The direction of the ray, this doesn't have to be normalized.
+    /// - `max_distance` - The length of the ray.
+    ///
+    /// # Returns
+    /// The distance from the origin to the box, `0` if the origin is inside the box and `None` if the ray misses it.
+    pub fn intersect_ray(&self, origin: Vec3, direction: Vec3, max_distance: f64) -> Option<f64> {
+        let direction = direction.normalize()?;
+        let (mut near, mut far) = (0.0_f64, max_distance);
+
+        for axis in 0..3 {
+            let (start, step) = (origin.axis(axis), direction.axis(axis));
+            let (min, max) = (self.min.axis(axis), self.max.axis(axis));
+
+            if step == 0.0 {
+                if start < min || start > max {
+                    return None;
+                }
+                continue;
+            }
+
+            let (enter, exit) = ((min - start) / step, (max - start) / step);
+            near = near.max(enter.min(exit));
+            far = far.min(enter.max(exit));
+
+            if near > far {
+                return None;
+            }
+        }
+
+        Some(near)
+    }
+}
+
+/// Finds the first solid block along a ray, using the voxel traversal of Amanatides & Woo.
+///
+/// Every block the ray passes through is visited exactly once, in order. If the origin is inside a solid block,
+/// that block is returned with a distance of `0`.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::raycast::{raycast_blocks, BlockFace, Vec3};
+///
+/// let world = |_x: i32, y: i32, _z: i32| y < 64;
+/// let hit = raycast_blocks(&world, Vec3::new(0.5, 65.62, 0.5), Vec3::new(0.0, -1.0, 0.0), 4.5).unwrap();
+///
+/// assert_eq!((hit.x, hit.y, hit.z), (0, 63, 0));
+/// assert_eq!(hit.face, BlockFace::Top);
+/// ```
+///
+/// # Parameters
+/// - `world` - The blocks the ray travels through.
+/// - `origin` - The start of the ray, for players this is the eye position.
+/// - `direction` - The direction of the ray, this doesn't have to be normalized.
+/// - `max_distance` - The length of the ray.
+///
+/// # Returns
+/// The first block that was hit, `None` if there is no solid block within the distance.
+pub fn raycast_blocks<W: BlockView + ?Sized>(
+    world: &W,
+    origin: Vec3,
+    direction: Vec3,
+    max_distance: f64,
+) -> Option<BlockHit> {
+    let direction = direction.normalize()?;
+
+    let mut block = [0; 3];
+    let mut step = [0; 3];
+    let mut t_max = [f64::INFINITY; 3];
+    let mut t_delta = [f64::INFINITY; 3];
+
+    for axis in 0..3 {
+        let (start, delta) = (origin.axis(axis), direction.axis(axis));
+        block[axis] = start.floor() as i32;
+
+        if delta > 0.0 {
+            step[axis] = 1;
+            t_max[axis] = (block[axis] as f64 + 1.0 - start) / delta;
+        } else if delta < 0.0 {
+            step[axis] = -1;
+            t_max[axis] = (block[axis] as f64 - start) / delta;
+        }
+
+        if delta != 0.0 {
+            t_delta[axis] = 1.0 / delta.abs();
+        }
+    }
+
+    // When starting inside a block, the ray counts as entering through the face it is mostly moving through.
+    let main_axis = (0..3)
+        .max_by(|a, b| {
+            direction
+                .axis(*a)
+                .abs()
+                .total_cmp(&direction.axis(*b).abs())
+        })
+        .unwrap_or(0);
+    let mut face = BlockFace::entered(main_axis, step[main_axis]);
+    let mut distance = 0.0;
+
+    loop {
+        if world.is_solid(block[0], block[1], block[2]) {
+            return Some(BlockHit {
+                x: block[0],
+                y: block[1],
+                z: block[2],
+                face,
+                distance,
+            });
+        }
+
+        let axis = (0..3)
+            .min_by(|a, b| t_max[*a].total_cmp(&t_max[*b]))
+            .unwrap_or(0);
+        if t_max[axis] > max_distance {
+            return None;
+        }
+
+        distance = t_max[axis];
+        block[axis] += step[axis];
+        t_max[axis] += t_delta[axis];
+        face = BlockFace::entered(axis, step[axis]);
+    }
+}
+
+/// Checks whether a player can interact with a block, like when it sends a `UseItemOn` or `PlayerAction` packet.
+///
+/// The block has to be within range of the eyes and no other solid block may be in the way.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::raycast::{can_reach_block, Vec3, BLOCK_INTERACTION_RANGE};
+///
+/// let world = |x: i32, y: i32, _z: i32| y < 64 || x == 3;
+/// let eye = Vec3::new(0.5, 65.62, 0.5);
+///
+/// assert!(can_reach_block(&world, eye, (0, 63, 2), BLOCK_INTERACTION_RANGE));
+/// assert!(!can_reach_block(&world, eye, (4, 64, 0), BLOCK_INTERACTION_RANGE));
+/// assert!(!can_reach_block(&world, eye, (0, 63, 10), BLOCK_INTERACTION_RANGE));
+/// ```
+///
+/// # Parameters
+/// - `world` - The blocks around the player.
+/// - `eye` - The eye position of the player.
+/// - `block` - The position of the block the player interacts with.
+/// - `range` - The interaction range of the player, normally `[BLOCK_INTERACTION_RANGE]`.
+pub fn can_reach_block<W: BlockView + ?Sized>(
+    world: &W,
+    eye: Vec3,
+    block: (i32, i32, i32),
+    range: f64,
+) -> bool {
+    let (x, y, z) = (block.0 as f64, block.1 as f64, block.2 as f64);
+    let bounds = Aabb::new(Vec3::new(x, y, z), Vec3::new(x + 1.0, y + 1.0, z + 1.0));
+    if bounds.distance_squared(eye) > range * range {
+        return false;
+    }
+
+    let target = bounds.center();
+    match raycast_blocks(
+        world,
+        eye,
+        eye.to(target),
+        eye.distance_squared(target).sqrt(),
+    ) {
+        Some(hit) => (hit.x, hit.y, hit.z) == block,
+        None => true,
+    }
+}
+
+/// Checks whether a player can interact with an entity, like when it sends an `Interact` packet.
+///
+/// The hitbox has to be within range of the eyes and no solid block may be between the eyes and the hitbox.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::raycast::{can_reach_entity, Aabb, Vec3, ENTITY_INTERACTION_RANGE};
+///
+/// let world = |_x: i32, y: i32, z: i32| y < 64 || z == 2;
+/// let eye = Vec3::new(0.5, 65.62, 0.5);
+///
+/// let close = Aabb::entity(Vec3::new(0.5, 64.0, 1.5), 0.6, 1.8);
+/// let behind_wall = Aabb::entity(Vec3::new(0.5, 64.0, 3.0), 0.6, 1.8);
+///
+/// assert!(can_reach_entity(&world, eye, &close, ENTITY_INTERACTION_RANGE));
+/// assert!(!can_reach_entity(&world, eye, &behind_wall, ENTITY_INTERACTION_RANGE));
+/// ```
+///
+/// # Parameters
+/// - `world` - The blocks around the player.
+/// - `eye` - The eye position of the player.
+/// - `hitbox` - The hitbox of the entity the player interacts with.
+/// - `range` - The interaction range of the player, normally `[ENTITY_INTERACTION_RANGE]`.
+pub fn can_reach_entity<W: BlockView + ?Sized>(
+    world: &W,
+    eye: Vec3,
+    hitbox: &Aabb,
+    range: f64,
+) -> bool {
+    if hitbox.distance_squared(eye) > range * range {
+        return false;
+    }
+    if hitbox.contains(eye) {
+        return true;
+    }
+
+    let direction = eye.to(hitbox.center());
+    let Some(distance) = hitbox.intersect_ray(eye, direction, direction.length()) else {
+        return false;
+    };
+
+    raycast_blocks(world, eye, direction, distance).is_none()
+}
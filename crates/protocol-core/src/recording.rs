@@ -0,0 +1,286 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use protocol_buf::{
+    buffer::{NormalBuffer, PacketBuffer},
+    types::VarInt,
+};
+use protocol_packets::ConnectionState;
+
+use crate::interceptor::PacketDirection;
+
+/// The bytes every recording starts with.
+pub const RECORDING_MAGIC: [u8; 4] = *b"MCPR";
+
+/// The version of the recording format, which follows the magic bytes.
+pub const RECORDING_VERSION: u8 = 1;
+
+/// The states in the order of their IDs in a recording.
+const STATES: [ConnectionState; 6] = [
+    ConnectionState::Handshake,
+    ConnectionState::Status,
+    ConnectionState::Login,
+    ConnectionState::Transfer,
+    ConnectionState::Configuration,
+    ConnectionState::Play,
+];
+
+/// A packet that was recorded, after it was decrypted and decompressed.
+///
+/// # Examples
+/// ```rust
+/// use std::time::Duration;
+/// use protocol_buf::buffer::Buffer;
+/// use protocol_core::{interceptor::PacketDirection, recording::RecordedFrame};
+/// use protocol_packets::ConnectionState;
+///
+/// let frame = RecordedFrame {
+///     direction: PacketDirection::Clientbound,
+///     elapsed: Duration::from_millis(50),
+///     state: ConnectionState::Status,
+///     packet_id: 0x01,
+///     data: 42_i64.to_be_bytes().to_vec(),
+/// };
+///
+/// let mut packet = frame.to_buffer();
+/// assert_eq!(*packet.packet_id, 0x01);
+/// assert_eq!(packet.read::<i64>().unwrap(), 42);
+/// ```
+///
+/// # Fields
+/// - `direction` - The direction the packet was sent in.
+/// - `elapsed` - The time between the start of the recording and the packet.
+/// - `state` - The state the connection was in.
+/// - `packet_id` - The ID of the packet.
+/// - `data` - The fields of the packet, without the packet ID.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedFrame {
+    pub direction: PacketDirection,
+    pub elapsed: Duration,
+    pub state: ConnectionState,
+    pub packet_id: i32,
+    pub data: Vec<u8>,
+}
+
+impl RecordedFrame {
+    /// Returns the packet as a `[PacketBuffer]`, which can be read by the packets of its state.
+    pub fn to_buffer(&self) -> PacketBuffer {
+        PacketBuffer::with_id(
+            VarInt::from(self.packet_id),
+            NormalBuffer::new(self.data.clone()),
+        )
+    }
+
+    /// Writes the frame in the format of a recording.
+    ///
+    /// Every frame is the direction and the state as one byte each, the elapsed microseconds as a `u64`,
+    /// the packet ID as an `i32` and the length of the data as a `u32` followed by the data, all big endian.
+    fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        let direction = match self.direction {
+            PacketDirection::Serverbound => 0,
+            PacketDirection::Clientbound => 1,
+        };
+        let state = STATES
+            .iter()
+            .position(|state| *state == self.state)
+            .unwrap_or_default() as u8;
+        let length = u32::try_from(self.data.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Packet is too large"))?;
+
+        writer.write_all(&[direction, state])?;
+        writer.write_all(&(self.elapsed.as_micros() as u64).to_be_bytes())?;
+        writer.write_all(&self.packet_id.to_be_bytes())?;
+        writer.write_all(&length.to_be_bytes())?;
+        writer.write_all(&self.data)
+    }
+
+    /// Reads the next frame of a recording.
+    ///
+    /// # Returns
+    /// The frame, `None` if the recording ended.
+    fn read_from(reader: &mut impl Read) -> io::Result<Option<Self>> {
+        let mut header = [0; 2];
+        match reader.read_exact(&mut header) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(e) => return Err(e),
+        }
+
+        let direction = match header[0] {
+            0 => PacketDirection::Serverbound,
+            1 => PacketDirection::Clientbound,
+            direction => return Err(invalid_data(format!("Unknown direction {direction}"))),
+        };
+        let state = *STATES
+            .get(header[1] as usize)
+            .ok_or_else(|| invalid_data(format!("Unknown state {}", header[1])))?;
+
+        let mut elapsed = [0; 8];
+        reader.read_exact(&mut elapsed)?;
+        let mut packet_id = [0; 4];
+        reader.read_exact(&mut packet_id)?;
+        let mut length = [0; 4];
+        reader.read_exact(&mut length)?;
+
+        let mut data = vec![0; u32::from_be_bytes(length) as usize];
+        reader.read_exact(&mut data)?;
+
+        Ok(Some(Self {
+            direction,
+            elapsed: Duration::from_micros(u64::from_be_bytes(elapsed)),
+            state,
+            packet_id: i32::from_be_bytes(packet_id),
+            data,
+        }))
+    }
+}
+
+/// Records the packets of a connection, so they can be replayed with `[PacketReplay]`.
+///
+/// Packets are recorded after they were decrypted and decompressed, so the recording can be read without the keys
+/// of the connection.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::{
+///     interceptor::PacketDirection,
+///     recording::{PacketRecorder, PacketReplay},
+/// };
+/// use protocol_packets::ConnectionState;
+///
+/// let mut recorder = PacketRecorder::new(Vec::new()).unwrap();
+/// recorder.record(PacketDirection::Serverbound, ConnectionState::Status, 0x00, &[]).unwrap();
+/// recorder.record(PacketDirection::Clientbound, ConnectionState::Status, 0x00, b"{}").unwrap();
+///
+/// let recording = recorder.into_inner().unwrap();
+/// let frames = PacketReplay::new(recording.as_slice())
+///     .unwrap()
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// assert_eq!(frames.len(), 2);
+/// assert_eq!(frames[1].direction, PacketDirection::Clientbound);
+/// assert_eq!(frames[1].data, b"{}");
+/// ```
+///
+/// # Fields
+/// - `writer` - Where the recording is written to.
+/// - `started` - When the recording started, the time of every packet is relative to it.
+pub struct PacketRecorder<W: Write = BufWriter<File>> {
+    writer: W,
+    started: Instant,
+}
+
+impl PacketRecorder {
+    /// Creates a file and starts recording into it, an existing file is replaced.
+    ///
+    /// # Parameters
+    /// - `path` - The path of the file.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?))
+    }
+}
+
+impl<W: Write> PacketRecorder<W> {
+    /// Creates a new `[PacketRecorder]` and writes the header of the recording.
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(&RECORDING_MAGIC)?;
+        writer.write_all(&[RECORDING_VERSION])?;
+
+        Ok(Self {
+            writer,
+            started: Instant::now(),
+        })
+    }
+
+    /// Records a packet.
+    ///
+    /// # Parameters
+    /// - `direction` - The direction the packet was sent in.
+    /// - `state` - The state the connection was in.
+    /// - `packet_id` - The ID of the packet.
+    /// - `data` - The fields of the packet, without the packet ID.
+    pub fn record(
+        &mut self,
+        direction: PacketDirection,
+        state: ConnectionState,
+        packet_id: i32,
+        data: &[u8],
+    ) -> io::Result<()> {
+        let frame = RecordedFrame {
+            direction,
+            elapsed: self.started.elapsed(),
+            state,
+            packet_id,
+            data: data.to_vec(),
+        };
+        frame.write_to(&mut self.writer)
+    }
+
+    /// Writes the buffered packets to the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Ends the recording and returns the underlying writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Reads the packets of a recording written by a `[PacketRecorder]`, in the order they were recorded.
+///
+/// # Fields
+/// - `reader` - The recording, after its header.
+pub struct PacketReplay<R: Read = BufReader<File>> {
+    reader: R,
+}
+
+impl PacketReplay {
+    /// Opens a recording file.
+    ///
+    /// # Parameters
+    /// - `path` - The path of the file.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> PacketReplay<R> {
+    /// Creates a new `[PacketReplay]` and checks the header of the recording.
+    ///
+    /// # Returns
+    /// An error if the recording wasn't written by a `[PacketRecorder]` or has another version.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut header = [0; 5];
+        reader.read_exact(&mut header)?;
+        if header[..4] != RECORDING_MAGIC {
+            return Err(invalid_data("Not a packet recording"));
+        }
+        if header[4] != RECORDING_VERSION {
+            return Err(invalid_data(format!(
+                "Unsupported recording version {}",
+                header[4]
+            )));
+        }
+
+        Ok(Self { reader })
+    }
+}
+
+impl<R: Read> Iterator for PacketReplay<R> {
+    type Item = io::Result<RecordedFrame>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        RecordedFrame::read_from(&mut self.reader).transpose()
+    }
+}
+
+fn invalid_data(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
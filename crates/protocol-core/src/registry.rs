@@ -0,0 +1,371 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+    time::Duration,
+};
+
+use protocol_buf::{
+    buffer::{NormalBuffer, PacketBuffer},
+    compression::CompressionHint,
+    text::Component,
+};
+use protocol_packets::{ClientboundPacket, Packet};
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use uuid::Uuid;
+
+use crate::{auth::GameProfile, game_rules::GameRuleChange};
+
+/// A message sent to a `[Client]` from outside of its connection task.
+///
+/// # Variants
+/// - `Packet` - A packet that should be sent to the client.
+/// - `PlayPacket` - A packet that is only sent while the client is in the `Play` state, it is dropped otherwise.
+/// - `Disconnect` - The client should be kicked with the given reason.
+/// - `GameRule` - A game rule changed, the client is told about it once it is in the `Play` state.
+/// - `ViewDistance` - The view distance of the server changed, only clients in the `Play` state are told about it.
+/// - `PermissionLevel` - The permission level of the player changed, only clients in the `Play` state are told about it.
+#[derive(Debug, Clone)]
+pub enum ClientMessage {
+    Packet(EncodedPacket),
+    PlayPacket(EncodedPacket),
+    Disconnect(Component),
+    GameRule(GameRuleChange),
+    ViewDistance { from: u32, to: u32 },
+    PermissionLevel,
+}
+
+/// A packet that was already written, but not compressed yet.
+///
+/// Packets are written once when they are broadcasted, every client compresses them with its own compression data.
+/// The written data is shared, so cloning the packet for every client doesn't copy it.
+///
+/// # Fields
+/// - `buffer` - The written packet.
+/// - `hint` - The compression hint of the packet.
+#[derive(Debug, Clone)]
+pub struct EncodedPacket {
+    buffer: PacketBuffer,
+    hint: CompressionHint,
+}
+
+impl EncodedPacket {
+    /// Writes the given packet.
+    pub fn new<P: ClientboundPacket>(packet: &P) -> Self {
+        let mut buffer = packet.write_packet(NormalBuffer::new(Vec::new()));
+        buffer.buffer.freeze();
+
+        Self {
+            buffer,
+            hint: packet.compression_hint(),
+        }
+    }
+}
+
+impl Packet for EncodedPacket {
+    fn id(&self) -> i32 {
+        *self.buffer.packet_id
+    }
+
+    fn compression_hint(&self) -> CompressionHint {
+        self.hint
+    }
+}
+
+impl ClientboundPacket for EncodedPacket {
+    fn write_packet(&self, _buffer: NormalBuffer) -> PacketBuffer {
+        self.buffer.clone()
+    }
+}
+
+/// A handle to a connected `[Client]`, which can be used from any task.
+///
+/// Messages are queued and handled by the connection task of the client, in the order they were sent.
+///
+/// # Fields
+/// - `uuid` - The UUID of the connection of the client.
+/// - `sender` - The queue of the client.
+/// - `profile` - The game profile of the player, this is shared by all handles of the client and set once it logged in.
+///   It only changes afterwards if the skin of the player is replaced.
+/// - `equipment` - The packet that shows the whole equipment of the player, set once it joined the game.
+/// - `latency` - The latency of the client, measured with the keep alive packets.
+#[derive(Debug, Clone)]
+pub struct ClientHandle {
+    uuid: Uuid,
+    sender: UnboundedSender<ClientMessage>,
+    profile: Arc<RwLock<Option<GameProfile>>>,
+    equipment: Arc<RwLock<Option<EncodedPacket>>>,
+    latency: Arc<RwLock<Duration>>,
+}
+
+impl ClientHandle {
+    /// Creates a new `[ClientHandle]` together with the queue the client reads its messages from.
+    pub fn new(uuid: Uuid) -> (Self, UnboundedReceiver<ClientMessage>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let handle = Self {
+            uuid,
+            sender,
+            profile: Arc::new(RwLock::new(None)),
+            equipment: Arc::new(RwLock::new(None)),
+            latency: Arc::new(RwLock::new(Duration::ZERO)),
+        };
+        (handle, receiver)
+    }
+
+    /// Returns the UUID of the connection of the client, this isn't the UUID of the player.
+    pub const fn uuid(&self) -> Uuid {
+        self.uuid
+    }
+
+    /// Returns the game profile of the player, `None` if the client didn't log in yet.
+    pub fn profile(&self) -> Option<GameProfile> {
+        self.profile.read().unwrap().clone()
+    }
+
+    /// Stores the game profile of the player once it logged in, every handle of the client returns it afterwards.
+    ///
+    /// # Returns
+    /// `false` if the profile was already set.
+    pub fn set_profile(&self, profile: GameProfile) -> bool {
+        let mut current = self.profile.write().unwrap();
+        if current.is_some() {
+            return false;
+        }
+
+        *current = Some(profile);
+        true
+    }
+
+    /// Replaces the game profile of the player, like after its skin changed.
+    pub(crate) fn replace_profile(&self, profile: GameProfile) {
+        *self.profile.write().unwrap() = Some(profile);
+    }
+
+    /// Returns the packet that shows the whole equipment of the player, `None` if it didn't join the game yet.
+    pub fn equipment(&self) -> Option<EncodedPacket> {
+        self.equipment.read().unwrap().clone()
+    }
+
+    /// Replaces the packet that shows the whole equipment of the player, after its equipment changed.
+    pub(crate) fn set_equipment(&self, packet: EncodedPacket) {
+        *self.equipment.write().unwrap() = Some(packet);
+    }
+
+    /// Returns the latency of the client, `Duration::ZERO` until it answered the first keep alive.
+    pub fn latency(&self) -> Duration {
+        *self.latency.read().unwrap()
+    }
+
+    /// Replaces the latency of the client, after it answered a keep alive.
+    pub(crate) fn set_latency(&self, latency: Duration) {
+        *self.latency.write().unwrap() = latency;
+    }
+
+    /// Returns whether the connection of the client is still open.
+    pub fn is_connected(&self) -> bool {
+        !self.sender.is_closed()
+    }
+
+    /// Queues a packet for the client.
+    ///
+    /// # Returns
+    /// `false` if the client already disconnected.
+    pub fn send_packet<P: ClientboundPacket>(&self, packet: &P) -> bool {
+        self.send(ClientMessage::Packet(EncodedPacket::new(packet)))
+    }
+
+    /// Kicks the client with the given reason.
+    ///
+    /// # Returns
+    /// `false` if the client already disconnected.
+    pub fn disconnect(&self, reason: impl Into<Component>) -> bool {
+        self.send(ClientMessage::Disconnect(reason.into()))
+    }
+
+    pub(crate) fn send(&self, message: ClientMessage) -> bool {
+        self.sender.send(message).is_ok()
+    }
+}
+
+/// Keeps track of all connected clients, so packets can be broadcasted to them.
+///
+/// The `[ServerConnection]` registers every client that starts logging in and removes it once its connection is closed.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::{auth::GameProfile, registry::{ClientHandle, ClientRegistry}};
+/// use protocol_packets::v1_21::play::UpdateTimePacket;
+/// use uuid::Uuid;
+///
+/// let registry = ClientRegistry::new();
+/// let (handle, mut messages) = ClientHandle::new(Uuid::new_v4());
+/// registry.register(handle.clone());
+///
+/// let sent = registry.broadcast_packet(&UpdateTimePacket { world_age: 20, time_of_day: 20 });
+///
+/// assert_eq!(sent, 1);
+/// assert!(messages.try_recv().is_ok());
+/// assert!(registry.get_client(handle.uuid()).is_some());
+///
+/// // Players are found by their profile once they logged in.
+/// assert!(registry.get_player("Notch").is_none());
+/// handle.set_profile(GameProfile { uuid: Uuid::nil(), name: "Notch".to_string(), properties: Vec::new() });
+/// assert_eq!(registry.get_player("notch").map(|player| player.uuid()), Some(handle.uuid()));
+/// assert_eq!(registry.players().len(), 1);
+/// ```
+///
+/// # Fields
+/// - `clients` - The handles of all connected clients.
+#[derive(Debug, Default)]
+pub struct ClientRegistry {
+    clients: Mutex<HashMap<Uuid, ClientHandle>>,
+}
+
+impl ClientRegistry {
+    /// Creates a new empty `[ClientRegistry]`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a client to the registry. A client with the same UUID is replaced.
+    pub fn register(&self, handle: ClientHandle) {
+        self.clients.lock().unwrap().insert(handle.uuid, handle);
+    }
+
+    /// Removes a client from the registry.
+    pub fn unregister(&self, uuid: Uuid) -> Option<ClientHandle> {
+        self.clients.lock().unwrap().remove(&uuid)
+    }
+
+    /// Returns the handle of the client with the given UUID.
+    pub fn get_client(&self, uuid: Uuid) -> Option<ClientHandle> {
+        self.clients.lock().unwrap().get(&uuid).cloned()
+    }
+
+    /// Returns the handles of all connected clients.
+    pub fn clients(&self) -> Vec<ClientHandle> {
+        self.clients.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Returns the game profiles of all players that logged in.
+    pub fn players(&self) -> Vec<GameProfile> {
+        self.clients
+            .lock()
+            .unwrap()
+            .values()
+            .filter_map(ClientHandle::profile)
+            .collect()
+    }
+
+    /// Returns the game profile and the latency of every player that logged in.
+    pub fn player_latencies(&self) -> Vec<(GameProfile, Duration)> {
+        self.clients
+            .lock()
+            .unwrap()
+            .values()
+            .filter_map(|client| Some((client.profile()?, client.latency())))
+            .collect()
+    }
+
+    /// Returns the packets that show the equipment of every player in the game, except the given client.
+    pub fn equipment_except(&self, uuid: Uuid) -> Vec<EncodedPacket> {
+        self.clients
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|client| client.uuid != uuid)
+            .filter_map(ClientHandle::equipment)
+            .collect()
+    }
+
+    /// Returns the handle of the player with the given username, the case of the username is ignored.
+    pub fn get_player(&self, name: &str) -> Option<ClientHandle> {
+        self.find_player(|profile| profile.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the handle of the player with the given UUID, this is the UUID of the `[GameProfile]`.
+    pub fn get_player_by_uuid(&self, uuid: Uuid) -> Option<ClientHandle> {
+        self.find_player(|profile| profile.uuid == uuid)
+    }
+
+    fn find_player(&self, predicate: impl Fn(&GameProfile) -> bool) -> Option<ClientHandle> {
+        self.clients
+            .lock()
+            .unwrap()
+            .values()
+            .find(|client| client.profile().is_some_and(|profile| predicate(&profile)))
+            .cloned()
+    }
+
+    /// Returns the amount of connected clients.
+    pub fn len(&self) -> usize {
+        self.clients.lock().unwrap().len()
+    }
+
+    /// Returns whether no clients are connected.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sends a packet to every connected client. The packet is only written once.
+    ///
+    /// # Returns
+    /// The amount of clients the packet was queued for.
+    pub fn broadcast_packet<P: ClientboundPacket>(&self, packet: &P) -> usize {
+        self.broadcast(ClientMessage::Packet(EncodedPacket::new(packet)))
+    }
+
+    /// Sends a message to every connected client.
+    ///
+    /// # Returns
+    /// The amount of clients the message was queued for.
+    pub fn broadcast(&self, message: ClientMessage) -> usize {
+        self.clients
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|client| client.send(message.clone()))
+            .count()
+    }
+
+    /// Sends a message to the given clients, like the players returned by `[SpatialIndex::players_viewing]`.
+    ///
+    /// # Returns
+    /// The amount of clients the message was queued for.
+    pub fn broadcast_to(
+        &self,
+        uuids: impl IntoIterator<Item = Uuid>,
+        message: ClientMessage,
+    ) -> usize {
+        let clients = self.clients.lock().unwrap();
+        uuids
+            .into_iter()
+            .filter_map(|uuid| clients.get(&uuid))
+            .filter(|client| client.send(message.clone()))
+            .count()
+    }
+
+    /// Sends a message to every connected client, except for the given client.
+    ///
+    /// This is used for changes made by a client, which the client already knows about.
+    ///
+    /// # Returns
+    /// The amount of clients the message was queued for.
+    pub fn broadcast_except(&self, uuid: Uuid, message: ClientMessage) -> usize {
+        self.clients
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|client| client.uuid != uuid && client.send(message.clone()))
+            .count()
+    }
+
+    /// Kicks every connected client with the given reason.
+    ///
+    /// The clients are removed from the registry once their connection is closed.
+    pub fn disconnect_all(&self, reason: impl Into<Component>) {
+        let message = ClientMessage::Disconnect(reason.into());
+        for client in self.clients.lock().unwrap().values() {
+            client.send(message.clone());
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use protocol_buf::{text::Component, types::PrefixedOptional};
+use protocol_packets::{
+    resource_pack::ResourcePackStatus,
+    v1_21::{configuration::ConfigurationAddResourcePackPacket, play::PlayAddResourcePackPacket},
+};
+use uuid::Uuid;
+
+/// A resource pack the server sends to its players.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::resource_pack::ResourcePack;
+///
+/// let pack = ResourcePack::new("https://example.com/pack.zip", "")
+///     .required()
+///     .with_prompt("This server uses its own textures");
+///
+/// assert!(pack.required);
+/// assert_eq!(pack.uuid, ResourcePack::new("https://example.com/pack.zip", "").uuid);
+/// ```
+///
+/// # Fields
+/// - `uuid` - The UUID of the pack, a pack with the same UUID replaces the pack on the client.
+/// - `url` - The URL the pack is downloaded from.
+/// - `hash` - The SHA-1 hash of the pack as a hexadecimal string, empty if the client shouldn't cache the pack.
+/// - `required` - Whether players that decline the pack or can't load it are kicked.
+/// - `prompt` - The message shown when the player is asked to accept the pack.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourcePack {
+    pub uuid: Uuid,
+    pub url: String,
+    pub hash: String,
+    pub required: bool,
+    pub prompt: Option<Component>,
+}
+
+impl ResourcePack {
+    /// Creates a new optional `[ResourcePack]`, its UUID is derived from the URL.
+    ///
+    /// # Parameters
+    /// - `url` - The URL the pack is downloaded from.
+    /// - `hash` - The SHA-1 hash of the pack as a hexadecimal string, empty if the client shouldn't cache the pack.
+    pub fn new(url: impl Into<String>, hash: impl Into<String>) -> Self {
+        let url = url.into();
+        Self {
+            uuid: Uuid::new_v3(&Uuid::nil(), url.as_bytes()),
+            url,
+            hash: hash.into(),
+            required: false,
+            prompt: None,
+        }
+    }
+
+    /// Makes the pack required, players that decline it or can't load it are kicked.
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    /// Sets the message shown when the player is asked to accept the pack.
+    pub fn with_prompt(mut self, prompt: impl Into<Component>) -> Self {
+        self.prompt = Some(prompt.into());
+        self
+    }
+
+    /// Creates the packet that adds the pack while the client is being configured.
+    pub fn configuration_packet(&self) -> ConfigurationAddResourcePackPacket {
+        ConfigurationAddResourcePackPacket {
+            uuid: self.uuid.as_u128(),
+            url: self.url.clone(),
+            hash: self.hash.clone(),
+            forced: self.required,
+            prompt: PrefixedOptional(self.prompt.clone()),
+        }
+    }
+
+    /// Creates the packet that adds the pack while the client is playing.
+    pub fn play_packet(&self) -> PlayAddResourcePackPacket {
+        PlayAddResourcePackPacket {
+            uuid: self.uuid.as_u128(),
+            url: self.url.clone(),
+            hash: self.hash.clone(),
+            forced: self.required,
+            prompt: PrefixedOptional(self.prompt.clone()),
+        }
+    }
+}
+
+/// The resource packs a player has, in the order they were added, with the last status the player sent for each.
+///
+/// The client applies the packs on top of each other, so the pack that was added last has the highest priority.
+/// A pack with the same UUID as an existing pack replaces it and moves to the top of the stack.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::resource_pack::{ResourcePack, ResourcePackStack};
+/// use protocol_packets::resource_pack::ResourcePackStatus;
+///
+/// let pack = ResourcePack::new("https://example.com/pack.zip", "").required();
+/// let mut stack = ResourcePackStack::new();
+/// stack.push(pack.clone());
+///
+/// assert_eq!(stack.status(pack.uuid), None);
+/// assert!(stack.record(pack.uuid, ResourcePackStatus::Loaded));
+/// assert_eq!(stack.status(pack.uuid), Some(ResourcePackStatus::Loaded));
+/// assert!(stack.is_loaded());
+///
+/// assert_eq!(stack.remove(pack.uuid), Some(pack));
+/// assert!(stack.is_empty());
+/// ```
+///
+/// # Fields
+/// - `packs` - The packs with the last status the player sent, `None` if the player hasn't answered yet.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResourcePackStack {
+    packs: Vec<(ResourcePack, Option<ResourcePackStatus>)>,
+}
+
+impl ResourcePackStack {
+    /// Creates a new empty `[ResourcePackStack]`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a pack to the top of the stack, replacing the pack with the same UUID.
+    pub fn push(&mut self, pack: ResourcePack) {
+        self.remove(pack.uuid);
+        self.packs.push((pack, None));
+    }
+
+    /// Removes a pack from the stack.
+    ///
+    /// # Returns
+    /// The removed pack, `None` if the player doesn't have the pack.
+    pub fn remove(&mut self, uuid: Uuid) -> Option<ResourcePack> {
+        let index = self.packs.iter().position(|(pack, _)| pack.uuid == uuid)?;
+        Some(self.packs.remove(index).0)
+    }
+
+    /// Removes every pack from the stack.
+    pub fn clear(&mut self) {
+        self.packs.clear();
+    }
+
+    /// Records a status the player sent for a pack.
+    ///
+    /// # Returns
+    /// `false` if the player doesn't have the pack.
+    pub fn record(&mut self, uuid: Uuid, status: ResourcePackStatus) -> bool {
+        let Some((_, current)) = self.packs.iter_mut().find(|(pack, _)| pack.uuid == uuid) else {
+            return false;
+        };
+
+        *current = Some(status);
+        true
+    }
+
+    /// Forgets the statuses of every pack, like when the packs are sent to the player again.
+    pub fn reset_statuses(&mut self) {
+        for (_, status) in &mut self.packs {
+            *status = None;
+        }
+    }
+
+    /// Returns the pack with the given UUID, `None` if the player doesn't have the pack.
+    pub fn get(&self, uuid: Uuid) -> Option<&ResourcePack> {
+        self.packs
+            .iter()
+            .map(|(pack, _)| pack)
+            .find(|pack| pack.uuid == uuid)
+    }
+
+    /// Returns the last status the player sent for a pack, `None` if the player hasn't answered yet or doesn't have the pack.
+    pub fn status(&self, uuid: Uuid) -> Option<ResourcePackStatus> {
+        self.packs
+            .iter()
+            .find(|(pack, _)| pack.uuid == uuid)
+            .and_then(|(_, status)| *status)
+    }
+
+    /// Returns the packs from the bottom to the top of the stack.
+    pub fn packs(&self) -> impl Iterator<Item = &ResourcePack> {
+        self.packs.iter().map(|(pack, _)| pack)
+    }
+
+    /// Returns whether the player has any pack.
+    pub fn is_empty(&self) -> bool {
+        self.packs.is_empty()
+    }
+
+    /// Returns whether the player loaded every pack of the stack.
+    pub fn is_loaded(&self) -> bool {
+        self.packs
+            .iter()
+            .all(|(_, status)| *status == Some(ResourcePackStatus::Loaded))
+    }
+}
+
+impl FromIterator<ResourcePack> for ResourcePackStack {
+    fn from_iter<I: IntoIterator<Item = ResourcePack>>(packs: I) -> Self {
+        let mut stack = Self::new();
+        for pack in packs {
+            stack.push(pack);
+        }
+        stack
+    }
+}
@@ -0,0 +1,187 @@
+use std::{fmt, mem};
+
+use protocol_packets::{
+    v1_21::{
+        configuration::{AcknowledgeFinishConfigurationPacket, ServerboundKnownPacksPacket},
+        login::{LoginAcknowledgedPacket, LoginCookieResponsePacket, LoginStartPacket},
+        play::AcknowledgeConfigurationPacket,
+    },
+    ConnectionState, ServerboundPacket,
+};
+
+/// The amount of serverbound packets in the `Login` state.
+pub const LOGIN_PACKETS: i32 = 0x05;
+
+/// The amount of serverbound packets in the `Configuration` state.
+pub const CONFIGURATION_PACKETS: i32 = 0x08;
+
+/// The amount of serverbound packets in the `Play` state.
+pub const PLAY_PACKETS: i32 = 0x3A;
+
+/// A packet the client sent although it isn't allowed at that point of the connection.
+///
+/// The message of the violation is used as the reason the client is kicked with.
+///
+/// # Variants
+/// - `UnknownPacket` - The packet ID doesn't exist in the state.
+/// - `UnexpectedPacket` - The packet exists, but the server didn't ask for it, like a second `[LoginStartPacket]`.
+/// - `UnknownTeleport` - The client confirmed a teleport that was never sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceViolation {
+    UnknownPacket {
+        state: ConnectionState,
+        packet_id: i32,
+    },
+    UnexpectedPacket {
+        state: ConnectionState,
+        packet_id: i32,
+    },
+    UnknownTeleport(i32),
+}
+
+impl fmt::Display for SequenceViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownPacket { state, packet_id } => {
+                write!(f, "Unknown packet 0x{packet_id:02X} in the {state:?} state")
+            }
+            Self::UnexpectedPacket { state, packet_id } => {
+                write!(
+                    f,
+                    "Unexpected packet 0x{packet_id:02X} in the {state:?} state"
+                )
+            }
+            Self::UnknownTeleport(teleport_id) => write!(f, "Unknown teleport ID {teleport_id}"),
+        }
+    }
+}
+
+/// Tracks which packets a client may send next, so clients that don't follow the login sequence are kicked.
+///
+/// The packets that move the connection to the next phase are only allowed once the server asked for them,
+/// like the `[LoginAcknowledgedPacket]` after the login success was sent. The server never asks for encryption or
+/// plugin responses while logging in, so only the login start, the acknowledgement and cookie responses are allowed there.
+/// Cookie responses are checked against the requested cookies by the `[CookieJar]` of the client.
+/// The other packets of the `Configuration` and `Play` states are allowed at any time.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::sequence::{ProtocolSequence, SequenceViolation};
+/// use protocol_packets::ConnectionState;
+///
+/// let mut sequence = ProtocolSequence::new();
+///
+/// assert!(sequence.receive(ConnectionState::Login, 0x00).is_ok());
+/// assert!(sequence.receive(ConnectionState::Login, 0x00).is_err());
+/// assert!(sequence.receive(ConnectionState::Login, 0x03).is_err());
+///
+/// sequence.sent_login_success();
+/// assert!(sequence.receive(ConnectionState::Login, 0x03).is_ok());
+///
+/// let teleport_id = sequence.next_teleport();
+/// assert!(sequence.confirm_teleport(teleport_id).is_ok());
+/// assert_eq!(sequence.confirm_teleport(5), Err(SequenceViolation::UnknownTeleport(5)));
+/// ```
+///
+/// # Fields
+/// - `login_started` - Whether the client sent the `[LoginStartPacket]`.
+/// - `login_succeeded` - Whether the login success was sent and not acknowledged yet.
+/// - `known_packs_requested` - Whether the known packs were requested and not answered yet.
+/// - `configuration_finished` - Whether the configuration was finished and not acknowledged yet.
+/// - `teleport_id` - The ID of the last teleport sent to the client.
+#[derive(Debug, Clone, Default)]
+pub struct ProtocolSequence {
+    login_started: bool,
+    login_succeeded: bool,
+    known_packs_requested: bool,
+    configuration_finished: bool,
+    teleport_id: i32,
+}
+
+impl ProtocolSequence {
+    /// Creates a new `[ProtocolSequence]` for a client that just sent its handshake.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Checks a packet received from the client and moves the sequence forward.
+    ///
+    /// # Parameters
+    /// - `state` - The state the connection is in.
+    /// - `packet_id` - The ID of the packet.
+    pub fn receive(
+        &mut self,
+        state: ConnectionState,
+        packet_id: i32,
+    ) -> Result<(), SequenceViolation> {
+        let packets = match state {
+            ConnectionState::Login => LOGIN_PACKETS,
+            ConnectionState::Configuration => CONFIGURATION_PACKETS,
+            ConnectionState::Play => PLAY_PACKETS,
+            _ => 0,
+        };
+        if !(0..packets).contains(&packet_id) {
+            return Err(SequenceViolation::UnknownPacket { state, packet_id });
+        }
+
+        let expected = match (state, packet_id) {
+            (ConnectionState::Login, LoginStartPacket::ID) => {
+                !mem::replace(&mut self.login_started, true)
+            }
+            (ConnectionState::Login, LoginAcknowledgedPacket::ID) => {
+                mem::take(&mut self.login_succeeded)
+            }
+            (ConnectionState::Login, LoginCookieResponsePacket::ID) => true,
+            (ConnectionState::Login, _) => false,
+            (ConnectionState::Configuration, ServerboundKnownPacksPacket::ID) => {
+                mem::take(&mut self.known_packs_requested)
+            }
+            (ConnectionState::Configuration, AcknowledgeFinishConfigurationPacket::ID) => {
+                mem::take(&mut self.configuration_finished)
+            }
+            (ConnectionState::Play, AcknowledgeConfigurationPacket::ID) => false,
+            _ => true,
+        };
+
+        if expected {
+            Ok(())
+        } else {
+            Err(SequenceViolation::UnexpectedPacket { state, packet_id })
+        }
+    }
+
+    /// Records that the login success was sent, which the client has to acknowledge.
+    pub fn sent_login_success(&mut self) {
+        self.login_succeeded = true;
+    }
+
+    /// Records that the known packs were requested, which the client has to answer.
+    pub fn sent_known_packs(&mut self) {
+        self.known_packs_requested = true;
+    }
+
+    /// Records that the configuration was finished, which the client has to acknowledge.
+    pub fn sent_finish_configuration(&mut self) {
+        self.configuration_finished = true;
+    }
+
+    /// Returns the ID of the next teleport sent to the client.
+    pub fn next_teleport(&mut self) -> i32 {
+        self.teleport_id = self.teleport_id.wrapping_add(1);
+        self.teleport_id
+    }
+
+    /// Checks a teleport confirmed by the client.
+    ///
+    /// Teleports that were overridden by a later teleport can still be confirmed, the client confirms every teleport.
+    ///
+    /// # Parameters
+    /// - `teleport_id` - The ID of the confirmed teleport.
+    pub fn confirm_teleport(&self, teleport_id: i32) -> Result<(), SequenceViolation> {
+        if (1..=self.teleport_id).contains(&teleport_id) {
+            Ok(())
+        } else {
+            Err(SequenceViolation::UnknownTeleport(teleport_id))
+        }
+    }
+}
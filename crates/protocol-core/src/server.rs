@@ -1,12 +1,60 @@
 use std::{
     future::Future,
-    sync::atomic::{AtomicBool, Ordering},
+    io,
+    net::SocketAddr,
+    path::Path,
+    sync::{atomic::AtomicBool, Arc, RwLock},
+    time::Duration,
 };
 
-use protocol_buf::compression::{CompressionData, CompressionType};
-use tokio::net::TcpListener;
+use protocol_buf::{
+    compression::{CompressionData, CompressionType},
+    text::Component,
+};
+use protocol_packets::{
+    registry::{Registries, RegistryBuilder},
+    report::ReportDetail,
+    tags::TagRegistry,
+    v1_21::play::PlayerInfoRemovePacket,
+    ConnectionState, ServerboundPacket,
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    time,
+};
+use uuid::Uuid;
 
-use crate::client::Client;
+use crate::{
+    audit::{AuditEvent, AuditLog},
+    auth::Authenticator,
+    client::Client,
+    combat::{Combat, CombatSettings},
+    config::{ServerConfig, ServerLinks, SpawnPosition},
+    config_cache::ConfigurationCache,
+    entity::RemovalBatch,
+    forwarding::PlayerForwarding,
+    game_rules::{GameRuleValue, GameRules},
+    handler::PacketHandlers,
+    idle::IdlePolicy,
+    keep_alive,
+    listener::{Listener, ListenerHandle},
+    operators::{Operator, OperatorList, OPS_FILE},
+    outbound::OutboundSettings,
+    proxy::{read_proxy_header, PROXY_HEADER_TIMEOUT},
+    queue::LoginQueue,
+    registry::{ClientHandle, ClientMessage, ClientRegistry, EncodedPacket},
+    resource_pack::ResourcePack,
+    shutdown::ShutdownHandle,
+    status::{
+        validate_handshake, HandshakeConnection, HandshakeRejections, ServerStatus, StatusLimiter,
+        StatusRateLimit,
+    },
+    throttle::{ConnectionThrottle, ThrottleSettings},
+    timings::{PacketTimings, Timings},
+    vehicle::VehicleRegistry,
+    view_distance::{ViewDistanceController, ViewDistanceSettings},
+    world::{LoadedChunks, World},
+};
 
 /// Represents the `[MinecraftServer]` Connection.
 ///
@@ -16,12 +64,18 @@ use crate::client::Client;
 /// This is the size at which all packets should be compressed. Any packets smaller than this size will NOT be compressed.
 ///
 /// # Fields
-/// - `stream` - The TCP listener that listens for incoming connections.
+/// - `listeners` - The TCP listeners that listen for incoming connections, every listener is accepted in its own task.
 /// - `compression_threshold` - The threshold at which packets should be compressed.
 /// - `is_running` - A flag that indicates if the server is running.
+/// - `status_limiter` - The rate limiter for status requests.
+/// - `throttle` - Limits the amount of connections per IP address.
+/// - `config` - The server config shared with every new client.
+/// - `handlers` - The packet handlers shared with every new client.
+/// - `status` - The status shown in the server list.
+/// - `shutdown` - Stops the server and closes the connections it accepted.
 ///
 /// # Examples
-/// ```rust
+/// ```rust,no_run
 /// use tokio::net::TcpListener;
 /// use protocol_core::server::ServerConnection;
 ///
@@ -29,15 +83,21 @@ use crate::client::Client;
 /// async fn main() {
 ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
 ///    let mut server = ServerConnection::new(listener);
-///    server.accept_connections(|client| async move {
+///    server.accept_connections(|mut client| async move {
 ///     client.start().await;
-///    });
+///    }).await;
 /// }
 /// ```
 pub struct ServerConnection {
-    stream: TcpListener,
+    listeners: Vec<Listener>,
     pub compression_threshold: i32,
-    pub is_running: AtomicBool,
+    pub is_running: Arc<AtomicBool>,
+    status_limiter: Arc<StatusLimiter>,
+    throttle: Arc<ConnectionThrottle>,
+    config: Arc<ServerConfig>,
+    handlers: Arc<PacketHandlers>,
+    status: Arc<ServerStatus>,
+    shutdown: ShutdownHandle,
 }
 
 impl ServerConnection {
@@ -46,7 +106,7 @@ impl ServerConnection {
     /// The TCP listener is usally created by binding to an address and port inside `[MinecraftServer]` instead of creating this object yourself.
     ///
     /// # Examples
-    /// ```rust
+    /// ```rust,no_run
     /// use tokio::net::TcpListener;
     /// use protocol_core::server::ServerConnection;
     ///
@@ -56,11 +116,21 @@ impl ServerConnection {
     ///     let server = ServerConnection::new(listener);
     /// }
     /// ```
-    pub const fn new(stream: TcpListener) -> Self {
+    pub fn new(stream: TcpListener) -> Self {
+        let is_running = Arc::new(AtomicBool::new(true));
+        let config = ServerConfig::default();
+        let shutdown = ShutdownHandle::new(is_running.clone(), config.clients.clone());
+
         Self {
-            stream,
+            listeners: vec![Listener::new(stream).expect("a bound listener has a local address")],
             compression_threshold: 256,
-            is_running: AtomicBool::new(true),
+            is_running,
+            status_limiter: Arc::new(StatusLimiter::default()),
+            throttle: ConnectionThrottle::new(config.throttle),
+            config: Arc::new(config),
+            handlers: Arc::new(PacketHandlers::default()),
+            status: Arc::new(ServerStatus::default()),
+            shutdown,
         }
     }
 
@@ -68,34 +138,120 @@ impl ServerConnection {
     ///
     /// This method will call whenever a client tries to connect with the server. This is usually started with the Handshake Packet.
     ///
+    /// Status requests are answered directly without creating a `[Client]`, the callback is only called for clients that want to login.
+    ///
     /// # Parameters
     /// - `callback` - The callback to call when a client connects.
-    pub async fn accept_connections<T, F>(&mut self, mut callback: T)
+    pub async fn accept_connections<T, F>(&mut self, callback: T)
     where
         T: FnMut(Client) -> F + Send + Clone + Copy + 'static,
         F: Future<Output = ()> + Send + 'static,
     {
-        while self.is_running.load(Ordering::SeqCst) {
-            if let Ok((socket, _)) = self.stream.accept().await {
-                let client = Client::new(
-                    socket,
-                    CompressionData::new(self.compression_threshold, CompressionType::None),
-                );
+        let acceptor = Acceptor {
+            compression_threshold: self.compression_threshold,
+            status_limiter: self.status_limiter.clone(),
+            throttle: self.throttle.clone(),
+            config: self.config.clone(),
+            handlers: self.handlers.clone(),
+            status: self.status.clone(),
+            shutdown: self.shutdown.clone(),
+            callback,
+        };
 
+        let tasks: Vec<_> = self
+            .listeners
+            .iter()
+            .cloned()
+            .map(|listener| {
+                let shutdown = self.shutdown.clone();
+                let acceptor = acceptor.clone();
                 tokio::spawn(async move {
-                    callback(client).await;
-                });
-            }
+                    listener
+                        .listen(shutdown, move |socket, address| {
+                            acceptor.accept(socket, address)
+                        })
+                        .await
+                })
+            })
+            .collect();
+
+        let view_distance = self
+            .config
+            .view_distance
+            .clone()
+            .map(|controller| tokio::spawn(controller.run(self.config.clients.clone())));
+        let timings = tokio::spawn(self.config.timings.clone().run());
+        let latencies = tokio::spawn(keep_alive::broadcast_latencies(self.config.clients.clone()));
+        let removals = tokio::spawn(RemovalBatch::run(
+            self.config.removals.clone(),
+            self.config.clients.clone(),
+        ));
+        let chunks = self
+            .config
+            .world
+            .clone()
+            .map(|world| tokio::spawn(LoadedChunks::run(self.config.chunks.clone(), world)));
+
+        for task in tasks {
+            let _ = task.await;
+        }
+        if let Some(view_distance) = view_distance {
+            view_distance.abort();
+        }
+        timings.abort();
+        latencies.abort();
+        removals.abort();
+        if let Some(chunks) = chunks {
+            chunks.abort();
         }
     }
 
+    /// Adds another address the server accepts connections on, like an IPv6 address or another port.
+    ///
+    /// Listeners that are added while the server accepts connections are used once `[ServerConnection::accept_connections]` is called again.
+    ///
+    /// # Parameters
+    /// - `stream` - The TCP listener, bound to the address.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::server::ServerConnection;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("0.0.0.0:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    let handle = server.add_listener(TcpListener::bind("[::]:25565").await.unwrap()).unwrap();
+    ///    println!("Listening on {}", handle.address());
+    /// }
+    /// ```
+    ///
+    /// # Returns
+    /// The handle that starts and stops the listener, an error if the address of the listener can't be read.
+    pub fn add_listener(&mut self, stream: TcpListener) -> io::Result<ListenerHandle> {
+        let listener = Listener::new(stream)?;
+        let handle = listener.handle.clone();
+        self.listeners.push(listener);
+        Ok(handle)
+    }
+
+    /// Returns the handles of every address the server accepts connections on.
+    pub fn listeners(&self) -> Vec<ListenerHandle> {
+        self.listeners
+            .iter()
+            .map(|listener| listener.handle.clone())
+            .collect()
+    }
+
     /// Stops the server from accepting new connections.
     ///
     /// This method also will not stop all the existing connections.
     /// Therefore, you'll have to manually kick all existing connections or they will be timed out after 15 seconds.
+    /// Use `[ServerConnection::shutdown]` to kick them instead.
     ///
     /// # Examples
-    /// ```rust
+    /// ```rust,no_run
     /// use tokio::net::TcpListener;
     /// use protocol_core::server::ServerConnection;
     ///
@@ -107,7 +263,45 @@ impl ServerConnection {
     /// }
     /// ```
     pub fn stop(&mut self) {
-        self.is_running.store(false, Ordering::SeqCst);
+        self.shutdown.stop();
+        for listener in &self.listeners {
+            listener.handle.stop();
+        }
+    }
+
+    /// Stops the server and kicks every connected client with the given reason.
+    ///
+    /// The clients get until the timeout to receive their disconnect packet, connections that are still open afterwards are aborted.
+    ///
+    /// # Parameters
+    /// - `reason` - The reason shown to the kicked clients.
+    /// - `timeout` - The time the connections get to close.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::{server::ServerConnection, shutdown::SHUTDOWN_TIMEOUT};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.shutdown("Server closed", SHUTDOWN_TIMEOUT).await;
+    /// }
+    /// ```
+    ///
+    /// # Returns
+    /// The amount of connections that had to be aborted.
+    pub async fn shutdown(&mut self, reason: impl Into<Component>, timeout: Duration) -> usize {
+        for listener in &self.listeners {
+            listener.handle.stop();
+        }
+        self.shutdown.shutdown(reason, timeout).await
+    }
+
+    /// Returns a handle that shuts the server down, which can be used while the server accepts connections.
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
     }
 
     /// This method sets the compression threshold for all new connections.
@@ -118,7 +312,7 @@ impl ServerConnection {
     /// - `threshold` - The threshold at which packets should be compressed.
     ///
     /// # Examples
-    /// ```rust
+    /// ```rust,no_run
     /// use tokio::net::TcpListener;
     /// use protocol_core::server::ServerConnection;
     ///
@@ -132,113 +326,1949 @@ impl ServerConnection {
     pub fn set_compression_threshold(&mut self, threshold: i32) {
         self.compression_threshold = threshold;
     }
-}
 
-/// Represents the main Minecraft Server object.
-///
-/// This struct holds everything needed to run the Minecraft Server. This includes the server connection and the server itself.
-///
-/// # Fields
-/// - `connection` - The server connection that listens for incoming connections.
-///
-/// # Examples
-/// ```rust
-/// use protocol_core::server::MinecraftServer;
-///
-/// #[tokio::main]
-/// async fn main() {
-///    let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
-/// }
-/// ```
-pub struct MinecraftServer {
-    pub connection: ServerConnection,
-}
+    /// This method sets the status request rate limit for all new connections.
+    ///
+    /// The limit is counted per IP address. Status requests above the limit are dropped without a response.
+    ///
+    /// # Parameters
+    /// - `limit` - The amount of status requests allowed inside a window.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::{server::ServerConnection, status::StatusRateLimit};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_status_rate_limit(StatusRateLimit { max_requests: 5, window: Duration::from_secs(5) });
+    /// }
+    /// ```
+    pub fn set_status_rate_limit(&mut self, limit: StatusRateLimit) {
+        self.status_limiter = Arc::new(StatusLimiter::new(limit));
+    }
 
-impl MinecraftServer {
-    /// Creates a new `[MinecraftServer]` instance with the given address and port.
+    /// This method sets the limits of the connections per IP address and the packets per client for all new connections.
     ///
-    /// This method will bind to the given address and port and start listening for incoming connections.
+    /// Connections above the limits of their IP address are dropped right away, clients that send too many packets are kicked.
     ///
     /// # Parameters
-    /// - `addr` - The address to bind to.
-    /// - `port` - The port to bind to.
+    /// - `settings` - The limits of the connections and packets.
     ///
     /// # Examples
-    /// ```rust
-    /// use protocol_core::server::MinecraftServer;
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::{server::ServerConnection, throttle::ThrottleSettings};
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///   let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_connection_throttle(ThrottleSettings { max_connections_per_ip: 3, ..ThrottleSettings::default() });
     /// }
     /// ```
-    pub async fn new(addr: &str, port: u16) -> Self {
-        Self {
-            connection: ServerConnection::new(
-                TcpListener::bind(format!("{}:{}", addr, port))
-                    .await
-                    .unwrap(),
-            ),
-        }
+    pub fn set_connection_throttle(&mut self, settings: ThrottleSettings) {
+        self.throttle = ConnectionThrottle::new(settings);
+        Arc::make_mut(&mut self.config).throttle = settings;
     }
 
-    /// This method accepts incoming connections from clients.
+    /// This method sets whether new connections start with a PROXY protocol header, like connections from HAProxy or a cloud load balancer.
     ///
-    /// This method will call whenever a client tries to connect with the server. This is usually started with the Handshake Packet.
+    /// The address of the client is read from the header instead of the socket, connections without a valid header are dropped.
+    /// Only enable this if every connection comes from a trusted proxy, otherwise clients can send any address.
+    ///
+    /// # Parameters
+    /// - `enabled` - Whether connections start with a PROXY protocol header.
     ///
     /// # Examples
-    /// ```rust
-    /// use protocol_core::server::MinecraftServer;
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::server::ServerConnection;
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
-    ///     server.accept_connections().await;
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_proxy_protocol(true);
     /// }
     /// ```
-    pub async fn accept_connections(&mut self) {
-        self.connection
-            .accept_connections(|mut connection| async move { connection.start().await })
-            .await;
+    pub fn set_proxy_protocol(&mut self, enabled: bool) {
+        Arc::make_mut(&mut self.config).proxy_protocol = enabled;
     }
 
-    /// Stops the server from accepting new connections.
+    /// This method sets whether handshakes have to be for the port of the listener that accepted the connection.
     ///
-    /// This method also will not stop all the existing connections.
-    /// Therefore, you'll have to manually kick all existing connections or they will be timed out after 15 seconds.
+    /// Handshakes with an unknown intent or without a server address are always rejected. Leave this disabled if
+    /// the port the players connect to isn't the port of the server, like behind a proxy or a forwarded port.
+    ///
+    /// # Parameters
+    /// - `enabled` - Whether handshakes for another port are rejected.
     ///
     /// # Examples
-    /// ```rust
-    /// use protocol_core::server::MinecraftServer;
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::server::ServerConnection;
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
-    ///     server.stop();
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_strict_handshake(true);
     /// }
     /// ```
-    pub fn stop(&mut self) {
-        self.connection.stop();
+    pub fn set_strict_handshake(&mut self, enabled: bool) {
+        Arc::make_mut(&mut self.config).strict_handshake = enabled;
     }
 
-    /// This method sets the compression threshold for all new connections.
+    /// This method returns the counts of the handshakes that were rejected.
+    pub fn handshake_rejections(&self) -> Arc<HandshakeRejections> {
+        self.config.handshake_rejections.clone()
+    }
+
+    /// This method sets how a proxy like BungeeCord or Velocity forwards the players to the server.
     ///
-    /// This WILL not affect existing connections. If you are looking to change the compression threshold for all existing connections. You'll have to manually change it yourself.
+    /// Forwarded players aren't authenticated by the server, their UUID, skin and address are taken from the proxy.
+    /// Only enable this if the server can't be reached without going through the proxy.
     ///
     /// # Parameters
-    /// - `threshold` - The threshold at which packets should be compressed.
+    /// - `forwarding` - How the players are forwarded.
     ///
     /// # Examples
-    /// ```rust
-    /// use protocol_core::server::MinecraftServer;
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::{forwarding::PlayerForwarding, server::ServerConnection};
     ///
     /// #[tokio::main]
     /// async fn main() {
-    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
-    ///     server.set_compression_threshold(25);
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_player_forwarding(PlayerForwarding::Velocity { secret: b"secret".to_vec() });
     /// }
     /// ```
-    pub fn set_compression_threshold(&mut self, threshold: i32) {
-        self.connection.set_compression_threshold(threshold);
+    pub fn set_player_forwarding(&mut self, forwarding: PlayerForwarding) {
+        Arc::make_mut(&mut self.config).forwarding = forwarding;
+    }
+
+    /// This method sets the feature flags that are sent to all new connections during the configuration state.
+    ///
+    /// This WILL not affect existing connections. The vanilla feature flag `minecraft:vanilla` is enabled by default.
+    ///
+    /// # Parameters
+    /// - `feature_flags` - The identifiers of the feature flags to enable.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::server::ServerConnection;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_feature_flags(vec!["minecraft:vanilla".to_string(), "minecraft:trade_rebalance".to_string()]);
+    /// }
+    /// ```
+    pub fn set_feature_flags(&mut self, feature_flags: Vec<String>) {
+        Arc::make_mut(&mut self.config).feature_flags = feature_flags;
+    }
+
+    /// This method sets the brand that is sent to all new connections on the `minecraft:brand` channel.
+    ///
+    /// This WILL not affect existing connections. The brand is shown in the debug screen of the client.
+    ///
+    /// # Parameters
+    /// - `brand` - The brand of the server.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::server::ServerConnection;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_brand("My Server");
+    /// }
+    /// ```
+    pub fn set_brand(&mut self, brand: impl Into<String>) {
+        Arc::make_mut(&mut self.config).brand = brand.into();
+    }
+
+    /// This method sets the position players are teleported to when they join.
+    ///
+    /// This WILL not affect existing connections.
+    ///
+    /// # Parameters
+    /// - `spawn` - The spawn position.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::{config::SpawnPosition, server::ServerConnection};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_spawn(SpawnPosition { y: 100.0, ..SpawnPosition::default() });
+    /// }
+    /// ```
+    pub fn set_spawn(&mut self, spawn: SpawnPosition) {
+        Arc::make_mut(&mut self.config).spawn = spawn;
+    }
+
+    /// This method sets the authenticator used to verify players that log in.
+    ///
+    /// This WILL not affect existing connections. Players join without authentication by default.
+    ///
+    /// # Parameters
+    /// - `authenticator` - The authenticator to use.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::{auth::MojangAuthenticator, server::ServerConnection};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_authenticator(MojangAuthenticator::with_base_url("https://session.example.com"));
+    /// }
+    /// ```
+    pub fn set_authenticator(&mut self, authenticator: impl Authenticator + 'static) {
+        Arc::make_mut(&mut self.config).authenticator = Arc::new(authenticator);
+    }
+
+    /// This method sets the registries that are sent to all new connections during the configuration state.
+    ///
+    /// This WILL not affect existing connections. The vanilla registries are sent by default.
+    ///
+    /// # Parameters
+    /// - `registries` - The registries to send.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::server::ServerConnection;
+    /// use protocol_packets::registry::Registries;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///
+    ///    let mut registries = Registries::vanilla();
+    ///    registries.load_json(&std::fs::read_to_string("registries.json").unwrap()).unwrap();
+    ///    server.set_registries(registries);
+    /// }
+    /// ```
+    pub fn set_registries(&mut self, registries: Registries) {
+        let config = Arc::make_mut(&mut self.config);
+        config.registries = Arc::new(registries);
+        config.configuration_cache = Arc::new(ConfigurationCache::new());
+    }
+
+    /// This method adds custom registry entries to the registries that are sent to all new connections.
+    ///
+    /// This WILL not affect existing connections. The entries are added to the current registries,
+    /// so they can be combined with `[ServerConnection::set_registries]`.
+    ///
+    /// # Parameters
+    /// - `builder` - The custom entries to add.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_buf::nbt::NbtCompound;
+    /// use protocol_core::server::ServerConnection;
+    /// use protocol_packets::registry::RegistryBuilder;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///
+    ///    server.register_entries(
+    ///        RegistryBuilder::new().entry("custom:spell", "custom:fireball", NbtCompound::new().with("damage", 4.0f32)),
+    ///    );
+    /// }
+    /// ```
+    pub fn register_entries(&mut self, builder: RegistryBuilder) {
+        let registries = builder.apply(self.config.registries.as_ref().clone());
+        self.set_registries(registries);
+    }
+
+    /// This method sets the tags that are sent to all new connections after the registries.
+    ///
+    /// This WILL not affect existing connections. The vanilla fluid and damage type tags are sent by default.
+    ///
+    /// # Parameters
+    /// - `tags` - The tags to send, their entries are resolved with the registries of the server.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::server::ServerConnection;
+    /// use protocol_packets::tags::TagRegistry;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_tags(TagRegistry::vanilla().with_tag(
+    ///        "minecraft:damage_type",
+    ///        "minecraft:no_knockback",
+    ///        ["minecraft:drown"],
+    ///    ));
+    /// }
+    /// ```
+    pub fn set_tags(&mut self, tags: TagRegistry) {
+        let config = Arc::make_mut(&mut self.config);
+        config.tags = Arc::new(tags);
+        config.configuration_cache = Arc::new(ConfigurationCache::new());
+    }
+
+    /// This method limits the amount of players that can be logging in at the same time.
+    ///
+    /// Other players wait in a queue until it's their turn. This WILL not affect existing connections.
+    ///
+    /// # Parameters
+    /// - `max_active` - The maximum amount of players in the `Login` and `Configuration` states.
+    /// - `max_waiting` - The maximum amount of players waiting in the queue, other players are kicked.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::server::ServerConnection;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_login_queue(5, 100);
+    /// }
+    /// ```
+    pub fn set_login_queue(&mut self, max_active: usize, max_waiting: usize) {
+        Arc::make_mut(&mut self.config).login_queue = LoginQueue::new(max_active, max_waiting);
+    }
+
+    /// This method sets the links that are sent to all new connections during the configuration state.
+    ///
+    /// The links are shown in the pause menu of the client. This WILL not affect existing connections.
+    ///
+    /// # Parameters
+    /// - `links` - The links of the server.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::{config::ServerLinks, server::ServerConnection};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_links(ServerLinks {
+    ///        website: Some("https://example.com".to_string()),
+    ///        ..ServerLinks::default()
+    ///    });
+    /// }
+    /// ```
+    pub fn set_links(&mut self, links: ServerLinks) {
+        Arc::make_mut(&mut self.config).links = links;
+    }
+
+    /// This method sets the resource packs that are sent to all new connections during the configuration state.
+    ///
+    /// Players that decline a required pack, or can't load it, are kicked. This WILL not affect existing connections.
+    ///
+    /// # Parameters
+    /// - `packs` - The resource packs of the server.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::{resource_pack::ResourcePack, server::ServerConnection};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_resource_packs(vec![
+    ///        ResourcePack::new("https://example.com/pack.zip", "").required(),
+    ///    ]);
+    /// }
+    /// ```
+    pub fn set_resource_packs(&mut self, packs: Vec<ResourcePack>) {
+        Arc::make_mut(&mut self.config).resource_packs = packs;
+    }
+
+    /// This method sets the details that are added to the crash reports and disconnect reports of all new connections.
+    ///
+    /// The details are sent during the configuration state, only the first `[MAX_REPORT_DETAILS]` are sent.
+    /// This WILL not affect existing connections, use `[Client::send_report_details]` for those.
+    ///
+    /// # Parameters
+    /// - `details` - The details, like the name of the server.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::server::ServerConnection;
+    /// use protocol_packets::report::ReportDetail;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_report_details(vec![ReportDetail::new("Server", "lobby-1").unwrap()]);
+    /// }
+    /// ```
+    pub fn set_report_details(&mut self, details: Vec<ReportDetail>) {
+        Arc::make_mut(&mut self.config).report_details = details;
+    }
+
+    /// This method lets players attack each other. This WILL not affect existing connections.
+    ///
+    /// # Parameters
+    /// - `settings` - The damage and knockback of the attacks.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::{combat::CombatSettings, server::ServerConnection};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_combat(CombatSettings { damage: 2.0, ..CombatSettings::default() });
+    /// }
+    /// ```
+    pub fn set_combat(&mut self, settings: CombatSettings) {
+        let config = Arc::make_mut(&mut self.config);
+        config.combat = Some(Arc::new(Combat::new(
+            settings,
+            config.clients.clone(),
+            config.spatial.clone(),
+        )));
+    }
+
+    /// This method returns the combat module, `None` if players can't attack each other.
+    pub fn combat(&self) -> Option<Arc<Combat>> {
+        self.config.combat.clone()
+    }
+
+    /// This method sets the policy that warns and kicks idle players. This WILL not affect existing connections.
+    ///
+    /// # Parameters
+    /// - `policy` - The policy, like an `[IdleTimeout]`.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::{idle::IdleTimeout, server::ServerConnection};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_idle_policy(IdleTimeout::new(Duration::from_secs(600)));
+    /// }
+    /// ```
+    pub fn set_idle_policy(&mut self, policy: impl IdlePolicy + 'static) {
+        Arc::make_mut(&mut self.config).idle_policy = Some(Arc::new(policy));
+    }
+
+    /// This method sets the log the chat messages, commands, joins and quits of the players are recorded to.
+    /// This WILL not affect existing connections.
+    ///
+    /// # Parameters
+    /// - `log` - The log, with the sinks the events are written to.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::{
+    ///     audit::{AuditLog, RotatingFileSink},
+    ///     server::ServerConnection,
+    /// };
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_audit_log(AuditLog::new().with_sink(RotatingFileSink::new("logs/audit.log")));
+    /// }
+    /// ```
+    pub fn set_audit_log(&mut self, log: AuditLog) {
+        Arc::make_mut(&mut self.config).audit_log = Some(Arc::new(log));
+    }
+
+    /// This method sets the limits of the queue of packets that are written to every client.
+    /// This WILL not affect existing connections.
+    ///
+    /// Once a client has `max_queued` writes waiting, sending to it waits until they were written.
+    ///
+    /// # Parameters
+    /// - `settings` - The limits of the queue.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::{outbound::OutboundSettings, server::ServerConnection};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_outbound_settings(OutboundSettings { max_queued: 256, ..OutboundSettings::default() });
+    /// }
+    /// ```
+    pub fn set_outbound_settings(&mut self, settings: OutboundSettings) {
+        Arc::make_mut(&mut self.config).outbound = settings;
+    }
+
+    /// This method scales the view distance with the load of the server. This WILL not affect existing connections.
+    ///
+    /// The view distance is lowered while ticks take too long or too much is sent to the clients, and raised again once the load is gone.
+    /// The durations of the ticks have to be recorded on the `[ViewDistanceController]`.
+    ///
+    /// # Parameters
+    /// - `settings` - The limits of the view distance and the load.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::{server::ServerConnection, view_distance::ViewDistanceSettings};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_view_distance_scaling(ViewDistanceSettings { min: 6, ..ViewDistanceSettings::default() });
+    /// }
+    /// ```
+    ///
+    /// # Returns
+    /// The controller, which the durations of the ticks are recorded on.
+    pub fn set_view_distance_scaling(
+        &mut self,
+        settings: ViewDistanceSettings,
+    ) -> Arc<ViewDistanceController> {
+        let controller = Arc::new(ViewDistanceController::new(settings));
+        Arc::make_mut(&mut self.config).view_distance = Some(controller.clone());
+        controller
+    }
+
+    /// This method returns the controller that scales the view distance, `None` if the view distance is fixed.
+    pub fn view_distance_controller(&self) -> Option<Arc<ViewDistanceController>> {
+        self.config.view_distance.clone()
+    }
+
+    /// This method sets the world whose chunks are streamed to the players as they move.
+    /// This WILL not affect existing connections.
+    ///
+    /// The players spawn at the spawn point of the world and join its dimension.
+    ///
+    /// # Parameters
+    /// - `world` - The world to send.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::{server::ServerConnection, world::FlatWorld};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_world(FlatWorld::new(24, -64, 0, vec![79, 10, 10, 9]));
+    /// }
+    /// ```
+    pub fn set_world(&mut self, world: impl World + 'static) {
+        let config = Arc::make_mut(&mut self.config);
+        config.spawn = world.spawn_point();
+        config.world = Some(Arc::new(world));
+    }
+
+    /// This method counts the packets of every type and the time spent on them. This WILL not affect existing connections.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::server::ServerConnection;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    let timings = server.enable_packet_timings();
+    /// }
+    /// ```
+    ///
+    /// # Returns
+    /// The timings, which are shared by every client.
+    pub fn enable_packet_timings(&mut self) -> Arc<PacketTimings> {
+        let timings = Arc::new(PacketTimings::new());
+        Arc::make_mut(&mut self.config).packet_timings = Some(timings.clone());
+        timings
+    }
+
+    /// This method returns the timings of the packets, `None` if packets aren't timed.
+    pub fn packet_timings(&self) -> Option<Arc<PacketTimings>> {
+        self.config.packet_timings.clone()
+    }
+
+    /// This method returns the timings that capture the spans of ticks, they are shared by every client.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::server::ServerConnection;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let server = ServerConnection::new(listener);
+    ///    server.timings().capture(100);
+    /// }
+    /// ```
+    pub fn timings(&self) -> Arc<Timings> {
+        self.config.timings.clone()
+    }
+
+    /// This method returns the game rules of the server.
+    pub fn game_rules(&self) -> Arc<RwLock<GameRules>> {
+        self.config.game_rules.clone()
+    }
+
+    /// This method sets a game rule.
+    ///
+    /// Unlike the other settings, this DOES affect existing connections. Connected clients are told about the change
+    /// if they know the game rule, like `doImmediateRespawn` or `reducedDebugInfo`.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the game rule, like `keepInventory`.
+    /// - `value` - The new value of the game rule.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::{game_rules::REDUCED_DEBUG_INFO, server::ServerConnection};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let server = ServerConnection::new(listener);
+    ///    server.set_game_rule(REDUCED_DEBUG_INFO, true);
+    /// }
+    /// ```
+    pub fn set_game_rule(&self, name: &str, value: impl Into<GameRuleValue>) {
+        let change = self.config.game_rules.write().unwrap().set(name, value);
+        if let Some(change) = change {
+            self.config
+                .clients
+                .broadcast(ClientMessage::GameRule(change));
+        }
+    }
+
+    /// This method returns the operators of the server.
+    pub fn operators(&self) -> Arc<RwLock<OperatorList>> {
+        self.config.operators.clone()
+    }
+
+    /// This method loads the operators from the given file, like an `ops.json`.
+    ///
+    /// Unlike the other settings, this DOES affect existing connections. Connected clients are told about their new permission level.
+    ///
+    /// # Parameters
+    /// - `path` - The file to load the operators from, it is created once an operator is added.
+    ///
+    /// # Returns
+    /// An error if the file couldn't be read or isn't a valid `ops.json`, the operators aren't changed then.
+    pub fn load_operators(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let operators = OperatorList::load(path)?;
+        *self.config.operators.write().unwrap() = operators;
+        self.config
+            .clients
+            .broadcast(ClientMessage::PermissionLevel);
+        Ok(())
+    }
+
+    /// This method adds an operator and saves the operators.
+    ///
+    /// Unlike the other settings, this DOES affect existing connections. The player is told about its new permission level if it is connected.
+    ///
+    /// # Parameters
+    /// - `operator` - The operator to add, an existing operator with the same UUID is replaced.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::{operators::Operator, server::ServerConnection};
+    /// use uuid::Uuid;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let server = ServerConnection::new(listener);
+    ///    server.add_operator(Operator { uuid: Uuid::nil(), name: "Notch".to_string(), level: 4, bypasses_player_limit: false }).unwrap();
+    /// }
+    /// ```
+    ///
+    /// # Returns
+    /// An error if the operators couldn't be saved.
+    pub fn add_operator(&self, operator: Operator) -> io::Result<()> {
+        let uuid = operator.uuid;
+        self.config.operators.write().unwrap().add(operator)?;
+        self.notify_permission_level(uuid);
+        Ok(())
+    }
+
+    /// This method removes the operator with the given UUID and saves the operators.
+    ///
+    /// Unlike the other settings, this DOES affect existing connections. The player is told about its new permission level if it is connected.
+    ///
+    /// # Parameters
+    /// - `uuid` - The UUID of the player.
+    ///
+    /// # Returns
+    /// The removed operator, `None` if the player wasn't an operator. An error if the operators couldn't be saved.
+    pub fn remove_operator(&self, uuid: &Uuid) -> io::Result<Option<Operator>> {
+        let removed = self.config.operators.write().unwrap().remove(uuid)?;
+        if removed.is_some() {
+            self.notify_permission_level(*uuid);
+        }
+        Ok(removed)
+    }
+
+    fn notify_permission_level(&self, uuid: Uuid) {
+        if let Some(client) = self.config.clients.get_player_by_uuid(uuid) {
+            client.send(ClientMessage::PermissionLevel);
+        }
+    }
+
+    /// This method sets the status shown in the server list.
+    ///
+    /// # Parameters
+    /// - `status` - The new status.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::{server::ServerConnection, status::ServerStatus};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.set_status(ServerStatus::builder().motd("Hello World").build());
+    /// }
+    /// ```
+    pub fn set_status(&mut self, status: ServerStatus) {
+        self.status = Arc::new(status);
+    }
+
+    /// Returns the registry of all connected clients.
+    ///
+    /// Clients are registered once they start logging in and removed after their connection is closed.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::server::ServerConnection;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let server = ServerConnection::new(listener);
+    ///    server.clients().disconnect_all("Server closed");
+    /// }
+    /// ```
+    pub fn clients(&self) -> Arc<ClientRegistry> {
+        self.config.clients.clone()
+    }
+
+    /// Returns the registry of the vehicles placed by the server.
+    ///
+    /// Vehicles are shared with existing connections, placing a vehicle spawns it for every client in the game.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::{raycast::Vec3, server::ServerConnection, vehicle::VehicleKind};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let server = ServerConnection::new(listener);
+    ///    server.vehicles().spawn(VehicleKind::Boat, Vec3::new(0.5, 64.0, 0.5), 0.0);
+    /// }
+    /// ```
+    pub fn vehicles(&self) -> Arc<VehicleRegistry> {
+        self.config.vehicles.clone()
+    }
+
+    /// Registers a handler that is called before the built-in handling of the packet.
+    ///
+    /// The handler is only used by new connections.
+    ///
+    /// # Parameters
+    /// - `handler` - The callback, which receives the client and the packet.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::server::ServerConnection;
+    /// use protocol_packets::v1_21::play::ServerboundKeepAlivePacket;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.on::<ServerboundKeepAlivePacket>(|_client, packet| {
+    ///        println!("Keep Alive: {}", packet.keep_alive_id);
+    ///    });
+    /// }
+    /// ```
+    pub fn on<P: ServerboundPacket + 'static>(
+        &mut self,
+        handler: impl Fn(&mut Client, &P) + Send + Sync + 'static,
+    ) {
+        Arc::make_mut(&mut self.handlers).on(handler);
+    }
+
+    /// Registers a handler that is called after the built-in handling of the packet.
+    ///
+    /// The handler is only used by new connections.
+    ///
+    /// # Parameters
+    /// - `handler` - The callback, which receives the client and the packet.
+    pub fn after<P: ServerboundPacket + 'static>(
+        &mut self,
+        handler: impl Fn(&mut Client, &P) + Send + Sync + 'static,
+    ) {
+        Arc::make_mut(&mut self.handlers).after(handler);
+    }
+
+    /// Registers a handler for the messages clients send on a plugin channel.
+    ///
+    /// The handler is only used by new connections.
+    ///
+    /// # Parameters
+    /// - `channel` - The identifier of the channel, like `example:ping`.
+    /// - `handler` - The callback, which receives the client and the data of the message.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::server::ServerConnection;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.on_channel("example:ping", |client, data| {
+    ///        println!("{} sent {} bytes", client.uuid, data.len());
+    ///    });
+    /// }
+    /// ```
+    pub fn on_channel(
+        &mut self,
+        channel: impl Into<String>,
+        handler: impl Fn(&mut Client, &[u8]) + Send + Sync + 'static,
+    ) {
+        Arc::make_mut(&mut self.handlers).on_channel(channel, handler);
+    }
+
+    /// Registers a handler for the chat messages players send.
+    ///
+    /// Messages are broadcasted to every client in the game, unless a handler returns `false`.
+    /// The handler is only used by new connections.
+    ///
+    /// # Parameters
+    /// - `handler` - The callback, which receives the client and the message and returns whether it is broadcasted.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use tokio::net::TcpListener;
+    /// use protocol_core::server::ServerConnection;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let listener = TcpListener::bind("127.0.0.1:25565").await.unwrap();
+    ///    let mut server = ServerConnection::new(listener);
+    ///    server.on_chat(|_client, message| !message.is_empty());
+    /// }
+    /// ```
+    pub fn on_chat(&mut self, handler: impl Fn(&mut Client, &str) -> bool + Send + Sync + 'static) {
+        Arc::make_mut(&mut self.handlers).on_chat(handler);
+    }
+}
+
+/// Represents the main Minecraft Server object.
+///
+/// This struct holds everything needed to run the Minecraft Server. This includes the server connection and the server itself.
+///
+/// # Fields
+/// - `connection` - The server connection that listens for incoming connections.
+///
+/// # Examples
+/// ```rust,no_run
+/// use protocol_core::server::MinecraftServer;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+/// }
+/// ```
+pub struct MinecraftServer {
+    pub connection: ServerConnection,
+}
+
+impl MinecraftServer {
+    /// Creates a new `[MinecraftServer]` instance with the given address and port.
+    ///
+    /// This method will bind to the given address and port and start listening for incoming connections.
+    /// The operators are loaded from the `ops.json` in the working directory, the server starts without operators if it is invalid.
+    ///
+    /// # Parameters
+    /// - `addr` - The address to bind to.
+    /// - `port` - The port to bind to.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::server::MinecraftServer;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///   let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    /// }
+    /// ```
+    pub async fn new(addr: &str, port: u16) -> Self {
+        let connection = ServerConnection::new(
+            TcpListener::bind(format!("{}:{}", addr, port))
+                .await
+                .unwrap(),
+        );
+        if let Err(e) = connection.load_operators(OPS_FILE) {
+            println!(
+                "Failed to load the operators from {}; err = {}",
+                OPS_FILE, e
+            );
+        }
+
+        Self { connection }
+    }
+
+    /// Creates a new `[MinecraftServer]` instance that listens on every given address, like an IPv4 and an IPv6 address.
+    ///
+    /// All listeners feed the same clients, every listener can be stopped on its own through its `[ListenerHandle]`.
+    /// The operators are loaded from the `ops.json` in the working directory.
+    ///
+    /// # Parameters
+    /// - `addresses` - The addresses to bind to.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::server::MinecraftServer;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let addresses = ["0.0.0.0:25565".parse().unwrap(), "[::]:25565".parse().unwrap()];
+    ///     let mut server = MinecraftServer::bind(&addresses).await.unwrap();
+    /// }
+    /// ```
+    ///
+    /// # Returns
+    /// An error if no address was given, an address can't be bound or the operators can't be loaded.
+    pub async fn bind(addresses: &[SocketAddr]) -> io::Result<Self> {
+        let Some((first, others)) = addresses.split_first() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "no address to bind to",
+            ));
+        };
+
+        let mut connection = ServerConnection::new(TcpListener::bind(first).await?);
+        for address in others {
+            connection.add_listener(TcpListener::bind(address).await?)?;
+        }
+
+        connection.load_operators(OPS_FILE)?;
+
+        Ok(Self { connection })
+    }
+
+    /// Binds another address the server accepts connections on, like another port.
+    ///
+    /// # Parameters
+    /// - `address` - The address to bind to.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::server::MinecraftServer;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     let handle = server.add_listener("127.0.0.1:25566".parse().unwrap()).await.unwrap();
+    ///     handle.stop();
+    /// }
+    /// ```
+    ///
+    /// # Returns
+    /// The handle that starts and stops the listener, an error if the address can't be bound.
+    pub async fn add_listener(&mut self, address: SocketAddr) -> io::Result<ListenerHandle> {
+        let listener = TcpListener::bind(address).await?;
+        self.connection.add_listener(listener)
+    }
+
+    /// Returns the handles of every address the server accepts connections on.
+    pub fn listeners(&self) -> Vec<ListenerHandle> {
+        self.connection.listeners()
+    }
+
+    /// This method accepts incoming connections from clients.
+    ///
+    /// This method will call whenever a client tries to connect with the server. This is usually started with the Handshake Packet.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::server::MinecraftServer;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.accept_connections().await;
+    /// }
+    /// ```
+    pub async fn accept_connections(&mut self) {
+        self.connection
+            .accept_connections(|mut connection| async move { connection.start().await })
+            .await;
+    }
+
+    /// Stops the server from accepting new connections.
+    ///
+    /// This method also will not stop all the existing connections.
+    /// Therefore, you'll have to manually kick all existing connections or they will be timed out after 15 seconds.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::server::MinecraftServer;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.stop();
+    /// }
+    /// ```
+    pub fn stop(&mut self) {
+        self.connection.stop();
+    }
+
+    /// Stops the server and kicks every connected client with the given reason.
+    ///
+    /// The clients get until the timeout to receive their disconnect packet, connections that are still open afterwards are aborted.
+    ///
+    /// # Parameters
+    /// - `reason` - The reason shown to the kicked clients.
+    /// - `timeout` - The time the connections get to close.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::{server::MinecraftServer, shutdown::SHUTDOWN_TIMEOUT};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.shutdown("Server closed", SHUTDOWN_TIMEOUT).await;
+    /// }
+    /// ```
+    ///
+    /// # Returns
+    /// The amount of connections that had to be aborted.
+    pub async fn shutdown(&mut self, reason: impl Into<Component>, timeout: Duration) -> usize {
+        self.connection.shutdown(reason, timeout).await
+    }
+
+    /// Returns a handle that shuts the server down, which can be used while the server accepts connections.
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.connection.shutdown_handle()
+    }
+
+    /// This method sets the compression threshold for all new connections.
+    ///
+    /// This WILL not affect existing connections. If you are looking to change the compression threshold for all existing connections. You'll have to manually change it yourself.
+    ///
+    /// # Parameters
+    /// - `threshold` - The threshold at which packets should be compressed.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::server::MinecraftServer;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.set_compression_threshold(25);
+    /// }
+    /// ```
+    pub fn set_compression_threshold(&mut self, threshold: i32) {
+        self.connection.set_compression_threshold(threshold);
+    }
+
+    /// This method sets the status request rate limit for all new connections.
+    ///
+    /// The limit is counted per IP address. Status requests above the limit are dropped without a response.
+    ///
+    /// # Parameters
+    /// - `limit` - The amount of status requests allowed inside a window.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use protocol_core::{server::MinecraftServer, status::StatusRateLimit};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.set_status_rate_limit(StatusRateLimit { max_requests: 5, window: Duration::from_secs(5) });
+    /// }
+    /// ```
+    pub fn set_status_rate_limit(&mut self, limit: StatusRateLimit) {
+        self.connection.set_status_rate_limit(limit);
+    }
+
+    /// This method sets the limits of the connections per IP address and the packets per client for all new connections.
+    ///
+    /// Connections above the limits of their IP address are dropped right away, clients that send too many packets are kicked.
+    ///
+    /// # Parameters
+    /// - `settings` - The limits of the connections and packets.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::{server::MinecraftServer, throttle::ThrottleSettings};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.set_connection_throttle(ThrottleSettings { max_packets_per_second: 200, ..ThrottleSettings::default() });
+    /// }
+    /// ```
+    pub fn set_connection_throttle(&mut self, settings: ThrottleSettings) {
+        self.connection.set_connection_throttle(settings);
+    }
+
+    /// This method sets whether new connections start with a PROXY protocol header, like connections from HAProxy or a cloud load balancer.
+    ///
+    /// The address of the client is read from the header instead of the socket, connections without a valid header are dropped.
+    /// Only enable this if every connection comes from a trusted proxy, otherwise clients can send any address.
+    ///
+    /// # Parameters
+    /// - `enabled` - Whether connections start with a PROXY protocol header.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::server::MinecraftServer;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("0.0.0.0", 25565).await;
+    ///     server.set_proxy_protocol(true);
+    /// }
+    /// ```
+    pub fn set_proxy_protocol(&mut self, enabled: bool) {
+        self.connection.set_proxy_protocol(enabled);
+    }
+
+    /// This method sets whether handshakes have to be for the port of the listener that accepted the connection.
+    ///
+    /// Handshakes with an unknown intent or without a server address are always rejected.
+    ///
+    /// # Parameters
+    /// - `enabled` - Whether handshakes for another port are rejected.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::server::MinecraftServer;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.set_strict_handshake(true);
+    ///     println!("{} handshakes were rejected", server.handshake_rejections().total());
+    /// }
+    /// ```
+    pub fn set_strict_handshake(&mut self, enabled: bool) {
+        self.connection.set_strict_handshake(enabled);
+    }
+
+    /// This method returns the counts of the handshakes that were rejected.
+    pub fn handshake_rejections(&self) -> Arc<HandshakeRejections> {
+        self.connection.handshake_rejections()
+    }
+
+    /// This method sets how a proxy like BungeeCord or Velocity forwards the players to the server.
+    ///
+    /// Forwarded players aren't authenticated by the server, their UUID, skin and address are taken from the proxy.
+    /// Only enable this if the server can't be reached without going through the proxy.
+    ///
+    /// # Parameters
+    /// - `forwarding` - How the players are forwarded.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::{forwarding::PlayerForwarding, server::MinecraftServer};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("0.0.0.0", 25565).await;
+    ///     server.set_player_forwarding(PlayerForwarding::BungeeCord);
+    /// }
+    /// ```
+    pub fn set_player_forwarding(&mut self, forwarding: PlayerForwarding) {
+        self.connection.set_player_forwarding(forwarding);
+    }
+
+    /// This method sets the feature flags that are sent to all new connections during the configuration state.
+    ///
+    /// This WILL not affect existing connections. The vanilla feature flag `minecraft:vanilla` is enabled by default.
+    ///
+    /// # Parameters
+    /// - `feature_flags` - The identifiers of the feature flags to enable.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::server::MinecraftServer;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.set_feature_flags(vec!["minecraft:vanilla".to_string(), "minecraft:trade_rebalance".to_string()]);
+    /// }
+    /// ```
+    pub fn set_feature_flags(&mut self, feature_flags: Vec<String>) {
+        self.connection.set_feature_flags(feature_flags);
+    }
+
+    /// This method sets the brand that is sent to all new connections on the `minecraft:brand` channel.
+    ///
+    /// This WILL not affect existing connections. The brand is shown in the debug screen of the client.
+    ///
+    /// # Parameters
+    /// - `brand` - The brand of the server.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::server::MinecraftServer;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.set_brand("My Server");
+    /// }
+    /// ```
+    pub fn set_brand(&mut self, brand: impl Into<String>) {
+        self.connection.set_brand(brand);
+    }
+
+    /// This method sets the position players are teleported to when they join.
+    ///
+    /// This WILL not affect existing connections.
+    ///
+    /// # Parameters
+    /// - `spawn` - The spawn position.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::{config::SpawnPosition, server::MinecraftServer};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.set_spawn(SpawnPosition { y: 100.0, ..SpawnPosition::default() });
+    /// }
+    /// ```
+    pub fn set_spawn(&mut self, spawn: SpawnPosition) {
+        self.connection.set_spawn(spawn);
+    }
+
+    /// This method sets the authenticator used to verify players that log in.
+    ///
+    /// This WILL not affect existing connections. Players join without authentication by default.
+    ///
+    /// # Parameters
+    /// - `authenticator` - The authenticator to use.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::{auth::OfflineAuthenticator, server::MinecraftServer};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.set_authenticator(OfflineAuthenticator);
+    /// }
+    /// ```
+    pub fn set_authenticator(&mut self, authenticator: impl Authenticator + 'static) {
+        self.connection.set_authenticator(authenticator);
+    }
+
+    /// This method sets the registries that are sent to all new connections during the configuration state.
+    ///
+    /// This WILL not affect existing connections. The vanilla registries are sent by default.
+    ///
+    /// # Parameters
+    /// - `registries` - The registries to send.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::server::MinecraftServer;
+    /// use protocol_packets::registry::Registries;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.set_registries(Registries::from_json(r#"{ "minecraft:wolf_variant": {} }"#).unwrap());
+    /// }
+    /// ```
+    pub fn set_registries(&mut self, registries: Registries) {
+        self.connection.set_registries(registries);
+    }
+
+    /// This method adds custom registry entries to the registries that are sent to all new connections.
+    ///
+    /// This WILL not affect existing connections.
+    ///
+    /// # Parameters
+    /// - `builder` - The custom entries to add.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::server::MinecraftServer;
+    /// use protocol_packets::damage_type::{DamageScaling, DamageType};
+    /// use protocol_packets::registry::RegistryBuilder;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.register_entries(
+    ///         RegistryBuilder::new().value(&DamageType::new("custom:laser", "laser", DamageScaling::Never, 0.0)),
+    ///     );
+    /// }
+    /// ```
+    pub fn register_entries(&mut self, builder: RegistryBuilder) {
+        self.connection.register_entries(builder);
+    }
+
+    /// This method sets the tags that are sent to all new connections after the registries.
+    ///
+    /// This WILL not affect existing connections. The vanilla fluid and damage type tags are sent by default.
+    ///
+    /// # Parameters
+    /// - `tags` - The tags to send, their entries are resolved with the registries of the server.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::server::MinecraftServer;
+    /// use protocol_packets::tags::TagRegistry;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.set_tags(TagRegistry::new());
+    /// }
+    /// ```
+    pub fn set_tags(&mut self, tags: TagRegistry) {
+        self.connection.set_tags(tags);
+    }
+
+    /// This method limits the amount of players that can be logging in at the same time.
+    ///
+    /// Other players wait in a queue until it's their turn. This WILL not affect existing connections.
+    ///
+    /// # Parameters
+    /// - `max_active` - The maximum amount of players in the `Login` and `Configuration` states.
+    /// - `max_waiting` - The maximum amount of players waiting in the queue, other players are kicked.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::server::MinecraftServer;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.set_login_queue(5, 100);
+    /// }
+    /// ```
+    pub fn set_login_queue(&mut self, max_active: usize, max_waiting: usize) {
+        self.connection.set_login_queue(max_active, max_waiting);
+    }
+
+    /// This method sets the links that are sent to all new connections during the configuration state.
+    ///
+    /// # Parameters
+    /// - `links` - The links of the server.
+    pub fn set_links(&mut self, links: ServerLinks) {
+        self.connection.set_links(links);
+    }
+
+    /// This method sets the resource packs that are sent to all new connections during the configuration state.
+    ///
+    /// # Parameters
+    /// - `packs` - The resource packs of the server.
+    pub fn set_resource_packs(&mut self, packs: Vec<ResourcePack>) {
+        self.connection.set_resource_packs(packs);
+    }
+
+    /// This method sets the details that are added to the crash reports and disconnect reports of all new connections.
+    ///
+    /// # Parameters
+    /// - `details` - The details, like the name of the server.
+    pub fn set_report_details(&mut self, details: Vec<ReportDetail>) {
+        self.connection.set_report_details(details);
+    }
+
+    /// This method lets players attack each other. This WILL not affect existing connections.
+    ///
+    /// # Parameters
+    /// - `settings` - The damage and knockback of the attacks.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::{combat::CombatSettings, server::MinecraftServer};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.set_combat(CombatSettings::default());
+    /// }
+    /// ```
+    pub fn set_combat(&mut self, settings: CombatSettings) {
+        self.connection.set_combat(settings);
+    }
+
+    /// This method returns the combat module, `None` if players can't attack each other.
+    pub fn combat(&self) -> Option<Arc<Combat>> {
+        self.connection.combat()
+    }
+
+    /// This method sets the policy that warns and kicks idle players. This WILL not affect existing connections.
+    ///
+    /// # Parameters
+    /// - `policy` - The policy, like an `[IdleTimeout]`.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use protocol_core::{idle::IdleTimeout, server::MinecraftServer};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.set_idle_policy(IdleTimeout::new(Duration::from_secs(600)));
+    /// }
+    /// ```
+    pub fn set_idle_policy(&mut self, policy: impl IdlePolicy + 'static) {
+        self.connection.set_idle_policy(policy);
+    }
+
+    /// This method sets the log the chat messages, commands, joins and quits of the players are recorded to.
+    /// This WILL not affect existing connections.
+    ///
+    /// # Parameters
+    /// - `log` - The log, with the sinks the events are written to.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use protocol_core::{
+    ///     audit::{AuditLog, RotatingFileSink},
+    ///     server::MinecraftServer,
+    /// };
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     let sink = RotatingFileSink::new("logs/audit.log").with_max_age(Duration::from_secs(24 * 60 * 60));
+    ///     server.set_audit_log(AuditLog::new().with_sink(sink));
+    /// }
+    /// ```
+    pub fn set_audit_log(&mut self, log: AuditLog) {
+        self.connection.set_audit_log(log);
+    }
+
+    /// This method sets the limits of the queue of packets that are written to every client.
+    /// This WILL not affect existing connections.
+    ///
+    /// Once a client has `max_queued` writes waiting, sending to it waits until they were written.
+    ///
+    /// # Parameters
+    /// - `settings` - The limits of the queue.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::{outbound::OutboundSettings, server::MinecraftServer};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.set_outbound_settings(OutboundSettings { max_queued: 256, ..OutboundSettings::default() });
+    /// }
+    /// ```
+    pub fn set_outbound_settings(&mut self, settings: OutboundSettings) {
+        self.connection.set_outbound_settings(settings);
+    }
+
+    /// This method scales the view distance with the load of the server. This WILL not affect existing connections.
+    ///
+    /// The view distance is lowered while ticks take too long or too much is sent to the clients, and raised again once the load is gone.
+    ///
+    /// # Parameters
+    /// - `settings` - The limits of the view distance and the load.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use std::time::{Duration, Instant};
+    /// use protocol_core::{server::MinecraftServer, view_distance::ViewDistanceSettings};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     let controller = server.set_view_distance_scaling(ViewDistanceSettings {
+    ///         max_bandwidth: Some(10 * 1024 * 1024),
+    ///         ..ViewDistanceSettings::default()
+    ///     });
+    ///
+    ///     let tick = Instant::now();
+    ///     // Run the tick of the game.
+    ///     controller.record_tick(tick.elapsed());
+    /// }
+    /// ```
+    ///
+    /// # Returns
+    /// The controller, which the durations of the ticks are recorded on.
+    pub fn set_view_distance_scaling(
+        &mut self,
+        settings: ViewDistanceSettings,
+    ) -> Arc<ViewDistanceController> {
+        self.connection.set_view_distance_scaling(settings)
+    }
+
+    /// This method returns the controller that scales the view distance, `None` if the view distance is fixed.
+    pub fn view_distance_controller(&self) -> Option<Arc<ViewDistanceController>> {
+        self.connection.view_distance_controller()
+    }
+
+    /// This method sets the world whose chunks are streamed to the players as they move.
+    /// This WILL not affect existing connections.
+    ///
+    /// # Parameters
+    /// - `world` - The world to send.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::{server::MinecraftServer, world::FlatWorld};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.set_world(FlatWorld::new(24, -64, 0, vec![79, 10, 10, 9]));
+    /// }
+    /// ```
+    pub fn set_world(&mut self, world: impl World + 'static) {
+        self.connection.set_world(world);
+    }
+
+    /// This method counts the packets of every type and the time spent on them, to find the packets that take up most of the time.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::server::MinecraftServer;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     let timings = server.enable_packet_timings();
+    ///
+    ///     for (key, timing) in timings.snapshot().iter().take(5) {
+    ///         println!("{:?}: {} packets, {:?}", key, timing.count, timing.time);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// # Returns
+    /// The timings, which are shared by every client.
+    pub fn enable_packet_timings(&mut self) -> Arc<PacketTimings> {
+        self.connection.enable_packet_timings()
+    }
+
+    /// This method returns the timings of the packets, `None` if packets aren't timed.
+    pub fn packet_timings(&self) -> Option<Arc<PacketTimings>> {
+        self.connection.packet_timings()
+    }
+
+    /// This method returns the timings that capture the spans of ticks, they are shared by every client.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use protocol_core::{server::MinecraftServer, timings::Timings};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     let timings = server.timings();
+    ///     timings.capture(100);
+    ///
+    ///     tokio::time::sleep(Duration::from_secs(5)).await;
+    ///     if let Some(spans) = timings.take_capture() {
+    ///         std::fs::write("timings.json", Timings::to_chrome_trace(&spans)).unwrap();
+    ///     }
+    /// }
+    /// ```
+    pub fn timings(&self) -> Arc<Timings> {
+        self.connection.timings()
+    }
+
+    /// This method returns the game rules of the server.
+    pub fn game_rules(&self) -> Arc<RwLock<GameRules>> {
+        self.connection.game_rules()
+    }
+
+    /// This method sets a game rule.
+    ///
+    /// Unlike the other settings, this DOES affect existing connections. Connected clients are told about the change
+    /// if they know the game rule, like `doImmediateRespawn` or `reducedDebugInfo`.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the game rule, like `keepInventory`.
+    /// - `value` - The new value of the game rule.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::{game_rules::DO_IMMEDIATE_RESPAWN, server::MinecraftServer};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.set_game_rule(DO_IMMEDIATE_RESPAWN, true);
+    /// }
+    /// ```
+    pub fn set_game_rule(&self, name: &str, value: impl Into<GameRuleValue>) {
+        self.connection.set_game_rule(name, value);
+    }
+
+    /// This method returns the operators of the server.
+    pub fn operators(&self) -> Arc<RwLock<OperatorList>> {
+        self.connection.operators()
+    }
+
+    /// This method loads the operators from the given file, like an `ops.json`.
+    ///
+    /// Unlike the other settings, this DOES affect existing connections. Connected clients are told about their new permission level.
+    ///
+    /// # Parameters
+    /// - `path` - The file to load the operators from, it is created once an operator is added.
+    ///
+    /// # Returns
+    /// An error if the file couldn't be read or isn't a valid `ops.json`, the operators aren't changed then.
+    pub fn load_operators(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.connection.load_operators(path)
+    }
+
+    /// This method adds an operator and saves the operators.
+    ///
+    /// Unlike the other settings, this DOES affect existing connections. The player is told about its new permission level if it is connected.
+    ///
+    /// # Parameters
+    /// - `operator` - The operator to add, an existing operator with the same UUID is replaced.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::{operators::Operator, server::MinecraftServer};
+    /// use uuid::Uuid;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.add_operator(Operator { uuid: Uuid::nil(), name: "Notch".to_string(), level: 4, bypasses_player_limit: false }).unwrap();
+    /// }
+    /// ```
+    ///
+    /// # Returns
+    /// An error if the operators couldn't be saved.
+    pub fn add_operator(&self, operator: Operator) -> io::Result<()> {
+        self.connection.add_operator(operator)
+    }
+
+    /// This method removes the operator with the given UUID and saves the operators.
+    ///
+    /// Unlike the other settings, this DOES affect existing connections. The player is told about its new permission level if it is connected.
+    ///
+    /// # Parameters
+    /// - `uuid` - The UUID of the player.
+    ///
+    /// # Returns
+    /// The removed operator, `None` if the player wasn't an operator. An error if the operators couldn't be saved.
+    pub fn remove_operator(&self, uuid: &Uuid) -> io::Result<Option<Operator>> {
+        self.connection.remove_operator(uuid)
+    }
+
+    /// This method sets the status shown in the server list.
+    ///
+    /// # Parameters
+    /// - `status` - The new status.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::{server::MinecraftServer, status::ServerStatus};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.set_status(ServerStatus::builder().motd("Hello World").max_players(100).build());
+    /// }
+    /// ```
+    pub fn set_status(&mut self, status: ServerStatus) {
+        self.connection.set_status(status);
+    }
+
+    /// Returns the registry of all connected clients, which can be used to broadcast packets.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::server::MinecraftServer;
+    /// use protocol_packets::v1_21::play::UpdateTimePacket;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.clients().broadcast_packet(&UpdateTimePacket { world_age: 0, time_of_day: 6000 });
+    /// }
+    /// ```
+    pub fn clients(&self) -> Arc<ClientRegistry> {
+        self.connection.clients()
+    }
+
+    /// Returns the registry of the vehicles placed by the server, which players can ride.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::{raycast::Vec3, server::MinecraftServer, vehicle::VehicleKind};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.vehicles().spawn(VehicleKind::Minecart, Vec3::new(0.5, 64.0, 0.5), 0.0);
+    /// }
+    /// ```
+    pub fn vehicles(&self) -> Arc<VehicleRegistry> {
+        self.connection.vehicles()
+    }
+
+    /// Registers a handler that is called before the built-in handling of the packet.
+    ///
+    /// # Parameters
+    /// - `handler` - The callback, which receives the client and the packet.
+    ///
+    /// # Examples
+    /// ```rust,no_run
+    /// use protocol_core::server::MinecraftServer;
+    /// use protocol_packets::v1_21::play::ServerboundKeepAlivePacket;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    ///     server.on::<ServerboundKeepAlivePacket>(|_client, packet| {
+    ///         println!("Keep Alive: {}", packet.keep_alive_id);
+    ///     });
+    /// }
+    /// ```
+    pub fn on<P: ServerboundPacket + 'static>(
+        &mut self,
+        handler: impl Fn(&mut Client, &P) + Send + Sync + 'static,
+    ) {
+        self.connection.on(handler);
+    }
+
+    /// Registers a handler that is called after the built-in handling of the packet.
+    ///
+    /// # Parameters
+    /// - `handler` - The callback, which receives the client and the packet.
+    pub fn after<P: ServerboundPacket + 'static>(
+        &mut self,
+        handler: impl Fn(&mut Client, &P) + Send + Sync + 'static,
+    ) {
+        self.connection.after(handler);
+    }
+
+    /// Registers a handler for the messages clients send on a plugin channel.
+    ///
+    /// # Parameters
+    /// - `channel` - The identifier of the channel, like `example:ping`.
+    /// - `handler` - The callback, which receives the client and the data of the message.
+    pub fn on_channel(
+        &mut self,
+        channel: impl Into<String>,
+        handler: impl Fn(&mut Client, &[u8]) + Send + Sync + 'static,
+    ) {
+        self.connection.on_channel(channel, handler);
+    }
+
+    /// Registers a handler for the chat messages players send.
+    ///
+    /// # Parameters
+    /// - `handler` - The callback, which receives the client and the message and returns whether it is broadcasted.
+    pub fn on_chat(&mut self, handler: impl Fn(&mut Client, &str) -> bool + Send + Sync + 'static) {
+        self.connection.on_chat(handler);
+    }
+}
+
+/// Hands the connections accepted by the listeners of a `[ServerConnection]` to the client callback.
+///
+/// # Fields
+/// - `compression_threshold` - The threshold at which packets should be compressed.
+/// - `status_limiter` - The rate limiter for status requests.
+/// - `throttle` - Limits the amount of connections per IP address.
+/// - `config` - The server config shared with every new client.
+/// - `handlers` - The packet handlers shared with every new client.
+/// - `status` - The status shown in the server list.
+/// - `shutdown` - Keeps track of the connection tasks, so they can be aborted once the server shuts down.
+/// - `callback` - The callback that is called for every client that wants to login.
+#[derive(Clone)]
+struct Acceptor<T> {
+    compression_threshold: i32,
+    status_limiter: Arc<StatusLimiter>,
+    throttle: Arc<ConnectionThrottle>,
+    config: Arc<ServerConfig>,
+    handlers: Arc<PacketHandlers>,
+    status: Arc<ServerStatus>,
+    shutdown: ShutdownHandle,
+    callback: T,
+}
+
+impl<T, F> Acceptor<T>
+where
+    T: FnMut(Client) -> F + Send + Clone + Copy + 'static,
+    F: Future<Output = ()> + Send + 'static,
+{
+    /// Handles an accepted connection in its own task.
+    ///
+    /// Status requests are answered directly, the callback is only called for clients that want to login.
+    /// Connections of throttled addresses are dropped right away, behind a proxy once the PROXY protocol header was read.
+    fn accept(&self, mut socket: TcpStream, address: SocketAddr) {
+        let throttle = self.throttle.clone();
+        let proxy_protocol = self.config.proxy_protocol;
+        let compression = CompressionData::new(self.compression_threshold, CompressionType::None);
+        let status_limiter = self.status_limiter.clone();
+        let config = self.config.clone();
+        let handlers = self.handlers.clone();
+        let status = self.status.clone();
+        let clients = self.config.clients.clone();
+        let vehicles = self.config.vehicles.clone();
+        let spatial = self.config.spatial.clone();
+        let combat = self.config.combat.clone();
+        let audit_log = self.config.audit_log.clone();
+        let port = socket.local_addr().ok().map(|address| address.port());
+        let mut callback = self.callback;
+
+        self.shutdown.spawn(async move {
+            // Behind a proxy every connection comes from the proxy, the address of the client is sent in the header.
+            let address = if proxy_protocol {
+                match time::timeout(PROXY_HEADER_TIMEOUT, read_proxy_header(&mut socket)).await {
+                    Ok(Ok(Some(client_address))) => client_address,
+                    Ok(Ok(None)) => address,
+                    _ => return,
+                }
+            } else {
+                address
+            };
+            let Some(_permit) = throttle.try_connect(address.ip()) else {
+                return;
+            };
+
+            let mut connection = HandshakeConnection::new(socket);
+            match connection.handle_legacy_ping(&status).await {
+                Ok(false) => {}
+                _ => return,
+            }
+
+            let handshake = match config.forwarding {
+                PlayerForwarding::BungeeCord => connection.read_forwarded_handshake().await,
+                _ => connection
+                    .read_handshake()
+                    .await
+                    .map(|handshake| handshake.map(|handshake| (handshake, None))),
+            };
+            let Ok(Some((handshake, forwarded))) = handshake else {
+                return;
+            };
+
+            let port = port.filter(|_| config.strict_handshake);
+            let intent = match validate_handshake(&handshake, port) {
+                Ok(intent) => intent,
+                Err(rejection) => {
+                    println!("Rejecting handshake from {}; err = {}", address, rejection);
+                    config.handshake_rejections.record(rejection);
+                    let _ = connection.reject(&handshake, rejection).await;
+                    return;
+                }
+            };
+
+            match intent {
+                ConnectionState::Status if status_limiter.try_acquire(address.ip()) => {
+                    let _ = connection.handle_status(&status.to_json()).await;
+                }
+                ConnectionState::Login | ConnectionState::Transfer => {
+                    let mut client = connection.into_client(
+                        ConnectionState::Login,
+                        compression,
+                        config,
+                        handlers,
+                    );
+                    client.protocol_version = *handshake.protocol_version;
+                    client.transferred = intent == ConnectionState::Transfer;
+                    client.connection.set_address(address);
+                    if let Some(forwarded) = forwarded {
+                        client.set_forwarded(forwarded);
+                    }
+                    let uuid = client.uuid;
+                    let entity_id = client.entity_id;
+                    clients.register(client.handle());
+                    callback(client).await;
+                    let handle = clients.unregister(uuid);
+                    if let Some(profile) = handle.as_ref().and_then(ClientHandle::profile) {
+                        if let Some(audit_log) = &audit_log {
+                            audit_log.record(AuditEvent::Quit {
+                                name: profile.name.clone(),
+                                uuid: profile.uuid,
+                            });
+                        }
+                        let packet = PlayerInfoRemovePacket {
+                            uuids: vec![profile.uuid.as_u128()],
+                        };
+                        let message = ClientMessage::PlayPacket(EncodedPacket::new(&packet));
+                        clients.broadcast(message);
+                    }
+                    vehicles.dismount(uuid);
+                    spatial.write().unwrap().remove(entity_id);
+                    if let Some(combat) = &combat {
+                        combat.remove(entity_id);
+                    }
+                }
+                _ => {}
+            }
+        });
     }
 }
@@ -0,0 +1,111 @@
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use protocol_buf::text::Component;
+use tokio::{sync::watch, task::JoinSet};
+
+use crate::registry::ClientRegistry;
+
+/// The time clients get to receive their disconnect packet before their connections are closed.
+pub const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Shuts a server down, which can be used from any task, like the task that waits for Ctrl-C.
+///
+/// Shutting down stops every listener of the server, kicks every registered client and waits until their
+/// connections were closed, so their disconnect packets are written. Connections that are still open once the
+/// timeout passed, like clients that don't read or connections that are still in their handshake, are aborted.
+///
+/// # Examples
+/// ```rust,no_run
+/// use protocol_core::{server::MinecraftServer, shutdown::SHUTDOWN_TIMEOUT};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+///     let shutdown = server.shutdown_handle();
+///     tokio::spawn(async move {
+///         server.accept_connections().await;
+///     });
+///
+///     tokio::signal::ctrl_c().await.unwrap();
+///     shutdown.shutdown("Server closed", SHUTDOWN_TIMEOUT).await;
+/// }
+/// ```
+///
+/// # Fields
+/// - `is_running` - Whether the server accepts connections, shared with the `[ServerConnection]`.
+/// - `stopped` - Tells the listeners that the server stopped, so they don't wait for another connection.
+/// - `clients` - The registry of all connected clients.
+/// - `tasks` - The tasks of the connections accepted by the server.
+#[derive(Debug, Clone)]
+pub struct ShutdownHandle {
+    is_running: Arc<AtomicBool>,
+    stopped: Arc<watch::Sender<bool>>,
+    clients: Arc<ClientRegistry>,
+    tasks: Arc<Mutex<JoinSet<()>>>,
+}
+
+impl ShutdownHandle {
+    /// Creates a new `[ShutdownHandle]` for a running server.
+    pub(crate) fn new(is_running: Arc<AtomicBool>, clients: Arc<ClientRegistry>) -> Self {
+        Self {
+            is_running,
+            stopped: Arc::new(watch::Sender::new(false)),
+            clients,
+            tasks: Arc::new(Mutex::new(JoinSet::new())),
+        }
+    }
+
+    /// Returns whether the server accepts connections.
+    pub fn is_running(&self) -> bool {
+        self.is_running.load(Ordering::SeqCst)
+    }
+
+    /// Stops the server from accepting new connections, without closing the existing connections.
+    pub fn stop(&self) {
+        self.is_running.store(false, Ordering::SeqCst);
+        self.stopped.send_replace(true);
+    }
+
+    /// Stops the server, kicks every client with the given reason and waits until their connections were closed.
+    ///
+    /// # Parameters
+    /// - `reason` - The reason shown to the kicked clients.
+    /// - `timeout` - The time the connections get to close, before they are aborted.
+    ///
+    /// # Returns
+    /// The amount of connections that had to be aborted.
+    pub async fn shutdown(&self, reason: impl Into<Component>, timeout: Duration) -> usize {
+        self.stop();
+        self.clients.disconnect_all(reason);
+
+        let mut tasks = std::mem::take(&mut *self.tasks.lock().unwrap());
+        let _ = tokio::time::timeout(timeout, async {
+            while tasks.join_next().await.is_some() {}
+        })
+        .await;
+
+        let aborted = tasks.len();
+        tasks.shutdown().await;
+        aborted
+    }
+
+    /// Waits until the server is stopped.
+    pub(crate) async fn stopped(&self) {
+        let mut stopped = self.stopped.subscribe();
+        let _ = stopped.wait_for(|stopped| *stopped).await;
+    }
+
+    /// Spawns the task of an accepted connection, so it can be aborted once the server shuts down.
+    pub(crate) fn spawn(&self, task: impl Future<Output = ()> + Send + 'static) {
+        let mut tasks = self.tasks.lock().unwrap();
+        while tasks.try_join_next().is_some() {}
+        tasks.spawn(task);
+    }
+}
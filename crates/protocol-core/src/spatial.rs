@@ -0,0 +1,217 @@
+use std::collections::{HashMap, HashSet};
+
+use protocol_packets::chunk::ChunkPos;
+use uuid::Uuid;
+
+use crate::raycast::Vec3;
+
+/// A player whose position is tracked by the `[SpatialIndex]`.
+///
+/// # Fields
+/// - `uuid` - The UUID of the client, which is used to send it packets.
+/// - `view_distance` - The view distance of the player in chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Viewer {
+    pub uuid: Uuid,
+    pub view_distance: u32,
+}
+
+/// Keeps track of the positions of entities and players, sorted into the chunks they are in.
+///
+/// Range queries only look at the chunks that can contain a result, so the cost depends on the size of the range
+/// and not on the amount of entities. The index has to be updated whenever an entity moves.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::{raycast::Vec3, spatial::SpatialIndex};
+/// use protocol_packets::chunk::ChunkPos;
+/// use uuid::Uuid;
+///
+/// let mut index = SpatialIndex::new();
+/// index.insert(1, Vec3::new(0.0, 64.0, 0.0));
+/// index.insert(2, Vec3::new(20.0, 64.0, 0.0));
+///
+/// let player = Uuid::new_v4();
+/// index.insert_player(3, player, Vec3::new(100.0, 64.0, 0.0), 2);
+///
+/// assert_eq!(index.entities_within(Vec3::new(1.0, 64.0, 0.0), 5.0), vec![1]);
+/// assert_eq!(index.players_viewing(ChunkPos::new(7, 0)), vec![player]);
+/// assert!(index.players_viewing(ChunkPos::new(0, 0)).is_empty());
+///
+/// // Moving into another chunk updates the buckets.
+/// assert!(index.update(3, Vec3::new(10.0, 64.0, 0.0)));
+/// assert_eq!(index.players_viewing(ChunkPos::new(0, 0)), vec![player]);
+/// ```
+///
+/// # Fields
+/// - `positions` - The position of every entity.
+/// - `chunks` - The entities in every chunk.
+/// - `viewers` - The players, by their entity ID.
+/// - `max_view_distance` - The largest view distance of all players, this limits the chunks `[SpatialIndex::players_viewing]` looks at.
+#[derive(Debug, Clone, Default)]
+pub struct SpatialIndex {
+    positions: HashMap<i32, Vec3>,
+    chunks: HashMap<ChunkPos, HashSet<i32>>,
+    viewers: HashMap<i32, Viewer>,
+    max_view_distance: u32,
+}
+
+impl SpatialIndex {
+    /// Creates a new empty `[SpatialIndex]`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an entity at the given position, an entity that was already added is moved.
+    pub fn insert(&mut self, entity_id: i32, position: Vec3) {
+        self.update(entity_id, position);
+    }
+
+    /// Adds a player at the given position, players are returned by `[SpatialIndex::players_viewing]`.
+    ///
+    /// # Parameters
+    /// - `entity_id` - The entity ID of the player.
+    /// - `uuid` - The UUID of the client.
+    /// - `position` - The position of the player.
+    /// - `view_distance` - The view distance of the player in chunks.
+    pub fn insert_player(
+        &mut self,
+        entity_id: i32,
+        uuid: Uuid,
+        position: Vec3,
+        view_distance: u32,
+    ) {
+        self.insert(entity_id, position);
+        self.viewers.insert(
+            entity_id,
+            Viewer {
+                uuid,
+                view_distance,
+            },
+        );
+        self.max_view_distance = self.max_view_distance.max(view_distance);
+    }
+
+    /// Changes the view distance of a player.
+    pub fn set_view_distance(&mut self, entity_id: i32, view_distance: u32) {
+        if let Some(viewer) = self.viewers.get_mut(&entity_id) {
+            viewer.view_distance = view_distance;
+            self.update_max_view_distance();
+        }
+    }
+
+    /// Moves an entity to the given position.
+    ///
+    /// # Returns
+    /// Whether the entity is in another chunk now, this is also `true` for entities that weren't added yet.
+    pub fn update(&mut self, entity_id: i32, position: Vec3) -> bool {
+        let chunk = chunk_of(position);
+        let previous = self.positions.insert(entity_id, position).map(chunk_of);
+        if previous == Some(chunk) {
+            return false;
+        }
+
+        if let Some(previous) = previous {
+            self.remove_from_chunk(previous, entity_id);
+        }
+        self.chunks.entry(chunk).or_default().insert(entity_id);
+        true
+    }
+
+    /// Removes an entity or a player.
+    ///
+    /// # Returns
+    /// The last position of the entity.
+    pub fn remove(&mut self, entity_id: i32) -> Option<Vec3> {
+        let position = self.positions.remove(&entity_id)?;
+        self.remove_from_chunk(chunk_of(position), entity_id);
+
+        if self.viewers.remove(&entity_id).is_some() {
+            self.update_max_view_distance();
+        }
+        Some(position)
+    }
+
+    /// Returns the position of an entity.
+    pub fn position(&self, entity_id: i32) -> Option<Vec3> {
+        self.positions.get(&entity_id).copied()
+    }
+
+    /// Returns the player with the given entity ID.
+    pub fn viewer(&self, entity_id: i32) -> Option<Viewer> {
+        self.viewers.get(&entity_id).copied()
+    }
+
+    /// Returns the amount of entities, including players.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns whether the index doesn't contain any entities.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Returns the entities, including players, that are at most `radius` blocks away from the given position.
+    pub fn entities_within(&self, position: Vec3, radius: f64) -> Vec<i32> {
+        let min = chunk_of(Vec3::new(position.x - radius, 0.0, position.z - radius));
+        let max = chunk_of(Vec3::new(position.x + radius, 0.0, position.z + radius));
+
+        let mut entities = Vec::new();
+        for x in min.x..=max.x {
+            for z in min.z..=max.z {
+                let Some(chunk) = self.chunks.get(&ChunkPos::new(x, z)) else {
+                    continue;
+                };
+
+                entities.extend(chunk.iter().copied().filter(|entity_id| {
+                    self.positions[entity_id].distance_squared(position) <= radius * radius
+                }));
+            }
+        }
+
+        entities
+    }
+
+    /// Returns the clients of the players that have the given chunk inside their view distance.
+    ///
+    /// This is used to send packets about something that happened in the chunk, like a sound or a moving entity.
+    pub fn players_viewing(&self, chunk: ChunkPos) -> Vec<Uuid> {
+        let mut players = Vec::new();
+        for pos in chunk.square(self.max_view_distance) {
+            let Some(entities) = self.chunks.get(&pos) else {
+                continue;
+            };
+
+            players.extend(entities.iter().filter_map(|entity_id| {
+                let viewer = self.viewers.get(entity_id)?;
+                (pos.distance(chunk) <= viewer.view_distance).then_some(viewer.uuid)
+            }));
+        }
+
+        players
+    }
+
+    fn remove_from_chunk(&mut self, chunk: ChunkPos, entity_id: i32) {
+        if let Some(entities) = self.chunks.get_mut(&chunk) {
+            entities.remove(&entity_id);
+            if entities.is_empty() {
+                self.chunks.remove(&chunk);
+            }
+        }
+    }
+
+    fn update_max_view_distance(&mut self) {
+        self.max_view_distance = self
+            .viewers
+            .values()
+            .map(|viewer| viewer.view_distance)
+            .max()
+            .unwrap_or(0);
+    }
+}
+
+/// Returns the chunk that contains the given position.
+pub fn chunk_of(position: Vec3) -> ChunkPos {
+    ChunkPos::from_block(position.x.floor() as i32, position.z.floor() as i32)
+}
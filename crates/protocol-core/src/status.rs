@@ -0,0 +1,874 @@
+use std::{
+    collections::HashMap,
+    fmt, io,
+    net::IpAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use protocol_buf::{
+    buffer::{Buffer, NormalBuffer, PacketBuffer},
+    compression::CompressionData,
+    text::Component,
+    types::{BoundedString, VarInt},
+};
+use protocol_packets::{
+    v1_21::{
+        handshake::HandshakePacket,
+        login::LoginDisconnectPacket,
+        status::{PingRequestPacket, PongResponsePacket, StatusResponsePacket},
+        PROTOCOL_VERSION, VERSION_NAME,
+    },
+    ClientboundPacket, ConnectionState, ServerboundPacket,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+use uuid::Uuid;
+
+use crate::{
+    client::{Client, ClientConnection},
+    config::ServerConfig,
+    forwarding::ForwardedPlayer,
+    handler::PacketHandlers,
+};
+
+/// The size of the stack buffer used by a `[HandshakeConnection]`.
+///
+/// This fits the largest possible handshake packet and the players forwarded by BungeeCord, status packets are a lot smaller than this.
+pub const HANDSHAKE_BUFFER_SIZE: usize = 4096;
+
+/// The maximum length of a server address that contains a player forwarded by BungeeCord.
+pub const MAX_FORWARDED_ADDRESS_LENGTH: usize = 32767;
+
+/// The MOTD that is shown when no other MOTD is configured.
+pub const DEFAULT_MOTD: &str = "Wowie a Rust Status Request!";
+
+/// The first byte sent by clients older than Minecraft 1.7 when they ping a server.
+pub const LEGACY_PING: u8 = 0xFE;
+
+/// The ID of the kick packet, which is used to answer a legacy ping.
+const LEGACY_KICK: u8 = 0xFF;
+
+/// The ID of the plugin message that clients from Minecraft 1.6 send right after a legacy ping.
+const LEGACY_PLUGIN_MESSAGE: u8 = 0xFA;
+
+/// The channel of the plugin message that clients from Minecraft 1.6 send right after a legacy ping.
+const LEGACY_PING_CHANNEL: &str = "MC|PingHost";
+
+/// The amount of tracked addresses after which expired entries are removed from the `[StatusLimiter]`.
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// Provides the amount of online players. This is called for every status request.
+pub type PlayerCountProvider = Arc<dyn Fn() -> i32 + Send + Sync>;
+
+/// Represents a player shown when hovering over the player count in the server list.
+///
+/// # Fields
+/// - `name` - The name of the player.
+/// - `id` - The UUID of the player.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerSample {
+    pub name: String,
+    pub id: Uuid,
+}
+
+/// Represents the status shown in the server list of the client.
+///
+/// The status is usually created with a `[StatusBuilder]`.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::status::ServerStatus;
+///
+/// let status = ServerStatus::builder()
+///     .motd("A Minecraft Server")
+///     .max_players(100)
+///     .player_count(|| 5)
+///     .build();
+///
+/// let json = status.to_json();
+/// assert!(json.contains(r#""online":5"#));
+/// assert!(json.contains("A Minecraft Server"));
+/// ```
+///
+/// # Fields
+/// - `version_name` - The name of the version shown when the client uses a different version.
+/// - `protocol` - The protocol version of the server.
+/// - `max_players` - The maximum amount of players.
+/// - `online_players` - Provides the amount of players that are currently online.
+/// - `sample` - The players shown when hovering over the player count.
+/// - `description` - The MOTD.
+/// - `favicon` - The icon of the server as a base64 PNG data URL.
+/// - `enforces_secure_chat` - Whether the server requires signed chat messages.
+#[derive(Clone)]
+pub struct ServerStatus {
+    pub version_name: String,
+    pub protocol: i32,
+    pub max_players: i32,
+    pub online_players: PlayerCountProvider,
+    pub sample: Vec<PlayerSample>,
+    pub description: Component,
+    pub favicon: Option<String>,
+    pub enforces_secure_chat: bool,
+}
+
+impl ServerStatus {
+    /// Creates a new `[StatusBuilder]` with the default status.
+    pub fn builder() -> StatusBuilder {
+        StatusBuilder::new()
+    }
+
+    /// Creates the JSON response sent to the client.
+    ///
+    /// The amount of online players is requested from the provider every time this is called.
+    pub fn to_json(&self) -> String {
+        let mut status = json!({
+            "version": {
+                "name": self.version_name,
+                "protocol": self.protocol,
+            },
+            "players": {
+                "max": self.max_players,
+                "online": (self.online_players)(),
+                "sample": self.sample,
+            },
+            "description": self.description,
+            "enforcesSecureChat": self.enforces_secure_chat,
+        });
+
+        if let Some(favicon) = &self.favicon {
+            status["favicon"] = json!(favicon);
+        }
+
+        status.to_string()
+    }
+
+    /// Creates the kick packet that answers a legacy ping of clients older than Minecraft 1.7.
+    ///
+    /// Clients from 1.4 to 1.6 send `0xFE 0x01` and receive the version and protocol as well,
+    /// older clients only send `0xFE` and receive the MOTD and player count.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_core::status::ServerStatus;
+    ///
+    /// let status = ServerStatus::builder().motd("Hi").max_players(20).player_count(|| 1).build();
+    /// let response = status.to_legacy(false);
+    ///
+    /// let text: Vec<u16> = "Hi§1§20".encode_utf16().collect();
+    /// assert_eq!(response[0], 0xFF);
+    /// assert_eq!(u16::from_be_bytes([response[1], response[2]]) as usize, text.len());
+    /// ```
+    ///
+    /// # Parameters
+    /// - `with_version` - Whether the response contains the version, this is `true` for clients from 1.4 to 1.6.
+    pub fn to_legacy(&self, with_version: bool) -> Vec<u8> {
+        let motd = self.description.to_plain_text();
+        let (online, max) = ((self.online_players)(), self.max_players);
+
+        let text = if with_version {
+            format!(
+                "§1\0{}\0{}\0{}\0{}\0{}",
+                self.protocol, self.version_name, motd, online, max
+            )
+        } else {
+            format!("{}§{}§{}", motd.replace('§', ""), online, max)
+        };
+        let text: Vec<u16> = text.encode_utf16().collect();
+
+        let mut bytes = vec![LEGACY_KICK];
+        bytes.extend_from_slice(&(text.len() as u16).to_be_bytes());
+        for unit in text {
+            bytes.extend_from_slice(&unit.to_be_bytes());
+        }
+        bytes
+    }
+}
+
+/// Represents the status of another server, received with a server list ping.
+///
+/// # Examples
+/// ```rust
+/// use std::time::Duration;
+/// use protocol_core::status::{ServerStatus, StatusResponse};
+///
+/// let json = ServerStatus::builder().motd("Hello").max_players(10).build().to_json();
+/// let status = StatusResponse::from_json(&json, Duration::from_millis(12)).unwrap();
+///
+/// assert_eq!(status.max_players, 10);
+/// assert_eq!(status.description.to_json(), r#"{"text":"Hello"}"#);
+/// assert_eq!(status.latency, Duration::from_millis(12));
+/// ```
+///
+/// # Fields
+/// - `version_name` - The name of the version the server is running.
+/// - `protocol` - The protocol version of the server.
+/// - `max_players` - The maximum amount of players.
+/// - `online_players` - The amount of players that are online.
+/// - `sample` - The players shown when hovering over the player count.
+/// - `description` - The MOTD.
+/// - `favicon` - The icon of the server as a base64 PNG data URL.
+/// - `enforces_secure_chat` - Whether the server requires signed chat messages.
+/// - `latency` - The time it took for the server to answer the ping request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusResponse {
+    pub version_name: String,
+    pub protocol: i32,
+    pub max_players: i32,
+    pub online_players: i32,
+    pub sample: Vec<PlayerSample>,
+    pub description: Component,
+    pub favicon: Option<String>,
+    pub enforces_secure_chat: bool,
+    pub latency: Duration,
+}
+
+impl StatusResponse {
+    /// Parses the status JSON sent by a server.
+    ///
+    /// Missing fields are set to their default value, a description can either be a string or a text component.
+    ///
+    /// # Parameters
+    /// - `json` - The status JSON.
+    /// - `latency` - The measured latency to the server.
+    ///
+    /// # Returns
+    /// The status, an error if the JSON is invalid.
+    pub fn from_json(json: &str, latency: Duration) -> serde_json::Result<Self> {
+        let status: Value = serde_json::from_str(json)?;
+        let number = |value: &Value| value.as_i64().unwrap_or_default() as i32;
+
+        let description = match &status["description"] {
+            Value::String(text) => Component::text(text.as_str()),
+            Value::Null => Component::text(""),
+            description => serde_json::from_value(description.clone())?,
+        };
+        let sample = match &status["players"]["sample"] {
+            Value::Null => Vec::new(),
+            sample => serde_json::from_value(sample.clone())?,
+        };
+
+        Ok(Self {
+            version_name: status["version"]["name"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            protocol: number(&status["version"]["protocol"]),
+            max_players: number(&status["players"]["max"]),
+            online_players: number(&status["players"]["online"]),
+            sample,
+            description,
+            favicon: status["favicon"].as_str().map(str::to_string),
+            enforces_secure_chat: status["enforcesSecureChat"].as_bool().unwrap_or_default(),
+            latency,
+        })
+    }
+}
+
+impl Default for ServerStatus {
+    fn default() -> Self {
+        Self {
+            version_name: VERSION_NAME.to_string(),
+            protocol: PROTOCOL_VERSION,
+            max_players: 20,
+            online_players: Arc::new(|| 0),
+            sample: Vec::new(),
+            description: Component::text(DEFAULT_MOTD),
+            favicon: None,
+            enforces_secure_chat: false,
+        }
+    }
+}
+
+/// A builder for the `[ServerStatus]`.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::status::{PlayerSample, StatusBuilder};
+/// use uuid::Uuid;
+///
+/// let status = StatusBuilder::new()
+///     .motd("Hello World")
+///     .sample(vec![PlayerSample { name: "Notch".to_string(), id: Uuid::nil() }])
+///     .enforces_secure_chat(true)
+///     .build();
+///
+/// assert_eq!(status.sample.len(), 1);
+/// assert!(status.enforces_secure_chat);
+/// ```
+#[derive(Clone, Default)]
+pub struct StatusBuilder {
+    status: ServerStatus,
+}
+
+impl StatusBuilder {
+    /// Creates a new `[StatusBuilder]` with the default status.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the version shown to the client.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the version.
+    /// - `protocol` - The protocol version.
+    pub fn version(mut self, name: impl Into<String>, protocol: i32) -> Self {
+        self.status.version_name = name.into();
+        self.status.protocol = protocol;
+        self
+    }
+
+    /// Sets the MOTD, this can be plain text or a styled `[Component]`.
+    pub fn motd(mut self, motd: impl Into<Component>) -> Self {
+        self.status.description = motd.into();
+        self
+    }
+
+    /// Sets the maximum amount of players.
+    pub fn max_players(mut self, max_players: i32) -> Self {
+        self.status.max_players = max_players;
+        self
+    }
+
+    /// Sets the provider for the amount of online players.
+    pub fn player_count(mut self, provider: impl Fn() -> i32 + Send + Sync + 'static) -> Self {
+        self.status.online_players = Arc::new(provider);
+        self
+    }
+
+    /// Sets the players shown when hovering over the player count.
+    pub fn sample(mut self, sample: Vec<PlayerSample>) -> Self {
+        self.status.sample = sample;
+        self
+    }
+
+    /// Sets the icon of the server.
+    ///
+    /// # Parameters
+    /// - `png` - The bytes of the icon, which has to be a 64x64 PNG.
+    pub fn favicon(mut self, png: &[u8]) -> Self {
+        self.status.favicon = Some(format!("data:image/png;base64,{}", STANDARD.encode(png)));
+        self
+    }
+
+    /// Sets whether the server requires signed chat messages.
+    pub fn enforces_secure_chat(mut self, enforces_secure_chat: bool) -> Self {
+        self.status.enforces_secure_chat = enforces_secure_chat;
+        self
+    }
+
+    /// Creates the `[ServerStatus]`.
+    pub fn build(self) -> ServerStatus {
+        self.status
+    }
+}
+
+/// Defines how many status requests a single IP address can make.
+///
+/// # Fields
+/// - `max_requests` - The maximum amount of status requests inside the window.
+/// - `window` - The duration after which the requests are reset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatusRateLimit {
+    pub max_requests: u32,
+    pub window: Duration,
+}
+
+impl Default for StatusRateLimit {
+    fn default() -> Self {
+        Self {
+            max_requests: 10,
+            window: Duration::from_secs(10),
+        }
+    }
+}
+
+/// Limits the amount of status requests per IP address.
+///
+/// Each address gets a fixed window. Once `max_requests` is reached inside the window, every other status request is dropped until the window is over.
+///
+/// # Examples
+/// ```rust
+/// use std::time::Duration;
+/// use protocol_core::status::{StatusLimiter, StatusRateLimit};
+///
+/// let limiter = StatusLimiter::new(StatusRateLimit { max_requests: 1, window: Duration::from_secs(10) });
+/// let address = "127.0.0.1".parse().unwrap();
+///
+/// assert!(limiter.try_acquire(address));
+/// assert!(!limiter.try_acquire(address));
+/// ```
+#[derive(Debug, Default)]
+pub struct StatusLimiter {
+    limit: StatusRateLimit,
+    requests: Mutex<HashMap<IpAddr, (Instant, u32)>>,
+}
+
+impl StatusLimiter {
+    /// Creates a new `[StatusLimiter]` with the given rate limit.
+    pub fn new(limit: StatusRateLimit) -> Self {
+        Self {
+            limit,
+            requests: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Tries to count a status request for the given address.
+    ///
+    /// # Parameters
+    /// - `address` - The address of the client.
+    ///
+    /// # Returns
+    /// `true` if the status request is allowed, `false` if the address is being rate limited.
+    pub fn try_acquire(&self, address: IpAddr) -> bool {
+        let now = Instant::now();
+        let window = self.limit.window;
+        let mut requests = self.requests.lock().unwrap();
+
+        if requests.len() >= PRUNE_THRESHOLD {
+            requests.retain(|_, (start, _)| now.duration_since(*start) < window);
+        }
+
+        let (start, count) = requests.entry(address).or_insert((now, 0));
+        if now.duration_since(*start) >= window {
+            *start = now;
+            *count = 0;
+        }
+
+        if *count >= self.limit.max_requests {
+            return false;
+        }
+
+        *count += 1;
+        true
+    }
+}
+
+/// The reason a handshake was rejected.
+///
+/// The message of the rejection is used as the reason clients that want to login are disconnected with.
+///
+/// # Variants
+/// - `UnknownIntent` - The intent of the handshake isn't status, login or transfer.
+/// - `EmptyAddress` - The server address of the handshake is empty.
+/// - `PortMismatch` - The port of the handshake isn't the port the connection was accepted on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandshakeRejection {
+    UnknownIntent(i32),
+    EmptyAddress,
+    PortMismatch { expected: u16, received: u16 },
+}
+
+impl fmt::Display for HandshakeRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownIntent(intent) => write!(f, "Unknown handshake intent {intent}"),
+            Self::EmptyAddress => write!(f, "The handshake doesn't contain a server address"),
+            Self::PortMismatch { expected, received } => write!(
+                f,
+                "The handshake is for port {received}, but the server listens on port {expected}"
+            ),
+        }
+    }
+}
+
+/// Checks whether the first bytes of a connection are a legacy ping, the way the vanilla server checks them.
+///
+/// `[LEGACY_PING]` is also the first byte of a frame length of 254, 382 and so on, so only the exact legacy pings are
+/// accepted: a bare `0xFE`, `0xFE 0x01`, or `0xFE 0x01` followed by the `MC|PingHost` plugin message.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::status::legacy_ping;
+///
+/// assert_eq!(legacy_ping(&[0xFE]), Some(false));
+/// assert_eq!(legacy_ping(&[0xFE, 0x01]), Some(true));
+///
+/// let mut ping_host = vec![0xFE, 0x01, 0xFA, 0x00, 0x0B];
+/// ping_host.extend("MC|PingHost".encode_utf16().flat_map(u16::to_be_bytes));
+/// assert_eq!(legacy_ping(&ping_host), Some(true));
+///
+/// // A handshake frame with a length of 254.
+/// assert_eq!(legacy_ping(&[0xFE, 0x01, 0x00, 0xFF, 0x05]), None);
+/// assert_eq!(legacy_ping(&[0x10, 0x00]), None);
+/// ```
+///
+/// # Parameters
+/// - `bytes` - The bytes that were received first.
+///
+/// # Returns
+/// Whether the response has to contain the version, `None` if the bytes aren't a legacy ping.
+pub fn legacy_ping(bytes: &[u8]) -> Option<bool> {
+    match bytes {
+        [LEGACY_PING] => Some(false),
+        [LEGACY_PING, 0x01] => Some(true),
+        [LEGACY_PING, 0x01, LEGACY_PLUGIN_MESSAGE, message @ ..] => {
+            let mut channel = (LEGACY_PING_CHANNEL.len() as u16).to_be_bytes().to_vec();
+            channel.extend(
+                LEGACY_PING_CHANNEL
+                    .encode_utf16()
+                    .flat_map(u16::to_be_bytes),
+            );
+            message.starts_with(&channel).then_some(true)
+        }
+        _ => None,
+    }
+}
+
+/// Checks the data of a handshake and returns the state the connection switches to.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::types::{BoundedString, VarInt};
+/// use protocol_core::status::{validate_handshake, HandshakeRejection};
+/// use protocol_packets::{v1_21::handshake::HandshakePacket, ConnectionState};
+///
+/// let mut handshake = HandshakePacket {
+///     protocol_version: VarInt::from(767),
+///     server_address: BoundedString::new("localhost".to_string()).unwrap(),
+///     server_port: 25565,
+///     next_state: VarInt::from(2),
+/// };
+/// assert_eq!(validate_handshake(&handshake, Some(25565)), Ok(ConnectionState::Login));
+/// assert_eq!(
+///     validate_handshake(&handshake, Some(25566)),
+///     Err(HandshakeRejection::PortMismatch { expected: 25566, received: 25565 })
+/// );
+///
+/// handshake.next_state = VarInt::from(7);
+/// assert_eq!(validate_handshake(&handshake, None), Err(HandshakeRejection::UnknownIntent(7)));
+/// ```
+///
+/// # Parameters
+/// - `handshake` - The handshake sent by the client.
+/// - `port` - The port the handshake has to be for, `None` if any port is allowed.
+///
+/// # Returns
+/// The next state, the reason the handshake is rejected otherwise.
+pub fn validate_handshake(
+    handshake: &HandshakePacket,
+    port: Option<u16>,
+) -> Result<ConnectionState, HandshakeRejection> {
+    let intent = ConnectionState::from_intent(*handshake.next_state)
+        .ok_or(HandshakeRejection::UnknownIntent(*handshake.next_state))?;
+    if handshake.server_address.is_empty() {
+        return Err(HandshakeRejection::EmptyAddress);
+    }
+    if let Some(expected) = port.filter(|port| *port != handshake.server_port) {
+        return Err(HandshakeRejection::PortMismatch {
+            expected,
+            received: handshake.server_port,
+        });
+    }
+
+    Ok(intent)
+}
+
+/// Counts the handshakes that were rejected, by the reason they were rejected for.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::status::{HandshakeRejection, HandshakeRejections};
+///
+/// let rejections = HandshakeRejections::default();
+/// rejections.record(HandshakeRejection::UnknownIntent(7));
+/// rejections.record(HandshakeRejection::EmptyAddress);
+///
+/// assert_eq!(rejections.unknown_intents(), 1);
+/// assert_eq!(rejections.total(), 2);
+/// ```
+///
+/// # Fields
+/// - `unknown_intents` - The handshakes with an unknown intent.
+/// - `empty_addresses` - The handshakes without a server address.
+/// - `port_mismatches` - The handshakes for another port.
+#[derive(Debug, Default)]
+pub struct HandshakeRejections {
+    unknown_intents: AtomicU64,
+    empty_addresses: AtomicU64,
+    port_mismatches: AtomicU64,
+}
+
+impl HandshakeRejections {
+    /// Counts a rejected handshake.
+    pub fn record(&self, rejection: HandshakeRejection) {
+        let counter = match rejection {
+            HandshakeRejection::UnknownIntent(_) => &self.unknown_intents,
+            HandshakeRejection::EmptyAddress => &self.empty_addresses,
+            HandshakeRejection::PortMismatch { .. } => &self.port_mismatches,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the amount of handshakes with an unknown intent.
+    pub fn unknown_intents(&self) -> u64 {
+        self.unknown_intents.load(Ordering::Relaxed)
+    }
+
+    /// Returns the amount of handshakes without a server address.
+    pub fn empty_addresses(&self) -> u64 {
+        self.empty_addresses.load(Ordering::Relaxed)
+    }
+
+    /// Returns the amount of handshakes for another port.
+    pub fn port_mismatches(&self) -> u64 {
+        self.port_mismatches.load(Ordering::Relaxed)
+    }
+
+    /// Returns the amount of rejected handshakes.
+    pub fn total(&self) -> u64 {
+        self.unknown_intents() + self.empty_addresses() + self.port_mismatches()
+    }
+}
+
+/// Represents a connection that didn't finish the handshake yet.
+///
+/// All data is read into a fixed buffer on the stack. Status requests are answered directly from this connection,
+/// only connections that want to login are turned into a `[Client]`.
+///
+/// # Fields
+/// - `stream` - The TCP stream of the connection.
+/// - `buffer` - The bytes read from the stream.
+/// - `length` - The amount of bytes inside the buffer.
+pub struct HandshakeConnection {
+    stream: TcpStream,
+    buffer: [u8; HANDSHAKE_BUFFER_SIZE],
+    length: usize,
+}
+
+impl HandshakeConnection {
+    /// Creates a new `[HandshakeConnection]` with the given TCP stream.
+    pub const fn new(stream: TcpStream) -> Self {
+        Self {
+            stream,
+            buffer: [0; HANDSHAKE_BUFFER_SIZE],
+            length: 0,
+        }
+    }
+
+    /// Reads the next full packet from the TCP stream.
+    ///
+    /// # Returns
+    /// The next packet, `None` if the client disconnected.
+    async fn read_packet(&mut self) -> io::Result<Option<PacketBuffer>> {
+        loop {
+            match PacketBuffer::frame_size(&self.buffer[..self.length]) {
+                Ok(Some(size)) => {
+                    let frame = self.buffer[..size].to_vec();
+                    self.buffer.copy_within(size..self.length, 0);
+                    self.length -= size;
+
+                    return PacketBuffer::new(frame, &CompressionData::default())
+                        .map(Some)
+                        .ok_or_else(|| {
+                            io::Error::new(io::ErrorKind::InvalidData, "Invalid packet")
+                        });
+                }
+                Ok(None) if self.length == HANDSHAKE_BUFFER_SIZE => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "Packet too large",
+                    ));
+                }
+                Ok(None) => {}
+                Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+            }
+
+            let read = self.stream.read(&mut self.buffer[self.length..]).await?;
+            if read == 0 {
+                return Ok(None);
+            }
+
+            self.length += read;
+        }
+    }
+
+    /// Writes the given packet to the TCP stream without compression.
+    async fn send_packet<P: ClientboundPacket>(&mut self, packet: &P) -> io::Result<()> {
+        let compression = CompressionData::default();
+        let buffer = packet.write_packet(NormalBuffer::new(Vec::new()));
+        let bytes = compression
+            .to_buffer(buffer, &compression)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        self.stream.write_all(&bytes).await
+    }
+
+    /// Checks whether the connection starts with a legacy ping and answers it.
+    ///
+    /// Legacy pings are sent by clients older than Minecraft 1.7 and many monitoring tools.
+    /// They start with `[LEGACY_PING]`, which is also the first byte of some packet lengths,
+    /// so the bytes received first have to match a legacy ping exactly, see `[legacy_ping]`.
+    ///
+    /// # Parameters
+    /// - `status` - The status that is sent to the client.
+    ///
+    /// # Returns
+    /// `true` if a legacy ping was answered, the connection should be closed afterwards.
+    pub async fn handle_legacy_ping(&mut self, status: &ServerStatus) -> io::Result<bool> {
+        while self.length == 0 {
+            let read = self.stream.read(&mut self.buffer).await?;
+            if read == 0 {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+            self.length = read;
+        }
+
+        let Some(with_version) = legacy_ping(&self.buffer[..self.length]) else {
+            return Ok(false);
+        };
+
+        self.stream
+            .write_all(&status.to_legacy(with_version))
+            .await?;
+        self.stream.shutdown().await?;
+        Ok(true)
+    }
+
+    /// Reads the handshake packet from the TCP stream.
+    ///
+    /// # Returns
+    /// The handshake packet, `None` if the client disconnected.
+    pub async fn read_handshake(&mut self) -> io::Result<Option<HandshakePacket>> {
+        match self.read_packet().await? {
+            Some(packet) if *packet.packet_id == 0x00 => {
+                HandshakePacket::read_packet(packet.buffer)
+                    .map(Some)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+            Some(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Expected handshake packet",
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Reads the handshake packet of a connection from BungeeCord, which forwards the player in the server address.
+    ///
+    /// The forwarded data is removed from the server address of the returned handshake.
+    ///
+    /// # Returns
+    /// The handshake packet and the forwarded player, if the proxy forwarded one. `None` if the client disconnected.
+    pub async fn read_forwarded_handshake(
+        &mut self,
+    ) -> io::Result<Option<(HandshakePacket, Option<ForwardedPlayer>)>> {
+        let mut buffer = match self.read_packet().await? {
+            Some(packet) if *packet.packet_id == 0x00 => packet.buffer,
+            Some(_) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Expected handshake packet",
+                ))
+            }
+            None => return Ok(None),
+        };
+
+        let invalid_data = |e| io::Error::new(io::ErrorKind::InvalidData, e);
+        let protocol_version = buffer.read::<VarInt>().map_err(invalid_data)?;
+        let server_address = buffer
+            .read::<BoundedString<MAX_FORWARDED_ADDRESS_LENGTH>>()
+            .map_err(invalid_data)?;
+        let server_port = buffer.read::<u16>().map_err(invalid_data)?;
+        let next_state = buffer.read::<VarInt>().map_err(invalid_data)?;
+
+        let (host, forwarded) = ForwardedPlayer::from_bungee_address(&server_address)?;
+        let handshake = HandshakePacket {
+            protocol_version,
+            server_address: BoundedString::new(host)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            server_port,
+            next_state,
+        };
+        Ok(Some((handshake, forwarded)))
+    }
+
+    /// Disconnects a client whose handshake was rejected.
+    ///
+    /// Clients that want to login are sent the reason first, so the player sees why it can't join.
+    ///
+    /// # Parameters
+    /// - `handshake` - The rejected handshake.
+    /// - `rejection` - Why the handshake was rejected.
+    pub async fn reject(
+        mut self,
+        handshake: &HandshakePacket,
+        rejection: HandshakeRejection,
+    ) -> io::Result<()> {
+        if matches!(
+            ConnectionState::from_intent(*handshake.next_state),
+            Some(ConnectionState::Login | ConnectionState::Transfer)
+        ) {
+            let packet = LoginDisconnectPacket {
+                reason: Component::text(rejection.to_string()).to_json(),
+            };
+            self.send_packet(&packet).await?;
+        }
+
+        self.stream.shutdown().await
+    }
+
+    /// Answers the status and ping requests of the client.
+    ///
+    /// The connection is closed after the ping request has been answered or an unknown packet has been received.
+    ///
+    /// # Parameters
+    /// - `response` - The status JSON sent to the client.
+    pub async fn handle_status(mut self, response: &str) -> io::Result<()> {
+        while let Some(packet) = self.read_packet().await? {
+            match *packet.packet_id {
+                0x00 => {
+                    let status = StatusResponsePacket {
+                        json_response: response.to_string(),
+                    };
+                    self.send_packet(&status).await?;
+                }
+                0x01 => {
+                    let ping = PingRequestPacket::read_packet(packet.buffer)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                    let pong = PongResponsePacket {
+                        payload: ping.payload,
+                    };
+                    self.send_packet(&pong).await?;
+                    break;
+                }
+                _ => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Turns this connection into a full `[Client]`.
+    ///
+    /// Any bytes that were already read after the handshake are kept, so no packets are lost.
+    ///
+    /// # Parameters
+    /// - `state` - The state the client is switching to.
+    /// - `compression` - The compression data of the client.
+    /// - `config` - The server config shared with the client.
+    /// - `handlers` - The packet handlers registered on the server.
+    pub fn into_client(
+        self,
+        state: ConnectionState,
+        compression: CompressionData,
+        config: Arc<ServerConfig>,
+        handlers: Arc<PacketHandlers>,
+    ) -> Client {
+        let connection = ClientConnection::with_incoming(
+            self.stream,
+            &self.buffer[..self.length],
+            config.outbound,
+            Some(config.timings.clone()),
+        );
+        Client::with_connection(connection, compression, state, config, handlers)
+    }
+}
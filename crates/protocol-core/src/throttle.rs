@@ -0,0 +1,213 @@
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use protocol_buf::compression::MAX_UNCOMPRESSED_LENGTH;
+
+/// The window in which handshakes and packets are counted.
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// The amount of tracked addresses after which expired entries are removed from the `[ConnectionThrottle]`.
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// Defines how many connections and packets a single IP address or client can make.
+///
+/// # Fields
+/// - `max_connections_per_ip` - The maximum amount of open connections of a single IP address.
+/// - `max_handshakes_per_second` - The maximum amount of new connections of a single IP address per second, like status pings.
+/// - `max_packets_per_second` - The maximum amount of packets a single client can send per second, clients that send more are kicked.
+/// - `max_packet_size` - The largest packet a single client can send in bytes, before and after decompression. Frames can't be larger than `[MAX_PACKET_SIZE]` either way,
+///   clients that send a larger packet are disconnected before it is read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThrottleSettings {
+    pub max_connections_per_ip: u32,
+    pub max_handshakes_per_second: u32,
+    pub max_packets_per_second: u32,
+    pub max_packet_size: usize,
+}
+
+impl Default for ThrottleSettings {
+    fn default() -> Self {
+        Self {
+            max_connections_per_ip: 16,
+            max_handshakes_per_second: 10,
+            max_packets_per_second: 500,
+            max_packet_size: MAX_UNCOMPRESSED_LENGTH as usize,
+        }
+    }
+}
+
+/// Limits the amount of connections per IP address, to protect the server against status ping floods and bots.
+///
+/// Connections that exceed a limit are dropped right after they were accepted, before anything is read from them.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::throttle::{ConnectionThrottle, ThrottleSettings};
+///
+/// let throttle = ConnectionThrottle::new(ThrottleSettings {
+///     max_connections_per_ip: 1,
+///     ..ThrottleSettings::default()
+/// });
+/// let address = "127.0.0.1".parse().unwrap();
+///
+/// let permit = throttle.try_connect(address).unwrap();
+/// assert!(throttle.try_connect(address).is_none());
+///
+/// drop(permit);
+/// assert_eq!(throttle.connections(address), 0);
+/// assert!(throttle.try_connect(address).is_some());
+/// ```
+///
+/// # Fields
+/// - `settings` - The limits of the throttle.
+/// - `connections` - The amount of open connections per IP address.
+/// - `handshakes` - The start of the current window and the amount of connections inside it per IP address.
+#[derive(Debug)]
+pub struct ConnectionThrottle {
+    settings: ThrottleSettings,
+    connections: Mutex<HashMap<IpAddr, u32>>,
+    handshakes: Mutex<HashMap<IpAddr, (Instant, u32)>>,
+}
+
+impl ConnectionThrottle {
+    /// Creates a new `[ConnectionThrottle]` with the given limits.
+    pub fn new(settings: ThrottleSettings) -> Arc<Self> {
+        Arc::new(Self {
+            settings,
+            connections: Mutex::new(HashMap::new()),
+            handshakes: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Returns the limits of the throttle.
+    pub const fn settings(&self) -> ThrottleSettings {
+        self.settings
+    }
+
+    /// Returns the amount of open connections of the given address.
+    pub fn connections(&self, address: IpAddr) -> u32 {
+        self.connections
+            .lock()
+            .unwrap()
+            .get(&address)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Tries to count a new connection of the given address.
+    ///
+    /// # Parameters
+    /// - `address` - The address of the client.
+    ///
+    /// # Returns
+    /// The permit of the connection, which is held until the connection is closed. `None` if the address is being throttled.
+    pub fn try_connect(self: &Arc<Self>, address: IpAddr) -> Option<ConnectionPermit> {
+        let now = Instant::now();
+        {
+            let mut handshakes = self.handshakes.lock().unwrap();
+            if handshakes.len() >= PRUNE_THRESHOLD {
+                handshakes.retain(|_, (start, _)| now.duration_since(*start) < WINDOW);
+            }
+
+            let (start, count) = handshakes.entry(address).or_insert((now, 0));
+            if now.duration_since(*start) >= WINDOW {
+                *start = now;
+                *count = 0;
+            }
+
+            if *count >= self.settings.max_handshakes_per_second {
+                return None;
+            }
+            *count += 1;
+        }
+
+        let mut connections = self.connections.lock().unwrap();
+        let count = connections.entry(address).or_insert(0);
+        if *count >= self.settings.max_connections_per_ip {
+            return None;
+        }
+
+        *count += 1;
+        Some(ConnectionPermit {
+            throttle: self.clone(),
+            address,
+        })
+    }
+}
+
+/// Counts an open connection of an IP address, the connection is no longer counted once this is dropped.
+#[derive(Debug)]
+pub struct ConnectionPermit {
+    throttle: Arc<ConnectionThrottle>,
+    address: IpAddr,
+}
+
+impl Drop for ConnectionPermit {
+    fn drop(&mut self) {
+        let mut connections = self.throttle.connections.lock().unwrap();
+        if let Some(count) = connections.get_mut(&self.address) {
+            *count -= 1;
+            if *count == 0 {
+                connections.remove(&self.address);
+            }
+        }
+    }
+}
+
+/// Limits the amount of packets a single client can send per second.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::throttle::PacketRateLimiter;
+///
+/// let mut limiter = PacketRateLimiter::new(2);
+///
+/// assert!(limiter.try_acquire());
+/// assert!(limiter.try_acquire());
+/// assert!(!limiter.try_acquire());
+/// ```
+///
+/// # Fields
+/// - `max_packets` - The maximum amount of packets per second.
+/// - `start` - The start of the current window.
+/// - `count` - The amount of packets inside the current window.
+#[derive(Debug, Clone)]
+pub struct PacketRateLimiter {
+    max_packets: u32,
+    start: Instant,
+    count: u32,
+}
+
+impl PacketRateLimiter {
+    /// Creates a new `[PacketRateLimiter]` that allows the given amount of packets per second.
+    pub fn new(max_packets: u32) -> Self {
+        Self {
+            max_packets,
+            start: Instant::now(),
+            count: 0,
+        }
+    }
+
+    /// Tries to count a packet.
+    ///
+    /// # Returns
+    /// `true` if the packet is allowed, `false` if the client sent too many packets.
+    pub fn try_acquire(&mut self) -> bool {
+        let now = Instant::now();
+        if now.duration_since(self.start) >= WINDOW {
+            self.start = now;
+            self.count = 0;
+        }
+
+        if self.count >= self.max_packets {
+            return false;
+        }
+
+        self.count += 1;
+        true
+    }
+}
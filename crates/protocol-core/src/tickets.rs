@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+
+use protocol_packets::chunk::ChunkPos;
+
+/// The amount of unload passes a chunk has to be without tickets before it is unloaded.
+pub const DEFAULT_UNLOAD_DELAY: u32 = 15;
+
+/// The type of a chunk ticket.
+///
+/// # Variants
+/// - `Player` - The chunk is inside the view distance of a player.
+/// - `Forced` - The chunk was forced to stay loaded through the API, like with the `/forceload` command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TicketType {
+    Player,
+    Forced,
+}
+
+/// The amount of tickets of every type a chunk has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct Tickets {
+    player: u32,
+    forced: u32,
+}
+
+impl Tickets {
+    fn count_mut(&mut self, ticket: TicketType) -> &mut u32 {
+        match ticket {
+            TicketType::Player => &mut self.player,
+            TicketType::Forced => &mut self.forced,
+        }
+    }
+
+    const fn is_empty(&self) -> bool {
+        self.player == 0 && self.forced == 0
+    }
+}
+
+/// Keeps track of which chunks have to stay loaded.
+///
+/// Every chunk has a reference count of tickets. Players add a ticket to every chunk in their view distance
+/// and plugins can add forced tickets. Chunks without tickets are unloaded by `[ChunkTickets::unload_chunks]`,
+/// once they were without tickets for `unload_delay` passes, so chunks at the edge of the view distance aren't
+/// unloaded and loaded again while a player walks back and forth.
+///
+/// # Examples
+/// ```rust
+/// use std::collections::HashMap;
+/// use protocol_core::tickets::{ChunkTickets, TicketType};
+/// use protocol_packets::chunk::ChunkPos;
+///
+/// let mut tickets = ChunkTickets::new(1);
+/// tickets.add_player(ChunkPos::new(0, 0), 2);
+/// tickets.add(ChunkPos::new(100, 100), TicketType::Forced);
+///
+/// let mut chunks: HashMap<ChunkPos, &str> = HashMap::new();
+/// chunks.insert(ChunkPos::new(0, 0), "spawn");
+/// chunks.insert(ChunkPos::new(50, 50), "explored");
+/// chunks.insert(ChunkPos::new(100, 100), "farm");
+///
+/// let mut saved = Vec::new();
+/// tickets.unload_chunks(&mut chunks, |pos, chunk| saved.push((pos, chunk)));
+///
+/// assert_eq!(saved, vec![(ChunkPos::new(50, 50), "explored")]);
+/// assert_eq!(chunks.len(), 2);
+/// ```
+///
+/// # Fields
+/// - `tickets` - The tickets of every chunk that has at least one ticket.
+/// - `idle` - The amount of unload passes loaded chunks were without tickets.
+/// - `unload_delay` - The amount of unload passes a chunk has to be without tickets before it is unloaded.
+#[derive(Debug, Clone, Default)]
+pub struct ChunkTickets {
+    tickets: HashMap<ChunkPos, Tickets>,
+    idle: HashMap<ChunkPos, u32>,
+    pub unload_delay: u32,
+}
+
+impl ChunkTickets {
+    /// Creates a new `[ChunkTickets]` without any tickets.
+    ///
+    /// # Parameters
+    /// - `unload_delay` - The amount of unload passes a chunk has to be without tickets before it is unloaded.
+    pub fn new(unload_delay: u32) -> Self {
+        Self {
+            unload_delay,
+            ..Self::default()
+        }
+    }
+
+    /// Adds a ticket to the given chunk.
+    pub fn add(&mut self, pos: ChunkPos, ticket: TicketType) {
+        *self.tickets.entry(pos).or_default().count_mut(ticket) += 1;
+        self.idle.remove(&pos);
+    }
+
+    /// Removes a ticket from the given chunk.
+    ///
+    /// # Returns
+    /// `false` if the chunk didn't have a ticket of the given type.
+    pub fn remove(&mut self, pos: ChunkPos, ticket: TicketType) -> bool {
+        let Some(tickets) = self.tickets.get_mut(&pos) else {
+            return false;
+        };
+
+        let count = tickets.count_mut(ticket);
+        if *count == 0 {
+            return false;
+        }
+
+        *count -= 1;
+        if tickets.is_empty() {
+            self.tickets.remove(&pos);
+        }
+        true
+    }
+
+    /// Adds a player ticket to every chunk in the view distance of a player.
+    ///
+    /// # Parameters
+    /// - `center` - The chunk the player is in.
+    /// - `view_distance` - The view distance of the player.
+    pub fn add_player(&mut self, center: ChunkPos, view_distance: u32) {
+        for pos in center.square(view_distance) {
+            self.add(pos, TicketType::Player);
+        }
+    }
+
+    /// Removes the player tickets that were added with `[ChunkTickets::add_player]`.
+    pub fn remove_player(&mut self, center: ChunkPos, view_distance: u32) {
+        for pos in center.square(view_distance) {
+            self.remove(pos, TicketType::Player);
+        }
+    }
+
+    /// Moves the player tickets of a player that moved into another chunk.
+    ///
+    /// Only the tickets of chunks that entered or left the view distance are changed.
+    pub fn move_player(&mut self, from: ChunkPos, to: ChunkPos, view_distance: u32) {
+        if from == to {
+            return;
+        }
+
+        for pos in to.square(view_distance) {
+            if from.distance(pos) > view_distance {
+                self.add(pos, TicketType::Player);
+            }
+        }
+        for pos in from.square(view_distance) {
+            if to.distance(pos) > view_distance {
+                self.remove(pos, TicketType::Player);
+            }
+        }
+    }
+
+    /// Returns whether the given chunk has any ticket.
+    pub fn has_tickets(&self, pos: ChunkPos) -> bool {
+        self.tickets.contains_key(&pos)
+    }
+
+    /// Returns the amount of tickets of the given type the chunk has.
+    pub fn count(&self, pos: ChunkPos, ticket: TicketType) -> u32 {
+        self.tickets.get(&pos).map_or(0, |tickets| match ticket {
+            TicketType::Player => tickets.player,
+            TicketType::Forced => tickets.forced,
+        })
+    }
+
+    /// Returns all chunks that have at least one ticket.
+    pub fn ticketed(&self) -> impl Iterator<Item = ChunkPos> + '_ {
+        self.tickets.keys().copied()
+    }
+
+    /// Unloads the chunks that were without tickets for `unload_delay` passes.
+    ///
+    /// This should be called periodically, for example once every second.
+    ///
+    /// # Parameters
+    /// - `chunks` - The loaded chunks, unloaded chunks are removed from it.
+    /// - `save` - Called for every unloaded chunk, so it can be saved.
+    ///
+    /// # Returns
+    /// The amount of unloaded chunks.
+    pub fn unload_chunks<C>(
+        &mut self,
+        chunks: &mut HashMap<ChunkPos, C>,
+        mut save: impl FnMut(ChunkPos, C),
+    ) -> usize {
+        self.idle.retain(|pos, _| chunks.contains_key(pos));
+
+        let mut unload = Vec::new();
+        for pos in chunks.keys() {
+            if self.tickets.contains_key(pos) {
+                continue;
+            }
+
+            let idle = self.idle.entry(*pos).or_default();
+            *idle += 1;
+            if *idle >= self.unload_delay {
+                unload.push(*pos);
+            }
+        }
+
+        for pos in &unload {
+            self.idle.remove(pos);
+            if let Some(chunk) = chunks.remove(pos) {
+                save(*pos, chunk);
+            }
+        }
+
+        unload.len()
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::HashSet;
+
+use protocol_packets::v1_21::play::UpdateTimePacket;
+use uuid::Uuid;
+
+/// The amount of ticks in a Minecraft day.
+pub const TICKS_PER_DAY: i64 = 24000;
+
+/// The amount of ticks between two `[UpdateTimePacket]`s.
+pub const TIME_UPDATE_INTERVAL: i64 = 20;
+
+/// The time of day at which players can start sleeping.
+const NIGHT_START: i64 = 12542;
+
+/// The time of day at which the night is over.
+const NIGHT_END: i64 = 23460;
+
+/// Keeps track of the time of a world.
+///
+/// The time is driven by calling `[WorldTime::tick]` once every tick.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::time::WorldTime;
+///
+/// let mut time = WorldTime::default();
+/// let updates = (0..40).filter_map(|_| time.tick()).count();
+///
+/// assert_eq!(updates, 2);
+/// assert_eq!(time.time_of_day, 40);
+/// ```
+///
+/// # Fields
+/// - `world_age` - The amount of ticks since the world was created, this always moves forward.
+/// - `time_of_day` - The time of day in ticks, where `0` is sunrise.
+/// - `daylight_cycle` - Whether the time of day moves forward, like the `doDaylightCycle` game rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WorldTime {
+    pub world_age: i64,
+    pub time_of_day: i64,
+    pub daylight_cycle: bool,
+}
+
+impl Default for WorldTime {
+    fn default() -> Self {
+        Self {
+            world_age: 0,
+            time_of_day: 0,
+            daylight_cycle: true,
+        }
+    }
+}
+
+impl WorldTime {
+    /// Moves the time forward by a single tick.
+    ///
+    /// # Returns
+    /// The `[UpdateTimePacket]` that should be sent to all players, once every `[TIME_UPDATE_INTERVAL]` ticks.
+    pub fn tick(&mut self) -> Option<UpdateTimePacket> {
+        self.world_age += 1;
+        if self.daylight_cycle {
+            self.time_of_day += 1;
+        }
+
+        (self.world_age % TIME_UPDATE_INTERVAL == 0).then(|| self.update_packet())
+    }
+
+    /// Creates the `[UpdateTimePacket]` for the current time.
+    ///
+    /// If the daylight cycle is disabled, the time of day is sent negative, so the client doesn't move the sun.
+    pub fn update_packet(&self) -> UpdateTimePacket {
+        let time_of_day = if self.daylight_cycle {
+            self.time_of_day
+        } else {
+            -self.time_of_day.max(1)
+        };
+
+        UpdateTimePacket {
+            world_age: self.world_age,
+            time_of_day,
+        }
+    }
+
+    /// Returns the time of the current day, from `0` to `[TICKS_PER_DAY]`.
+    pub const fn day_time(&self) -> i64 {
+        self.time_of_day.rem_euclid(TICKS_PER_DAY)
+    }
+
+    /// Checks if it's night, which is when players can sleep.
+    pub const fn is_night(&self) -> bool {
+        let time = self.day_time();
+        time >= NIGHT_START && time < NIGHT_END
+    }
+
+    /// Skips the rest of the current day, so the next day starts at sunrise.
+    ///
+    /// # Returns
+    /// The `[UpdateTimePacket]` that should be sent to all players.
+    pub fn skip_night(&mut self) -> UpdateTimePacket {
+        self.time_of_day += TICKS_PER_DAY - self.day_time();
+        self.update_packet()
+    }
+}
+
+/// Keeps track of the sleeping players, to decide when the night should be skipped.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::time::SleepVote;
+/// use uuid::Uuid;
+///
+/// let mut vote = SleepVote::new(50);
+/// vote.set_sleeping(Uuid::from_u128(1), true);
+///
+/// assert!(vote.should_skip(2));
+/// assert!(!vote.should_skip(3));
+/// ```
+///
+/// # Fields
+/// - `percentage` - The percentage of online players that have to sleep, like the `playersSleepingPercentage` game rule.
+/// - `sleeping` - The players that are sleeping.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SleepVote {
+    pub percentage: u8,
+    sleeping: HashSet<Uuid>,
+}
+
+impl Default for SleepVote {
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
+
+impl SleepVote {
+    /// Creates a new `[SleepVote]` where the given percentage of players has to sleep.
+    pub fn new(percentage: u8) -> Self {
+        Self {
+            percentage,
+            sleeping: HashSet::new(),
+        }
+    }
+
+    /// Marks the given player as sleeping or awake.
+    pub fn set_sleeping(&mut self, player: Uuid, sleeping: bool) {
+        if sleeping {
+            self.sleeping.insert(player);
+        } else {
+            self.sleeping.remove(&player);
+        }
+    }
+
+    /// Returns the amount of sleeping players.
+    pub fn sleeping(&self) -> usize {
+        self.sleeping.len()
+    }
+
+    /// Returns the amount of players that have to sleep to skip the night. At least one player has to sleep.
+    ///
+    /// # Parameters
+    /// - `online` - The amount of online players.
+    pub fn required(&self, online: usize) -> usize {
+        (online * self.percentage.min(100) as usize)
+            .div_ceil(100)
+            .max(1)
+    }
+
+    /// Checks if enough players are sleeping to skip the night.
+    ///
+    /// # Parameters
+    /// - `online` - The amount of online players.
+    pub fn should_skip(&self, online: usize) -> bool {
+        online > 0 && self.sleeping() >= self.required(online)
+    }
+
+    /// Wakes up all players. This should be called once the night was skipped.
+    pub fn clear(&mut self) {
+        self.sleeping.clear();
+    }
+}
@@ -0,0 +1,314 @@
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use protocol_packets::ConnectionState;
+use serde_json::{json, Value};
+use tokio::time;
+
+use crate::{interceptor::PacketDirection, view_distance::TICK_DURATION};
+
+/// Represents a single timed span of a captured tick.
+///
+/// # Fields
+/// - `name` - The name of the span, like `chunk_send` or `player_flush`.
+/// - `tick` - The tick of the capture the span was recorded in, starting at 0.
+/// - `start` - The time between the start of the capture and the start of the span.
+/// - `duration` - How long the span took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimingSpan {
+    pub name: String,
+    pub tick: u32,
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+/// The state of a running capture.
+#[derive(Debug)]
+struct Capture {
+    started: Instant,
+    tick: u32,
+    ticks: u32,
+    spans: Vec<TimingSpan>,
+}
+
+/// Records detailed spans for a limited amount of ticks, to find out what causes tick spikes.
+///
+/// Spans are only recorded while a capture is running, otherwise creating a span is almost free.
+/// A finished capture can be exported with `[Timings::to_chrome_trace]`, which can be opened in `chrome://tracing`, Perfetto or converted to a flamegraph.
+///
+/// The server records the `chunk_send` and `player_flush` spans and ends a tick every 50 milliseconds while it accepts connections.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::timings::Timings;
+///
+/// let timings = Timings::new();
+/// timings.capture(1);
+///
+/// {
+///     let _tick = timings.span("tick");
+///     let _flush = timings.span("player_flush");
+/// }
+/// timings.end_tick();
+///
+/// let spans = timings.take_capture().unwrap();
+/// assert_eq!(spans.len(), 2);
+/// assert!(Timings::to_chrome_trace(&spans).contains("player_flush"));
+/// ```
+///
+/// # Fields
+/// - `capture` - The running capture, if any.
+/// - `finished` - The spans of the last finished capture.
+#[derive(Debug, Default)]
+pub struct Timings {
+    capture: Mutex<Option<Capture>>,
+    finished: Mutex<Option<Vec<TimingSpan>>>,
+}
+
+impl Timings {
+    /// Creates a new `[Timings]` without a running capture.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts capturing spans for the given amount of ticks. A running capture is restarted.
+    ///
+    /// # Parameters
+    /// - `ticks` - The amount of ticks to capture.
+    pub fn capture(&self, ticks: u32) {
+        *self.capture.lock().unwrap() = Some(Capture {
+            started: Instant::now(),
+            tick: 0,
+            ticks,
+            spans: Vec::new(),
+        });
+    }
+
+    /// Checks if a capture is currently running.
+    pub fn is_capturing(&self) -> bool {
+        self.capture.lock().unwrap().is_some()
+    }
+
+    /// Starts a new span, which is recorded once the returned guard is dropped.
+    ///
+    /// # Parameters
+    /// - `name` - The name of the span.
+    pub fn span(&self, name: &'static str) -> SpanGuard<'_> {
+        SpanGuard {
+            timings: self,
+            name,
+            start: self.is_capturing().then(Instant::now),
+        }
+    }
+
+    /// Marks the end of the current tick. Once all ticks are captured, the capture is finished.
+    pub fn end_tick(&self) {
+        let mut capture = self.capture.lock().unwrap();
+        let Some(running) = capture.as_mut() else {
+            return;
+        };
+
+        running.tick += 1;
+        if running.tick >= running.ticks {
+            let spans = capture.take().map(|capture| capture.spans);
+            *self.finished.lock().unwrap() = spans;
+        }
+    }
+
+    /// Ends a tick every `[TICK_DURATION]`, since the server doesn't run a game loop itself.
+    pub(crate) async fn run(self: Arc<Self>) {
+        let mut interval = time::interval(TICK_DURATION);
+        loop {
+            interval.tick().await;
+            self.end_tick();
+        }
+    }
+
+    /// Takes the spans of the last finished capture.
+    ///
+    /// # Returns
+    /// The spans, `None` if no capture has finished since the last call.
+    pub fn take_capture(&self) -> Option<Vec<TimingSpan>> {
+        self.finished.lock().unwrap().take()
+    }
+
+    /// Converts the spans into the chrome tracing JSON format.
+    ///
+    /// Every span becomes a complete event, the tick is used as the thread ID so every tick is shown on its own row.
+    ///
+    /// # Parameters
+    /// - `spans` - The spans to convert.
+    pub fn to_chrome_trace(spans: &[TimingSpan]) -> String {
+        let events: Vec<Value> = spans
+            .iter()
+            .map(|span| {
+                json!({
+                    "name": span.name,
+                    "ph": "X",
+                    "ts": span.start.as_micros() as u64,
+                    "dur": span.duration.as_micros() as u64,
+                    "pid": 0,
+                    "tid": span.tick,
+                })
+            })
+            .collect();
+
+        json!({ "traceEvents": events }).to_string()
+    }
+
+    /// Records a finished span, if the capture is still running.
+    fn record(&self, name: &'static str, start: Instant) {
+        let duration = start.elapsed();
+        let mut capture = self.capture.lock().unwrap();
+        let Some(running) = capture.as_mut() else {
+            return;
+        };
+
+        running.spans.push(TimingSpan {
+            name: name.to_string(),
+            tick: running.tick,
+            start: start.saturating_duration_since(running.started),
+            duration,
+        });
+    }
+}
+
+/// Records a span of `[Timings]` once it is dropped.
+///
+/// # Fields
+/// - `timings` - The timings the span is recorded to.
+/// - `name` - The name of the span.
+/// - `start` - When the span started, `None` if no capture was running.
+#[must_use]
+pub struct SpanGuard<'a> {
+    timings: &'a Timings,
+    name: &'static str,
+    start: Option<Instant>,
+}
+
+impl Drop for SpanGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            self.timings.record(self.name, start);
+        }
+    }
+}
+
+/// Identifies a type of packet, by its direction, the state it is sent in and its ID.
+///
+/// # Fields
+/// - `direction` - The direction the packet is sent in.
+/// - `state` - The state of the connection.
+/// - `packet_id` - The ID of the packet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PacketKey {
+    pub direction: PacketDirection,
+    pub state: ConnectionState,
+    pub packet_id: i32,
+}
+
+/// The packets of a type that were sent or received, and the time spent on them.
+///
+/// # Fields
+/// - `count` - The amount of packets.
+/// - `bytes` - The size of the packets, without their IDs and before they were compressed.
+/// - `time` - The time spent writing the packets, or reading and handling them for serverbound packets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PacketTiming {
+    pub count: u64,
+    pub bytes: u64,
+    pub time: Duration,
+}
+
+impl PacketTiming {
+    /// Returns the average time spent on one packet.
+    pub fn average(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        self.time / self.count.min(u32::MAX as u64) as u32
+    }
+}
+
+/// Counts the packets of every type and the time spent on them, to find the packets that take up most of the time.
+///
+/// Clientbound packets are timed while they are written, serverbound packets while they are read and handled,
+/// which includes the built-in handling and the registered handlers.
+/// The timings are shared by every client of the server, see `[ServerConnection::enable_packet_timings]`.
+///
+/// # Examples
+/// ```rust
+/// use std::time::Duration;
+/// use protocol_core::{interceptor::PacketDirection, timings::{PacketKey, PacketTimings}};
+/// use protocol_packets::ConnectionState;
+///
+/// let timings = PacketTimings::new();
+/// let key = PacketKey {
+///     direction: PacketDirection::Clientbound,
+///     state: ConnectionState::Play,
+///     packet_id: 0x27,
+/// };
+/// timings.record(key, 2048, Duration::from_micros(300));
+/// timings.record(key, 1024, Duration::from_micros(100));
+///
+/// let timing = timings.get(key).unwrap();
+/// assert_eq!(timing.count, 2);
+/// assert_eq!(timing.bytes, 3072);
+/// assert_eq!(timing.average(), Duration::from_micros(200));
+/// assert_eq!(timings.snapshot()[0].0, key);
+/// ```
+///
+/// # Fields
+/// - `packets` - The timings of every type of packet that was sent or received.
+#[derive(Debug, Default)]
+pub struct PacketTimings {
+    packets: Mutex<HashMap<PacketKey, PacketTiming>>,
+}
+
+impl PacketTimings {
+    /// Creates a new `[PacketTimings]` without any packets.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a packet that was sent or received.
+    ///
+    /// # Parameters
+    /// - `key` - The type of the packet.
+    /// - `bytes` - The size of the packet, without its ID.
+    /// - `time` - The time spent on the packet.
+    pub fn record(&self, key: PacketKey, bytes: usize, time: Duration) {
+        let mut packets = self.packets.lock().unwrap();
+        let timing = packets.entry(key).or_default();
+        timing.count += 1;
+        timing.bytes += bytes as u64;
+        timing.time += time;
+    }
+
+    /// Returns the timing of a type of packet, `None` if no packet of the type was recorded.
+    pub fn get(&self, key: PacketKey) -> Option<PacketTiming> {
+        self.packets.lock().unwrap().get(&key).copied()
+    }
+
+    /// Returns the timings of every type of packet, the type with the most time spent on it first.
+    pub fn snapshot(&self) -> Vec<(PacketKey, PacketTiming)> {
+        let mut timings: Vec<_> = self
+            .packets
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(key, timing)| (*key, *timing))
+            .collect();
+        timings.sort_by_key(|(_, timing)| Reverse(timing.time));
+        timings
+    }
+
+    /// Forgets every recorded packet, like after the timings were exported.
+    pub fn reset(&self) {
+        self.packets.lock().unwrap().clear();
+    }
+}
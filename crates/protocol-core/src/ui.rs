@@ -0,0 +1,151 @@
+use protocol_packets::ClientboundPacket;
+use uuid::Uuid;
+
+use crate::registry::EncodedPacket;
+
+/// A part of the user interface of a player, which the client forgets when it respawns or changes its dimension.
+///
+/// # Variants
+/// - `BossBar` - A boss bar with the given UUID.
+/// - `Objective` - A scoreboard objective with the given name, together with its scores and display slot.
+/// - `TabList` - The header, footer and entries of the tab list.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum UiComponent {
+    BossBar(Uuid),
+    Objective(String),
+    TabList,
+}
+
+/// The packets that build up a single `[UiComponent]`.
+///
+/// # Fields
+/// - `component` - The component the packets belong to.
+/// - `packets` - The packets, in the order they have to be sent.
+/// - `persistent` - Whether the packets are sent again after a respawn.
+#[derive(Debug, Clone)]
+struct UiEntry {
+    component: UiComponent,
+    packets: Vec<EncodedPacket>,
+    persistent: bool,
+}
+
+/// Keeps track of the boss bars, scoreboards and tab list of a player, so they can be sent again after a respawn.
+///
+/// Owners of a component store the packets that create it, and mark it as persistent if it should survive a respawn.
+/// `[Client::respawn]` sends the packets of all persistent components again, in the order they were first added.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::ui::{UiComponent, UiState};
+/// use protocol_packets::v1_21::play::UpdateTimePacket;
+///
+/// let mut ui = UiState::new();
+/// ui.set(UiComponent::TabList, &UpdateTimePacket { world_age: 0, time_of_day: 0 });
+/// ui.set(UiComponent::Objective("kills".into()), &UpdateTimePacket { world_age: 1, time_of_day: 1 });
+/// ui.set_persistent(&UiComponent::TabList, true);
+///
+/// assert_eq!(ui.respawn_packets().count(), 1);
+/// assert!(ui.is_persistent(&UiComponent::TabList));
+/// ```
+///
+/// # Fields
+/// - `entries` - The stored components, in the order they were added.
+#[derive(Debug, Clone, Default)]
+pub struct UiState {
+    entries: Vec<UiEntry>,
+}
+
+impl UiState {
+    /// Creates a new empty `[UiState]`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the packets of a component with the given packet.
+    ///
+    /// This should be called with the packet that creates the component in its current state,
+    /// like an add boss bar action with the current title and health. A new component isn't persistent.
+    pub fn set<P: ClientboundPacket>(&mut self, component: UiComponent, packet: &P) {
+        let packet = EncodedPacket::new(packet);
+        match self.entry_mut(&component) {
+            Some(entry) => entry.packets = vec![packet],
+            None => self.entries.push(UiEntry {
+                component,
+                packets: vec![packet],
+                persistent: false,
+            }),
+        }
+    }
+
+    /// Adds a packet to the packets of a component, it is sent after the packets that were already stored.
+    ///
+    /// This can be used for components that need multiple packets, like an objective together with its display slot.
+    pub fn push<P: ClientboundPacket>(&mut self, component: UiComponent, packet: &P) {
+        let packet = EncodedPacket::new(packet);
+        match self.entry_mut(&component) {
+            Some(entry) => entry.packets.push(packet),
+            None => self.entries.push(UiEntry {
+                component,
+                packets: vec![packet],
+                persistent: false,
+            }),
+        }
+    }
+
+    /// Marks a component as persistent across respawns.
+    ///
+    /// # Returns
+    /// `false` if the component isn't stored.
+    pub fn set_persistent(&mut self, component: &UiComponent, persistent: bool) -> bool {
+        match self.entry_mut(component) {
+            Some(entry) => {
+                entry.persistent = persistent;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns whether the component is stored and persistent across respawns.
+    pub fn is_persistent(&self, component: &UiComponent) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.component == *component && entry.persistent)
+    }
+
+    /// Removes a component, this should be called when it is removed from the client.
+    ///
+    /// # Returns
+    /// `false` if the component wasn't stored.
+    pub fn remove(&mut self, component: &UiComponent) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|entry| entry.component != *component);
+        self.entries.len() != len
+    }
+
+    /// Removes all components.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Removes all components that aren't persistent, the client forgets them when it respawns.
+    pub fn clear_transient(&mut self) {
+        self.entries.retain(|entry| entry.persistent);
+    }
+
+    /// Returns the packets that have to be sent again after a respawn.
+    ///
+    /// Components that aren't persistent are skipped, the client already forgot them.
+    pub fn respawn_packets(&self) -> impl Iterator<Item = &EncodedPacket> + '_ {
+        self.entries
+            .iter()
+            .filter(|entry| entry.persistent)
+            .flat_map(|entry| entry.packets.iter())
+    }
+
+    fn entry_mut(&mut self, component: &UiComponent) -> Option<&mut UiEntry> {
+        self.entries
+            .iter_mut()
+            .find(|entry| entry.component == *component)
+    }
+}
@@ -0,0 +1,394 @@
+use std::sync::{Arc, Mutex, RwLock};
+
+use protocol_buf::types::{Angle, VarInt, Velocity};
+use protocol_packets::{
+    v1_21::play::{
+        ClientboundMoveVehiclePacket, SetPassengersPacket, SpawnEntityPacket, TeleportEntityPacket,
+    },
+    ClientboundPacket,
+};
+use uuid::Uuid;
+
+use crate::{
+    components::Entities,
+    entity::RemovalBatch,
+    raycast::Vec3,
+    registry::{ClientHandle, ClientMessage, ClientRegistry, EncodedPacket},
+    spatial::{chunk_of, SpatialIndex},
+};
+
+/// The largest distance a vehicle may move between two `[ServerboundMoveVehiclePacket]`s.
+///
+/// The vanilla server uses the same limit, movements that are larger are sent back to the client.
+pub const MAX_VEHICLE_MOVEMENT: f64 = 10.0;
+
+/// The kind of a vehicle that can be placed by the server.
+///
+/// # Variants
+/// - `Boat` - An oak boat.
+/// - `Minecart` - A minecart without any blocks inside of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VehicleKind {
+    Boat,
+    Minecart,
+}
+
+impl VehicleKind {
+    /// Returns the ID of the entity type in the `minecraft:entity_type` registry of 1.21.
+    pub const fn entity_type(&self) -> i32 {
+        match self {
+            Self::Boat => 10,
+            Self::Minecart => 69,
+        }
+    }
+}
+
+/// A player riding a vehicle.
+///
+/// # Fields
+/// - `uuid` - The UUID of the client, which is used to send it packets.
+/// - `entity_id` - The entity ID of the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Passenger {
+    pub uuid: Uuid,
+    pub entity_id: i32,
+}
+
+/// A vehicle placed by the server, which can be ridden by a single player.
+///
+/// The player riding the vehicle controls it, the server only checks that the movements are possible.
+///
+/// # Fields
+/// - `entity_id` - The entity ID of the vehicle.
+/// - `uuid` - The UUID of the vehicle.
+/// - `kind` - The kind of the vehicle.
+/// - `position` - The position of the vehicle.
+/// - `yaw` - The yaw in degrees.
+/// - `pitch` - The pitch in degrees.
+/// - `passenger` - The player riding the vehicle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vehicle {
+    pub entity_id: i32,
+    pub uuid: Uuid,
+    pub kind: VehicleKind,
+    pub position: Vec3,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub passenger: Option<Passenger>,
+}
+
+impl Vehicle {
+    /// Creates the packet that spawns the vehicle on the client.
+    pub fn spawn_packet(&self) -> SpawnEntityPacket {
+        SpawnEntityPacket {
+            entity_id: VarInt::from(self.entity_id),
+            entity_uuid: self.uuid.as_u128(),
+            entity_type: VarInt::from(self.kind.entity_type()),
+            x: self.position.x,
+            y: self.position.y,
+            z: self.position.z,
+            pitch: Angle::from_degrees(self.pitch),
+            yaw: Angle::from_degrees(self.yaw),
+            head_yaw: Angle::from_degrees(self.yaw),
+            data: VarInt::from(0),
+            velocity_x: Velocity::default(),
+            velocity_y: Velocity::default(),
+            velocity_z: Velocity::default(),
+        }
+    }
+
+    /// Creates the packet that tells the client who is riding the vehicle.
+    pub fn passengers_packet(&self) -> SetPassengersPacket {
+        SetPassengersPacket {
+            entity_id: VarInt::from(self.entity_id),
+            passengers: self
+                .passenger
+                .iter()
+                .map(|passenger| VarInt::from(passenger.entity_id))
+                .collect(),
+        }
+    }
+
+    /// Creates the packet that moves the vehicle to its position on the client.
+    pub fn teleport_packet(&self) -> TeleportEntityPacket {
+        TeleportEntityPacket {
+            entity_id: VarInt::from(self.entity_id),
+            x: self.position.x,
+            y: self.position.y,
+            z: self.position.z,
+            yaw: Angle::from_degrees(self.yaw),
+            pitch: Angle::from_degrees(self.pitch),
+            on_ground: false,
+        }
+    }
+
+    /// Creates the packet that moves the vehicle back to its position on the client of its passenger.
+    pub fn correction_packet(&self) -> ClientboundMoveVehiclePacket {
+        ClientboundMoveVehiclePacket {
+            x: self.position.x,
+            y: self.position.y,
+            z: self.position.z,
+            yaw: self.yaw,
+            pitch: self.pitch,
+        }
+    }
+}
+
+/// The result of a movement of a vehicle sent by a client.
+///
+/// # Variants
+/// - `Moved` - The vehicle was moved and the movement was sent to the other clients that can see it.
+/// - `NotRiding` - The client isn't riding a vehicle, the movement is ignored.
+/// - `TooFast` - The vehicle moved further than `[MAX_VEHICLE_MOVEMENT]`, the client has to be sent the packet to move it back.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VehicleMovement {
+    Moved,
+    NotRiding,
+    TooFast(ClientboundMoveVehiclePacket),
+}
+
+/// Keeps track of the vehicles placed by the server and the players riding them.
+///
+/// Every change is sent to the clients in the game, clients that join later are sent the vehicles while joining.
+/// Movements are only sent to the players that have the vehicle inside their view distance.
+///
+/// # Examples
+/// ```rust
+/// use std::sync::{Arc, Mutex, RwLock};
+/// use protocol_core::{
+///     entity::RemovalBatch,
+///     raycast::Vec3,
+///     registry::ClientRegistry,
+///     spatial::SpatialIndex,
+///     vehicle::{Passenger, VehicleKind, VehicleMovement, VehicleRegistry},
+/// };
+/// use uuid::Uuid;
+///
+/// let spatial = Arc::new(RwLock::new(SpatialIndex::new()));
+/// let removals = Arc::new(Mutex::new(RemovalBatch::new()));
+/// let vehicles = VehicleRegistry::new(Arc::new(ClientRegistry::new()), spatial.clone(), removals);
+/// let boat = vehicles.spawn(VehicleKind::Boat, Vec3::new(0.0, 63.0, 0.0), 90.0);
+///
+/// let player = Passenger { uuid: Uuid::new_v4(), entity_id: 1 };
+/// assert!(vehicles.mount(boat, player));
+/// assert!(!vehicles.mount(boat, Passenger { uuid: Uuid::new_v4(), entity_id: 2 }));
+///
+/// assert_eq!(vehicles.move_vehicle(player.uuid, Vec3::new(2.0, 63.0, 0.0), 90.0, 0.0), VehicleMovement::Moved);
+/// assert!(matches!(vehicles.move_vehicle(player.uuid, Vec3::new(50.0, 63.0, 0.0), 90.0, 0.0), VehicleMovement::TooFast(_)));
+/// assert_eq!(vehicles.get(boat).unwrap().position, Vec3::new(2.0, 63.0, 0.0));
+/// assert_eq!(spatial.read().unwrap().position(boat), Some(Vec3::new(2.0, 63.0, 0.0)));
+///
+/// assert_eq!(vehicles.dismount(player.uuid), Some(boat));
+/// assert_eq!(vehicles.move_vehicle(player.uuid, Vec3::new(3.0, 63.0, 0.0), 90.0, 0.0), VehicleMovement::NotRiding);
+/// ```
+///
+/// # Fields
+/// - `entities` - The vehicles, every vehicle is an entity with a `[Vehicle]` component.
+/// - `clients` - The clients the changes are sent to.
+/// - `spatial` - The index the positions of the vehicles are stored in.
+/// - `removals` - The batch removed vehicles are queued in, it is flushed once every tick.
+#[derive(Debug)]
+pub struct VehicleRegistry {
+    entities: Mutex<Entities>,
+    clients: Arc<ClientRegistry>,
+    spatial: Arc<RwLock<SpatialIndex>>,
+    removals: Arc<Mutex<RemovalBatch<Uuid>>>,
+}
+
+impl VehicleRegistry {
+    /// Creates a new `[VehicleRegistry]` without any vehicles.
+    ///
+    /// # Parameters
+    /// - `clients` - The clients the vehicles are sent to.
+    /// - `spatial` - The index the positions of the vehicles are stored in, it is used to find the players that can see them.
+    /// - `removals` - The batch removed vehicles are queued in, shared with the other entities of the server.
+    pub fn new(
+        clients: Arc<ClientRegistry>,
+        spatial: Arc<RwLock<SpatialIndex>>,
+        removals: Arc<Mutex<RemovalBatch<Uuid>>>,
+    ) -> Self {
+        Self {
+            entities: Mutex::new(Entities::new()),
+            clients,
+            spatial,
+            removals,
+        }
+    }
+
+    /// Places a new vehicle and spawns it for every client in the game.
+    ///
+    /// # Parameters
+    /// - `kind` - The kind of the vehicle.
+    /// - `position` - The position of the vehicle.
+    /// - `yaw` - The yaw in degrees.
+    ///
+    /// # Returns
+    /// The entity ID of the vehicle.
+    pub fn spawn(&self, kind: VehicleKind, position: Vec3, yaw: f32) -> i32 {
+        let mut entities = self.entities.lock().unwrap();
+        let entity_id = entities.spawn();
+        let vehicle = Vehicle {
+            entity_id,
+            uuid: Uuid::new_v4(),
+            kind,
+            position,
+            yaw,
+            pitch: 0.0,
+            passenger: None,
+        };
+
+        self.broadcast(&vehicle.spawn_packet());
+        self.spatial.write().unwrap().insert(entity_id, position);
+        entities.insert(entity_id, vehicle);
+        entity_id
+    }
+
+    /// Removes a vehicle from the world, its passenger is dismounted.
+    ///
+    /// The vehicle is queued in the `[RemovalBatch]`, so the clients remove it at the end of the tick
+    /// together with the other entities removed during it.
+    pub fn remove(&self, entity_id: i32) -> Option<Vehicle> {
+        let mut entities = self.entities.lock().unwrap();
+        let vehicle = entities.remove::<Vehicle>(entity_id)?;
+        entities.despawn(entity_id);
+        self.spatial.write().unwrap().remove(entity_id);
+
+        let viewers: Vec<_> = self
+            .clients
+            .clients()
+            .iter()
+            .map(ClientHandle::uuid)
+            .collect();
+        self.removals.lock().unwrap().queue_all(viewers, entity_id);
+        Some(vehicle)
+    }
+
+    /// Returns the vehicle with the given entity ID.
+    pub fn get(&self, entity_id: i32) -> Option<Vehicle> {
+        self.entities
+            .lock()
+            .unwrap()
+            .get::<Vehicle>(entity_id)
+            .cloned()
+    }
+
+    /// Returns all vehicles, this is used to spawn them for clients that join the game.
+    pub fn vehicles(&self) -> Vec<Vehicle> {
+        self.entities
+            .lock()
+            .unwrap()
+            .iter::<Vehicle>()
+            .map(|(_, vehicle)| vehicle.clone())
+            .collect()
+    }
+
+    /// Returns the entity ID of the vehicle the client is riding.
+    pub fn vehicle_of(&self, uuid: Uuid) -> Option<i32> {
+        self.entities
+            .lock()
+            .unwrap()
+            .iter::<Vehicle>()
+            .map(|(_, vehicle)| vehicle)
+            .find(|vehicle| {
+                vehicle
+                    .passenger
+                    .is_some_and(|passenger| passenger.uuid == uuid)
+            })
+            .map(|vehicle| vehicle.entity_id)
+    }
+
+    /// Lets a player ride a vehicle. A player riding another vehicle leaves it first.
+    ///
+    /// # Returns
+    /// `false` if the vehicle doesn't exist or someone else is already riding it.
+    pub fn mount(&self, entity_id: i32, passenger: Passenger) -> bool {
+        let mut entities = self.entities.lock().unwrap();
+        match entities.get::<Vehicle>(entity_id) {
+            Some(vehicle) if vehicle.passenger.is_none() => {}
+            Some(vehicle) => return vehicle.passenger == Some(passenger),
+            None => return false,
+        }
+
+        for (_, vehicle) in entities.iter_mut::<Vehicle>() {
+            if vehicle
+                .passenger
+                .is_some_and(|rider| rider.uuid == passenger.uuid)
+            {
+                vehicle.passenger = None;
+                self.broadcast(&vehicle.passengers_packet());
+            }
+        }
+
+        let vehicle = entities.get_mut::<Vehicle>(entity_id).unwrap();
+        vehicle.passenger = Some(passenger);
+        self.broadcast(&vehicle.passengers_packet());
+        true
+    }
+
+    /// Makes the client leave the vehicle it is riding.
+    ///
+    /// # Returns
+    /// The entity ID of the vehicle, `None` if the client wasn't riding a vehicle.
+    pub fn dismount(&self, uuid: Uuid) -> Option<i32> {
+        let mut entities = self.entities.lock().unwrap();
+        let (_, vehicle) = entities.iter_mut::<Vehicle>().find(|(_, vehicle)| {
+            vehicle
+                .passenger
+                .is_some_and(|passenger| passenger.uuid == uuid)
+        })?;
+
+        vehicle.passenger = None;
+        self.broadcast(&vehicle.passengers_packet());
+        Some(vehicle.entity_id)
+    }
+
+    /// Moves the vehicle the client is riding and sends the movement to the other clients that can see it.
+    ///
+    /// # Parameters
+    /// - `uuid` - The UUID of the client that moved the vehicle.
+    /// - `position` - The new position of the vehicle.
+    /// - `yaw` - The new yaw in degrees.
+    /// - `pitch` - The new pitch in degrees.
+    pub fn move_vehicle(
+        &self,
+        uuid: Uuid,
+        position: Vec3,
+        yaw: f32,
+        pitch: f32,
+    ) -> VehicleMovement {
+        let mut entities = self.entities.lock().unwrap();
+        let Some((_, vehicle)) = entities.iter_mut::<Vehicle>().find(|(_, vehicle)| {
+            vehicle
+                .passenger
+                .is_some_and(|passenger| passenger.uuid == uuid)
+        }) else {
+            return VehicleMovement::NotRiding;
+        };
+
+        if vehicle.position.distance_squared(position) > MAX_VEHICLE_MOVEMENT * MAX_VEHICLE_MOVEMENT
+        {
+            return VehicleMovement::TooFast(vehicle.correction_packet());
+        }
+
+        vehicle.position = position;
+        vehicle.yaw = yaw;
+        vehicle.pitch = pitch;
+
+        let mut spatial = self.spatial.write().unwrap();
+        spatial.update(vehicle.entity_id, position);
+        let viewers = spatial
+            .players_viewing(chunk_of(position))
+            .into_iter()
+            .filter(|viewer| *viewer != uuid);
+        let message = ClientMessage::PlayPacket(EncodedPacket::new(&vehicle.teleport_packet()));
+        self.clients.broadcast_to(viewers, message);
+
+        VehicleMovement::Moved
+    }
+
+    /// Sends a packet to every client in the game.
+    fn broadcast<P: ClientboundPacket>(&self, packet: &P) {
+        let message = ClientMessage::PlayPacket(EncodedPacket::new(packet));
+        self.clients.broadcast(message);
+    }
+}
@@ -0,0 +1,200 @@
+use std::{
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use tokio::time;
+
+use crate::registry::{ClientMessage, ClientRegistry};
+
+/// The duration of a tick at 20 ticks per second.
+pub const TICK_DURATION: Duration = Duration::from_millis(50);
+
+/// The settings of a `[ViewDistanceController]`.
+///
+/// # Fields
+/// - `min` - The smallest view distance the controller lowers the view distance to.
+/// - `max` - The largest view distance the controller raises the view distance to, this is the view distance the server starts with.
+/// - `target_tick` - The tick duration the server should stay below, the view distance is lowered while ticks take longer.
+/// - `max_bandwidth` - The bytes per second the server should stay below, `None` if the bandwidth isn't limited.
+/// - `interval` - How often the load is checked, the view distance changes by at most one chunk every interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewDistanceSettings {
+    pub min: u32,
+    pub max: u32,
+    pub target_tick: Duration,
+    pub max_bandwidth: Option<u64>,
+    pub interval: Duration,
+}
+
+impl Default for ViewDistanceSettings {
+    fn default() -> Self {
+        Self {
+            min: 4,
+            max: 10,
+            target_tick: TICK_DURATION,
+            max_bandwidth: None,
+            interval: Duration::from_secs(5),
+        }
+    }
+}
+
+/// The load measured since the last check of a `[ViewDistanceController]`.
+///
+/// # Fields
+/// - `tick_time` - The total duration of the recorded ticks.
+/// - `ticks` - The amount of recorded ticks.
+/// - `bytes` - The amount of bytes sent to the clients.
+/// - `since` - When the load was checked the last time.
+#[derive(Debug)]
+struct Load {
+    tick_time: Duration,
+    ticks: u32,
+    bytes: u64,
+    since: Instant,
+}
+
+impl Load {
+    fn new() -> Self {
+        Self {
+            tick_time: Duration::ZERO,
+            ticks: 0,
+            bytes: 0,
+            since: Instant::now(),
+        }
+    }
+}
+
+/// Lowers the view distance of the server while it is under load and raises it again once the load is gone.
+///
+/// The controller watches the average tick duration and the bytes sent to the clients. Once either of them is above
+/// its limit, the view distance is lowered by one chunk every interval, until it reaches the minimum. Once both are
+/// comfortably below their limits again, the view distance is raised by one chunk every interval.
+///
+/// Clients are told about every change, chunks outside of their new view distance are unloaded.
+/// The server doesn't run a game loop itself, so the durations of the ticks have to be recorded with
+/// `[ViewDistanceController::record_tick]`. Without recorded ticks only the bandwidth is taken into account.
+///
+/// # Examples
+/// ```rust
+/// use std::time::Duration;
+/// use protocol_core::view_distance::{ViewDistanceController, ViewDistanceSettings};
+///
+/// let controller = ViewDistanceController::new(ViewDistanceSettings::default());
+/// assert_eq!(controller.view_distance(), 10);
+///
+/// // Ticks take 80ms instead of 50ms, so the view distance is lowered.
+/// controller.record_tick(Duration::from_millis(80));
+/// assert_eq!(controller.update(), Some((10, 9)));
+///
+/// // Ticks are fast again, so the view distance is raised.
+/// controller.record_tick(Duration::from_millis(10));
+/// assert_eq!(controller.update(), Some((9, 10)));
+/// assert_eq!(controller.update(), None);
+/// ```
+///
+/// # Fields
+/// - `settings` - The limits of the controller.
+/// - `view_distance` - The current view distance of the server.
+/// - `load` - The load measured since the last check.
+#[derive(Debug)]
+pub struct ViewDistanceController {
+    settings: ViewDistanceSettings,
+    view_distance: AtomicU32,
+    load: Mutex<Load>,
+}
+
+impl ViewDistanceController {
+    /// Creates a new `[ViewDistanceController]`, which starts at the maximum view distance.
+    pub fn new(settings: ViewDistanceSettings) -> Self {
+        let settings = ViewDistanceSettings {
+            min: settings.min.clamp(2, settings.max.max(2)),
+            max: settings.max.max(2),
+            ..settings
+        };
+
+        Self {
+            settings,
+            view_distance: AtomicU32::new(settings.max),
+            load: Mutex::new(Load::new()),
+        }
+    }
+
+    /// Returns the settings of the controller.
+    pub fn settings(&self) -> ViewDistanceSettings {
+        self.settings
+    }
+
+    /// Returns the current view distance of the server in chunks.
+    pub fn view_distance(&self) -> u32 {
+        self.view_distance.load(Ordering::SeqCst)
+    }
+
+    /// Records how long a tick of the server took.
+    pub fn record_tick(&self, duration: Duration) {
+        let mut load = self.load.lock().unwrap();
+        load.tick_time += duration;
+        load.ticks += 1;
+    }
+
+    /// Records bytes that were sent to a client, this is done by every client.
+    pub fn record_sent(&self, bytes: u64) {
+        self.load.lock().unwrap().bytes += bytes;
+    }
+
+    /// Checks the load measured since the last check and changes the view distance by one chunk if needed.
+    ///
+    /// # Returns
+    /// The previous and the new view distance, `None` if the view distance didn't change.
+    pub fn update(&self) -> Option<(u32, u32)> {
+        let load = std::mem::replace(&mut *self.load.lock().unwrap(), Load::new());
+        let elapsed = load.since.elapsed().max(Duration::from_millis(1));
+        let bandwidth = (load.bytes as f64 / elapsed.as_secs_f64()) as u64;
+        let tick = (load.ticks > 0).then(|| load.tick_time / load.ticks);
+
+        let target = self.settings.target_tick;
+        let overloaded = tick.is_some_and(|tick| tick > target)
+            || self
+                .settings
+                .max_bandwidth
+                .is_some_and(|max| bandwidth > max);
+        // Only raise the view distance with some headroom, so it doesn't go up and down every check.
+        let relaxed = tick.is_none_or(|tick| tick <= target * 3 / 4)
+            && self
+                .settings
+                .max_bandwidth
+                .is_none_or(|max| bandwidth <= max * 3 / 4);
+
+        let current = self.view_distance();
+        let next = if overloaded {
+            current.saturating_sub(1).max(self.settings.min)
+        } else if relaxed {
+            (current + 1).min(self.settings.max)
+        } else {
+            current
+        };
+
+        if next == current {
+            return None;
+        }
+        self.view_distance.store(next, Ordering::SeqCst);
+        Some((current, next))
+    }
+
+    /// Checks the load every interval and tells every client about changes of the view distance.
+    pub(crate) async fn run(self: Arc<Self>, clients: Arc<ClientRegistry>) {
+        let mut interval = time::interval(self.settings.interval);
+        interval.tick().await;
+        *self.load.lock().unwrap() = Load::new();
+
+        loop {
+            interval.tick().await;
+            if let Some((from, to)) = self.update() {
+                clients.broadcast(ClientMessage::ViewDistance { from, to });
+            }
+        }
+    }
+}
@@ -0,0 +1,459 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use protocol_packets::chunk::{Chunk, ChunkPos, LightData};
+use tokio::{task, time};
+
+use crate::{
+    config::SpawnPosition,
+    tickets::{ChunkTickets, TicketType},
+};
+
+/// The identifier of the overworld, which is the dimension and dimension type of a `[World]` by default.
+pub const OVERWORLD: &str = "minecraft:overworld";
+
+/// How often the server unloads the chunks that are without tickets.
+pub const UNLOAD_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The blocks players walk around in, the chunks of the world are streamed to the players as they move.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::{config::SpawnPosition, world::World};
+/// use protocol_packets::chunk::{Chunk, ChunkPos};
+///
+/// /// A world with a single stone platform below the spawn.
+/// #[derive(Debug)]
+/// struct Platform;
+///
+/// impl World for Platform {
+///     fn chunk(&self, pos: ChunkPos) -> Option<Chunk> {
+///         let layers: &[i32] = if pos == ChunkPos::new(0, 0) { &[1] } else { &[] };
+///         Some(Chunk::flat(pos.x, pos.z, 24, 0, layers))
+///     }
+///
+///     fn spawn_point(&self) -> SpawnPosition {
+///         SpawnPosition { y: -63.0, ..SpawnPosition::default() }
+///     }
+/// }
+///
+/// assert_eq!(Platform.chunk(ChunkPos::new(0, 0)).unwrap().get_block(0, 0, 0), 1);
+/// assert_eq!(Platform.dimension_type(), "minecraft:overworld");
+/// ```
+pub trait World: fmt::Debug + Send + Sync {
+    /// Returns the chunk at the given position, `None` if the chunk doesn't exist and isn't sent.
+    fn chunk(&self, pos: ChunkPos) -> Option<Chunk>;
+
+    /// Returns the position players spawn at.
+    fn spawn_point(&self) -> SpawnPosition;
+
+    /// Returns the dimension type of the world, it has to be an entry of the `minecraft:dimension_type` registry.
+    fn dimension_type(&self) -> &str {
+        OVERWORLD
+    }
+
+    /// Returns the name of the dimension, like `minecraft:the_nether`.
+    fn dimension_name(&self) -> &str {
+        OVERWORLD
+    }
+
+    /// Returns the light of the given chunk, which is sent together with the chunk.
+    ///
+    /// Every block has full sky light by default.
+    fn light(&self, chunk: &Chunk) -> LightData {
+        LightData::uniform(chunk.sections().len(), 15, 0)
+    }
+
+    /// Called once a chunk was without tickets long enough and is unloaded, so it can be saved.
+    ///
+    /// The chunk is dropped by default.
+    fn unload_chunk(&self, _pos: ChunkPos, _chunk: Chunk) {}
+}
+
+/// A superflat `[World]`, where every chunk is made out of the same layers.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::world::{FlatWorld, World};
+/// use protocol_packets::chunk::ChunkPos;
+///
+/// // Bedrock, two layers of dirt and grass.
+/// let world = FlatWorld::new(24, -64, 0, vec![79, 10, 10, 9]);
+///
+/// let chunk = world.chunk(ChunkPos::new(3, -2)).unwrap();
+/// assert_eq!((chunk.x, chunk.z), (3, -2));
+/// assert_eq!(chunk.get_block(0, 3, 0), 9);
+/// assert_eq!(world.spawn_point().y, -60.0);
+/// ```
+///
+/// # Fields
+/// - `section_count` - The amount of sections of every chunk, this is the world height divided by 16.
+/// - `min_y` - The Y position of the bottom of the world.
+/// - `biome` - The biome of the whole world.
+/// - `layers` - The block state of every layer, starting at the bottom.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlatWorld {
+    pub section_count: usize,
+    pub min_y: i32,
+    pub biome: i32,
+    pub layers: Vec<i32>,
+}
+
+impl FlatWorld {
+    /// Creates a new `[FlatWorld]`.
+    ///
+    /// # Parameters
+    /// - `section_count` - The amount of sections of every chunk.
+    /// - `min_y` - The Y position of the bottom of the world.
+    /// - `biome` - The biome of the whole world.
+    /// - `layers` - The block state of every layer, starting at the bottom.
+    pub fn new(section_count: usize, min_y: i32, biome: i32, layers: Vec<i32>) -> Self {
+        Self {
+            section_count,
+            min_y,
+            biome,
+            layers,
+        }
+    }
+}
+
+impl World for FlatWorld {
+    fn chunk(&self, pos: ChunkPos) -> Option<Chunk> {
+        Some(Chunk::flat(
+            pos.x,
+            pos.z,
+            self.section_count,
+            self.biome,
+            &self.layers,
+        ))
+    }
+
+    fn spawn_point(&self) -> SpawnPosition {
+        SpawnPosition {
+            y: (self.min_y + self.layers.len() as i32) as f64,
+            ..SpawnPosition::default()
+        }
+    }
+}
+
+/// The chunks of a `[World]` that are loaded, a chunk stays loaded while it has tickets.
+///
+/// Every `[ChunkView]` that was created with `[ChunkView::with_tickets]` adds a player ticket to the chunks its client has loaded.
+/// Chunks without tickets are unloaded after `unload_delay` passes of `[LoadedChunks::unload_chunks]`,
+/// which the server runs every `[UNLOAD_INTERVAL]`.
+///
+/// The chunks are shared behind a lock by every client, so the `[World]` is never called while the lock is held.
+///
+/// # Examples
+/// ```rust
+/// use std::sync::Mutex;
+/// use protocol_core::world::{FlatWorld, LoadedChunks};
+/// use protocol_packets::chunk::ChunkPos;
+///
+/// let world = FlatWorld::new(24, -64, 0, vec![79, 10, 10, 9]);
+/// let chunks = Mutex::new(LoadedChunks::new(1));
+///
+/// chunks.lock().unwrap().tickets.add_player(ChunkPos::new(0, 0), 0);
+/// assert!(LoadedChunks::load(&chunks, &world, ChunkPos::new(0, 0)).is_some());
+/// assert!(LoadedChunks::load(&chunks, &world, ChunkPos::new(5, 5)).is_some());
+///
+/// let mut chunks = chunks.into_inner().unwrap();
+/// assert_eq!(chunks.len(), 2);
+///
+/// // Only the chunk without a ticket is unloaded.
+/// let unloaded = chunks.unload_chunks();
+/// assert_eq!(unloaded.len(), 1);
+/// assert_eq!(unloaded[0].0, ChunkPos::new(5, 5));
+/// assert!(chunks.is_loaded(ChunkPos::new(0, 0)));
+/// ```
+///
+/// # Fields
+/// - `tickets` - The tickets that keep the chunks loaded.
+/// - `chunks` - The loaded chunks.
+#[derive(Debug, Default)]
+pub struct LoadedChunks {
+    pub tickets: ChunkTickets,
+    chunks: HashMap<ChunkPos, Arc<Chunk>>,
+}
+
+impl LoadedChunks {
+    /// Creates a new `[LoadedChunks]` without any loaded chunks.
+    ///
+    /// # Parameters
+    /// - `unload_delay` - The amount of unload passes a chunk has to be without tickets before it is unloaded.
+    pub fn new(unload_delay: u32) -> Self {
+        Self {
+            tickets: ChunkTickets::new(unload_delay),
+            chunks: HashMap::new(),
+        }
+    }
+
+    /// Returns the given chunk, it is loaded from the world if it isn't loaded yet.
+    ///
+    /// The lock is only held to look up and insert the chunk, the world loads it while the lock is released.
+    /// If another task loaded the chunk in the meantime, its chunk is kept.
+    ///
+    /// # Parameters
+    /// - `chunks` - The loaded chunks.
+    /// - `world` - The world the chunk is loaded from.
+    /// - `pos` - The position of the chunk.
+    ///
+    /// # Returns
+    /// The chunk, `None` if the world doesn't have it.
+    pub fn load(chunks: &Mutex<Self>, world: &dyn World, pos: ChunkPos) -> Option<Arc<Chunk>> {
+        if let Some(chunk) = chunks.lock().unwrap().get(pos) {
+            return Some(chunk);
+        }
+
+        let chunk = Arc::new(world.chunk(pos)?);
+        Some(
+            chunks
+                .lock()
+                .unwrap()
+                .chunks
+                .entry(pos)
+                .or_insert(chunk)
+                .clone(),
+        )
+    }
+
+    /// Returns the given chunk, `None` if it isn't loaded.
+    pub fn get(&self, pos: ChunkPos) -> Option<Arc<Chunk>> {
+        self.chunks.get(&pos).cloned()
+    }
+
+    /// Returns whether the given chunk is loaded.
+    pub fn is_loaded(&self, pos: ChunkPos) -> bool {
+        self.chunks.contains_key(&pos)
+    }
+
+    /// Returns the amount of loaded chunks.
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns whether no chunks are loaded.
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Unloads the chunks that were without tickets long enough.
+    ///
+    /// # Returns
+    /// The unloaded chunks, which still have to be given to `[World::unload_chunk]`.
+    pub fn unload_chunks(&mut self) -> Vec<(ChunkPos, Chunk)> {
+        let mut unloaded = Vec::new();
+        self.tickets.unload_chunks(&mut self.chunks, |pos, chunk| {
+            unloaded.push((pos, Arc::unwrap_or_clone(chunk)))
+        });
+        unloaded
+    }
+
+    /// Unloads the chunks without tickets every `[UNLOAD_INTERVAL]`.
+    ///
+    /// The unloaded chunks are given to `[World::unload_chunk]` on a blocking thread, after the lock was released.
+    pub(crate) async fn run(chunks: Arc<Mutex<Self>>, world: Arc<dyn World>) {
+        let mut interval = time::interval(UNLOAD_INTERVAL);
+        loop {
+            interval.tick().await;
+            let unloaded = chunks.lock().unwrap().unload_chunks();
+            if unloaded.is_empty() {
+                continue;
+            }
+
+            let world = world.clone();
+            let saved = task::spawn_blocking(move || {
+                for (pos, chunk) in unloaded {
+                    world.unload_chunk(pos, chunk);
+                }
+            });
+            if let Err(e) = saved.await {
+                println!("Failed to unload chunks; err = {}", e);
+            }
+        }
+    }
+}
+
+/// The changes to the chunks a client has loaded, returned by `[ChunkView::update]`.
+///
+/// # Fields
+/// - `center` - The new center chunk, `None` if the center didn't change.
+/// - `load` - The chunks to send, ordered from the center outwards.
+/// - `unload` - The chunks to unload, which are outside of the view distance now.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ChunkUpdate {
+    pub center: Option<ChunkPos>,
+    pub load: Vec<ChunkPos>,
+    pub unload: Vec<ChunkPos>,
+}
+
+impl ChunkUpdate {
+    /// Returns whether nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.center.is_none() && self.load.is_empty() && self.unload.is_empty()
+    }
+}
+
+/// Keeps track of the chunks a client has loaded, so only the chunks that came into view are sent when it moves.
+///
+/// A chunk only counts as loaded once it was passed to `[ChunkView::mark_sent]`, so chunks the world doesn't have
+/// are requested again by the next update.
+/// A view created with `[ChunkView::with_tickets]` keeps a player ticket on every chunk it has loaded,
+/// the tickets are removed once the chunks are unloaded, the view is cleared or it is dropped.
+///
+/// # Examples
+/// ```rust
+/// use protocol_core::world::ChunkView;
+/// use protocol_packets::chunk::ChunkPos;
+///
+/// let mut view = ChunkView::new();
+///
+/// let update = view.update(ChunkPos::new(0, 0), 2);
+/// assert_eq!(update.center, Some(ChunkPos::new(0, 0)));
+/// assert_eq!(update.load.len(), 25);
+/// assert_eq!(update.load[0], ChunkPos::new(0, 0));
+/// update.load.iter().for_each(|chunk| view.mark_sent(*chunk));
+///
+/// // Moving one chunk along the X axis loads and unloads a single row.
+/// let update = view.update(ChunkPos::new(1, 0), 2);
+/// assert_eq!(update.load.len(), 5);
+/// assert_eq!(update.unload.len(), 5);
+/// assert!(update.unload.iter().all(|chunk| chunk.x == -2));
+///
+/// // A chunk that wasn't sent is requested again.
+/// update.load.iter().skip(1).for_each(|chunk| view.mark_sent(*chunk));
+/// assert_eq!(view.update(ChunkPos::new(1, 0), 2).load, vec![update.load[0]]);
+/// ```
+///
+/// # Fields
+/// - `center` - The chunk the client is in, `None` if no chunks were sent yet.
+/// - `loaded` - The chunks the client has loaded.
+/// - `chunks` - The loaded chunks of the world the player tickets are added to, `None` if the view doesn't add tickets.
+#[derive(Debug, Default)]
+pub struct ChunkView {
+    center: Option<ChunkPos>,
+    loaded: HashSet<ChunkPos>,
+    chunks: Option<Arc<Mutex<LoadedChunks>>>,
+}
+
+impl ChunkView {
+    /// Creates a new `[ChunkView]` without any loaded chunks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new `[ChunkView]` that adds a player ticket to every chunk it loads.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use std::sync::{Arc, Mutex};
+    /// use protocol_core::{tickets::TicketType, world::{ChunkView, LoadedChunks}};
+    /// use protocol_packets::chunk::ChunkPos;
+    ///
+    /// let chunks = Arc::new(Mutex::new(LoadedChunks::new(1)));
+    /// let mut view = ChunkView::with_tickets(chunks.clone());
+    ///
+    /// for center in [ChunkPos::new(0, 0), ChunkPos::new(1, 0)] {
+    ///     let update = view.update(center, 2);
+    ///     update.load.iter().for_each(|chunk| view.mark_sent(*chunk));
+    /// }
+    /// let count = |x, z| chunks.lock().unwrap().tickets.count(ChunkPos::new(x, z), TicketType::Player);
+    /// assert_eq!((count(-2, 0), count(3, 0)), (0, 1));
+    ///
+    /// drop(view);
+    /// assert_eq!(chunks.lock().unwrap().tickets.ticketed().count(), 0);
+    /// ```
+    ///
+    /// # Parameters
+    /// - `chunks` - The loaded chunks of the world.
+    pub fn with_tickets(chunks: Arc<Mutex<LoadedChunks>>) -> Self {
+        Self {
+            center: None,
+            loaded: HashSet::new(),
+            chunks: Some(chunks),
+        }
+    }
+
+    /// Returns the chunk the client is in, `None` if no chunks were sent yet.
+    pub fn center(&self) -> Option<ChunkPos> {
+        self.center
+    }
+
+    /// Returns whether the client has loaded the given chunk.
+    pub fn is_loaded(&self, chunk: ChunkPos) -> bool {
+        self.loaded.contains(&chunk)
+    }
+
+    /// Moves the view and returns the chunks that have to be sent and unloaded.
+    ///
+    /// The chunks to send aren't loaded until they are passed to `[ChunkView::mark_sent]`.
+    ///
+    /// # Parameters
+    /// - `center` - The chunk the client is in.
+    /// - `view_distance` - The view distance of the client in chunks.
+    pub fn update(&mut self, center: ChunkPos, view_distance: u32) -> ChunkUpdate {
+        let mut update = ChunkUpdate {
+            center: (self.center != Some(center)).then_some(center),
+            ..ChunkUpdate::default()
+        };
+        self.center = Some(center);
+
+        self.loaded.retain(|chunk| {
+            let visible = chunk.distance(center) <= view_distance;
+            if !visible {
+                update.unload.push(*chunk);
+            }
+            visible
+        });
+
+        update.load = center
+            .square(view_distance)
+            .filter(|chunk| !self.loaded.contains(chunk))
+            .collect();
+        update.load.sort_by_key(|chunk| chunk.distance(center));
+
+        if let Some(chunks) = &self.chunks {
+            let tickets = &mut chunks.lock().unwrap().tickets;
+            for chunk in &update.unload {
+                tickets.remove(*chunk, TicketType::Player);
+            }
+        }
+        update
+    }
+
+    /// Marks a chunk as loaded by the client, after it was sent. A view with tickets adds a player ticket to it.
+    pub fn mark_sent(&mut self, chunk: ChunkPos) {
+        if !self.loaded.insert(chunk) {
+            return;
+        }
+        if let Some(chunks) = &self.chunks {
+            chunks
+                .lock()
+                .unwrap()
+                .tickets
+                .add(chunk, TicketType::Player);
+        }
+    }
+
+    /// Forgets every loaded chunk, like after the client respawned into another dimension.
+    pub fn clear(&mut self) {
+        self.center = None;
+        if let Some(chunks) = &self.chunks {
+            let tickets = &mut chunks.lock().unwrap().tickets;
+            for chunk in &self.loaded {
+                tickets.remove(*chunk, TicketType::Player);
+            }
+        }
+        self.loaded.clear();
+    }
+}
+
+impl Drop for ChunkView {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
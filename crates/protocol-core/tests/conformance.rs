@@ -0,0 +1,886 @@
+//! Conformance tests that join the server the way the vanilla client does.
+//!
+//! The `[VanillaSession]` sends the same serverbound packets, in the same order, as a vanilla 1.21 client
+//! and answers everything the client has to answer. The packets the server sends back are decoded
+//! independently from the writers in `protocol_packets`, so a change that breaks the wire format
+//! breaks these tests, even if the server still agrees with itself.
+
+use std::{
+    fs,
+    net::{IpAddr, SocketAddr},
+    ops::RangeInclusive,
+    panic,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use protocol_buf::{
+    buffer::{Buffer, NormalBuffer, PacketBuffer},
+    error::ProtocolResult,
+    nbt::Nbt,
+    types::{RemainingBytes, VarInt},
+    ToNetwork,
+};
+use protocol_core::{
+    auth::{server_hash, AuthFuture, Authenticator, OfflineAuthenticator},
+    connector::MinecraftConnector,
+    keep_alive::KeepAlive,
+    operators::Operator,
+    sequence::{LOGIN_PACKETS, PLAY_PACKETS},
+    server::ServerConnection,
+    shutdown::ShutdownHandle,
+    throttle::ThrottleSettings,
+    world::FlatWorld,
+};
+use protocol_packets::{
+    chunk::{Chunk, LightData},
+    plugin::{encode_brand, BRAND_CHANNEL},
+    registry::KnownPack,
+    settings::ClientInformation,
+    v1_21::{
+        configuration::{
+            AcknowledgeFinishConfigurationPacket, ConfigurationClientInformationPacket,
+            ServerboundConfigurationKeepAlivePacket, ServerboundConfigurationPluginMessagePacket,
+            ServerboundKnownPacksPacket,
+        },
+        login::{EncryptionResponsePacket, LoginAcknowledgedPacket},
+        play::{
+            ChunkDataAndUpdateLightPacket, ConfirmTeleportationPacket, GameEventPacket,
+            PlayerCommandPacket, ServerboundKeepAlivePacket,
+        },
+    },
+    ConnectionState, ServerboundPacket,
+};
+use rand::{
+    rngs::{OsRng, StdRng},
+    Rng, SeedableRng,
+};
+use rsa::{pkcs8::DecodePublicKey, Pkcs1v15Encrypt, RsaPublicKey};
+use tokio::{net::TcpListener, time};
+
+/// The amount of chunk sections of the overworld.
+const SECTION_COUNT: usize = 24;
+
+/// The layers of the superflat chunks sent by the test server: bedrock, two layers of dirt and grass.
+const LAYERS: [i32; 4] = [79, 10, 10, 9];
+
+/// The radius of the square of chunks the test server sends.
+const RADIUS: i32 = 1;
+
+/// The game event that tells the client the chunks around it are being sent.
+const START_WAITING_FOR_CHUNKS: u8 = 13;
+
+/// The time a whole session may take before the test fails.
+const SESSION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The time the server gets to answer a malformed packet, before the connection is considered to be still open.
+const MALFORMED_PACKET_GRACE: Duration = Duration::from_millis(20);
+
+/// Starts a server on a free port, which sends superflat chunks to every player that joins.
+///
+/// # Returns
+/// The address of the server, the amount of keep alive responses it received and the handle that shuts it down.
+async fn start_server(
+    keep_alive_interval: Duration,
+) -> (SocketAddr, Arc<AtomicUsize>, ShutdownHandle) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let mut server = ServerConnection::new(listener);
+    // Every session connects from the same address, some tests connect a lot of times.
+    server.set_connection_throttle(ThrottleSettings {
+        max_connections_per_ip: u32::MAX,
+        max_handshakes_per_second: u32::MAX,
+        ..ThrottleSettings::default()
+    });
+
+    let chunks: Vec<_> = (-RADIUS..=RADIUS)
+        .flat_map(|x| (-RADIUS..=RADIUS).map(move |z| (x, z)))
+        .map(|(x, z)| {
+            let chunk = Chunk::flat(x, z, SECTION_COUNT, 0, &LAYERS);
+            ChunkDataAndUpdateLightPacket::new(&chunk, LightData::uniform(SECTION_COUNT, 15, 0))
+        })
+        .collect();
+    server.after::<AcknowledgeFinishConfigurationPacket>(move |client, _packet| {
+        let handle = client.handle();
+        handle.send_packet(&GameEventPacket {
+            event: START_WAITING_FOR_CHUNKS,
+            value: 0.0,
+        });
+        for chunk in &chunks {
+            handle.send_packet(chunk);
+        }
+    });
+
+    let keep_alives = Arc::new(AtomicUsize::new(0));
+    let counter = keep_alives.clone();
+    server.after::<ServerboundKeepAlivePacket>(move |_client, _packet| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let shutdown = server.shutdown_handle();
+    let interval = keep_alive_interval;
+    tokio::spawn(async move {
+        server
+            .accept_connections(move |mut client| async move {
+                client.keep_alive = KeepAlive::new(interval, Duration::from_secs(5));
+                client.start().await
+            })
+            .await;
+    });
+
+    (address, keep_alives, shutdown)
+}
+
+/// The index, the type and the raw value of a decoded metadata entry.
+type MetadataEntry = (u8, i32, Vec<u8>);
+
+/// A decoded chunk section.
+///
+/// # Fields
+/// - `block_count` - The amount of blocks that are not air.
+/// - `block_palette` - The palette of the block states, empty if the palette is direct.
+struct DecodedSection {
+    block_count: i16,
+    block_palette: Vec<i32>,
+}
+
+/// Plays the client side of a connection, the way the vanilla client does.
+///
+/// # Fields
+/// - `connector` - The connection to the server.
+/// - `received` - The state and ID of every packet the server sent, in order.
+/// - `chunks` - The position and sections of every chunk the server sent.
+/// - `keep_alives` - The amount of keep alives that were answered in the `Play` state.
+/// - `entity_id` - The entity ID of the player, this is set once the `LoginPlay` packet was received.
+/// - `metadata` - The entity ID and the raw metadata entries of every metadata packet.
+struct VanillaSession {
+    connector: MinecraftConnector,
+    received: Vec<(ConnectionState, i32)>,
+    chunks: Vec<((i32, i32), Vec<DecodedSection>)>,
+    keep_alives: usize,
+    entity_id: Option<i32>,
+    metadata: Vec<(i32, Vec<MetadataEntry>)>,
+}
+
+impl VanillaSession {
+    /// Connects to the server and logs in until the server switches the client to the `Play` state.
+    async fn join(address: SocketAddr, username: &str) -> Self {
+        let connector = MinecraftConnector::connect(&address.ip().to_string(), address.port())
+            .await
+            .unwrap();
+        let mut session = Self {
+            connector,
+            received: Vec::new(),
+            chunks: Vec::new(),
+            keep_alives: 0,
+            entity_id: None,
+            metadata: Vec::new(),
+        };
+
+        session
+            .connector
+            .handshake(ConnectionState::Login)
+            .await
+            .unwrap();
+        let uuid = OfflineAuthenticator::offline_uuid(username);
+        session.connector.login_start(username, uuid).await.unwrap();
+
+        while session.connector.state != ConnectionState::Play {
+            let mut packet = session.next_packet().await;
+            match (session.connector.state, *packet.packet_id) {
+                (ConnectionState::Login, 0x02) => {
+                    let login_uuid: u128 = packet.read().unwrap();
+                    assert_eq!(login_uuid, uuid.as_u128());
+                    assert_eq!(packet.read_string().unwrap(), username);
+
+                    session.send(&LoginAcknowledgedPacket {}).await;
+                    session.connector.state = ConnectionState::Configuration;
+
+                    session.send(&client_information()).await;
+                    session
+                        .send(&ServerboundConfigurationPluginMessagePacket {
+                            channel: BRAND_CHANNEL.to_string(),
+                            data: RemainingBytes::from(encode_brand("vanilla")),
+                        })
+                        .await;
+                }
+                (ConnectionState::Configuration, 0x03) => {
+                    session.send(&AcknowledgeFinishConfigurationPacket {}).await;
+                    session.connector.state = ConnectionState::Play;
+                }
+                (ConnectionState::Configuration, 0x04) => {
+                    let keep_alive_id = packet.read().unwrap();
+                    session
+                        .send(&ServerboundConfigurationKeepAlivePacket { keep_alive_id })
+                        .await;
+                }
+                (ConnectionState::Configuration, 0x0E) => {
+                    let packs = vec![KnownPack::core()];
+                    session.send(&ServerboundKnownPacksPacket { packs }).await;
+                }
+                (ConnectionState::Login, 0x00) | (ConnectionState::Configuration, 0x02) => {
+                    panic!("Disconnected while joining");
+                }
+                _ => {}
+            }
+        }
+
+        session
+    }
+
+    /// Handles the packets of the `Play` state until the condition is met.
+    async fn play_until(&mut self, condition: impl Fn(&Self) -> bool) {
+        while !condition(self) {
+            let mut packet = self.next_packet().await;
+            match *packet.packet_id {
+                0x1D => panic!("Disconnected while playing"),
+                0x26 => {
+                    let keep_alive_id = packet.read().unwrap();
+                    self.send(&ServerboundKeepAlivePacket { keep_alive_id })
+                        .await;
+                    self.keep_alives += 1;
+                }
+                0x2B => self.entity_id = Some(packet.read().unwrap()),
+                0x58 => {
+                    let metadata = decode_metadata(&mut packet).unwrap();
+                    self.metadata.push(metadata);
+                }
+                0x27 => {
+                    let chunk = decode_chunk(&mut packet).unwrap();
+                    assert_eq!(packet.remaining(), 0, "Trailing data after a chunk");
+                    self.chunks.push(chunk);
+                }
+                0x40 => {
+                    let teleport_id = read_teleport_id(&mut packet).unwrap();
+                    self.send(&ConfirmTeleportationPacket { teleport_id }).await;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the IDs of the packets received in the given state, in order.
+    fn received_in(&self, state: ConnectionState) -> Vec<i32> {
+        self.received
+            .iter()
+            .filter(|(received_state, _)| *received_state == state)
+            .map(|(_, id)| *id)
+            .collect()
+    }
+
+    async fn next_packet(&mut self) -> PacketBuffer {
+        let packet = self
+            .connector
+            .read_packet()
+            .await
+            .unwrap()
+            .expect("The server closed the connection");
+        self.received
+            .push((self.connector.state, *packet.packet_id));
+        packet
+    }
+
+    async fn send<P: ServerboundPacket>(&mut self, packet: &P) {
+        self.connector.send_packet(packet).await.unwrap();
+    }
+
+    /// Sends a packet with the given data, which doesn't have to be valid.
+    ///
+    /// # Returns
+    /// `false` if the server kicked the client or closed the connection.
+    async fn send_malformed(&mut self, packet_id: i32, data: &[u8]) -> bool {
+        if self.connector.send_raw(packet_id, data).await.is_err() {
+            return false;
+        }
+
+        loop {
+            match time::timeout(MALFORMED_PACKET_GRACE, self.connector.read_packet()).await {
+                Err(_) => return true,
+                Ok(Ok(Some(packet))) if *packet.packet_id != 0x1D => {}
+                Ok(_) => return false,
+            }
+        }
+    }
+}
+
+/// Generates the data of a malformed packet, which is made of random bytes and VarInts.
+fn malformed_data(rng: &mut StdRng) -> Vec<u8> {
+    let mut data = Vec::new();
+    for _ in 0..rng.gen_range(0..8) {
+        if rng.gen_bool(0.5) {
+            data.extend_from_slice(&VarInt::from(rng.gen::<i32>()).to_network());
+        } else {
+            data.extend((0..rng.gen_range(0..16)).map(|_| rng.gen::<u8>()));
+        }
+    }
+    data
+}
+
+/// The client information the vanilla client sends by default.
+fn client_information() -> ConfigurationClientInformationPacket {
+    ConfigurationClientInformationPacket {
+        information: ClientInformation {
+            view_distance: 12,
+            ..ClientInformation::default()
+        },
+    }
+}
+
+/// Reads the ID of a teleport, which is the last field of the packet.
+fn read_teleport_id(packet: &mut PacketBuffer) -> ProtocolResult<VarInt> {
+    for _ in 0..3 {
+        packet.read_double()?;
+    }
+    for _ in 0..2 {
+        packet.read_float()?;
+    }
+    packet.read_byte()?;
+
+    packet.read_varint()
+}
+
+/// Decodes a metadata packet, only the value types the server uses for players can be read.
+///
+/// # Returns
+/// The entity ID and the index, type and raw value of every entry.
+fn decode_metadata(packet: &mut PacketBuffer) -> ProtocolResult<(i32, Vec<MetadataEntry>)> {
+    let entity_id = *packet.read_varint()?;
+
+    let mut entries = Vec::new();
+    loop {
+        let index = packet.read_byte()?;
+        if index == 0xFF {
+            break;
+        }
+
+        let value_type = *packet.read_varint()?;
+        let value = match value_type {
+            0 => vec![packet.read_byte()?],
+            21 => vec![*packet.read_varint()? as u8],
+            value_type => panic!("Unexpected metadata type {}", value_type),
+        };
+        entries.push((index, value_type, value));
+    }
+    assert_eq!(packet.remaining(), 0, "Trailing data after the metadata");
+
+    Ok((entity_id, entries))
+}
+
+/// Decodes a chunk packet the way the vanilla client reads it.
+fn decode_chunk(packet: &mut PacketBuffer) -> ProtocolResult<((i32, i32), Vec<DecodedSection>)> {
+    let chunk_x: i32 = packet.read()?;
+    let chunk_z: i32 = packet.read()?;
+    let _heightmaps: Nbt = packet.read()?;
+
+    let data: Vec<u8> = packet.read()?;
+    let mut data = NormalBuffer::new(data);
+    let sections = (0..SECTION_COUNT)
+        .map(|_| {
+            let block_count: i16 = data.read()?;
+            let block_palette = decode_paletted_container(&mut data, 4096, 4..=8, 15)?;
+            decode_paletted_container(&mut data, 64, 1..=3, 6)?;
+
+            Ok(DecodedSection {
+                block_count,
+                block_palette,
+            })
+        })
+        .collect::<ProtocolResult<Vec<_>>>()?;
+    assert_eq!(
+        data.remaining(),
+        0,
+        "Trailing data after the chunk sections"
+    );
+
+    let block_entities = *packet.read_varint()?;
+    assert_eq!(block_entities, 0);
+
+    for _ in 0..4 {
+        let mask: Vec<i64> = packet.read()?;
+        assert!(mask.len() <= SECTION_COUNT.div_ceil(64) + 1);
+    }
+    for _ in 0..2 {
+        let arrays = *packet.read_varint()?;
+        for _ in 0..arrays {
+            let array: Vec<u8> = packet.read()?;
+            assert_eq!(array.len(), 2048);
+        }
+    }
+
+    Ok(((chunk_x, chunk_z), sections))
+}
+
+/// Decodes a paletted container and checks that its data array has the length the client expects.
+///
+/// # Parameters
+/// - `entries` - The amount of entries of the container.
+/// - `indirect_bits` - The amount of bits an indirect palette can use, lower amounts are raised to the minimum.
+/// - `direct_bits` - The amount of bits of a direct palette.
+///
+/// # Returns
+/// The palette, empty if the palette is direct.
+fn decode_paletted_container(
+    buffer: &mut NormalBuffer,
+    entries: usize,
+    indirect_bits: RangeInclusive<u8>,
+    direct_bits: u8,
+) -> ProtocolResult<Vec<i32>> {
+    let bits = buffer.read_byte()?;
+
+    let palette = match bits {
+        0 => vec![*buffer.read_varint()?],
+        bits if bits <= *indirect_bits.end() => {
+            let length = *buffer.read_varint()?;
+            (0..length)
+                .map(|_| buffer.read_varint().map(|value| *value))
+                .collect::<ProtocolResult<_>>()?
+        }
+        bits => {
+            assert_eq!(bits, direct_bits);
+            Vec::new()
+        }
+    };
+
+    let longs = *buffer.read_varint()? as usize;
+    let expected = match bits {
+        0 => 0,
+        bits => entries.div_ceil(64 / bits.max(*indirect_bits.start()) as usize),
+    };
+    assert_eq!(
+        longs, expected,
+        "Invalid data array length for {} bits",
+        bits
+    );
+    buffer.read_bytes(longs * 8)?;
+
+    Ok(palette)
+}
+
+#[tokio::test]
+async fn login_reaches_play() {
+    let (address, _, _) = start_server(Duration::from_secs(15)).await;
+
+    time::timeout(SESSION_TIMEOUT, async {
+        let mut session = VanillaSession::join(address, "Steve").await;
+        session
+            .play_until(|session| session.received_in(ConnectionState::Play).contains(&0x40))
+            .await;
+
+        assert_eq!(
+            session.received_in(ConnectionState::Login),
+            vec![0x03, 0x02]
+        );
+
+        let configuration = session.received_in(ConnectionState::Configuration);
+        assert_eq!(
+            configuration.first(),
+            Some(&0x01),
+            "The brand is sent first"
+        );
+        assert_eq!(configuration.last(), Some(&0x03));
+        for packet_id in [0x0C, 0x0E, 0x07, 0x0D] {
+            assert!(
+                configuration.contains(&packet_id),
+                "Configuration packet 0x{:02X} is missing",
+                packet_id
+            );
+        }
+
+        assert_eq!(
+            session.received_in(ConnectionState::Play)[..3],
+            [0x2B, 0x1F, 0x40]
+        );
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn chunks_can_be_decoded() {
+    let (address, _, _) = start_server(Duration::from_secs(15)).await;
+    let chunk_count = ((RADIUS * 2 + 1) * (RADIUS * 2 + 1)) as usize;
+
+    time::timeout(SESSION_TIMEOUT, async {
+        let mut session = VanillaSession::join(address, "Alex").await;
+        session
+            .play_until(|session| session.chunks.len() == chunk_count)
+            .await;
+
+        let play = session.received_in(ConnectionState::Play);
+        let first_chunk = play.iter().position(|id| *id == 0x27).unwrap();
+        assert!(
+            play[..first_chunk].contains(&0x22),
+            "The game event has to be sent before the chunks"
+        );
+
+        for ((x, z), sections) in &session.chunks {
+            assert!(x.abs() <= RADIUS && z.abs() <= RADIUS);
+
+            let bottom = &sections[0];
+            assert_eq!(bottom.block_count, (LAYERS.len() * 256) as i16);
+            for layer in LAYERS {
+                assert!(bottom.block_palette.contains(&layer));
+            }
+            for section in &sections[1..] {
+                assert_eq!(section.block_count, 0);
+                assert_eq!(section.block_palette, vec![0]);
+            }
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn keep_alives_are_exchanged() {
+    let (address, responses, _) = start_server(Duration::from_millis(100)).await;
+
+    time::timeout(SESSION_TIMEOUT, async {
+        let mut session = VanillaSession::join(address, "Notch").await;
+        session.play_until(|session| session.keep_alives == 3).await;
+
+        // The connection is still open, so the server accepted every response.
+        session.play_until(|session| session.keep_alives == 4).await;
+    })
+    .await
+    .unwrap();
+
+    assert!(responses.load(Ordering::SeqCst) >= 3);
+}
+
+#[tokio::test]
+async fn sneaking_is_broadcasted() {
+    let (address, _, _) = start_server(Duration::from_secs(15)).await;
+
+    time::timeout(SESSION_TIMEOUT, async {
+        let mut session = VanillaSession::join(address, "Jeb").await;
+        session
+            .play_until(|session| session.received_in(ConnectionState::Play).contains(&0x40))
+            .await;
+
+        let entity_id = session.entity_id.unwrap();
+        session
+            .send(&PlayerCommandPacket {
+                entity_id: VarInt::from(entity_id),
+                action: VarInt::from(0),
+                jump_boost: VarInt::from(0),
+            })
+            .await;
+        session
+            .play_until(|session| !session.metadata.is_empty())
+            .await;
+
+        let (metadata_entity, entries) = &session.metadata[0];
+        assert_eq!(*metadata_entity, entity_id);
+        assert!(
+            entries.contains(&(0, 0, vec![0x02])),
+            "The sneaking flag is set"
+        );
+        assert!(entries.contains(&(6, 21, vec![5])), "The pose is sneaking");
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn shutdown_kicks_players() {
+    let (address, _, shutdown) = start_server(Duration::from_secs(15)).await;
+
+    time::timeout(SESSION_TIMEOUT, async {
+        let mut session = VanillaSession::join(address, "Dinnerbone").await;
+        session
+            .play_until(|session| session.received_in(ConnectionState::Play).contains(&0x40))
+            .await;
+
+        let shutdown = tokio::spawn(async move {
+            shutdown
+                .shutdown("Server closed", Duration::from_secs(5))
+                .await
+        });
+
+        while session
+            .connector
+            .read_packet()
+            .await
+            .unwrap()
+            .is_some_and(|packet| *packet.packet_id != 0x1D)
+        {}
+        assert!(
+            session.connector.read_packet().await.unwrap().is_none(),
+            "The connection is closed after the disconnect packet"
+        );
+        assert_eq!(
+            shutdown.await.unwrap(),
+            0,
+            "No connection had to be aborted"
+        );
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn malformed_packets_dont_panic() {
+    let panicked = Arc::new(AtomicBool::new(false));
+    let flag = panicked.clone();
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        flag.store(true, Ordering::SeqCst);
+        hook(info);
+    }));
+
+    let (address, _, _) = start_server(Duration::from_secs(15)).await;
+    let mut rng = StdRng::seed_from_u64(0x3801);
+
+    time::timeout(SESSION_TIMEOUT * 3, async {
+        for _ in 0..16 {
+            let mut connector =
+                MinecraftConnector::connect(&address.ip().to_string(), address.port())
+                    .await
+                    .unwrap();
+            connector.handshake(ConnectionState::Login).await.unwrap();
+            let packet_id = rng.gen_range(0..=LOGIN_PACKETS);
+            let _ = connector
+                .send_raw(packet_id, &malformed_data(&mut rng))
+                .await;
+            let _ = time::timeout(MALFORMED_PACKET_GRACE, connector.read_packet()).await;
+        }
+
+        let mut session = None;
+        for packet_id in (0..=PLAY_PACKETS).flat_map(|packet_id| [packet_id; 2]) {
+            let current = match &mut session {
+                Some(session) => session,
+                None => session.insert(VanillaSession::join(address, "Grumm").await),
+            };
+            if !current
+                .send_malformed(packet_id, &malformed_data(&mut rng))
+                .await
+            {
+                session = None;
+            }
+        }
+    })
+    .await
+    .unwrap();
+
+    assert!(!panicked.load(Ordering::SeqCst), "The server panicked");
+}
+
+/// Accepts every player like an offline server, but requires encryption and remembers the server hashes.
+#[derive(Debug)]
+struct EncryptedAuthenticator {
+    hashes: Arc<Mutex<Vec<String>>>,
+}
+
+impl Authenticator for EncryptedAuthenticator {
+    fn requires_encryption(&self) -> bool {
+        true
+    }
+
+    fn authenticate<'a>(
+        &'a self,
+        username: &'a str,
+        server_hash: &'a str,
+        ip: Option<IpAddr>,
+    ) -> AuthFuture<'a> {
+        self.hashes.lock().unwrap().push(server_hash.to_string());
+        OfflineAuthenticator.authenticate(username, server_hash, ip)
+    }
+}
+
+#[tokio::test]
+async fn online_mode_encrypts_the_connection() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let mut server = ServerConnection::new(listener);
+    let hashes = Arc::new(Mutex::new(Vec::new()));
+    server.set_authenticator(EncryptedAuthenticator {
+        hashes: hashes.clone(),
+    });
+    tokio::spawn(async move {
+        server
+            .accept_connections(|mut client| async move { client.start().await })
+            .await;
+    });
+
+    time::timeout(SESSION_TIMEOUT, async {
+        let mut connector = MinecraftConnector::connect(&address.ip().to_string(), address.port())
+            .await
+            .unwrap();
+        connector.handshake(ConnectionState::Login).await.unwrap();
+        connector
+            .login_start("Steve", OfflineAuthenticator::offline_uuid("Steve"))
+            .await
+            .unwrap();
+
+        let mut request = connector.read_packet().await.unwrap().unwrap();
+        assert_eq!(*request.packet_id, 0x01, "The encryption request is sent");
+        let server_id = request.read_string().unwrap();
+        let public_key: Vec<u8> = request.read().unwrap();
+        let verify_token: Vec<u8> = request.read().unwrap();
+        assert!(request.read::<bool>().unwrap());
+
+        let shared_secret = [7; 16];
+        let key = RsaPublicKey::from_public_key_der(&public_key).unwrap();
+        let response = EncryptionResponsePacket {
+            shared_secret: key
+                .encrypt(&mut OsRng, Pkcs1v15Encrypt, &shared_secret)
+                .unwrap(),
+            verify_token: key
+                .encrypt(&mut OsRng, Pkcs1v15Encrypt, &verify_token)
+                .unwrap(),
+        };
+        connector.send_packet(&response).await.unwrap();
+        connector.enable_encryption(&shared_secret);
+
+        // The set compression and login success packets can only be read if they are encrypted.
+        let mut received = Vec::new();
+        while received.last() != Some(&0x02) {
+            let packet = connector.read_packet().await.unwrap().unwrap();
+            received.push(*packet.packet_id);
+        }
+        assert_eq!(received, vec![0x03, 0x02]);
+        assert_eq!(
+            *hashes.lock().unwrap(),
+            vec![server_hash(&server_id, &shared_secret, &public_key)]
+        );
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn handshakes_starting_like_legacy_pings_are_read() {
+    let (address, _, _shutdown) = start_server(Duration::from_secs(15)).await;
+
+    time::timeout(SESSION_TIMEOUT, async {
+        let mut connector = MinecraftConnector::connect(&address.ip().to_string(), address.port())
+            .await
+            .unwrap();
+
+        // A long server address, like the one of a BungeeCord handshake, makes the frame 254 bytes long,
+        // so the frame starts with `0xFE 0x01` like a legacy ping.
+        let server_address = "a".repeat(246);
+        let mut data = VarInt::from(767).to_network();
+        data.extend(VarInt::from(server_address.len() as i32).to_network());
+        data.extend(server_address.as_bytes());
+        data.extend(address.port().to_network());
+        data.extend(VarInt::from(1).to_network());
+        assert_eq!(data.len() + 1, 254);
+
+        connector.send_raw(0x00, &data).await.unwrap();
+        connector.state = ConnectionState::Status;
+        assert!(connector.status().await.unwrap().contains("description"));
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn invalid_usernames_are_kicked() {
+    let (address, _, _shutdown) = start_server(Duration::from_secs(15)).await;
+
+    time::timeout(SESSION_TIMEOUT, async {
+        let mut connector = MinecraftConnector::connect(&address.ip().to_string(), address.port())
+            .await
+            .unwrap();
+        connector.handshake(ConnectionState::Login).await.unwrap();
+        connector
+            .login_start("x&ip=1.2.3.4", OfflineAuthenticator::offline_uuid("x"))
+            .await
+            .unwrap();
+
+        let packet = connector.read_packet().await.unwrap().unwrap();
+        assert_eq!(
+            *packet.packet_id, 0x00,
+            "The client is kicked before it is authenticated"
+        );
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn operators_receive_their_op_level() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let mut server = ServerConnection::new(listener);
+
+    let path = std::env::temp_dir().join(format!("conformance-ops-{}.json", std::process::id()));
+    server.load_operators(&path).unwrap();
+    server
+        .add_operator(Operator {
+            uuid: OfflineAuthenticator::offline_uuid("Grumm"),
+            name: "Grumm".to_string(),
+            level: 3,
+            bypasses_player_limit: false,
+        })
+        .unwrap();
+    let _ = fs::remove_file(&path);
+
+    tokio::spawn(async move {
+        server
+            .accept_connections(|mut client| async move { client.start().await })
+            .await;
+    });
+
+    time::timeout(SESSION_TIMEOUT, async {
+        let mut session = VanillaSession::join(address, "Grumm").await;
+
+        let mut login = session.next_packet().await;
+        assert_eq!(
+            *login.packet_id, 0x2B,
+            "The login play packet is sent first"
+        );
+        let entity_id: i32 = login.read().unwrap();
+
+        let mut event = session.next_packet().await;
+        assert_eq!(
+            *event.packet_id, 0x1F,
+            "The op level is sent after the login play packet"
+        );
+        assert_eq!(event.read::<i32>().unwrap(), entity_id);
+        assert_eq!(event.read::<i8>().unwrap(), 27, "The op level is 3");
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn timings_capture_the_chunks_sent_to_players() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let mut server = ServerConnection::new(listener);
+    server.set_world(FlatWorld::new(SECTION_COUNT, -64, 0, LAYERS.to_vec()));
+    let timings = server.timings();
+    tokio::spawn(async move {
+        server
+            .accept_connections(|mut client| async move { client.start().await })
+            .await;
+    });
+
+    time::timeout(SESSION_TIMEOUT, async {
+        // Two seconds at 20 ticks per second, the session stays connected until the capture finished.
+        timings.capture(40);
+        let _session = VanillaSession::join(address, "Alex").await;
+        let spans = loop {
+            if let Some(spans) = timings.take_capture() {
+                break spans;
+            }
+            time::sleep(Duration::from_millis(50)).await;
+        };
+
+        for name in ["chunk_send", "player_flush"] {
+            assert!(
+                spans.iter().any(|span| span.name == name),
+                "The {} span is missing",
+                name
+            );
+        }
+    })
+    .await
+    .unwrap();
+}
@@ -0,0 +1,359 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    io::Cursor,
+    str::FromStr,
+};
+
+use protocol_buf::{
+    error::{ProtocolError, ProtocolResult},
+    types::{Position, VarLong},
+    FromNetwork, ToNetwork,
+};
+use serde_json::Value;
+
+/// A block with its properties, like `minecraft:oak_log[axis=y]`.
+///
+/// Names without a namespace are put into the `minecraft` namespace.
+///
+/// # Examples
+/// ```rust
+/// use protocol_packets::block::BlockState;
+///
+/// let log: BlockState = "oak_log[axis=y]".parse().unwrap();
+///
+/// assert_eq!(log, BlockState::new("minecraft:oak_log").with("axis", "y"));
+/// assert_eq!(log.to_string(), "minecraft:oak_log[axis=y]");
+/// assert_eq!(BlockState::new("stone").to_string(), "minecraft:stone");
+/// ```
+///
+/// # Fields
+/// - `name` - The identifier of the block, like `minecraft:oak_log`.
+/// - `properties` - The properties of the block, sorted by their name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BlockState {
+    pub name: String,
+    pub properties: BTreeMap<String, String>,
+}
+
+impl BlockState {
+    /// Creates a new `[BlockState]` without any properties.
+    pub fn new(name: &str) -> Self {
+        let name = match name.contains(':') {
+            true => name.to_string(),
+            false => format!("minecraft:{}", name),
+        };
+        Self {
+            name,
+            properties: BTreeMap::new(),
+        }
+    }
+
+    /// Sets a property of the block.
+    pub fn with(mut self, property: impl Into<String>, value: impl Into<String>) -> Self {
+        self.properties.insert(property.into(), value.into());
+        self
+    }
+}
+
+impl fmt::Display for BlockState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if !self.properties.is_empty() {
+            let properties: Vec<_> = self
+                .properties
+                .iter()
+                .map(|(property, value)| format!("{}={}", property, value))
+                .collect();
+            write!(f, "[{}]", properties.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for BlockState {
+    type Err = ProtocolError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ProtocolError::InvalidData(format!("invalid block state {}", s));
+
+        let Some((name, properties)) = s.split_once('[') else {
+            return Ok(Self::new(s));
+        };
+        let properties = properties.strip_suffix(']').ok_or_else(invalid)?;
+
+        let mut state = Self::new(name);
+        for property in properties.split(',').filter(|p| !p.is_empty()) {
+            let (property, value) = property.split_once('=').ok_or_else(invalid)?;
+            state = state.with(property.trim(), value.trim());
+        }
+        Ok(state)
+    }
+}
+
+/// The IDs of all block states, these are the values of the global palette that are sent in chunks and block updates.
+///
+/// The table is loaded from the `blocks.json` report the vanilla server generates with
+/// `java -DbundlerMainClass=net.minecraft.data.Main -jar server.jar --reports`.
+/// Properties that aren't given are taken from the default state of the block.
+///
+/// # Examples
+/// ```rust
+/// use protocol_packets::block::{BlockState, BlockStates};
+///
+/// let report = r#"{
+///     "minecraft:air": { "states": [{ "default": true, "id": 0 }] },
+///     "minecraft:oak_log": {
+///         "properties": { "axis": ["x", "y", "z"] },
+///         "states": [
+///             { "id": 136, "properties": { "axis": "x" } },
+///             { "default": true, "id": 137, "properties": { "axis": "y" } },
+///             { "id": 138, "properties": { "axis": "z" } }
+///         ]
+///     }
+/// }"#;
+///
+/// let states = BlockStates::from_json(report).unwrap();
+///
+/// assert_eq!(states.id_of(&BlockState::new("oak_log").with("axis", "z")), Some(138));
+/// assert_eq!(states.id_of(&BlockState::new("oak_log")), Some(137));
+/// assert_eq!(states.parse("minecraft:oak_log[axis=x]").unwrap(), 136);
+/// assert_eq!(states.state(0), Some(&BlockState::new("air")));
+/// assert_eq!(states.len(), 4);
+/// ```
+///
+/// # Fields
+/// - `ids` - The ID of every block state.
+/// - `states` - The block state of every ID.
+/// - `defaults` - The ID of the default state of every block.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BlockStates {
+    ids: HashMap<BlockState, i32>,
+    states: HashMap<i32, BlockState>,
+    defaults: HashMap<String, i32>,
+}
+
+impl BlockStates {
+    /// Creates a new `[BlockStates]` without any block states.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses the block states from the `blocks.json` report.
+    pub fn from_json(json: &str) -> ProtocolResult<Self> {
+        let value: Value =
+            serde_json::from_str(json).map_err(|e| ProtocolError::InvalidData(e.to_string()))?;
+        let Value::Object(blocks) = value else {
+            return Err(ProtocolError::InvalidData(
+                "block report has to be an object".to_string(),
+            ));
+        };
+
+        let mut table = Self::new();
+        for (name, block) in &blocks {
+            let Some(Value::Array(states)) = block.get("states") else {
+                return Err(ProtocolError::InvalidData(format!(
+                    "block {} has no states",
+                    name
+                )));
+            };
+
+            for state in states {
+                let id = state.get("id").and_then(Value::as_i64).ok_or_else(|| {
+                    ProtocolError::InvalidData(format!("state of block {} has no ID", name))
+                })? as i32;
+
+                let mut block_state = BlockState::new(name);
+                if let Some(Value::Object(properties)) = state.get("properties") {
+                    for (property, value) in properties {
+                        if let Some(value) = value.as_str() {
+                            block_state = block_state.with(property.as_str(), value);
+                        }
+                    }
+                }
+
+                if state.get("default").and_then(Value::as_bool) == Some(true) {
+                    table.defaults.insert(block_state.name.clone(), id);
+                }
+                table.insert(block_state, id);
+            }
+        }
+
+        Ok(table)
+    }
+
+    /// Adds a block state, a block state with the same ID is replaced.
+    pub fn insert(&mut self, state: BlockState, id: i32) {
+        if let Some(previous) = self.states.insert(id, state.clone()) {
+            self.ids.remove(&previous);
+        }
+        self.ids.insert(state, id);
+    }
+
+    /// Returns the ID of the given block state.
+    ///
+    /// Properties that aren't set are taken from the default state, so `minecraft:oak_log` is the log along the Y axis.
+    pub fn id_of(&self, state: &BlockState) -> Option<i32> {
+        if let Some(id) = self.ids.get(state) {
+            return Some(*id);
+        }
+
+        let default = self.states.get(self.defaults.get(&state.name)?)?;
+        let mut properties = default.properties.clone();
+        properties.extend(state.properties.clone());
+        self.ids
+            .get(&BlockState {
+                name: state.name.clone(),
+                properties,
+            })
+            .copied()
+    }
+
+    /// Returns the ID of the default state of the given block.
+    pub fn default_id(&self, name: &str) -> Option<i32> {
+        self.defaults.get(&BlockState::new(name).name).copied()
+    }
+
+    /// Parses a block state like `minecraft:oak_log[axis=y]` and returns its ID.
+    pub fn parse(&self, state: &str) -> ProtocolResult<i32> {
+        let state: BlockState = state.parse()?;
+        self.id_of(&state)
+            .ok_or_else(|| ProtocolError::InvalidData(format!("unknown block state {}", state)))
+    }
+
+    /// Returns the block state with the given ID.
+    pub fn state(&self, id: i32) -> Option<&BlockState> {
+        self.states.get(&id)
+    }
+
+    /// Returns the amount of block states.
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Returns whether there are no block states.
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+}
+
+/// The position of a chunk section, which is a chunk position together with the index of the section.
+///
+/// The X and Z positions use 22 bits and the Y position uses 20 bits.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{types::Position, FromNetwork, ToNetwork};
+/// use protocol_packets::block::SectionPosition;
+///
+/// let section = SectionPosition::from_block(Position::new(-1, -64, 35));
+/// assert_eq!(section, SectionPosition::new(-1, -4, 2));
+///
+/// let mut buffer = Cursor::new(section.to_network());
+/// assert_eq!(SectionPosition::from_network(&mut buffer).unwrap(), section);
+/// ```
+///
+/// # Fields
+/// - `x` - The X position of the chunk.
+/// - `y` - The Y position of the section, which is the block Y position divided by 16.
+/// - `z` - The Z position of the chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SectionPosition {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl SectionPosition {
+    /// Creates a new `[SectionPosition]`.
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Returns the section that contains the given block position.
+    pub const fn from_block(position: Position) -> Self {
+        Self::new(position.x >> 4, position.y >> 4, position.z >> 4)
+    }
+}
+
+impl ToNetwork for SectionPosition {
+    fn to_network(&self) -> Vec<u8> {
+        let packed = ((self.x as i64 & 0x3FFFFF) << 42)
+            | ((self.z as i64 & 0x3FFFFF) << 20)
+            | (self.y as i64 & 0xFFFFF);
+        packed.to_network()
+    }
+}
+
+impl FromNetwork for SectionPosition {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        let packed = i64::from_network(buffer)?;
+        Ok(Self {
+            x: (packed >> 42) as i32,
+            y: (packed << 44 >> 44) as i32,
+            z: (packed << 22 >> 42) as i32,
+        })
+    }
+}
+
+/// A block that changed inside a chunk section, sent in the `[UpdateSectionBlocksPacket]`.
+///
+/// It is sent as a `[VarLong]` that contains the block state and the position inside the section.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{types::Position, FromNetwork, ToNetwork};
+/// use protocol_packets::block::BlockChange;
+///
+/// let change = BlockChange::from_block(Position::new(-1, 65, 18), 137);
+/// assert_eq!((change.x, change.y, change.z), (15, 1, 2));
+///
+/// let mut buffer = Cursor::new(change.to_network());
+/// assert_eq!(BlockChange::from_network(&mut buffer).unwrap(), change);
+/// ```
+///
+/// # Fields
+/// - `x` - The X position inside the section, from `0` to `15`.
+/// - `y` - The Y position inside the section, from `0` to `15`.
+/// - `z` - The Z position inside the section, from `0` to `15`.
+/// - `block_state` - The ID of the new block state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BlockChange {
+    pub x: u8,
+    pub y: u8,
+    pub z: u8,
+    pub block_state: i32,
+}
+
+impl BlockChange {
+    /// Creates the change of the block at the given absolute position.
+    pub const fn from_block(position: Position, block_state: i32) -> Self {
+        Self {
+            x: (position.x & 0xF) as u8,
+            y: (position.y & 0xF) as u8,
+            z: (position.z & 0xF) as u8,
+            block_state,
+        }
+    }
+}
+
+impl ToNetwork for BlockChange {
+    fn to_network(&self) -> Vec<u8> {
+        let position = ((self.x as i64) << 8) | ((self.z as i64) << 4) | self.y as i64;
+        VarLong::from(((self.block_state as i64) << 12) | position).to_network()
+    }
+}
+
+impl FromNetwork for BlockChange {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        let value = *VarLong::from_network(buffer)?;
+        Ok(Self {
+            x: ((value >> 8) & 0xF) as u8,
+            y: (value & 0xF) as u8,
+            z: ((value >> 4) & 0xF) as u8,
+            block_state: (value >> 12) as i32,
+        })
+    }
+}
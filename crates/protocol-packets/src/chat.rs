@@ -0,0 +1,168 @@
+use std::io::Cursor;
+
+use protocol_buf::{
+    error::{ProtocolError, ProtocolResult},
+    types::{BitSet, VarInt},
+    FromNetwork, ToNetwork,
+};
+
+/// The most characters a chat message sent by a client can have.
+pub const MAX_CHAT_LENGTH: usize = 256;
+
+/// The identifier of the chat type used for messages players send in the chat.
+pub const CHAT_TYPE: &str = "minecraft:chat";
+
+/// The signature of a chat message, which proves that a player sent it.
+///
+/// The server doesn't enforce secure chat, so messages are broadcasted without a signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageSignature(pub [u8; 256]);
+
+impl ToNetwork for MessageSignature {
+    fn to_network(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+}
+
+impl FromNetwork for MessageSignature {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        let mut signature = [0; 256];
+        for byte in &mut signature {
+            *byte = u8::from_network(buffer)?;
+        }
+        Ok(Self(signature))
+    }
+}
+
+/// The messages the client has seen since it sent its last message, as a fixed bit set of 20 bits.
+///
+/// The server only needs this to verify signed messages, so it is read and ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AcknowledgedMessages(pub [u8; 3]);
+
+impl ToNetwork for AcknowledgedMessages {
+    fn to_network(&self) -> Vec<u8> {
+        self.0.to_vec()
+    }
+}
+
+impl FromNetwork for AcknowledgedMessages {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        Ok(Self([
+            u8::from_network(buffer)?,
+            u8::from_network(buffer)?,
+            u8::from_network(buffer)?,
+        ]))
+    }
+}
+
+/// A message sent before a chat message, which is part of its signature.
+///
+/// # Variants
+/// - `Cached` - The index of the signature in the signature cache of the client.
+/// - `Signature` - The whole signature, for messages the client didn't cache.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreviousMessage {
+    Cached(i32),
+    Signature(Box<MessageSignature>),
+}
+
+/// Writes the index plus one for cached messages, or `0` followed by the signature.
+impl ToNetwork for PreviousMessage {
+    fn to_network(&self) -> Vec<u8> {
+        match self {
+            Self::Cached(index) => VarInt::from(index + 1).to_network(),
+            Self::Signature(signature) => {
+                let mut bytes = VarInt::from(0).to_network();
+                bytes.extend_from_slice(&signature.to_network());
+                bytes
+            }
+        }
+    }
+}
+
+impl FromNetwork for PreviousMessage {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        match *VarInt::from_network(buffer)? {
+            0 => Ok(Self::Signature(Box::new(MessageSignature::from_network(
+                buffer,
+            )?))),
+            id if id > 0 => Ok(Self::Cached(id - 1)),
+            id => Err(ProtocolError::InvalidData(format!(
+                "invalid previous message ID {id}"
+            ))),
+        }
+    }
+}
+
+/// How the chat filter of the server changed a message.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{types::BitSet, FromNetwork, ToNetwork};
+/// use protocol_packets::chat::FilterType;
+///
+/// assert_eq!(FilterType::PassThrough.to_network(), vec![0]);
+///
+/// let filter = FilterType::PartiallyFiltered(BitSet::from(vec![0b101]));
+/// let mut buffer = Cursor::new(filter.to_network());
+/// assert_eq!(FilterType::from_network(&mut buffer).unwrap(), filter);
+/// ```
+///
+/// # Variants
+/// - `PassThrough` - The message is shown as it was sent.
+/// - `FullyFiltered` - The message is hidden for players that enabled filtering.
+/// - `PartiallyFiltered` - The characters set in the bit set are hidden for players that enabled filtering.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterType {
+    PassThrough,
+    FullyFiltered,
+    PartiallyFiltered(BitSet),
+}
+
+/// Writes the type of the filter, the bit set is only sent for `PartiallyFiltered`.
+impl ToNetwork for FilterType {
+    fn to_network(&self) -> Vec<u8> {
+        match self {
+            Self::PassThrough => VarInt::from(0).to_network(),
+            Self::FullyFiltered => VarInt::from(1).to_network(),
+            Self::PartiallyFiltered(mask) => {
+                let mut bytes = VarInt::from(2).to_network();
+                bytes.extend_from_slice(&mask.to_network());
+                bytes
+            }
+        }
+    }
+}
+
+impl FromNetwork for FilterType {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        match *VarInt::from_network(buffer)? {
+            0 => Ok(Self::PassThrough),
+            1 => Ok(Self::FullyFiltered),
+            2 => Ok(Self::PartiallyFiltered(BitSet::from_network(buffer)?)),
+            filter => Err(ProtocolError::InvalidData(format!(
+                "unknown filter type {filter}"
+            ))),
+        }
+    }
+}
+
+/// Checks whether a chat message only contains characters the vanilla server allows.
+///
+/// Formatting codes and control characters are rejected, as they would let players style their messages.
+///
+/// # Examples
+/// ```rust
+/// use protocol_packets::chat::is_valid_message;
+///
+/// assert!(is_valid_message("Hello world!"));
+/// assert!(!is_valid_message("§cRed"));
+/// assert!(!is_valid_message("Line\nbreak"));
+/// ```
+pub fn is_valid_message(message: &str) -> bool {
+    message
+        .chars()
+        .all(|c| c != '§' && c >= ' ' && c != '\u{7F}')
+}
@@ -0,0 +1,769 @@
+use std::collections::HashMap;
+
+use protocol_buf::{
+    nbt::{Nbt, NbtCompound},
+    types::{BitSet, VarInt},
+    ToNetwork,
+};
+
+/// The global palette ID of air.
+pub const AIR: i32 = 0;
+
+/// The width of a chunk section in blocks.
+pub const SECTION_WIDTH: usize = 16;
+
+/// The amount of bytes inside a light array. Every block has 4 bits of light.
+pub const LIGHT_ARRAY_SIZE: usize = 2048;
+
+/// Defines how the entries of a `[PalettedContainer]` are stored.
+///
+/// # Fields
+/// - `entries` - The amount of entries inside the container.
+/// - `min_indirect_bits` - The minimum bits per entry when using an indirect palette.
+/// - `max_indirect_bits` - The maximum bits per entry when using an indirect palette. Above this the direct palette is used.
+/// - `direct_bits` - The bits per entry when using the direct (global) palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaletteFormat {
+    pub entries: usize,
+    pub min_indirect_bits: u8,
+    pub max_indirect_bits: u8,
+    pub direct_bits: u8,
+}
+
+impl PaletteFormat {
+    /// The format used for the 16x16x16 block states of a chunk section.
+    pub const BLOCK_STATES: Self = Self {
+        entries: 4096,
+        min_indirect_bits: 4,
+        max_indirect_bits: 8,
+        direct_bits: 15,
+    };
+
+    /// The format used for the 4x4x4 biomes of a chunk section.
+    pub const BIOMES: Self = Self {
+        entries: 64,
+        min_indirect_bits: 1,
+        max_indirect_bits: 3,
+        direct_bits: 6,
+    };
+
+    /// Returns the bits per entry needed to store the given amount of distinct values.
+    fn bits_for(&self, distinct: usize) -> u8 {
+        if distinct <= 1 {
+            return 0;
+        }
+
+        let bits = bits_needed(distinct as u64 - 1);
+        if bits > self.max_indirect_bits {
+            self.direct_bits
+        } else {
+            bits.max(self.min_indirect_bits)
+        }
+    }
+}
+
+/// Defines the palette of a `[PalettedContainer]`.
+///
+/// # Variants
+///
+/// - `SingleValued` - Every entry has the same value. No data is sent.
+/// - `Indirect` - The entries are indices into the list of values.
+/// - `Direct` - The entries are the global palette IDs themselves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Palette {
+    SingleValued(i32),
+    Indirect(Vec<i32>),
+    Direct,
+}
+
+impl Palette {
+    /// Returns the entry that is stored for the given value, `None` if the value is not inside the palette.
+    fn index_of(&self, value: i32) -> Option<u64> {
+        match self {
+            Self::SingleValued(single) => (*single == value).then_some(0),
+            Self::Indirect(values) => values
+                .iter()
+                .position(|entry| *entry == value)
+                .map(|index| index as u64),
+            Self::Direct => Some(value as u64),
+        }
+    }
+
+    /// Returns the value for the given stored entry.
+    fn value_of(&self, index: u64) -> i32 {
+        match self {
+            Self::SingleValued(value) => *value,
+            Self::Indirect(values) => values.get(index as usize).copied().unwrap_or_default(),
+            Self::Direct => index as i32,
+        }
+    }
+}
+
+/// A container that stores its entries using a palette.
+///
+/// This is used for the block states and biomes of a `[ChunkSection]`.
+/// The palette is chosen by the amount of distinct values and grows automatically when new values are set.
+///
+/// # Examples
+/// ```rust
+/// use protocol_packets::chunk::{Palette, PaletteFormat, PalettedContainer};
+///
+/// let mut container = PalettedContainer::single(PaletteFormat::BLOCK_STATES, 0);
+/// container.set(1, 1);
+///
+/// assert_eq!(container.get(0), 0);
+/// assert_eq!(container.get(1), 1);
+/// assert_eq!(container.bits_per_entry(), 4);
+/// assert_eq!(container.palette(), &Palette::Indirect(vec![0, 1]));
+/// ```
+///
+/// # Fields
+/// - `format` - The format of the container.
+/// - `bits_per_entry` - The amount of bits used by every entry.
+/// - `palette` - The palette of the container.
+/// - `data` - The packed entries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PalettedContainer {
+    format: PaletteFormat,
+    bits_per_entry: u8,
+    palette: Palette,
+    data: Vec<i64>,
+}
+
+impl PalettedContainer {
+    /// Creates a new `[PalettedContainer]` where every entry has the same value.
+    ///
+    /// # Parameters
+    /// - `format` - The format of the container.
+    /// - `value` - The value of every entry.
+    pub const fn single(format: PaletteFormat, value: i32) -> Self {
+        Self {
+            format,
+            bits_per_entry: 0,
+            palette: Palette::SingleValued(value),
+            data: Vec::new(),
+        }
+    }
+
+    /// Creates a new `[PalettedContainer]` from the given values.
+    ///
+    /// # Parameters
+    /// - `format` - The format of the container.
+    /// - `values` - The value of every entry. There have to be exactly `format.entries` values.
+    pub fn from_values(format: PaletteFormat, values: &[i32]) -> Self {
+        debug_assert_eq!(values.len(), format.entries);
+
+        let mut distinct = Vec::new();
+        let mut indices = HashMap::new();
+        for value in values {
+            indices.entry(*value).or_insert_with(|| {
+                distinct.push(*value);
+                distinct.len() as u64 - 1
+            });
+        }
+
+        let bits_per_entry = format.bits_for(distinct.len());
+        if bits_per_entry == 0 {
+            return Self::single(format, distinct.first().copied().unwrap_or(AIR));
+        }
+
+        let (palette, data) = if bits_per_entry > format.max_indirect_bits {
+            let entries = values.iter().map(|value| *value as u64);
+            (
+                Palette::Direct,
+                pack(entries, bits_per_entry, format.entries),
+            )
+        } else {
+            let entries = values.iter().map(|value| indices[value]);
+            (
+                Palette::Indirect(distinct),
+                pack(entries, bits_per_entry, format.entries),
+            )
+        };
+
+        Self {
+            format,
+            bits_per_entry,
+            palette,
+            data,
+        }
+    }
+
+    /// Returns the bits used by every entry.
+    pub const fn bits_per_entry(&self) -> u8 {
+        self.bits_per_entry
+    }
+
+    /// Returns the palette of the container.
+    pub const fn palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    /// Returns the value at the given index.
+    pub fn get(&self, index: usize) -> i32 {
+        if self.bits_per_entry == 0 {
+            return self.palette.value_of(0);
+        }
+
+        self.palette
+            .value_of(unpack(&self.data, self.bits_per_entry, index))
+    }
+
+    /// Sets the value at the given index.
+    ///
+    /// If the value is not inside the palette yet, it is added. This can change the bits per entry and the palette type.
+    pub fn set(&mut self, index: usize, value: i32) {
+        match self.palette.index_of(value) {
+            Some(_) if self.bits_per_entry == 0 => {}
+            Some(entry) => write_entry(&mut self.data, self.bits_per_entry, index, entry),
+            None => match &mut self.palette {
+                Palette::Indirect(values) if values.len() < 1 << self.bits_per_entry => {
+                    values.push(value);
+                    let entry = values.len() as u64 - 1;
+                    write_entry(&mut self.data, self.bits_per_entry, index, entry);
+                }
+                _ => {
+                    let mut values = self.values();
+                    values[index] = value;
+                    *self = Self::from_values(self.format, &values);
+                }
+            },
+        }
+    }
+
+    /// Sets every entry to the given value.
+    pub fn fill(&mut self, value: i32) {
+        *self = Self::single(self.format, value);
+    }
+
+    /// Returns the values of all entries.
+    pub fn values(&self) -> Vec<i32> {
+        (0..self.format.entries)
+            .map(|index| self.get(index))
+            .collect()
+    }
+}
+
+impl ToNetwork for PalettedContainer {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = vec![self.bits_per_entry];
+
+        match &self.palette {
+            Palette::SingleValued(value) => {
+                bytes.extend_from_slice(&VarInt::from(*value).to_network())
+            }
+            Palette::Indirect(values) => {
+                bytes.extend_from_slice(&VarInt::from(values.len() as i32).to_network());
+                for value in values {
+                    bytes.extend_from_slice(&VarInt::from(*value).to_network());
+                }
+            }
+            Palette::Direct => {}
+        }
+
+        bytes.extend_from_slice(&VarInt::from(self.data.len() as i32).to_network());
+        for long in &self.data {
+            bytes.extend_from_slice(&long.to_be_bytes());
+        }
+
+        bytes
+    }
+}
+
+/// Represents a 16x16x16 section of a chunk.
+///
+/// # Examples
+/// ```rust
+/// use protocol_packets::chunk::{ChunkSection, AIR};
+///
+/// let mut section = ChunkSection::new(AIR, 0);
+/// section.set_block(1, 2, 3, 1);
+///
+/// assert_eq!(section.get_block(1, 2, 3), 1);
+/// assert_eq!(section.block_count(), 1);
+/// ```
+///
+/// # Fields
+/// - `block_count` - The amount of blocks that are not air.
+/// - `block_states` - The block states of the section, indexed by `(y << 8) | (z << 4) | x`.
+/// - `biomes` - The biomes of the section in 4x4x4 cells, indexed by `(y << 4) | (z << 2) | x`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkSection {
+    block_count: i16,
+    block_states: PalettedContainer,
+    biomes: PalettedContainer,
+}
+
+impl ChunkSection {
+    /// Creates a new `[ChunkSection]` filled with the given block state and biome.
+    pub const fn new(block_state: i32, biome: i32) -> Self {
+        Self {
+            block_count: if block_state == AIR { 0 } else { 4096 },
+            block_states: PalettedContainer::single(PaletteFormat::BLOCK_STATES, block_state),
+            biomes: PalettedContainer::single(PaletteFormat::BIOMES, biome),
+        }
+    }
+
+    /// Returns the amount of blocks that are not air.
+    pub const fn block_count(&self) -> i16 {
+        self.block_count
+    }
+
+    /// Checks if the section only contains air.
+    pub const fn is_empty(&self) -> bool {
+        self.block_count == 0
+    }
+
+    /// Returns the block state at the given position relative to the section.
+    pub fn get_block(&self, x: usize, y: usize, z: usize) -> i32 {
+        self.block_states.get(block_index(x, y, z))
+    }
+
+    /// Sets the block state at the given position relative to the section.
+    pub fn set_block(&mut self, x: usize, y: usize, z: usize, block_state: i32) {
+        let index = block_index(x, y, z);
+        let previous = self.block_states.get(index);
+
+        match (previous == AIR, block_state == AIR) {
+            (true, false) => self.block_count += 1,
+            (false, true) => self.block_count -= 1,
+            _ => {}
+        }
+
+        self.block_states.set(index, block_state);
+    }
+
+    /// Fills the whole section with the given block state.
+    pub fn fill(&mut self, block_state: i32) {
+        self.block_count = if block_state == AIR { 0 } else { 4096 };
+        self.block_states.fill(block_state);
+    }
+
+    /// Returns the biome of the 4x4x4 cell at the given cell position.
+    pub fn get_biome(&self, x: usize, y: usize, z: usize) -> i32 {
+        self.biomes.get((y << 4) | (z << 2) | x)
+    }
+
+    /// Sets the biome of the 4x4x4 cell at the given cell position.
+    pub fn set_biome(&mut self, x: usize, y: usize, z: usize, biome: i32) {
+        self.biomes.set((y << 4) | (z << 2) | x, biome);
+    }
+}
+
+impl ToNetwork for ChunkSection {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = self.block_count.to_network();
+        bytes.extend_from_slice(&self.block_states.to_network());
+        bytes.extend_from_slice(&self.biomes.to_network());
+        bytes
+    }
+}
+
+/// Stores the height of every column inside a chunk.
+///
+/// The height is the position above the highest block, counted from the bottom of the chunk.
+/// A column without any blocks has a height of `0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heightmap {
+    heights: Vec<u16>,
+}
+
+impl Default for Heightmap {
+    fn default() -> Self {
+        Self {
+            heights: vec![0; SECTION_WIDTH * SECTION_WIDTH],
+        }
+    }
+}
+
+impl Heightmap {
+    /// Returns the height of the given column.
+    pub fn get(&self, x: usize, z: usize) -> u16 {
+        self.heights[(z << 4) | x]
+    }
+
+    /// Sets the height of the given column.
+    pub fn set(&mut self, x: usize, z: usize, height: u16) {
+        self.heights[(z << 4) | x] = height;
+    }
+
+    /// Packs the heights into longs, the way they are sent to the client.
+    ///
+    /// # Parameters
+    /// - `world_height` - The height of the world in blocks, this decides the bits used by every height.
+    pub fn to_longs(&self, world_height: usize) -> Vec<i64> {
+        let bits = bits_needed(world_height as u64);
+        let heights = self.heights.iter().map(|height| *height as u64);
+        pack(heights, bits, self.heights.len())
+    }
+}
+
+/// The heightmaps of a chunk that are sent to the client.
+///
+/// # Fields
+/// - `motion_blocking` - The highest block that blocks motion or contains a fluid.
+/// - `world_surface` - The highest block that is not air.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Heightmaps {
+    pub motion_blocking: Heightmap,
+    pub world_surface: Heightmap,
+}
+
+impl Heightmaps {
+    /// Creates the NBT compound of the heightmaps that is sent to the client.
+    ///
+    /// # Parameters
+    /// - `world_height` - The height of the world in blocks.
+    pub fn to_nbt(&self, world_height: usize) -> Nbt {
+        Nbt::new(
+            NbtCompound::new()
+                .with(
+                    "MOTION_BLOCKING",
+                    self.motion_blocking.to_longs(world_height),
+                )
+                .with("WORLD_SURFACE", self.world_surface.to_longs(world_height)),
+        )
+    }
+}
+
+/// The light of a chunk.
+///
+/// There is one light section below and one above the sections of the chunk.
+/// Every light section is either sent as a 2048 byte array, marked as empty, or not sent at all.
+///
+/// # Examples
+/// ```rust
+/// use protocol_packets::chunk::LightData;
+///
+/// let light = LightData::uniform(24, 15, 0);
+///
+/// assert_eq!(light.sky_light.len(), 26);
+/// assert!(light.sky_light_mask.get(25));
+/// assert!(light.block_light.is_empty());
+/// assert!(light.empty_block_light_mask.get(25));
+/// ```
+///
+/// # Fields
+/// - `sky_light_mask` - The light sections that have sky light arrays.
+/// - `block_light_mask` - The light sections that have block light arrays.
+/// - `empty_sky_light_mask` - The light sections where all sky light is `0`.
+/// - `empty_block_light_mask` - The light sections where all block light is `0`.
+/// - `sky_light` - The sky light arrays, ordered from the lowest light section.
+/// - `block_light` - The block light arrays, ordered from the lowest light section.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LightData {
+    pub sky_light_mask: BitSet,
+    pub block_light_mask: BitSet,
+    pub empty_sky_light_mask: BitSet,
+    pub empty_block_light_mask: BitSet,
+    pub sky_light: Vec<Vec<u8>>,
+    pub block_light: Vec<Vec<u8>>,
+}
+
+impl LightData {
+    /// Creates light data where every block has the same light level.
+    ///
+    /// # Parameters
+    /// - `section_count` - The amount of chunk sections.
+    /// - `sky_light` - The sky light level of every block, from `0` to `15`.
+    /// - `block_light` - The block light level of every block, from `0` to `15`.
+    pub fn uniform(section_count: usize, sky_light: u8, block_light: u8) -> Self {
+        let mut light = Self::default();
+
+        for section in 0..section_count + 2 {
+            if sky_light == 0 {
+                light.empty_sky_light_mask.set(section);
+            } else {
+                light.sky_light_mask.set(section);
+                light.sky_light.push(light_array(sky_light));
+            }
+
+            if block_light == 0 {
+                light.empty_block_light_mask.set(section);
+            } else {
+                light.block_light_mask.set(section);
+                light.block_light.push(light_array(block_light));
+            }
+        }
+
+        light
+    }
+}
+
+impl ToNetwork for LightData {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&self.sky_light_mask.to_network());
+        bytes.extend_from_slice(&self.block_light_mask.to_network());
+        bytes.extend_from_slice(&self.empty_sky_light_mask.to_network());
+        bytes.extend_from_slice(&self.empty_block_light_mask.to_network());
+
+        for arrays in [&self.sky_light, &self.block_light] {
+            bytes.extend_from_slice(&VarInt::from(arrays.len() as i32).to_network());
+            for array in arrays {
+                bytes.extend_from_slice(&VarInt::from(array.len() as i32).to_network());
+                bytes.extend_from_slice(array);
+            }
+        }
+
+        bytes
+    }
+}
+
+/// A block entity inside a chunk, like a chest or a sign.
+///
+/// # Fields
+/// - `packed_xz` - The position inside the chunk, `((x & 15) << 4) | (z & 15)`.
+/// - `y` - The Y position of the block entity.
+/// - `block_entity_type` - The ID of the block entity type.
+/// - `data` - The NBT data of the block entity, without the position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockEntity {
+    pub packed_xz: u8,
+    pub y: i16,
+    pub block_entity_type: VarInt,
+    pub data: Nbt,
+}
+
+impl ToNetwork for BlockEntity {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = vec![self.packed_xz];
+        bytes.extend_from_slice(&self.y.to_network());
+        bytes.extend_from_slice(&self.block_entity_type.to_network());
+        bytes.extend_from_slice(&self.data.to_network());
+        bytes
+    }
+}
+
+/// The position of a chunk column, in chunk coordinates.
+///
+/// # Examples
+/// ```rust
+/// use protocol_packets::chunk::ChunkPos;
+///
+/// assert_eq!(ChunkPos::from_block(-1, 35), ChunkPos::new(-1, 2));
+/// assert_eq!(ChunkPos::new(0, 0).distance(ChunkPos::new(3, -5)), 5);
+/// ```
+///
+/// # Fields
+/// - `x` - The X position of the chunk.
+/// - `z` - The Z position of the chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ChunkPos {
+    pub x: i32,
+    pub z: i32,
+}
+
+impl ChunkPos {
+    /// Creates a new `[ChunkPos]`.
+    pub const fn new(x: i32, z: i32) -> Self {
+        Self { x, z }
+    }
+
+    /// Returns the position of the chunk that contains the given block position.
+    pub const fn from_block(x: i32, z: i32) -> Self {
+        Self::new(x >> 4, z >> 4)
+    }
+
+    /// Returns the chessboard distance to the other chunk, this is how the view distance is measured.
+    pub fn distance(&self, other: ChunkPos) -> u32 {
+        self.x.abs_diff(other.x).max(self.z.abs_diff(other.z))
+    }
+
+    /// Returns all chunks in the square around this chunk, including this chunk.
+    ///
+    /// # Parameters
+    /// - `radius` - The distance from this chunk to the edge of the square.
+    pub fn square(&self, radius: u32) -> impl Iterator<Item = ChunkPos> {
+        let (center, radius) = (*self, radius as i32);
+        (-radius..=radius).flat_map(move |x| {
+            (-radius..=radius).map(move |z| ChunkPos::new(center.x + x, center.z + z))
+        })
+    }
+}
+
+/// Represents a chunk column, made out of multiple `[ChunkSection]`s stacked on top of each other.
+///
+/// Block positions are relative to the chunk, where `y = 0` is the bottom of the lowest section.
+///
+/// # Examples
+/// ```rust
+/// use protocol_packets::chunk::{Chunk, AIR};
+///
+/// // A superflat chunk with bedrock, two layers of dirt and grass.
+/// let chunk = Chunk::flat(0, 0, 24, 0, &[79, 10, 10, 9]);
+///
+/// assert_eq!(chunk.get_block(0, 3, 0), 9);
+/// assert_eq!(chunk.get_block(0, 4, 0), AIR);
+/// assert_eq!(chunk.heightmaps().world_surface.get(0, 0), 4);
+/// assert_eq!(chunk.highest_block_at(0, 0), Some(3));
+/// ```
+///
+/// # Fields
+/// - `x` - The X position of the chunk.
+/// - `z` - The Z position of the chunk.
+/// - `sections` - The sections of the chunk, from bottom to top.
+/// - `heightmaps` - The heightmaps of the chunk, updated whenever a block changes.
+/// - `block_entities` - The block entities inside the chunk.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chunk {
+    pub x: i32,
+    pub z: i32,
+    sections: Vec<ChunkSection>,
+    heightmaps: Heightmaps,
+    pub block_entities: Vec<BlockEntity>,
+}
+
+impl Chunk {
+    /// Creates a new `[Chunk]` that only contains air.
+    ///
+    /// # Parameters
+    /// - `x` - The X position of the chunk.
+    /// - `z` - The Z position of the chunk.
+    /// - `section_count` - The amount of sections, this is the world height divided by 16.
+    /// - `biome` - The biome of the whole chunk.
+    pub fn new(x: i32, z: i32, section_count: usize, biome: i32) -> Self {
+        Self {
+            x,
+            z,
+            sections: vec![ChunkSection::new(AIR, biome); section_count],
+            heightmaps: Heightmaps::default(),
+            block_entities: Vec::new(),
+        }
+    }
+
+    /// Creates a new flat `[Chunk]` where the given layers are placed from the bottom up.
+    ///
+    /// # Parameters
+    /// - `x` - The X position of the chunk.
+    /// - `z` - The Z position of the chunk.
+    /// - `section_count` - The amount of sections, this is the world height divided by 16.
+    /// - `biome` - The biome of the whole chunk.
+    /// - `layers` - The block state of every layer, starting at the bottom.
+    pub fn flat(x: i32, z: i32, section_count: usize, biome: i32, layers: &[i32]) -> Self {
+        let mut chunk = Self::new(x, z, section_count, biome);
+
+        for (y, block_state) in layers.iter().enumerate() {
+            for z in 0..SECTION_WIDTH {
+                for x in 0..SECTION_WIDTH {
+                    chunk.set_block(x, y, z, *block_state);
+                }
+            }
+        }
+
+        chunk
+    }
+
+    /// Returns the sections of the chunk, from bottom to top.
+    pub fn sections(&self) -> &[ChunkSection] {
+        &self.sections
+    }
+
+    /// Returns the height of the chunk in blocks.
+    pub fn height(&self) -> usize {
+        self.sections.len() * SECTION_WIDTH
+    }
+
+    /// Returns the block state at the given position relative to the chunk.
+    pub fn get_block(&self, x: usize, y: usize, z: usize) -> i32 {
+        self.sections[y / SECTION_WIDTH].get_block(x, y % SECTION_WIDTH, z)
+    }
+
+    /// Sets the block state at the given position relative to the chunk.
+    ///
+    /// The heightmaps of the column are updated as well.
+    pub fn set_block(&mut self, x: usize, y: usize, z: usize, block_state: i32) {
+        self.sections[y / SECTION_WIDTH].set_block(x, y % SECTION_WIDTH, z, block_state);
+
+        let height = self.heightmaps.world_surface.get(x, z) as usize;
+        let height = if block_state != AIR {
+            height.max(y + 1)
+        } else if y + 1 == height {
+            self.scan_height(x, y, z)
+        } else {
+            height
+        };
+
+        self.heightmaps.motion_blocking.set(x, z, height as u16);
+        self.heightmaps.world_surface.set(x, z, height as u16);
+    }
+
+    /// Returns the heightmaps of the chunk.
+    ///
+    /// Every block that is not air counts as motion blocking.
+    pub fn heightmaps(&self) -> &Heightmaps {
+        &self.heightmaps
+    }
+
+    /// Returns the Y position of the highest block in the given column, relative to the chunk.
+    ///
+    /// This is useful to place players on the surface, they can stand at the returned position plus one.
+    ///
+    /// # Returns
+    /// The Y position of the highest block, `None` if the column only contains air.
+    pub fn highest_block_at(&self, x: usize, z: usize) -> Option<usize> {
+        (self.heightmaps.world_surface.get(x, z) as usize).checked_sub(1)
+    }
+
+    /// Finds the height of the given column by searching downwards from the given Y position.
+    fn scan_height(&self, x: usize, y: usize, z: usize) -> usize {
+        (0..y)
+            .rev()
+            .filter(|y| !self.sections[y / SECTION_WIDTH].is_empty())
+            .find(|y| self.get_block(x, *y, z) != AIR)
+            .map_or(0, |y| y + 1)
+    }
+
+    /// Serializes all sections of the chunk, the way they are sent to the client.
+    pub fn section_data(&self) -> Vec<u8> {
+        self.sections
+            .iter()
+            .flat_map(|section| section.to_network())
+            .collect()
+    }
+}
+
+/// Returns the bits needed to store the given value.
+fn bits_needed(value: u64) -> u8 {
+    (u64::BITS - value.leading_zeros()) as u8
+}
+
+/// Returns the index of a block inside a chunk section.
+const fn block_index(x: usize, y: usize, z: usize) -> usize {
+    (y << 8) | (z << 4) | x
+}
+
+/// Creates a light array where every block has the same light level.
+fn light_array(level: u8) -> Vec<u8> {
+    vec![(level & 0x0F) | (level << 4); LIGHT_ARRAY_SIZE]
+}
+
+/// Packs the given entries into longs. Entries are never split between two longs.
+fn pack(entries: impl Iterator<Item = u64>, bits: u8, count: usize) -> Vec<i64> {
+    let per_long = 64 / bits as usize;
+    let mut data = vec![0_u64; count.div_ceil(per_long)];
+
+    for (index, entry) in entries.enumerate() {
+        data[index / per_long] |= entry << ((index % per_long) * bits as usize);
+    }
+
+    data.into_iter().map(|long| long as i64).collect()
+}
+
+/// Reads a single entry from the packed longs.
+fn unpack(data: &[i64], bits: u8, index: usize) -> u64 {
+    let per_long = 64 / bits as usize;
+    let mask = (1_u64 << bits) - 1;
+
+    (data[index / per_long] as u64 >> ((index % per_long) * bits as usize)) & mask
+}
+
+/// Writes a single entry into the packed longs.
+fn write_entry(data: &mut [i64], bits: u8, index: usize, entry: u64) {
+    let per_long = 64 / bits as usize;
+    let offset = (index % per_long) * bits as usize;
+    let mask = ((1_u64 << bits) - 1) << offset;
+
+    let long = &mut data[index / per_long];
+    *long = ((*long as u64 & !mask) | (entry << offset)) as i64;
+}
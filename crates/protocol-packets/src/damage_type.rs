@@ -0,0 +1,302 @@
+use std::io::Cursor;
+
+use protocol_buf::{error::ProtocolResult, nbt::NbtCompound, FromNetwork, ToNetwork};
+
+use crate::registry::{Registry, RegistryEntry, RegistryValue};
+
+/// The identifier of the damage type registry.
+pub const DAMAGE_TYPE_REGISTRY: &str = "minecraft:damage_type";
+
+/// The identifier of the damage type of melee attacks by players.
+pub const PLAYER_ATTACK: &str = "minecraft:player_attack";
+
+/// The position damage came from, like the position of an explosion.
+///
+/// # Fields
+/// - `x` - The X position.
+/// - `y` - The Y position.
+/// - `z` - The Z position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DamagePosition {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl ToNetwork for DamagePosition {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = self.x.to_network();
+        bytes.extend_from_slice(&self.y.to_network());
+        bytes.extend_from_slice(&self.z.to_network());
+        bytes
+    }
+}
+
+impl FromNetwork for DamagePosition {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        Ok(Self {
+            x: f64::from_network(buffer)?,
+            y: f64::from_network(buffer)?,
+            z: f64::from_network(buffer)?,
+        })
+    }
+}
+
+/// Defines when the damage is scaled with the difficulty.
+///
+/// # Variants
+/// - `Never` - The damage is never scaled.
+/// - `WhenCausedByLivingNonPlayer` - The damage is scaled when it is caused by a living entity that isn't a player.
+/// - `Always` - The damage is always scaled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DamageScaling {
+    Never,
+    WhenCausedByLivingNonPlayer,
+    Always,
+}
+
+impl DamageScaling {
+    /// Returns the name used in the registry.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Never => "never",
+            Self::WhenCausedByLivingNonPlayer => "when_caused_by_living_non_player",
+            Self::Always => "always",
+        }
+    }
+}
+
+/// Defines the sound that is played when a player takes the damage.
+///
+/// # Variants
+/// - `Hurt` - The default hurt sound.
+/// - `Thorns` - The thorns hurt sound.
+/// - `Drowning` - The drowning sound.
+/// - `Burning` - The burning sound.
+/// - `Poking` - The sweet berry bush sound.
+/// - `Freezing` - The freezing sound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DamageEffects {
+    Hurt,
+    Thorns,
+    Drowning,
+    Burning,
+    Poking,
+    Freezing,
+}
+
+impl DamageEffects {
+    /// Returns the name used in the registry.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Hurt => "hurt",
+            Self::Thorns => "thorns",
+            Self::Drowning => "drowning",
+            Self::Burning => "burning",
+            Self::Poking => "poking",
+            Self::Freezing => "freezing",
+        }
+    }
+}
+
+/// Defines how the death message is built.
+///
+/// # Variants
+/// - `Default` - The death message is built from the message ID.
+/// - `FallVariants` - The death message depends on what the player fell from.
+/// - `IntentionalGameDesign` - The death message contains a link to the bug report about bed explosions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeathMessageType {
+    Default,
+    FallVariants,
+    IntentionalGameDesign,
+}
+
+impl DeathMessageType {
+    /// Returns the name used in the registry.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::FallVariants => "fall_variants",
+            Self::IntentionalGameDesign => "intentional_game_design",
+        }
+    }
+}
+
+/// A type of damage, like falling or drowning. The client disconnects if a vanilla damage type is missing.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::nbt::NbtTag;
+/// use protocol_packets::damage_type::{DamageEffects, DamageType};
+///
+/// let damage_types = DamageType::vanilla_defaults();
+/// let lava = damage_types.iter().find(|damage| damage.id == "minecraft:lava").unwrap();
+///
+/// assert_eq!(lava.effects, DamageEffects::Burning);
+/// assert_eq!(lava.to_nbt().get("message_id"), Some(&NbtTag::String("lava".into())));
+/// ```
+///
+/// # Fields
+/// - `id` - The identifier of the damage type, like `minecraft:fall`.
+/// - `message_id` - The ID of the death message, like `fall` for `death.attack.fall`.
+/// - `scaling` - When the damage is scaled with the difficulty.
+/// - `exhaustion` - The amount of exhaustion the damage causes.
+/// - `effects` - The sound played when a player takes the damage.
+/// - `death_message_type` - How the death message is built.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DamageType {
+    pub id: String,
+    pub message_id: String,
+    pub scaling: DamageScaling,
+    pub exhaustion: f32,
+    pub effects: DamageEffects,
+    pub death_message_type: DeathMessageType,
+}
+
+impl DamageType {
+    /// Creates a new `[DamageType]` with the default effects and death message type.
+    ///
+    /// # Parameters
+    /// - `id` - The identifier of the damage type.
+    /// - `message_id` - The ID of the death message.
+    /// - `scaling` - When the damage is scaled with the difficulty.
+    /// - `exhaustion` - The amount of exhaustion the damage causes.
+    pub fn new(
+        id: impl Into<String>,
+        message_id: impl Into<String>,
+        scaling: DamageScaling,
+        exhaustion: f32,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            message_id: message_id.into(),
+            scaling,
+            exhaustion,
+            effects: DamageEffects::Hurt,
+            death_message_type: DeathMessageType::Default,
+        }
+    }
+
+    /// Sets the sound played when a player takes the damage.
+    pub fn with_effects(mut self, effects: DamageEffects) -> Self {
+        self.effects = effects;
+        self
+    }
+
+    /// Sets how the death message is built.
+    pub fn with_death_message_type(mut self, death_message_type: DeathMessageType) -> Self {
+        self.death_message_type = death_message_type;
+        self
+    }
+
+    /// Returns every vanilla damage type.
+    pub fn vanilla_defaults() -> Vec<Self> {
+        use DamageEffects::*;
+        use DamageScaling::*;
+
+        let damage = |id: &str, message_id: &str, scaling, exhaustion| {
+            Self::new(format!("minecraft:{}", id), message_id, scaling, exhaustion)
+        };
+        let mob = |id: &str, message_id: &str, exhaustion| {
+            damage(id, message_id, WhenCausedByLivingNonPlayer, exhaustion)
+        };
+
+        vec![
+            mob("arrow", "arrow", 0.1),
+            damage("bad_respawn_point", "badRespawnPoint", Always, 0.1)
+                .with_death_message_type(DeathMessageType::IntentionalGameDesign),
+            mob("cactus", "cactus", 0.1),
+            mob("campfire", "inFire", 0.1).with_effects(Burning),
+            mob("cramming", "cramming", 0.0),
+            mob("dragon_breath", "dragonBreath", 0.0),
+            mob("drown", "drown", 0.0).with_effects(Drowning),
+            mob("dry_out", "dryout", 0.1),
+            damage("explosion", "explosion", Always, 0.1),
+            mob("fall", "fall", 0.0).with_death_message_type(DeathMessageType::FallVariants),
+            mob("falling_anvil", "anvil", 0.1),
+            mob("falling_block", "fallingBlock", 0.1),
+            mob("falling_stalactite", "fallingStalactite", 0.1),
+            mob("fireball", "fireball", 0.1).with_effects(Burning),
+            mob("fireworks", "fireworks", 0.1),
+            mob("fly_into_wall", "flyIntoWall", 0.0),
+            mob("freeze", "freeze", 0.0).with_effects(Freezing),
+            mob("generic", "generic", 0.0),
+            mob("generic_kill", "genericKill", 0.0),
+            mob("hot_floor", "hotFloor", 0.1).with_effects(Burning),
+            mob("in_fire", "inFire", 0.1).with_effects(Burning),
+            mob("in_wall", "inWall", 0.0),
+            mob("indirect_magic", "indirectMagic", 0.0),
+            mob("lava", "lava", 0.1).with_effects(Burning),
+            mob("lightning_bolt", "lightningBolt", 0.1),
+            mob("mace_smash", "mace_smash", 0.1),
+            mob("magic", "magic", 0.0),
+            mob("mob_attack", "mob", 0.1),
+            mob("mob_attack_no_aggro", "mob", 0.1),
+            mob("mob_projectile", "mob", 0.1),
+            mob("on_fire", "onFire", 0.0).with_effects(Burning),
+            mob("out_of_world", "outOfWorld", 0.0),
+            mob("outside_border", "outsideBorder", 0.0),
+            mob("player_attack", "player", 0.1),
+            damage("player_explosion", "explosion.player", Always, 0.1),
+            damage("sonic_boom", "sonic_boom", Always, 0.0),
+            mob("spit", "mob", 0.1),
+            mob("stalagmite", "stalagmite", 0.0),
+            mob("starve", "starve", 0.0),
+            mob("sting", "sting", 0.1),
+            mob("sweet_berry_bush", "sweetBerryBush", 0.1).with_effects(Poking),
+            mob("thorns", "thorns", 0.1).with_effects(Thorns),
+            mob("thrown", "thrown", 0.1),
+            mob("trident", "trident", 0.1),
+            mob("unattributed_fireball", "onFire", 0.1).with_effects(Burning),
+            mob("wind_charge", "mob", 0.1),
+            mob("wither", "wither", 0.0),
+            mob("wither_skull", "witherSkull", 0.1),
+        ]
+    }
+
+    /// Returns the registry with every vanilla damage type, including their data.
+    pub fn vanilla_registry() -> Registry {
+        let mut registry = Registry::new(DAMAGE_TYPE_REGISTRY);
+        for damage_type in Self::vanilla_defaults() {
+            registry.insert(damage_type.to_entry());
+        }
+
+        registry
+    }
+
+    /// Serializes the damage type the way it is stored in the registry.
+    ///
+    /// The effects and death message type are only written if they aren't the default.
+    pub fn to_nbt(&self) -> NbtCompound {
+        let mut compound = NbtCompound::new()
+            .with("message_id", self.message_id.as_str())
+            .with("scaling", self.scaling.name())
+            .with("exhaustion", self.exhaustion);
+        if self.effects != DamageEffects::Hurt {
+            compound.insert("effects", self.effects.name());
+        }
+        if self.death_message_type != DeathMessageType::Default {
+            compound.insert("death_message_type", self.death_message_type.name());
+        }
+
+        compound
+    }
+
+    /// Returns the registry entry of this damage type.
+    pub fn to_entry(&self) -> RegistryEntry {
+        RegistryEntry::new(self.id.as_str(), self.to_nbt())
+    }
+}
+
+impl RegistryValue for DamageType {
+    const REGISTRY: &'static str = DAMAGE_TYPE_REGISTRY;
+
+    fn identifier(&self) -> &str {
+        &self.id
+    }
+
+    fn to_nbt(&self) -> NbtCompound {
+        DamageType::to_nbt(self)
+    }
+}
@@ -0,0 +1,107 @@
+use std::io::Cursor;
+
+use protocol_buf::{
+    error::{ProtocolError, ProtocolResult},
+    FromNetwork, ToNetwork,
+};
+
+use crate::item::Slot;
+
+/// The bit of an equipment slot that is set if another entry follows.
+const HAS_NEXT: u8 = 0x80;
+
+/// A slot of an entity that other players can see, like the item in its main hand or its helmet.
+///
+/// # Variants
+/// - `MainHand` - The item in the main hand.
+/// - `OffHand` - The item in the off hand.
+/// - `Boots` - The boots.
+/// - `Leggings` - The leggings.
+/// - `Chestplate` - The chestplate.
+/// - `Helmet` - The helmet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EquipmentSlot {
+    MainHand,
+    OffHand,
+    Boots,
+    Leggings,
+    Chestplate,
+    Helmet,
+}
+
+impl EquipmentSlot {
+    /// Every equipment slot, in the order of their IDs.
+    pub const ALL: [Self; 6] = [
+        Self::MainHand,
+        Self::OffHand,
+        Self::Boots,
+        Self::Leggings,
+        Self::Chestplate,
+        Self::Helmet,
+    ];
+
+    /// Returns the ID of the slot, as it is sent in packets.
+    pub const fn id(self) -> u8 {
+        self as u8
+    }
+
+    /// Returns the slot with the given ID, `None` if the ID is unknown.
+    pub fn from_id(id: u8) -> Option<Self> {
+        Self::ALL.get(id as usize).copied()
+    }
+}
+
+/// The visible items of an entity, every entry replaces the item in its slot.
+///
+/// The entries aren't prefixed with their amount, instead every slot except the last has its highest bit set.
+/// At least one entry has to be sent.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{FromNetwork, ToNetwork};
+/// use protocol_packets::{
+///     equipment::{Equipment, EquipmentSlot},
+///     item::{ItemStack, Slot},
+/// };
+///
+/// let equipment = Equipment(vec![
+///     (EquipmentSlot::MainHand, Slot::from(ItemStack::new(1, 1))),
+///     (EquipmentSlot::Helmet, Slot::empty()),
+/// ]);
+/// assert_eq!(equipment.to_network(), vec![0x80, 1, 1, 0, 0, 5, 0]);
+///
+/// let mut buffer = Cursor::new(equipment.to_network());
+/// assert_eq!(Equipment::from_network(&mut buffer).unwrap(), equipment);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Equipment(pub Vec<(EquipmentSlot, Slot)>);
+
+impl ToNetwork for Equipment {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for (index, (slot, item)) in self.0.iter().enumerate() {
+            let has_next = index + 1 < self.0.len();
+            bytes.push(slot.id() | if has_next { HAS_NEXT } else { 0 });
+            bytes.extend_from_slice(&item.to_network());
+        }
+        bytes
+    }
+}
+
+impl FromNetwork for Equipment {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        let mut entries = Vec::new();
+        loop {
+            let id = u8::from_network(buffer)?;
+            let slot = EquipmentSlot::from_id(id & !HAS_NEXT).ok_or_else(|| {
+                ProtocolError::InvalidData(format!("unknown equipment slot {}", id & !HAS_NEXT))
+            })?;
+            entries.push((slot, Slot::from_network(buffer)?));
+
+            if id & HAS_NEXT == 0 {
+                return Ok(Self(entries));
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::io::Cursor;
+
+use protocol_buf::{
+    error::{ProtocolError, ProtocolResult},
+    types::VarInt,
+    FromNetwork, ToNetwork,
+};
+
+/// The main hand of the player, the item in it is used first.
+pub const MAIN_HAND: i32 = 0;
+
+/// The off hand of the player.
+pub const OFF_HAND: i32 = 1;
+
+/// The input flag that is set while the player is jumping.
+pub const INPUT_JUMP: u8 = 0x01;
+
+/// The input flag that is set while the player is sneaking, which makes it leave its vehicle.
+pub const INPUT_UNMOUNT: u8 = 0x02;
+
+/// What the player did with an entity it clicked on.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{FromNetwork, ToNetwork};
+/// use protocol_packets::input::{InteractAction, MAIN_HAND};
+///
+/// let action = InteractAction::InteractAt { target_x: 0.5, target_y: 1.0, target_z: 0.5, hand: MAIN_HAND };
+/// let mut buffer = Cursor::new(action.to_network());
+///
+/// assert_eq!(InteractAction::from_network(&mut buffer).unwrap(), action);
+/// assert_eq!(InteractAction::Attack.to_network(), vec![1]);
+/// assert!(InteractAction::from_network(&mut Cursor::new(vec![3])).is_err());
+/// ```
+///
+/// # Variants
+/// - `Interact` - The player right clicked the entity with the given hand.
+/// - `Attack` - The player left clicked the entity.
+/// - `InteractAt` - The player right clicked the given position on the hitbox of the entity, relative to the entity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InteractAction {
+    Interact {
+        hand: i32,
+    },
+    Attack,
+    InteractAt {
+        target_x: f32,
+        target_y: f32,
+        target_z: f32,
+        hand: i32,
+    },
+}
+
+impl ToNetwork for InteractAction {
+    fn to_network(&self) -> Vec<u8> {
+        match self {
+            Self::Interact { hand } => {
+                let mut bytes = VarInt::from(0).to_network();
+                bytes.extend_from_slice(&VarInt::from(*hand).to_network());
+                bytes
+            }
+            Self::Attack => VarInt::from(1).to_network(),
+            Self::InteractAt {
+                target_x,
+                target_y,
+                target_z,
+                hand,
+            } => {
+                let mut bytes = VarInt::from(2).to_network();
+                bytes.extend_from_slice(&target_x.to_network());
+                bytes.extend_from_slice(&target_y.to_network());
+                bytes.extend_from_slice(&target_z.to_network());
+                bytes.extend_from_slice(&VarInt::from(*hand).to_network());
+                bytes
+            }
+        }
+    }
+}
+
+/// Reads the type of the action, the target is only sent for `InteractAt` and the hand isn't sent for `Attack`.
+impl FromNetwork for InteractAction {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        match *VarInt::from_network(buffer)? {
+            0 => Ok(Self::Interact {
+                hand: *VarInt::from_network(buffer)?,
+            }),
+            1 => Ok(Self::Attack),
+            2 => Ok(Self::InteractAt {
+                target_x: f32::from_network(buffer)?,
+                target_y: f32::from_network(buffer)?,
+                target_z: f32::from_network(buffer)?,
+                hand: *VarInt::from_network(buffer)?,
+            }),
+            action => Err(ProtocolError::InvalidData(format!(
+                "unknown interact action {action}"
+            ))),
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use std::{io::Cursor, ops::Deref};
+
+use protocol_buf::{
+    error::{ProtocolError, ProtocolResult},
+    text::Component,
+    types::VarInt,
+    FromNetwork, ToNetwork,
+};
+
+/// A structured component of an item, which changes a property of the item like its name.
+///
+/// Only the components with a simple encoding are typed, other components can be written with `Raw`.
+/// Text components can't be read yet, so items with a name or a lore can only be sent to the client.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{FromNetwork, ToNetwork};
+/// use protocol_packets::item::ItemComponent;
+///
+/// let component = ItemComponent::Unbreakable { show_in_tooltip: false };
+/// assert_eq!(component.to_network(), vec![4, 0]);
+///
+/// let mut buffer = Cursor::new(vec![3, 10]);
+/// assert_eq!(ItemComponent::from_network(&mut buffer).unwrap(), ItemComponent::Damage(10));
+/// ```
+///
+/// # Variants
+/// - `MaxStackSize` - The amount of items that fit into a single slot, from `1` to `99`.
+/// - `MaxDamage` - The durability of the item.
+/// - `Damage` - The durability the item lost.
+/// - `Unbreakable` - The item doesn't lose durability.
+/// - `CustomName` - The name a player gave the item, which is shown in italics.
+/// - `ItemName` - The default name of the item, which can't be changed in an anvil.
+/// - `Lore` - The lines shown below the name of the item.
+/// - `Rarity` - The rarity of the item, from `0` for common to `3` for epic, which sets the color of the name.
+/// - `CustomModelData` - The value resource packs use to pick the model of the item.
+/// - `HideAdditionalTooltip` - Hides the tooltip lines that depend on the item, like the effects of a potion.
+/// - `HideTooltip` - Hides the whole tooltip.
+/// - `RepairCost` - The experience levels repairing the item in an anvil costs additionally.
+/// - `EnchantmentGlintOverride` - Whether the item always or never has the enchantment glint.
+/// - `FireResistant` - The item doesn't burn in fire and lava.
+/// - `Raw` - Any other component, with its already encoded data.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemComponent {
+    MaxStackSize(i32),
+    MaxDamage(i32),
+    Damage(i32),
+    Unbreakable { show_in_tooltip: bool },
+    CustomName(Component),
+    ItemName(Component),
+    Lore(Vec<Component>),
+    Rarity(i32),
+    CustomModelData(i32),
+    HideAdditionalTooltip,
+    HideTooltip,
+    RepairCost(i32),
+    EnchantmentGlintOverride(bool),
+    FireResistant,
+    Raw { id: i32, data: Vec<u8> },
+}
+
+impl ItemComponent {
+    /// Returns the ID of the component type in the `minecraft:data_component_type` registry.
+    pub fn id(&self) -> i32 {
+        match self {
+            Self::MaxStackSize(_) => 1,
+            Self::MaxDamage(_) => 2,
+            Self::Damage(_) => 3,
+            Self::Unbreakable { .. } => 4,
+            Self::CustomName(_) => 5,
+            Self::ItemName(_) => 6,
+            Self::Lore(_) => 7,
+            Self::Rarity(_) => 8,
+            Self::CustomModelData(_) => 13,
+            Self::HideAdditionalTooltip => 14,
+            Self::HideTooltip => 15,
+            Self::RepairCost(_) => 16,
+            Self::EnchantmentGlintOverride(_) => 18,
+            Self::FireResistant => 21,
+            Self::Raw { id, .. } => *id,
+        }
+    }
+}
+
+/// Writes the ID of the component type followed by the data of the component.
+impl ToNetwork for ItemComponent {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = VarInt::from(self.id()).to_network();
+        bytes.extend_from_slice(&match self {
+            Self::MaxStackSize(value)
+            | Self::MaxDamage(value)
+            | Self::Damage(value)
+            | Self::Rarity(value)
+            | Self::CustomModelData(value)
+            | Self::RepairCost(value) => VarInt::from(*value).to_network(),
+            Self::Unbreakable { show_in_tooltip } => show_in_tooltip.to_network(),
+            Self::CustomName(name) | Self::ItemName(name) => name.to_network(),
+            Self::Lore(lines) => lines.to_network(),
+            Self::EnchantmentGlintOverride(glint) => glint.to_network(),
+            Self::HideAdditionalTooltip | Self::HideTooltip | Self::FireResistant => Vec::new(),
+            Self::Raw { data, .. } => data.clone(),
+        });
+        bytes
+    }
+}
+
+/// Reads a component, components that aren't typed or contain text components can't be read.
+impl FromNetwork for ItemComponent {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        let id = *VarInt::from_network(buffer)?;
+        let mut varint = || VarInt::from_network(buffer).map(|value| *value);
+        Ok(match id {
+            1 => Self::MaxStackSize(varint()?),
+            2 => Self::MaxDamage(varint()?),
+            3 => Self::Damage(varint()?),
+            4 => Self::Unbreakable {
+                show_in_tooltip: bool::from_network(buffer)?,
+            },
+            8 => Self::Rarity(varint()?),
+            13 => Self::CustomModelData(varint()?),
+            14 => Self::HideAdditionalTooltip,
+            15 => Self::HideTooltip,
+            16 => Self::RepairCost(varint()?),
+            18 => Self::EnchantmentGlintOverride(bool::from_network(buffer)?),
+            21 => Self::FireResistant,
+            id => {
+                return Err(ProtocolError::InvalidData(format!(
+                    "item component {id} can't be read"
+                )))
+            }
+        })
+    }
+}
+
+/// A stack of items, like the content of an inventory slot.
+///
+/// # Fields
+/// - `item_id` - The ID of the item in the `minecraft:item` registry.
+/// - `count` - The amount of items, at least `1`.
+/// - `components` - The components that are added to the default components of the item, or replace them.
+/// - `removed_components` - The IDs of the default components of the item that are removed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemStack {
+    pub item_id: i32,
+    pub count: i32,
+    pub components: Vec<ItemComponent>,
+    pub removed_components: Vec<i32>,
+}
+
+impl ItemStack {
+    /// Creates a new `[ItemStack]` with the default components of the item.
+    pub fn new(item_id: i32, count: i32) -> Self {
+        Self {
+            item_id,
+            count,
+            components: Vec::new(),
+            removed_components: Vec::new(),
+        }
+    }
+
+    /// Adds a component to the item.
+    pub fn with(mut self, component: ItemComponent) -> Self {
+        self.components.push(component);
+        self
+    }
+}
+
+/// An inventory slot, which is either empty or contains an `[ItemStack]`.
+///
+/// Empty slots are written as a count of `0`. The components of an item aren't prefixed with their length,
+/// but the amount of added and removed components is written before them.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{FromNetwork, ToNetwork};
+/// use protocol_packets::item::{ItemComponent, ItemStack, Slot};
+///
+/// assert_eq!(Slot::empty().to_network(), vec![0]);
+///
+/// let slot = Slot::from(ItemStack::new(1, 64).with(ItemComponent::MaxStackSize(99)));
+/// assert_eq!(slot.to_network(), vec![64, 1, 1, 0, 1, 99]);
+///
+/// let mut buffer = Cursor::new(slot.to_network());
+/// assert_eq!(Slot::from_network(&mut buffer).unwrap(), slot);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Slot(pub Option<ItemStack>);
+
+impl Slot {
+    /// Creates an empty `[Slot]`.
+    pub const fn empty() -> Self {
+        Self(None)
+    }
+}
+
+impl Deref for Slot {
+    type Target = Option<ItemStack>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<ItemStack> for Slot {
+    fn from(item: ItemStack) -> Self {
+        Self(Some(item))
+    }
+}
+
+impl ToNetwork for Slot {
+    fn to_network(&self) -> Vec<u8> {
+        let Some(item) = self.0.as_ref().filter(|item| item.count > 0) else {
+            return VarInt::from(0).to_network();
+        };
+
+        let mut bytes = VarInt::from(item.count).to_network();
+        bytes.extend_from_slice(&VarInt::from(item.item_id).to_network());
+        bytes.extend_from_slice(&VarInt::from(item.components.len() as i32).to_network());
+        bytes.extend_from_slice(&VarInt::from(item.removed_components.len() as i32).to_network());
+        for component in &item.components {
+            bytes.extend_from_slice(&component.to_network());
+        }
+        for id in &item.removed_components {
+            bytes.extend_from_slice(&VarInt::from(*id).to_network());
+        }
+        bytes
+    }
+}
+
+impl FromNetwork for Slot {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        let count = *VarInt::from_network(buffer)?;
+        if count <= 0 {
+            return Ok(Self::empty());
+        }
+
+        let item_id = *VarInt::from_network(buffer)?;
+        let added = *VarInt::from_network(buffer)?;
+        let removed = *VarInt::from_network(buffer)?;
+        if added < 0 || removed < 0 {
+            return Err(ProtocolError::InvalidData(
+                "negative amount of item components".to_string(),
+            ));
+        }
+
+        let components = (0..added)
+            .map(|_| ItemComponent::from_network(buffer))
+            .collect::<ProtocolResult<_>>()?;
+        let removed_components = (0..removed)
+            .map(|_| VarInt::from_network(buffer).map(|id| *id))
+            .collect::<ProtocolResult<_>>()?;
+
+        Ok(Self(Some(ItemStack {
+            item_id,
+            count,
+            components,
+            removed_components,
+        })))
+    }
+}
@@ -1,6 +1,32 @@
-use protocol_buf::buffer::{NormalBuffer, PacketBuffer};
+use protocol_buf::{
+    buffer::{NormalBuffer, PacketBuffer},
+    compression::{CompressionData, CompressionHint, CompressionResult},
+    error::ProtocolResult,
+};
 
+pub mod block;
+pub mod chat;
+pub mod chunk;
+pub mod damage_type;
+pub mod equipment;
+pub mod input;
+pub mod item;
+pub mod links;
 pub mod macros;
+pub mod metadata;
+pub mod particle;
+pub mod player_info;
+pub mod plugin;
+pub mod registry;
+pub mod report;
+pub mod resource_pack;
+pub mod scoreboard;
+pub mod settings;
+pub mod sound;
+pub mod structure;
+pub mod tags;
+pub mod teleport;
+pub mod v1_21;
 
 /// This trait defines all packets that can be send between the client or the server.
 ///
@@ -8,22 +34,30 @@ pub mod macros;
 ///
 /// # Examples
 /// ```rust
-/// use protocol::Packet;
+/// use protocol_packets::Packet;
 ///
 /// struct HandshakePacket {
 ///    pub protocol_version: i32,
 ///    pub server_address: String,
 ///    pub server_port: u16,
-///   pub next_state: i32,
+///    pub next_state: i32,
 /// }
 ///
 /// impl Packet for HandshakePacket {
 ///    fn id(&self) -> i32 {
 ///       0x00
-///   }
+///    }
 /// }
+/// ```
 pub trait Packet {
     fn id(&self) -> i32;
+
+    /// Defines how this packet should be compressed once compression is enabled.
+    ///
+    /// By default the packet is compressed if it reaches the compression threshold.
+    fn compression_hint(&self) -> CompressionHint {
+        CompressionHint::Threshold
+    }
 }
 
 /// Defines a packet that can be sent from the server to the client.
@@ -32,26 +66,23 @@ pub trait Packet {
 ///
 /// # Examples
 /// ```rust
-/// use protocol::ClientboundPacket;
+/// use protocol_buf::{buffer::{Buffer, NormalBuffer, PacketBuffer}, types::VarInt};
+/// use protocol_packets::{ClientboundPacket, Packet};
 ///
-/// struct HandshakePacket {
-///   pub protocol_version: i32,
-///   pub server_address: String,
-///   pub server_port: u16,
-///   pub next_state: i32,
+/// struct StatusResponsePacket {
+///   pub json_response: String,
 /// }
 ///
-/// impl ClientboundPacket for HandshakePacket {
+/// impl Packet for StatusResponsePacket {
 ///   fn id(&self) -> i32 {
 ///     0x00
 ///   }
+/// }
 ///
-///   fn write_packet(&self, buffer: NormalBuffer) -> PacketBuffer {
-///     buffer.write(self.protocol_version);
-///     buffer.write(self.server_address.clone());
-///     buffer.write(self.server_port);
-///     buffer.write(self.next_state);
-///     buffer
+/// impl ClientboundPacket for StatusResponsePacket {
+///   fn write_packet(&self, mut buffer: NormalBuffer) -> PacketBuffer {
+///     buffer.write(self.json_response.clone());
+///     PacketBuffer::with_id(VarInt::from(self.id()), buffer)
 ///   }
 /// }
 /// ```
@@ -65,33 +96,192 @@ pub trait ClientboundPacket: Packet {
 ///
 /// # Examples
 /// ```rust
-/// use protocol::ServerboundPacket;
+/// use protocol_buf::{buffer::{Buffer, NormalBuffer, PacketBuffer}, error::ProtocolResult, types::VarInt};
+/// use protocol_packets::{ConnectionState, Packet, ServerboundPacket};
 ///
 /// struct HandshakePacket {
-///   pub protocol_version: i32,
+///   pub protocol_version: VarInt,
 ///   pub server_address: String,
 ///   pub server_port: u16,
-///   pub next_state: i32,
+///   pub next_state: VarInt,
 /// }
 ///
-/// impl ServerboundPacket for HandshakePacket {
+/// impl Packet for HandshakePacket {
 ///   fn id(&self) -> i32 {
 ///     0x00
 ///   }
+/// }
 ///
-///   fn read_packet(buffer: PacketBuffer) -> Self {
-///     let protocol_version: i32 = buffer.read();
-///     let server_address: String = buffer.read();
-///     let server_port: u16 = buffer.read();
-///     let next_state: i32 = buffer.read();
-///     HandshakePacket {
-///       protocol_version,
-///       server_address,
-///       server_port,
-///       next_state,
-///     }
+/// impl ServerboundPacket for HandshakePacket {
+///   const STATE: ConnectionState = ConnectionState::Handshake;
+///   const ID: i32 = 0x00;
+///
+///   fn read_packet(mut buffer: NormalBuffer) -> ProtocolResult<Self> {
+///     Ok(HandshakePacket {
+///       protocol_version: buffer.read()?,
+///       server_address: buffer.read()?,
+///       server_port: buffer.read()?,
+///       next_state: buffer.read()?,
+///     })
+///   }
+///
+///   fn write_packet(&self, mut buffer: NormalBuffer) -> PacketBuffer {
+///     buffer.write(self.protocol_version);
+///     buffer.write(self.server_address.clone());
+///     buffer.write(self.server_port);
+///     buffer.write(self.next_state);
+///     PacketBuffer::with_id(VarInt::from(Self::ID), buffer)
 ///   }
 /// }
-pub trait ServerboundPacket: Packet {
-    fn read_packet(buffer: NormalBuffer) -> Self;
+/// ```
+pub trait ServerboundPacket: Packet + Sized {
+    /// The state the connection has to be in for this packet.
+    const STATE: ConnectionState;
+
+    /// The ID of this packet inside its state.
+    const ID: i32;
+
+    /// Reads the packet from the given buffer.
+    ///
+    /// # Returns
+    /// The packet, an error if the packet data is malformed.
+    fn read_packet(buffer: NormalBuffer) -> ProtocolResult<Self>;
+
+    /// Writes the packet into the given buffer, this is used when connecting to other servers.
+    fn write_packet(&self, buffer: NormalBuffer) -> PacketBuffer;
+}
+
+/// A clientbound packet that was compressed ahead of time.
+///
+/// This is useful for packets that are sent a lot of times with the same data, like cached chunk data.
+/// The packet is only compressed once and then sent as it is with `[CompressionHint::Precompressed]`.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::compression::{CompressionData, CompressionType};
+/// use protocol_packets::{v1_21::status::StatusResponsePacket, Packet, PrecompressedPacket};
+///
+/// let compression = CompressionData::new(256, CompressionType::Zlib);
+/// let status = StatusResponsePacket { json_response: "{}".to_string() };
+/// let packet = PrecompressedPacket::new(&status, &compression).unwrap();
+///
+/// assert_eq!(packet.id(), 0x00);
+/// ```
+///
+/// # Fields
+/// - `buffer` - The compressed packet buffer.
+#[derive(Debug, Clone)]
+pub struct PrecompressedPacket {
+    buffer: PacketBuffer,
+}
+
+impl PrecompressedPacket {
+    /// Compresses the given packet.
+    ///
+    /// # Parameters
+    /// - `packet` - The packet to compress.
+    /// - `compression` - The compression data used to compress the packet.
+    pub fn new<P: ClientboundPacket>(
+        packet: &P,
+        compression: &CompressionData,
+    ) -> CompressionResult<Self> {
+        let buffer = packet.write_packet(NormalBuffer::new(Vec::new()));
+        Ok(Self {
+            buffer: compression.precompress(buffer)?,
+        })
+    }
+}
+
+impl Packet for PrecompressedPacket {
+    fn id(&self) -> i32 {
+        *self.buffer.packet_id
+    }
+
+    fn compression_hint(&self) -> CompressionHint {
+        CompressionHint::Precompressed
+    }
+}
+
+impl ClientboundPacket for PrecompressedPacket {
+    fn write_packet(&self, _buffer: NormalBuffer) -> PacketBuffer {
+        self.buffer.clone()
+    }
+}
+
+/// Defines the state a connection is currently in.
+///
+/// Every connection starts in the `Handshake` state. The `next_state` of the handshake packet decides which state comes next.
+///
+/// # Variants
+///
+/// - `Handshake` - The client has to send the handshake packet.
+/// - `Status` - The client is requesting the server list status.
+/// - `Login` - The client is logging in.
+/// - `Transfer` - The client is logging in after being transferred from another server.
+/// - `Configuration` - The client is being configured before joining the game.
+/// - `Play` - The client is in the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConnectionState {
+    Handshake,
+    Status,
+    Login,
+    Transfer,
+    Configuration,
+    Play,
+}
+
+impl ConnectionState {
+    /// Grabs the state that follows the handshake for the given intent.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_packets::ConnectionState;
+    ///
+    /// assert_eq!(ConnectionState::from_intent(1), Some(ConnectionState::Status));
+    /// assert_eq!(ConnectionState::from_intent(4), None);
+    /// ```
+    ///
+    /// # Returns
+    /// The next state, `None` if the intent is unknown.
+    pub const fn from_intent(intent: i32) -> Option<Self> {
+        match intent {
+            1 => Some(Self::Status),
+            2 => Some(Self::Login),
+            3 => Some(Self::Transfer),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the state, like `Play`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Handshake => "Handshake",
+            Self::Status => "Status",
+            Self::Login => "Login",
+            Self::Transfer => "Transfer",
+            Self::Configuration => "Configuration",
+            Self::Play => "Play",
+        }
+    }
+
+    /// Grabs the intent that is sent in the handshake to switch to this state.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_packets::ConnectionState;
+    ///
+    /// assert_eq!(ConnectionState::Login.to_intent(), Some(2));
+    /// assert_eq!(ConnectionState::Play.to_intent(), None);
+    /// ```
+    ///
+    /// # Returns
+    /// The intent, `None` if the state can't follow the handshake.
+    pub const fn to_intent(&self) -> Option<i32> {
+        match self {
+            Self::Status => Some(1),
+            Self::Login => Some(2),
+            Self::Transfer => Some(3),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,100 @@
+use protocol_buf::{text::Component, types::VarInt, ToNetwork};
+
+/// A label of a server link that the client translates itself.
+///
+/// # Variants
+/// - `BugReport` - The link to report bugs, the client also shows it on the disconnect screen.
+/// - `CommunityGuidelines` - The rules of the community.
+/// - `Support` - The support of the server.
+/// - `Status` - The status page of the server.
+/// - `Feedback` - The link to give feedback.
+/// - `Community` - The community of the server.
+/// - `Website` - The website of the server.
+/// - `Forums` - The forums of the server.
+/// - `News` - The news of the server.
+/// - `Announcements` - The announcements of the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuiltInLink {
+    BugReport,
+    CommunityGuidelines,
+    Support,
+    Status,
+    Feedback,
+    Community,
+    Website,
+    Forums,
+    News,
+    Announcements,
+}
+
+/// The label of a server link, which is shown on the button of the link.
+///
+/// # Variants
+/// - `BuiltIn` - A label the client translates.
+/// - `Custom` - Any text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServerLinkLabel {
+    BuiltIn(BuiltInLink),
+    Custom(Component),
+}
+
+/// Writes whether the label is built-in, followed by the ID of the built-in label or the text.
+impl ToNetwork for ServerLinkLabel {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = matches!(self, Self::BuiltIn(_)).to_network();
+        bytes.extend_from_slice(&match self {
+            Self::BuiltIn(link) => VarInt::from(*link as i32).to_network(),
+            Self::Custom(text) => text.to_network(),
+        });
+        bytes
+    }
+}
+
+/// A link the client shows in the server links screen of the pause menu.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::ToNetwork;
+/// use protocol_packets::links::{BuiltInLink, ServerLink};
+///
+/// let link = ServerLink::built_in(BuiltInLink::Website, "https://example.com");
+///
+/// let mut expected = vec![0x01, 6];
+/// expected.extend_from_slice(&"https://example.com".to_string().to_network());
+/// assert_eq!(link.to_network(), expected);
+/// ```
+///
+/// # Fields
+/// - `label` - The label of the link.
+/// - `url` - The URL the link opens.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerLink {
+    pub label: ServerLinkLabel,
+    pub url: String,
+}
+
+impl ServerLink {
+    /// Creates a link with a label the client translates.
+    pub fn built_in(link: BuiltInLink, url: impl Into<String>) -> Self {
+        Self {
+            label: ServerLinkLabel::BuiltIn(link),
+            url: url.into(),
+        }
+    }
+
+    /// Creates a link with a custom label.
+    pub fn custom(label: impl Into<Component>, url: impl Into<String>) -> Self {
+        Self {
+            label: ServerLinkLabel::Custom(label.into()),
+            url: url.into(),
+        }
+    }
+}
+
+impl ToNetwork for ServerLink {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = self.label.to_network();
+        bytes.extend_from_slice(&self.url.to_network());
+        bytes
+    }
+}
@@ -0,0 +1,344 @@
+/// Defines a packet that is sent from the server to the client.
+///
+/// This creates the packet struct and implements `[Packet]` and `[ClientboundPacket]` for it.
+/// The fields are written in the same order as they are defined.
+///
+/// A `[CompressionHint]` variant can be added after the packet ID to change how the packet is compressed.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::{buffer::{Buffer, NormalBuffer, PacketBuffer}, types::VarInt};
+/// use protocol_packets::{clientbound_packet, ClientboundPacket, Packet};
+///
+/// clientbound_packet! {
+///     /// The response to a ping request.
+///     PongResponsePacket, 0x01 {
+///         payload: i64,
+///     }
+/// }
+///
+/// let packet = PongResponsePacket { payload: 1 };
+/// let buffer = packet.write_packet(NormalBuffer::new(Vec::new()));
+///
+/// assert_eq!(*buffer.packet_id, 0x01);
+/// assert_eq!(buffer.get_ref(), &[0, 0, 0, 0, 0, 0, 0, 1]);
+/// ```
+///
+/// ```rust
+/// use protocol_buf::{buffer::{Buffer, NormalBuffer, PacketBuffer}, compression::CompressionHint, types::VarInt};
+/// use protocol_packets::{clientbound_packet, ClientboundPacket, Packet};
+///
+/// clientbound_packet! {
+///     /// A keep alive that is never compressed.
+///     KeepAlivePacket, 0x26, Never {
+///         keep_alive_id: i64,
+///     }
+/// }
+///
+/// assert_eq!(KeepAlivePacket { keep_alive_id: 1 }.compression_hint(), CompressionHint::Never);
+/// ```
+#[macro_export]
+macro_rules! clientbound_packet {
+    {
+        $(#[$meta:meta])*
+        $name:ident, $id:literal $(, $hint:ident)? {
+            $( $(#[$field_meta:meta])* $field:ident: $field_type:ty ),* $(,)?
+        }
+    } => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct $name {
+            $( $(#[$field_meta])* pub $field: $field_type, )*
+        }
+
+        impl Packet for $name {
+            fn id(&self) -> i32 {
+                $id
+            }
+
+            $(
+                fn compression_hint(&self) -> CompressionHint {
+                    CompressionHint::$hint
+                }
+            )?
+        }
+
+        impl ClientboundPacket for $name {
+            #[allow(unused_mut)]
+            fn write_packet(&self, mut buffer: NormalBuffer) -> PacketBuffer {
+                $( buffer.write(self.$field.clone()); )*
+                PacketBuffer::with_id(VarInt::from($id), buffer)
+            }
+        }
+    };
+}
+
+/// Defines a packet that is sent from the client to the server.
+///
+/// This creates the packet struct and implements `[Packet]` and `[ServerboundPacket]` for it.
+/// The fields are read and written in the same order as they are defined.
+/// If a field can't be read, the error contains the packet, the field and its offset.
+///
+/// The `[ConnectionState]` variant the packet is sent in has to be added before the packet ID.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::{buffer::{Buffer, NormalBuffer, PacketBuffer}, error::ProtocolResult, types::VarInt};
+/// use protocol_packets::{serverbound_packet, ConnectionState, Packet, ServerboundPacket};
+///
+/// serverbound_packet! {
+///     /// The ping request sent by the client.
+///     PingRequestPacket, Status, 0x01 {
+///         payload: i64,
+///     }
+/// }
+///
+/// let packet = PingRequestPacket::read_packet(NormalBuffer::new(vec![0, 0, 0, 0, 0, 0, 0, 1])).unwrap();
+///
+/// assert_eq!(packet.payload, 1);
+/// assert_eq!(packet.id(), 0x01);
+/// assert_eq!(PingRequestPacket::STATE, ConnectionState::Status);
+/// assert_eq!(packet.write_packet(NormalBuffer::new(Vec::new())).get_ref(), &[0, 0, 0, 0, 0, 0, 0, 1]);
+///
+/// let error = PingRequestPacket::read_packet(NormalBuffer::new(vec![0, 0])).unwrap_err();
+/// assert!(error.to_string().contains("`payload` of serverbound Status packet PingRequestPacket (0x01)"));
+/// ```
+#[macro_export]
+macro_rules! serverbound_packet {
+    {
+        $(#[$meta:meta])*
+        $name:ident, $state:ident, $id:literal {
+            $( $(#[$field_meta:meta])* $field:ident: $field_type:ty ),* $(,)?
+        }
+    } => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct $name {
+            $( $(#[$field_meta])* pub $field: $field_type, )*
+        }
+
+        impl Packet for $name {
+            fn id(&self) -> i32 {
+                $id
+            }
+        }
+
+        impl ServerboundPacket for $name {
+            const STATE: ConnectionState = ConnectionState::$state;
+            const ID: i32 = $id;
+
+            #[allow(unused_mut, unused_variables)]
+            fn read_packet(mut buffer: NormalBuffer) -> ProtocolResult<Self> {
+                Ok(Self {
+                    $(
+                        $field: buffer.read_field(
+                            stringify!($name),
+                            "serverbound",
+                            ConnectionState::$state.name(),
+                            $id,
+                            stringify!($field),
+                        )?,
+                    )*
+                })
+            }
+
+            #[allow(unused_mut)]
+            fn write_packet(&self, mut buffer: NormalBuffer) -> PacketBuffer {
+                $( buffer.write(self.$field.clone()); )*
+                PacketBuffer::with_id(VarInt::from($id), buffer)
+            }
+        }
+    };
+}
+
+/// Defines an enum of every packet the client can send in a state.
+///
+/// Every variant wraps one packet defined with `[serverbound_packet!]`. A packet can be decoded from its ID and fields
+/// with `decode`, so a received packet can be matched exhaustively instead of checking its ID against every packet.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::{buffer::{Buffer, NormalBuffer, PacketBuffer}, error::ProtocolResult, types::VarInt};
+/// use protocol_packets::{serverbound_packet, serverbound_packets, ConnectionState, Packet, ServerboundPacket};
+///
+/// serverbound_packet! {
+///     /// Requests the server list status.
+///     StatusRequestPacket, Status, 0x00 {}
+/// }
+///
+/// serverbound_packet! {
+///     /// The ping request sent by the client.
+///     PingRequestPacket, Status, 0x01 {
+///         payload: i64,
+///     }
+/// }
+///
+/// serverbound_packets! {
+///     /// Every packet the client sends in the `Status` state.
+///     ServerboundStatusPacket, Status {
+///         StatusRequest(StatusRequestPacket),
+///         PingRequest(PingRequestPacket),
+///     }
+/// }
+///
+/// let packet = ServerboundStatusPacket::decode(0x01, NormalBuffer::new(vec![0, 0, 0, 0, 0, 0, 0, 1])).unwrap();
+/// assert_eq!(packet, ServerboundStatusPacket::PingRequest(PingRequestPacket { payload: 1 }));
+/// assert_eq!(packet.id(), 0x01);
+/// assert_eq!(packet.encode().get_ref(), &[0, 0, 0, 0, 0, 0, 0, 1]);
+///
+/// assert!(ServerboundStatusPacket::decode(0x02, NormalBuffer::new(Vec::new())).is_err());
+/// ```
+#[macro_export]
+macro_rules! serverbound_packets {
+    {
+        $(#[$meta:meta])*
+        $name:ident, $state:ident {
+            $( $variant:ident($packet:ty) ),* $(,)?
+        }
+    } => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq)]
+        #[allow(clippy::large_enum_variant)]
+        pub enum $name {
+            $( $variant($packet), )*
+        }
+
+        impl $name {
+            /// The state the packets are sent in.
+            pub const STATE: $crate::ConnectionState = $crate::ConnectionState::$state;
+
+            /// Reads the packet with the given ID from its fields.
+            ///
+            /// # Returns
+            /// The packet, `[ProtocolError::BadPacketId]` if no packet of the state has the ID.
+            pub fn decode(
+                packet_id: i32,
+                buffer: protocol_buf::buffer::NormalBuffer,
+            ) -> protocol_buf::error::ProtocolResult<Self> {
+                $(
+                    if packet_id == <$packet as $crate::ServerboundPacket>::ID {
+                        return <$packet as $crate::ServerboundPacket>::read_packet(buffer)
+                            .map(Self::$variant);
+                    }
+                )*
+                Err(protocol_buf::error::ProtocolError::BadPacketId)
+            }
+
+            /// Writes the packet, like it is sent by the client.
+            pub fn encode(&self) -> protocol_buf::buffer::PacketBuffer {
+                let buffer = protocol_buf::buffer::NormalBuffer::new(Vec::new());
+                match self {
+                    $( Self::$variant(packet) => $crate::ServerboundPacket::write_packet(packet, buffer), )*
+                }
+            }
+        }
+
+        impl $crate::Packet for $name {
+            fn id(&self) -> i32 {
+                match self {
+                    $( Self::$variant(packet) => $crate::Packet::id(packet), )*
+                }
+            }
+        }
+
+        $(
+            impl From<$packet> for $name {
+                fn from(packet: $packet) -> Self {
+                    Self::$variant(packet)
+                }
+            }
+        )*
+    };
+}
+
+/// Defines an enum of every packet the server can send in a state.
+///
+/// Every variant wraps one packet defined with `[clientbound_packet!]`. The enum is a `[ClientboundPacket]` itself,
+/// so any packet of the state can be passed around and sent as one type.
+/// Clientbound packets are only written by the server, so the enum can't decode packets.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::{buffer::{Buffer, NormalBuffer, PacketBuffer}, types::VarInt};
+/// use protocol_packets::{clientbound_packet, clientbound_packets, ClientboundPacket, Packet};
+///
+/// clientbound_packet! {
+///     /// The response to a ping request.
+///     PongResponsePacket, 0x01 {
+///         payload: i64,
+///     }
+/// }
+///
+/// clientbound_packets! {
+///     /// Every packet the server sends in the `Status` state.
+///     ClientboundStatusPacket, Status {
+///         PongResponse(PongResponsePacket),
+///     }
+/// }
+///
+/// let packet = ClientboundStatusPacket::from(PongResponsePacket { payload: 1 });
+/// assert_eq!(packet.id(), 0x01);
+/// assert_eq!(packet.encode().get_ref(), &[0, 0, 0, 0, 0, 0, 0, 1]);
+/// ```
+#[macro_export]
+macro_rules! clientbound_packets {
+    {
+        $(#[$meta:meta])*
+        $name:ident, $state:ident {
+            $( $variant:ident($packet:ty) ),* $(,)?
+        }
+    } => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq)]
+        #[allow(clippy::large_enum_variant)]
+        pub enum $name {
+            $( $variant($packet), )*
+        }
+
+        impl $name {
+            /// The state the packets are sent in.
+            pub const STATE: $crate::ConnectionState = $crate::ConnectionState::$state;
+
+            /// Writes the packet, like it is sent by the server.
+            pub fn encode(&self) -> protocol_buf::buffer::PacketBuffer {
+                $crate::ClientboundPacket::write_packet(
+                    self,
+                    protocol_buf::buffer::NormalBuffer::new(Vec::new()),
+                )
+            }
+        }
+
+        impl $crate::Packet for $name {
+            fn id(&self) -> i32 {
+                match self {
+                    $( Self::$variant(packet) => $crate::Packet::id(packet), )*
+                }
+            }
+
+            fn compression_hint(&self) -> protocol_buf::compression::CompressionHint {
+                match self {
+                    $( Self::$variant(packet) => $crate::Packet::compression_hint(packet), )*
+                }
+            }
+        }
+
+        impl $crate::ClientboundPacket for $name {
+            fn write_packet(
+                &self,
+                buffer: protocol_buf::buffer::NormalBuffer,
+            ) -> protocol_buf::buffer::PacketBuffer {
+                match self {
+                    $( Self::$variant(packet) => $crate::ClientboundPacket::write_packet(packet, buffer), )*
+                }
+            }
+        }
+
+        $(
+            impl From<$packet> for $name {
+                fn from(packet: $packet) -> Self {
+                    Self::$variant(packet)
+                }
+            }
+        )*
+    };
+}
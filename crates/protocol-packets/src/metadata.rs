@@ -0,0 +1,502 @@
+use protocol_buf::{
+    nbt::NbtTag,
+    text::Component,
+    types::{GlobalPosition, Position, PrefixedOptional, VarInt, VarLong},
+    ToNetwork,
+};
+
+use crate::{item::Slot, particle::ParticleOptions};
+
+/// The index of the flags every entity has, like whether it is sneaking or on fire.
+pub const ENTITY_FLAGS_INDEX: u8 = 0;
+
+/// The index of the custom name every entity has, which is an optional text component.
+pub const CUSTOM_NAME_INDEX: u8 = 2;
+
+/// The index of whether the custom name of an entity is always shown.
+pub const CUSTOM_NAME_VISIBLE_INDEX: u8 = 3;
+
+/// The index of the pose every entity has.
+pub const POSE_INDEX: u8 = 6;
+
+/// The index of the skin parts a player displays, like the cape or the hat layer.
+pub const PLAYER_SKIN_PARTS_INDEX: u8 = 17;
+
+/// The index of the main hand of a player, `0` for left and `1` for right.
+pub const PLAYER_MAIN_HAND_INDEX: u8 = 18;
+
+/// The entity flag that is set while the entity is on fire.
+pub const FLAG_ON_FIRE: u8 = 0x01;
+
+/// The entity flag that is set while the entity is sneaking.
+pub const FLAG_SNEAKING: u8 = 0x02;
+
+/// The entity flag that is set while the entity is sprinting.
+pub const FLAG_SPRINTING: u8 = 0x08;
+
+/// The entity flag that is set while the entity is swimming.
+pub const FLAG_SWIMMING: u8 = 0x10;
+
+/// The entity flag that is set while the entity is invisible.
+pub const FLAG_INVISIBLE: u8 = 0x20;
+
+/// The entity flag that is set while the entity is glowing.
+pub const FLAG_GLOWING: u8 = 0x40;
+
+/// The entity flag that is set while the entity is flying with an elytra.
+pub const FLAG_FALL_FLYING: u8 = 0x80;
+
+/// The marker that ends the metadata of an entity.
+const END_OF_METADATA: u8 = 0xFF;
+
+/// The pose of an entity, which defines its hitbox and how it is rendered.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::ToNetwork;
+/// use protocol_packets::metadata::Pose;
+///
+/// assert_eq!(Pose::Sneaking.to_network(), vec![5]);
+/// assert_eq!(Pose::Sneaking.player_dimensions(), (0.6, 1.5));
+/// assert!(Pose::Swimming.player_eye_height() < Pose::Standing.player_eye_height());
+/// ```
+///
+/// # Variants
+/// - `Standing` - The default pose.
+/// - `FallFlying` - The entity is flying with an elytra.
+/// - `Sleeping` - The entity is lying in a bed.
+/// - `Swimming` - The entity is swimming or crawling.
+/// - `SpinAttack` - The entity is using a riptide trident.
+/// - `Sneaking` - The entity is sneaking.
+/// - `LongJumping` - A goat is jumping.
+/// - `Dying` - The entity died.
+/// - `Croaking` - A frog is croaking.
+/// - `UsingTongue` - A frog is using its tongue.
+/// - `Sitting` - A camel is sitting.
+/// - `Roaring` - A warden is roaring.
+/// - `Sniffing` - A warden is sniffing.
+/// - `Emerging` - A warden is emerging from the ground.
+/// - `Digging` - A warden is digging into the ground.
+/// - `Sliding` - A breeze is sliding.
+/// - `Shooting` - A breeze is shooting.
+/// - `Inhaling` - A breeze is inhaling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Pose {
+    #[default]
+    Standing,
+    FallFlying,
+    Sleeping,
+    Swimming,
+    SpinAttack,
+    Sneaking,
+    LongJumping,
+    Dying,
+    Croaking,
+    UsingTongue,
+    Sitting,
+    Roaring,
+    Sniffing,
+    Emerging,
+    Digging,
+    Sliding,
+    Shooting,
+    Inhaling,
+}
+
+impl Pose {
+    /// Returns the width and the height of the hitbox of a player in this pose.
+    ///
+    /// Poses players can't be in use the standing hitbox.
+    pub const fn player_dimensions(&self) -> (f64, f64) {
+        match self {
+            Self::Sneaking => (0.6, 1.5),
+            Self::FallFlying | Self::Swimming | Self::SpinAttack => (0.6, 0.6),
+            Self::Sleeping | Self::Dying => (0.2, 0.2),
+            _ => (0.6, 1.8),
+        }
+    }
+
+    /// Returns the height of the eyes of a player in this pose, measured from the feet.
+    pub const fn player_eye_height(&self) -> f64 {
+        match self {
+            Self::Sneaking => 1.27,
+            Self::FallFlying | Self::Swimming | Self::SpinAttack => 0.4,
+            Self::Sleeping | Self::Dying => 0.2,
+            _ => 1.62,
+        }
+    }
+}
+
+impl ToNetwork for Pose {
+    fn to_network(&self) -> Vec<u8> {
+        VarInt::from(*self as i32).to_network()
+    }
+}
+
+/// The type of a metadata value, the ID of every type is written in front of the value.
+///
+/// # Examples
+/// ```rust
+/// use protocol_packets::metadata::MetadataType;
+///
+/// assert_eq!(MetadataType::OptionalTextComponent.id(), 6);
+/// assert_eq!(MetadataType::from_id(21), Some(MetadataType::Pose));
+/// assert_eq!(MetadataType::from_id(31), None);
+/// ```
+///
+/// # Variants
+/// The variants are the types of 1.21, in the order of their IDs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MetadataType {
+    Byte,
+    VarInt,
+    VarLong,
+    Float,
+    String,
+    TextComponent,
+    OptionalTextComponent,
+    Slot,
+    Boolean,
+    Rotations,
+    Position,
+    OptionalPosition,
+    Direction,
+    OptionalUuid,
+    BlockState,
+    OptionalBlockState,
+    Nbt,
+    Particle,
+    Particles,
+    VillagerData,
+    OptionalVarInt,
+    Pose,
+    CatVariant,
+    WolfVariant,
+    FrogVariant,
+    OptionalGlobalPosition,
+    PaintingVariant,
+    SnifferState,
+    ArmadilloState,
+    Vector3,
+    Quaternion,
+}
+
+impl MetadataType {
+    /// Every type, indexed by its ID.
+    const ALL: [Self; 31] = [
+        Self::Byte,
+        Self::VarInt,
+        Self::VarLong,
+        Self::Float,
+        Self::String,
+        Self::TextComponent,
+        Self::OptionalTextComponent,
+        Self::Slot,
+        Self::Boolean,
+        Self::Rotations,
+        Self::Position,
+        Self::OptionalPosition,
+        Self::Direction,
+        Self::OptionalUuid,
+        Self::BlockState,
+        Self::OptionalBlockState,
+        Self::Nbt,
+        Self::Particle,
+        Self::Particles,
+        Self::VillagerData,
+        Self::OptionalVarInt,
+        Self::Pose,
+        Self::CatVariant,
+        Self::WolfVariant,
+        Self::FrogVariant,
+        Self::OptionalGlobalPosition,
+        Self::PaintingVariant,
+        Self::SnifferState,
+        Self::ArmadilloState,
+        Self::Vector3,
+        Self::Quaternion,
+    ];
+
+    /// Returns the ID of the type.
+    pub const fn id(&self) -> i32 {
+        *self as i32
+    }
+
+    /// Returns the type with the given ID.
+    pub fn from_id(id: i32) -> Option<Self> {
+        Self::ALL.get(usize::try_from(id).ok()?).copied()
+    }
+}
+
+/// A value of the metadata of an entity, every value is written together with its type.
+///
+/// Values that are optional are `None` if they aren't set, like an entity without a custom name.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::{text::Component, ToNetwork};
+/// use protocol_packets::metadata::{MetadataType, MetadataValue};
+///
+/// let name = MetadataValue::OptionalTextComponent(Some(Component::text("Boat")));
+/// assert_eq!(name.metadata_type(), MetadataType::OptionalTextComponent);
+///
+/// assert_eq!(MetadataValue::OptionalTextComponent(None).to_network(), vec![6, 0]);
+/// assert_eq!(MetadataValue::OptionalVarInt(Some(4)).to_network(), vec![20, 5]);
+/// ```
+///
+/// # Variants
+/// - `Byte` - A byte, like the entity flags or the displayed skin parts.
+/// - `VarInt` - A `[VarInt]`, like the air supply of an entity.
+/// - `VarLong` - A `[VarLong]`.
+/// - `Float` - A float, like the health of a living entity.
+/// - `String` - A string.
+/// - `TextComponent` - A text component.
+/// - `OptionalTextComponent` - An optional text component, like the custom name of an entity.
+/// - `Slot` - An item, like the item shown by an item frame.
+/// - `Boolean` - A boolean, like whether the entity is silent.
+/// - `Rotations` - The X, Y and Z rotation in degrees, like the pose of an armor stand.
+/// - `Position` - A block position.
+/// - `OptionalPosition` - An optional block position, like the beam target of an end crystal.
+/// - `Direction` - A direction, from `0` for down to `5` for east, like the attached face of a shulker.
+/// - `OptionalUuid` - An optional UUID, like the owner of a tamed animal.
+/// - `BlockState` - The ID of a block state.
+/// - `OptionalBlockState` - The ID of an optional block state, like the block carried by an enderman.
+/// - `Nbt` - A NBT tag, like the shoulder entities of a player.
+/// - `Particle` - The ID of a particle type together with its options.
+/// - `Particles` - A list of particles, like the effect particles of a living entity.
+/// - `VillagerData` - The type, the profession and the level of a villager.
+/// - `OptionalVarInt` - An optional `[VarInt]`, like the ID of the entity a warden is angry at.
+/// - `Pose` - The `[Pose]` of the entity.
+/// - `CatVariant` - The ID of a cat variant.
+/// - `WolfVariant` - The ID of a wolf variant in the `minecraft:wolf_variant` registry.
+/// - `FrogVariant` - The ID of a frog variant.
+/// - `OptionalGlobalPosition` - An optional position in a dimension, like the target of a lodestone compass.
+/// - `PaintingVariant` - The ID of a painting variant in the `minecraft:painting_variant` registry.
+/// - `SnifferState` - The state of a sniffer, like whether it is digging.
+/// - `ArmadilloState` - The state of an armadillo, like whether it is rolled up.
+/// - `Vector3` - A vector, like the translation of a display entity.
+/// - `Quaternion` - A rotation, like the rotation of a display entity.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetadataValue {
+    Byte(u8),
+    VarInt(i32),
+    VarLong(i64),
+    Float(f32),
+    String(String),
+    TextComponent(Component),
+    OptionalTextComponent(Option<Component>),
+    Slot(Slot),
+    Boolean(bool),
+    Rotations([f32; 3]),
+    Position(Position),
+    OptionalPosition(Option<Position>),
+    Direction(i32),
+    OptionalUuid(Option<u128>),
+    BlockState(i32),
+    OptionalBlockState(Option<i32>),
+    Nbt(NbtTag),
+    Particle(i32, ParticleOptions),
+    Particles(Vec<(i32, ParticleOptions)>),
+    VillagerData {
+        villager_type: i32,
+        profession: i32,
+        level: i32,
+    },
+    OptionalVarInt(Option<i32>),
+    Pose(Pose),
+    CatVariant(i32),
+    WolfVariant(i32),
+    FrogVariant(i32),
+    OptionalGlobalPosition(Option<GlobalPosition>),
+    PaintingVariant(i32),
+    SnifferState(i32),
+    ArmadilloState(i32),
+    Vector3([f32; 3]),
+    Quaternion([f32; 4]),
+}
+
+impl MetadataValue {
+    /// Returns the type of the value.
+    pub const fn metadata_type(&self) -> MetadataType {
+        match self {
+            Self::Byte(_) => MetadataType::Byte,
+            Self::VarInt(_) => MetadataType::VarInt,
+            Self::VarLong(_) => MetadataType::VarLong,
+            Self::Float(_) => MetadataType::Float,
+            Self::String(_) => MetadataType::String,
+            Self::TextComponent(_) => MetadataType::TextComponent,
+            Self::OptionalTextComponent(_) => MetadataType::OptionalTextComponent,
+            Self::Slot(_) => MetadataType::Slot,
+            Self::Boolean(_) => MetadataType::Boolean,
+            Self::Rotations(_) => MetadataType::Rotations,
+            Self::Position(_) => MetadataType::Position,
+            Self::OptionalPosition(_) => MetadataType::OptionalPosition,
+            Self::Direction(_) => MetadataType::Direction,
+            Self::OptionalUuid(_) => MetadataType::OptionalUuid,
+            Self::BlockState(_) => MetadataType::BlockState,
+            Self::OptionalBlockState(_) => MetadataType::OptionalBlockState,
+            Self::Nbt(_) => MetadataType::Nbt,
+            Self::Particle(..) => MetadataType::Particle,
+            Self::Particles(_) => MetadataType::Particles,
+            Self::VillagerData { .. } => MetadataType::VillagerData,
+            Self::OptionalVarInt(_) => MetadataType::OptionalVarInt,
+            Self::Pose(_) => MetadataType::Pose,
+            Self::CatVariant(_) => MetadataType::CatVariant,
+            Self::WolfVariant(_) => MetadataType::WolfVariant,
+            Self::FrogVariant(_) => MetadataType::FrogVariant,
+            Self::OptionalGlobalPosition(_) => MetadataType::OptionalGlobalPosition,
+            Self::PaintingVariant(_) => MetadataType::PaintingVariant,
+            Self::SnifferState(_) => MetadataType::SnifferState,
+            Self::ArmadilloState(_) => MetadataType::ArmadilloState,
+            Self::Vector3(_) => MetadataType::Vector3,
+            Self::Quaternion(_) => MetadataType::Quaternion,
+        }
+    }
+
+    /// Returns the ID of the type of the value.
+    pub const fn type_id(&self) -> i32 {
+        self.metadata_type().id()
+    }
+}
+
+/// Writes the type of the value followed by the value.
+///
+/// Optional block states and optional `[VarInt]`s are written as `0` if they are missing, the other optionals are prefixed with a boolean.
+impl ToNetwork for MetadataValue {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = VarInt::from(self.type_id()).to_network();
+        bytes.extend_from_slice(&match self {
+            Self::Byte(value) => value.to_network(),
+            Self::VarInt(value)
+            | Self::Direction(value)
+            | Self::BlockState(value)
+            | Self::CatVariant(value)
+            | Self::WolfVariant(value)
+            | Self::FrogVariant(value)
+            | Self::PaintingVariant(value)
+            | Self::SnifferState(value)
+            | Self::ArmadilloState(value) => VarInt::from(*value).to_network(),
+            Self::VarLong(value) => VarLong::from(*value).to_network(),
+            Self::Float(value) => value.to_network(),
+            Self::String(value) => value.to_network(),
+            Self::TextComponent(component) => component.to_network(),
+            Self::OptionalTextComponent(component) => {
+                PrefixedOptional(component.clone()).to_network()
+            }
+            Self::Slot(slot) => slot.to_network(),
+            Self::Boolean(value) => value.to_network(),
+            Self::Rotations(values) | Self::Vector3(values) => floats(values),
+            Self::Position(position) => position.to_network(),
+            Self::OptionalPosition(position) => PrefixedOptional(*position).to_network(),
+            Self::OptionalUuid(uuid) => PrefixedOptional(*uuid).to_network(),
+            Self::OptionalBlockState(state) => VarInt::from(state.unwrap_or(0)).to_network(),
+            Self::Nbt(tag) => tag.to_network(),
+            Self::Particle(id, options) => particle(*id, options),
+            Self::Particles(particles) => {
+                let mut bytes = VarInt::from(particles.len() as i32).to_network();
+                for (id, options) in particles {
+                    bytes.extend_from_slice(&particle(*id, options));
+                }
+                bytes
+            }
+            Self::VillagerData {
+                villager_type,
+                profession,
+                level,
+            } => [villager_type, profession, level]
+                .iter()
+                .flat_map(|value| VarInt::from(**value).to_network())
+                .collect(),
+            Self::OptionalVarInt(value) => {
+                VarInt::from(value.map_or(0, |value| value + 1)).to_network()
+            }
+            Self::Pose(pose) => pose.to_network(),
+            Self::OptionalGlobalPosition(position) => {
+                PrefixedOptional(position.clone()).to_network()
+            }
+            Self::Quaternion(values) => floats(values),
+        });
+        bytes
+    }
+}
+
+/// Writes every float of an array, without a length.
+fn floats(values: &[f32]) -> Vec<u8> {
+    values.iter().flat_map(|value| value.to_network()).collect()
+}
+
+/// Writes the ID of a particle type followed by its options.
+fn particle(id: i32, options: &ParticleOptions) -> Vec<u8> {
+    let mut bytes = VarInt::from(id).to_network();
+    bytes.extend_from_slice(&options.to_network());
+    bytes
+}
+
+/// The metadata of an entity, this only has to contain the values that changed.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::ToNetwork;
+/// use protocol_packets::metadata::{EntityMetadata, MetadataValue, Pose, FLAG_SNEAKING, ENTITY_FLAGS_INDEX, POSE_INDEX};
+///
+/// let metadata = EntityMetadata::new()
+///     .with(ENTITY_FLAGS_INDEX, MetadataValue::Byte(FLAG_SNEAKING))
+///     .with(POSE_INDEX, MetadataValue::Pose(Pose::Sneaking));
+///
+/// assert_eq!(metadata.get(POSE_INDEX), Some(&MetadataValue::Pose(Pose::Sneaking)));
+/// assert_eq!(metadata.to_network(), vec![0, 0, 0x02, 6, 21, 5, 0xFF]);
+/// ```
+///
+/// # Fields
+/// - `entries` - The index and the value of every entry, ordered by the index.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EntityMetadata {
+    entries: Vec<(u8, MetadataValue)>,
+}
+
+impl EntityMetadata {
+    /// Creates a new empty `[EntityMetadata]`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value at the given index.
+    pub fn with(mut self, index: u8, value: MetadataValue) -> Self {
+        self.set(index, value);
+        self
+    }
+
+    /// Sets the value at the given index, replacing the previous value.
+    pub fn set(&mut self, index: u8, value: MetadataValue) {
+        match self.entries.binary_search_by_key(&index, |(i, _)| *i) {
+            Ok(position) => self.entries[position].1 = value,
+            Err(position) => self.entries.insert(position, (index, value)),
+        }
+    }
+
+    /// Returns the value at the given index.
+    pub fn get(&self, index: u8) -> Option<&MetadataValue> {
+        self.entries
+            .binary_search_by_key(&index, |(i, _)| *i)
+            .ok()
+            .map(|position| &self.entries[position].1)
+    }
+
+    /// Returns whether the metadata doesn't contain any values.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Writes every entry as its index followed by the value, the metadata ends with `0xFF`.
+impl ToNetwork for EntityMetadata {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for (index, value) in &self.entries {
+            bytes.push(*index);
+            bytes.extend_from_slice(&value.to_network());
+        }
+        bytes.push(END_OF_METADATA);
+        bytes
+    }
+}
@@ -0,0 +1,136 @@
+use protocol_buf::{
+    nbt::{NbtCompound, NbtTag},
+    types::VarInt,
+    ToNetwork,
+};
+
+/// The options of a particle, this is the particle type together with its extra data.
+///
+/// The same options are used for the ambient particles of a biome, which are sent as NBT,
+/// and for the `[ParticlePacket]`, which only sends the extra data after the particle ID.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::{nbt::NbtTag, ToNetwork};
+/// use protocol_packets::particle::ParticleOptions;
+///
+/// let dust = ParticleOptions::Dust { color: [1.0, 0.0, 0.0], scale: 2.0 };
+///
+/// assert_eq!(dust.particle_type(), "minecraft:dust");
+/// assert_eq!(dust.to_network().len(), 16);
+///
+/// let NbtTag::Compound(nbt) = dust.to_nbt() else { panic!() };
+/// assert_eq!(nbt.get("scale"), Some(&NbtTag::Float(2.0)));
+/// ```
+///
+/// # Variants
+/// - `Simple` - A particle without extra data, like `minecraft:ash` or `minecraft:white_ash`.
+/// - `Dust` - A colored dust particle, the color is RGB from `0` to `1` and the scale is clamped between `0.01` and `4`.
+/// - `Block` - A particle using the texture of a block, like `minecraft:block`, `minecraft:falling_dust` or `minecraft:dust_pillar`.
+/// - `Item` - A particle using the texture of an item.
+/// - `EntityEffect` - A potion effect particle with an ARGB color.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParticleOptions {
+    Simple {
+        particle_type: String,
+    },
+    Dust {
+        color: [f32; 3],
+        scale: f32,
+    },
+    Block {
+        particle_type: String,
+        block: String,
+        block_state: i32,
+    },
+    Item {
+        item: String,
+        item_id: i32,
+        count: i32,
+    },
+    EntityEffect {
+        color: i32,
+    },
+}
+
+impl ParticleOptions {
+    /// Creates the options for a particle without extra data.
+    pub fn simple(particle_type: impl Into<String>) -> Self {
+        Self::Simple {
+            particle_type: particle_type.into(),
+        }
+    }
+
+    /// Returns the identifier of the particle type, like `minecraft:dust`.
+    pub fn particle_type(&self) -> &str {
+        match self {
+            Self::Simple { particle_type } | Self::Block { particle_type, .. } => particle_type,
+            Self::Dust { .. } => "minecraft:dust",
+            Self::Item { .. } => "minecraft:item",
+            Self::EntityEffect { .. } => "minecraft:entity_effect",
+        }
+    }
+
+    /// Serializes the options the way they are stored in the biome registry.
+    ///
+    /// Particles without extra data are stored as a compound with only their type.
+    pub fn to_nbt(&self) -> NbtTag {
+        let compound = NbtCompound::new().with("type", self.particle_type());
+
+        let compound = match self {
+            Self::Simple { .. } => compound,
+            Self::Dust { color, scale } => compound
+                .with(
+                    "color",
+                    color.iter().map(|c| NbtTag::Float(*c)).collect::<Vec<_>>(),
+                )
+                .with("scale", *scale),
+            Self::Block { block, .. } => compound.with(
+                "block_state",
+                NbtCompound::new().with("Name", block.as_str()),
+            ),
+            Self::Item { item, count, .. } => compound.with(
+                "item",
+                NbtCompound::new()
+                    .with("id", item.as_str())
+                    .with("count", *count),
+            ),
+            Self::EntityEffect { color } => compound.with("color", *color),
+        };
+
+        NbtTag::Compound(compound)
+    }
+}
+
+/// Writes the extra data of the particle, the particle ID itself is written by the `[ParticlePacket]`.
+///
+/// Items are written as a slot without any components.
+impl ToNetwork for ParticleOptions {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        match self {
+            Self::Simple { .. } => {}
+            Self::Dust { color, scale } => {
+                for component in color {
+                    bytes.extend_from_slice(&component.to_network());
+                }
+                bytes.extend_from_slice(&scale.clamp(0.01, 4.0).to_network());
+            }
+            Self::Block { block_state, .. } => {
+                bytes.extend_from_slice(&VarInt::from(*block_state).to_network());
+            }
+            Self::Item { item_id, count, .. } => {
+                bytes.extend_from_slice(&VarInt::from(*count).to_network());
+                if *count > 0 {
+                    bytes.extend_from_slice(&VarInt::from(*item_id).to_network());
+                    bytes.extend_from_slice(&VarInt::from(0).to_network());
+                    bytes.extend_from_slice(&VarInt::from(0).to_network());
+                }
+            }
+            Self::EntityEffect { color } => bytes.extend_from_slice(&color.to_network()),
+        }
+
+        bytes
+    }
+}
@@ -0,0 +1,154 @@
+use protocol_buf::{
+    text::Component,
+    types::{PrefixedOptional, VarInt},
+    ToNetwork,
+};
+
+use crate::v1_21::login::ProfileProperty;
+
+/// The action that adds a player to the player list of the client.
+pub const ADD_PLAYER: u8 = 0x01;
+
+/// The action that sets the chat session of a player, which is used to verify its signed messages.
+pub const INITIALIZE_CHAT: u8 = 0x02;
+
+/// The action that changes the game mode shown for a player.
+pub const UPDATE_GAME_MODE: u8 = 0x04;
+
+/// The action that changes whether a player is shown in the tab list.
+pub const UPDATE_LISTED: u8 = 0x08;
+
+/// The action that changes the latency shown for a player.
+pub const UPDATE_LATENCY: u8 = 0x10;
+
+/// The action that changes the name shown for a player in the tab list.
+pub const UPDATE_DISPLAY_NAME: u8 = 0x20;
+
+/// A change to a single entry of the player list.
+///
+/// # Variants
+/// - `AddPlayer` - Adds the player with the given username and game profile properties, like its skin.
+/// - `UpdateGameMode` - Changes the game mode, spectators are shown at the bottom of the tab list.
+/// - `UpdateListed` - Changes whether the player is shown in the tab list.
+/// - `UpdateLatency` - Changes the latency in milliseconds, which is shown as bars.
+/// - `UpdateDisplayName` - Changes the name shown in the tab list, `None` shows the username.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlayerAction {
+    AddPlayer {
+        name: String,
+        properties: Vec<ProfileProperty>,
+    },
+    UpdateGameMode(i32),
+    UpdateListed(bool),
+    UpdateLatency(i32),
+    UpdateDisplayName(Option<Component>),
+}
+
+impl PlayerAction {
+    /// Returns the bit of the action in the action mask of the `[PlayerInfoUpdatePacket]`.
+    pub fn mask(&self) -> u8 {
+        match self {
+            Self::AddPlayer { .. } => ADD_PLAYER,
+            Self::UpdateGameMode(_) => UPDATE_GAME_MODE,
+            Self::UpdateListed(_) => UPDATE_LISTED,
+            Self::UpdateLatency(_) => UPDATE_LATENCY,
+            Self::UpdateDisplayName(_) => UPDATE_DISPLAY_NAME,
+        }
+    }
+}
+
+/// Writes the data of the action, the action itself is part of the action mask.
+impl ToNetwork for PlayerAction {
+    fn to_network(&self) -> Vec<u8> {
+        match self {
+            Self::AddPlayer { name, properties } => {
+                let mut bytes = name.to_network();
+                bytes.extend_from_slice(&properties.to_network());
+                bytes
+            }
+            Self::UpdateGameMode(game_mode) => VarInt::from(*game_mode).to_network(),
+            Self::UpdateListed(listed) => listed.to_network(),
+            Self::UpdateLatency(latency) => VarInt::from(*latency).to_network(),
+            Self::UpdateDisplayName(name) => PrefixedOptional(name.clone()).to_network(),
+        }
+    }
+}
+
+/// The changes to the player list entry of a single player.
+///
+/// The actions are kept in the order of their bits, which is the order the client reads them in.
+/// Every entry of a `[PlayerInfoUpdatePacket]` has to contain the same actions.
+///
+/// # Examples
+/// ```rust
+/// use protocol_packets::player_info::{PlayerAction, PlayerInfo, UPDATE_LATENCY, UPDATE_LISTED};
+///
+/// let info = PlayerInfo::new(1)
+///     .with(PlayerAction::UpdateLatency(20))
+///     .with(PlayerAction::UpdateListed(true))
+///     .with(PlayerAction::UpdateLatency(50));
+///
+/// assert_eq!(info.mask(), UPDATE_LISTED | UPDATE_LATENCY);
+/// assert_eq!(info.actions, vec![PlayerAction::UpdateListed(true), PlayerAction::UpdateLatency(50)]);
+/// ```
+///
+/// # Fields
+/// - `uuid` - The UUID of the player, as a 128 bit number.
+/// - `actions` - The changes to the entry, sorted by their bit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerInfo {
+    pub uuid: u128,
+    pub actions: Vec<PlayerAction>,
+}
+
+impl PlayerInfo {
+    /// Creates a new `[PlayerInfo]` without any actions.
+    pub fn new(uuid: u128) -> Self {
+        Self {
+            uuid,
+            actions: Vec::new(),
+        }
+    }
+
+    /// Adds an action to the entry, an action of the same kind is replaced.
+    pub fn with(mut self, action: PlayerAction) -> Self {
+        let mask = action.mask();
+        match self.actions.binary_search_by_key(&mask, PlayerAction::mask) {
+            Ok(index) => self.actions[index] = action,
+            Err(index) => self.actions.insert(index, action),
+        }
+        self
+    }
+
+    /// Creates the entry that adds a player to the player list and shows it in the tab list.
+    ///
+    /// # Parameters
+    /// - `uuid` - The UUID of the player.
+    /// - `name` - The username of the player.
+    /// - `properties` - The properties of the game profile of the player, like its skin.
+    pub fn add(uuid: u128, name: impl Into<String>, properties: Vec<ProfileProperty>) -> Self {
+        Self::new(uuid)
+            .with(PlayerAction::AddPlayer {
+                name: name.into(),
+                properties,
+            })
+            .with(PlayerAction::UpdateListed(true))
+    }
+
+    /// Returns the bits of all actions of the entry.
+    pub fn mask(&self) -> u8 {
+        self.actions
+            .iter()
+            .fold(0, |mask, action| mask | action.mask())
+    }
+}
+
+impl ToNetwork for PlayerInfo {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = self.uuid.to_network();
+        for action in &self.actions {
+            bytes.extend_from_slice(&action.to_network());
+        }
+        bytes
+    }
+}
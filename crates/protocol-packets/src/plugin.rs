@@ -0,0 +1,29 @@
+use std::io::Cursor;
+
+use protocol_buf::{error::ProtocolResult, FromNetwork, ToNetwork};
+
+/// The channel the client and the server use to tell each other their brand, like `vanilla` or `fabric`.
+pub const BRAND_CHANNEL: &str = "minecraft:brand";
+
+/// Writes the data of a `minecraft:brand` plugin message, which is the brand as a string.
+///
+/// # Examples
+/// ```rust
+/// use protocol_packets::plugin::{decode_brand, encode_brand};
+///
+/// let data = encode_brand("minecraft-protocol");
+///
+/// assert_eq!(data[0], 18);
+/// assert_eq!(decode_brand(&data).unwrap(), "minecraft-protocol");
+/// ```
+pub fn encode_brand(brand: &str) -> Vec<u8> {
+    brand.to_string().to_network()
+}
+
+/// Reads the brand from the data of a `minecraft:brand` plugin message.
+///
+/// # Returns
+/// An error if the data isn't a valid string.
+pub fn decode_brand(data: &[u8]) -> ProtocolResult<String> {
+    String::from_network(&mut Cursor::new(data))
+}
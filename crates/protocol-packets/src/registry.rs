@@ -0,0 +1,767 @@
+use std::io::Cursor;
+
+use protocol_buf::{
+    error::{ProtocolError, ProtocolResult},
+    nbt::{Nbt, NbtCompound, NbtTag},
+    types::PrefixedOptional,
+    FromNetwork, ToNetwork,
+};
+use serde_json::Value;
+
+use crate::{damage_type::DamageType, v1_21::configuration::RegistryDataPacket};
+
+/// The registries that are sent to the client during the configuration, together with their vanilla entries.
+///
+/// The vanilla entries are sent without data, the client loads them from its core pack.
+/// Damage types are sent with their data, see `[DamageType::vanilla_defaults]`.
+const VANILLA_REGISTRIES: &[(&str, &[&str])] = &[
+    (
+        "minecraft:banner_pattern",
+        &[
+            "base",
+            "border",
+            "bricks",
+            "circle",
+            "creeper",
+            "cross",
+            "curly_border",
+            "diagonal_left",
+            "diagonal_right",
+            "diagonal_up_left",
+            "diagonal_up_right",
+            "flow",
+            "flower",
+            "globe",
+            "gradient",
+            "gradient_up",
+            "guster",
+            "half_horizontal",
+            "half_horizontal_bottom",
+            "half_vertical",
+            "half_vertical_right",
+            "mojang",
+            "piglin",
+            "rhombus",
+            "skull",
+            "small_stripes",
+            "square_bottom_left",
+            "square_bottom_right",
+            "square_top_left",
+            "square_top_right",
+            "straight_cross",
+            "stripe_bottom",
+            "stripe_center",
+            "stripe_downleft",
+            "stripe_downright",
+            "stripe_left",
+            "stripe_middle",
+            "stripe_right",
+            "stripe_top",
+            "triangle_bottom",
+            "triangle_top",
+            "triangles_bottom",
+            "triangles_top",
+        ],
+    ),
+    (
+        "minecraft:chat_type",
+        &[
+            "chat",
+            "emote_command",
+            "msg_command_incoming",
+            "msg_command_outgoing",
+            "say_command",
+            "team_msg_command_incoming",
+            "team_msg_command_outgoing",
+        ],
+    ),
+    (
+        "minecraft:dimension_type",
+        &["overworld", "overworld_caves", "the_end", "the_nether"],
+    ),
+    (
+        "minecraft:enchantment",
+        &[
+            "aqua_affinity",
+            "bane_of_arthropods",
+            "binding_curse",
+            "blast_protection",
+            "breach",
+            "channeling",
+            "density",
+            "depth_strider",
+            "efficiency",
+            "feather_falling",
+            "fire_aspect",
+            "fire_protection",
+            "flame",
+            "fortune",
+            "frost_walker",
+            "impaling",
+            "infinity",
+            "knockback",
+            "looting",
+            "loyalty",
+            "luck_of_the_sea",
+            "lure",
+            "mending",
+            "multishot",
+            "piercing",
+            "power",
+            "projectile_protection",
+            "protection",
+            "punch",
+            "quick_charge",
+            "respiration",
+            "riptide",
+            "sharpness",
+            "silk_touch",
+            "smite",
+            "soul_speed",
+            "sweeping_edge",
+            "swift_sneak",
+            "thorns",
+            "unbreaking",
+            "vanishing_curse",
+            "wind_burst",
+        ],
+    ),
+    (
+        "minecraft:jukebox_song",
+        &[
+            "11",
+            "13",
+            "5",
+            "blocks",
+            "cat",
+            "chirp",
+            "creator",
+            "creator_music_box",
+            "far",
+            "mall",
+            "mellohi",
+            "otherside",
+            "pigstep",
+            "precipice",
+            "relic",
+            "stal",
+            "strad",
+            "wait",
+            "ward",
+        ],
+    ),
+    (
+        "minecraft:painting_variant",
+        &[
+            "alban",
+            "aztec",
+            "aztec2",
+            "backyard",
+            "baroque",
+            "bomb",
+            "bouquet",
+            "burning_skull",
+            "bust",
+            "cavebird",
+            "changing",
+            "cotan",
+            "courbet",
+            "creebet",
+            "donkey_kong",
+            "earth",
+            "endboss",
+            "fern",
+            "fighters",
+            "finding",
+            "fire",
+            "graham",
+            "humble",
+            "kebab",
+            "lowmist",
+            "match",
+            "meditative",
+            "orb",
+            "owlemons",
+            "passage",
+            "pigscene",
+            "plant",
+            "pointer",
+            "pond",
+            "pool",
+            "prairie_ride",
+            "sea",
+            "skeleton",
+            "skull_and_roses",
+            "stage",
+            "sunflowers",
+            "sunset",
+            "tides",
+            "unpacked",
+            "void",
+            "wanderer",
+            "wasteland",
+            "water",
+            "wind",
+            "wither",
+        ],
+    ),
+    (
+        "minecraft:trim_material",
+        &[
+            "amethyst",
+            "copper",
+            "diamond",
+            "emerald",
+            "gold",
+            "iron",
+            "lapis",
+            "netherite",
+            "quartz",
+            "redstone",
+        ],
+    ),
+    (
+        "minecraft:trim_pattern",
+        &[
+            "bolt",
+            "coast",
+            "dune",
+            "eye",
+            "flow",
+            "host",
+            "raiser",
+            "rib",
+            "sentry",
+            "shaper",
+            "silence",
+            "snout",
+            "spire",
+            "tide",
+            "vex",
+            "ward",
+            "wayfinder",
+            "wild",
+        ],
+    ),
+    (
+        "minecraft:wolf_variant",
+        &[
+            "ashen", "black", "chestnut", "pale", "rusty", "snowy", "spotted", "striped", "woods",
+        ],
+    ),
+    (
+        "minecraft:worldgen/biome",
+        &[
+            "badlands",
+            "bamboo_jungle",
+            "basalt_deltas",
+            "beach",
+            "birch_forest",
+            "cherry_grove",
+            "cold_ocean",
+            "crimson_forest",
+            "dark_forest",
+            "deep_cold_ocean",
+            "deep_dark",
+            "deep_frozen_ocean",
+            "deep_lukewarm_ocean",
+            "deep_ocean",
+            "desert",
+            "dripstone_caves",
+            "end_barrens",
+            "end_highlands",
+            "end_midlands",
+            "eroded_badlands",
+            "flower_forest",
+            "forest",
+            "frozen_ocean",
+            "frozen_peaks",
+            "frozen_river",
+            "grove",
+            "ice_spikes",
+            "jagged_peaks",
+            "jungle",
+            "lukewarm_ocean",
+            "lush_caves",
+            "mangrove_swamp",
+            "meadow",
+            "mushroom_fields",
+            "nether_wastes",
+            "ocean",
+            "old_growth_birch_forest",
+            "old_growth_pine_taiga",
+            "old_growth_spruce_taiga",
+            "plains",
+            "river",
+            "savanna",
+            "savanna_plateau",
+            "small_end_islands",
+            "snowy_beach",
+            "snowy_plains",
+            "snowy_slopes",
+            "snowy_taiga",
+            "soul_sand_valley",
+            "sparse_jungle",
+            "stony_peaks",
+            "stony_shore",
+            "sunflower_plains",
+            "swamp",
+            "taiga",
+            "the_end",
+            "the_void",
+            "warm_ocean",
+            "warped_forest",
+            "windswept_forest",
+            "windswept_gravelly_hills",
+            "windswept_hills",
+            "windswept_savanna",
+            "wooded_badlands",
+        ],
+    ),
+];
+
+/// A data pack the client and the server both know, entries of known packs can be sent without their data.
+///
+/// # Fields
+/// - `namespace` - The namespace of the pack, like `minecraft`.
+/// - `id` - The ID of the pack, like `core`.
+/// - `version` - The version of the pack, like `1.21`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KnownPack {
+    pub namespace: String,
+    pub id: String,
+    pub version: String,
+}
+
+impl KnownPack {
+    /// Returns the vanilla data pack, which contains the vanilla registry entries.
+    pub fn core() -> Self {
+        Self {
+            namespace: "minecraft".to_string(),
+            id: "core".to_string(),
+            version: "1.21".to_string(),
+        }
+    }
+}
+
+impl ToNetwork for KnownPack {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = self.namespace.to_network();
+        bytes.extend_from_slice(&self.id.to_network());
+        bytes.extend_from_slice(&self.version.to_network());
+        bytes
+    }
+}
+
+impl FromNetwork for KnownPack {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        Ok(Self {
+            namespace: String::from_network(buffer)?,
+            id: String::from_network(buffer)?,
+            version: String::from_network(buffer)?,
+        })
+    }
+}
+
+/// A single entry of a registry.
+///
+/// # Fields
+/// - `id` - The identifier of the entry, like `minecraft:plains`.
+/// - `data` - The data of the entry, `None` if the client should load it from a known pack.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegistryEntry {
+    pub id: String,
+    pub data: PrefixedOptional<Nbt>,
+}
+
+impl RegistryEntry {
+    /// Creates a new `[RegistryEntry]` that is loaded from a known pack.
+    pub fn known(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            data: PrefixedOptional(None),
+        }
+    }
+
+    /// Creates a new `[RegistryEntry]` with the given data.
+    pub fn new(id: impl Into<String>, data: impl Into<Nbt>) -> Self {
+        Self {
+            id: id.into(),
+            data: PrefixedOptional(Some(data.into())),
+        }
+    }
+}
+
+impl ToNetwork for RegistryEntry {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = self.id.to_network();
+        bytes.extend_from_slice(&self.data.to_network());
+        bytes
+    }
+}
+
+/// A registry that is synchronized with the client, like the biomes or the damage types.
+///
+/// The client assigns the IDs of the entries in the order they are sent.
+///
+/// # Fields
+/// - `id` - The identifier of the registry, like `minecraft:worldgen/biome`.
+/// - `entries` - The entries of the registry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Registry {
+    pub id: String,
+    pub entries: Vec<RegistryEntry>,
+}
+
+impl Registry {
+    /// Creates a new empty `[Registry]`.
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds an entry, an entry with the same identifier is replaced but keeps its ID.
+    pub fn insert(&mut self, entry: RegistryEntry) {
+        match self.entries.iter_mut().find(|e| e.id == entry.id) {
+            Some(existing) => *existing = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    /// Returns the ID of the entry with the given identifier, this is the ID used in packets.
+    pub fn id_of(&self, id: &str) -> Option<i32> {
+        self.entries
+            .iter()
+            .position(|entry| entry.id == id)
+            .map(|index| index as i32)
+    }
+
+    /// Returns the `[RegistryDataPacket]` that sends this registry to the client.
+    pub fn to_packet(&self) -> RegistryDataPacket {
+        RegistryDataPacket {
+            registry_id: self.id.clone(),
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+/// All registries that are sent to the client during the configuration.
+///
+/// By default the vanilla entries are sent without data, so the client loads them from its `[KnownPack::core]`.
+/// Registries can also be loaded from a JSON dump, so the data is sent with the entries.
+///
+/// # Examples
+/// ```rust
+/// use protocol_packets::registry::Registries;
+///
+/// let json = r#"{
+///     "minecraft:worldgen/biome": {
+///         "minecraft:plains": { "has_precipitation": true, "temperature": 0.8 },
+///         "minecraft:desert": { "has_precipitation": false, "temperature": 2.0 }
+///     }
+/// }"#;
+///
+/// let mut registries = Registries::vanilla();
+/// registries.load_json(json).unwrap();
+///
+/// let biomes = registries.get("minecraft:worldgen/biome").unwrap();
+/// assert_eq!(biomes.entries.len(), 64);
+/// assert!(biomes.entries[biomes.id_of("minecraft:plains").unwrap() as usize].data.is_some());
+/// assert!(registries.get("minecraft:damage_type").is_some());
+/// ```
+///
+/// # Fields
+/// - `registries` - The registries, in the order they are sent.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Registries {
+    registries: Vec<Registry>,
+}
+
+impl Registries {
+    /// Creates a new `[Registries]` without any registries.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the vanilla registries, with every entry of the core pack.
+    ///
+    /// The damage types are sent with their data, as the client disconnects if one of them is missing.
+    pub fn vanilla() -> Self {
+        let mut registries: Vec<Registry> = VANILLA_REGISTRIES
+            .iter()
+            .map(|(id, entries)| Registry {
+                id: id.to_string(),
+                entries: entries
+                    .iter()
+                    .map(|entry| RegistryEntry::known(format!("minecraft:{}", entry)))
+                    .collect(),
+            })
+            .collect();
+        registries.push(DamageType::vanilla_registry());
+
+        Self { registries }
+    }
+
+    /// Parses registries from a JSON dump.
+    ///
+    /// Two formats are supported. Either every registry is an object of entry identifiers to their data,
+    /// or every registry has a `value` array with the `name` and `element` of every entry, like the registry codec dumps.
+    pub fn from_json(json: &str) -> ProtocolResult<Self> {
+        let mut registries = Self::new();
+        registries.load_json(json)?;
+        Ok(registries)
+    }
+
+    /// Loads registries from a JSON dump, see `[Registries::from_json]` for the format.
+    ///
+    /// The entries are added to the existing registries, entries that already exist are replaced.
+    pub fn load_json(&mut self, json: &str) -> ProtocolResult<()> {
+        let value: Value =
+            serde_json::from_str(json).map_err(|e| ProtocolError::InvalidData(e.to_string()))?;
+        let Value::Object(dump) = value else {
+            return Err(ProtocolError::InvalidData(
+                "registry dump has to be an object".to_string(),
+            ));
+        };
+
+        for (registry_id, registry) in &dump {
+            let entries: Vec<(&str, &Value)> = match registry.get("value") {
+                Some(Value::Array(values)) => values
+                    .iter()
+                    .filter_map(|value| Some((value.get("name")?.as_str()?, value.get("element")?)))
+                    .collect(),
+                _ => match registry {
+                    Value::Object(entries) => entries
+                        .iter()
+                        .map(|(id, value)| (id.as_str(), value))
+                        .collect(),
+                    _ => {
+                        return Err(ProtocolError::InvalidData(format!(
+                            "registry {} has to be an object",
+                            registry_id
+                        )))
+                    }
+                },
+            };
+
+            let registry = self.get_or_insert(registry_id);
+            for (id, data) in entries {
+                let entry = match NbtTag::from_json(data) {
+                    Some(NbtTag::Compound(data)) => RegistryEntry::new(id, data),
+                    _ => {
+                        return Err(ProtocolError::InvalidData(format!(
+                            "entry {} of registry {} has to be an object",
+                            id, registry.id
+                        )))
+                    }
+                };
+                registry.insert(entry);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds a registry, a registry with the same identifier is replaced.
+    pub fn insert(&mut self, registry: Registry) {
+        match self.registries.iter_mut().find(|r| r.id == registry.id) {
+            Some(existing) => *existing = registry,
+            None => self.registries.push(registry),
+        }
+    }
+
+    /// Returns the registry with the given identifier.
+    pub fn get(&self, id: &str) -> Option<&Registry> {
+        self.registries.iter().find(|registry| registry.id == id)
+    }
+
+    /// Returns the registry with the given identifier, mutable.
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut Registry> {
+        self.registries
+            .iter_mut()
+            .find(|registry| registry.id == id)
+    }
+
+    /// Returns an iterator over all registries.
+    pub fn iter(&self) -> impl Iterator<Item = &Registry> {
+        self.registries.iter()
+    }
+
+    /// Returns the packets that send all registries to the client.
+    pub fn to_packets(&self) -> Vec<RegistryDataPacket> {
+        self.registries.iter().map(Registry::to_packet).collect()
+    }
+
+    /// Returns the data packs the client has to know to load the entries that are sent without data.
+    ///
+    /// This is `[KnownPack::core]` if any entry is loaded from a known pack, otherwise no pack is needed.
+    pub fn known_packs(&self) -> Vec<KnownPack> {
+        let needs_core = self
+            .registries
+            .iter()
+            .flat_map(|registry| &registry.entries)
+            .any(|entry| entry.data.is_none());
+        match needs_core {
+            true => vec![KnownPack::core()],
+            false => Vec::new(),
+        }
+    }
+
+    fn get_or_insert(&mut self, id: &str) -> &mut Registry {
+        match self
+            .registries
+            .iter()
+            .position(|registry| registry.id == id)
+        {
+            Some(index) => &mut self.registries[index],
+            None => {
+                self.registries.push(Registry::new(id));
+                self.registries.last_mut().unwrap()
+            }
+        }
+    }
+}
+
+/// A typed registry entry, like a `[DamageType]`, that can be added to a `[RegistryBuilder]`.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::nbt::NbtCompound;
+/// use protocol_packets::registry::RegistryValue;
+///
+/// struct PaintingVariant {
+///     id: String,
+///     asset_id: String,
+///     width: i32,
+///     height: i32,
+/// }
+///
+/// impl RegistryValue for PaintingVariant {
+///     const REGISTRY: &'static str = "minecraft:painting_variant";
+///
+///     fn identifier(&self) -> &str {
+///         &self.id
+///     }
+///
+///     fn to_nbt(&self) -> NbtCompound {
+///         NbtCompound::new()
+///             .with("asset_id", self.asset_id.as_str())
+///             .with("width", self.width)
+///             .with("height", self.height)
+///     }
+/// }
+/// ```
+pub trait RegistryValue {
+    /// The identifier of the registry the entries belong to, like `minecraft:damage_type`.
+    const REGISTRY: &'static str;
+
+    /// Returns the identifier of the entry, like `minecraft:fall`.
+    fn identifier(&self) -> &str;
+
+    /// Serializes the entry the way it is stored in the registry.
+    fn to_nbt(&self) -> NbtCompound;
+}
+
+/// Collects custom registry entries, which are added to the registries that are sent during the configuration.
+///
+/// Entries can be added to any registry, registries that don't exist yet are created and sent after the
+/// existing ones. Custom entries are always sent with their data, as the client can't load them from a known pack.
+/// An entry that replaces an existing entry keeps its ID, new entries get the next free ID in the order they were added.
+/// Identifiers without a namespace are put into the `minecraft` namespace.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::nbt::NbtCompound;
+/// use protocol_packets::damage_type::{DamageScaling, DamageType};
+/// use protocol_packets::registry::{KnownPack, RegistryBuilder};
+///
+/// let registries = RegistryBuilder::new()
+///     .value(&DamageType::new("custom:laser", "laser", DamageScaling::Never, 0.0))
+///     .entry("custom:spell", "fireball", NbtCompound::new().with("damage", 4.0f32))
+///     .entry("minecraft:worldgen/biome", "custom:crystal", NbtCompound::new().with("temperature", 0.5f32))
+///     .build();
+///
+/// let damage_types = registries.get("minecraft:damage_type").unwrap();
+/// assert_eq!(damage_types.id_of("custom:laser"), Some(damage_types.entries.len() as i32 - 1));
+///
+/// let spells = registries.get("custom:spell").unwrap();
+/// assert_eq!(spells.id_of("minecraft:fireball"), Some(0));
+///
+/// // The vanilla biomes are still loaded from the core pack.
+/// let biomes = registries.get("minecraft:worldgen/biome").unwrap();
+/// assert_eq!(biomes.id_of("custom:crystal"), Some(64));
+/// assert_eq!(registries.known_packs(), vec![KnownPack::core()]);
+///
+/// // Without the vanilla registries, no pack is needed.
+/// let registries = RegistryBuilder::new()
+///     .entry("custom:spell", "custom:fireball", NbtCompound::new())
+///     .apply(Default::default());
+/// assert!(registries.known_packs().is_empty());
+/// ```
+///
+/// # Fields
+/// - `entries` - The registry identifier and the entry of every added entry, in the order they were added.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RegistryBuilder {
+    entries: Vec<(String, RegistryEntry)>,
+}
+
+impl RegistryBuilder {
+    /// Creates a new `[RegistryBuilder]` without any entries.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an entry with the given data.
+    ///
+    /// # Parameters
+    /// - `registry` - The identifier of the registry, like `minecraft:worldgen/biome`.
+    /// - `id` - The identifier of the entry.
+    /// - `data` - The data of the entry.
+    pub fn entry(mut self, registry: &str, id: &str, data: impl Into<Nbt>) -> Self {
+        self.entries.push((
+            namespaced(registry),
+            RegistryEntry::new(namespaced(id), data),
+        ));
+        self
+    }
+
+    /// Adds a typed entry to its registry.
+    pub fn value<T: RegistryValue>(self, value: &T) -> Self {
+        self.entry(T::REGISTRY, value.identifier(), value.to_nbt())
+    }
+
+    /// Returns the amount of added entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns whether no entries were added.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Adds the entries to the given registries and returns them.
+    pub fn apply(self, mut registries: Registries) -> Registries {
+        for (registry, entry) in self.entries {
+            registries.get_or_insert(&registry).insert(entry);
+        }
+
+        registries
+    }
+
+    /// Adds the entries to the vanilla registries, see `[Registries::vanilla]`.
+    pub fn build(self) -> Registries {
+        self.apply(Registries::vanilla())
+    }
+}
+
+/// Puts an identifier without a namespace into the `minecraft` namespace.
+fn namespaced(id: &str) -> String {
+    match id.contains(':') {
+        true => id.to_string(),
+        false => format!("minecraft:{}", id),
+    }
+}
@@ -0,0 +1,63 @@
+use std::io::Cursor;
+
+use protocol_buf::{error::ProtocolResult, types::BoundedString, FromNetwork, ToNetwork};
+
+/// The most details the client accepts, it disconnects if more are sent.
+pub const MAX_REPORT_DETAILS: usize = 32;
+
+/// The most characters the title of a detail can have.
+pub const MAX_TITLE_LENGTH: usize = 128;
+
+/// The most characters the description of a detail can have.
+pub const MAX_DESCRIPTION_LENGTH: usize = 4096;
+
+/// A detail the client adds to its crash reports and disconnect reports, like the name of the server.
+///
+/// # Examples
+/// ```rust
+/// use protocol_packets::report::ReportDetail;
+///
+/// let detail = ReportDetail::new("Server", "lobby-1").unwrap();
+/// assert_eq!(detail.title.as_str(), "Server");
+///
+/// assert!(ReportDetail::new("x".repeat(129), "too long").is_err());
+/// ```
+///
+/// # Fields
+/// - `title` - The title of the detail, at most `[MAX_TITLE_LENGTH]` characters.
+/// - `description` - The description of the detail, at most `[MAX_DESCRIPTION_LENGTH]` characters.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReportDetail {
+    pub title: BoundedString<MAX_TITLE_LENGTH>,
+    pub description: BoundedString<MAX_DESCRIPTION_LENGTH>,
+}
+
+impl ReportDetail {
+    /// Creates a new `[ReportDetail]`.
+    ///
+    /// # Returns
+    /// An error if the title or the description is too long.
+    pub fn new(title: impl Into<String>, description: impl Into<String>) -> ProtocolResult<Self> {
+        Ok(Self {
+            title: BoundedString::new(title)?,
+            description: BoundedString::new(description)?,
+        })
+    }
+}
+
+impl ToNetwork for ReportDetail {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = self.title.to_network();
+        bytes.extend_from_slice(&self.description.to_network());
+        bytes
+    }
+}
+
+impl FromNetwork for ReportDetail {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        Ok(Self {
+            title: BoundedString::from_network(buffer)?,
+            description: BoundedString::from_network(buffer)?,
+        })
+    }
+}
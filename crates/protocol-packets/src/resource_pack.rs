@@ -0,0 +1,72 @@
+/// The status of a resource pack the client sends while it downloads and loads the pack.
+///
+/// A pack the player accepts is answered with `Accepted`, `Downloaded` and `Loaded`, every other status ends the
+/// download of the pack.
+///
+/// # Examples
+/// ```rust
+/// use protocol_packets::resource_pack::ResourcePackStatus;
+///
+/// let status = ResourcePackStatus::from_id(1).unwrap();
+///
+/// assert_eq!(status, ResourcePackStatus::Declined);
+/// assert!(status.is_failure());
+/// assert!(!ResourcePackStatus::Accepted.is_final());
+/// assert!(ResourcePackStatus::from_id(8).is_none());
+/// ```
+///
+/// # Variants
+/// - `Loaded` - The pack was downloaded and loaded.
+/// - `Declined` - The player declined the pack.
+/// - `FailedDownload` - The pack couldn't be downloaded.
+/// - `Accepted` - The player accepted the pack, the download started.
+/// - `Downloaded` - The pack was downloaded, it is loaded next.
+/// - `InvalidUrl` - The URL of the pack isn't valid.
+/// - `FailedReload` - The pack was downloaded, but the resources couldn't be reloaded.
+/// - `Discarded` - The pack was removed before it was loaded, like when another pack with the same UUID was sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourcePackStatus {
+    Loaded,
+    Declined,
+    FailedDownload,
+    Accepted,
+    Downloaded,
+    InvalidUrl,
+    FailedReload,
+    Discarded,
+}
+
+impl ResourcePackStatus {
+    /// Returns the status with the given ID, `None` if the ID is unknown.
+    pub const fn from_id(id: i32) -> Option<Self> {
+        Some(match id {
+            0 => Self::Loaded,
+            1 => Self::Declined,
+            2 => Self::FailedDownload,
+            3 => Self::Accepted,
+            4 => Self::Downloaded,
+            5 => Self::InvalidUrl,
+            6 => Self::FailedReload,
+            7 => Self::Discarded,
+            _ => return None,
+        })
+    }
+
+    /// Returns the ID of the status, as it is sent in packets.
+    pub const fn id(self) -> i32 {
+        self as i32
+    }
+
+    /// Returns whether the status ends the download, no other status of the pack follows.
+    pub const fn is_final(self) -> bool {
+        !matches!(self, Self::Accepted | Self::Downloaded)
+    }
+
+    /// Returns whether the pack isn't used by the client, because it was declined or couldn't be loaded.
+    pub const fn is_failure(self) -> bool {
+        matches!(
+            self,
+            Self::Declined | Self::FailedDownload | Self::InvalidUrl | Self::FailedReload
+        )
+    }
+}
@@ -0,0 +1,154 @@
+use protocol_buf::{
+    nbt::{NbtCompound, NbtTag},
+    text::Component,
+    types::{PrefixedOptional, VarInt},
+    ToNetwork,
+};
+
+/// How the scores of an objective are shown, like in the sidebar or the player list.
+///
+/// Number formats were added in Minecraft 1.20.3, a blank format hides the red numbers of the sidebar.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::{nbt::NbtTag, text::Component, ToNetwork};
+/// use protocol_packets::scoreboard::NumberFormat;
+///
+/// assert_eq!(NumberFormat::Blank.to_network(), vec![0]);
+///
+/// // Only the style of the component is sent, its text is ignored.
+/// let styled = NumberFormat::Styled(Component::text("").color("gold").bold(true));
+/// assert_eq!(styled.style().get("color"), Some(&NbtTag::String("gold".into())));
+/// assert!(styled.style().get("text").is_none());
+///
+/// assert_eq!(NumberFormat::Fixed(Component::text("-")).to_network()[0], 2);
+/// ```
+///
+/// # Variants
+/// - `Blank` - The scores aren't shown.
+/// - `Styled` - The scores are shown with the style of the component, like its color.
+/// - `Fixed` - The component is shown instead of the scores.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NumberFormat {
+    Blank,
+    Styled(Component),
+    Fixed(Component),
+}
+
+impl NumberFormat {
+    /// Returns the style the scores are shown with, this is empty unless the format is `Styled`.
+    pub fn style(&self) -> NbtCompound {
+        let Self::Styled(component) = self else {
+            return NbtCompound::new();
+        };
+        let NbtTag::Compound(compound) = component.to_nbt() else {
+            return NbtCompound::new();
+        };
+
+        let mut style = NbtCompound::new();
+        for (name, tag) in compound.iter().filter(|(name, _)| *name != "text") {
+            style.insert(name, tag.clone());
+        }
+        style
+    }
+}
+
+impl ToNetwork for NumberFormat {
+    fn to_network(&self) -> Vec<u8> {
+        match self {
+            Self::Blank => VarInt::from(0).to_network(),
+            Self::Styled(_) => {
+                let mut bytes = VarInt::from(1).to_network();
+                bytes.extend_from_slice(&NbtTag::Compound(self.style()).to_network());
+                bytes
+            }
+            Self::Fixed(component) => {
+                let mut bytes = VarInt::from(2).to_network();
+                bytes.extend_from_slice(&component.to_network());
+                bytes
+            }
+        }
+    }
+}
+
+/// How the client renders the scores of an objective in the player list.
+///
+/// # Variants
+/// - `Integer` - The scores are shown as numbers.
+/// - `Hearts` - The scores are shown as hearts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScoreRenderType {
+    #[default]
+    Integer,
+    Hearts,
+}
+
+impl ToNetwork for ScoreRenderType {
+    fn to_network(&self) -> Vec<u8> {
+        VarInt::from(*self as i32).to_network()
+    }
+}
+
+/// What happens to an objective in the `[UpdateObjectivesPacket]`.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::{text::Component, ToNetwork};
+/// use protocol_packets::scoreboard::{NumberFormat, ObjectiveAction, ScoreRenderType};
+///
+/// let action = ObjectiveAction::Create {
+///     display_name: Component::text("Kills"),
+///     render_type: ScoreRenderType::Integer,
+///     number_format: Some(NumberFormat::Blank),
+/// };
+/// let bytes = action.to_network();
+///
+/// assert_eq!(bytes[0], 0);
+/// assert_eq!(&bytes[bytes.len() - 3..], &[0, 1, 0]);
+/// assert_eq!(ObjectiveAction::Remove.to_network(), vec![1]);
+/// ```
+///
+/// # Variants
+/// - `Create` - Creates the objective with the given display name, render type and number format.
+/// - `Remove` - Removes the objective.
+/// - `Update` - Changes the display name, render type and number format of the objective.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObjectiveAction {
+    Create {
+        display_name: Component,
+        render_type: ScoreRenderType,
+        number_format: Option<NumberFormat>,
+    },
+    Remove,
+    Update {
+        display_name: Component,
+        render_type: ScoreRenderType,
+        number_format: Option<NumberFormat>,
+    },
+}
+
+impl ToNetwork for ObjectiveAction {
+    fn to_network(&self) -> Vec<u8> {
+        let (mode, fields) = match self {
+            Self::Create {
+                display_name,
+                render_type,
+                number_format,
+            } => (0, Some((display_name, render_type, number_format))),
+            Self::Remove => (1, None),
+            Self::Update {
+                display_name,
+                render_type,
+                number_format,
+            } => (2, Some((display_name, render_type, number_format))),
+        };
+
+        let mut bytes = vec![mode];
+        if let Some((display_name, render_type, number_format)) = fields {
+            bytes.extend_from_slice(&display_name.to_network());
+            bytes.extend_from_slice(&render_type.to_network());
+            bytes.extend_from_slice(&PrefixedOptional(number_format.clone()).to_network());
+        }
+        bytes
+    }
+}
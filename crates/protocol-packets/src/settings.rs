@@ -0,0 +1,92 @@
+use std::io::Cursor;
+
+use protocol_buf::{
+    error::ProtocolResult,
+    types::{BoundedString, VarInt},
+    FromNetwork, ToNetwork,
+};
+
+/// The main hand of a player that is left handed.
+pub const MAIN_HAND_LEFT: i32 = 0;
+
+/// The main hand of a player that is right handed, this is the default.
+pub const MAIN_HAND_RIGHT: i32 = 1;
+
+/// The settings of the client, sent while configuring and whenever the player changes them.
+///
+/// # Examples
+/// ```rust
+/// use std::io::Cursor;
+/// use protocol_buf::{FromNetwork, ToNetwork};
+/// use protocol_packets::settings::{ClientInformation, MAIN_HAND_LEFT};
+///
+/// let information = ClientInformation { main_hand: MAIN_HAND_LEFT, ..ClientInformation::default() };
+/// let mut buffer = Cursor::new(information.to_network());
+///
+/// assert_eq!(ClientInformation::from_network(&mut buffer).unwrap(), information);
+/// ```
+///
+/// # Fields
+/// - `locale` - The language of the client, like `en_us`, up to 16 characters.
+/// - `view_distance` - The render distance of the client in chunks.
+/// - `chat_mode` - Which messages are shown, `0` for all, `1` for commands only and `2` for none.
+/// - `chat_colors` - Whether colors are shown in the chat.
+/// - `displayed_skin_parts` - The layers of the skin that are shown, like the cape or the hat.
+/// - `main_hand` - The main hand of the player, `[MAIN_HAND_LEFT]` or `[MAIN_HAND_RIGHT]`.
+/// - `enable_text_filtering` - Whether the text on signs and in books is filtered.
+/// - `allow_server_listings` - Whether the player may be shown in the player sample of the server list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientInformation {
+    pub locale: String,
+    pub view_distance: i8,
+    pub chat_mode: i32,
+    pub chat_colors: bool,
+    pub displayed_skin_parts: u8,
+    pub main_hand: i32,
+    pub enable_text_filtering: bool,
+    pub allow_server_listings: bool,
+}
+
+impl Default for ClientInformation {
+    fn default() -> Self {
+        Self {
+            locale: "en_us".to_string(),
+            view_distance: 10,
+            chat_mode: 0,
+            chat_colors: true,
+            displayed_skin_parts: 0x7F,
+            main_hand: MAIN_HAND_RIGHT,
+            enable_text_filtering: false,
+            allow_server_listings: true,
+        }
+    }
+}
+
+impl ToNetwork for ClientInformation {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = self.locale.to_network();
+        bytes.extend_from_slice(&self.view_distance.to_network());
+        bytes.extend_from_slice(&VarInt::from(self.chat_mode).to_network());
+        bytes.extend_from_slice(&self.chat_colors.to_network());
+        bytes.extend_from_slice(&self.displayed_skin_parts.to_network());
+        bytes.extend_from_slice(&VarInt::from(self.main_hand).to_network());
+        bytes.extend_from_slice(&self.enable_text_filtering.to_network());
+        bytes.extend_from_slice(&self.allow_server_listings.to_network());
+        bytes
+    }
+}
+
+impl FromNetwork for ClientInformation {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        Ok(Self {
+            locale: BoundedString::<16>::from_network(buffer)?.into_inner(),
+            view_distance: i8::from_network(buffer)?,
+            chat_mode: *VarInt::from_network(buffer)?,
+            chat_colors: bool::from_network(buffer)?,
+            displayed_skin_parts: u8::from_network(buffer)?,
+            main_hand: *VarInt::from_network(buffer)?,
+            enable_text_filtering: bool::from_network(buffer)?,
+            allow_server_listings: bool::from_network(buffer)?,
+        })
+    }
+}
@@ -0,0 +1,261 @@
+use protocol_buf::{
+    nbt::{NbtCompound, NbtTag},
+    types::{PrefixedOptional, VarInt},
+    ToNetwork,
+};
+
+/// A sound event, this is the identifier of a sound together with an optional fixed range.
+///
+/// The same type is used for the sounds of a biome, which are sent as NBT,
+/// and for the `[SoundEffectPacket]`, which can send the sound event inline.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::nbt::NbtTag;
+/// use protocol_packets::sound::SoundEvent;
+///
+/// let sound = SoundEvent::new("minecraft:ambient.cave").with_range(16.0);
+///
+/// let NbtTag::Compound(nbt) = sound.to_nbt() else { panic!() };
+/// assert_eq!(nbt.get("sound_id"), Some(&NbtTag::String("minecraft:ambient.cave".into())));
+/// assert_eq!(nbt.get("range"), Some(&NbtTag::Float(16.0)));
+/// ```
+///
+/// # Fields
+/// - `sound_id` - The identifier of the sound, like `minecraft:ambient.cave`.
+/// - `fixed_range` - The range in which the sound can be heard, if this is `None` the range depends on the volume.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoundEvent {
+    pub sound_id: String,
+    pub fixed_range: Option<f32>,
+}
+
+impl SoundEvent {
+    /// Creates a new `[SoundEvent]` without a fixed range.
+    pub fn new(sound_id: impl Into<String>) -> Self {
+        Self {
+            sound_id: sound_id.into(),
+            fixed_range: None,
+        }
+    }
+
+    /// Sets the fixed range of the sound.
+    pub fn with_range(mut self, range: f32) -> Self {
+        self.fixed_range = Some(range);
+        self
+    }
+
+    /// Serializes the sound event the way it is stored in a registry.
+    pub fn to_nbt(&self) -> NbtTag {
+        let mut compound = NbtCompound::new().with("sound_id", self.sound_id.as_str());
+        if let Some(range) = self.fixed_range {
+            compound.insert("range", range);
+        }
+
+        NbtTag::Compound(compound)
+    }
+}
+
+impl ToNetwork for SoundEvent {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = self.sound_id.to_network();
+        bytes.extend_from_slice(&PrefixedOptional::from(self.fixed_range).to_network());
+        bytes
+    }
+}
+
+/// A reference to a sound event, either by its registry ID or inline.
+///
+/// # Variants
+/// - `Registered` - The ID of the sound event in the sound event registry.
+/// - `Inline` - A sound event that is sent as a whole, this can be used for sounds of resource packs.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sound {
+    Registered(i32),
+    Inline(SoundEvent),
+}
+
+/// Writes the ID plus one for registered sounds, or `0` followed by the sound event for inline sounds.
+impl ToNetwork for Sound {
+    fn to_network(&self) -> Vec<u8> {
+        match self {
+            Self::Registered(id) => VarInt::from(id + 1).to_network(),
+            Self::Inline(sound) => {
+                let mut bytes = VarInt::from(0).to_network();
+                bytes.extend_from_slice(&sound.to_network());
+                bytes
+            }
+        }
+    }
+}
+
+/// The category of a sound, the client has a volume slider for each category.
+///
+/// # Variants
+/// - `Master` - Sounds that are only affected by the master volume.
+/// - `Music` - Background music.
+/// - `Record` - Jukeboxes and note blocks.
+/// - `Weather` - Rain and thunder.
+/// - `Block` - Sounds of blocks.
+/// - `Hostile` - Sounds of hostile mobs.
+/// - `Neutral` - Sounds of friendly mobs.
+/// - `Player` - Sounds of players.
+/// - `Ambient` - Ambient sounds, like cave sounds.
+/// - `Voice` - Voice and speech.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoundCategory {
+    Master,
+    Music,
+    Record,
+    Weather,
+    Block,
+    Hostile,
+    Neutral,
+    Player,
+    Ambient,
+    Voice,
+}
+
+impl ToNetwork for SoundCategory {
+    fn to_network(&self) -> Vec<u8> {
+        VarInt::from(*self as i32).to_network()
+    }
+}
+
+/// The mood sound of a biome, which is played randomly in dark places.
+///
+/// # Fields
+/// - `sound` - The sound that is played.
+/// - `tick_delay` - The minimum amount of ticks between two sounds.
+/// - `block_search_extent` - The radius used to search for dark places around the player.
+/// - `offset` - The distance from the player at which the sound is played.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoodSound {
+    pub sound: SoundEvent,
+    pub tick_delay: i32,
+    pub block_search_extent: i32,
+    pub offset: f64,
+}
+
+impl MoodSound {
+    /// Creates the mood sound vanilla uses in every biome.
+    pub fn cave() -> Self {
+        Self {
+            sound: SoundEvent::new("minecraft:ambient.cave"),
+            tick_delay: 6000,
+            block_search_extent: 8,
+            offset: 2.0,
+        }
+    }
+
+    /// Serializes the mood sound the way it is stored in the biome registry.
+    pub fn to_nbt(&self) -> NbtTag {
+        NbtTag::Compound(
+            NbtCompound::new()
+                .with("sound", self.sound.to_nbt())
+                .with("tick_delay", self.tick_delay)
+                .with("block_search_extent", self.block_search_extent)
+                .with("offset", self.offset),
+        )
+    }
+}
+
+/// The additions sound of a biome, which has a chance to be played every tick.
+///
+/// # Fields
+/// - `sound` - The sound that is played.
+/// - `tick_chance` - The chance that the sound is played each tick, from `0` to `1`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdditionsSound {
+    pub sound: SoundEvent,
+    pub tick_chance: f64,
+}
+
+impl AdditionsSound {
+    /// Serializes the additions sound the way it is stored in the biome registry.
+    pub fn to_nbt(&self) -> NbtTag {
+        NbtTag::Compound(
+            NbtCompound::new()
+                .with("sound", self.sound.to_nbt())
+                .with("tick_chance", self.tick_chance),
+        )
+    }
+}
+
+/// The music of a biome.
+///
+/// # Fields
+/// - `sound` - The music that is played.
+/// - `min_delay` - The minimum amount of ticks before the music starts.
+/// - `max_delay` - The maximum amount of ticks before the music starts.
+/// - `replace_current_music` - Whether the music replaces music that is already playing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Music {
+    pub sound: SoundEvent,
+    pub min_delay: i32,
+    pub max_delay: i32,
+    pub replace_current_music: bool,
+}
+
+impl Music {
+    /// Serializes the music the way it is stored in the biome registry.
+    pub fn to_nbt(&self) -> NbtTag {
+        NbtTag::Compound(
+            NbtCompound::new()
+                .with("sound", self.sound.to_nbt())
+                .with("min_delay", self.min_delay)
+                .with("max_delay", self.max_delay)
+                .with("replace_current_music", self.replace_current_music),
+        )
+    }
+}
+
+/// All sounds of a biome. Every sound is optional.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::nbt::NbtTag;
+/// use protocol_packets::sound::{BiomeSounds, MoodSound};
+///
+/// let sounds = BiomeSounds {
+///     mood_sound: Some(MoodSound::cave()),
+///     ..BiomeSounds::default()
+/// };
+///
+/// let effects = sounds.write_nbt(Default::default());
+/// assert!(effects.get("mood_sound").is_some());
+/// assert!(effects.get("music").is_none());
+/// ```
+///
+/// # Fields
+/// - `ambient_sound` - The sound that is played constantly while in the biome.
+/// - `mood_sound` - The sound that is played randomly in dark places.
+/// - `additions_sound` - The sound that has a chance to be played every tick.
+/// - `music` - The music of the biome.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BiomeSounds {
+    pub ambient_sound: Option<SoundEvent>,
+    pub mood_sound: Option<MoodSound>,
+    pub additions_sound: Option<AdditionsSound>,
+    pub music: Option<Music>,
+}
+
+impl BiomeSounds {
+    /// Adds all sounds that are set to the `effects` compound of a biome.
+    pub fn write_nbt(&self, mut effects: NbtCompound) -> NbtCompound {
+        if let Some(sound) = &self.ambient_sound {
+            effects.insert("ambient_sound", sound.to_nbt());
+        }
+        if let Some(sound) = &self.mood_sound {
+            effects.insert("mood_sound", sound.to_nbt());
+        }
+        if let Some(sound) = &self.additions_sound {
+            effects.insert("additions_sound", sound.to_nbt());
+        }
+        if let Some(music) = &self.music {
+            effects.insert("music", music.to_nbt());
+        }
+
+        effects
+    }
+}
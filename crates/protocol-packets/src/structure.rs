@@ -0,0 +1,498 @@
+use std::{collections::HashMap, fmt, fs, io, io::Cursor, path::Path, str::FromStr};
+
+use protocol_buf::{
+    error::{ProtocolError, ProtocolResult},
+    nbt::{Nbt, NbtCompound, NbtTag},
+    types::VarInt,
+    FromNetwork,
+};
+
+use crate::chunk::Chunk;
+
+/// A block state by its name, like `minecraft:oak_stairs[facing=north]`.
+///
+/// Structures store block states by name, so they don't depend on the IDs of a protocol version.
+///
+/// # Examples
+/// ```rust
+/// use protocol_packets::structure::BlockState;
+///
+/// let state = BlockState::new("minecraft:oak_stairs")
+///     .with("facing", "north")
+///     .with("half", "bottom");
+///
+/// assert_eq!(state.property("facing"), Some("north"));
+/// assert_eq!(state.to_string(), "minecraft:oak_stairs[facing=north,half=bottom]");
+/// assert_eq!(BlockState::new("minecraft:stone").to_string(), "minecraft:stone");
+/// ```
+///
+/// # Fields
+/// - `name` - The name of the block.
+/// - `properties` - The properties of the block state and their values.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BlockState {
+    pub name: String,
+    pub properties: Vec<(String, String)>,
+}
+
+impl BlockState {
+    /// Creates a new `[BlockState]` without any properties.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            properties: Vec::new(),
+        }
+    }
+
+    /// Sets a property of the block state, a property with the same name is replaced.
+    pub fn with(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let (name, value) = (name.into(), value.into());
+        match self.properties.iter_mut().find(|(entry, _)| *entry == name) {
+            Some((_, entry)) => *entry = value,
+            None => self.properties.push((name, value)),
+        }
+        self
+    }
+
+    /// Returns the value of a property.
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|(entry, _)| entry == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Reads a block state from an entry of the palette of a structure.
+    fn from_nbt(compound: &NbtCompound) -> ProtocolResult<Self> {
+        let Some(NbtTag::String(name)) = compound.get("Name") else {
+            return Err(invalid("palette entry without a name"));
+        };
+
+        let mut state = Self::new(name.clone());
+        if let Some(NbtTag::Compound(properties)) = compound.get("Properties") {
+            for (property, value) in properties.iter() {
+                let NbtTag::String(value) = value else {
+                    return Err(invalid("block state property that isn't a string"));
+                };
+                state = state.with(property, value.clone());
+            }
+        }
+        Ok(state)
+    }
+}
+
+/// Reads a block state the way the vanilla commands and schematics write it, like `minecraft:oak_stairs[facing=north]`.
+///
+/// # Examples
+/// ```rust
+/// use protocol_packets::structure::BlockState;
+///
+/// let state: BlockState = "minecraft:oak_stairs[facing=north,half=bottom]".parse().unwrap();
+/// assert_eq!(state, BlockState::new("minecraft:oak_stairs").with("facing", "north").with("half", "bottom"));
+/// assert!("minecraft:oak_stairs[facing]".parse::<BlockState>().is_err());
+/// ```
+impl FromStr for BlockState {
+    type Err = ProtocolError;
+
+    fn from_str(value: &str) -> ProtocolResult<Self> {
+        let Some((name, properties)) = value.split_once('[') else {
+            return Ok(Self::new(value));
+        };
+        let properties = properties
+            .strip_suffix(']')
+            .ok_or_else(|| invalid("block state without a closing bracket"))?;
+
+        let mut state = Self::new(name);
+        for property in properties
+            .split(',')
+            .filter(|property| !property.is_empty())
+        {
+            let (name, value) = property
+                .split_once('=')
+                .ok_or_else(|| invalid("block state property without a value"))?;
+            state = state.with(name, value);
+        }
+        Ok(state)
+    }
+}
+
+/// Writes the block state the way the vanilla commands accept it.
+impl fmt::Display for BlockState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)?;
+        if self.properties.is_empty() {
+            return Ok(());
+        }
+
+        let properties: Vec<_> = self
+            .properties
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect();
+        write!(f, "[{}]", properties.join(","))
+    }
+}
+
+/// A single block of a `[Structure]`.
+///
+/// # Fields
+/// - `position` - The position relative to the origin of the structure.
+/// - `state` - The index of the block state in the palette of the structure.
+/// - `nbt` - The data of the block entity, like the items in a chest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructureBlock {
+    pub position: [i32; 3],
+    pub state: usize,
+    pub nbt: Option<NbtCompound>,
+}
+
+/// A structure saved by a structure block, which can be pasted into chunks to build lobbies or minigame maps.
+///
+/// Structures are loaded from the `.nbt` files structure blocks write, or from the Sponge schematics WorldEdit writes. Positions that contain a structure void
+/// aren't part of the structure, so pasting keeps the blocks that are already there. Structures with multiple
+/// palettes, like shipwrecks, are loaded with their first palette. Entities aren't loaded.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::nbt::{Nbt, NbtCompound, NbtTag};
+/// use protocol_packets::{chunk::Chunk, structure::{BlockState, Structure}};
+///
+/// let position = |x, y, z| NbtTag::List(vec![NbtTag::Int(x), NbtTag::Int(y), NbtTag::Int(z)]);
+/// let nbt = Nbt::new(
+///     NbtCompound::new()
+///         .with("size", position(2, 1, 1))
+///         .with("palette", NbtTag::List(vec![
+///             NbtTag::Compound(NbtCompound::new().with("Name", "minecraft:stone")),
+///         ]))
+///         .with("blocks", NbtTag::List(vec![
+///             NbtTag::Compound(NbtCompound::new().with("state", 0).with("pos", position(0, 0, 0))),
+///             NbtTag::Compound(NbtCompound::new().with("state", 0).with("pos", position(1, 0, 0))),
+///         ])),
+/// );
+///
+/// let structure = Structure::from_file_bytes(&nbt.to_file_bytes()).unwrap();
+/// assert_eq!(structure.size, [2, 1, 1]);
+/// assert_eq!(structure.palette, vec![BlockState::new("minecraft:stone")]);
+///
+/// // The structure is pasted at X 15, so it crosses the border of the chunk.
+/// let mut chunk = Chunk::new(0, 0, 24, 0);
+/// let placed = structure.paste(&mut chunk, [15, 0, 0], -64, |state| (state.name == "minecraft:stone").then_some(1));
+///
+/// assert_eq!(placed, 1);
+/// assert_eq!(chunk.get_block(15, 64, 0), 1);
+/// ```
+///
+/// # Fields
+/// - `size` - The size of the structure in blocks.
+/// - `palette` - The block states used by the structure.
+/// - `blocks` - The blocks of the structure.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Structure {
+    pub size: [i32; 3],
+    pub palette: Vec<BlockState>,
+    pub blocks: Vec<StructureBlock>,
+}
+
+impl Structure {
+    /// Loads a structure from a `.nbt` file.
+    ///
+    /// # Parameters
+    /// - `path` - The path of the file.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        Self::from_file_bytes(&bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Reads a structure from the content of a `.nbt` file, which is usually gzip compressed.
+    pub fn from_file_bytes(bytes: &[u8]) -> ProtocolResult<Self> {
+        Self::from_nbt(&Nbt::from_file_bytes(bytes)?)
+    }
+
+    /// Loads a Sponge schematic from a `.schem` file, like the files WorldEdit writes.
+    ///
+    /// # Parameters
+    /// - `path` - The path of the file.
+    pub fn load_schematic(path: impl AsRef<Path>) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        Self::from_schematic_bytes(&bytes)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Reads a Sponge schematic from the content of a `.schem` file, which is usually gzip compressed.
+    pub fn from_schematic_bytes(bytes: &[u8]) -> ProtocolResult<Self> {
+        Self::from_schematic(&Nbt::from_file_bytes(bytes)?)
+    }
+
+    /// Reads a Sponge schematic from its NBT, versions 1 to 3 of the format are supported.
+    ///
+    /// The block states of the schematic are stored by name in the palette of the structure, so they are mapped to the
+    /// block state IDs of the server once the structure is pasted. The offset of the schematic and its entities are ignored.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_buf::nbt::{Nbt, NbtCompound, NbtTag};
+    /// use protocol_packets::structure::{BlockState, Structure};
+    ///
+    /// let palette = NbtCompound::new().with("minecraft:air", 0).with("minecraft:oak_log[axis=y]", 1);
+    /// let schematic = NbtCompound::new()
+    ///     .with("Version", 3)
+    ///     .with("Width", 2i16)
+    ///     .with("Height", 1i16)
+    ///     .with("Length", 1i16)
+    ///     .with("Blocks", NbtCompound::new().with("Palette", palette).with("Data", vec![1i8, 0]));
+    /// let nbt = Nbt::new(NbtCompound::new().with("Schematic", schematic));
+    ///
+    /// let structure = Structure::from_schematic_bytes(&nbt.to_file_bytes()).unwrap();
+    /// let blocks: Vec<_> = structure.blocks_at([0, 64, 0]).map(|(position, state)| (position, state.to_string())).collect();
+    ///
+    /// assert_eq!(structure.size, [2, 1, 1]);
+    /// assert_eq!(blocks, vec![
+    ///     ([0, 64, 0], "minecraft:oak_log[axis=y]".to_string()),
+    ///     ([1, 64, 0], "minecraft:air".to_string()),
+    /// ]);
+    /// ```
+    pub fn from_schematic(nbt: &Nbt) -> ProtocolResult<Self> {
+        // Version 3 wraps the schematic into a compound and moves the blocks into their own compound.
+        let (schematic, blocks) = match nbt.root.get("Schematic") {
+            Some(NbtTag::Compound(schematic)) => match schematic.get("Blocks") {
+                Some(NbtTag::Compound(blocks)) => (schematic, blocks),
+                _ => return Err(invalid("schematic without blocks")),
+            },
+            _ => (&nbt.root, &nbt.root),
+        };
+
+        let dimension = |name| match schematic.get(name) {
+            Some(NbtTag::Short(value)) => Ok(i32::from(*value as u16)),
+            _ => Err(invalid("schematic without a size")),
+        };
+        let size = [
+            dimension("Width")?,
+            dimension("Height")?,
+            dimension("Length")?,
+        ];
+
+        let Some(NbtTag::Compound(entries)) = blocks.get("Palette") else {
+            return Err(invalid("schematic without a palette"));
+        };
+        let mut entries = entries
+            .iter()
+            .map(|(state, index)| match index {
+                NbtTag::Int(index) => Ok((*index, state.parse::<BlockState>()?)),
+                _ => Err(invalid("palette index that isn't an int")),
+            })
+            .collect::<ProtocolResult<Vec<_>>>()?;
+        entries.sort_by_key(|(index, _)| *index);
+        let indices: HashMap<_, _> = entries
+            .iter()
+            .enumerate()
+            .map(|(position, (index, _))| (*index, position))
+            .collect();
+        let palette = entries.into_iter().map(|(_, state)| state).collect();
+
+        let data = match (blocks.get("Data"), blocks.get("BlockData")) {
+            (Some(NbtTag::ByteArray(data)), _) | (None, Some(NbtTag::ByteArray(data))) => data,
+            _ => return Err(invalid("schematic without block data")),
+        };
+        let data: Vec<u8> = data.iter().map(|byte| *byte as u8).collect();
+        let mut data = Cursor::new(data);
+
+        let [width, height, length] = size;
+        let mut blocks_by_position = Vec::with_capacity((width * height * length) as usize);
+        for y in 0..height {
+            for z in 0..length {
+                for x in 0..width {
+                    let index = *VarInt::from_network(&mut data)?;
+                    let state = *indices
+                        .get(&index)
+                        .ok_or_else(|| invalid("block with an unknown state"))?;
+                    blocks_by_position.push(StructureBlock {
+                        position: [x, y, z],
+                        state,
+                        nbt: None,
+                    });
+                }
+            }
+        }
+
+        let block_entities = match (blocks.get("BlockEntities"), schematic.get("TileEntities")) {
+            (Some(NbtTag::List(entities)), _) | (None, Some(NbtTag::List(entities))) => {
+                entities.as_slice()
+            }
+            _ => &[],
+        };
+        for entity in block_entities {
+            let NbtTag::Compound(entity) = entity else {
+                return Err(invalid("block entity that isn't a compound"));
+            };
+            let Some(NbtTag::IntArray(position)) = entity.get("Pos") else {
+                return Err(invalid("block entity without a position"));
+            };
+            let &[x, y, z] = position.as_slice() else {
+                return Err(invalid("position that isn't 3 ints"));
+            };
+            if !(0..width).contains(&x) || !(0..height).contains(&y) || !(0..length).contains(&z) {
+                return Err(invalid("block entity outside of the schematic"));
+            }
+
+            // Version 3 moves the data into its own compound, the older versions store it next to the position.
+            let mut nbt = match entity.get("Data") {
+                Some(NbtTag::Compound(data)) => data.clone(),
+                _ => {
+                    let mut data = NbtCompound::new();
+                    for (name, tag) in entity
+                        .iter()
+                        .filter(|(name, _)| !matches!(*name, "Pos" | "Id"))
+                    {
+                        data.insert(name, tag.clone());
+                    }
+                    data
+                }
+            };
+            if let Some(NbtTag::String(id)) = entity.get("Id") {
+                nbt.insert("id", id.clone());
+            }
+
+            let index = (x + z * width + y * width * length) as usize;
+            blocks_by_position[index].nbt = Some(nbt);
+        }
+
+        Ok(Self {
+            size,
+            palette,
+            blocks: blocks_by_position,
+        })
+    }
+
+    /// Reads a structure from its NBT.
+    pub fn from_nbt(nbt: &Nbt) -> ProtocolResult<Self> {
+        let root = &nbt.root;
+        let size = position(root.get("size"))?;
+
+        let palette = match (root.get("palette"), root.get("palettes")) {
+            (Some(NbtTag::List(palette)), _) => palette,
+            (None, Some(NbtTag::List(palettes))) => match palettes.first() {
+                Some(NbtTag::List(palette)) => palette,
+                _ => return Err(invalid("structure without a palette")),
+            },
+            _ => return Err(invalid("structure without a palette")),
+        };
+        let palette = palette
+            .iter()
+            .map(|entry| match entry {
+                NbtTag::Compound(compound) => BlockState::from_nbt(compound),
+                _ => Err(invalid("palette entry that isn't a compound")),
+            })
+            .collect::<ProtocolResult<Vec<_>>>()?;
+
+        let blocks = match root.get("blocks") {
+            Some(NbtTag::List(blocks)) => blocks.as_slice(),
+            None => &[],
+            _ => return Err(invalid("blocks that aren't a list")),
+        };
+        let blocks = blocks
+            .iter()
+            .map(|block| {
+                let NbtTag::Compound(block) = block else {
+                    return Err(invalid("block that isn't a compound"));
+                };
+                let state = match block.get("state") {
+                    Some(NbtTag::Int(state)) => usize::try_from(*state)
+                        .ok()
+                        .filter(|state| *state < palette.len()),
+                    _ => None,
+                }
+                .ok_or_else(|| invalid("block with an unknown state"))?;
+                let nbt = match block.get("nbt") {
+                    Some(NbtTag::Compound(nbt)) => Some(nbt.clone()),
+                    _ => None,
+                };
+
+                Ok(StructureBlock {
+                    position: position(block.get("pos"))?,
+                    state,
+                    nbt,
+                })
+            })
+            .collect::<ProtocolResult<_>>()?;
+
+        Ok(Self {
+            size,
+            palette,
+            blocks,
+        })
+    }
+
+    /// Returns the world positions and block states of every block, when the structure is placed at the given origin.
+    ///
+    /// This can be used to place the structure block by block, for example with block updates.
+    pub fn blocks_at(
+        &self,
+        origin: [i32; 3],
+    ) -> impl Iterator<Item = ([i32; 3], &BlockState)> + '_ {
+        self.blocks.iter().map(move |block| {
+            let [x, y, z] = block.position;
+            (
+                [origin[0] + x, origin[1] + y, origin[2] + z],
+                &self.palette[block.state],
+            )
+        })
+    }
+
+    /// Pastes the part of the structure that is inside the given chunk.
+    ///
+    /// Structures that span multiple chunks are pasted by pasting them into every chunk they touch.
+    /// The data of block entities isn't pasted, since their types depend on the registries of the server.
+    ///
+    /// # Parameters
+    /// - `chunk` - The chunk to paste into.
+    /// - `origin` - The world position the lowest corner of the structure is placed at.
+    /// - `min_y` - The Y position of the bottom of the chunk in the world.
+    /// - `resolve` - Returns the ID of a block state, blocks without an ID are skipped.
+    ///
+    /// # Returns
+    /// The amount of blocks that were placed.
+    pub fn paste(
+        &self,
+        chunk: &mut Chunk,
+        origin: [i32; 3],
+        min_y: i32,
+        mut resolve: impl FnMut(&BlockState) -> Option<i32>,
+    ) -> usize {
+        let ids: Vec<_> = self.palette.iter().map(&mut resolve).collect();
+        let height = chunk.height() as i32;
+        let mut placed = 0;
+
+        for block in &self.blocks {
+            let [x, y, z] = block.position;
+            let x = origin[0] + x - chunk.x * 16;
+            let y = origin[1] + y - min_y;
+            let z = origin[2] + z - chunk.z * 16;
+            if !(0..16).contains(&x) || !(0..16).contains(&z) || !(0..height).contains(&y) {
+                continue;
+            }
+
+            if let Some(id) = ids[block.state] {
+                chunk.set_block(x as usize, y as usize, z as usize, id);
+                placed += 1;
+            }
+        }
+
+        placed
+    }
+}
+
+/// Reads a position, which is a list of 3 ints.
+fn position(tag: Option<&NbtTag>) -> ProtocolResult<[i32; 3]> {
+    match tag {
+        Some(NbtTag::List(values)) => match values.as_slice() {
+            [NbtTag::Int(x), NbtTag::Int(y), NbtTag::Int(z)] => Ok([*x, *y, *z]),
+            _ => Err(invalid("position that isn't 3 ints")),
+        },
+        _ => Err(invalid("missing position")),
+    }
+}
+
+/// Creates the error of a malformed structure.
+fn invalid(message: &str) -> ProtocolError {
+    ProtocolError::InvalidData(format!("invalid structure: {message}"))
+}
@@ -0,0 +1,269 @@
+use protocol_buf::{types::VarInt, ToNetwork};
+
+use crate::{registry::Registries, v1_21::configuration::UpdateTagsPacket};
+
+/// The fluids in the order of the built-in fluid registry, fluids aren't sent as a registry so their IDs are fixed.
+const FLUIDS: &[&str] = &[
+    "minecraft:empty",
+    "minecraft:flowing_water",
+    "minecraft:water",
+    "minecraft:flowing_lava",
+    "minecraft:lava",
+];
+
+/// The tags of a registry, by the name of the tag.
+type NamedTags<T> = Vec<(T, Vec<T>)>;
+
+/// The vanilla tags of a registry, the names of the tags and entries without the `minecraft` namespace.
+type VanillaTags = &'static [(&'static str, &'static [&'static str])];
+
+/// The vanilla tags that are sent by default, by registry.
+const VANILLA_TAGS: &[(&str, VanillaTags)] = &[
+    (
+        "minecraft:fluid",
+        &[
+            ("water", &["water", "flowing_water"]),
+            ("lava", &["lava", "flowing_lava"]),
+        ],
+    ),
+    (
+        "minecraft:damage_type",
+        &[
+            (
+                "bypasses_armor",
+                &[
+                    "on_fire",
+                    "in_wall",
+                    "cramming",
+                    "drown",
+                    "fly_into_wall",
+                    "generic",
+                    "wither",
+                    "dragon_breath",
+                    "starve",
+                    "fall",
+                    "freeze",
+                    "stalagmite",
+                    "magic",
+                    "indirect_magic",
+                    "out_of_world",
+                    "generic_kill",
+                    "sonic_boom",
+                    "outside_border",
+                ],
+            ),
+            ("is_drowning", &["drown"]),
+            (
+                "is_explosion",
+                &[
+                    "fireworks",
+                    "explosion",
+                    "player_explosion",
+                    "bad_respawn_point",
+                ],
+            ),
+            ("is_fall", &["fall", "stalagmite"]),
+            (
+                "is_fire",
+                &[
+                    "in_fire",
+                    "campfire",
+                    "on_fire",
+                    "lava",
+                    "hot_floor",
+                    "unattributed_fireball",
+                    "fireball",
+                ],
+            ),
+            ("is_freezing", &["freeze"]),
+            ("is_lightning", &["lightning_bolt"]),
+            (
+                "is_projectile",
+                &[
+                    "arrow",
+                    "trident",
+                    "mob_projectile",
+                    "unattributed_fireball",
+                    "fireball",
+                    "wither_skull",
+                    "thrown",
+                    "wind_charge",
+                ],
+            ),
+        ],
+    ),
+];
+
+/// A tag of a registry, like `minecraft:water` in the fluid registry.
+///
+/// # Fields
+/// - `name` - The identifier of the tag.
+/// - `entries` - The IDs of the entries in the registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    pub name: String,
+    pub entries: Vec<VarInt>,
+}
+
+impl ToNetwork for Tag {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = self.name.to_network();
+        bytes.extend_from_slice(&self.entries.to_network());
+        bytes
+    }
+}
+
+/// All tags of a single registry.
+///
+/// # Fields
+/// - `registry` - The identifier of the registry, like `minecraft:fluid`.
+/// - `tags` - The tags of the registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryTags {
+    pub registry: String,
+    pub tags: Vec<Tag>,
+}
+
+impl ToNetwork for RegistryTags {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = self.registry.to_network();
+        bytes.extend_from_slice(&self.tags.to_network());
+        bytes
+    }
+}
+
+/// Builds the tags that are sent to the client during the configuration.
+///
+/// Tags are stored with the identifiers of their entries, which are resolved to IDs when the `[UpdateTagsPacket]` is created.
+/// Entries of synchronized registries are resolved with the `[Registries]` that are sent to the client,
+/// entries that can't be resolved are skipped.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::types::VarInt;
+/// use protocol_packets::{registry::Registries, tags::TagRegistry};
+///
+/// let tags = TagRegistry::new()
+///     .with_tag("minecraft:fluid", "minecraft:water", ["minecraft:water", "minecraft:flowing_water"])
+///     .with_tag("minecraft:damage_type", "minecraft:is_fall", ["minecraft:fall", "minecraft:unknown"]);
+///
+/// let packet = tags.to_packet(&Registries::vanilla());
+/// let fluids = &packet.registries[0].tags[0];
+/// let damage_types = &packet.registries[1].tags[0];
+///
+/// assert_eq!(fluids.entries, vec![VarInt::from(2), VarInt::from(1)]);
+/// assert_eq!(damage_types.entries.len(), 1);
+/// ```
+///
+/// # Fields
+/// - `registries` - The tags of every registry, with the identifiers of their entries.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TagRegistry {
+    registries: Vec<(String, NamedTags<String>)>,
+}
+
+impl TagRegistry {
+    /// Creates a new `[TagRegistry]` without any tags.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the default vanilla tags, these are the fluid tags and the common damage type tags.
+    pub fn vanilla() -> Self {
+        let mut tags = Self::new();
+        for (registry, registry_tags) in VANILLA_TAGS {
+            for (name, entries) in registry_tags.iter() {
+                tags.add(
+                    *registry,
+                    format!("minecraft:{}", name),
+                    entries.iter().map(|entry| format!("minecraft:{}", entry)),
+                );
+            }
+        }
+
+        tags
+    }
+
+    /// Adds a tag, a tag with the same name in the same registry is replaced.
+    ///
+    /// # Parameters
+    /// - `registry` - The identifier of the registry, like `minecraft:fluid`.
+    /// - `name` - The identifier of the tag, like `minecraft:water`.
+    /// - `entries` - The identifiers of the entries of the tag.
+    pub fn add<S: Into<String>>(
+        &mut self,
+        registry: impl Into<String>,
+        name: impl Into<String>,
+        entries: impl IntoIterator<Item = S>,
+    ) {
+        let (registry, name) = (registry.into(), name.into());
+        let entries = entries.into_iter().map(Into::into).collect();
+
+        let index = match self.registries.iter().position(|(id, _)| *id == registry) {
+            Some(index) => index,
+            None => {
+                self.registries.push((registry, Vec::new()));
+                self.registries.len() - 1
+            }
+        };
+
+        let tags = &mut self.registries[index].1;
+        match tags.iter_mut().find(|(tag, _)| *tag == name) {
+            Some(tag) => tag.1 = entries,
+            None => tags.push((name, entries)),
+        }
+    }
+
+    /// Adds a tag and returns the registry. This is useful for building the tags.
+    pub fn with_tag<S: Into<String>>(
+        mut self,
+        registry: impl Into<String>,
+        name: impl Into<String>,
+        entries: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.add(registry, name, entries);
+        self
+    }
+
+    /// Returns the identifiers of the entries of the given tag.
+    pub fn get(&self, registry: &str, name: &str) -> Option<&[String]> {
+        let (_, tags) = self.registries.iter().find(|(id, _)| id == registry)?;
+        tags.iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, entries)| entries.as_slice())
+    }
+
+    /// Creates the `[UpdateTagsPacket]`, resolving the entries with the given registries.
+    pub fn to_packet(&self, registries: &Registries) -> UpdateTagsPacket {
+        let registries = self
+            .registries
+            .iter()
+            .map(|(registry, tags)| {
+                let resolve = |entry: &String| match registry.as_str() {
+                    "minecraft:fluid" => FLUIDS
+                        .iter()
+                        .position(|fluid| fluid == entry)
+                        .map(|id| id as i32),
+                    _ => registries.get(registry)?.id_of(entry),
+                };
+
+                RegistryTags {
+                    registry: registry.clone(),
+                    tags: tags
+                        .iter()
+                        .map(|(name, entries)| Tag {
+                            name: name.clone(),
+                            entries: entries
+                                .iter()
+                                .filter_map(resolve)
+                                .map(VarInt::from)
+                                .collect(),
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        UpdateTagsPacket { registries }
+    }
+}
@@ -0,0 +1,95 @@
+use std::{io::Cursor, ops::BitOr};
+
+use protocol_buf::{error::ProtocolResult, FromNetwork, ToNetwork};
+
+/// Defines which values of a `[SynchronizePlayerPositionPacket]` are relative to the current position and rotation.
+///
+/// Relative values are added to the values of the client, so a relative rotation of `0` keeps the direction the
+/// player is looking at. Teleports with a relative rotation don't snap the camera, which makes them look smooth.
+/// The velocity flags were added in 1.21.2, so they aren't part of this protocol version.
+///
+/// # Examples
+/// ```rust
+/// use protocol_buf::ToNetwork;
+/// use protocol_packets::teleport::RelativeFlags;
+///
+/// // Moves the player up while keeping its X and Z position and the direction it is looking at.
+/// let flags = RelativeFlags::X | RelativeFlags::Z | RelativeFlags::ROTATION;
+///
+/// assert!(flags.contains(RelativeFlags::YAW));
+/// assert!(!flags.contains(RelativeFlags::Y));
+/// assert_eq!(flags.to_network(), vec![0x1D]);
+/// assert_eq!(RelativeFlags::ALL.without(RelativeFlags::Y), flags);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RelativeFlags(u8);
+
+impl RelativeFlags {
+    /// Every value is absolute.
+    pub const ABSOLUTE: Self = Self(0);
+
+    /// The X position is relative.
+    pub const X: Self = Self(0x01);
+
+    /// The Y position is relative.
+    pub const Y: Self = Self(0x02);
+
+    /// The Z position is relative.
+    pub const Z: Self = Self(0x04);
+
+    /// The yaw is relative.
+    pub const YAW: Self = Self(0x08);
+
+    /// The pitch is relative.
+    pub const PITCH: Self = Self(0x10);
+
+    /// The whole position is relative.
+    pub const POSITION: Self = Self(0x07);
+
+    /// The whole rotation is relative.
+    pub const ROTATION: Self = Self(0x18);
+
+    /// Every value is relative.
+    pub const ALL: Self = Self(0x1F);
+
+    /// Returns the flags with the given flags set.
+    pub const fn with(self, flags: Self) -> Self {
+        Self(self.0 | flags.0)
+    }
+
+    /// Returns the flags with the given flags cleared.
+    pub const fn without(self, flags: Self) -> Self {
+        Self(self.0 & !flags.0)
+    }
+
+    /// Returns whether all of the given flags are set.
+    pub const fn contains(self, flags: Self) -> bool {
+        self.0 & flags.0 == flags.0
+    }
+
+    /// Returns the bits of the flags, like they are sent to the client.
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+}
+
+impl BitOr for RelativeFlags {
+    type Output = Self;
+
+    fn bitor(self, flags: Self) -> Self {
+        self.with(flags)
+    }
+}
+
+impl ToNetwork for RelativeFlags {
+    fn to_network(&self) -> Vec<u8> {
+        vec![self.0]
+    }
+}
+
+/// Reads the flags, unknown bits are ignored like the client does.
+impl FromNetwork for RelativeFlags {
+    fn from_network<B: AsRef<[u8]>>(buffer: &mut Cursor<B>) -> ProtocolResult<Self> {
+        Ok(Self(u8::from_network(buffer)? & Self::ALL.0))
+    }
+}
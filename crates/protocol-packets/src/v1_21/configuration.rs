@@ -0,0 +1,301 @@
+use protocol_buf::{
+    buffer::{Buffer, NormalBuffer, PacketBuffer},
+    compression::CompressionHint,
+    error::ProtocolResult,
+    text::Component,
+    types::{PrefixedOptional, RemainingBytes, VarInt},
+};
+
+use crate::{
+    clientbound_packet, clientbound_packets,
+    links::ServerLink,
+    plugin::{encode_brand, BRAND_CHANNEL},
+    registry::{KnownPack, RegistryEntry},
+    report::ReportDetail,
+    serverbound_packet, serverbound_packets,
+    settings::ClientInformation,
+    tags::RegistryTags,
+    ClientboundPacket, ConnectionState, Packet, ServerboundPacket,
+};
+
+clientbound_packet! {
+    /// Tells the client which feature flags are enabled, for example to enable experimental content.
+    ///
+    /// # Fields
+    /// - `feature_flags` - The identifiers of the enabled feature flags, like `minecraft:vanilla`.
+    UpdateEnabledFeaturesPacket, 0x0C {
+        feature_flags: Vec<String>,
+    }
+}
+
+clientbound_packet! {
+    /// Tells the client that the configuration is done.
+    /// The client responds with a `[AcknowledgeFinishConfigurationPacket]` and switches to the `Play` state.
+    FinishConfigurationPacket, 0x03 {}
+}
+
+serverbound_packet! {
+    /// Sent by the client once it received the `[FinishConfigurationPacket]`.
+    /// After this packet, the connection is in the `Play` state.
+    AcknowledgeFinishConfigurationPacket, Configuration, 0x03 {}
+}
+
+clientbound_packet! {
+    /// Disconnects the client while it is being configured.
+    ///
+    /// # Fields
+    /// - `reason` - The reason shown to the client.
+    ConfigurationDisconnectPacket, 0x02 {
+        reason: Component,
+    }
+}
+
+clientbound_packet! {
+    /// Sends the entries of a registry to the client.
+    ///
+    /// # Fields
+    /// - `registry_id` - The identifier of the registry, like `minecraft:worldgen/biome`.
+    /// - `entries` - The entries of the registry, the client assigns their IDs in this order.
+    RegistryDataPacket, 0x07 {
+        registry_id: String,
+        entries: Vec<RegistryEntry>,
+    }
+}
+
+clientbound_packet! {
+    /// Tells the client which data packs the server knows.
+    /// The client responds with a `[ServerboundKnownPacksPacket]`, containing the packs both sides know.
+    ///
+    /// # Fields
+    /// - `packs` - The data packs the server knows.
+    ClientboundKnownPacksPacket, 0x0E {
+        packs: Vec<KnownPack>,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client as a response to the `[ClientboundKnownPacksPacket]`.
+    ///
+    /// # Fields
+    /// - `packs` - The data packs both the client and the server know.
+    ServerboundKnownPacksPacket, Configuration, 0x07 {
+        packs: Vec<KnownPack>,
+    }
+}
+
+clientbound_packet! {
+    /// Sent by the server to check if the client is still connected while it is being configured.
+    /// The client has to respond with a `[ServerboundConfigurationKeepAlivePacket]` containing the same ID.
+    ///
+    /// # Fields
+    /// - `keep_alive_id` - The ID the client has to respond with.
+    ClientboundConfigurationKeepAlivePacket, 0x04, Never {
+        keep_alive_id: i64,
+    }
+}
+
+serverbound_packet! {
+    /// The response to a `[ClientboundConfigurationKeepAlivePacket]`.
+    ///
+    /// # Fields
+    /// - `keep_alive_id` - The ID of the keep alive the client is responding to.
+    ServerboundConfigurationKeepAlivePacket, Configuration, 0x04 {
+        keep_alive_id: i64,
+    }
+}
+
+clientbound_packet! {
+    /// Sends the tags of the registries, like the blocks that are climbable or the fluids that are water.
+    ///
+    /// # Fields
+    /// - `registries` - The tags of every registry.
+    UpdateTagsPacket, 0x0D {
+        registries: Vec<RegistryTags>,
+    }
+}
+
+clientbound_packet! {
+    /// Sends a message on a plugin channel while the client is being configured.
+    ///
+    /// # Fields
+    /// - `channel` - The identifier of the channel, like `minecraft:brand`.
+    /// - `data` - The data of the message, its format depends on the channel.
+    ClientboundConfigurationPluginMessagePacket, 0x01 {
+        channel: String,
+        data: RemainingBytes,
+    }
+}
+
+impl ClientboundConfigurationPluginMessagePacket {
+    /// Creates the `minecraft:brand` message, which is shown in the debug screen of the client.
+    pub fn brand(brand: &str) -> Self {
+        Self {
+            channel: BRAND_CHANNEL.to_string(),
+            data: RemainingBytes::from(encode_brand(brand)),
+        }
+    }
+}
+
+serverbound_packet! {
+    /// A message sent by the client on a plugin channel while it is being configured.
+    ///
+    /// # Fields
+    /// - `channel` - The identifier of the channel, like `minecraft:brand`.
+    /// - `data` - The data of the message, its format depends on the channel.
+    ServerboundConfigurationPluginMessagePacket, Configuration, 0x02 {
+        channel: String,
+        data: RemainingBytes,
+    }
+}
+
+clientbound_packet! {
+    /// Asks the client for a cookie while it is being configured, the client answers with a `[ConfigurationCookieResponsePacket]`.
+    ///
+    /// # Fields
+    /// - `key` - The identifier of the cookie, like `example:session`.
+    ConfigurationCookieRequestPacket, 0x00 {
+        key: String,
+    }
+}
+
+serverbound_packet! {
+    /// The answer to a `[ConfigurationCookieRequestPacket]`.
+    ///
+    /// # Fields
+    /// - `key` - The identifier of the cookie.
+    /// - `payload` - The data of the cookie, up to 5 KiB. `None` if the client doesn't have the cookie.
+    ConfigurationCookieResponsePacket, Configuration, 0x01 {
+        key: String,
+        payload: PrefixedOptional<Vec<u8>>,
+    }
+}
+
+clientbound_packet! {
+    /// Stores a cookie on the client while it is being configured, replacing the cookie with the same key.
+    ///
+    /// Cookies are kept until the client disconnects, transfers included.
+    ///
+    /// # Fields
+    /// - `key` - The identifier of the cookie, like `example:session`.
+    /// - `payload` - The data of the cookie, up to 5 KiB.
+    ConfigurationStoreCookiePacket, 0x0A {
+        key: String,
+        payload: Vec<u8>,
+    }
+}
+
+clientbound_packet! {
+    /// Adds a resource pack while the client is being configured, the client answers with a `[ConfigurationResourcePackResponsePacket]` for every status.
+    ///
+    /// # Fields
+    /// - `uuid` - The UUID of the pack, a pack with the same UUID is replaced.
+    /// - `url` - The URL the pack is downloaded from.
+    /// - `hash` - The SHA-1 hash of the pack as a hexadecimal string, used to cache the pack. Empty if the pack isn't cached.
+    /// - `forced` - Whether the player is disconnected if it declines the pack.
+    /// - `prompt` - The message shown when the player is asked to accept the pack.
+    ConfigurationAddResourcePackPacket, 0x09 {
+        uuid: u128,
+        url: String,
+        hash: String,
+        forced: bool,
+        prompt: PrefixedOptional<Component>,
+    }
+}
+
+clientbound_packet! {
+    /// Removes a resource pack while the client is being configured.
+    ///
+    /// # Fields
+    /// - `uuid` - The UUID of the pack, `None` removes every pack.
+    ConfigurationRemoveResourcePackPacket, 0x08 {
+        uuid: PrefixedOptional<u128>,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client when the status of a resource pack changed, like when the player accepted it.
+    ///
+    /// # Fields
+    /// - `uuid` - The UUID of the pack.
+    /// - `result` - The ID of the `[ResourcePackStatus]`.
+    ConfigurationResourcePackResponsePacket, Configuration, 0x06 {
+        uuid: u128,
+        result: VarInt,
+    }
+}
+
+clientbound_packet! {
+    /// Tells the client to connect to another server, the client disconnects and logs in with the `Transfer` intent.
+    ///
+    /// # Fields
+    /// - `host` - The address of the other server.
+    /// - `port` - The port of the other server.
+    ConfigurationTransferPacket, 0x0B {
+        host: String,
+        port: VarInt,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client right after the login is acknowledged, this contains the settings of the client.
+    ///
+    /// # Fields
+    /// - `information` - The settings of the client.
+    ConfigurationClientInformationPacket, Configuration, 0x00 {
+        information: ClientInformation,
+    }
+}
+
+clientbound_packet! {
+    /// Sends the links that are shown in the pause menu and on the disconnect screen.
+    ///
+    /// # Fields
+    /// - `links` - The links, in the order they are shown.
+    ConfigurationServerLinksPacket, 0x10 {
+        links: Vec<ServerLink>,
+    }
+}
+
+clientbound_packet! {
+    /// Sends the details the client adds to its crash reports and disconnect reports.
+    ///
+    /// # Fields
+    /// - `details` - The details, at most `[MAX_REPORT_DETAILS]`.
+    ConfigurationCustomReportDetailsPacket, 0x0F {
+        details: Vec<ReportDetail>,
+    }
+}
+
+serverbound_packets! {
+    /// Every packet the client can send in the `Configuration` state, ordered by their IDs.
+    ServerboundConfigurationPacket, Configuration {
+        ConfigurationClientInformation(ConfigurationClientInformationPacket),
+        ConfigurationCookieResponse(ConfigurationCookieResponsePacket),
+        ServerboundConfigurationPluginMessage(ServerboundConfigurationPluginMessagePacket),
+        AcknowledgeFinishConfiguration(AcknowledgeFinishConfigurationPacket),
+        ServerboundConfigurationKeepAlive(ServerboundConfigurationKeepAlivePacket),
+        ConfigurationResourcePackResponse(ConfigurationResourcePackResponsePacket),
+        ServerboundKnownPacks(ServerboundKnownPacksPacket),
+    }
+}
+
+clientbound_packets! {
+    /// Every packet the server can send in the `Configuration` state, ordered by their IDs.
+    ClientboundConfigurationPacket, Configuration {
+        ConfigurationCookieRequest(ConfigurationCookieRequestPacket),
+        ClientboundConfigurationPluginMessage(ClientboundConfigurationPluginMessagePacket),
+        ConfigurationDisconnect(ConfigurationDisconnectPacket),
+        FinishConfiguration(FinishConfigurationPacket),
+        ClientboundConfigurationKeepAlive(ClientboundConfigurationKeepAlivePacket),
+        RegistryData(RegistryDataPacket),
+        ConfigurationRemoveResourcePack(ConfigurationRemoveResourcePackPacket),
+        ConfigurationAddResourcePack(ConfigurationAddResourcePackPacket),
+        ConfigurationStoreCookie(ConfigurationStoreCookiePacket),
+        ConfigurationTransfer(ConfigurationTransferPacket),
+        UpdateEnabledFeatures(UpdateEnabledFeaturesPacket),
+        UpdateTags(UpdateTagsPacket),
+        ClientboundKnownPacks(ClientboundKnownPacksPacket),
+        ConfigurationCustomReportDetails(ConfigurationCustomReportDetailsPacket),
+        ConfigurationServerLinks(ConfigurationServerLinksPacket),
+    }
+}
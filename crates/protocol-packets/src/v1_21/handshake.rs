@@ -0,0 +1,30 @@
+use protocol_buf::{
+    buffer::{Buffer, NormalBuffer, PacketBuffer},
+    error::ProtocolResult,
+    types::{BoundedString, VarInt},
+};
+
+use crate::{serverbound_packet, serverbound_packets, ConnectionState, Packet, ServerboundPacket};
+
+serverbound_packet! {
+    /// The first packet sent by the client. This decides which state the connection switches to.
+    ///
+    /// # Fields
+    /// - `protocol_version` - The protocol version of the client.
+    /// - `server_address` - The address the client used to connect, up to 255 characters.
+    /// - `server_port` - The port the client used to connect.
+    /// - `next_state` - The intent of the connection. `1` for status, `2` for login and `3` for transfer.
+    HandshakePacket, Handshake, 0x00 {
+        protocol_version: VarInt,
+        server_address: BoundedString<255>,
+        server_port: u16,
+        next_state: VarInt,
+    }
+}
+
+serverbound_packets! {
+    /// Every packet the client can send in the `Handshake` state, ordered by their IDs.
+    ServerboundHandshakePacket, Handshake {
+        Handshake(HandshakePacket),
+    }
+}
@@ -0,0 +1,189 @@
+use protocol_buf::{
+    buffer::{Buffer, NormalBuffer, PacketBuffer},
+    error::ProtocolResult,
+    types::{BoundedString, PrefixedOptional, RemainingBytes, VarInt},
+    ToNetwork,
+};
+
+use crate::{
+    clientbound_packet, clientbound_packets, serverbound_packet, serverbound_packets,
+    ClientboundPacket, ConnectionState, Packet, ServerboundPacket,
+};
+
+serverbound_packet! {
+    /// The first packet sent by the client while logging in.
+    ///
+    /// # Fields
+    /// - `name` - The username of the player, up to 16 characters.
+    /// - `uuid` - The UUID of the player, as a 128 bit number.
+    LoginStartPacket, Login, 0x00 {
+        name: BoundedString<16>,
+        uuid: u128,
+    }
+}
+
+clientbound_packet! {
+    /// Disconnects the client while it is logging in.
+    ///
+    /// # Fields
+    /// - `reason` - The reason shown to the client, as a JSON text component.
+    LoginDisconnectPacket, 0x00 {
+        reason: String,
+    }
+}
+
+/// A property of a game profile, like the skin of the player.
+///
+/// # Fields
+/// - `name` - The name of the property, like `textures`.
+/// - `value` - The value of the property, usually base64 encoded JSON.
+/// - `signature` - The signature of the value, signed by the session server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileProperty {
+    pub name: String,
+    pub value: String,
+    pub signature: PrefixedOptional<String>,
+}
+
+impl ToNetwork for ProfileProperty {
+    fn to_network(&self) -> Vec<u8> {
+        let mut bytes = self.name.to_network();
+        bytes.extend_from_slice(&self.value.to_network());
+        bytes.extend_from_slice(&self.signature.to_network());
+        bytes
+    }
+}
+
+clientbound_packet! {
+    /// Starts the encryption of the connection, the client answers with a `[EncryptionResponsePacket]`.
+    ///
+    /// # Fields
+    /// - `server_id` - The ID of the server, empty for vanilla servers. It is part of the server hash.
+    /// - `public_key` - The RSA public key of the server, encoded as DER.
+    /// - `verify_token` - Random bytes the client has to encrypt with the public key.
+    /// - `should_authenticate` - Whether the client has to join the server through the session server.
+    EncryptionRequestPacket, 0x01 {
+        server_id: String,
+        public_key: Vec<u8>,
+        verify_token: Vec<u8>,
+        should_authenticate: bool,
+    }
+}
+
+serverbound_packet! {
+    /// The answer to a `[EncryptionRequestPacket]`, every following byte is encrypted with the shared secret.
+    ///
+    /// # Fields
+    /// - `shared_secret` - The shared secret, encrypted with the public key of the server.
+    /// - `verify_token` - The verify token of the request, encrypted with the public key of the server.
+    EncryptionResponsePacket, Login, 0x01 {
+        shared_secret: Vec<u8>,
+        verify_token: Vec<u8>,
+    }
+}
+
+clientbound_packet! {
+    /// Finishes the login, the client answers with a `[LoginAcknowledgedPacket]`.
+    ///
+    /// # Fields
+    /// - `uuid` - The UUID of the player, as a 128 bit number.
+    /// - `username` - The username of the player.
+    /// - `properties` - The properties of the game profile of the player.
+    /// - `strict_error_handling` - Whether the client disconnects if it fails to read a packet, instead of ignoring it.
+    LoginSuccessPacket, 0x02 {
+        uuid: u128,
+        username: String,
+        properties: Vec<ProfileProperty>,
+        strict_error_handling: bool,
+    }
+}
+
+clientbound_packet! {
+    /// Enables compression for the connection. Every packet after this one is compressed.
+    ///
+    /// # Fields
+    /// - `threshold` - The size at which packets are compressed. A negative threshold disables compression.
+    SetCompressionPacket, 0x03 {
+        threshold: VarInt,
+    }
+}
+
+clientbound_packet! {
+    /// Asks the client for data on a plugin channel while it is logging in, the client answers with a `[LoginPluginResponsePacket]`.
+    ///
+    /// Vanilla clients don't know any channel, this is used by proxies like Velocity to forward the player.
+    ///
+    /// # Fields
+    /// - `message_id` - The ID of the request, the response has the same ID.
+    /// - `channel` - The identifier of the channel, like `velocity:player_info`.
+    /// - `data` - The data of the request.
+    LoginPluginRequestPacket, 0x04 {
+        message_id: VarInt,
+        channel: String,
+        data: RemainingBytes,
+    }
+}
+
+serverbound_packet! {
+    /// The answer to a `[LoginPluginRequestPacket]`.
+    ///
+    /// # Fields
+    /// - `message_id` - The ID of the request.
+    /// - `data` - The data of the answer, `None` if the client doesn't know the channel.
+    LoginPluginResponsePacket, Login, 0x02 {
+        message_id: VarInt,
+        data: PrefixedOptional<RemainingBytes>,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client after it received the login success, the connection switches to the `Configuration` state.
+    LoginAcknowledgedPacket, Login, 0x03 {}
+}
+
+clientbound_packet! {
+    /// Asks the client for a cookie while it is logging in, the client answers with a `[LoginCookieResponsePacket]`.
+    ///
+    /// Cookies are stored by the client across transfers, so the previous server can pass data to the next server.
+    ///
+    /// # Fields
+    /// - `key` - The identifier of the cookie, like `example:session`.
+    LoginCookieRequestPacket, 0x05 {
+        key: String,
+    }
+}
+
+serverbound_packet! {
+    /// The answer to a `[LoginCookieRequestPacket]`.
+    ///
+    /// # Fields
+    /// - `key` - The identifier of the cookie.
+    /// - `payload` - The data of the cookie, up to 5 KiB. `None` if the client doesn't have the cookie.
+    LoginCookieResponsePacket, Login, 0x04 {
+        key: String,
+        payload: PrefixedOptional<Vec<u8>>,
+    }
+}
+
+serverbound_packets! {
+    /// Every packet the client can send in the `Login` state, ordered by their IDs.
+    ServerboundLoginPacket, Login {
+        LoginStart(LoginStartPacket),
+        EncryptionResponse(EncryptionResponsePacket),
+        LoginPluginResponse(LoginPluginResponsePacket),
+        LoginAcknowledged(LoginAcknowledgedPacket),
+        LoginCookieResponse(LoginCookieResponsePacket),
+    }
+}
+
+clientbound_packets! {
+    /// Every packet the server can send in the `Login` state, ordered by their IDs.
+    ClientboundLoginPacket, Login {
+        LoginDisconnect(LoginDisconnectPacket),
+        EncryptionRequest(EncryptionRequestPacket),
+        LoginSuccess(LoginSuccessPacket),
+        SetCompression(SetCompressionPacket),
+        LoginPluginRequest(LoginPluginRequestPacket),
+        LoginCookieRequest(LoginCookieRequestPacket),
+    }
+}
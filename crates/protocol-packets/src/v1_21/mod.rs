@@ -0,0 +1,11 @@
+pub mod configuration;
+pub mod handshake;
+pub mod login;
+pub mod play;
+pub mod status;
+
+/// The protocol version of Minecraft 1.21.
+pub const PROTOCOL_VERSION: i32 = 767;
+
+/// The Minecraft version name of this protocol version.
+pub const VERSION_NAME: &str = "1.21";
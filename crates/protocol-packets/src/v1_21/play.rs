@@ -0,0 +1,1257 @@
+use protocol_buf::{
+    buffer::{Buffer, NormalBuffer, PacketBuffer},
+    compression::CompressionHint,
+    error::ProtocolResult,
+    nbt::Nbt,
+    text::Component,
+    types::{
+        Angle, BoundedString, GlobalPosition, Position, PrefixedOptional, RemainingBytes, VarInt,
+        Velocity,
+    },
+};
+
+use crate::{
+    block::{BlockChange, SectionPosition},
+    chat::{AcknowledgedMessages, FilterType, MessageSignature, PreviousMessage, MAX_CHAT_LENGTH},
+    chunk::{BlockEntity, Chunk, LightData},
+    clientbound_packet, clientbound_packets,
+    damage_type::DamagePosition,
+    equipment::Equipment,
+    input::InteractAction,
+    item::Slot,
+    links::ServerLink,
+    metadata::EntityMetadata,
+    particle::ParticleOptions,
+    player_info::{PlayerAction, PlayerInfo},
+    plugin::{encode_brand, BRAND_CHANNEL},
+    report::ReportDetail,
+    scoreboard::{NumberFormat, ObjectiveAction, ScoreRenderType},
+    serverbound_packet, serverbound_packets,
+    settings::ClientInformation,
+    sound::{Sound, SoundCategory},
+    teleport::RelativeFlags,
+    ClientboundPacket, ConnectionState, Packet, ServerboundPacket,
+};
+
+clientbound_packet! {
+    /// Sent by the server to check if the client is still connected.
+    /// The client has to respond with a `[ServerboundKeepAlivePacket]` containing the same ID.
+    /// This packet is tiny and sent often, so it's never compressed.
+    ///
+    /// # Fields
+    /// - `keep_alive_id` - The ID the client has to respond with.
+    ClientboundKeepAlivePacket, 0x26, Never {
+        keep_alive_id: i64,
+    }
+}
+
+serverbound_packet! {
+    /// The response to a `[ClientboundKeepAlivePacket]`.
+    ///
+    /// # Fields
+    /// - `keep_alive_id` - The ID of the keep alive the client is responding to.
+    ServerboundKeepAlivePacket, Play, 0x18 {
+        keep_alive_id: i64,
+    }
+}
+
+clientbound_packet! {
+    /// Sends a whole chunk column, including its light, to the client.
+    ///
+    /// # Fields
+    /// - `chunk_x` - The X position of the chunk.
+    /// - `chunk_z` - The Z position of the chunk.
+    /// - `heightmaps` - The heightmaps of the chunk as NBT.
+    /// - `data` - The serialized chunk sections.
+    /// - `block_entities` - The block entities inside the chunk.
+    /// - `light` - The sky and block light of the chunk.
+    ChunkDataAndUpdateLightPacket, 0x27 {
+        chunk_x: i32,
+        chunk_z: i32,
+        heightmaps: Nbt,
+        data: Vec<u8>,
+        block_entities: Vec<BlockEntity>,
+        light: LightData,
+    }
+}
+
+impl ChunkDataAndUpdateLightPacket {
+    /// Creates the packet for the given chunk.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_packets::{chunk::{Chunk, LightData}, v1_21::play::ChunkDataAndUpdateLightPacket};
+    ///
+    /// let chunk = Chunk::new(0, 0, 24, 0);
+    /// let packet = ChunkDataAndUpdateLightPacket::new(&chunk, LightData::uniform(24, 15, 0));
+    ///
+    /// assert_eq!(packet.chunk_x, 0);
+    /// ```
+    ///
+    /// # Parameters
+    /// - `chunk` - The chunk to send.
+    /// - `light` - The light of the chunk.
+    pub fn new(chunk: &Chunk, light: LightData) -> Self {
+        Self {
+            chunk_x: chunk.x,
+            chunk_z: chunk.z,
+            heightmaps: chunk.heightmaps().to_nbt(chunk.height()),
+            data: chunk.section_data(),
+            block_entities: chunk.block_entities.clone(),
+            light,
+        }
+    }
+}
+
+clientbound_packet! {
+    /// Unloads a chunk on the client, like a chunk that is outside of the view distance of the player.
+    ///
+    /// # Fields
+    /// - `chunk_z` - The Z position of the chunk, this is sent before the X position.
+    /// - `chunk_x` - The X position of the chunk.
+    UnloadChunkPacket, 0x21 {
+        chunk_z: i32,
+        chunk_x: i32,
+    }
+}
+
+clientbound_packet! {
+    /// Sets the chunk the player is in, the client only keeps the chunks within its view distance around it.
+    ///
+    /// # Fields
+    /// - `chunk_x` - The X position of the chunk.
+    /// - `chunk_z` - The Z position of the chunk.
+    SetCenterChunkPacket, 0x54 {
+        chunk_x: VarInt,
+        chunk_z: VarInt,
+    }
+}
+
+clientbound_packet! {
+    /// Creates, removes or updates a scoreboard objective.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_buf::text::Component;
+    /// use protocol_packets::{scoreboard::NumberFormat, v1_21::play::UpdateObjectivesPacket};
+    ///
+    /// // A sidebar without the red numbers.
+    /// let packet = UpdateObjectivesPacket::create("sidebar", Component::text("My Server"), Some(NumberFormat::Blank));
+    /// assert_eq!(packet.objective_name, "sidebar");
+    /// ```
+    ///
+    /// # Fields
+    /// - `objective_name` - The unique name of the objective.
+    /// - `action` - What happens to the objective.
+    UpdateObjectivesPacket, 0x5E {
+        objective_name: String,
+        action: ObjectiveAction,
+    }
+}
+
+impl UpdateObjectivesPacket {
+    /// Creates an objective whose scores are shown as numbers.
+    ///
+    /// # Parameters
+    /// - `objective_name` - The unique name of the objective.
+    /// - `display_name` - The name shown above the scores.
+    /// - `number_format` - How the scores are shown, `None` to show them as plain numbers.
+    pub fn create(
+        objective_name: impl Into<String>,
+        display_name: Component,
+        number_format: Option<NumberFormat>,
+    ) -> Self {
+        Self {
+            objective_name: objective_name.into(),
+            action: ObjectiveAction::Create {
+                display_name,
+                render_type: ScoreRenderType::Integer,
+                number_format,
+            },
+        }
+    }
+
+    /// Removes an objective.
+    pub fn remove(objective_name: impl Into<String>) -> Self {
+        Self {
+            objective_name: objective_name.into(),
+            action: ObjectiveAction::Remove,
+        }
+    }
+}
+
+clientbound_packet! {
+    /// Sets the score of an entity, or a line of the sidebar, for an objective.
+    ///
+    /// # Fields
+    /// - `entity_name` - The name of the player or the UUID of the entity the score belongs to.
+    /// - `objective_name` - The name of the objective.
+    /// - `value` - The score.
+    /// - `display_name` - The name shown instead of the entity name, `None` to show the entity name.
+    /// - `number_format` - How the score is shown, `None` to use the number format of the objective.
+    UpdateScorePacket, 0x61 {
+        entity_name: String,
+        objective_name: String,
+        value: VarInt,
+        display_name: PrefixedOptional<Component>,
+        number_format: PrefixedOptional<NumberFormat>,
+    }
+}
+
+clientbound_packet! {
+    /// Changes the view distance of the server, the client renders the smaller one of its own and this view distance.
+    ///
+    /// # Fields
+    /// - `view_distance` - The view distance in chunks.
+    SetRenderDistancePacket, 0x55 {
+        view_distance: VarInt,
+    }
+}
+
+clientbound_packet! {
+    /// Triggers an entity event on the client, like the operator permission level of the player.
+    ///
+    /// # Fields
+    /// - `entity_id` - The ID of the entity.
+    /// - `entity_status` - The event to trigger, the meaning depends on the entity type.
+    EntityEventPacket, 0x1F {
+        entity_id: i32,
+        entity_status: i8,
+    }
+}
+
+clientbound_packet! {
+    /// Teleports the player to the given position. This is also used to send the spawn position while joining.
+    /// The client has to confirm the teleport with a `[ConfirmTeleportationPacket]`.
+    ///
+    /// # Fields
+    /// - `x` - The X position, relative if `[RelativeFlags::X]` is set.
+    /// - `y` - The Y position, relative if `[RelativeFlags::Y]` is set.
+    /// - `z` - The Z position, relative if `[RelativeFlags::Z]` is set.
+    /// - `yaw` - The yaw in degrees, relative if `[RelativeFlags::YAW]` is set.
+    /// - `pitch` - The pitch in degrees, relative if `[RelativeFlags::PITCH]` is set.
+    /// - `flags` - Defines which of the values are relative.
+    /// - `teleport_id` - The ID the client has to confirm the teleport with.
+    SynchronizePlayerPositionPacket, 0x40 {
+        x: f64,
+        y: f64,
+        z: f64,
+        yaw: f32,
+        pitch: f32,
+        flags: RelativeFlags,
+        teleport_id: VarInt,
+    }
+}
+
+serverbound_packet! {
+    /// Confirms a `[SynchronizePlayerPositionPacket]`.
+    ///
+    /// # Fields
+    /// - `teleport_id` - The ID of the teleport that is confirmed.
+    ConfirmTeleportationPacket, Play, 0x00 {
+        teleport_id: VarInt,
+    }
+}
+
+clientbound_packet! {
+    /// Synchronizes the time of the world with the client. This is usually sent every second.
+    ///
+    /// # Fields
+    /// - `world_age` - The amount of ticks since the world was created.
+    /// - `time_of_day` - The time of day in ticks. If negative, the sun stops moving at the absolute value.
+    UpdateTimePacket, 0x64 {
+        world_age: i64,
+        time_of_day: i64,
+    }
+}
+
+clientbound_packet! {
+    /// Removes the given entities from the client.
+    ///
+    /// # Fields
+    /// - `entity_ids` - The IDs of the entities to remove.
+    RemoveEntitiesPacket, 0x42 {
+        entity_ids: Vec<VarInt>,
+    }
+}
+
+clientbound_packet! {
+    /// Disconnects the client while it is in the game.
+    ///
+    /// # Fields
+    /// - `reason` - The reason shown to the client.
+    PlayDisconnectPacket, 0x1D {
+        reason: Component,
+    }
+}
+
+clientbound_packet! {
+    /// Spawns one or more particles on the client.
+    ///
+    /// # Fields
+    /// - `long_distance` - Whether the particles are visible from up to 512 blocks away instead of 32 blocks.
+    /// - `x` - The X position of the particles.
+    /// - `y` - The Y position of the particles.
+    /// - `z` - The Z position of the particles.
+    /// - `offset_x` - The random spread on the X axis, multiplied by a gaussian random number.
+    /// - `offset_y` - The random spread on the Y axis, multiplied by a gaussian random number.
+    /// - `offset_z` - The random spread on the Z axis, multiplied by a gaussian random number.
+    /// - `max_speed` - The maximum speed of the particles.
+    /// - `particle_count` - The amount of particles to spawn.
+    /// - `particle_id` - The ID of the particle type in the particle type registry.
+    /// - `options` - The extra data of the particle, this has to match the particle type.
+    ParticlePacket, 0x29 {
+        long_distance: bool,
+        x: f64,
+        y: f64,
+        z: f64,
+        offset_x: f32,
+        offset_y: f32,
+        offset_z: f32,
+        max_speed: f32,
+        particle_count: i32,
+        particle_id: VarInt,
+        options: ParticleOptions,
+    }
+}
+
+clientbound_packet! {
+    /// Plays a sound at the given position.
+    ///
+    /// # Fields
+    /// - `sound` - The sound event, either from the registry or inline.
+    /// - `category` - The category of the sound.
+    /// - `x` - The X position multiplied by 8.
+    /// - `y` - The Y position multiplied by 8.
+    /// - `z` - The Z position multiplied by 8.
+    /// - `volume` - The volume of the sound, `1` is the normal volume.
+    /// - `pitch` - The pitch of the sound, from `0.5` to `2`.
+    /// - `seed` - The seed used to pick a random sound variant.
+    SoundEffectPacket, 0x68 {
+        sound: Sound,
+        category: SoundCategory,
+        x: i32,
+        y: i32,
+        z: i32,
+        volume: f32,
+        pitch: f32,
+        seed: i64,
+    }
+}
+
+clientbound_packet! {
+    /// Respawns the player, this is also used to switch the dimension of the player.
+    ///
+    /// The client forgets its boss bars, scoreboards and tab list after this packet, so they have to be sent again.
+    ///
+    /// # Fields
+    /// - `dimension_type` - The ID of the dimension type in the dimension type registry.
+    /// - `dimension_name` - The name of the dimension, like `minecraft:overworld`.
+    /// - `hashed_seed` - The first 8 bytes of the SHA-256 hash of the world seed.
+    /// - `game_mode` - The game mode of the player, `0` for survival, `1` for creative, `2` for adventure and `3` for spectator.
+    /// - `previous_game_mode` - The previous game mode of the player, `-1` if there is none.
+    /// - `is_debug` - Whether the world is a debug world.
+    /// - `is_flat` - Whether the world is a superflat world, this changes the horizon.
+    /// - `death_location` - The location the player died at, used by recovery compasses.
+    /// - `portal_cooldown` - The amount of ticks before the player can use a portal again.
+    /// - `data_kept` - Which data is kept, `0x01` keeps the attributes and `0x02` keeps the metadata.
+    RespawnPacket, 0x47 {
+        dimension_type: VarInt,
+        dimension_name: String,
+        hashed_seed: i64,
+        game_mode: u8,
+        previous_game_mode: i8,
+        is_debug: bool,
+        is_flat: bool,
+        death_location: PrefixedOptional<GlobalPosition>,
+        portal_cooldown: VarInt,
+        data_kept: i8,
+    }
+}
+
+clientbound_packet! {
+    /// Changes the state of the game for the client, like the weather or the game mode.
+    ///
+    /// # Fields
+    /// - `event` - The event, like `3` to change the game mode or `11` to enable the respawn screen.
+    /// - `value` - The value of the event, the meaning depends on the event.
+    GameEventPacket, 0x22 {
+        event: u8,
+        value: f32,
+    }
+}
+
+clientbound_packet! {
+    /// The first packet sent in the `Play` state, it contains everything the client needs to join the world.
+    ///
+    /// # Fields
+    /// - `entity_id` - The entity ID of the player.
+    /// - `is_hardcore` - Whether the world is in hardcore mode.
+    /// - `dimension_names` - The names of all dimensions on the server.
+    /// - `max_players` - The maximum amount of players, this is unused by the client.
+    /// - `view_distance` - The render distance of the server in chunks.
+    /// - `simulation_distance` - The distance in chunks in which entities are ticked.
+    /// - `reduced_debug_info` - Whether the debug screen shows less information, like the `reducedDebugInfo` game rule.
+    /// - `enable_respawn_screen` - Whether the respawn screen is shown, this is the opposite of the `doImmediateRespawn` game rule.
+    /// - `do_limited_crafting` - Whether players can only craft unlocked recipes, like the `doLimitedCrafting` game rule.
+    /// - `dimension_type` - The ID of the dimension type in the dimension type registry.
+    /// - `dimension_name` - The name of the dimension the player spawns in.
+    /// - `hashed_seed` - The first 8 bytes of the SHA-256 hash of the world seed.
+    /// - `game_mode` - The game mode of the player, `0` for survival, `1` for creative, `2` for adventure and `3` for spectator.
+    /// - `previous_game_mode` - The previous game mode of the player, `-1` if there is none.
+    /// - `is_debug` - Whether the world is a debug world.
+    /// - `is_flat` - Whether the world is a superflat world, this changes the horizon.
+    /// - `death_location` - The location the player died at, used by recovery compasses.
+    /// - `portal_cooldown` - The amount of ticks before the player can use a portal again.
+    /// - `enforces_secure_chat` - Whether the server requires signed chat messages.
+    LoginPlayPacket, 0x2B {
+        entity_id: i32,
+        is_hardcore: bool,
+        dimension_names: Vec<String>,
+        max_players: VarInt,
+        view_distance: VarInt,
+        simulation_distance: VarInt,
+        reduced_debug_info: bool,
+        enable_respawn_screen: bool,
+        do_limited_crafting: bool,
+        dimension_type: VarInt,
+        dimension_name: String,
+        hashed_seed: i64,
+        game_mode: u8,
+        previous_game_mode: i8,
+        is_debug: bool,
+        is_flat: bool,
+        death_location: PrefixedOptional<GlobalPosition>,
+        portal_cooldown: VarInt,
+        enforces_secure_chat: bool,
+    }
+}
+
+clientbound_packet! {
+    /// Sends a message on a plugin channel while the client is in the game.
+    ///
+    /// # Fields
+    /// - `channel` - The identifier of the channel, like `minecraft:brand`.
+    /// - `data` - The data of the message, its format depends on the channel.
+    ClientboundPluginMessagePacket, 0x19 {
+        channel: String,
+        data: RemainingBytes,
+    }
+}
+
+impl ClientboundPluginMessagePacket {
+    /// Creates the `minecraft:brand` message, which is shown in the debug screen of the client.
+    pub fn brand(brand: &str) -> Self {
+        Self {
+            channel: BRAND_CHANNEL.to_string(),
+            data: RemainingBytes::from(encode_brand(brand)),
+        }
+    }
+}
+
+serverbound_packet! {
+    /// A message sent by the client on a plugin channel while it is in the game.
+    ///
+    /// # Fields
+    /// - `channel` - The identifier of the channel, like `minecraft:brand`.
+    /// - `data` - The data of the message, its format depends on the channel.
+    ServerboundPluginMessagePacket, Play, 0x12 {
+        channel: String,
+        data: RemainingBytes,
+    }
+}
+
+clientbound_packet! {
+    /// Updates the metadata of an entity, like its pose or whether it is sneaking.
+    ///
+    /// # Fields
+    /// - `entity_id` - The ID of the entity.
+    /// - `metadata` - The values of the metadata that changed.
+    SetEntityMetadataPacket, 0x58 {
+        entity_id: VarInt,
+        metadata: EntityMetadata,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client when the player starts or stops sneaking, sprinting or flying with an elytra.
+    ///
+    /// # Fields
+    /// - `entity_id` - The entity ID of the player.
+    /// - `action` - The action, like `0` to start sneaking or `1` to stop sneaking.
+    /// - `jump_boost` - The strength of a horse jump from `0` to `100`, `0` for every other action.
+    PlayerCommandPacket, Play, 0x25 {
+        entity_id: VarInt,
+        action: VarInt,
+        jump_boost: VarInt,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client when the player changes its settings while in the game.
+    ///
+    /// # Fields
+    /// - `information` - The settings of the client.
+    ClientInformationPacket, Play, 0x0A {
+        information: ClientInformation,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client once it switched back into the `Configuration` state.
+    /// The server has to ask the client to reconfigure first, which it never does.
+    AcknowledgeConfigurationPacket, Play, 0x0C {}
+}
+
+clientbound_packet! {
+    /// Spawns an entity that isn't a player on the client, like a boat or a minecart.
+    ///
+    /// # Fields
+    /// - `entity_id` - The ID of the entity.
+    /// - `entity_uuid` - The UUID of the entity, as a 128 bit number.
+    /// - `entity_type` - The ID of the type of the entity in the `minecraft:entity_type` registry.
+    /// - `x` - The X position.
+    /// - `y` - The Y position.
+    /// - `z` - The Z position.
+    /// - `pitch` - The pitch.
+    /// - `yaw` - The yaw.
+    /// - `head_yaw` - The yaw of the head, this is only used by living entities.
+    /// - `data` - Additional data, its meaning depends on the entity type.
+    /// - `velocity_x` - The X velocity.
+    /// - `velocity_y` - The Y velocity.
+    /// - `velocity_z` - The Z velocity.
+    SpawnEntityPacket, 0x01 {
+        entity_id: VarInt,
+        entity_uuid: u128,
+        entity_type: VarInt,
+        x: f64,
+        y: f64,
+        z: f64,
+        pitch: Angle,
+        yaw: Angle,
+        head_yaw: Angle,
+        data: VarInt,
+        velocity_x: Velocity,
+        velocity_y: Velocity,
+        velocity_z: Velocity,
+    }
+}
+
+clientbound_packet! {
+    /// Moves an entity to the given position, this is used for movements that are too large to be sent relative.
+    ///
+    /// # Fields
+    /// - `entity_id` - The ID of the entity.
+    /// - `x` - The X position.
+    /// - `y` - The Y position.
+    /// - `z` - The Z position.
+    /// - `yaw` - The yaw.
+    /// - `pitch` - The pitch.
+    /// - `on_ground` - Whether the entity is on the ground.
+    TeleportEntityPacket, 0x70 {
+        entity_id: VarInt,
+        x: f64,
+        y: f64,
+        z: f64,
+        yaw: Angle,
+        pitch: Angle,
+        on_ground: bool,
+    }
+}
+
+clientbound_packet! {
+    /// Sets the entities that are riding an entity, an empty list makes every passenger leave.
+    ///
+    /// # Fields
+    /// - `entity_id` - The ID of the vehicle.
+    /// - `passengers` - The IDs of the passengers, the first passenger controls the vehicle.
+    SetPassengersPacket, 0x5F {
+        entity_id: VarInt,
+        passengers: Vec<VarInt>,
+    }
+}
+
+clientbound_packet! {
+    /// Moves the vehicle the player is controlling, this is sent when the server doesn't accept a movement of the client.
+    ///
+    /// # Fields
+    /// - `x` - The X position.
+    /// - `y` - The Y position.
+    /// - `z` - The Z position.
+    /// - `yaw` - The yaw in degrees.
+    /// - `pitch` - The pitch in degrees.
+    ClientboundMoveVehiclePacket, 0x31 {
+        x: f64,
+        y: f64,
+        z: f64,
+        yaw: f32,
+        pitch: f32,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client when the player moved without turning.
+    ///
+    /// # Fields
+    /// - `x` - The X position of the feet.
+    /// - `y` - The Y position of the feet.
+    /// - `z` - The Z position of the feet.
+    /// - `on_ground` - Whether the player is standing on the ground.
+    SetPlayerPositionPacket, Play, 0x1A {
+        x: f64,
+        y: f64,
+        z: f64,
+        on_ground: bool,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client when the player moved and turned.
+    ///
+    /// # Fields
+    /// - `x` - The X position of the feet.
+    /// - `y` - The Y position of the feet.
+    /// - `z` - The Z position of the feet.
+    /// - `yaw` - The yaw in degrees.
+    /// - `pitch` - The pitch in degrees.
+    /// - `on_ground` - Whether the player is standing on the ground.
+    SetPlayerPositionAndRotationPacket, Play, 0x1B {
+        x: f64,
+        y: f64,
+        z: f64,
+        yaw: f32,
+        pitch: f32,
+        on_ground: bool,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client when the player turned without moving.
+    ///
+    /// # Fields
+    /// - `yaw` - The yaw in degrees.
+    /// - `pitch` - The pitch in degrees.
+    /// - `on_ground` - Whether the player is standing on the ground.
+    SetPlayerRotationPacket, Play, 0x1C {
+        yaw: f32,
+        pitch: f32,
+        on_ground: bool,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client when the player started or stopped standing on the ground, without moving or turning.
+    ///
+    /// # Fields
+    /// - `on_ground` - Whether the player is standing on the ground.
+    SetPlayerOnGroundPacket, Play, 0x1D {
+        on_ground: bool,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client when the vehicle the player is controlling moved.
+    ///
+    /// # Fields
+    /// - `x` - The X position.
+    /// - `y` - The Y position.
+    /// - `z` - The Z position.
+    /// - `yaw` - The yaw in degrees.
+    /// - `pitch` - The pitch in degrees.
+    ServerboundMoveVehiclePacket, Play, 0x1E {
+        x: f64,
+        y: f64,
+        z: f64,
+        yaw: f32,
+        pitch: f32,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client while the player is steering a boat, this is used to animate the paddles.
+    ///
+    /// # Fields
+    /// - `left_paddle` - Whether the left paddle is turning.
+    /// - `right_paddle` - Whether the right paddle is turning.
+    PaddleBoatPacket, Play, 0x1F {
+        left_paddle: bool,
+        right_paddle: bool,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client when the player clicks on an entity.
+    ///
+    /// # Fields
+    /// - `entity_id` - The ID of the entity.
+    /// - `action` - Whether the entity was attacked or interacted with.
+    /// - `sneaking` - Whether the player is sneaking.
+    InteractPacket, Play, 0x16 {
+        entity_id: VarInt,
+        action: InteractAction,
+        sneaking: bool,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client when the player uses the item in one of its hands, like eating food or throwing an ender pearl.
+    ///
+    /// The server answers with a `[AcknowledgeBlockChangePacket]` containing the sequence.
+    ///
+    /// # Fields
+    /// - `hand` - The hand holding the item, `[MAIN_HAND]` or `[OFF_HAND]`.
+    /// - `sequence` - The sequence number of the action, used to acknowledge it.
+    /// - `yaw` - The yaw of the player when it used the item.
+    /// - `pitch` - The pitch of the player when it used the item.
+    UseItemPacket, Play, 0x39 {
+        hand: VarInt,
+        sequence: VarInt,
+        yaw: f32,
+        pitch: f32,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client when the player selects another slot of its hotbar.
+    ///
+    /// # Fields
+    /// - `slot` - The selected slot of the hotbar, from `0` to `8`.
+    SetHeldItemPacket, Play, 0x2F {
+        slot: i16,
+    }
+}
+
+clientbound_packet! {
+    /// Acknowledges the actions of the client up to the given sequence number, like using an item.
+    ///
+    /// # Fields
+    /// - `sequence` - The sequence number of the last handled action.
+    AcknowledgeBlockChangePacket, 0x05 {
+        sequence: VarInt,
+    }
+}
+
+clientbound_packet! {
+    /// Changes a single block in the world of the client.
+    ///
+    /// # Fields
+    /// - `location` - The position of the block.
+    /// - `block_id` - The ID of the new block state, see `[BlockStates]`.
+    BlockUpdatePacket, 0x09 {
+        location: Position,
+        block_id: VarInt,
+    }
+}
+
+clientbound_packet! {
+    /// Changes multiple blocks inside a single chunk section at once.
+    ///
+    /// # Fields
+    /// - `section` - The position of the section.
+    /// - `blocks` - The changed blocks.
+    UpdateSectionBlocksPacket, 0x49 {
+        section: SectionPosition,
+        blocks: Vec<BlockChange>,
+    }
+}
+
+impl UpdateSectionBlocksPacket {
+    /// Groups block changes by their section, this creates a packet for every section that contains a changed block.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_buf::types::Position;
+    /// use protocol_packets::{block::SectionPosition, v1_21::play::UpdateSectionBlocksPacket};
+    ///
+    /// let packets = UpdateSectionBlocksPacket::from_changes([
+    ///     (Position::new(0, 64, 0), 1),
+    ///     (Position::new(15, 79, 15), 1),
+    ///     (Position::new(16, 64, 0), 1),
+    /// ]);
+    ///
+    /// assert_eq!(packets.len(), 2);
+    /// assert_eq!(packets[0].section, SectionPosition::new(0, 4, 0));
+    /// assert_eq!(packets[0].blocks.len(), 2);
+    /// ```
+    ///
+    /// # Parameters
+    /// - `changes` - The absolute position and the new block state of every changed block.
+    pub fn from_changes(changes: impl IntoIterator<Item = (Position, i32)>) -> Vec<Self> {
+        let mut packets: Vec<Self> = Vec::new();
+        for (position, block_state) in changes {
+            let section = SectionPosition::from_block(position);
+            let change = BlockChange::from_block(position, block_state);
+            match packets.iter_mut().find(|packet| packet.section == section) {
+                Some(packet) => packet.blocks.push(change),
+                None => packets.push(Self {
+                    section,
+                    blocks: vec![change],
+                }),
+            }
+        }
+
+        packets
+    }
+}
+
+clientbound_packet! {
+    /// Replaces the visible items of an entity, like the item in the hand of a player or its armor.
+    ///
+    /// # Fields
+    /// - `entity_id` - The ID of the entity.
+    /// - `equipment` - The slots that changed, with their new items.
+    SetEquipmentPacket, 0x5B {
+        entity_id: VarInt,
+        equipment: Equipment,
+    }
+}
+
+clientbound_packet! {
+    /// Starts a cooldown on an item, the client can't use the item until the cooldown is over.
+    ///
+    /// A cooldown of `0` ticks removes the cooldown.
+    ///
+    /// # Fields
+    /// - `item_id` - The ID of the item in the `minecraft:item` registry, every stack of the item is affected.
+    /// - `cooldown_ticks` - The duration of the cooldown in ticks.
+    SetCooldownPacket, 0x17 {
+        item_id: VarInt,
+        cooldown_ticks: VarInt,
+    }
+}
+
+clientbound_packet! {
+    /// Replaces the item in a slot of a window.
+    ///
+    /// # Fields
+    /// - `window_id` - The ID of the window, `0` for the inventory of the player.
+    /// - `state_id` - The state of the window, the client sends it back when it clicks into the window.
+    /// - `slot` - The index of the slot in the window.
+    /// - `item` - The new content of the slot.
+    SetContainerSlotPacket, 0x15 {
+        window_id: u8,
+        state_id: VarInt,
+        slot: i16,
+        item: Slot,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client while the player is riding a vehicle.
+    ///
+    /// # Fields
+    /// - `sideways` - The sideways movement, positive to the left.
+    /// - `forward` - The forward movement, positive forward.
+    /// - `flags` - The pressed keys, like `[INPUT_JUMP]` or `[INPUT_UNMOUNT]`.
+    PlayerInputPacket, Play, 0x26 {
+        sideways: f32,
+        forward: f32,
+        flags: u8,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client when the player runs a command without signed arguments.
+    ///
+    /// # Fields
+    /// - `command` - The command, without the leading slash.
+    ChatCommandPacket, Play, 0x04 {
+        command: String,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client when the player sends a message in the chat, commands are sent with their own packets.
+    ///
+    /// # Fields
+    /// - `message` - The message, at most `[MAX_CHAT_LENGTH]` characters.
+    /// - `timestamp` - The time the message was sent, in milliseconds since the Unix epoch.
+    /// - `salt` - The salt used for the signature.
+    /// - `signature` - The signature of the message, `None` if the player has no chat session.
+    /// - `message_count` - The amount of messages the client has seen since its last message.
+    /// - `acknowledged` - Which of the last 20 messages the client has seen.
+    ServerboundChatMessagePacket, Play, 0x06 {
+        message: BoundedString<MAX_CHAT_LENGTH>,
+        timestamp: i64,
+        salt: i64,
+        signature: PrefixedOptional<MessageSignature>,
+        message_count: VarInt,
+        acknowledged: AcknowledgedMessages,
+    }
+}
+
+clientbound_packet! {
+    /// Shows a message a player sent in the chat.
+    ///
+    /// # Fields
+    /// - `sender` - The UUID of the player that sent the message.
+    /// - `index` - The amount of messages the player sent before this one.
+    /// - `signature` - The signature of the message, `None` for unsigned messages.
+    /// - `message` - The message as the player sent it.
+    /// - `timestamp` - The time the message was sent, in milliseconds since the Unix epoch.
+    /// - `salt` - The salt used for the signature.
+    /// - `previous_messages` - The messages that are part of the signature, at most 20.
+    /// - `unsigned_content` - The message shown instead of `message`, like a message changed by the server.
+    /// - `filter` - How the chat filter changed the message.
+    /// - `chat_type` - The ID of the chat type in the `minecraft:chat_type` registry plus one.
+    /// - `sender_name` - The name of the player, which is used by the chat type.
+    /// - `target_name` - The name of the receiver, which is used by chat types like `minecraft:msg_command_outgoing`.
+    PlayerChatMessagePacket, 0x39 {
+        sender: u128,
+        index: VarInt,
+        signature: PrefixedOptional<MessageSignature>,
+        message: String,
+        timestamp: i64,
+        salt: i64,
+        previous_messages: Vec<PreviousMessage>,
+        unsigned_content: PrefixedOptional<Component>,
+        filter: FilterType,
+        chat_type: VarInt,
+        sender_name: Component,
+        target_name: PrefixedOptional<Component>,
+    }
+}
+
+clientbound_packet! {
+    /// Shows a chat message without a signature, which doesn't need the sender in the player list of the client.
+    ///
+    /// # Fields
+    /// - `message` - The message.
+    /// - `chat_type` - The ID of the chat type in the `minecraft:chat_type` registry plus one.
+    /// - `sender_name` - The name of the sender, which is used by the chat type.
+    /// - `target_name` - The name of the receiver, which is used by chat types like `minecraft:msg_command_outgoing`.
+    DisguisedChatMessagePacket, 0x1E {
+        message: Component,
+        chat_type: VarInt,
+        sender_name: Component,
+        target_name: PrefixedOptional<Component>,
+    }
+}
+
+clientbound_packet! {
+    /// Shows a message sent by the server, which isn't sent by a player.
+    ///
+    /// # Fields
+    /// - `content` - The message.
+    /// - `overlay` - Whether the message is shown above the hotbar instead of in the chat.
+    SystemChatMessagePacket, 0x6C {
+        content: Component,
+        overlay: bool,
+    }
+}
+
+clientbound_packet! {
+    /// Sets the velocity of an entity, this is also used to knock back players.
+    ///
+    /// # Fields
+    /// - `entity_id` - The ID of the entity.
+    /// - `velocity_x` - The velocity on the X axis.
+    /// - `velocity_y` - The velocity on the Y axis.
+    /// - `velocity_z` - The velocity on the Z axis.
+    SetEntityVelocityPacket, 0x5A {
+        entity_id: VarInt,
+        velocity_x: Velocity,
+        velocity_y: Velocity,
+        velocity_z: Velocity,
+    }
+}
+
+clientbound_packet! {
+    /// Tells the client that an entity took damage, which plays the hurt animation and sound.
+    ///
+    /// # Fields
+    /// - `entity_id` - The ID of the entity that took damage.
+    /// - `source_type_id` - The ID of the damage type in the `minecraft:damage_type` registry.
+    /// - `source_cause_id` - The ID of the entity that caused the damage plus one, `0` if there is none.
+    /// - `source_direct_id` - The ID of the entity that dealt the damage plus one, like an arrow, `0` if there is none.
+    /// - `source_position` - The position the damage came from, this is only sent if there is no entity.
+    DamageEventPacket, 0x1A {
+        entity_id: VarInt,
+        source_type_id: VarInt,
+        source_cause_id: VarInt,
+        source_direct_id: VarInt,
+        source_position: PrefixedOptional<DamagePosition>,
+    }
+}
+
+clientbound_packet! {
+    /// Sets the health, food and saturation of the player.
+    ///
+    /// # Fields
+    /// - `health` - The health of the player, `0` or less means the player is dead.
+    /// - `food` - The food level, from `0` to `20`.
+    /// - `saturation` - The food saturation, from `0` to `5`.
+    SetHealthPacket, 0x5D {
+        health: f32,
+        food: VarInt,
+        saturation: f32,
+    }
+}
+
+clientbound_packet! {
+    /// Replaces the links that are shown in the pause menu and on the disconnect screen.
+    ///
+    /// # Fields
+    /// - `links` - The links, in the order they are shown.
+    PlayServerLinksPacket, 0x7B {
+        links: Vec<ServerLink>,
+    }
+}
+
+clientbound_packet! {
+    /// Asks the client for a cookie while it is playing, the client answers with a `[PlayCookieResponsePacket]`.
+    ///
+    /// # Fields
+    /// - `key` - The identifier of the cookie, like `example:session`.
+    PlayCookieRequestPacket, 0x16 {
+        key: String,
+    }
+}
+
+serverbound_packet! {
+    /// The answer to a `[PlayCookieRequestPacket]`.
+    ///
+    /// # Fields
+    /// - `key` - The identifier of the cookie.
+    /// - `payload` - The data of the cookie, up to 5 KiB. `None` if the client doesn't have the cookie.
+    PlayCookieResponsePacket, Play, 0x11 {
+        key: String,
+        payload: PrefixedOptional<Vec<u8>>,
+    }
+}
+
+clientbound_packet! {
+    /// Stores a cookie on the client while it is playing, replacing the cookie with the same key.
+    ///
+    /// Cookies are kept until the client disconnects, transfers included.
+    ///
+    /// # Fields
+    /// - `key` - The identifier of the cookie, like `example:session`.
+    /// - `payload` - The data of the cookie, up to 5 KiB.
+    PlayStoreCookiePacket, 0x6B {
+        key: String,
+        payload: Vec<u8>,
+    }
+}
+
+clientbound_packet! {
+    /// Adds a resource pack while the client is playing, the client answers with a `[PlayResourcePackResponsePacket]` for every status.
+    ///
+    /// # Fields
+    /// - `uuid` - The UUID of the pack, a pack with the same UUID is replaced.
+    /// - `url` - The URL the pack is downloaded from.
+    /// - `hash` - The SHA-1 hash of the pack as a hexadecimal string, used to cache the pack. Empty if the pack isn't cached.
+    /// - `forced` - Whether the player is disconnected if it declines the pack.
+    /// - `prompt` - The message shown when the player is asked to accept the pack.
+    PlayAddResourcePackPacket, 0x46 {
+        uuid: u128,
+        url: String,
+        hash: String,
+        forced: bool,
+        prompt: PrefixedOptional<Component>,
+    }
+}
+
+clientbound_packet! {
+    /// Removes a resource pack while the client is playing.
+    ///
+    /// # Fields
+    /// - `uuid` - The UUID of the pack, `None` removes every pack.
+    PlayRemoveResourcePackPacket, 0x45 {
+        uuid: PrefixedOptional<u128>,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client when the status of a resource pack changed, like when the player accepted it.
+    ///
+    /// # Fields
+    /// - `uuid` - The UUID of the pack.
+    /// - `result` - The ID of the `[ResourcePackStatus]`.
+    PlayResourcePackResponsePacket, Play, 0x2B {
+        uuid: u128,
+        result: VarInt,
+    }
+}
+
+clientbound_packet! {
+    /// Tells the client to connect to another server, the client disconnects and logs in with the `Transfer` intent.
+    ///
+    /// # Fields
+    /// - `host` - The address of the other server.
+    /// - `port` - The port of the other server.
+    PlayTransferPacket, 0x73 {
+        host: String,
+        port: VarInt,
+    }
+}
+
+clientbound_packet! {
+    /// Replaces the details the client adds to its crash reports and disconnect reports.
+    ///
+    /// # Fields
+    /// - `details` - The details, at most `[MAX_REPORT_DETAILS]`.
+    PlayCustomReportDetailsPacket, 0x7A {
+        details: Vec<ReportDetail>,
+    }
+}
+
+clientbound_packet! {
+    /// Removes players from the player list of the client, this also removes them from the tab list.
+    ///
+    /// # Fields
+    /// - `uuids` - The UUIDs of the players, as 128 bit numbers.
+    PlayerInfoRemovePacket, 0x3D {
+        uuids: Vec<u128>,
+    }
+}
+
+clientbound_packet! {
+    /// Adds players to the player list of the client or changes their entries, like their latency.
+    ///
+    /// The client only renders players and shows their chat messages once they are in its player list.
+    ///
+    /// # Fields
+    /// - `actions` - The bits of the actions every entry contains, like `[ADD_PLAYER]`.
+    /// - `players` - The entries, every entry contains the data of every action.
+    PlayerInfoUpdatePacket, 0x3E {
+        actions: u8,
+        players: Vec<PlayerInfo>,
+    }
+}
+
+impl PlayerInfoUpdatePacket {
+    /// Creates the packet for the given entries, which all have to contain the same actions.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use protocol_buf::ToNetwork;
+    /// use protocol_packets::{
+    ///     player_info::{PlayerInfo, ADD_PLAYER, UPDATE_LISTED},
+    ///     v1_21::play::PlayerInfoUpdatePacket,
+    /// };
+    ///
+    /// let packet = PlayerInfoUpdatePacket::new(vec![PlayerInfo::add(7, "Notch", Vec::new())]);
+    /// assert_eq!(packet.actions, ADD_PLAYER | UPDATE_LISTED);
+    ///
+    /// let mut expected = 7u128.to_network();
+    /// expected.extend_from_slice(&"Notch".to_string().to_network());
+    /// expected.extend_from_slice(&[0, 1]);
+    /// assert_eq!(packet.players[0].to_network(), expected);
+    /// ```
+    pub fn new(players: Vec<PlayerInfo>) -> Self {
+        Self {
+            actions: players.first().map_or(0, PlayerInfo::mask),
+            players,
+        }
+    }
+
+    /// Creates the packet that changes the latency shown for a player.
+    ///
+    /// # Parameters
+    /// - `uuid` - The UUID of the player.
+    /// - `latency` - The latency in milliseconds.
+    pub fn update_latency(uuid: u128, latency: i32) -> Self {
+        Self::new(vec![
+            PlayerInfo::new(uuid).with(PlayerAction::UpdateLatency(latency))
+        ])
+    }
+
+    /// Creates the packet that changes the name shown for a player in the tab list.
+    ///
+    /// # Parameters
+    /// - `uuid` - The UUID of the player.
+    /// - `name` - The name to show, `None` shows the username.
+    pub fn update_display_name(uuid: u128, name: Option<Component>) -> Self {
+        Self::new(vec![
+            PlayerInfo::new(uuid).with(PlayerAction::UpdateDisplayName(name))
+        ])
+    }
+
+    /// Creates the packet that changes whether a player is shown in the tab list.
+    ///
+    /// # Parameters
+    /// - `uuid` - The UUID of the player.
+    /// - `listed` - Whether the player is shown.
+    pub fn update_listed(uuid: u128, listed: bool) -> Self {
+        Self::new(vec![
+            PlayerInfo::new(uuid).with(PlayerAction::UpdateListed(listed))
+        ])
+    }
+}
+
+serverbound_packets! {
+    /// Every packet the client can send in the `Play` state, ordered by their IDs.
+    ServerboundPlayPacket, Play {
+        ConfirmTeleportation(ConfirmTeleportationPacket),
+        ChatCommand(ChatCommandPacket),
+        ServerboundChatMessage(ServerboundChatMessagePacket),
+        ClientInformation(ClientInformationPacket),
+        AcknowledgeConfiguration(AcknowledgeConfigurationPacket),
+        PlayCookieResponse(PlayCookieResponsePacket),
+        ServerboundPluginMessage(ServerboundPluginMessagePacket),
+        Interact(InteractPacket),
+        ServerboundKeepAlive(ServerboundKeepAlivePacket),
+        SetPlayerPosition(SetPlayerPositionPacket),
+        SetPlayerPositionAndRotation(SetPlayerPositionAndRotationPacket),
+        SetPlayerRotation(SetPlayerRotationPacket),
+        SetPlayerOnGround(SetPlayerOnGroundPacket),
+        ServerboundMoveVehicle(ServerboundMoveVehiclePacket),
+        PaddleBoat(PaddleBoatPacket),
+        PlayerCommand(PlayerCommandPacket),
+        PlayerInput(PlayerInputPacket),
+        PlayResourcePackResponse(PlayResourcePackResponsePacket),
+        SetHeldItem(SetHeldItemPacket),
+        UseItem(UseItemPacket),
+    }
+}
+
+clientbound_packets! {
+    /// Every packet the server can send in the `Play` state, ordered by their IDs.
+    ClientboundPlayPacket, Play {
+        SpawnEntity(SpawnEntityPacket),
+        AcknowledgeBlockChange(AcknowledgeBlockChangePacket),
+        BlockUpdate(BlockUpdatePacket),
+        SetContainerSlot(SetContainerSlotPacket),
+        PlayCookieRequest(PlayCookieRequestPacket),
+        SetCooldown(SetCooldownPacket),
+        ClientboundPluginMessage(ClientboundPluginMessagePacket),
+        DamageEvent(DamageEventPacket),
+        PlayDisconnect(PlayDisconnectPacket),
+        DisguisedChatMessage(DisguisedChatMessagePacket),
+        EntityEvent(EntityEventPacket),
+        UnloadChunk(UnloadChunkPacket),
+        GameEvent(GameEventPacket),
+        ClientboundKeepAlive(ClientboundKeepAlivePacket),
+        ChunkDataAndUpdateLight(ChunkDataAndUpdateLightPacket),
+        Particle(ParticlePacket),
+        LoginPlay(LoginPlayPacket),
+        ClientboundMoveVehicle(ClientboundMoveVehiclePacket),
+        PlayerChatMessage(PlayerChatMessagePacket),
+        PlayerInfoRemove(PlayerInfoRemovePacket),
+        PlayerInfoUpdate(PlayerInfoUpdatePacket),
+        SynchronizePlayerPosition(SynchronizePlayerPositionPacket),
+        RemoveEntities(RemoveEntitiesPacket),
+        PlayRemoveResourcePack(PlayRemoveResourcePackPacket),
+        PlayAddResourcePack(PlayAddResourcePackPacket),
+        Respawn(RespawnPacket),
+        UpdateSectionBlocks(UpdateSectionBlocksPacket),
+        SetCenterChunk(SetCenterChunkPacket),
+        SetRenderDistance(SetRenderDistancePacket),
+        SetEntityMetadata(SetEntityMetadataPacket),
+        SetEntityVelocity(SetEntityVelocityPacket),
+        SetEquipment(SetEquipmentPacket),
+        SetHealth(SetHealthPacket),
+        UpdateObjectives(UpdateObjectivesPacket),
+        SetPassengers(SetPassengersPacket),
+        UpdateScore(UpdateScorePacket),
+        UpdateTime(UpdateTimePacket),
+        SoundEffect(SoundEffectPacket),
+        PlayStoreCookie(PlayStoreCookiePacket),
+        SystemChatMessage(SystemChatMessagePacket),
+        TeleportEntity(TeleportEntityPacket),
+        PlayTransfer(PlayTransferPacket),
+        PlayCustomReportDetails(PlayCustomReportDetailsPacket),
+        PlayServerLinks(PlayServerLinksPacket),
+    }
+}
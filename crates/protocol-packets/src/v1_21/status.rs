@@ -0,0 +1,61 @@
+use protocol_buf::{
+    buffer::{Buffer, NormalBuffer, PacketBuffer},
+    error::ProtocolResult,
+    types::VarInt,
+};
+
+use crate::{
+    clientbound_packet, clientbound_packets, serverbound_packet, serverbound_packets,
+    ClientboundPacket, ConnectionState, Packet, ServerboundPacket,
+};
+
+serverbound_packet! {
+    /// Requests the server list status. This packet has no fields.
+    StatusRequestPacket, Status, 0x00 {}
+}
+
+clientbound_packet! {
+    /// The server list status as a JSON string.
+    ///
+    /// # Fields
+    /// - `json_response` - The status JSON containing the version, players and description.
+    StatusResponsePacket, 0x00 {
+        json_response: String,
+    }
+}
+
+serverbound_packet! {
+    /// Sent by the client to measure the latency to the server.
+    ///
+    /// # Fields
+    /// - `payload` - A number that has to be sent back in the `[PongResponsePacket]`.
+    PingRequestPacket, Status, 0x01 {
+        payload: i64,
+    }
+}
+
+clientbound_packet! {
+    /// The response to a `[PingRequestPacket]`. The connection is closed after this packet.
+    ///
+    /// # Fields
+    /// - `payload` - The same payload that was sent by the client.
+    PongResponsePacket, 0x01 {
+        payload: i64,
+    }
+}
+
+serverbound_packets! {
+    /// Every packet the client can send in the `Status` state, ordered by their IDs.
+    ServerboundStatusPacket, Status {
+        StatusRequest(StatusRequestPacket),
+        PingRequest(PingRequestPacket),
+    }
+}
+
+clientbound_packets! {
+    /// Every packet the server can send in the `Status` state, ordered by their IDs.
+    ClientboundStatusPacket, Status {
+        StatusResponse(StatusResponsePacket),
+        PongResponse(PongResponsePacket),
+    }
+}
@@ -0,0 +1,145 @@
+//! Golden tests for the chunk packets.
+//!
+//! The chunks of a small deterministic test world are written and compared byte by byte with the packets checked
+//! into `tests/golden`, so a change to the palettes, the heightmaps or the light data shows up as a failing test
+//! instead of a client that renders the chunk wrong. Run the tests with `UPDATE_GOLDEN=1` to write the files again
+//! after an intended change of the wire format.
+
+use std::{env, fs, path::PathBuf};
+
+use protocol_buf::{
+    buffer::{Buffer, NormalBuffer},
+    types::VarInt,
+    ToNetwork,
+};
+use protocol_packets::{
+    chunk::{Chunk, LightData, LIGHT_ARRAY_SIZE, SECTION_WIDTH},
+    v1_21::play::ChunkDataAndUpdateLightPacket,
+    ClientboundPacket,
+};
+
+/// The amount of chunk sections of the overworld.
+const SECTION_COUNT: usize = 24;
+
+/// The block states the test world is built from.
+const BEDROCK: i32 = 79;
+const STONE: i32 = 1;
+const DIRT: i32 = 10;
+const GRASS_BLOCK: i32 = 9;
+const COBBLESTONE: i32 = 14;
+
+/// The biome of every chunk of the test world.
+const PLAINS: i32 = 0;
+
+/// The layers of the superflat chunk: bedrock, two layers of dirt and grass.
+const FLAT_LAYERS: [i32; 4] = [BEDROCK, DIRT, DIRT, GRASS_BLOCK];
+
+/// Creates the superflat chunk, without any light.
+fn flat_chunk() -> ChunkDataAndUpdateLightPacket {
+    let chunk = Chunk::flat(0, 0, SECTION_COUNT, PLAINS, &FLAT_LAYERS);
+    ChunkDataAndUpdateLightPacket::new(&chunk, LightData::uniform(SECTION_COUNT, 0, 0))
+}
+
+/// Creates a chunk with terrain of different heights and light that isn't uniform.
+///
+/// The stone columns rise towards the corner of the chunk, so every column has another height and the sections
+/// above the ground need palettes with more than one entry. A cobblestone pillar stands in the middle.
+fn terrain_chunk() -> ChunkDataAndUpdateLightPacket {
+    let mut chunk = Chunk::flat(-1, 2, SECTION_COUNT, PLAINS, &[BEDROCK]);
+    for z in 0..SECTION_WIDTH {
+        for x in 0..SECTION_WIDTH {
+            let height = 4 + x + z;
+            for y in 1..height {
+                chunk.set_block(x, y, z, STONE);
+            }
+            chunk.set_block(x, height, z, GRASS_BLOCK);
+        }
+    }
+    for y in 20..40 {
+        chunk.set_block(8, y, 8, COBBLESTONE);
+    }
+
+    let mut light = LightData::default();
+    // The light sections below the terrain are dark, the sections right above it have full sky light.
+    // The sky light of the other sections isn't sent, so it stays the same on the client.
+    for section in 0..4 {
+        light.empty_sky_light_mask.set(section);
+    }
+    for section in 4..8 {
+        light.sky_light_mask.set(section);
+        light.sky_light.push(vec![0xFF; LIGHT_ARRAY_SIZE]);
+    }
+    // Two light sections have block light that fades out along the X axis.
+    for section in [2, 3] {
+        light.block_light_mask.set(section);
+        let array = (0..LIGHT_ARRAY_SIZE)
+            .map(|index| {
+                let level = 15u8.saturating_sub((index % 8) as u8 * 2);
+                level | (level.saturating_sub(1) << 4)
+            })
+            .collect();
+        light.block_light.push(array);
+    }
+
+    ChunkDataAndUpdateLightPacket::new(&chunk, light)
+}
+
+/// Writes a packet the way it is framed before compression: the packet ID followed by its fields.
+fn packet_bytes(packet: &impl ClientboundPacket) -> Vec<u8> {
+    let buffer = packet.write_packet(NormalBuffer::new(Vec::new()));
+    let mut bytes = VarInt::from(packet.id()).to_network();
+    bytes.extend_from_slice(buffer.get_ref());
+    bytes
+}
+
+/// Compares the bytes with the golden file, or replaces the file if `UPDATE_GOLDEN` is set.
+fn assert_golden(name: &str, bytes: &[u8]) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(name);
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, bytes).unwrap();
+        return;
+    }
+
+    let golden = fs::read(&path).unwrap_or_else(|e| panic!("Can't read {}: {}", path.display(), e));
+    if let Some(offset) = golden.iter().zip(bytes).position(|(a, b)| a != b) {
+        panic!(
+            "{} differs at byte {}: expected {:#04x}, got {:#04x}",
+            name, offset, golden[offset], bytes[offset]
+        );
+    }
+    assert_eq!(
+        golden.len(),
+        bytes.len(),
+        "{} has another length than its golden file",
+        name
+    );
+}
+
+#[test]
+fn flat_chunk_matches_golden_bytes() {
+    let packet = flat_chunk();
+    let bytes = packet_bytes(&packet);
+
+    assert_eq!(bytes[0], 0x27);
+    assert_golden("flat_chunk.bin", &bytes);
+}
+
+#[test]
+fn terrain_chunk_matches_golden_bytes() {
+    let packet = terrain_chunk();
+    let bytes = packet_bytes(&packet);
+
+    assert_eq!(&bytes[1..9], &[0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 2]);
+    assert_golden("terrain_chunk.bin", &bytes);
+}
+
+#[test]
+fn test_world_is_deterministic() {
+    assert_eq!(packet_bytes(&flat_chunk()), packet_bytes(&flat_chunk()));
+    assert_eq!(
+        packet_bytes(&terrain_chunk()),
+        packet_bytes(&terrain_chunk())
+    );
+}
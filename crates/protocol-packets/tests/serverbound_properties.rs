@@ -0,0 +1,84 @@
+//! Property tests for the serverbound packets.
+//!
+//! The packets are read from bytes the clients send, so reading arbitrary bytes as any serverbound packet has to
+//! return an error instead of panicking.
+
+use proptest::{collection::vec, prelude::*};
+use protocol_buf::buffer::NormalBuffer;
+use protocol_packets::{
+    v1_21::{configuration::*, handshake::*, login::*, play::*, status::*},
+    Packet, ServerboundPacket,
+};
+
+/// Reads the bytes as every given serverbound packet, the results are ignored since only panics fail the test.
+macro_rules! read_all {
+    ($bytes:expr, $($packet:ty),* $(,)?) => {
+        $(
+            let _ = <$packet>::read_packet(NormalBuffer::new($bytes.clone()));
+        )*
+    };
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_payloads_dont_panic(bytes in vec(any::<u8>(), 0..256)) {
+        read_all!(
+            bytes,
+            HandshakePacket,
+            StatusRequestPacket,
+            PingRequestPacket,
+            LoginStartPacket,
+            EncryptionResponsePacket,
+            LoginPluginResponsePacket,
+            LoginAcknowledgedPacket,
+            LoginCookieResponsePacket,
+            ConfigurationClientInformationPacket,
+            ConfigurationCookieResponsePacket,
+            ServerboundConfigurationPluginMessagePacket,
+            AcknowledgeFinishConfigurationPacket,
+            ServerboundConfigurationKeepAlivePacket,
+            ConfigurationResourcePackResponsePacket,
+            ServerboundKnownPacksPacket,
+            ConfirmTeleportationPacket,
+            ChatCommandPacket,
+            ServerboundChatMessagePacket,
+            ClientInformationPacket,
+            AcknowledgeConfigurationPacket,
+            PlayCookieResponsePacket,
+            ServerboundPluginMessagePacket,
+            InteractPacket,
+            ServerboundKeepAlivePacket,
+            SetPlayerPositionPacket,
+            SetPlayerPositionAndRotationPacket,
+            SetPlayerRotationPacket,
+            SetPlayerOnGroundPacket,
+            ServerboundMoveVehiclePacket,
+            PaddleBoatPacket,
+            PlayerCommandPacket,
+            PlayerInputPacket,
+            PlayResourcePackResponsePacket,
+            SetHeldItemPacket,
+            UseItemPacket,
+        );
+    }
+
+    #[test]
+    fn decoded_packets_keep_their_id(packet_id in 0..0x40, bytes in vec(any::<u8>(), 0..256)) {
+        if let Ok(packet) = ServerboundHandshakePacket::decode(packet_id, NormalBuffer::new(bytes.clone())) {
+            prop_assert_eq!(packet.id(), packet_id);
+        }
+        if let Ok(packet) = ServerboundStatusPacket::decode(packet_id, NormalBuffer::new(bytes.clone())) {
+            prop_assert_eq!(packet.id(), packet_id);
+        }
+        if let Ok(packet) = ServerboundLoginPacket::decode(packet_id, NormalBuffer::new(bytes.clone())) {
+            prop_assert_eq!(packet.id(), packet_id);
+        }
+        if let Ok(packet) = ServerboundConfigurationPacket::decode(packet_id, NormalBuffer::new(bytes.clone())) {
+            prop_assert_eq!(packet.id(), packet_id);
+        }
+        if let Ok(packet) = ServerboundPlayPacket::decode(packet_id, NormalBuffer::new(bytes)) {
+            prop_assert_eq!(*packet.encode().packet_id, packet_id);
+            prop_assert_eq!(packet.id(), packet_id);
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! A bot that joins a server in offline mode and repeats every chat message of the other players.
+//!
+//! ```text
+//! cargo run --example chat_bot -- 127.0.0.1:25565 EchoBot
+//! ```
+
+use std::{
+    env, io, process,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use protocol_buf::{
+    buffer::{Buffer, NormalBuffer, PacketBuffer},
+    error::ProtocolResult,
+    nbt::NbtTag,
+    text::Component,
+    types::{RemainingBytes, VarInt},
+};
+use protocol_core::{auth::OfflineAuthenticator, connector::MinecraftConnector};
+use protocol_packets::{
+    registry::KnownPack,
+    serverbound_packet,
+    v1_21::{
+        configuration::{
+            AcknowledgeFinishConfigurationPacket, ServerboundConfigurationKeepAlivePacket,
+            ServerboundKnownPacksPacket,
+        },
+        login::LoginAcknowledgedPacket,
+        play::{ConfirmTeleportationPacket, ServerboundKeepAlivePacket},
+    },
+    ConnectionState, Packet, ServerboundPacket,
+};
+
+serverbound_packet! {
+    /// Sends an unsigned chat message, servers in offline mode accept these.
+    ///
+    /// # Fields
+    /// - `message` - The message, at most 256 characters.
+    /// - `timestamp` - The time the message was sent, in milliseconds since the epoch.
+    /// - `salt` - The salt of the signature.
+    /// - `has_signature` - Whether the message is signed, this bot never signs its messages.
+    /// - `message_count` - The amount of messages that were acknowledged.
+    /// - `acknowledged` - The fixed bit set of the last 20 acknowledged messages.
+    ChatMessagePacket, Play, 0x06 {
+        message: String,
+        timestamp: i64,
+        salt: i64,
+        has_signature: bool,
+        message_count: VarInt,
+        acknowledged: RemainingBytes,
+    }
+}
+
+impl ChatMessagePacket {
+    /// Creates an unsigned chat message that doesn't acknowledge any messages.
+    fn new(message: impl Into<String>) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_millis() as i64);
+
+        Self {
+            message: message.into(),
+            timestamp,
+            salt: 0,
+            has_signature: false,
+            message_count: VarInt::from(0),
+            acknowledged: RemainingBytes::from(vec![0; 3]),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let mut args = env::args().skip(1);
+    let target = args.next().unwrap_or_else(|| "127.0.0.1:25565".to_string());
+    let username = args.next().unwrap_or_else(|| "EchoBot".to_string());
+    let (address, port) = target.rsplit_once(':').unwrap_or((&target, "25565"));
+    let Ok(port) = port.parse() else {
+        eprintln!("Invalid port: {}", port);
+        process::exit(1);
+    };
+
+    if let Err(error) = run(address, port, &username).await {
+        eprintln!("Disconnected: {}", error);
+        process::exit(1);
+    }
+}
+
+/// Joins the server and handles its packets until the connection is closed.
+async fn run(address: &str, port: u16, username: &str) -> io::Result<()> {
+    let mut connector = MinecraftConnector::connect(address, port).await?;
+    connector.handshake(ConnectionState::Login).await?;
+
+    let uuid = OfflineAuthenticator::offline_uuid(username);
+    connector.login_start(username, uuid).await?;
+
+    while let Some(mut packet) = connector.read_packet().await? {
+        match (connector.state, *packet.packet_id) {
+            (ConnectionState::Login, 0x00) => return Err(disconnected(&mut packet, true)),
+            (ConnectionState::Configuration, 0x02) | (ConnectionState::Play, 0x1D) => {
+                return Err(disconnected(&mut packet, false));
+            }
+            (ConnectionState::Login, 0x01) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "The server is in online mode",
+                ));
+            }
+            (ConnectionState::Login, 0x02) => {
+                connector.send_packet(&LoginAcknowledgedPacket {}).await?;
+                connector.state = ConnectionState::Configuration;
+            }
+            (ConnectionState::Configuration, 0x03) => {
+                connector
+                    .send_packet(&AcknowledgeFinishConfigurationPacket {})
+                    .await?;
+                connector.state = ConnectionState::Play;
+                println!("Joined {}:{} as {}", address, port, username);
+            }
+            (ConnectionState::Configuration, 0x04) => {
+                let keep_alive_id = packet.read().map_err(invalid_data)?;
+                connector
+                    .send_packet(&ServerboundConfigurationKeepAlivePacket { keep_alive_id })
+                    .await?;
+            }
+            (ConnectionState::Configuration, 0x0E) => {
+                let packs = vec![KnownPack::core()];
+                connector
+                    .send_packet(&ServerboundKnownPacksPacket { packs })
+                    .await?;
+            }
+            (ConnectionState::Play, 0x26) => {
+                let keep_alive_id = packet.read().map_err(invalid_data)?;
+                connector
+                    .send_packet(&ServerboundKeepAlivePacket { keep_alive_id })
+                    .await?;
+            }
+            (ConnectionState::Play, 0x39) => {
+                let (sender, message) = read_player_chat(&mut packet).map_err(invalid_data)?;
+                println!("<{}> {}", sender, message);
+
+                if sender != uuid.as_u128() {
+                    connector
+                        .send_packet(&ChatMessagePacket::new(message))
+                        .await?;
+                }
+            }
+            (ConnectionState::Play, 0x40) => {
+                let teleport_id = read_teleport_id(&mut packet).map_err(invalid_data)?;
+                connector
+                    .send_packet(&ConfirmTeleportationPacket { teleport_id })
+                    .await?;
+            }
+            (ConnectionState::Play, 0x6C) => {
+                let content: NbtTag = packet.read().map_err(invalid_data)?;
+                println!("{}", component_text(&content));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads the sender and the message of a player chat packet.
+fn read_player_chat(packet: &mut PacketBuffer) -> ProtocolResult<(u128, String)> {
+    let sender: u128 = packet.read()?;
+    packet.read_varint()?;
+    if packet.read_bool()? {
+        packet.read_bytes(256)?;
+    }
+
+    Ok((sender, packet.read_string()?))
+}
+
+/// Reads the ID of a teleport, which has to be confirmed before the server accepts movement.
+fn read_teleport_id(packet: &mut PacketBuffer) -> ProtocolResult<VarInt> {
+    for _ in 0..3 {
+        packet.read_double()?;
+    }
+    for _ in 0..2 {
+        packet.read_float()?;
+    }
+    packet.read_byte()?;
+
+    packet.read_varint()
+}
+
+/// Returns the text of a text component sent as NBT.
+fn component_text(tag: &NbtTag) -> String {
+    match tag {
+        NbtTag::String(text) => text.clone(),
+        NbtTag::Compound(compound) => match compound.get("text") {
+            Some(NbtTag::String(text)) => text.clone(),
+            _ => format!("{:?}", compound),
+        },
+        tag => format!("{:?}", tag),
+    }
+}
+
+/// Creates the error for a disconnect packet, the reason is JSON while logging in and NBT afterwards.
+fn disconnected(packet: &mut PacketBuffer, json: bool) -> io::Error {
+    let reason = if json {
+        packet
+            .read_string()
+            .ok()
+            .and_then(|json| Component::from_json(&json).ok())
+            .map(|component| component.to_plain_text())
+    } else {
+        packet.read::<NbtTag>().ok().map(|tag| component_text(&tag))
+    };
+
+    io::Error::new(
+        io::ErrorKind::ConnectionAborted,
+        reason.unwrap_or_else(|| "Disconnected by the server".to_string()),
+    )
+}
+
+/// Converts a protocol error into an IO error.
+fn invalid_data(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
@@ -0,0 +1,72 @@
+//! A server that lets players join a small superflat world.
+//!
+//! Every player receives the same chunks around the spawn once the configuration is finished.
+//!
+//! ```text
+//! cargo run --example hello_world_server
+//! ```
+
+use protocol_core::{config::SpawnPosition, server::MinecraftServer, status::ServerStatus};
+use protocol_packets::{
+    chunk::{Chunk, LightData},
+    v1_21::{
+        configuration::AcknowledgeFinishConfigurationPacket,
+        play::{ChunkDataAndUpdateLightPacket, GameEventPacket},
+    },
+};
+
+/// The amount of chunk sections of the overworld, which goes from `-64` to `320`.
+const SECTION_COUNT: usize = 24;
+
+/// The lowest Y position of the overworld.
+const MIN_Y: i32 = -64;
+
+/// The block states of the layers, from the bottom up: bedrock, two layers of dirt and grass.
+const LAYERS: [i32; 4] = [79, 10, 10, 9];
+
+/// The radius of the square of chunks around the spawn that is sent to players.
+const RADIUS: i32 = 3;
+
+/// The game event that tells the client the chunks around it are being sent.
+const START_WAITING_FOR_CHUNKS: u8 = 13;
+
+#[tokio::main]
+async fn main() {
+    let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    server.set_status(
+        ServerStatus::builder()
+            .motd("Hello, world!")
+            .max_players(20)
+            .build(),
+    );
+
+    let spawn_chunk = Chunk::flat(0, 0, SECTION_COUNT, 0, &LAYERS);
+    server.set_spawn(SpawnPosition::on_surface(&spawn_chunk, 8, 8, MIN_Y));
+
+    let chunks: Vec<_> = (-RADIUS..=RADIUS)
+        .flat_map(|x| (-RADIUS..=RADIUS).map(move |z| (x, z)))
+        .map(|(x, z)| {
+            let chunk = Chunk::flat(x, z, SECTION_COUNT, 0, &LAYERS);
+            ChunkDataAndUpdateLightPacket::new(&chunk, LightData::uniform(SECTION_COUNT, 15, 0))
+        })
+        .collect();
+
+    // The client is in the `Play` state once the built-in handling of the acknowledgement is done.
+    server.after::<AcknowledgeFinishConfigurationPacket>(move |client, _packet| {
+        let handle = client.handle();
+        handle.send_packet(&GameEventPacket {
+            event: START_WAITING_FOR_CHUNKS,
+            value: 0.0,
+        });
+        for chunk in &chunks {
+            handle.send_packet(chunk);
+        }
+    });
+
+    tokio::spawn(async move {
+        server.accept_connections().await;
+    });
+
+    println!("Server started on 127.0.0.1:25565! Press Ctrl-C to stop.");
+    tokio::signal::ctrl_c().await.unwrap();
+}
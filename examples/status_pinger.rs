@@ -0,0 +1,46 @@
+//! Requests the status of a server, the way the server list of the client does.
+//!
+//! ```text
+//! cargo run --example status_pinger -- 127.0.0.1:25565
+//! ```
+
+use std::{env, process};
+
+use protocol_core::connector;
+
+#[tokio::main]
+async fn main() {
+    let target = env::args()
+        .nth(1)
+        .unwrap_or_else(|| "127.0.0.1:25565".to_string());
+    let (address, port) = match target.rsplit_once(':') {
+        Some((address, port)) => match port.parse() {
+            Ok(port) => (address.to_string(), port),
+            Err(_) => {
+                eprintln!("Invalid port: {}", port);
+                process::exit(1);
+            }
+        },
+        None => (target, 25565),
+    };
+
+    let status = match connector::ping(&address, port).await {
+        Ok(status) => status,
+        Err(error) => {
+            eprintln!("Failed to ping {}:{}: {}", address, port, error);
+            process::exit(1);
+        }
+    };
+
+    println!("{}:{}", address, port);
+    println!("  Version: {} ({})", status.version_name, status.protocol);
+    println!(
+        "  Players: {}/{}",
+        status.online_players, status.max_players
+    );
+    for player in &status.sample {
+        println!("    - {}", player.name);
+    }
+    println!("  MOTD:    {}", status.description.to_plain_text());
+    println!("  Latency: {:?}", status.latency);
+}
@@ -1,12 +1,14 @@
-use protocol_core::server::MinecraftServer;
+use protocol_core::{server::MinecraftServer, shutdown::SHUTDOWN_TIMEOUT};
 
 #[tokio::main]
 async fn main() {
     let mut server = MinecraftServer::new("127.0.0.1", 25565).await;
+    let shutdown = server.shutdown_handle();
     tokio::spawn(async move {
         server.accept_connections().await;
     });
 
     println!("Server started! Press Ctrl-C to stop.");
     tokio::signal::ctrl_c().await.unwrap();
+    shutdown.shutdown("Server closed", SHUTDOWN_TIMEOUT).await;
 }